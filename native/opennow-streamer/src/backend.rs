@@ -1,14 +1,17 @@
+use crate::flags::{flags, Flag};
 use crate::input::{PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL, PARTIALLY_RELIABLE_HID_DEVICE_MASK_ALL};
 use crate::protocol::{
     missing_field, ColorQuality, CommandEnvelope, Event, MediaConnectionInfo,
-    NativeStreamerCapabilities, NativeStreamerSessionContext, Response, VideoCodec,
-    PROTOCOL_VERSION,
+    NativeStreamerCapabilities, NativeStreamerSessionContext, NativeVideoLayer, Response,
+    VideoCodec, PROTOCOL_VERSION,
 };
 use crate::sdp::{
-    duplicate_session_webrtc_attributes_to_media, extract_ice_credentials, fix_server_ip,
-    parse_resolution, prefer_codec, sanitize_ice_pwd_for_gstreamer,
-    summarize_media_transport_attributes, NvstParams, PreferCodecOptions,
+    duplicate_session_webrtc_attributes_to_media, extract_ice_credentials, extract_video_layers,
+    fix_server_ip, offers_video_tmmbr, parse_resolution, prefer_codec,
+    sanitize_ice_pwd_for_gstreamer, summarize_media_transport_attributes, DecoderProfiles,
+    NvstParams, PreferCodecOptions,
 };
+use crate::sdp_model::SessionDescription;
 use std::env;
 use std::sync::mpsc::Sender;
 
@@ -21,6 +24,9 @@ pub trait NativeStreamerBackend {
     fn update_render_surface(&mut self, command: CommandEnvelope) -> BackendReply;
     fn update_bitrate_limit(&mut self, command: CommandEnvelope) -> BackendReply;
     fn update_shortcuts(&mut self, command: CommandEnvelope) -> BackendReply;
+    fn update_volume(&mut self, command: CommandEnvelope) -> BackendReply;
+    fn select_video_layer(&mut self, command: CommandEnvelope) -> BackendReply;
+    fn update_render_fps_cap(&mut self, command: CommandEnvelope) -> BackendReply;
     fn stop(&mut self, command: CommandEnvelope) -> BackendReply;
 }
 
@@ -113,16 +119,22 @@ impl BackendReply {
 pub struct PreparedNativeOffer {
     pub original_sdp_len: usize,
     pub fixed_offer_sdp: String,
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
     pub gstreamer_offer_sdp: String,
     pub gstreamer_ice_pwd_replacements: usize,
     pub gstreamer_framerate_adjusted: bool,
     pub nvst_params: NvstParams,
     pub media_connection_info: Option<MediaConnectionInfo>,
+    pub video_layers: Vec<NativeVideoLayer>,
+    pub video_tmmbr: bool,
+    pub requested_codec: VideoCodec,
+    pub rejected_video_payloads: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PrepareNativeOfferError {
     InvalidResolution { resolution: String },
+    InvalidSdp { reason: String },
 }
 
 impl PrepareNativeOfferError {
@@ -133,6 +145,11 @@ impl PrepareNativeOfferError {
                 code: "invalid-resolution".to_owned(),
                 message: format!("Invalid stream resolution: {resolution}"),
             },
+            Self::InvalidSdp { reason } => Response::Error {
+                id: Some(id),
+                code: "invalid-remote-sdp".to_owned(),
+                message: format!("Invalid remote offer SDP: {reason}"),
+            },
         }
     }
 }
@@ -140,6 +157,7 @@ impl PrepareNativeOfferError {
 pub fn prepare_native_offer(
     context: &NativeStreamerSessionContext,
     offer_sdp: &str,
+    decoder_profiles: &DecoderProfiles,
 ) -> Result<PreparedNativeOffer, PrepareNativeOfferError> {
     let Some((width, height)) = parse_resolution(&context.settings.resolution) else {
         return Err(PrepareNativeOfferError::InvalidResolution {
@@ -147,23 +165,28 @@ pub fn prepare_native_offer(
         });
     };
 
-    let fixed_offer_sdp = duplicate_session_webrtc_attributes_to_media(&fix_server_ip(
-        offer_sdp,
-        &context.session.server_ip,
-    ));
-    let codec = resolve_native_codec(context.settings.codec);
-    let fixed_offer_sdp = prefer_codec(
-        &fixed_offer_sdp,
-        codec,
-        PreferCodecOptions {
-            prefer_hevc_profile_id: Some(preferred_hevc_profile_id(context.settings.color_quality)),
-        },
+    let mut offer = SessionDescription::parse(offer_sdp).map_err(|error| {
+        PrepareNativeOfferError::InvalidSdp {
+            reason: error.to_string(),
+        }
+    })?;
+    fix_server_ip(&mut offer, &context.session.server_ip);
+    duplicate_session_webrtc_attributes_to_media(&mut offer);
+    let requested_codec = resolve_native_codec(context.settings.codec);
+    let (codec, rejected_video_payloads) = prefer_decodable_codec(
+        &mut offer,
+        requested_codec,
+        preferred_hevc_profile_id(context.settings.color_quality),
+        decoder_profiles,
     );
-    let (gstreamer_framerate_offer_sdp, gstreamer_framerate_adjusted) =
-        align_video_sdp_framerate_for_gstreamer(&fixed_offer_sdp, context.settings.fps);
-    let (gstreamer_offer_sdp, gstreamer_ice_pwd_replacements) =
-        sanitize_ice_pwd_for_gstreamer(&gstreamer_framerate_offer_sdp);
+    let fixed_offer_sdp = offer.to_string();
     let credentials = extract_ice_credentials(&fixed_offer_sdp);
+    let video_layers = extract_video_layers(&offer);
+    let video_tmmbr = offers_video_tmmbr(&offer) && flags().get(Flag::LayerPause).unwrap_or(false);
+    let gstreamer_framerate_adjusted =
+        align_video_sdp_framerate_for_gstreamer(&mut offer, context.settings.fps);
+    let gstreamer_ice_pwd_replacements = sanitize_ice_pwd_for_gstreamer(&mut offer);
+    let gstreamer_offer_sdp = offer.to_string();
     let nvst_params = NvstParams {
         width,
         height,
@@ -186,63 +209,87 @@ pub fn prepare_native_offer(
         gstreamer_framerate_adjusted,
         nvst_params,
         media_connection_info: context.session.media_connection_info.clone(),
+        video_layers,
+        video_tmmbr,
+        requested_codec,
+        rejected_video_payloads,
     })
 }
 
-fn align_video_sdp_framerate_for_gstreamer(sdp: &str, fps: u32) -> (String, bool) {
-    if fps == 0 {
-        return (sdp.to_owned(), false);
-    }
-
-    let line_ending = if sdp.contains("\r\n") { "\r\n" } else { "\n" };
-    let has_trailing_ending = sdp.ends_with(line_ending);
-    let mut lines: Vec<String> = sdp
-        .split(line_ending)
-        .filter(|line| !line.is_empty() || !has_trailing_ending)
-        .map(ToOwned::to_owned)
-        .collect();
-    let mut output = Vec::with_capacity(lines.len() + 1);
-    let mut in_video = false;
-    let mut video_has_framerate = false;
-    let mut changed = false;
-    let target = format!("a=framerate:{fps}");
-
-    for line in lines.drain(..) {
-        if line.starts_with("m=") {
-            if in_video && !video_has_framerate {
-                output.push(target.clone());
-                changed = true;
-            }
-            in_video = line.starts_with("m=video");
-            video_has_framerate = false;
-            output.push(line);
-            continue;
+/// Filters the offer down to `requested` payloads the local decoder can
+/// handle. When the decoder rejects every offered profile of that codec, the
+/// next lower codec is negotiated instead so the session starts on a stream
+/// the decoder accepts rather than failing once media arrives.
+fn prefer_decodable_codec(
+    offer: &mut SessionDescription,
+    requested: VideoCodec,
+    prefer_hevc_profile_id: u8,
+    decoder_profiles: &DecoderProfiles,
+) -> (VideoCodec, Vec<String>) {
+    let mut rejected = Vec::new();
+    for &codec in std::iter::once(&requested).chain(lower_codec_fallbacks(requested)) {
+        let mut candidate = offer.clone();
+        let outcome = prefer_codec(
+            &mut candidate,
+            codec,
+            PreferCodecOptions {
+                prefer_hevc_profile_id: Some(prefer_hevc_profile_id),
+                decoder_support: Some(decoder_profiles.for_codec(codec)),
+            },
+        );
+        rejected.extend(
+            outcome
+                .rejected
+                .into_iter()
+                .map(|payload| format!("{} {payload}", codec.as_str())),
+        );
+        if outcome.applied {
+            *offer = candidate;
+            return (codec, rejected);
         }
-
-        if in_video && line.starts_with("a=framerate:") {
-            video_has_framerate = true;
-            if line != target {
-                output.push(target.clone());
-                changed = true;
-            } else {
-                output.push(line);
-            }
-            continue;
+        if rejected.is_empty() {
+            // The requested codec was not offered at all; keep the previous
+            // behaviour of leaving the offer alone.
+            break;
         }
+    }
+    (requested, rejected)
+}
 
-        output.push(line);
+fn lower_codec_fallbacks(codec: VideoCodec) -> &'static [VideoCodec] {
+    match codec {
+        VideoCodec::AV1 => &[VideoCodec::H265, VideoCodec::H264],
+        VideoCodec::H265 => &[VideoCodec::H264],
+        VideoCodec::H264 => &[],
     }
+}
 
-    if in_video && !video_has_framerate {
-        output.push(target);
-        changed = true;
+fn align_video_sdp_framerate_for_gstreamer(offer: &mut SessionDescription, fps: u32) -> bool {
+    if fps == 0 {
+        return false;
     }
 
-    let mut result = output.join(line_ending);
-    if has_trailing_ending {
-        result.push_str(line_ending);
+    let target = format!("a=framerate:{fps}");
+    let mut changed = false;
+    for section in offer.video_sections_mut() {
+        let existing = section
+            .lines
+            .iter_mut()
+            .filter(|line| line.starts_with("a=framerate:"));
+        let mut has_framerate = false;
+        for line in existing {
+            has_framerate = true;
+            if *line != target {
+                line.clone_from(&target);
+                changed = true;
+            }
+        }
+        if !has_framerate {
+            section.lines.push(target.clone());
+            changed = true;
+        }
     }
-    (result, changed)
+    changed
 }
 
 fn resolve_native_codec(configured: VideoCodec) -> VideoCodec {
@@ -254,7 +301,16 @@ fn resolve_native_codec(configured: VideoCodec) -> VideoCodec {
         "h264" | "avc" => VideoCodec::H264,
         "h265" | "hevc" => VideoCodec::H265,
         "av1" => VideoCodec::AV1,
-        _ => configured,
+        _ => apply_av1_flag(configured, flags().get(Flag::Av1)),
+    }
+}
+
+/// An explicit `OPENNOW_NATIVE_CODEC` still wins over the flag.
+fn apply_av1_flag(configured: VideoCodec, av1: Option<bool>) -> VideoCodec {
+    match (configured, av1) {
+        (_, Some(true)) => VideoCodec::AV1,
+        (VideoCodec::AV1, Some(false)) => VideoCodec::H265,
+        (codec, _) => codec,
     }
 }
 
@@ -284,6 +340,25 @@ pub fn prepared_offer_events(prepared: &PreparedNativeOffer) -> Vec<Event> {
         });
     }
 
+    if !prepared.rejected_video_payloads.is_empty() {
+        let outcome = if nvst.codec == prepared.requested_codec {
+            format!("negotiating the remaining {} payloads", nvst.codec.as_str())
+        } else {
+            format!(
+                "falling back from {} to {}",
+                prepared.requested_codec.as_str(),
+                nvst.codec.as_str()
+            )
+        };
+        events.push(Event::Log {
+            level: "warn",
+            message: format!(
+                "Local decoder does not support offered video payload(s) {}; {outcome}.",
+                prepared.rejected_video_payloads.join(", ")
+            ),
+        });
+    }
+
     if let Some(media_connection_info) = &prepared.media_connection_info {
         events.push(Event::Log {
             level: "debug",
@@ -300,6 +375,13 @@ pub fn prepared_offer_events(prepared: &PreparedNativeOffer) -> Vec<Event> {
             summarize_media_transport_attributes(&prepared.fixed_offer_sdp)
         ),
     });
+    if let [first, _, ..] = prepared.video_layers.as_slice() {
+        events.push(Event::VideoLayers {
+            layers: prepared.video_layers.clone(),
+            active: first.id.clone(),
+            paused_at_sender: prepared.video_tmmbr,
+        });
+    }
     if prepared.gstreamer_ice_pwd_replacements > 0 {
         events.push(Event::Log {
             level: "warn",
@@ -363,6 +445,7 @@ impl NativeStreamerBackend for StubBackend {
             supports_local_ice: false,
             supports_input: false,
             video_backends: Vec::new(),
+            active_flags: flags().describe(),
         }
     }
 
@@ -402,7 +485,8 @@ impl NativeStreamerBackend for StubBackend {
             return BackendReply::response(missing_field(&id, "sdp"));
         };
 
-        let prepared = match prepare_native_offer(&context, &offer_sdp) {
+        let prepared = match prepare_native_offer(&context, &offer_sdp, &DecoderProfiles::default())
+        {
             Ok(prepared) => prepared,
             Err(error) => return BackendReply::response(error.into_response(id)),
         };
@@ -466,6 +550,33 @@ impl NativeStreamerBackend for StubBackend {
         BackendReply::response(Response::Ok { id: command.id })
     }
 
+    fn update_volume(&mut self, command: CommandEnvelope) -> BackendReply {
+        if command.volume.is_none() {
+            return BackendReply::response(missing_field(&command.id, "volume"));
+        }
+
+        // Stub backend renders no audio.
+        BackendReply::response(Response::Ok { id: command.id })
+    }
+
+    fn select_video_layer(&mut self, command: CommandEnvelope) -> BackendReply {
+        if command.video_layer.is_none() {
+            return BackendReply::response(missing_field(&command.id, "videoLayer"));
+        }
+
+        // Stub backend receives no video.
+        BackendReply::response(Response::Ok { id: command.id })
+    }
+
+    fn update_render_fps_cap(&mut self, command: CommandEnvelope) -> BackendReply {
+        if command.render_fps_cap.is_none() {
+            return BackendReply::response(missing_field(&command.id, "renderFpsCap"));
+        }
+
+        // Stub backend presents no video.
+        BackendReply::response(Response::Ok { id: command.id })
+    }
+
     fn stop(&mut self, command: CommandEnvelope) -> BackendReply {
         self.active_context = None;
         let message = command
@@ -498,7 +609,9 @@ fn preferred_hevc_profile_id(color_quality: ColorQuality) -> u8 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::protocol::{ColorQuality, NativeStreamerShortcutBindings, SessionInfo, StreamSettings};
+    use crate::protocol::{
+        ColorQuality, NativeStreamerShortcutBindings, SessionInfo, StreamSettings,
+    };
 
     fn context(resolution: &str) -> NativeStreamerSessionContext {
         NativeStreamerSessionContext {
@@ -521,6 +634,17 @@ mod tests {
                 color_quality: ColorQuality::TenBit420,
                 enable_cloud_gsync: false,
                 native_transition_diagnostics: None,
+                ice_transport_policy: None,
+                custom_ice_servers: Vec::new(),
+                udp_port_range: None,
+                network_qos_marking: false,
+                audio_output: None,
+                controller_slots: None,
+                controller_light_color: None,
+                render_fps_cap: None,
+                media_thread_priority: false,
+                jitter_buffer_ms: None,
+                low_latency: false,
             },
             shortcuts: NativeStreamerShortcutBindings::default(),
         }
@@ -529,7 +653,9 @@ mod tests {
     #[test]
     fn prepares_offer_once_for_all_backends() {
         let offer = "v=0\nc=IN IP4 0.0.0.0\na=ice-ufrag:user\na=ice-pwd:pass\na=fingerprint:sha-256 AA:BB\n";
-        let prepared = prepare_native_offer(&context("1920x1080"), offer).expect("valid offer");
+        let prepared =
+            prepare_native_offer(&context("1920x1080"), offer, &DecoderProfiles::default())
+                .expect("valid offer");
 
         assert!(prepared.fixed_offer_sdp.contains("c=IN IP4 80.250.97.40"));
         assert!(prepared
@@ -549,6 +675,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn announces_video_layers_only_when_the_server_offers_several() {
+        let single = "v=0\nm=video 9 UDP/TLS/RTP/SAVPF 96\na=mid:1\n";
+        let prepared =
+            prepare_native_offer(&context("1920x1080"), single, &DecoderProfiles::default())
+                .expect("valid offer");
+        assert!(!prepared_offer_events(&prepared)
+            .iter()
+            .any(|event| matches!(event, Event::VideoLayers { .. })));
+
+        let simulcast = "v=0\nm=video 9 UDP/TLS/RTP/SAVPF 96\na=mid:1\na=rid:h send max-height=2160\na=rid:l send max-height=1080\n";
+        let prepared = prepare_native_offer(
+            &context("1920x1080"),
+            simulcast,
+            &DecoderProfiles::default(),
+        )
+        .expect("valid offer");
+        let active = prepared_offer_events(&prepared)
+            .into_iter()
+            .find_map(|event| match event {
+                Event::VideoLayers {
+                    layers,
+                    active,
+                    paused_at_sender,
+                } => Some((layers.len(), active, paused_at_sender)),
+                _ => None,
+            });
+        assert_eq!(active, Some((2, "h".to_owned(), false)));
+    }
+
     #[test]
     fn prepares_offer_filters_remote_video_to_requested_codec() {
         let offer = [
@@ -571,7 +727,9 @@ mod tests {
         ]
         .join("\n");
 
-        let prepared = prepare_native_offer(&context("1920x1080"), &offer).expect("valid offer");
+        let prepared =
+            prepare_native_offer(&context("1920x1080"), &offer, &DecoderProfiles::default())
+                .expect("valid offer");
 
         assert!(prepared
             .gstreamer_offer_sdp
@@ -597,7 +755,10 @@ mod tests {
     fn aligns_gstreamer_video_sdp_framerate() {
         let sdp = "v=0\nm=video 9 UDP/TLS/RTP/SAVPF 96\na=framerate:60\na=rtpmap:96 H265/90000\n";
 
-        let (aligned, changed) = align_video_sdp_framerate_for_gstreamer(sdp, 240);
+        let mut offer = SessionDescription::parse(sdp).expect("valid sdp");
+
+        let changed = align_video_sdp_framerate_for_gstreamer(&mut offer, 240);
+        let aligned = offer.to_string();
 
         assert!(changed);
         assert!(aligned.contains("a=framerate:240\n"));
@@ -608,7 +769,10 @@ mod tests {
     fn inserts_gstreamer_video_sdp_framerate_when_absent() {
         let sdp = "v=0\nm=audio 9 UDP/TLS/RTP/SAVPF 111\na=rtpmap:111 OPUS/48000/2\nm=video 9 UDP/TLS/RTP/SAVPF 96\na=rtpmap:96 H265/90000\nm=application 9 UDP/DTLS/SCTP webrtc-datachannel\n";
 
-        let (aligned, changed) = align_video_sdp_framerate_for_gstreamer(sdp, 120);
+        let mut offer = SessionDescription::parse(sdp).expect("valid sdp");
+
+        let changed = align_video_sdp_framerate_for_gstreamer(&mut offer, 120);
+        let aligned = offer.to_string();
 
         assert!(changed);
         assert!(aligned.contains(
@@ -620,16 +784,55 @@ mod tests {
     fn preserves_gstreamer_video_sdp_framerate_line_endings() {
         let sdp = "v=0\r\nm=video 9 UDP/TLS/RTP/SAVPF 96\r\na=rtpmap:96 H265/90000\r\n";
 
-        let (aligned, changed) = align_video_sdp_framerate_for_gstreamer(sdp, 240);
+        let mut offer = SessionDescription::parse(sdp).expect("valid sdp");
+
+        let changed = align_video_sdp_framerate_for_gstreamer(&mut offer, 240);
+        let aligned = offer.to_string();
 
         assert!(changed);
         assert!(aligned.contains("a=framerate:240\r\n"));
         assert!(!aligned.contains('\n') || aligned.contains("\r\n"));
     }
 
+    #[test]
+    fn falls_back_to_a_lower_codec_when_the_decoder_rejects_every_profile() {
+        let offer = [
+            "v=0",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96 98",
+            "a=rtpmap:96 H264/90000",
+            "a=fmtp:96 profile-level-id=64001f;packetization-mode=1",
+            "a=rtpmap:98 H265/90000",
+            "a=fmtp:98 profile-id=4;level-id=186",
+        ]
+        .join("\n");
+        let decoder_profiles = DecoderProfiles {
+            h265: crate::sdp::DecoderProfileSupport {
+                profiles: vec!["main".to_owned(), "main-10".to_owned()],
+                tiers: Vec::new(),
+            },
+            ..DecoderProfiles::default()
+        };
+
+        let prepared = prepare_native_offer(&context("1920x1080"), &offer, &decoder_profiles)
+            .expect("valid offer");
+
+        assert_eq!(prepared.nvst_params.codec, VideoCodec::H264);
+        assert_eq!(prepared.requested_codec, VideoCodec::H265);
+        assert_eq!(prepared.rejected_video_payloads, vec!["H265 98 (main-444)"]);
+        assert!(prepared
+            .fixed_offer_sdp
+            .contains("m=video 9 UDP/TLS/RTP/SAVPF 96"));
+        assert!(!prepared.fixed_offer_sdp.contains("a=rtpmap:98"));
+        assert!(prepared_offer_events(&prepared).iter().any(|event| matches!(
+            event,
+            Event::Log { level: "warn", message } if message.contains("falling back from H265 to H264")
+        )));
+    }
+
     #[test]
     fn rejects_invalid_resolution_during_offer_preparation() {
-        let error = prepare_native_offer(&context("bad"), "v=0").expect_err("invalid resolution");
+        let error = prepare_native_offer(&context("bad"), "v=0", &DecoderProfiles::default())
+            .expect_err("invalid resolution");
         assert_eq!(
             error,
             PrepareNativeOfferError::InvalidResolution {
@@ -638,6 +841,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_malformed_offer_sdp_during_offer_preparation() {
+        let error = prepare_native_offer(
+            &context("1920x1080"),
+            "v=0\r\nnot sdp\r\nm=video 9 UDP/TLS/RTP/SAVPF 96\r\na=mid:0\r\n",
+            &DecoderProfiles::default(),
+        )
+        .expect_err("malformed sdp");
+        let Response::Error { code, message, .. } = error.into_response("offer-1".to_owned())
+        else {
+            panic!("expected an error response");
+        };
+        assert_eq!(code, "invalid-remote-sdp");
+        assert!(message.contains("line 2"));
+    }
+
+    #[test]
+    fn rejects_empty_offer_sdp_during_offer_preparation() {
+        let error =
+            prepare_native_offer(&context("1920x1080"), "\r\n", &DecoderProfiles::default())
+                .expect_err("empty sdp");
+        let Response::Error { code, .. } = error.into_response("offer-1".to_owned()) else {
+            panic!("expected an error response");
+        };
+        assert_eq!(code, "invalid-remote-sdp");
+    }
+
     #[test]
     fn normalizes_native_bitrate_limits_to_slider_bounds() {
         assert_eq!(normalize_bitrate_kbps(1_000), 5_000);
@@ -680,4 +910,21 @@ mod tests {
             .as_deref()
             .is_some_and(|reason| reason.contains("without the gstreamer feature")),);
     }
+
+    #[test]
+    fn av1_flag_requests_or_avoids_av1() {
+        assert_eq!(
+            apply_av1_flag(VideoCodec::H264, Some(true)),
+            VideoCodec::AV1
+        );
+        assert_eq!(
+            apply_av1_flag(VideoCodec::AV1, Some(false)),
+            VideoCodec::H265
+        );
+        assert_eq!(
+            apply_av1_flag(VideoCodec::H264, Some(false)),
+            VideoCodec::H264
+        );
+        assert_eq!(apply_av1_flag(VideoCodec::AV1, None), VideoCodec::AV1);
+    }
 }
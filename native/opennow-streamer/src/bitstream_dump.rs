@@ -0,0 +1,330 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+//! Rewrites depayloaded video and RTP headers for the debug dump.
+//!
+//! The elementary stream always comes out Annex-B, so a dump replays with
+//! `filesrc ! h264parse` whatever the depayloader negotiated, and the parts
+//! that tie a dump to one GFN session are left out.
+
+use crate::sei::{rbsp, sei_varint, SeiCodec};
+
+const START_CODE: [u8; 4] = [0, 0, 0, 1];
+const RTP_HEADER_LEN: usize = 12;
+const H264_NAL_SEI: u8 = 6;
+const H265_NAL_PREFIX_SEI: u8 = 39;
+const H265_NAL_SUFFIX_SEI: u8 = 40;
+const SEI_USER_DATA_UNREGISTERED: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framing {
+    AnnexB,
+    LengthPrefixed { length_size: usize },
+}
+
+/// Turns depayloader output into an Annex-B stream. `avc` and `hvc1` streams
+/// get their parameter sets from `codec_data` written ahead of the next
+/// buffer, since those formats do not repeat them in band.
+#[derive(Debug)]
+pub(crate) struct AnnexBRewriter {
+    /// `None` for codecs without NAL units, which are written unchanged.
+    codec: Option<SeiCodec>,
+    framing: Framing,
+    parameter_sets: Vec<Vec<u8>>,
+    parameter_sets_pending: bool,
+}
+
+impl AnnexBRewriter {
+    pub(crate) fn new(codec: Option<SeiCodec>) -> Self {
+        Self {
+            codec,
+            framing: Framing::AnnexB,
+            parameter_sets: Vec::new(),
+            parameter_sets_pending: false,
+        }
+    }
+
+    /// Applies newly negotiated caps.
+    pub(crate) fn set_format(&mut self, stream_format: Option<&str>, codec_data: Option<&[u8]>) {
+        let Some(codec) = self.codec else {
+            return;
+        };
+        let record = match stream_format {
+            Some("avc" | "avc3" | "hvc1" | "hev1") => {
+                codec_data.and_then(|record| parse_decoder_config(codec, record))
+            }
+            _ => {
+                self.framing = Framing::AnnexB;
+                self.parameter_sets.clear();
+                return;
+            }
+        };
+        let (length_size, parameter_sets) = record.unwrap_or((4, Vec::new()));
+        self.framing = Framing::LengthPrefixed { length_size };
+        self.parameter_sets_pending = !parameter_sets.is_empty();
+        self.parameter_sets = parameter_sets;
+    }
+
+    pub(crate) fn rewrite(&mut self, data: &[u8], out: &mut Vec<u8>) {
+        let Some(codec) = self.codec else {
+            out.extend_from_slice(data);
+            return;
+        };
+        if std::mem::take(&mut self.parameter_sets_pending) {
+            for parameter_set in &self.parameter_sets {
+                out.extend_from_slice(&START_CODE);
+                out.extend_from_slice(parameter_set);
+            }
+        }
+        let mut push = |nal: &[u8]| {
+            if !nal.is_empty() && !carries_user_data(codec, nal) {
+                out.extend_from_slice(&START_CODE);
+                out.extend_from_slice(nal);
+            }
+        };
+        match self.framing {
+            Framing::AnnexB => {
+                let mut next = find_start_code(data, 0);
+                while let Some(start) = next {
+                    next = find_start_code(data, start);
+                    let mut nal = &data[start..next.map_or(data.len(), |next| next - 3)];
+                    // A four-byte start code leaves its leading zero here.
+                    while let [rest @ .., 0] = nal {
+                        nal = rest;
+                    }
+                    push(nal);
+                }
+            }
+            Framing::LengthPrefixed { length_size } => {
+                let mut pos = 0;
+                while let Some(prefix) = data.get(pos..pos + length_size) {
+                    let length = prefix
+                        .iter()
+                        .fold(0usize, |length, byte| length << 8 | usize::from(*byte));
+                    let Some(nal) = data.get(pos + length_size..pos + length_size + length) else {
+                        break;
+                    };
+                    push(nal);
+                    pos += length_size + length;
+                }
+            }
+        }
+    }
+}
+
+/// Reads the NAL length size and parameter sets from an `avcC` or `hvcC`
+/// record.
+fn parse_decoder_config(codec: SeiCodec, record: &[u8]) -> Option<(usize, Vec<Vec<u8>>)> {
+    let read_set = |pos: &mut usize| {
+        let length = usize::from(u16::from_be_bytes([
+            *record.get(*pos)?,
+            *record.get(*pos + 1)?,
+        ]));
+        let set = record.get(*pos + 2..*pos + 2 + length)?.to_vec();
+        *pos += 2 + length;
+        Some(set)
+    };
+    let mut parameter_sets = Vec::new();
+    match codec {
+        SeiCodec::H264 => {
+            let length_size = usize::from(record.get(4)? & 0x03) + 1;
+            let mut pos = 6;
+            for _ in 0..record.get(5)? & 0x1f {
+                parameter_sets.push(read_set(&mut pos)?);
+            }
+            let pps_count = *record.get(pos)?;
+            pos += 1;
+            for _ in 0..pps_count {
+                parameter_sets.push(read_set(&mut pos)?);
+            }
+            Some((length_size, parameter_sets))
+        }
+        SeiCodec::H265 => {
+            let length_size = usize::from(record.get(21)? & 0x03) + 1;
+            let mut pos = 23;
+            for _ in 0..*record.get(22)? {
+                let count = u16::from_be_bytes([*record.get(pos + 1)?, *record.get(pos + 2)?]);
+                pos += 3;
+                for _ in 0..count {
+                    parameter_sets.push(read_set(&mut pos)?);
+                }
+            }
+            Some((length_size, parameter_sets))
+        }
+    }
+}
+
+/// Encoders put build and session identifiers in unregistered user data, and
+/// decoders ignore it, so SEI NAL units carrying any are not dumped.
+fn carries_user_data(codec: SeiCodec, nal: &[u8]) -> bool {
+    let header_len = match codec {
+        SeiCodec::H264 if nal[0] & 0x1f == H264_NAL_SEI => 1,
+        SeiCodec::H265
+            if matches!(
+                (nal[0] >> 1) & 0x3f,
+                H265_NAL_PREFIX_SEI | H265_NAL_SUFFIX_SEI
+            ) =>
+        {
+            2
+        }
+        _ => return false,
+    };
+    let Some(payload) = nal.get(header_len..) else {
+        return false;
+    };
+    let rbsp = rbsp(payload);
+    let mut pos = 0;
+    while rbsp.len() > pos + 1 {
+        let (Some(payload_type), Some(payload_size)) =
+            (sei_varint(&rbsp, &mut pos), sei_varint(&rbsp, &mut pos))
+        else {
+            return false;
+        };
+        if payload_type == SEI_USER_DATA_UNREGISTERED {
+            return true;
+        }
+        pos += payload_size as usize;
+    }
+    false
+}
+
+/// Index just past the next `00 00 01` at or after `from`.
+fn find_start_code(data: &[u8], from: usize) -> Option<usize> {
+    data.get(from..)?
+        .windows(3)
+        .position(|window| window == [0, 0, 1])
+        .map(|index| from + index + 3)
+}
+
+/// Describes RTP packets without their payload. The SSRC is left out, and
+/// sequence numbers and timestamps count from the first packet, because their
+/// random starting values would match the trace to a capture of the session.
+#[derive(Debug, Default)]
+pub(crate) struct RtpTraceFormatter {
+    origin: Option<(u16, u32)>,
+}
+
+impl RtpTraceFormatter {
+    /// `header` needs the fixed 12 bytes; `size` is the whole packet.
+    pub(crate) fn line(&mut self, header: &[u8], size: usize, elapsed_us: u128) -> Option<String> {
+        if header.len() < RTP_HEADER_LEN || header[0] >> 6 != 2 {
+            return None;
+        }
+        let marker = header[1] & 0x80 != 0;
+        let payload_type = header[1] & 0x7f;
+        let sequence = u16::from_be_bytes([header[2], header[3]]);
+        let timestamp = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+        let (first_sequence, first_timestamp) = *self.origin.get_or_insert((sequence, timestamp));
+        Some(format!(
+            "{elapsed_us} seq={} ts={} pt={payload_type} marker={} size={size}\n",
+            sequence.wrapping_sub(first_sequence),
+            timestamp.wrapping_sub(first_timestamp),
+            u8::from(marker),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn length_prefixed(nals: &[&[u8]], length_size: usize) -> Vec<u8> {
+        nals.iter()
+            .flat_map(|nal| {
+                let length = (nal.len() as u32).to_be_bytes();
+                [&length[4 - length_size..], nal].concat()
+            })
+            .collect()
+    }
+
+    const SPS: &[u8] = &[0x67, 0x64, 0x00, 0x33];
+    const PPS: &[u8] = &[0x68, 0xee, 0x3c, 0x80];
+    const USER_DATA_SEI: &[u8] = &[0x06, 0x05, 0x02, 0xaa, 0xbb, 0x80];
+    const LIGHT_LEVEL_SEI: &[u8] = &[0x06, 0x90, 0x04, 0x03, 0xe8, 0x01, 0x90, 0x80];
+    const SLICE: &[u8] = &[0x65, 0x88, 0x84, 0x21];
+
+    #[test]
+    fn rewrites_avc_to_annex_b_with_parameter_sets_first() {
+        let avcc = [
+            &[0x01, 0x64, 0x00, 0x33, 0xfd, 0xe1, 0x00, 0x04][..],
+            SPS,
+            &[0x01, 0x00, 0x04],
+            PPS,
+        ]
+        .concat();
+        let mut rewriter = AnnexBRewriter::new(Some(SeiCodec::H264));
+        rewriter.set_format(Some("avc"), Some(&avcc));
+
+        let mut out = Vec::new();
+        rewriter.rewrite(&length_prefixed(&[LIGHT_LEVEL_SEI, SLICE], 2), &mut out);
+        rewriter.rewrite(&length_prefixed(&[SLICE], 2), &mut out);
+        let expected = [SPS, PPS, LIGHT_LEVEL_SEI, SLICE, SLICE]
+            .iter()
+            .flat_map(|nal| [&START_CODE[..], nal].concat())
+            .collect::<Vec<_>>();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn reads_hevc_decoder_config_records() {
+        let vps = [0x40, 0x01, 0x0c];
+        let sps = [0x42, 0x01, 0x01];
+        let mut hvcc = vec![0; 21];
+        hvcc.extend_from_slice(&[0x0f, 2]);
+        hvcc.extend_from_slice(&[0xa0, 0x00, 0x01, 0x00, 0x03]);
+        hvcc.extend_from_slice(&vps);
+        hvcc.extend_from_slice(&[0xa1, 0x00, 0x01, 0x00, 0x03]);
+        hvcc.extend_from_slice(&sps);
+        assert_eq!(
+            parse_decoder_config(SeiCodec::H265, &hvcc),
+            Some((4, vec![vps.to_vec(), sps.to_vec()]))
+        );
+        assert_eq!(parse_decoder_config(SeiCodec::H265, &hvcc[..26]), None);
+    }
+
+    #[test]
+    fn drops_user_data_sei_and_normalizes_start_codes() {
+        let input = [
+            &[0, 0, 1][..],
+            USER_DATA_SEI,
+            &[0, 0, 0, 1],
+            LIGHT_LEVEL_SEI,
+            &[0, 0, 1],
+            SLICE,
+        ]
+        .concat();
+        let mut rewriter = AnnexBRewriter::new(Some(SeiCodec::H264));
+        rewriter.set_format(Some("byte-stream"), None);
+        let mut out = Vec::new();
+        rewriter.rewrite(&input, &mut out);
+        assert_eq!(
+            out,
+            [&START_CODE[..], LIGHT_LEVEL_SEI, &START_CODE, SLICE].concat()
+        );
+
+        let mut passthrough = Vec::new();
+        AnnexBRewriter::new(None).rewrite(&input, &mut passthrough);
+        assert_eq!(passthrough, input);
+    }
+
+    #[test]
+    fn traces_rtp_headers_relative_to_the_first_packet() {
+        let packet = |sequence: u16, timestamp: u32| {
+            [
+                &[0x80, 0xe0][..],
+                &sequence.to_be_bytes(),
+                &timestamp.to_be_bytes(),
+                &[0xde, 0xad, 0xbe, 0xef],
+            ]
+            .concat()
+        };
+        let mut trace = RtpTraceFormatter::default();
+        let first = trace
+            .line(&packet(0xfffe, 4096), 1400, 42)
+            .expect("RTP packet");
+        assert_eq!(first, "42 seq=0 ts=0 pt=96 marker=1 size=1400\n");
+        let line = trace.line(&packet(1, 7096), 900, 50).expect("RTP packet");
+        assert_eq!(line, "50 seq=3 ts=3000 pt=96 marker=1 size=900\n");
+        assert!(!line.contains("3735928559"));
+        assert!(trace.line(&packet(2, 0)[..8], 8, 0).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+//! Health of the compressed video stream as it reaches the decoder.
+//!
+//! Damaged access units with a quiet decoder point at the network; decoder
+//! errors on an undamaged stream point at the decoder or driver.
+
+use std::sync::Mutex;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct BitstreamHealthSnapshot {
+    pub(crate) keyframes: u64,
+    /// Average gap between keyframes; GFN streams only send one on request
+    /// or after a scene cut, so this stays empty until a second one arrives.
+    pub(crate) keyframe_interval_ms: Option<f64>,
+    pub(crate) damaged_frames: u64,
+    pub(crate) decoder_errors: u64,
+}
+
+#[derive(Debug, Default)]
+struct BitstreamHealthState {
+    access_units: u64,
+    keyframes: u64,
+    first_keyframe_ms: Option<u64>,
+    last_keyframe_ms: Option<u64>,
+    damaged_frames: u64,
+    decoder_errors: u64,
+    /// Set when the client flushed the chain itself; the next unit is
+    /// discontinuous because of that flush, not because of the network.
+    flushed: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct BitstreamHealth {
+    state: Mutex<BitstreamHealthState>,
+}
+
+impl BitstreamHealth {
+    pub(crate) fn reset(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = BitstreamHealthState::default();
+        }
+    }
+
+    /// `discont` marks an access unit rebuilt across lost packets and
+    /// `corrupted` one the depayloader flagged as broken. The first unit of a
+    /// stream, and the first after [`Self::expect_flush_discontinuity`], is
+    /// discontinuous anyway, so that alone is not counted.
+    pub(crate) fn record_access_unit(
+        &self,
+        keyframe: bool,
+        discont: bool,
+        corrupted: bool,
+        now_ms: u64,
+    ) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let expected_discont = state.access_units == 0 || std::mem::take(&mut state.flushed);
+        if corrupted || (discont && !expected_discont) {
+            state.damaged_frames += 1;
+        }
+        state.access_units += 1;
+        if keyframe {
+            state.keyframes += 1;
+            state.first_keyframe_ms.get_or_insert(now_ms);
+            state.last_keyframe_ms = Some(now_ms);
+        }
+    }
+
+    /// Called before the client flushes the decode chain to skip ahead or
+    /// recover a transition.
+    pub(crate) fn expect_flush_discontinuity(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.flushed = true;
+        }
+    }
+
+    pub(crate) fn record_decoder_error(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.decoder_errors += 1;
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> BitstreamHealthSnapshot {
+        let Ok(state) = self.state.lock() else {
+            return BitstreamHealthSnapshot::default();
+        };
+        let keyframe_interval_ms = state
+            .first_keyframe_ms
+            .zip(state.last_keyframe_ms)
+            .filter(|_| state.keyframes >= 2)
+            .map(|(first, last)| last.saturating_sub(first) as f64 / (state.keyframes - 1) as f64);
+        BitstreamHealthSnapshot {
+            keyframes: state.keyframes,
+            keyframe_interval_ms,
+            damaged_frames: state.damaged_frames,
+            decoder_errors: state.decoder_errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_stream_damage_from_decoder_errors() {
+        let health = BitstreamHealth::default();
+        health.record_access_unit(true, true, false, 0);
+        health.record_access_unit(false, false, false, 16);
+        health.record_access_unit(false, true, false, 33);
+        assert_eq!(
+            health.snapshot(),
+            BitstreamHealthSnapshot {
+                keyframes: 1,
+                keyframe_interval_ms: None,
+                damaged_frames: 1,
+                decoder_errors: 0,
+            }
+        );
+
+        health.record_access_unit(true, false, false, 2_000);
+        health.record_access_unit(true, false, false, 5_000);
+        health.record_decoder_error();
+        let snapshot = health.snapshot();
+        assert_eq!(snapshot.keyframe_interval_ms, Some(2_500.0));
+        assert_eq!(snapshot.decoder_errors, 1);
+
+        health.reset();
+        assert_eq!(health.snapshot(), BitstreamHealthSnapshot::default());
+    }
+
+    #[test]
+    fn discontinuities_after_the_clients_own_flush_are_not_damage() {
+        let health = BitstreamHealth::default();
+        health.record_access_unit(true, false, false, 0);
+        health.expect_flush_discontinuity();
+        health.record_access_unit(true, true, false, 16);
+        assert_eq!(health.snapshot().damaged_frames, 0);
+
+        // Only the first unit after the flush is excused, and never a corrupt one.
+        health.record_access_unit(false, true, false, 33);
+        health.expect_flush_discontinuity();
+        health.record_access_unit(false, true, true, 50);
+        assert_eq!(health.snapshot().damaged_frames, 2);
+    }
+}
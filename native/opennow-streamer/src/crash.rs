@@ -0,0 +1,217 @@
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory the Electron host wants crash reports written to.
+pub(crate) const CRASH_DIR_ENV: &str = "OPENNOW_CRASH_DIR";
+const MAX_RECENT_LOG_LINES: usize = 200;
+/// What the runtime reports when a panic reaches a function that cannot
+/// unwind, such as a GStreamer callback; the process aborts right after.
+const CANNOT_UNWIND_MESSAGE: &str = "panic in a function that cannot unwind";
+
+static RECENT_LOG_LINES: LogHistory = LogHistory::new(MAX_RECENT_LOG_LINES);
+
+/// Bounded history of recent log lines, so a panic report has context even
+/// when the host never received the matching stderr output.
+struct LogHistory {
+    lines: Mutex<VecDeque<String>>,
+    limit: usize,
+}
+
+impl LogHistory {
+    const fn new(limit: usize) -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::new()),
+            limit,
+        }
+    }
+
+    fn record(&self, line: String) {
+        let Ok(mut lines) = self.lines.lock() else {
+            return;
+        };
+        if lines.len() == self.limit {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn lines(&self) -> Vec<String> {
+        match self.lines.lock() {
+            Ok(lines) => lines.iter().cloned().collect(),
+            // A panic while the lock was held must not stop the report from being written.
+            Err(poisoned) => poisoned.into_inner().iter().cloned().collect(),
+        }
+    }
+}
+
+pub(crate) fn record_log_line(line: impl Into<String>) {
+    RECENT_LOG_LINES.record(line.into());
+}
+
+/// Only panics that end the process get a crash report. With unwinding, a
+/// panic on a worker thread surfaces as a join error and the streamer keeps
+/// running, so it only goes into the log history.
+fn panic_ends_process(thread_name: Option<&str>, message: &str) -> bool {
+    cfg!(panic = "abort") || thread_name == Some("main") || message == CANNOT_UNWIND_MESSAGE
+}
+
+pub(crate) fn install_panic_hook() {
+    let crash_dir = std::env::var_os(CRASH_DIR_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_owned());
+        let current = thread::current();
+        let thread_name = current.name();
+        if !panic_ends_process(thread_name, &message) {
+            record_log_line(format!(
+                "panic on thread {}: {message}",
+                thread_name.unwrap_or("unnamed")
+            ));
+        } else if let Some(crash_dir) = &crash_dir {
+            let location = info.location().map(|location| {
+                format!(
+                    "{}:{}:{}",
+                    location.file(),
+                    location.line(),
+                    location.column()
+                )
+            });
+            let report = PanicReport {
+                message,
+                location,
+                thread: thread_name.unwrap_or("unnamed").to_owned(),
+                backtrace: Backtrace::force_capture().to_string(),
+                recent_logs: RECENT_LOG_LINES.lines(),
+            };
+            match write_crash_report(crash_dir, &report) {
+                Ok(path) => eprintln!(
+                    "[NativeStreamer] Crash report written to {}",
+                    path.display()
+                ),
+                Err(error) => eprintln!("[NativeStreamer] Failed to write crash report: {error}"),
+            }
+        }
+        default_hook(info);
+    }));
+}
+
+struct PanicReport {
+    message: String,
+    location: Option<String>,
+    thread: String,
+    backtrace: String,
+    recent_logs: Vec<String>,
+}
+
+fn format_crash_report(report: &PanicReport, timestamp_secs: u64) -> String {
+    let mut text = String::new();
+    text.push_str("OpenNOW Crash Report\n");
+    text.push_str("Source: native-streamer\n");
+    text.push_str(&format!("Timestamp: {timestamp_secs}\n"));
+    text.push_str(&format!("Version: {}\n", env!("CARGO_PKG_VERSION")));
+    text.push_str(&format!(
+        "System: {} {} ({})\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::FAMILY
+    ));
+    text.push_str(&format!(
+        "Backend: {}\n",
+        if cfg!(feature = "gstreamer") {
+            "gstreamer"
+        } else {
+            "stub"
+        }
+    ));
+    text.push_str(&format!("Thread: {}\n", report.thread));
+    text.push_str(&format!(
+        "Location: {}\n",
+        report.location.as_deref().unwrap_or("unknown")
+    ));
+    text.push_str(&format!("Reason: panic: {}\n", report.message));
+    text.push_str("\n== Backtrace ==\n");
+    text.push_str(report.backtrace.trim_end());
+    text.push_str("\n\n== Recent log ==\n");
+    for line in &report.recent_logs {
+        text.push_str(line);
+        text.push('\n');
+    }
+    text
+}
+
+fn write_crash_report(crash_dir: &Path, report: &PanicReport) -> io::Result<PathBuf> {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    fs::create_dir_all(crash_dir)?;
+    let path = crash_dir.join(format!("native-{timestamp_ms}.txt"));
+    fs::write(
+        &path,
+        format_crash_report(report, (timestamp_ms / 1000) as u64),
+    )?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_panic_report_with_backtrace_and_recent_logs() {
+        let report = PanicReport {
+            message: "index out of bounds".to_owned(),
+            location: Some("src/main.rs:10:5".to_owned()),
+            thread: "main".to_owned(),
+            backtrace: "0: opennow_streamer::main\n".to_owned(),
+            recent_logs: vec!["command start".to_owned(), "command offer".to_owned()],
+        };
+
+        let text = format_crash_report(&report, 1_700_000_000);
+
+        assert!(text.starts_with("OpenNOW Crash Report\nSource: native-streamer\n"));
+        assert!(text.contains("Timestamp: 1700000000\n"));
+        assert!(text.contains("Location: src/main.rs:10:5\n"));
+        assert!(text.contains("Reason: panic: index out of bounds\n"));
+        assert!(text.contains("== Backtrace ==\n0: opennow_streamer::main\n\n"));
+        assert!(text.ends_with("== Recent log ==\ncommand start\ncommand offer\n"));
+    }
+
+    #[test]
+    fn bounds_recent_log_history() {
+        let history = LogHistory::new(3);
+        for index in 0..5 {
+            history.record(format!("line {index}"));
+        }
+
+        assert_eq!(history.lines(), ["line 2", "line 3", "line 4"]);
+    }
+
+    #[test]
+    fn reports_only_panics_that_end_the_process() {
+        assert!(panic_ends_process(Some("main"), "index out of bounds"));
+        assert!(panic_ends_process(Some("gst-bus"), CANNOT_UNWIND_MESSAGE));
+        assert_eq!(
+            panic_ends_process(Some("dualsense"), "index out of bounds"),
+            cfg!(panic = "abort")
+        );
+        assert_eq!(
+            panic_ends_process(None, "index out of bounds"),
+            cfg!(panic = "abort")
+        );
+    }
+}
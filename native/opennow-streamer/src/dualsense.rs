@@ -0,0 +1,340 @@
+#![cfg_attr(not(feature = "dualsense"), allow(dead_code))]
+
+use crate::input::GamepadInput;
+
+pub(crate) const DUALSENSE_VENDOR_ID: u16 = 0x054c;
+/// DualSense and DualSense Edge.
+pub(crate) const DUALSENSE_PRODUCT_IDS: [u16; 2] = [0x0ce6, 0x0df2];
+/// Reading the calibration feature report switches a Bluetooth DualSense from
+/// its reduced 0x01 report to the full 0x31 report.
+pub(crate) const CALIBRATION_FEATURE_REPORT: u8 = 0x05;
+pub(crate) const CALIBRATION_FEATURE_REPORT_LEN: usize = 41;
+pub(crate) const INPUT_REPORT_MAX_LEN: usize = BT_INPUT_REPORT_LEN;
+/// `vendor:product` pairs (hex) that Steam Input has taken over. The shell
+/// sets it when launched from Steam; those pads are left to Steam's virtual
+/// controller so the game does not see every press twice.
+pub(crate) const STEAM_INPUT_DEVICES_ENV: &str = "OPENNOW_STEAM_INPUT_DEVICES";
+
+const USB_INPUT_REPORT: u8 = 0x01;
+const USB_INPUT_REPORT_LEN: usize = 64;
+const BT_INPUT_REPORT: u8 = 0x31;
+const BT_INPUT_REPORT_LEN: usize = 78;
+const USB_OUTPUT_REPORT: u8 = 0x02;
+const BT_OUTPUT_REPORT: u8 = 0x31;
+const BT_OUTPUT_REPORT_LEN: usize = 78;
+const BT_OUTPUT_TAG: u8 = 0x10;
+const BT_OUTPUT_CRC_SEED: u8 = 0xa2;
+const OUTPUT_COMMON_LEN: usize = 47;
+
+const VALID_FLAG0_COMPATIBLE_VIBRATION: u8 = 0x01;
+const VALID_FLAG0_HAPTICS_SELECT: u8 = 0x02;
+const VALID_FLAG1_LIGHTBAR: u8 = 0x04;
+const VALID_FLAG2_LIGHTBAR_SETUP: u8 = 0x02;
+const LIGHTBAR_SETUP_LIGHT_OUT: u8 = 0x02;
+
+const GAMEPAD_DPAD_UP: u16 = 0x0001;
+const GAMEPAD_DPAD_DOWN: u16 = 0x0002;
+const GAMEPAD_DPAD_LEFT: u16 = 0x0004;
+const GAMEPAD_DPAD_RIGHT: u16 = 0x0008;
+const GAMEPAD_START: u16 = 0x0010;
+const GAMEPAD_BACK: u16 = 0x0020;
+const GAMEPAD_LS: u16 = 0x0040;
+const GAMEPAD_RS: u16 = 0x0080;
+const GAMEPAD_LB: u16 = 0x0100;
+const GAMEPAD_RB: u16 = 0x0200;
+const GAMEPAD_GUIDE: u16 = 0x0400;
+const GAMEPAD_A: u16 = 0x1000;
+const GAMEPAD_B: u16 = 0x2000;
+const GAMEPAD_X: u16 = 0x4000;
+const GAMEPAD_Y: u16 = 0x8000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DualSenseTransport {
+    Usb,
+    Bluetooth,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct DualSenseState {
+    pub(crate) buttons: u16,
+    pub(crate) left_trigger: u8,
+    pub(crate) right_trigger: u8,
+    pub(crate) left_stick_x: i16,
+    pub(crate) left_stick_y: i16,
+    pub(crate) right_stick_x: i16,
+    pub(crate) right_stick_y: i16,
+}
+
+impl DualSenseState {
+    pub(crate) fn gamepad_input(&self, controller_id: u8, timestamp_us: u64) -> GamepadInput {
+        GamepadInput {
+            controller_id,
+            buttons: self.buttons,
+            left_trigger: self.left_trigger,
+            right_trigger: self.right_trigger,
+            left_stick_x: self.left_stick_x,
+            left_stick_y: self.left_stick_y,
+            right_stick_x: self.right_stick_x,
+            right_stick_y: self.right_stick_y,
+            connected: true,
+            timestamp_us,
+        }
+    }
+}
+
+/// Parses a full input report, mapping the pad onto the XInput layout GFN
+/// expects. Reduced Bluetooth reports return `None` until calibration has
+/// been read.
+pub(crate) fn parse_input_report(report: &[u8]) -> Option<(DualSenseTransport, DualSenseState)> {
+    let (transport, data) = match *report.first()? {
+        USB_INPUT_REPORT if report.len() >= USB_INPUT_REPORT_LEN => {
+            (DualSenseTransport::Usb, &report[1..])
+        }
+        BT_INPUT_REPORT if report.len() >= BT_INPUT_REPORT_LEN => {
+            (DualSenseTransport::Bluetooth, &report[2..])
+        }
+        _ => return None,
+    };
+
+    let face = data[7];
+    let shoulders = data[8];
+    let system = data[9];
+    let mut buttons = dpad_buttons(face & 0x0f);
+    for (pressed, flag) in [
+        (face & 0x10, GAMEPAD_X),
+        (face & 0x20, GAMEPAD_A),
+        (face & 0x40, GAMEPAD_B),
+        (face & 0x80, GAMEPAD_Y),
+        (shoulders & 0x01, GAMEPAD_LB),
+        (shoulders & 0x02, GAMEPAD_RB),
+        (shoulders & 0x10, GAMEPAD_BACK),
+        (shoulders & 0x20, GAMEPAD_START),
+        (shoulders & 0x40, GAMEPAD_LS),
+        (shoulders & 0x80, GAMEPAD_RS),
+        (system & 0x01, GAMEPAD_GUIDE),
+    ] {
+        if pressed != 0 {
+            buttons |= flag;
+        }
+    }
+
+    Some((
+        transport,
+        DualSenseState {
+            buttons,
+            left_trigger: data[4],
+            right_trigger: data[5],
+            left_stick_x: stick_axis(data[0], false),
+            left_stick_y: stick_axis(data[1], true),
+            right_stick_x: stick_axis(data[2], false),
+            right_stick_y: stick_axis(data[3], true),
+        },
+    ))
+}
+
+fn dpad_buttons(hat: u8) -> u16 {
+    match hat {
+        0 => GAMEPAD_DPAD_UP,
+        1 => GAMEPAD_DPAD_UP | GAMEPAD_DPAD_RIGHT,
+        2 => GAMEPAD_DPAD_RIGHT,
+        3 => GAMEPAD_DPAD_DOWN | GAMEPAD_DPAD_RIGHT,
+        4 => GAMEPAD_DPAD_DOWN,
+        5 => GAMEPAD_DPAD_DOWN | GAMEPAD_DPAD_LEFT,
+        6 => GAMEPAD_DPAD_LEFT,
+        7 => GAMEPAD_DPAD_UP | GAMEPAD_DPAD_LEFT,
+        _ => 0,
+    }
+}
+
+/// DualSense sticks report Y growing downwards; XInput expects up positive.
+fn stick_axis(raw: u8, inverted: bool) -> i16 {
+    let centered = i32::from(raw) - 128;
+    let centered = if inverted { -centered } else { centered };
+    (centered * i32::from(i16::MAX) / 127).clamp(i16::MIN.into(), i16::MAX.into()) as i16
+}
+
+/// Everything the client drives on the controller. Written in full on every
+/// change because the DualSense only applies fields whose valid flag is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct DualSenseOutput {
+    pub(crate) weak_rumble: u8,
+    pub(crate) strong_rumble: u8,
+    pub(crate) lightbar: Option<[u8; 3]>,
+}
+
+impl DualSenseOutput {
+    pub(crate) fn set_rumble(&mut self, weak_magnitude: u16, strong_magnitude: u16) {
+        self.weak_rumble = (weak_magnitude >> 8) as u8;
+        self.strong_rumble = (strong_magnitude >> 8) as u8;
+    }
+}
+
+/// Builds the output report for `transport`. Bluetooth reports carry a
+/// four-bit sequence number and a trailing CRC32.
+pub(crate) fn build_output_report(
+    transport: DualSenseTransport,
+    output: &DualSenseOutput,
+    sequence: u8,
+) -> Vec<u8> {
+    let common = output_report_common(output);
+    match transport {
+        DualSenseTransport::Usb => {
+            let mut report = Vec::with_capacity(1 + OUTPUT_COMMON_LEN);
+            report.push(USB_OUTPUT_REPORT);
+            report.extend_from_slice(&common);
+            report
+        }
+        DualSenseTransport::Bluetooth => {
+            let mut report = vec![0; BT_OUTPUT_REPORT_LEN];
+            report[0] = BT_OUTPUT_REPORT;
+            report[1] = (sequence & 0x0f) << 4;
+            report[2] = BT_OUTPUT_TAG;
+            report[3..3 + OUTPUT_COMMON_LEN].copy_from_slice(&common);
+            let crc_offset = BT_OUTPUT_REPORT_LEN - 4;
+            let crc = crc32(&[&[BT_OUTPUT_CRC_SEED], &report[..crc_offset]]);
+            report[crc_offset..].copy_from_slice(&crc.to_le_bytes());
+            report
+        }
+    }
+}
+
+fn output_report_common(output: &DualSenseOutput) -> [u8; OUTPUT_COMMON_LEN] {
+    let mut common = [0; OUTPUT_COMMON_LEN];
+    common[0] = VALID_FLAG0_COMPATIBLE_VIBRATION | VALID_FLAG0_HAPTICS_SELECT;
+    // The right motor is the light, high-frequency one.
+    common[2] = output.weak_rumble;
+    common[3] = output.strong_rumble;
+    if let Some([red, green, blue]) = output.lightbar {
+        common[1] |= VALID_FLAG1_LIGHTBAR;
+        common[38] |= VALID_FLAG2_LIGHTBAR_SETUP;
+        common[41] = LIGHTBAR_SETUP_LIGHT_OUT;
+        common[44..47].copy_from_slice(&[red, green, blue]);
+    }
+    common
+}
+
+fn crc32(chunks: &[&[u8]]) -> u32 {
+    let mut crc = u32::MAX;
+    for byte in chunks.iter().flat_map(|chunk| chunk.iter()) {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Accepts `#rrggbb` or `rrggbb`.
+pub(crate) fn parse_lightbar_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Parses `054c:0ce6,28de:1205`; malformed entries are skipped.
+pub(crate) fn parse_steam_input_devices(value: &str) -> Vec<(u16, u16)> {
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (vendor, product) = entry.trim().split_once(':')?;
+            Some((
+                u16::from_str_radix(vendor, 16).ok()?,
+                u16::from_str_radix(product, 16).ok()?,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usb_report() -> Vec<u8> {
+        let mut report = vec![0; USB_INPUT_REPORT_LEN];
+        report[0] = USB_INPUT_REPORT;
+        report[1..5].copy_from_slice(&[128, 128, 128, 128]);
+        report
+    }
+
+    #[test]
+    fn maps_input_reports_onto_the_xinput_layout() {
+        let mut report = usb_report();
+        report[1] = 255;
+        report[2] = 0;
+        report[5] = 40;
+        report[6] = 200;
+        report[8] = 0x20 | 0x03;
+        report[9] = 0x01 | 0x20;
+        // The touchpad click has no GFN button and is dropped.
+        report[10] = 0x02;
+        let (transport, state) = parse_input_report(&report).expect("usb report");
+        assert_eq!(transport, DualSenseTransport::Usb);
+        assert_eq!(
+            state.buttons,
+            GAMEPAD_A | GAMEPAD_DPAD_DOWN | GAMEPAD_DPAD_RIGHT | GAMEPAD_LB | GAMEPAD_START
+        );
+        assert_eq!(
+            (state.left_stick_x, state.left_stick_y),
+            (i16::MAX, i16::MAX)
+        );
+        assert_eq!((state.right_stick_x, state.right_stick_y), (0, 0));
+        assert_eq!((state.left_trigger, state.right_trigger), (40, 200));
+
+        let mut bluetooth = vec![BT_INPUT_REPORT, 0x00];
+        bluetooth.extend_from_slice(&report[1..]);
+        bluetooth.resize(BT_INPUT_REPORT_LEN, 0);
+        assert_eq!(
+            parse_input_report(&bluetooth),
+            Some((DualSenseTransport::Bluetooth, state))
+        );
+        assert_eq!(parse_input_report(&report[..10]), None);
+        assert_eq!(parse_input_report(&[0x01; 10]), None);
+    }
+
+    #[test]
+    fn builds_output_reports_for_both_transports() {
+        let mut output = DualSenseOutput {
+            lightbar: parse_lightbar_color("#ff8000"),
+            ..DualSenseOutput::default()
+        };
+        output.set_rumble(0x4000, 0xffff);
+
+        let usb = build_output_report(DualSenseTransport::Usb, &output, 0);
+        assert_eq!(usb.len(), 48);
+        assert_eq!(&usb[..5], &[USB_OUTPUT_REPORT, 0x03, 0x04, 0x40, 0xff]);
+        assert!(usb[11..33].iter().all(|&byte| byte == 0));
+        assert_eq!(&usb[45..48], &[0xff, 0x80, 0x00]);
+
+        let bluetooth = build_output_report(DualSenseTransport::Bluetooth, &output, 0x13);
+        assert_eq!(bluetooth.len(), BT_OUTPUT_REPORT_LEN);
+        assert_eq!(&bluetooth[..3], &[BT_OUTPUT_REPORT, 0x30, BT_OUTPUT_TAG]);
+        assert_eq!(&bluetooth[3..50], &usb[1..]);
+        let crc = crc32(&[&[BT_OUTPUT_CRC_SEED], &bluetooth[..74]]);
+        assert_eq!(&bluetooth[74..], &crc.to_le_bytes());
+        assert_eq!(crc32(&[b"1234", b"56789"]), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn parses_lightbar_colors() {
+        assert_eq!(parse_lightbar_color("00ff7f"), Some([0x00, 0xff, 0x7f]));
+        assert_eq!(parse_lightbar_color(" #0A0B0C "), Some([0x0a, 0x0b, 0x0c]));
+        assert_eq!(parse_lightbar_color("#fff"), None);
+        assert_eq!(parse_lightbar_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn parses_steam_input_devices() {
+        assert_eq!(
+            parse_steam_input_devices("054c:0ce6, 28de:1205,bogus,054c:"),
+            vec![(0x054c, 0x0ce6), (0x28de, 0x1205)]
+        );
+        assert!(parse_steam_input_devices("").is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! Runtime switches for experimental native paths.
+//!
+//! Flags come from two comma-separated lists: the app settings file, passed in
+//! by the Electron shell through [`SETTINGS_FLAGS_ENV`], and [`FLAGS_ENV`] set
+//! by the user. Entries are `name`, `name=on|off` or `!name`; the env list wins
+//! per flag so a shell override beats a saved setting. A flag nobody mentions
+//! stays unset and the built-in default applies.
+
+use std::sync::OnceLock;
+
+pub(crate) const FLAGS_ENV: &str = "OPENNOW_FLAGS";
+pub(crate) const SETTINGS_FLAGS_ENV: &str = "OPENNOW_NATIVE_SETTINGS_FLAGS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Flag {
+    /// Rank Vulkan Video first in the automatic decoder order, or drop it.
+    VulkanVideo,
+    /// Force or disable zero-copy decoder output when no explicit env is set.
+    ZeroCopy,
+    /// Negotiate AV1, or fall back to H.265 when AV1 is requested.
+    Av1,
+    /// Skip queued frames predicted to present late; off unless turned on or
+    /// low-latency mode is active.
+    LateFrameDrop,
+    /// Time every frame through the native pipeline stages for the stats
+    /// overlay; off unless turned on, though late-frame drop still needs it.
+    StageTiming,
+    /// Ask the server through TMMBR to stop sending the video layers that are
+    /// not shown. Off unless turned on, since a zero-bitrate request may stop
+    /// a layer outright on servers that do not expect it.
+    LayerPause,
+}
+
+impl Flag {
+    pub(crate) const ALL: [Flag; 6] = [
+        Flag::VulkanVideo,
+        Flag::ZeroCopy,
+        Flag::Av1,
+        Flag::LateFrameDrop,
+        Flag::StageTiming,
+        Flag::LayerPause,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::VulkanVideo => "vulkan-video",
+            Self::ZeroCopy => "zero-copy",
+            Self::Av1 => "av1",
+            Self::LateFrameDrop => "late-frame-drop",
+            Self::StageTiming => "stage-timing",
+            Self::LayerPause => "layer-pause",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let normalized = name.trim().to_ascii_lowercase().replace('_', "-");
+        Self::ALL.into_iter().find(|flag| flag.name() == normalized)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FlagSource {
+    Settings,
+    Env,
+}
+
+impl FlagSource {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Settings => "settings",
+            Self::Env => "env",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FlagSet {
+    values: Vec<(Flag, bool, FlagSource)>,
+}
+
+impl FlagSet {
+    pub(crate) fn from_sources(settings: Option<&str>, env: Option<&str>) -> Self {
+        let mut set = Self::default();
+        for (list, source) in [(settings, FlagSource::Settings), (env, FlagSource::Env)] {
+            for (flag, enabled) in parse_flag_list(list.unwrap_or_default()) {
+                set.values.retain(|(existing, _, _)| *existing != flag);
+                set.values.push((flag, enabled, source));
+            }
+        }
+        set.values
+            .sort_by_key(|(flag, _, _)| Flag::ALL.iter().position(|known| known == flag));
+        set
+    }
+
+    /// `None` when neither source mentions the flag.
+    pub(crate) fn get(&self, flag: Flag) -> Option<bool> {
+        self.values
+            .iter()
+            .find(|(existing, _, _)| *existing == flag)
+            .map(|(_, enabled, _)| *enabled)
+    }
+
+    /// One `name=on|off (source)` entry per set flag, for diagnostics.
+    pub(crate) fn describe(&self) -> Vec<String> {
+        self.values
+            .iter()
+            .map(|(flag, enabled, source)| {
+                format!(
+                    "{}={} ({})",
+                    flag.name(),
+                    if *enabled { "on" } else { "off" },
+                    source.label()
+                )
+            })
+            .collect()
+    }
+}
+
+/// Unknown names are ignored so a newer settings file cannot break an older streamer.
+fn parse_flag_list(list: &str) -> Vec<(Flag, bool)> {
+    list.split(',')
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                return None;
+            }
+            if let Some(name) = entry.strip_prefix('!') {
+                return Flag::from_name(name).map(|flag| (flag, false));
+            }
+            match entry.split_once('=') {
+                Some((name, value)) => {
+                    let enabled = match value.trim().to_ascii_lowercase().as_str() {
+                        "1" | "on" | "true" | "yes" => true,
+                        "0" | "off" | "false" | "no" => false,
+                        _ => return None,
+                    };
+                    Flag::from_name(name).map(|flag| (flag, enabled))
+                }
+                None => Flag::from_name(entry).map(|flag| (flag, true)),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn flags() -> &'static FlagSet {
+    static FLAGS: OnceLock<FlagSet> = OnceLock::new();
+    FLAGS.get_or_init(|| {
+        FlagSet::from_sources(
+            std::env::var(SETTINGS_FLAGS_ENV).ok().as_deref(),
+            std::env::var(FLAGS_ENV).ok().as_deref(),
+        )
+    })
+}
+
+/// Moves `target` to the front of an automatic priority list when the flag is
+/// on, removes it when off, and leaves the list alone when unset. A target the
+/// platform never lists is not added.
+#[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+pub(crate) fn apply_priority_flag<T: PartialEq>(
+    mut order: Vec<T>,
+    target: T,
+    flag: Option<bool>,
+) -> Vec<T> {
+    match flag {
+        Some(true) => {
+            if let Some(index) = order.iter().position(|item| *item == target) {
+                let item = order.remove(index);
+                order.insert(0, item);
+            }
+        }
+        Some(false) => order.retain(|item| *item != target),
+        None => {}
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_negations_and_values() {
+        assert_eq!(
+            parse_flag_list(" vulkan_video, !av1 ,zero-copy=off,unknown, av1=maybe"),
+            vec![
+                (Flag::VulkanVideo, true),
+                (Flag::Av1, false),
+                (Flag::ZeroCopy, false),
+            ]
+        );
+        assert!(parse_flag_list("").is_empty());
+    }
+
+    #[test]
+    fn env_overrides_settings_per_flag() {
+        let set = FlagSet::from_sources(Some("av1,zero-copy"), Some("!zero-copy"));
+        assert_eq!(set.get(Flag::Av1), Some(true));
+        assert_eq!(set.get(Flag::ZeroCopy), Some(false));
+        assert_eq!(set.get(Flag::VulkanVideo), None);
+        assert_eq!(
+            set.describe(),
+            vec![
+                "zero-copy=off (env)".to_owned(),
+                "av1=on (settings)".to_owned()
+            ]
+        );
+        assert!(FlagSet::from_sources(None, None).describe().is_empty());
+    }
+
+    #[test]
+    fn priority_flag_reorders_or_drops_the_target() {
+        let order = vec!["vaapi", "nvdec", "vulkan", "software"];
+        assert_eq!(
+            apply_priority_flag(order.clone(), "vulkan", Some(true)),
+            vec!["vulkan", "vaapi", "nvdec", "software"]
+        );
+        assert_eq!(
+            apply_priority_flag(order.clone(), "vulkan", Some(false)),
+            vec!["vaapi", "nvdec", "software"]
+        );
+        assert_eq!(apply_priority_flag(order.clone(), "vulkan", None), order);
+        assert_eq!(
+            apply_priority_flag(vec!["d3d11", "software"], "vulkan", Some(true)),
+            vec!["d3d11", "software"]
+        );
+    }
+}
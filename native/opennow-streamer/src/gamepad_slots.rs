@@ -0,0 +1,127 @@
+#![cfg_attr(
+    not(all(
+        feature = "gstreamer",
+        any(target_os = "windows", feature = "dualsense")
+    )),
+    allow(dead_code)
+)]
+
+//! GFN player slots shared by the native gamepad bridges.
+
+use crate::input::GAMEPAD_MAX_CONTROLLERS;
+use std::collections::HashMap;
+
+/// Hands out player slots the way the web client's `GamepadSlotAssigner`
+/// does: a pad keeps its slot while it stays connected, a reconnected pad
+/// returns to its previous slot when that is free, and any other pad takes
+/// the lowest free enabled slot. Pads are identified by a bridge-specific id
+/// so XInput and HID pads never share a slot.
+#[derive(Debug, Default)]
+pub(crate) struct GamepadSlotAssigner {
+    slots: [Option<String>; GAMEPAD_MAX_CONTROLLERS as usize],
+    last_slot_by_id: HashMap<String, u8>,
+}
+
+impl GamepadSlotAssigner {
+    /// Slot for a connected pad, claiming one when it has none. Bit `n` of
+    /// `enabled` allows slot `n`; a pad whose slot was disabled moves to
+    /// another enabled slot, or loses its slot when none is free.
+    pub(crate) fn claim(&mut self, id: &str, enabled: u8) -> Option<u8> {
+        if let Some(slot) = self.slot_of(id) {
+            if enabled & (1 << slot) != 0 {
+                return Some(slot);
+            }
+            self.slots[usize::from(slot)] = None;
+        }
+
+        let is_free =
+            |slot: u8| enabled & (1 << slot) != 0 && self.slots[usize::from(slot)].is_none();
+        let slot = self
+            .last_slot_by_id
+            .get(id)
+            .copied()
+            .filter(|&slot| is_free(slot))
+            .or_else(|| (0..GAMEPAD_MAX_CONTROLLERS).find(|&slot| is_free(slot)))?;
+        self.slots[usize::from(slot)] = Some(id.to_owned());
+        self.last_slot_by_id.insert(id.to_owned(), slot);
+        Some(slot)
+    }
+
+    /// Frees the pad's slot and returns it.
+    pub(crate) fn release(&mut self, id: &str) -> Option<u8> {
+        let slot = self.slot_of(id)?;
+        self.slots[usize::from(slot)] = None;
+        Some(slot)
+    }
+
+    pub(crate) fn slot_of(&self, id: &str) -> Option<u8> {
+        (0..GAMEPAD_MAX_CONTROLLERS)
+            .find(|&slot| self.slots[usize::from(slot)].as_deref() == Some(id))
+    }
+
+    /// Bit `n` is set while slot `n` has a pad.
+    pub(crate) fn bitmap(&self) -> u16 {
+        (0..GAMEPAD_MAX_CONTROLLERS)
+            .filter(|&slot| self.slots[usize::from(slot)].is_some())
+            .fold(0, |bitmap, slot| bitmap | (1 << slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: u8 = 0b1111;
+
+    #[test]
+    fn fills_enabled_slots_in_order() {
+        let mut slots = GamepadSlotAssigner::default();
+
+        assert_eq!(slots.claim("xinput:2", 0b1101), Some(0));
+        assert_eq!(slots.claim("xinput:3", 0b1101), Some(2));
+        assert_eq!(slots.claim("hid:a", 0b1101), Some(3));
+        assert_eq!(slots.claim("hid:b", 0b1101), None);
+        assert_eq!(slots.bitmap(), 0b1101);
+    }
+
+    #[test]
+    fn keeps_slots_across_hotplug() {
+        let mut slots = GamepadSlotAssigner::default();
+        slots.claim("xinput:0", ALL);
+        slots.claim("xinput:1", ALL);
+
+        assert_eq!(slots.release("xinput:0"), Some(0));
+        assert_eq!(slots.claim("xinput:1", ALL), Some(1));
+        assert_eq!(slots.claim("hid:a", ALL), Some(0));
+        assert_eq!(slots.claim("xinput:0", ALL), Some(2));
+
+        slots.release("xinput:0");
+        slots.release("hid:a");
+        assert_eq!(slots.claim("xinput:0", ALL), Some(2));
+        assert_eq!(slots.claim("hid:a", ALL), Some(0));
+        assert_eq!(slots.release("hid:b"), None);
+    }
+
+    #[test]
+    fn never_gives_xinput_and_hid_pads_the_same_slot() {
+        let mut slots = GamepadSlotAssigner::default();
+
+        assert_eq!(slots.claim("hid:/dev/hidraw3", ALL), Some(0));
+        assert_eq!(slots.claim("xinput:0", ALL), Some(1));
+        assert_eq!(slots.claim("hid:/dev/hidraw3", ALL), Some(0));
+        assert_eq!(slots.claim("xinput:0", ALL), Some(1));
+        assert_eq!(slots.bitmap(), 0b0011);
+    }
+
+    #[test]
+    fn moves_pads_off_slots_that_were_disabled() {
+        let mut slots = GamepadSlotAssigner::default();
+        slots.claim("xinput:0", ALL);
+        slots.claim("xinput:1", ALL);
+
+        assert_eq!(slots.claim("xinput:0", 0b1110), Some(2));
+        assert_eq!(slots.claim("xinput:1", 0b0100), None);
+        assert_eq!(slots.slot_of("xinput:1"), None);
+        assert_eq!(slots.bitmap(), 0b0100);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::gstreamer_backend::send_log;
+use crate::gstreamer_pipeline::set_property_if_supported;
+use crate::protocol::{AudioOutputSettings, Event};
+use gst::prelude::*;
+use gstreamer as gst;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+const AUDIO_OUTPUT_SAMPLE_RATE: u64 = 48_000;
+/// Device periods per requested buffer; two lets one period play while the
+/// next one is written.
+const AUDIO_OUTPUT_PERIODS: u64 = 2;
+const MIN_AUDIO_PERIOD_US: i64 = 1_000;
+
+#[derive(Debug, Default)]
+struct AudioOutputState {
+    settings: AudioOutputSettings,
+    clock: Option<gst::Clock>,
+    played_base_ns: Option<u64>,
+    written_ns: u64,
+    latency_ms: Option<f64>,
+    underruns: u64,
+    codec: Option<String>,
+    sample_rate: Option<u32>,
+    channels: Option<u32>,
+    volume: Option<f64>,
+    volume_element: Option<gst::Element>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct AudioOutputSnapshot {
+    pub(crate) latency_ms: Option<f64>,
+    pub(crate) underruns: u64,
+    pub(crate) codec: Option<String>,
+    pub(crate) sample_rate: Option<u32>,
+    pub(crate) channels: Option<u32>,
+}
+
+/// Applies the user's output buffering to the audio sink and measures how much
+/// audio is queued ahead of the device, from the sink's audio clock (samples
+/// actually played) against the buffer durations handed to it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AudioOutputMonitor {
+    state: Arc<Mutex<AudioOutputState>>,
+}
+
+impl AudioOutputMonitor {
+    pub(crate) fn configure(&self, settings: AudioOutputSettings) {
+        if let Ok(mut state) = self.state.lock() {
+            // Volume is a client preference, not part of the session.
+            *state = AudioOutputState {
+                settings,
+                volume: state.volume,
+                ..AudioOutputState::default()
+            };
+        }
+    }
+
+    pub(crate) fn settings(&self) -> AudioOutputSettings {
+        self.state
+            .lock()
+            .map(|state| state.settings)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn snapshot(&self) -> AudioOutputSnapshot {
+        let Ok(state) = self.state.lock() else {
+            return AudioOutputSnapshot::default();
+        };
+        AudioOutputSnapshot {
+            latency_ms: state.latency_ms,
+            underruns: state.underruns,
+            codec: state.codec.clone(),
+            sample_rate: state.sample_rate,
+            channels: state.channels,
+        }
+    }
+
+    /// RTP encoding name of the audio stream, e.g. `OPUS`.
+    pub(crate) fn set_codec(&self, codec: String) {
+        if let Ok(mut state) = self.state.lock() {
+            state.codec = Some(codec);
+        }
+    }
+
+    /// Format the decoder hands to the output chain, before any conversion
+    /// to the device format.
+    pub(crate) fn set_decoded_format(&self, pad: &gst::Pad) {
+        let caps = pad.current_caps().unwrap_or_else(|| pad.query_caps(None));
+        let Some(structure) = caps.structure(0) else {
+            return;
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.sample_rate = structure
+                .get::<i32>("rate")
+                .ok()
+                .and_then(|rate| u32::try_from(rate).ok());
+            state.channels = structure
+                .get::<i32>("channels")
+                .ok()
+                .and_then(|channels| u32::try_from(channels).ok());
+        }
+    }
+
+    /// Client-side output gain, applied to the decoded stream ahead of the
+    /// sink so the OS mixer level is left alone.
+    pub(crate) fn set_volume(&self, volume: f64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.volume = Some(volume);
+        if let Some(element) = state.volume_element.as_ref() {
+            element.set_property("volume", volume);
+        }
+    }
+
+    pub(crate) fn set_volume_element(&self, element: gst::Element) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        element.set_property("volume", state.volume.unwrap_or(1.0));
+        state.volume_element = Some(element);
+    }
+
+    pub(crate) fn watch_sink(&self, sink: &gst::Element, event_sender: &Option<Sender<Event>>) {
+        let settings = self.settings();
+        if let Some(bin) = sink.downcast_ref::<gst::Bin>() {
+            // autoaudiosink only creates the device sink when it starts.
+            let sender = event_sender.clone();
+            bin.connect_element_added(move |_bin, child| {
+                tune_audio_sink(child, settings, &sender);
+            });
+        } else {
+            tune_audio_sink(sink, settings, event_sender);
+        }
+
+        let Some(sink_pad) = sink.static_pad("sink") else {
+            return;
+        };
+        let monitor = self.clone();
+        let element = sink.clone();
+        sink_pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+            if let Some(buffer) = info.buffer() {
+                monitor.record_buffer(&element, buffer.duration());
+            }
+            gst::PadProbeReturn::Ok
+        });
+    }
+
+    fn record_buffer(&self, element: &gst::Element, duration: Option<gst::ClockTime>) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.clock.is_none() {
+            state.clock = find_audio_base_sink(element).and_then(|sink| sink.provide_clock());
+        }
+        let Some(played_ns) = state
+            .clock
+            .as_ref()
+            .map(|clock| clock.internal_time().nseconds())
+        else {
+            return;
+        };
+        let played_base_ns = *state.played_base_ns.get_or_insert(played_ns);
+        let played_ns = played_ns.saturating_sub(played_base_ns);
+        if is_output_underrun(state.written_ns, played_ns) {
+            state.underruns += 1;
+        }
+        let (written_ns, queued_ns) = queued_output_ns(state.written_ns, played_ns);
+        state.latency_ms = Some(queued_ns as f64 / 1_000_000.0);
+        state.written_ns = written_ns + duration.map(|value| value.nseconds()).unwrap_or_default();
+    }
+}
+
+/// Picks the element that ends the decoded audio chain. Exclusive mode needs
+/// the classic WASAPI sink; everything else keeps autoaudiosink's choice.
+pub(crate) fn audio_sink_factory(settings: AudioOutputSettings) -> &'static str {
+    #[cfg(target_os = "windows")]
+    {
+        if settings.exclusive && gst::ElementFactory::find("wasapisink").is_some() {
+            return "wasapisink";
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = settings;
+    "autoaudiosink"
+}
+
+fn tune_audio_sink(
+    element: &gst::Element,
+    settings: AudioOutputSettings,
+    event_sender: &Option<Sender<Event>>,
+) {
+    if element.find_property("buffer-time").is_none() {
+        return;
+    }
+    if !settings.exclusive && settings.buffer_ms == 0 {
+        return;
+    }
+    set_property_if_supported(element, "exclusive", settings.exclusive);
+    // WASAPI sinks drop to the device's minimum period in either mode.
+    set_property_if_supported(element, "low-latency", true);
+    if settings.buffer_ms > 0 {
+        let (buffer_time_us, latency_time_us) = audio_buffer_times_us(settings.buffer_ms);
+        set_property_if_supported(element, "buffer-time", buffer_time_us);
+        set_property_if_supported(element, "latency-time", latency_time_us);
+        // pulsesink forwards stream properties to PipeWire, which sizes the
+        // graph quantum from node.latency.
+        if element.find_property("stream-properties").is_some() {
+            let properties = gst::Structure::builder("props")
+                .field("node.latency", pipewire_node_latency(settings.buffer_ms))
+                .build();
+            element.set_property("stream-properties", properties);
+        }
+    }
+    let factory = element
+        .factory()
+        .map(|factory| factory.name().to_string())
+        .unwrap_or_default();
+    let mode = if settings.exclusive {
+        "exclusive"
+    } else {
+        "shared"
+    };
+    let buffer = match settings.buffer_ms {
+        0 => "system default".to_owned(),
+        buffer_ms => format!("{buffer_ms} ms"),
+    };
+    send_log(
+        event_sender,
+        "info",
+        format!("Audio output {factory}: {mode} mode, buffer {buffer}."),
+    );
+}
+
+/// Clamps a renderer volume to the 0..=1 range; anything unparseable plays at
+/// full volume rather than silently muting the stream.
+pub(crate) fn normalize_output_volume(volume: f64) -> f64 {
+    if volume.is_finite() {
+        volume.clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Real audio sink behind `element`, descending into autoaudiosink's bin.
+pub(crate) fn find_audio_base_sink(element: &gst::Element) -> Option<gst::Element> {
+    if element.find_property("slave-method").is_some() {
+        return Some(element.clone());
+    }
+    let bin = element.downcast_ref::<gst::Bin>()?;
+    bin.iterate_recurse()
+        .into_iter()
+        .flatten()
+        .find(|child| child.find_property("slave-method").is_some())
+}
+
+/// Ring buffer size and period for audiobasesink, in microseconds.
+pub(crate) fn audio_buffer_times_us(buffer_ms: u32) -> (i64, i64) {
+    let buffer_time_us = i64::from(buffer_ms) * 1_000;
+    let latency_time_us = (buffer_time_us / AUDIO_OUTPUT_PERIODS as i64).max(MIN_AUDIO_PERIOD_US);
+    (buffer_time_us.max(latency_time_us), latency_time_us)
+}
+
+pub(crate) fn pipewire_node_latency(buffer_ms: u32) -> String {
+    let frames = u64::from(buffer_ms) * AUDIO_OUTPUT_SAMPLE_RATE / 1_000 / AUDIO_OUTPUT_PERIODS;
+    format!("{}/{AUDIO_OUTPUT_SAMPLE_RATE}", frames.max(1))
+}
+
+/// The device played past everything written before this buffer arrived.
+pub(crate) fn is_output_underrun(written_ns: u64, played_ns: u64) -> bool {
+    written_ns > 0 && played_ns > written_ns
+}
+
+/// Audio handed to the sink but not yet played. An underrun plays silence the
+/// stream never wrote, so the written total is pulled up to what was played.
+pub(crate) fn queued_output_ns(written_ns: u64, played_ns: u64) -> (u64, u64) {
+    let written_ns = written_ns.max(played_ns);
+    (written_ns, written_ns - played_ns)
+}
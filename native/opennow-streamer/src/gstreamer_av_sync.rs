@@ -0,0 +1,236 @@
+use crate::gstreamer_audio_output::find_audio_base_sink;
+use crate::gstreamer_backend::send_log;
+use crate::gstreamer_config::AvSyncMode;
+use crate::gstreamer_pipeline::{set_property_from_str_if_supported, set_property_if_supported};
+use crate::protocol::Event;
+use gst::prelude::*;
+use gstreamer as gst;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Offsets inside this window are below what viewers notice as lip-sync error.
+const AV_SYNC_DEADBAND_MS: f64 = 15.0;
+/// Largest ts-offset change per adjustment, small enough that the audio sink's
+/// skew slaving absorbs it without an audible jump.
+const AV_SYNC_MAX_STEP_MS: f64 = 5.0;
+/// Audio is never held back further than this to chase a stalled video path.
+const AV_SYNC_MAX_AUDIO_DELAY_MS: f64 = 120.0;
+const AV_SYNC_SMOOTHING: f64 = 0.1;
+const AV_SYNC_ADJUST_INTERVAL: Duration = Duration::from_secs(1);
+/// Drift the audio sink tolerates before resampling (skew) kicks in, in µs.
+const AUDIO_DRIFT_TOLERANCE_US: i64 = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AvSyncStream {
+    Audio,
+    Video,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct AvSyncSnapshot {
+    /// Smoothed audio-minus-video presentation delay; positive means audio trails.
+    pub(crate) offset_ms: Option<f64>,
+    pub(crate) audio_correction_ms: f64,
+}
+
+#[derive(Debug)]
+struct AvSyncState {
+    mode: AvSyncMode,
+    video_delay_ms: Option<f64>,
+    audio_delay_ms: Option<f64>,
+    offset_ms: Option<f64>,
+    correction_ms: f64,
+    last_adjust_at: Instant,
+    audio_sink: Option<gst::Element>,
+}
+
+impl Default for AvSyncState {
+    fn default() -> Self {
+        Self {
+            mode: AvSyncMode::Off,
+            video_delay_ms: None,
+            audio_delay_ms: None,
+            offset_ms: None,
+            correction_ms: 0.0,
+            last_adjust_at: Instant::now(),
+            audio_sink: None,
+        }
+    }
+}
+
+/// Compares how late audio and video buffers reach their sinks relative to
+/// their running time. Both running times come from rtpbin, which maps RTP
+/// timestamps onto a common timeline using the RTCP sender reports, so the
+/// difference is the lip-sync error introduced by the two sink chains.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AvSyncMonitor {
+    state: Arc<Mutex<AvSyncState>>,
+}
+
+impl AvSyncMonitor {
+    pub(crate) fn configure(&self, mode: AvSyncMode) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = AvSyncState {
+                mode,
+                ..AvSyncState::default()
+            };
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Option<AvSyncSnapshot> {
+        let state = self.state.lock().ok()?;
+        (state.mode != AvSyncMode::Off).then_some(AvSyncSnapshot {
+            offset_ms: state.offset_ms,
+            audio_correction_ms: state.correction_ms,
+        })
+    }
+
+    pub(crate) fn watch_sink(
+        &self,
+        sink: &gst::Element,
+        stream: AvSyncStream,
+        event_sender: &Option<Sender<Event>>,
+    ) {
+        let mode = self
+            .state
+            .lock()
+            .map(|state| state.mode)
+            .unwrap_or(AvSyncMode::Off);
+        if mode == AvSyncMode::Off {
+            return;
+        }
+        let Some(sink_pad) = sink.static_pad("sink") else {
+            return;
+        };
+
+        let monitor = self.clone();
+        let element = sink.clone();
+        let sender = event_sender.clone();
+        let audio_sink_prepared = Arc::new(AtomicBool::new(false));
+        sink_pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+            let Some(buffer) = info.buffer() else {
+                return gst::PadProbeReturn::Ok;
+            };
+            let Some(delay_ms) = sink_entry_delay_ms(pad, &element, buffer) else {
+                return gst::PadProbeReturn::Ok;
+            };
+            if stream == AvSyncStream::Audio
+                && mode == AvSyncMode::Correct
+                && !audio_sink_prepared.swap(true, Ordering::Relaxed)
+            {
+                monitor.prepare_audio_sink(&element, &sender);
+            }
+            monitor.record_delay(stream, delay_ms, &sender);
+            gst::PadProbeReturn::Ok
+        });
+    }
+
+    /// Switches the real audio sink (autoaudiosink's child) to clock-synced
+    /// playback so ts-offset can hold it back, slaving with skew so clock drift
+    /// is corrected by small sample-rate adjustments instead of jumps.
+    fn prepare_audio_sink(&self, element: &gst::Element, event_sender: &Option<Sender<Event>>) {
+        let Some(sink) = find_audio_base_sink(element) else {
+            send_log(
+                event_sender,
+                "warn",
+                "A/V sync correction unavailable: no audio base sink found.".to_owned(),
+            );
+            return;
+        };
+        set_property_if_supported(&sink, "sync", true);
+        set_property_from_str_if_supported(&sink, "slave-method", "skew");
+        set_property_if_supported(&sink, "drift-tolerance", AUDIO_DRIFT_TOLERANCE_US);
+        if let Ok(mut state) = self.state.lock() {
+            state.audio_sink = Some(sink);
+        }
+        send_log(
+            event_sender,
+            "info",
+            "A/V sync correction enabled on the audio sink.".to_owned(),
+        );
+    }
+
+    fn record_delay(
+        &self,
+        stream: AvSyncStream,
+        delay_ms: f64,
+        event_sender: &Option<Sender<Event>>,
+    ) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        match stream {
+            AvSyncStream::Video => {
+                state.video_delay_ms = Some(smooth_delay_ms(state.video_delay_ms, delay_ms));
+                return;
+            }
+            AvSyncStream::Audio => {
+                // A synced sink waits out its ts-offset after this probe, so the
+                // audio is presented no earlier than the applied correction.
+                let presented_ms = delay_ms.max(state.correction_ms);
+                state.audio_delay_ms = Some(smooth_delay_ms(state.audio_delay_ms, presented_ms));
+            }
+        }
+
+        let (Some(audio_ms), Some(video_ms)) = (state.audio_delay_ms, state.video_delay_ms) else {
+            return;
+        };
+        let offset_ms = audio_ms - video_ms;
+        state.offset_ms = Some(offset_ms);
+
+        if state.mode != AvSyncMode::Correct
+            || state.last_adjust_at.elapsed() < AV_SYNC_ADJUST_INTERVAL
+        {
+            return;
+        }
+        state.last_adjust_at = Instant::now();
+        let next_ms = next_audio_correction_ms(state.correction_ms, offset_ms);
+        if (next_ms - state.correction_ms).abs() < f64::EPSILON {
+            return;
+        }
+        state.correction_ms = next_ms;
+        if let Some(sink) = &state.audio_sink {
+            set_property_if_supported(sink, "ts-offset", (next_ms * 1_000_000.0) as i64);
+        }
+        send_log(
+            event_sender,
+            "debug",
+            format!("A/V offset {offset_ms:.1} ms; audio delay now {next_ms:.0} ms."),
+        );
+    }
+}
+
+/// Running time elapsed between a buffer's scheduled presentation and its
+/// arrival at the sink pad.
+fn sink_entry_delay_ms(
+    pad: &gst::Pad,
+    element: &gst::Element,
+    buffer: &gst::BufferRef,
+) -> Option<f64> {
+    let pts = buffer.pts()?;
+    let segment_event = pad.sticky_event::<gst::event::Segment>(0)?;
+    let segment = segment_event.segment().downcast_ref::<gst::ClockTime>()?;
+    let buffer_running_time = segment.to_running_time(pts)?;
+    let now = element.current_running_time()?;
+    let delay_ns = now.nseconds() as i128 - buffer_running_time.nseconds() as i128;
+    Some(delay_ns as f64 / 1_000_000.0)
+}
+
+pub(crate) fn smooth_delay_ms(previous: Option<f64>, sample_ms: f64) -> f64 {
+    match previous {
+        Some(previous) => previous + (sample_ms - previous) * AV_SYNC_SMOOTHING,
+        None => sample_ms,
+    }
+}
+
+/// Moves the audio hold-back toward cancelling `offset_ms`, at most one step
+/// per call and never outside `0..=AV_SYNC_MAX_AUDIO_DELAY_MS`.
+pub(crate) fn next_audio_correction_ms(current_ms: f64, offset_ms: f64) -> f64 {
+    if offset_ms.abs() <= AV_SYNC_DEADBAND_MS {
+        return current_ms;
+    }
+    let step = (-offset_ms).clamp(-AV_SYNC_MAX_STEP_MS, AV_SYNC_MAX_STEP_MS);
+    (current_ms + step).clamp(0.0, AV_SYNC_MAX_AUDIO_DELAY_MS)
+}
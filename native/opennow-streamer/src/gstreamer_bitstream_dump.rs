@@ -0,0 +1,311 @@
+use crate::bitstream_dump::{AnnexBRewriter, RtpTraceFormatter};
+use crate::gstreamer_backend::send_log;
+use crate::gstreamer_config::{
+    NATIVE_BITSTREAM_DUMP_ENV, NATIVE_BITSTREAM_DUMP_MAX_MB_ENV, NATIVE_BITSTREAM_DUMP_RTP_ENV,
+};
+use crate::protocol::Event;
+use crate::sei::SeiCodec;
+use gst::prelude::*;
+use gstreamer as gst;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_BITSTREAM_DUMP_MAX_MB: u64 = 256;
+const RTP_HEADER_LEN: usize = 12;
+/// Buffers waiting for the writer thread. Past this the probes drop data
+/// rather than hold up the streaming thread on disk I/O.
+const DUMP_QUEUE_DEPTH: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BitstreamDumpConfig {
+    pub(crate) directory: PathBuf,
+    pub(crate) include_rtp: bool,
+    pub(crate) max_bytes: u64,
+}
+
+/// `OPENNOW_NATIVE_BITSTREAM_DUMP` takes a directory, or `1` for the system
+/// temp directory. The size limit covers the elementary stream and the RTP
+/// trace together.
+pub(crate) fn parse_bitstream_dump_config(
+    directory: Option<&str>,
+    include_rtp: Option<&str>,
+    max_mb: Option<&str>,
+) -> Option<BitstreamDumpConfig> {
+    let directory = directory.map(str::trim).filter(|value| !value.is_empty())?;
+    let directory = match directory.to_ascii_lowercase().as_str() {
+        "0" | "false" | "no" | "off" => return None,
+        "1" | "true" | "yes" | "on" => std::env::temp_dir().join("opennow-bitstream"),
+        _ => PathBuf::from(directory),
+    };
+    let include_rtp = include_rtp.is_some_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    });
+    let max_mb = max_mb
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|value| *value > 0)
+        .unwrap_or(DEFAULT_BITSTREAM_DUMP_MAX_MB);
+    Some(BitstreamDumpConfig {
+        directory,
+        include_rtp,
+        max_bytes: max_mb.saturating_mul(1024 * 1024),
+    })
+}
+
+pub(crate) fn bitstream_dump_config() -> Option<BitstreamDumpConfig> {
+    parse_bitstream_dump_config(
+        std::env::var(NATIVE_BITSTREAM_DUMP_ENV).ok().as_deref(),
+        std::env::var(NATIVE_BITSTREAM_DUMP_RTP_ENV).ok().as_deref(),
+        std::env::var(NATIVE_BITSTREAM_DUMP_MAX_MB_ENV)
+            .ok()
+            .as_deref(),
+    )
+}
+
+pub(crate) fn bitstream_dump_extension(codec: &str) -> &'static str {
+    match codec.to_ascii_uppercase().as_str() {
+        "H265" | "HEVC" => "h265",
+        "H264" => "h264",
+        "AV1" => "obu",
+        _ => "bin",
+    }
+}
+
+enum DumpChunk {
+    /// Depayloaded data with the caps it was negotiated under.
+    Elementary {
+        data: Vec<u8>,
+        caps: Option<gst::Caps>,
+    },
+    Rtp {
+        header: [u8; RTP_HEADER_LEN],
+        size: usize,
+        elapsed_us: u128,
+    },
+}
+
+struct DumpFiles {
+    elementary: BufWriter<File>,
+    rtp_trace: Option<BufWriter<File>>,
+    written: u64,
+    max_bytes: u64,
+}
+
+impl DumpFiles {
+    /// Returns false once the size limit has been reached.
+    fn write(&mut self, rtp: bool, bytes: &[u8]) -> bool {
+        if self.written.saturating_add(bytes.len() as u64) > self.max_bytes {
+            return false;
+        }
+        let writer = if rtp {
+            match self.rtp_trace.as_mut() {
+                Some(trace) => trace,
+                None => return true,
+            }
+        } else {
+            &mut self.elementary
+        };
+        if writer.write_all(bytes).is_ok() {
+            self.written += bytes.len() as u64;
+        }
+        true
+    }
+
+    fn flush(&mut self) {
+        let _ = self.elementary.flush();
+        if let Some(trace) = self.rtp_trace.as_mut() {
+            let _ = trace.flush();
+        }
+    }
+}
+
+fn create_dump_file(directory: &Path, name: &str) -> Result<BufWriter<File>, String> {
+    let path = directory.join(name);
+    File::create(&path)
+        .map(BufWriter::new)
+        .map_err(|error| format!("{}: {error}", path.display()))
+}
+
+/// Runs until the probes holding the other end of the channel are dropped
+/// with the pipeline.
+fn run_dump_writer(
+    receiver: Receiver<DumpChunk>,
+    mut files: DumpFiles,
+    codec: Option<SeiCodec>,
+    dropped: Arc<AtomicU64>,
+    event_sender: Option<Sender<Event>>,
+) {
+    let mut rewriter = AnnexBRewriter::new(codec);
+    let mut trace = RtpTraceFormatter::default();
+    let mut current_caps: Option<gst::Caps> = None;
+    let mut annex_b = Vec::new();
+    let mut limit_reached = false;
+    for chunk in receiver {
+        if limit_reached {
+            continue;
+        }
+        let written = match chunk {
+            DumpChunk::Elementary { data, caps } => {
+                if caps.is_some() && caps != current_caps {
+                    let structure = caps.as_ref().and_then(|caps| caps.structure(0));
+                    let codec_data = structure
+                        .and_then(|structure| structure.get::<gst::Buffer>("codec_data").ok());
+                    let codec_data = codec_data
+                        .as_ref()
+                        .and_then(|buffer| buffer.map_readable().ok());
+                    rewriter.set_format(
+                        structure.and_then(|structure| structure.get::<&str>("stream-format").ok()),
+                        codec_data.as_ref().map(|map| map.as_slice()),
+                    );
+                    current_caps = caps;
+                }
+                annex_b.clear();
+                rewriter.rewrite(&data, &mut annex_b);
+                files.write(false, &annex_b)
+            }
+            DumpChunk::Rtp {
+                header,
+                size,
+                elapsed_us,
+            } => match trace.line(&header, size, elapsed_us) {
+                Some(line) => files.write(true, line.as_bytes()),
+                None => true,
+            },
+        };
+        if !written {
+            limit_reached = true;
+            files.flush();
+            send_log(
+                &event_sender,
+                "warn",
+                "Bitstream dump reached its size limit; further data is not written.".to_owned(),
+            );
+        }
+    }
+    files.flush();
+    let dropped = dropped.load(Ordering::Relaxed);
+    if dropped > 0 {
+        send_log(
+            &event_sender,
+            "warn",
+            format!("Bitstream dump skipped {dropped} buffers while the disk fell behind; the dump has gaps."),
+        );
+    }
+}
+
+/// Tees the depayloaded elementary stream (and optionally an RTP header trace)
+/// to disk so decoder bugs can be replayed offline with `gst-launch-1.0`.
+/// The probes only copy and queue; a writer thread does the rewriting and
+/// file I/O.
+pub(crate) fn start_bitstream_dump(
+    depayloader: &gst::Element,
+    codec: &str,
+    event_sender: &Option<Sender<Event>>,
+) {
+    let Some(config) = bitstream_dump_config() else {
+        return;
+    };
+    let (Some(sink_pad), Some(src_pad)) = (
+        depayloader.static_pad("sink"),
+        depayloader.static_pad("src"),
+    ) else {
+        return;
+    };
+
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let base_name = format!("opennow-{stamp}");
+    let extension = bitstream_dump_extension(codec);
+    let files = fs::create_dir_all(&config.directory)
+        .map_err(|error| format!("{}: {error}", config.directory.display()))
+        .and_then(|_| {
+            let elementary =
+                create_dump_file(&config.directory, &format!("{base_name}.{extension}"))?;
+            let rtp_trace = config
+                .include_rtp
+                .then(|| create_dump_file(&config.directory, &format!("{base_name}.rtp.txt")))
+                .transpose()?;
+            Ok(DumpFiles {
+                elementary,
+                rtp_trace,
+                written: 0,
+                max_bytes: config.max_bytes,
+            })
+        });
+    let files = match files {
+        Ok(files) => files,
+        Err(error) => {
+            send_log(
+                event_sender,
+                "warn",
+                format!("Bitstream dump disabled; could not create dump file {error}."),
+            );
+            return;
+        }
+    };
+    send_log(
+        event_sender,
+        "info",
+        format!(
+            "Dumping {codec} bitstream to {} as {base_name}.{extension} (limit {} MiB, RTP trace {}).",
+            config.directory.display(),
+            config.max_bytes / (1024 * 1024),
+            if config.include_rtp { "on" } else { "off" }
+        ),
+    );
+
+    let (sender, receiver) = mpsc::sync_channel::<DumpChunk>(DUMP_QUEUE_DEPTH);
+    let dropped = Arc::new(AtomicU64::new(0));
+    {
+        let codec = SeiCodec::from_encoding(codec);
+        let dropped = dropped.clone();
+        let event_sender = event_sender.clone();
+        thread::spawn(move || run_dump_writer(receiver, files, codec, dropped, event_sender));
+    }
+    let queue = move |sender: &SyncSender<DumpChunk>, dropped: &AtomicU64, chunk| {
+        if sender.try_send(chunk).is_err() {
+            dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    };
+
+    if config.include_rtp {
+        let sender = sender.clone();
+        let dropped = dropped.clone();
+        let started = Instant::now();
+        sink_pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+            if let Some(map) = info.buffer().and_then(|buffer| buffer.map_readable().ok()) {
+                if let Some(header) = map.get(..RTP_HEADER_LEN) {
+                    let mut fixed = [0; RTP_HEADER_LEN];
+                    fixed.copy_from_slice(header);
+                    let chunk = DumpChunk::Rtp {
+                        header: fixed,
+                        size: map.len(),
+                        elapsed_us: started.elapsed().as_micros(),
+                    };
+                    queue(&sender, &dropped, chunk);
+                }
+            }
+            gst::PadProbeReturn::Ok
+        });
+    }
+    src_pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+        if let Some(map) = info.buffer().and_then(|buffer| buffer.map_readable().ok()) {
+            let chunk = DumpChunk::Elementary {
+                data: map.as_slice().to_vec(),
+                caps: pad.current_caps(),
+            };
+            queue(&sender, &dropped, chunk);
+        }
+        gst::PadProbeReturn::Ok
+    });
+}
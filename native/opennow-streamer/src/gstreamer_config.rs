@@ -1,10 +1,30 @@
+use crate::flags::{flags, Flag};
+use std::path::PathBuf;
+
 pub(crate) const EXTERNAL_RENDERER_ENV: &str = "OPENNOW_NATIVE_EXTERNAL_RENDERER";
 pub(crate) const NATIVE_VIDEO_API_ENV: &str = "OPENNOW_NATIVE_VIDEO_API";
 pub(crate) const NATIVE_VIDEO_BACKEND_ENV: &str = "OPENNOW_NATIVE_VIDEO_BACKEND";
 pub(crate) const NATIVE_ZERO_COPY_ENV: &str = "OPENNOW_NATIVE_ZERO_COPY";
 pub(crate) const NATIVE_PRESENT_MAX_FPS_ENV: &str = "OPENNOW_NATIVE_PRESENT_MAX_FPS";
 pub(crate) const NATIVE_D3D_FULLSCREEN_ENV: &str = "OPENNOW_NATIVE_D3D_FULLSCREEN";
+pub(crate) const NATIVE_SOFTWARE_DECODE_THREADS_ENV: &str =
+    "OPENNOW_NATIVE_SOFTWARE_DECODE_THREADS";
+pub(crate) const NATIVE_SOFTWARE_DECODE_LOW_DELAY_ENV: &str =
+    "OPENNOW_NATIVE_SOFTWARE_DECODE_LOW_DELAY";
+pub(crate) const NATIVE_SOFTWARE_GPU_CONVERT_ENV: &str = "OPENNOW_NATIVE_SOFTWARE_GPU_CONVERT";
+pub(crate) const NATIVE_BITSTREAM_DUMP_ENV: &str = "OPENNOW_NATIVE_BITSTREAM_DUMP";
+pub(crate) const NATIVE_BITSTREAM_DUMP_RTP_ENV: &str = "OPENNOW_NATIVE_BITSTREAM_DUMP_RTP";
+pub(crate) const NATIVE_BITSTREAM_DUMP_MAX_MB_ENV: &str = "OPENNOW_NATIVE_BITSTREAM_DUMP_MAX_MB";
+pub(crate) const NATIVE_JITTER_BUFFER_MS_ENV: &str = "OPENNOW_NATIVE_JITTER_BUFFER_MS";
+pub(crate) const NATIVE_AV_SYNC_ENV: &str = "OPENNOW_NATIVE_AV_SYNC";
+pub(crate) const NATIVE_INPUT_RECORD_ENV: &str = "OPENNOW_NATIVE_INPUT_RECORD";
+pub(crate) const NATIVE_INPUT_REPLAY_ENV: &str = "OPENNOW_NATIVE_INPUT_REPLAY";
 pub(crate) const PRESENT_LIMITER_AUTO_SENTINEL: u32 = u32::MAX;
+const DEFAULT_JITTER_BUFFER_MS: u32 = 2;
+const MAX_JITTER_BUFFER_MS: u32 = 50;
+/// Low-latency mode hands packets to the depayloader as soon as they arrive.
+pub(crate) const LOW_LATENCY_JITTER_BUFFER_MS: u32 = 0;
+const MAX_SOFTWARE_DECODE_THREADS: u32 = 32;
 
 pub(crate) fn use_external_renderer_window() -> bool {
     std::env::var(EXTERNAL_RENDERER_ENV)
@@ -17,6 +37,22 @@ pub(crate) fn use_external_renderer_window() -> bool {
         .unwrap_or(true)
 }
 
+/// File that every input event sent to the server is appended to.
+pub(crate) fn input_record_path() -> Option<PathBuf> {
+    env_path(NATIVE_INPUT_RECORD_ENV)
+}
+
+/// Recording to play into the encoder once the input handshake completes.
+pub(crate) fn input_replay_path() -> Option<PathBuf> {
+    env_path(NATIVE_INPUT_REPLAY_ENV)
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .map(PathBuf::from)
+        .filter(|path| !path.as_os_str().is_empty())
+}
+
 pub(crate) fn requested_video_backend() -> String {
     std::env::var(NATIVE_VIDEO_BACKEND_ENV)
         .or_else(|_| std::env::var(NATIVE_VIDEO_API_ENV))
@@ -24,16 +60,127 @@ pub(crate) fn requested_video_backend() -> String {
         .to_ascii_lowercase()
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ZeroCopyMode {
+    /// Prefer GPU memory between decoder and sink, but allow system memory when
+    /// caps negotiation cannot settle on it.
+    Auto,
+    /// Require GPU memory; negotiation fails instead of copying through the CPU.
+    Forced,
+    /// No memory capsfilter; decoder and sink negotiate on their own. The
+    /// default, since a capsfilter can fail startup while live caps settle.
+    Disabled,
+}
+
+pub(crate) fn parse_zero_copy_mode(value: Option<&str>) -> ZeroCopyMode {
+    match value
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("1" | "true" | "yes" | "forced") => ZeroCopyMode::Forced,
+        Some("auto" | "prefer") => ZeroCopyMode::Auto,
+        _ => ZeroCopyMode::Disabled,
+    }
+}
+
+pub(crate) fn zero_copy_mode() -> ZeroCopyMode {
+    match std::env::var(NATIVE_ZERO_COPY_ENV).ok().as_deref() {
+        Some(value) => parse_zero_copy_mode(Some(value)),
+        None => match flags().get(Flag::ZeroCopy) {
+            Some(true) => ZeroCopyMode::Forced,
+            Some(false) | None => ZeroCopyMode::Disabled,
+        },
+    }
+}
+
 pub(crate) fn zero_copy_requested() -> bool {
-    matches!(
-        std::env::var(NATIVE_ZERO_COPY_ENV)
-            .unwrap_or_else(|_| "auto".to_owned())
-            .to_ascii_lowercase()
-            .as_str(),
-        "1" | "true" | "yes" | "forced"
+    zero_copy_mode() == ZeroCopyMode::Forced
+}
+
+/// Returns 0 to let the decoder pick a thread count from the CPU topology.
+pub(crate) fn software_decode_threads() -> u32 {
+    parse_software_decode_threads(
+        std::env::var(NATIVE_SOFTWARE_DECODE_THREADS_ENV)
+            .ok()
+            .as_deref(),
     )
 }
 
+pub(crate) fn parse_software_decode_threads(value: Option<&str>) -> u32 {
+    value
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .map(|threads| threads.min(MAX_SOFTWARE_DECODE_THREADS))
+        .unwrap_or(0)
+}
+
+pub(crate) fn software_decode_low_delay() -> bool {
+    std::env::var(NATIVE_SOFTWARE_DECODE_LOW_DELAY_ENV)
+        .map(|value| {
+            !matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "0" | "false" | "no" | "off"
+            )
+        })
+        .unwrap_or(true)
+}
+
+/// Whether CPU-decoded frames are uploaded and colour-converted on the GPU
+/// instead of going through `videoconvert`. Off unless set to `1`: the
+/// software path is the fallback for machines whose GPU stack already failed.
+pub(crate) fn software_gpu_convert_enabled() -> bool {
+    std::env::var(NATIVE_SOFTWARE_GPU_CONVERT_ENV)
+        .map(|value| {
+            matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Target delay for webrtcbin's jitter buffers. Packets that arrive out of
+/// order within this window are reordered before depayloading; a larger value
+/// trades latency for fewer decode errors on lossy Wi-Fi. The env var beats
+/// the app setting so a shell override still applies.
+pub(crate) fn jitter_buffer_target_ms(setting: Option<u32>) -> u32 {
+    resolve_jitter_buffer_target_ms(
+        std::env::var(NATIVE_JITTER_BUFFER_MS_ENV).ok().as_deref(),
+        setting,
+    )
+}
+
+pub(crate) fn resolve_jitter_buffer_target_ms(env: Option<&str>, setting: Option<u32>) -> u32 {
+    env.and_then(|value| value.trim().parse::<u32>().ok())
+        .or(setting)
+        .map(|ms| ms.min(MAX_JITTER_BUFFER_MS))
+        .unwrap_or(DEFAULT_JITTER_BUFFER_MS)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AvSyncMode {
+    /// No sink probes at all; the default, since they run on every buffer.
+    Off,
+    /// Report the audio/video offset in stats without touching playback.
+    Measure,
+    /// Also hold audio back on the sink clock to line it up with video.
+    Correct,
+}
+
+pub(crate) fn av_sync_mode() -> AvSyncMode {
+    parse_av_sync_mode(std::env::var(NATIVE_AV_SYNC_ENV).ok().as_deref())
+}
+
+pub(crate) fn parse_av_sync_mode(value: Option<&str>) -> AvSyncMode {
+    match value
+        .map(|value| value.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("measure") => AvSyncMode::Measure,
+        Some("1" | "true" | "yes" | "on" | "correct") => AvSyncMode::Correct,
+        _ => AvSyncMode::Off,
+    }
+}
+
 pub(crate) fn resolve_present_max_fps(requested_fps: u32) -> u32 {
     if let Ok(value) = std::env::var(NATIVE_PRESENT_MAX_FPS_ENV) {
         let value = value.trim().to_ascii_lowercase();
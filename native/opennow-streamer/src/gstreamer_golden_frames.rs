@@ -0,0 +1,396 @@
+//! Golden-frame checks for the decoder backends.
+//!
+//! Each case encodes a deterministic `videotestsrc` clip, decodes it through the
+//! factory the live pipeline would pick for a backend, and compares every decoded
+//! frame component by component against the raw source frames. The decoder output
+//! is compared as it leaves the decoder, only downloaded to system memory, so a
+//! `videoconvert` cannot paper over what the decoder got wrong. The source frames
+//! are the reference rather than checked-in hashes because x264/x265 output
+//! differs between library versions; what must not drift is how close the decoded
+//! picture is to the source. Reading components through their own strides, pixel
+//! strides and shifts is what catches padding, color range and 10-bit regressions,
+//! and lets NV12 or P010 output be compared with planar references.
+//!
+//! A case that cannot run is a failure, not a skip. Software decoders run with
+//! the normal test suite and in CI. Hardware backends need the matching GPU, so
+//! they are opt-in and only check backends with a decoder on this machine:
+//! `cargo test --features gstreamer golden -- --ignored`.
+
+use crate::gstreamer_pipeline::{
+    all_rtp_video_apis, init_gstreamer, select_decoder_factory, RtpVideoApi,
+};
+use gst::prelude::*;
+use gst_video::prelude::*;
+use gstreamer as gst;
+use gstreamer_video as gst_video;
+use std::sync::{Arc, Mutex};
+
+const FRAME_COUNT: usize = 12;
+const MIN_PSNR_DB: f64 = 35.0;
+const PIPELINE_TIMEOUT_SECONDS: u64 = 60;
+
+struct GoldenCase {
+    name: &'static str,
+    codec: &'static str,
+    encoder: &'static str,
+    format: &'static str,
+    width: u32,
+    height: u32,
+    colorimetry: &'static str,
+}
+
+const GOLDEN_CASES: &[GoldenCase] = &[
+    GoldenCase {
+        name: "h264-limited-range-720p",
+        codec: "H264",
+        encoder: "x264enc pass=quant quantizer=12 speed-preset=ultrafast tune=zerolatency key-int-max=30 ! h264parse",
+        format: "I420",
+        width: 1280,
+        height: 720,
+        colorimetry: "bt709",
+    },
+    GoldenCase {
+        name: "h264-full-range",
+        codec: "H264",
+        encoder: "x264enc pass=quant quantizer=12 speed-preset=ultrafast tune=zerolatency key-int-max=30 ! h264parse",
+        format: "I420",
+        width: 640,
+        height: 360,
+        colorimetry: "1:3:5:1",
+    },
+    // Neither dimension is macroblock aligned and the chroma width is odd, so
+    // decoders hand back padded strides and cropped surfaces.
+    GoldenCase {
+        name: "h264-padded-stride",
+        codec: "H264",
+        encoder: "x264enc pass=quant quantizer=12 speed-preset=ultrafast tune=zerolatency key-int-max=30 ! h264parse",
+        format: "I420",
+        width: 1366,
+        height: 768,
+        colorimetry: "bt709",
+    },
+    GoldenCase {
+        name: "h265-limited-range-1080p",
+        codec: "H265",
+        encoder: "x265enc qp=12 speed-preset=ultrafast tune=zerolatency key-int-max=30 ! h265parse",
+        format: "I420",
+        width: 1920,
+        height: 1080,
+        colorimetry: "bt709",
+    },
+    GoldenCase {
+        name: "h265-main10",
+        codec: "H265",
+        encoder: "x265enc qp=12 speed-preset=ultrafast tune=zerolatency key-int-max=30 ! h265parse",
+        format: "I420_10LE",
+        width: 1280,
+        height: 720,
+        colorimetry: "bt2020-10",
+    },
+];
+
+struct CapturedFrame {
+    caps: gst::Caps,
+    buffer: gst::Buffer,
+}
+
+impl GoldenCase {
+    fn source(&self) -> String {
+        format!(
+            "videotestsrc num-buffers={FRAME_COUNT} pattern=smpte horizontal-speed=8 ! \
+             video/x-raw,format={},width={},height={},framerate=30/1,colorimetry={}",
+            self.format, self.width, self.height, self.colorimetry
+        )
+    }
+
+    fn encoder_factory(&self) -> &'static str {
+        self.encoder.split_whitespace().next().unwrap_or_default()
+    }
+
+    /// x265 only accepts 10-bit input when libx265 was built with it, which the
+    /// encoder's sink template reflects.
+    fn encoder_available(&self) -> bool {
+        let Some(factory) = gst::ElementFactory::find(self.encoder_factory()) else {
+            return false;
+        };
+        let raw_caps = gst::Caps::builder("video/x-raw")
+            .field("format", self.format)
+            .build();
+        factory.static_pad_templates().iter().any(|template| {
+            template.direction() == gst::PadDirection::Sink
+                && template.caps().can_intersect(&raw_caps)
+        })
+    }
+}
+
+fn capture_frames(description: &str) -> Result<Vec<CapturedFrame>, String> {
+    let pipeline = gst::parse::launch(description)
+        .map_err(|error| format!("Failed to parse `{description}`: {error}"))?
+        .downcast::<gst::Pipeline>()
+        .map_err(|_| "Parsed description is not a pipeline".to_string())?;
+    let sink = pipeline
+        .by_name("golden-sink")
+        .ok_or_else(|| "Pipeline has no golden-sink".to_string())?;
+
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let captured = frames.clone();
+    sink.connect("handoff", false, move |values| {
+        let buffer = values
+            .get(1)
+            .and_then(|value| value.get::<gst::Buffer>().ok());
+        let caps = values
+            .get(2)
+            .and_then(|value| value.get::<gst::Pad>().ok())
+            .and_then(|pad| pad.current_caps());
+        if let (Some(buffer), Some(caps)) = (buffer, caps) {
+            if let Ok(mut frames) = captured.lock() {
+                frames.push(CapturedFrame { caps, buffer });
+            }
+        }
+        None
+    });
+
+    let result = run_to_eos(&pipeline);
+    let _ = pipeline.set_state(gst::State::Null);
+    result?;
+
+    let mut frames = frames
+        .lock()
+        .map_err(|_| "Frame capture lock poisoned".to_string())?;
+    Ok(std::mem::take(&mut *frames))
+}
+
+fn run_to_eos(pipeline: &gst::Pipeline) -> Result<(), String> {
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|error| format!("Pipeline did not start: {error}"))?;
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "Pipeline has no bus".to_string())?;
+    let message = bus
+        .timed_pop_filtered(
+            gst::ClockTime::from_seconds(PIPELINE_TIMEOUT_SECONDS),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        )
+        .ok_or_else(|| format!("Pipeline did not finish within {PIPELINE_TIMEOUT_SECONDS}s"))?;
+    match message.view() {
+        gst::MessageView::Error(error) => Err(format!(
+            "{} ({})",
+            error.error(),
+            error.debug().unwrap_or_default()
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn sample_at(data: &[u8], offset: usize, depth: u32, shift: u32) -> Option<u32> {
+    let word = if depth > 8 {
+        let bytes = data.get(offset..offset + 2)?;
+        u32::from(u16::from_le_bytes([bytes[0], bytes[1]]))
+    } else {
+        u32::from(*data.get(offset)?)
+    };
+    Some((word >> shift) & ((1 << depth) - 1))
+}
+
+/// PSNR of every component of `decoded` against `reference`, reading each
+/// component through its own stride so decoder padding never counts as picture.
+fn component_psnr_db(
+    reference: &CapturedFrame,
+    decoded: &CapturedFrame,
+) -> Result<Vec<f64>, String> {
+    let reference_info = gst_video::VideoInfo::from_caps(&reference.caps)
+        .map_err(|error| format!("Bad reference caps: {error}"))?;
+    let decoded_info = gst_video::VideoInfo::from_caps(&decoded.caps)
+        .map_err(|error| format!("Bad decoded caps: {error}"))?;
+    let reference_layout = gst_video::VideoFormatInfo::from_format(reference_info.format());
+    let decoded_layout = gst_video::VideoFormatInfo::from_format(decoded_info.format());
+    if reference_layout.n_components() != decoded_layout.n_components()
+        || reference_layout.depth() != decoded_layout.depth()
+        || reference_layout.w_sub() != decoded_layout.w_sub()
+        || reference_layout.h_sub() != decoded_layout.h_sub()
+        || reference_info.width() != decoded_info.width()
+        || reference_info.height() != decoded_info.height()
+    {
+        return Err(format!(
+            "Decoded {:?} {}x{} does not match reference {:?} {}x{}",
+            decoded_info.format(),
+            decoded_info.width(),
+            decoded_info.height(),
+            reference_info.format(),
+            reference_info.width(),
+            reference_info.height()
+        ));
+    }
+
+    let reference_frame = gst_video::VideoFrameRef::from_buffer_ref_readable(
+        reference.buffer.as_ref(),
+        &reference_info,
+    )
+    .map_err(|error| format!("Failed to map reference frame: {error}"))?;
+    let decoded_frame =
+        gst_video::VideoFrameRef::from_buffer_ref_readable(decoded.buffer.as_ref(), &decoded_info)
+            .map_err(|error| format!("Failed to map decoded frame: {error}"))?;
+
+    let mut psnr = Vec::new();
+    for component in 0..reference_frame.n_components() {
+        let depth = reference_frame.comp_depth(component);
+        let reference_pixel_stride = reference_frame.comp_pstride(component).max(1) as usize;
+        let decoded_pixel_stride = decoded_frame.comp_pstride(component).max(1) as usize;
+        let reference_shift = reference_layout.shift()[component as usize];
+        let decoded_shift = decoded_layout.shift()[component as usize];
+        let width = reference_frame.comp_width(component) as usize;
+        let height = reference_frame.comp_height(component) as usize;
+        let reference_stride = reference_frame.comp_stride(component) as usize;
+        let decoded_stride = decoded_frame.comp_stride(component) as usize;
+        let reference_data = reference_frame
+            .comp_data(component)
+            .map_err(|error| format!("Reference component {component}: {error}"))?;
+        let decoded_data = decoded_frame
+            .comp_data(component)
+            .map_err(|error| format!("Decoded component {component}: {error}"))?;
+
+        let mut squared_error = 0f64;
+        for row in 0..height {
+            for column in 0..width {
+                let expected = sample_at(
+                    reference_data,
+                    row * reference_stride + column * reference_pixel_stride,
+                    depth,
+                    reference_shift,
+                )
+                .ok_or_else(|| format!("Reference component {component} is short"))?;
+                let actual = sample_at(
+                    decoded_data,
+                    row * decoded_stride + column * decoded_pixel_stride,
+                    depth,
+                    decoded_shift,
+                )
+                .ok_or_else(|| format!("Decoded component {component} is short"))?;
+                let difference = f64::from(expected) - f64::from(actual);
+                squared_error += difference * difference;
+            }
+        }
+
+        let mse = squared_error / (width * height).max(1) as f64;
+        let peak = f64::from((1u32 << depth) - 1);
+        psnr.push(if mse == 0.0 {
+            f64::INFINITY
+        } else {
+            10.0 * (peak * peak / mse).log10()
+        });
+    }
+    Ok(psnr)
+}
+
+/// Decodes `case` with `api`'s decoder and returns every mismatch found.
+/// A missing encoder or decoder is reported as a mismatch too.
+fn check_golden_case(api: RtpVideoApi, case: &GoldenCase) -> Vec<String> {
+    let Some(decoder) = select_decoder_factory(api, case.codec) else {
+        return vec![format!(
+            "{} {}: no {} decoder available",
+            api.label(),
+            case.name,
+            case.codec
+        )];
+    };
+    let label = format!("{} {} ({decoder})", api.label(), case.name);
+    if !case.encoder_available() {
+        return vec![format!(
+            "{label}: {} cannot encode {}",
+            case.encoder_factory(),
+            case.format
+        )];
+    }
+
+    let source = case.source();
+    let reference = match capture_frames(&format!(
+        "{source} ! fakesink name=golden-sink signal-handoffs=true sync=false"
+    )) {
+        Ok(frames) => frames,
+        Err(error) => return vec![format!("{label}: reference capture failed: {error}")],
+    };
+    // Plain `video/x-raw` only asks for system memory, so hardware decoders
+    // download their surfaces but keep their own format and layout.
+    let decoded = match capture_frames(&format!(
+        "{source} ! {} ! {decoder} ! video/x-raw ! \
+         fakesink name=golden-sink signal-handoffs=true sync=false",
+        case.encoder
+    )) {
+        Ok(frames) => frames,
+        Err(error) => return vec![format!("{label}: decode failed: {error}")],
+    };
+
+    let mut failures = Vec::new();
+    if decoded.len() != reference.len() {
+        failures.push(format!(
+            "{label}: decoded {} frames, expected {}",
+            decoded.len(),
+            reference.len()
+        ));
+    }
+    for (index, (reference, decoded)) in reference.iter().zip(&decoded).enumerate() {
+        let expected_range = gst_video::VideoInfo::from_caps(&reference.caps)
+            .map(|info| info.colorimetry().range())
+            .ok();
+        let decoded_range = gst_video::VideoInfo::from_caps(&decoded.caps)
+            .map(|info| info.colorimetry().range())
+            .ok();
+        if expected_range != decoded_range {
+            failures.push(format!(
+                "{label} frame {index}: color range {decoded_range:?}, expected {expected_range:?}"
+            ));
+        }
+        match component_psnr_db(reference, decoded) {
+            Ok(psnr) => {
+                if let Some((component, worst)) = psnr
+                    .iter()
+                    .enumerate()
+                    .find(|(_, value)| **value < MIN_PSNR_DB)
+                {
+                    failures.push(format!(
+                        "{label} frame {index}: component {component} PSNR {worst:.1} dB is below {MIN_PSNR_DB} dB"
+                    ));
+                }
+            }
+            Err(error) => failures.push(format!("{label} frame {index}: {error}")),
+        }
+    }
+    failures
+}
+
+fn check_backends(apis: &[RtpVideoApi]) {
+    let failures: Vec<_> = apis
+        .iter()
+        .flat_map(|&api| GOLDEN_CASES.iter().map(move |case| (api, case)))
+        .flat_map(|(api, case)| check_golden_case(api, case))
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn software_decoders_match_golden_frames() {
+    init_gstreamer().expect("gstreamer init");
+    check_backends(&[RtpVideoApi::Software]);
+}
+
+#[test]
+#[ignore = "needs the matching GPU decoder; run locally with --ignored"]
+fn hardware_decoders_match_golden_frames() {
+    init_gstreamer().expect("gstreamer init");
+    let hardware: Vec<_> = all_rtp_video_apis()
+        .iter()
+        .copied()
+        .filter(|api| *api != RtpVideoApi::Software)
+        .filter(|api| {
+            GOLDEN_CASES
+                .iter()
+                .any(|case| select_decoder_factory(*api, case.codec).is_some())
+        })
+        .collect();
+    assert!(
+        !hardware.is_empty(),
+        "no hardware decoder backend is available on this machine"
+    );
+    check_backends(&hardware);
+}
@@ -1,31 +1,46 @@
+#[cfg(feature = "dualsense")]
+use crate::dualsense::{
+    build_output_report, parse_input_report, parse_steam_input_devices, DualSenseOutput,
+    DualSenseTransport, CALIBRATION_FEATURE_REPORT, CALIBRATION_FEATURE_REPORT_LEN,
+    DUALSENSE_PRODUCT_IDS, DUALSENSE_VENDOR_ID, INPUT_REPORT_MAX_LEN, STEAM_INPUT_DEVICES_ENV,
+};
+use crate::gamepad_slots::GamepadSlotAssigner;
 use crate::gstreamer_backend::send_log;
+use crate::gstreamer_config::{input_record_path, input_replay_path};
 #[cfg(target_os = "windows")]
 use crate::gstreamer_platform::win32_renderer_window;
 use crate::input::InputEncoder;
 #[cfg(target_os = "windows")]
 use crate::input::{
-    layout_mapped_keyboard_keycode, layout_mapped_keyboard_scancode, GamepadInput, KeyboardPayload,
-    MouseButtonPayload, MouseMovePayload, MouseWheelPayload, GAMEPAD_MAX_CONTROLLERS,
-    PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL,
+    layout_mapped_keyboard_keycode, layout_mapped_keyboard_scancode, KeyboardPayload,
+    MouseButtonPayload, MouseMovePayload, MouseWheelPayload,
 };
+#[cfg(any(target_os = "windows", feature = "dualsense"))]
+use crate::input::{GamepadInput, GAMEPAD_MAX_CONTROLLERS, PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL};
+use crate::input_recording::{encode_replayed_input, parse_input_recording, InputRecorder};
 use crate::protocol::Event;
 #[cfg(target_os = "windows")]
 use crate::protocol::NativeStreamerShortcutAction;
+use crate::webrtc_protocol::{
+    decode_server_input_message, encoder_protocol_version, ControlMessage, ProtocolError,
+    ServerInputMessage, CONTROL_CHANNEL_LABEL,
+};
 use gst::glib;
 use gst::prelude::*;
 use gstreamer as gst;
 use gstreamer_webrtc as gst_webrtc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::fs::File;
+use std::io::LineWriter;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::mpsc::Sender;
 #[cfg(target_os = "windows")]
 use std::sync::mpsc::{self, RecvTimeoutError, TryRecvError};
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", feature = "dualsense"))]
 use std::sync::OnceLock;
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
-#[cfg(target_os = "windows")]
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 const RELIABLE_INPUT_CHANNEL_LABEL: &str = "input_channel_v1";
 const PARTIALLY_RELIABLE_INPUT_CHANNEL_LABEL: &str = "input_channel_partially_reliable";
@@ -36,12 +51,18 @@ const HEARTBEAT_STOP_POLL_INTERVAL: Duration = Duration::from_millis(50);
 const NATIVE_INPUT_BRIDGE_POLL_INTERVAL: Duration = Duration::from_millis(1);
 #[cfg(target_os = "windows")]
 const NATIVE_INPUT_DRAIN_MAX_EVENTS: usize = 512;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", feature = "dualsense"))]
 const NATIVE_GAMEPAD_POLL_INTERVAL: Duration = Duration::from_millis(4);
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", feature = "dualsense"))]
 const NATIVE_GAMEPAD_KEEPALIVE_INTERVAL: Duration = Duration::from_millis(100);
-
+/// Slot ids of the XInput user indices.
 #[cfg(target_os = "windows")]
+const XINPUT_PAD_IDS: [&str; GAMEPAD_MAX_CONTROLLERS as usize] =
+    ["xinput:0", "xinput:1", "xinput:2", "xinput:3"];
+#[cfg(feature = "dualsense")]
+const DUALSENSE_SCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+#[cfg(any(target_os = "windows", feature = "dualsense"))]
 static NATIVE_INPUT_STARTED_AT: OnceLock<Instant> = OnceLock::new();
 
 #[derive(Clone)]
@@ -50,6 +71,20 @@ pub(crate) struct GstreamerInputState {
     pub(crate) ready: Arc<AtomicBool>,
     heartbeat_stop: Arc<AtomicBool>,
     heartbeat_thread: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Bit `n` lets the native gamepad bridges forward player slot `n`.
+    #[cfg_attr(
+        not(any(target_os = "windows", feature = "dualsense")),
+        allow(dead_code)
+    )]
+    controller_slot_mask: Arc<AtomicU8>,
+    /// Player slots shared by every native gamepad bridge.
+    #[cfg_attr(
+        not(any(target_os = "windows", feature = "dualsense")),
+        allow(dead_code)
+    )]
+    gamepad_slots: Arc<Mutex<GamepadSlotAssigner>>,
+    #[cfg(feature = "dualsense")]
+    dualsense_outputs: Arc<Mutex<[DualSenseOutput; GAMEPAD_MAX_CONTROLLERS as usize]>>,
 }
 
 impl std::fmt::Debug for GstreamerInputState {
@@ -68,6 +103,10 @@ impl Default for GstreamerInputState {
             ready: Arc::new(AtomicBool::new(false)),
             heartbeat_stop: Arc::new(AtomicBool::new(false)),
             heartbeat_thread: Arc::new(Mutex::new(None)),
+            controller_slot_mask: Arc::new(AtomicU8::new(u8::MAX)),
+            gamepad_slots: Arc::new(Mutex::new(GamepadSlotAssigner::default())),
+            #[cfg(feature = "dualsense")]
+            dualsense_outputs: Arc::new(Mutex::new(Default::default())),
         }
     }
 }
@@ -81,6 +120,64 @@ impl GstreamerInputState {
         }
     }
 
+    pub(crate) fn set_controller_slot_mask(&self, mask: u8) {
+        self.controller_slot_mask.store(mask, Ordering::SeqCst);
+    }
+
+    /// Player slot for a connected native pad under the current slot settings.
+    #[cfg(any(target_os = "windows", feature = "dualsense"))]
+    fn claim_gamepad_slot(&self, id: &str) -> Option<u8> {
+        let enabled = self.controller_slot_mask.load(Ordering::SeqCst);
+        self.gamepad_slots.lock().ok()?.claim(id, enabled)
+    }
+
+    #[cfg(any(target_os = "windows", feature = "dualsense"))]
+    fn release_gamepad_slot(&self, id: &str) {
+        if let Ok(mut slots) = self.gamepad_slots.lock() {
+            slots.release(id);
+        }
+    }
+
+    /// Connected-controller bitmap across every native gamepad bridge.
+    #[cfg(any(target_os = "windows", feature = "dualsense"))]
+    fn gamepad_bitmap(&self) -> u16 {
+        self.gamepad_slots
+            .lock()
+            .map(|slots| slots.bitmap())
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "dualsense")]
+    pub(crate) fn set_controller_light_color(&self, color: Option<[u8; 3]>) {
+        if let Ok(mut outputs) = self.dualsense_outputs.lock() {
+            for output in outputs.iter_mut() {
+                output.lightbar = color;
+            }
+        }
+    }
+
+    #[cfg(feature = "dualsense")]
+    fn update_dualsense_output(
+        &self,
+        controller_id: u16,
+        update: impl FnOnce(&mut DualSenseOutput),
+    ) {
+        let Ok(mut outputs) = self.dualsense_outputs.lock() else {
+            return;
+        };
+        if let Some(output) = outputs.get_mut(usize::from(controller_id)) {
+            update(output);
+        }
+    }
+
+    #[cfg(feature = "dualsense")]
+    fn dualsense_output(&self, slot: u8) -> Option<DualSenseOutput> {
+        self.dualsense_outputs
+            .lock()
+            .ok()
+            .and_then(|outputs| outputs.get(usize::from(slot)).copied())
+    }
+
     pub(crate) fn stop_heartbeat(&self) {
         self.heartbeat_stop.store(true, Ordering::SeqCst);
         let Some(handle) = self
@@ -238,10 +335,13 @@ mod win32_xinput {
     }
 }
 
+type SharedInputRecorder = Arc<Mutex<InputRecorder<LineWriter<File>>>>;
+
 #[derive(Clone, Debug)]
 pub(crate) struct GstreamerInputChannels {
     reliable: gst_webrtc::WebRTCDataChannel,
     partially_reliable: gst_webrtc::WebRTCDataChannel,
+    recorder: Option<SharedInputRecorder>,
 }
 
 impl GstreamerInputChannels {
@@ -271,8 +371,137 @@ impl GstreamerInputChannels {
         }
 
         let bytes = glib::Bytes::from_owned(payload.to_vec());
-        channel.send_data_full(Some(&bytes)).is_ok()
+        if channel.send_data_full(Some(&bytes)).is_err() {
+            return false;
+        }
+        if let Some(mut recorder) = self
+            .recorder
+            .as_ref()
+            .and_then(|recorder| recorder.lock().ok())
+        {
+            let _ = recorder.record_packet(payload, partially_reliable);
+        }
+        true
+    }
+}
+
+/// Plays a recording from `OPENNOW_NATIVE_INPUT_REPLAY` into the encoder once
+/// the input handshake completes, keeping the recorded spacing between events.
+#[derive(Debug)]
+pub(crate) struct InputReplay {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl InputReplay {
+    pub(crate) fn start(
+        input_state: GstreamerInputState,
+        input_channels: GstreamerInputChannels,
+        event_sender: Option<Sender<Event>>,
+    ) -> Option<Self> {
+        let path = input_replay_path()?;
+        let events = match std::fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|text| parse_input_recording(&text))
+        {
+            Ok(events) => events,
+            Err(error) => {
+                send_log(
+                    &event_sender,
+                    "warn",
+                    format!("Input replay disabled: {}: {error}", path.display()),
+                );
+                return None;
+            }
+        };
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || {
+            if !sleep_until(
+                &thread_stop,
+                || input_state.ready.load(Ordering::SeqCst),
+                None,
+            ) {
+                return;
+            }
+            send_log(
+                &event_sender,
+                "info",
+                format!(
+                    "Replaying {} input events from {}.",
+                    events.len(),
+                    path.display()
+                ),
+            );
+
+            let started_at = Instant::now();
+            let mut sent = 0usize;
+            for event in &events {
+                let due = started_at + Duration::from_micros(event.offset_us);
+                if !sleep_until(&thread_stop, || Instant::now() >= due, Some(due)) {
+                    break;
+                }
+                let Ok(mut encoder) = input_state.encoder.lock() else {
+                    break;
+                };
+                let timestamp_us = started_at.elapsed().as_micros() as u64;
+                let payload = encode_replayed_input(&mut encoder, event, timestamp_us);
+                drop(encoder);
+                if input_channels.send_packet(&payload, event.partially_reliable) {
+                    sent += 1;
+                }
+            }
+
+            send_log(
+                &event_sender,
+                "info",
+                format!(
+                    "Input replay finished: sent {sent} of {} events.",
+                    events.len()
+                ),
+            );
+        });
+
+        Some(Self {
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    pub(crate) fn stop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for InputReplay {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Polls `done` until it holds, sleeping no later than `deadline`. Returns
+/// false when `stop` is raised first.
+fn sleep_until(
+    stop: &AtomicBool,
+    mut done: impl FnMut() -> bool,
+    deadline: Option<Instant>,
+) -> bool {
+    while !done() {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        let interval = deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+            .map_or(HEARTBEAT_STOP_POLL_INTERVAL, |remaining| {
+                remaining.min(HEARTBEAT_STOP_POLL_INTERVAL)
+            });
+        thread::sleep(interval);
     }
+    !stop.load(Ordering::SeqCst)
 }
 
 #[cfg(target_os = "windows")]
@@ -596,16 +825,20 @@ fn spawn_native_gamepad_thread(
 
         while !stop.load(Ordering::SeqCst) {
             if input_state.ready.load(Ordering::SeqCst) {
+                // Snapshots are indexed by player slot. Connected pads fill
+                // the enabled slots in order and keep them across hotplug.
                 let mut snapshots =
                     [NativeGamepadSnapshot::default(); GAMEPAD_MAX_CONTROLLERS as usize];
-                let mut bitmap = 0u16;
-
-                for controller_id in 0..GAMEPAD_MAX_CONTROLLERS as usize {
-                    if let Some(snapshot) = unsafe { xinput.get_state(controller_id as u32) } {
-                        snapshots[controller_id] = NativeGamepadSnapshot::from_xinput(snapshot);
-                        bitmap |= 1 << controller_id;
+                for (user_index, id) in XINPUT_PAD_IDS.iter().enumerate() {
+                    let Some(snapshot) = (unsafe { xinput.get_state(user_index as u32) }) else {
+                        input_state.release_gamepad_slot(id);
+                        continue;
+                    };
+                    if let Some(slot) = input_state.claim_gamepad_slot(id) {
+                        snapshots[usize::from(slot)] = NativeGamepadSnapshot::from_xinput(snapshot);
                     }
                 }
+                let bitmap = input_state.gamepad_bitmap();
 
                 for controller_id in 0..GAMEPAD_MAX_CONTROLLERS as usize {
                     let snapshot = snapshots[controller_id];
@@ -628,7 +861,8 @@ fn spawn_native_gamepad_thread(
                                 &event_sender,
                                 "info",
                                 format!(
-                                    "Native XInput controller {controller_id} {}.",
+                                    "Native XInput controller (player {}) {}.",
+                                    controller_id + 1,
                                     if snapshot.connected {
                                         "connected"
                                     } else {
@@ -655,26 +889,39 @@ fn send_native_gamepad_snapshot(
     controller_id: u8,
     bitmap: u16,
     snapshot: NativeGamepadSnapshot,
+) {
+    send_native_gamepad_input(
+        input_state,
+        input_channels,
+        bitmap,
+        GamepadInput {
+            controller_id,
+            buttons: snapshot.buttons,
+            left_trigger: snapshot.left_trigger,
+            right_trigger: snapshot.right_trigger,
+            left_stick_x: snapshot.left_stick_x,
+            left_stick_y: snapshot.left_stick_y,
+            right_stick_x: snapshot.right_stick_x,
+            right_stick_y: snapshot.right_stick_y,
+            connected: snapshot.connected,
+            timestamp_us: native_input_timestamp_us(),
+        },
+    );
+}
+
+#[cfg(any(target_os = "windows", feature = "dualsense"))]
+fn send_native_gamepad_input(
+    input_state: &GstreamerInputState,
+    input_channels: &GstreamerInputChannels,
+    bitmap: u16,
+    input: GamepadInput,
 ) {
     if !input_state.ready.load(Ordering::SeqCst) {
         return;
     }
 
     let use_partially_reliable =
-        (PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL & (1_u32 << u32::from(controller_id))) != 0;
-    let input = GamepadInput {
-        controller_id,
-        buttons: snapshot.buttons,
-        left_trigger: snapshot.left_trigger,
-        right_trigger: snapshot.right_trigger,
-        left_stick_x: snapshot.left_stick_x,
-        left_stick_y: snapshot.left_stick_y,
-        right_stick_x: snapshot.right_stick_x,
-        right_stick_y: snapshot.right_stick_y,
-        connected: snapshot.connected,
-        timestamp_us: native_input_timestamp_us(),
-    };
-
+        (PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL & (1_u32 << u32::from(input.controller_id))) != 0;
     let Ok(mut encoder) = input_state.encoder.lock() else {
         return;
     };
@@ -684,7 +931,7 @@ fn send_native_gamepad_snapshot(
     let _ = input_channels.send_packet(&payload, use_partially_reliable);
 }
 
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", feature = "dualsense"))]
 fn native_input_timestamp_us() -> u64 {
     NATIVE_INPUT_STARTED_AT
         .get_or_init(Instant::now)
@@ -693,6 +940,298 @@ fn native_input_timestamp_us() -> u64 {
         .min(u128::from(u64::MAX)) as u64
 }
 
+/// Reads DualSense pads over HID, alongside XInput, so their rumble and
+/// lightbar stay reachable.
+#[cfg(feature = "dualsense")]
+#[derive(Debug)]
+pub(crate) struct DualSenseBridge {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "dualsense")]
+impl DualSenseBridge {
+    pub(crate) fn start(
+        input_state: GstreamerInputState,
+        input_channels: GstreamerInputChannels,
+        event_sender: Option<Sender<Event>>,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = thread::spawn(move || {
+            run_dualsense_bridge(input_state, input_channels, event_sender, thread_stop)
+        });
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    pub(crate) fn stop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            if let Err(error) = thread.join() {
+                eprintln!("[NativeStreamer] Native DualSense thread panicked: {error:?}");
+            }
+        }
+    }
+}
+
+#[cfg(feature = "dualsense")]
+impl Drop for DualSenseBridge {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(feature = "dualsense")]
+struct DualSensePad {
+    device: hidapi::HidDevice,
+    path: std::ffi::CString,
+    /// Id the pad holds its player slot under.
+    slot_id: String,
+    slot: u8,
+    transport: Option<DualSenseTransport>,
+    /// Last state sent to the server, with a zero timestamp.
+    sent: Option<GamepadInput>,
+    last_sent: Instant,
+    written: Option<DualSenseOutput>,
+    output_sequence: u8,
+}
+
+#[cfg(feature = "dualsense")]
+fn run_dualsense_bridge(
+    input_state: GstreamerInputState,
+    input_channels: GstreamerInputChannels,
+    event_sender: Option<Sender<Event>>,
+    stop: Arc<AtomicBool>,
+) {
+    let mut api = match hidapi::HidApi::new() {
+        Ok(api) => api,
+        Err(error) => {
+            send_log(
+                &event_sender,
+                "warn",
+                format!("Native DualSense bridge unavailable: {error}."),
+            );
+            return;
+        }
+    };
+    send_log(
+        &event_sender,
+        "info",
+        "Native DualSense bridge armed.".to_owned(),
+    );
+    let steam_input_devices = std::env::var(STEAM_INPUT_DEVICES_ENV)
+        .map(|value| parse_steam_input_devices(&value))
+        .unwrap_or_default();
+    if !steam_input_devices.is_empty() {
+        send_log(
+            &event_sender,
+            "info",
+            "Steam Input is active; DualSense pads it manages stay with its virtual controller."
+                .to_owned(),
+        );
+    }
+
+    let mut pads = Vec::new();
+    let mut next_scan = Instant::now();
+    while !stop.load(Ordering::SeqCst) {
+        if Instant::now() >= next_scan {
+            attach_dualsense_pads(
+                &mut api,
+                &mut pads,
+                &steam_input_devices,
+                &input_state,
+                &event_sender,
+            );
+            next_scan = Instant::now() + DUALSENSE_SCAN_INTERVAL;
+        }
+        if input_state.ready.load(Ordering::SeqCst) {
+            pads.retain_mut(
+                |pad| match poll_dualsense_pad(pad, &input_state, &input_channels) {
+                    Ok(()) => true,
+                    Err(error) => {
+                        release_dualsense_pad(pad, &input_state, &input_channels);
+                        send_log(
+                            &event_sender,
+                            "info",
+                            format!(
+                                "Native DualSense controller (player {}) disconnected: {error}.",
+                                pad.slot + 1
+                            ),
+                        );
+                        false
+                    }
+                },
+            );
+        }
+        thread::sleep(NATIVE_GAMEPAD_POLL_INTERVAL);
+    }
+
+    for pad in &pads {
+        release_dualsense_pad(pad, &input_state, &input_channels);
+    }
+}
+
+#[cfg(feature = "dualsense")]
+fn attach_dualsense_pads(
+    api: &mut hidapi::HidApi,
+    pads: &mut Vec<DualSensePad>,
+    steam_input_devices: &[(u16, u16)],
+    input_state: &GstreamerInputState,
+    event_sender: &Option<Sender<Event>>,
+) {
+    if let Err(error) = api.refresh_devices() {
+        send_log(
+            event_sender,
+            "debug",
+            format!("DualSense device scan failed: {error}."),
+        );
+        return;
+    }
+
+    for info in api.device_list() {
+        if info.vendor_id() != DUALSENSE_VENDOR_ID
+            || !DUALSENSE_PRODUCT_IDS.contains(&info.product_id())
+            || steam_input_devices.contains(&(info.vendor_id(), info.product_id()))
+            || pads.iter().any(|pad| pad.path.as_c_str() == info.path())
+        {
+            continue;
+        }
+        let slot_id = format!("hid:{}", info.path().to_string_lossy());
+        let Some(slot) = input_state.claim_gamepad_slot(&slot_id) else {
+            continue;
+        };
+        let device = match info.open_device(api) {
+            Ok(device) => device,
+            Err(error) => {
+                input_state.release_gamepad_slot(&slot_id);
+                send_log(
+                    event_sender,
+                    "debug",
+                    format!("Could not open DualSense controller: {error}."),
+                );
+                continue;
+            }
+        };
+
+        // Bluetooth pads only send full input reports once this was read.
+        let mut calibration = [0; CALIBRATION_FEATURE_REPORT_LEN];
+        calibration[0] = CALIBRATION_FEATURE_REPORT;
+        let _ = device.get_feature_report(&mut calibration);
+
+        send_log(
+            event_sender,
+            "info",
+            format!(
+                "Native DualSense controller connected as player {}.",
+                slot + 1
+            ),
+        );
+        pads.push(DualSensePad {
+            device,
+            path: info.path().to_owned(),
+            slot_id,
+            slot,
+            transport: None,
+            sent: None,
+            last_sent: Instant::now(),
+            written: None,
+            output_sequence: 0,
+        });
+    }
+}
+
+#[cfg(feature = "dualsense")]
+fn poll_dualsense_pad(
+    pad: &mut DualSensePad,
+    input_state: &GstreamerInputState,
+    input_channels: &GstreamerInputChannels,
+) -> Result<(), hidapi::HidError> {
+    let mut report = [0; INPUT_REPORT_MAX_LEN];
+    let mut latest = None;
+    loop {
+        let len = pad.device.read_timeout(&mut report, 0)?;
+        if len == 0 {
+            break;
+        }
+        let Some((transport, state)) = parse_input_report(&report[..len]) else {
+            continue;
+        };
+        pad.transport = Some(transport);
+        latest = Some(state.gamepad_input(pad.slot, 0));
+    }
+
+    if let Some(input) = latest.or(pad.sent) {
+        if Some(input) != pad.sent || pad.last_sent.elapsed() >= NATIVE_GAMEPAD_KEEPALIVE_INTERVAL {
+            send_native_gamepad_input(
+                input_state,
+                input_channels,
+                input_state.gamepad_bitmap(),
+                GamepadInput {
+                    timestamp_us: native_input_timestamp_us(),
+                    ..input
+                },
+            );
+            pad.sent = Some(input);
+            pad.last_sent = Instant::now();
+        }
+    }
+
+    if let (Some(transport), Some(output)) = (pad.transport, input_state.dualsense_output(pad.slot))
+    {
+        if pad.written != Some(output) {
+            pad.device.write(&build_output_report(
+                transport,
+                &output,
+                pad.output_sequence,
+            ))?;
+            pad.output_sequence = pad.output_sequence.wrapping_add(1);
+            pad.written = Some(output);
+        }
+    }
+    Ok(())
+}
+
+/// Frees the pad's slot, tells the server it is gone and stops any rumble
+/// still applied.
+#[cfg(feature = "dualsense")]
+fn release_dualsense_pad(
+    pad: &DualSensePad,
+    input_state: &GstreamerInputState,
+    input_channels: &GstreamerInputChannels,
+) {
+    input_state.release_gamepad_slot(&pad.slot_id);
+    let bitmap = input_state.gamepad_bitmap();
+    if pad.sent.is_some() {
+        send_native_gamepad_input(
+            input_state,
+            input_channels,
+            bitmap,
+            GamepadInput {
+                controller_id: pad.slot,
+                buttons: 0,
+                left_trigger: 0,
+                right_trigger: 0,
+                left_stick_x: 0,
+                left_stick_y: 0,
+                right_stick_x: 0,
+                right_stick_y: 0,
+                connected: false,
+                timestamp_us: native_input_timestamp_us(),
+            },
+        );
+    }
+    if let Some(transport) = pad.transport {
+        let _ = pad.device.write(&build_output_report(
+            transport,
+            &DualSenseOutput::default(),
+            pad.output_sequence,
+        ));
+    }
+}
+
 pub(crate) fn wire_remote_data_channels(
     webrtc: &gst::Element,
     event_sender: Option<Sender<Event>>,
@@ -730,8 +1269,18 @@ pub(crate) fn create_input_data_channels(
     input_state: GstreamerInputState,
     event_sender: Option<Sender<Event>>,
     partial_reliable_threshold_ms: u32,
+    high_priority: bool,
 ) -> Result<GstreamerInputChannels, String> {
-    let reliable = create_data_channel(webrtc, RELIABLE_INPUT_CHANNEL_LABEL, None)?;
+    // webrtcbin DSCP-marks the SCTP transport by its highest channel priority.
+    let with_priority = |mut options: gst::Structure| {
+        if high_priority {
+            options.set("priority", gst_webrtc::WebRTCPriorityType::High);
+        }
+        options
+    };
+    let reliable_options =
+        high_priority.then(|| with_priority(gst::Structure::new_empty("data-channel-options")));
+    let reliable = create_data_channel(webrtc, RELIABLE_INPUT_CHANNEL_LABEL, reliable_options)?;
     connect_input_channel_callbacks(
         RELIABLE_INPUT_CHANNEL_LABEL,
         &reliable,
@@ -751,7 +1300,7 @@ pub(crate) fn create_input_data_channels(
     let partially_reliable = create_data_channel(
         webrtc,
         PARTIALLY_RELIABLE_INPUT_CHANNEL_LABEL,
-        Some(options),
+        Some(with_priority(options)),
     )?;
     connect_input_channel_callbacks(
         PARTIALLY_RELIABLE_INPUT_CHANNEL_LABEL,
@@ -764,19 +1313,48 @@ pub(crate) fn create_input_data_channels(
         &event_sender,
         "info",
         format!(
-            "Created WebRTC input data channels ({}, {} maxPacketLifeTime={}ms).",
+            "Created WebRTC input data channels ({}, {} maxPacketLifeTime={}ms{}).",
             RELIABLE_INPUT_CHANNEL_LABEL,
             PARTIALLY_RELIABLE_INPUT_CHANNEL_LABEL,
-            clamped_threshold_ms
+            clamped_threshold_ms,
+            if high_priority { ", high priority" } else { "" }
         ),
     );
 
     Ok(GstreamerInputChannels {
         reliable,
         partially_reliable,
+        recorder: input_record_path().and_then(|path| open_input_recorder(path, &event_sender)),
     })
 }
 
+fn open_input_recorder(
+    path: PathBuf,
+    event_sender: &Option<Sender<Event>>,
+) -> Option<SharedInputRecorder> {
+    match File::create(&path)
+        .map(LineWriter::new)
+        .and_then(InputRecorder::new)
+    {
+        Ok(recorder) => {
+            send_log(
+                event_sender,
+                "info",
+                format!("Recording input events to {}.", path.display()),
+            );
+            Some(Arc::new(Mutex::new(recorder)))
+        }
+        Err(error) => {
+            send_log(
+                event_sender,
+                "warn",
+                format!("Input recording disabled: {}: {error}.", path.display()),
+            );
+            None
+        }
+    }
+}
+
 fn create_data_channel(
     webrtc: &gst::Element,
     label: &'static str,
@@ -907,14 +1485,41 @@ fn connect_remote_data_channel_callbacks(
         );
     });
 
-    let error_sender = event_sender;
+    let error_sender = event_sender.clone();
+    let error_label = label.clone();
     channel.connect_on_error(move |_, error| {
         send_log(
             &error_sender,
             "warn",
-            format!("Remote data channel error on {label}: {error}."),
+            format!("Remote data channel error on {error_label}: {error}."),
         );
     });
+
+    if label != CONTROL_CHANNEL_LABEL {
+        return;
+    }
+
+    let string_sender = event_sender.clone();
+    channel.connect_on_message_string(move |_, message| {
+        if let Some(message) = message {
+            handle_control_channel_message(message, &string_sender);
+        }
+    });
+
+    let data_sender = event_sender;
+    channel.connect_on_message_data(move |_, data| {
+        let Some(bytes) = data else {
+            return;
+        };
+        match std::str::from_utf8(bytes.as_ref()) {
+            Ok(text) => handle_control_channel_message(text, &data_sender),
+            Err(_) => send_log(
+                &data_sender,
+                "debug",
+                format!("Ignoring non-UTF-8 {CONTROL_CHANNEL_LABEL} message."),
+            ),
+        }
+    });
 }
 
 fn handle_input_handshake_message(
@@ -923,13 +1528,41 @@ fn handle_input_handshake_message(
     input_state: GstreamerInputState,
     event_sender: Option<Sender<Event>>,
 ) {
-    let Some(protocol_version) = parse_input_handshake_version(bytes) else {
-        return;
+    let handshake_complete = input_state.ready.load(Ordering::SeqCst);
+    let protocol_version = match decode_server_input_message(bytes, handshake_complete) {
+        Ok(ServerInputMessage::Handshake { version }) => version,
+        Ok(ServerInputMessage::Haptics(haptics)) => {
+            #[cfg(feature = "dualsense")]
+            input_state.update_dualsense_output(haptics.controller_id, |output| {
+                output.set_rumble(haptics.weak_magnitude, haptics.strong_magnitude)
+            });
+            send_log(
+                &event_sender,
+                "debug",
+                format!(
+                    "Server haptics: controller={}, weak={}, strong={}.",
+                    haptics.controller_id, haptics.weak_magnitude, haptics.strong_magnitude
+                ),
+            );
+            return;
+        }
+        Ok(ServerInputMessage::HapticsIdle | ServerInputMessage::Ignored { .. }) => return,
+        Err(ProtocolError::NotAHandshake) => return,
+        Err(error) => {
+            send_log(
+                &event_sender,
+                "debug",
+                format!(
+                    "Ignoring input message on {}: {error}.",
+                    channel_label(channel)
+                ),
+            );
+            return;
+        }
     };
 
-    let encoder_version = protocol_version.min(u8::MAX as u16) as u8;
     if let Ok(mut encoder) = input_state.encoder.lock() {
-        encoder.set_protocol_version(encoder_version);
+        encoder.set_protocol_version(encoder_protocol_version(protocol_version));
     }
     let was_ready = input_state.ready.swap(true, Ordering::SeqCst);
     if was_ready {
@@ -951,25 +1584,23 @@ fn handle_input_handshake_message(
     start_input_heartbeat(input_state, channel.clone(), event_sender);
 }
 
-pub(crate) fn parse_input_handshake_version(bytes: &[u8]) -> Option<u16> {
-    if bytes.len() < 2 {
-        return None;
-    }
-
-    let first_word = u16::from_le_bytes([bytes[0], bytes[1]]);
-    if first_word == 526 {
-        return Some(if bytes.len() >= 4 {
-            u16::from_le_bytes([bytes[2], bytes[3]])
-        } else {
-            2
-        });
-    }
-
-    if bytes[0] == 0x0e {
-        return Some(first_word);
+fn handle_control_channel_message(text: &str, event_sender: &Option<Sender<Event>>) {
+    match ControlMessage::decode(text) {
+        Ok(ControlMessage::TimerNotification { code, seconds_left }) => send_log(
+            event_sender,
+            "info",
+            format!(
+                "Control timer notification: code={code}, secondsLeft={}.",
+                seconds_left.map_or_else(|| "n/a".to_owned(), |seconds| seconds.to_string())
+            ),
+        ),
+        Ok(_) => {}
+        Err(error) => send_log(
+            event_sender,
+            "debug",
+            format!("Ignoring {CONTROL_CHANNEL_LABEL} message: {error}."),
+        ),
     }
-
-    None
 }
 
 fn start_input_heartbeat(
@@ -0,0 +1,137 @@
+use crate::gstreamer_backend::send_log;
+use crate::gstreamer_pipeline::set_property_if_supported;
+use crate::protocol::Event;
+use gst::prelude::*;
+use gstreamer as gst;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct JitterBufferSnapshot {
+    pub(crate) packets_received: u64,
+    pub(crate) packets_lost: u64,
+    pub(crate) packets_late: u64,
+    pub(crate) jitter_ms: f64,
+}
+
+impl JitterBufferSnapshot {
+    pub(crate) fn from_stats(stats: &gst::StructureRef) -> Self {
+        let counter = |name: &str| stats.get::<u64>(name).unwrap_or(0);
+        Self {
+            packets_received: counter("num-pushed"),
+            packets_lost: counter("num-lost"),
+            packets_late: counter("num-late"),
+            jitter_ms: counter("avg-jitter") as f64 / 1_000_000.0,
+        }
+    }
+}
+
+/// Keeps track of the rtpjitterbuffer instances webrtcbin creates for each
+/// incoming SSRC so their reordering/loss counters can be polled for stats.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct JitterBufferMonitor {
+    buffers: Arc<Mutex<Vec<gst::Element>>>,
+    target_ms: Arc<AtomicU32>,
+}
+
+impl JitterBufferMonitor {
+    pub(crate) fn wire(
+        &self,
+        webrtc: &gst::Element,
+        target_ms: u32,
+        event_sender: &Option<Sender<Event>>,
+    ) {
+        self.target_ms.store(target_ms, Ordering::Relaxed);
+        let Some(rtpbin) = webrtc
+            .downcast_ref::<gst::Bin>()
+            .and_then(|bin| bin.by_name("rtpbin"))
+        else {
+            send_log(
+                event_sender,
+                "warn",
+                "webrtcbin has no rtpbin; native jitter buffer stats are unavailable.".to_owned(),
+            );
+            return;
+        };
+
+        let buffers = self.buffers.clone();
+        let target_ms = self.target_ms.clone();
+        let sender = event_sender.clone();
+        rtpbin.connect("new-jitterbuffer", false, move |values| {
+            let jitterbuffer = values
+                .get(1)
+                .and_then(|value| value.get::<gst::Element>().ok());
+            let ssrc = values.get(3).and_then(|value| value.get::<u32>().ok());
+            if let Some(jitterbuffer) = jitterbuffer {
+                // Lost-packet events let the depayloader skip a gap immediately,
+                // and dropping on latency keeps a burst of late packets from
+                // growing the buffer past the configured target.
+                set_property_if_supported(&jitterbuffer, "do-lost", true);
+                set_property_if_supported(&jitterbuffer, "drop-on-latency", true);
+                if let Ok(mut buffers) = buffers.lock() {
+                    buffers.push(jitterbuffer);
+                }
+                send_log(
+                    &sender,
+                    "info",
+                    format!(
+                        "Native jitter buffer created for SSRC {} with {} ms target delay.",
+                        ssrc.map(|ssrc| format!("{ssrc:#010x}"))
+                            .unwrap_or_else(|| "unknown".to_owned()),
+                        target_ms.load(Ordering::Relaxed)
+                    ),
+                );
+            }
+            None
+        });
+    }
+
+    /// Jitter buffers created from now on inherit webrtcbin's latency, which
+    /// the caller updates alongside this.
+    pub(crate) fn set_target_ms(&self, target_ms: u32) {
+        self.target_ms.store(target_ms, Ordering::Relaxed);
+    }
+
+    pub(crate) fn target_ms(&self) -> u32 {
+        self.target_ms.load(Ordering::Relaxed)
+    }
+
+    /// Sums the counters of every jitter buffer carrying video.
+    pub(crate) fn video_snapshot(&self) -> Option<JitterBufferSnapshot> {
+        let buffers = self.buffers.lock().ok()?;
+        let snapshots = buffers
+            .iter()
+            .filter(|jitterbuffer| {
+                jitterbuffer
+                    .static_pad("src")
+                    .and_then(|pad| pad.current_caps())
+                    .is_some_and(|caps| caps.to_string().contains("media=(string)video"))
+            })
+            .filter_map(|jitterbuffer| {
+                jitterbuffer
+                    .property_value("stats")
+                    .get::<gst::Structure>()
+                    .ok()
+                    .map(|stats| JitterBufferSnapshot::from_stats(&stats))
+            })
+            .collect::<Vec<_>>();
+        merge_jitter_buffer_snapshots(&snapshots)
+    }
+}
+
+pub(crate) fn merge_jitter_buffer_snapshots(
+    snapshots: &[JitterBufferSnapshot],
+) -> Option<JitterBufferSnapshot> {
+    if snapshots.is_empty() {
+        return None;
+    }
+    let mut merged = JitterBufferSnapshot::default();
+    for snapshot in snapshots {
+        merged.packets_received += snapshot.packets_received;
+        merged.packets_lost += snapshot.packets_lost;
+        merged.packets_late += snapshot.packets_late;
+        merged.jitter_ms = merged.jitter_ms.max(snapshot.jitter_ms);
+    }
+    Some(merged)
+}
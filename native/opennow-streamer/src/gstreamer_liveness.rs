@@ -1,20 +1,40 @@
+use crate::bitstream_health::BitstreamHealth;
+use crate::gstreamer_audio_output::AudioOutputMonitor;
+use crate::gstreamer_av_sync::AvSyncMonitor;
 use crate::gstreamer_backend::send_log;
-use crate::gstreamer_config::use_external_renderer_window;
+use crate::gstreamer_config::{av_sync_mode, use_external_renderer_window};
+use crate::gstreamer_jitter::JitterBufferMonitor;
 use crate::gstreamer_pipeline::{configure_queue, set_property_if_supported};
+use crate::gstreamer_stages::PipelineStageTimer;
 use crate::gstreamer_transitions::{
     format_transition_summary, resolve_queue_mode, TransitionSnapshot, TransitionTelemetry,
     DEFAULT_VIDEO_QUEUE_DEPTH,
 };
-use crate::protocol::{Event, NativeQueueMode, NativeStreamerSessionContext, VideoStallEvent};
+use crate::late_frames::present_fps_limit;
+use crate::memory_budget::{
+    available_system_memory_bytes, resolve_memory_budget_bytes, MemoryBudget,
+};
+use crate::protocol::{
+    Event, NativeQueueMode, NativeStreamerSessionContext, VideoRecoveryEvent, VideoStallEvent,
+};
+use crate::queue_backlog::{QueueBacklogMonitor, QueueLevel, SkipAhead};
+use crate::sei::{encoder_latency_ms, NalFraming, SeiCodec, SeiMessages, SeiParser};
+use crate::thread_priority::{format_thread_priority_summary, MediaThreadPriority};
 use gst::prelude::*;
 use gstreamer as gst;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 pub(crate) const VIDEO_SINK_RATE_LOG_INTERVAL: Duration = Duration::from_secs(1);
+/// Low-latency mode refreshes the overlay and stats panel every this many
+/// rate intervals, so text rendering and IPC stay off most presented frames.
+const LOW_LATENCY_STATS_DECIMATION: u32 = 4;
+/// Depth the present queue grows to after a transition flush, unless
+/// low-latency mode holds it at one frame.
+const TRANSITION_FLUSH_QUEUE_DEPTH: u32 = 2;
 const VIDEO_STALL_WARNING_MS: u64 = 2_500;
 const VIDEO_STALL_SECOND_ATTEMPT_MS: u64 = 5_000;
 const VIDEO_STALL_RESYNC_MS: u64 = 8_000;
@@ -26,6 +46,17 @@ const VIDEO_STARTUP_KEYFRAME_MS: u64 = 2_500;
 const VIDEO_STARTUP_RESYNC_MS: u64 = 5_000;
 const VIDEO_STARTUP_FATAL_MS: u64 = 8_000;
 const VIDEO_LIVENESS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// How long after a mid-stream resolution switch the decoder is left to
+/// renegotiate before a stall may escalate to flushing the decode chain.
+const RESOLUTION_CHANGE_GRACE_MS: u64 = 4_000;
+
+/// Last clock timestamp the encoder attached to a picture.
+#[derive(Debug, Clone, PartialEq)]
+struct EncoderClock {
+    timecode: String,
+    /// Only known when the time code follows the wall clock.
+    latency_ms: Option<u64>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub(crate) struct VideoRateSnapshot {
@@ -45,6 +76,37 @@ pub(crate) enum VideoStallAction {
     Recovered { stall_ms: u64 },
 }
 
+/// Recovery steps the stall watchdog applies, in escalation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VideoRecoveryStep {
+    Keyframe,
+    Resync,
+    PartialFlush,
+    DecoderReset,
+    /// Nothing left to try in the pipeline; the host tears the stream down
+    /// on the fatal error and reconnects.
+    Reconnect,
+}
+
+impl VideoRecoveryStep {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Keyframe => "keyframe",
+            Self::Resync => "resync",
+            Self::PartialFlush => "partial-flush",
+            Self::DecoderReset => "decoder-reset",
+            Self::Reconnect => "reconnect",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AppliedVideoRecovery {
+    step: VideoRecoveryStep,
+    attempt: u8,
+    likely_stage: &'static str,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct VideoStallTracker {
     in_stall: bool,
@@ -124,6 +186,9 @@ pub(crate) struct VideoLivenessState {
     hardware_acceleration: Mutex<String>,
     memory_mode: Mutex<String>,
     caps_framerate: Mutex<Option<String>>,
+    hdr_metadata: Mutex<Option<String>>,
+    encoder_clock: Mutex<Option<EncoderClock>>,
+    thread_priorities: Mutex<Vec<MediaThreadPriority>>,
     requested_streaming_features_summary: Mutex<String>,
     finalized_streaming_features_summary: Mutex<String>,
     transition_telemetry: Mutex<TransitionTelemetry>,
@@ -131,6 +196,12 @@ pub(crate) struct VideoLivenessState {
     pre_decode_queue: Mutex<Option<gst::Element>>,
     decoder: Mutex<Option<gst::Element>>,
     post_decode_queue: Mutex<Option<gst::Element>>,
+    pipeline_stages: PipelineStageTimer,
+    bitstream: BitstreamHealth,
+    jitter_buffers: JitterBufferMonitor,
+    av_sync: AvSyncMonitor,
+    audio_output: AudioOutputMonitor,
+    memory_budget: MemoryBudget,
     stats_overlay_visible: AtomicBool,
     target_bitrate_kbps: AtomicU32,
     encoded_bytes_total: AtomicU64,
@@ -146,7 +217,12 @@ pub(crate) struct VideoLivenessState {
     rtp_video_src_pad: Mutex<Option<gst::Pad>>,
     requested_fps: AtomicU32,
     framerate_mismatch_warned: AtomicBool,
+    resolution_changed_ms: AtomicU64,
+    /// Bumped whenever `resolution` changes, so per-frame code can notice
+    /// without taking the lock.
+    resolution_generation: AtomicU64,
     transition_flush_escalation_enabled: AtomicBool,
+    low_latency: AtomicBool,
     first_encoded_logged: AtomicBool,
     startup_keyframe_requested: AtomicBool,
     startup_resync_requested: AtomicBool,
@@ -162,6 +238,9 @@ impl VideoLivenessState {
             hardware_acceleration: Mutex::new(String::new()),
             memory_mode: Mutex::new("system-memory".to_owned()),
             caps_framerate: Mutex::new(None),
+            hdr_metadata: Mutex::new(None),
+            encoder_clock: Mutex::new(None),
+            thread_priorities: Mutex::new(Vec::new()),
             requested_streaming_features_summary: Mutex::new("none".to_owned()),
             finalized_streaming_features_summary: Mutex::new("none".to_owned()),
             transition_telemetry: Mutex::new(TransitionTelemetry::default()),
@@ -169,6 +248,12 @@ impl VideoLivenessState {
             pre_decode_queue: Mutex::new(None),
             decoder: Mutex::new(None),
             post_decode_queue: Mutex::new(None),
+            pipeline_stages: PipelineStageTimer::default(),
+            bitstream: BitstreamHealth::default(),
+            jitter_buffers: JitterBufferMonitor::default(),
+            av_sync: AvSyncMonitor::default(),
+            audio_output: AudioOutputMonitor::default(),
+            memory_budget: MemoryBudget::default(),
             stats_overlay_visible: AtomicBool::new(false),
             target_bitrate_kbps: AtomicU32::new(0),
             encoded_bytes_total: AtomicU64::new(0),
@@ -184,7 +269,10 @@ impl VideoLivenessState {
             rtp_video_src_pad: Mutex::new(None),
             requested_fps: AtomicU32::new(0),
             framerate_mismatch_warned: AtomicBool::new(false),
+            resolution_changed_ms: AtomicU64::new(0),
+            resolution_generation: AtomicU64::new(0),
             transition_flush_escalation_enabled: AtomicBool::new(true),
+            low_latency: AtomicBool::new(false),
             first_encoded_logged: AtomicBool::new(false),
             startup_keyframe_requested: AtomicBool::new(false),
             startup_resync_requested: AtomicBool::new(false),
@@ -211,9 +299,11 @@ impl VideoLivenessState {
         if let Ok(mut resolution) = self.resolution.lock() {
             *resolution = settings.resolution.clone();
         }
+        self.resolution_generation.fetch_add(1, Ordering::Relaxed);
         if let Ok(mut caps_framerate) = self.caps_framerate.lock() {
             *caps_framerate = None;
         }
+        self.bitstream.reset();
         if let Ok(mut requested_summary) = self.requested_streaming_features_summary.lock() {
             *requested_summary = context
                 .session
@@ -237,6 +327,7 @@ impl VideoLivenessState {
             telemetry.present_pacing_changes = 0;
             telemetry.partial_flush_count = 0;
             telemetry.complete_flush_count = 0;
+            telemetry.frames_skipped_ahead = 0;
             telemetry.last_transition = None;
         }
         self.target_bitrate_kbps
@@ -244,6 +335,15 @@ impl VideoLivenessState {
         self.requested_fps.store(settings.fps, Ordering::Relaxed);
         self.framerate_mismatch_warned
             .store(false, Ordering::Relaxed);
+        self.resolution_changed_ms.store(0, Ordering::Relaxed);
+        self.av_sync.configure(av_sync_mode());
+        self.audio_output
+            .configure(settings.audio_output.unwrap_or_default());
+        self.memory_budget.configure(
+            resolve_memory_budget_bytes(available_system_memory_bytes()),
+            &settings.resolution,
+            settings.color_quality,
+        );
         self.first_encoded_logged.store(false, Ordering::Relaxed);
         self.first_startup_audio_ms.store(0, Ordering::Relaxed);
         self.transition_flush_escalation_enabled.store(
@@ -254,6 +354,8 @@ impl VideoLivenessState {
                 .unwrap_or(true),
             Ordering::Relaxed,
         );
+        self.low_latency
+            .store(settings.low_latency, Ordering::Relaxed);
         self.startup_keyframe_requested
             .store(false, Ordering::Relaxed);
         self.startup_resync_requested
@@ -341,6 +443,85 @@ impl VideoLivenessState {
         }
     }
 
+    /// Keeps the latest HDR metadata and encoder clock timestamp. Returns the
+    /// new HDR summary when it changed; access units without HDR messages
+    /// keep the previous one.
+    fn record_sei(&self, messages: &SeiMessages) -> Option<String> {
+        if let Some(timestamp) = messages.clock_timestamp {
+            let latency_ms = self.requested_fps().and_then(|fps| {
+                let local_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .ok()?
+                    .as_millis();
+                encoder_latency_ms(timestamp.millis_of_day(fps), local_ms as u64)
+            });
+            if let Ok(mut clock) = self.encoder_clock.lock() {
+                *clock = Some(EncoderClock {
+                    timecode: timestamp.label(),
+                    latency_ms,
+                });
+            }
+        }
+        let summary = messages.hdr_metadata_summary()?;
+        let mut current = self.hdr_metadata.lock().ok()?;
+        if current.as_ref() == Some(&summary) {
+            return None;
+        }
+        *current = Some(summary.clone());
+        Some(summary)
+    }
+
+    fn encoder_clock(&self) -> Option<EncoderClock> {
+        self.encoder_clock
+            .lock()
+            .map(|clock| clock.clone())
+            .unwrap_or_default()
+    }
+
+    /// Tracks the coded size the parser reports. Returns the previous and new
+    /// resolution when the server switched mid-stream.
+    fn update_stream_resolution(&self, caps: &str) -> Option<(String, String)> {
+        let current = caps_resolution_summary(caps)?;
+        let mut resolution = self.resolution.lock().ok()?;
+        if *resolution == current {
+            return None;
+        }
+        let previous = std::mem::replace(&mut *resolution, current.clone());
+        self.resolution_changed_ms
+            .store(self.now_ms().max(1), Ordering::Relaxed);
+        self.resolution_generation.fetch_add(1, Ordering::Relaxed);
+        self.memory_budget.update_resolution(&current);
+        Some((previous, current))
+    }
+
+    fn resolution_change_pending(&self, now_ms: u64) -> bool {
+        let changed_ms = self.resolution_changed_ms.load(Ordering::Relaxed);
+        changed_ms > 0 && now_ms.saturating_sub(changed_ms) < RESOLUTION_CHANGE_GRACE_MS
+    }
+
+    /// A restarted streaming thread replaces the earlier report for its role.
+    fn record_thread_priority(&self, priority: MediaThreadPriority) {
+        if let Ok(mut threads) = self.thread_priorities.lock() {
+            threads.retain(|thread| thread.role != priority.role);
+            threads.push(priority);
+            threads.sort_by_key(|thread| thread.role.label());
+        }
+    }
+
+    fn thread_priority_summary(&self) -> Option<String> {
+        self.thread_priorities
+            .lock()
+            .ok()
+            .and_then(|threads| format_thread_priority_summary(&threads))
+    }
+
+    fn hdr_metadata(&self) -> Option<String> {
+        self.hdr_metadata
+            .lock()
+            .map(|metadata| metadata.clone())
+            .unwrap_or_default()
+    }
+
     fn zero_copy_d3d11(&self) -> bool {
         self.zero_copy_d3d11.load(Ordering::Relaxed)
     }
@@ -371,6 +552,13 @@ impl VideoLivenessState {
         (fps > 0).then_some(fps)
     }
 
+    fn resolution(&self) -> String {
+        self.resolution
+            .lock()
+            .map(|resolution| resolution.clone())
+            .unwrap_or_default()
+    }
+
     fn caps_framerate(&self) -> Option<String> {
         self.caps_framerate
             .lock()
@@ -421,6 +609,13 @@ impl VideoLivenessState {
             .and_then(|current| current.clone())
     }
 
+    fn post_decode_queue(&self) -> Option<gst::Element> {
+        self.post_decode_queue
+            .lock()
+            .ok()
+            .and_then(|current| current.clone())
+    }
+
     fn decoder(&self) -> Option<gst::Element> {
         self.decoder.lock().ok().and_then(|current| current.clone())
     }
@@ -431,6 +626,8 @@ impl VideoLivenessState {
         reason: &str,
         event_sender: &Option<Sender<Event>>,
     ) {
+        let requested_buffers = max_buffers;
+        let max_buffers = self.memory_budget.max_queued_frames(requested_buffers);
         let queue = self
             .post_decode_queue
             .lock()
@@ -450,10 +647,17 @@ impl VideoLivenessState {
         }
 
         if should_log {
+            let budget_note = if max_buffers < requested_buffers {
+                format!(", capped from {requested_buffers} by the memory budget")
+            } else {
+                String::new()
+            };
             send_log(
                 event_sender,
                 "info",
-                format!("Adjusted native post-decode queue depth to {max_buffers} ({reason})."),
+                format!(
+                    "Adjusted native post-decode queue depth to {max_buffers} ({reason}{budget_note})."
+                ),
             );
         }
     }
@@ -471,6 +675,22 @@ impl VideoLivenessState {
         }
     }
 
+    fn stats_decimation(&self) -> u32 {
+        if self.low_latency.load(Ordering::Relaxed) {
+            LOW_LATENCY_STATS_DECIMATION
+        } else {
+            1
+        }
+    }
+
+    fn transition_flush_queue_depth(&self) -> u32 {
+        if self.low_latency.load(Ordering::Relaxed) {
+            DEFAULT_VIDEO_QUEUE_DEPTH
+        } else {
+            TRANSITION_FLUSH_QUEUE_DEPTH
+        }
+    }
+
     fn transition_flush_escalation_enabled(&self) -> bool {
         self.transition_flush_escalation_enabled
             .load(Ordering::Relaxed)
@@ -569,6 +789,14 @@ impl VideoLivenessState {
             telemetry.complete_flush_count = telemetry.complete_flush_count.saturating_add(1);
         }
     }
+
+    fn record_skipped_ahead(&self, frames: u32) {
+        if let Ok(mut telemetry) = self.transition_telemetry.lock() {
+            telemetry.frames_skipped_ahead = telemetry
+                .frames_skipped_ahead
+                .saturating_add(u64::from(frames));
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -635,6 +863,22 @@ impl VideoLivenessMonitor {
         self.state.set_rtp_video_src_pad(pad);
     }
 
+    pub(crate) fn request_keyframe(&self, event_sender: &Option<Sender<Event>>) {
+        request_upstream_key_unit(&self.state, event_sender);
+    }
+
+    pub(crate) fn record_sei(&self, messages: &SeiMessages) -> Option<String> {
+        self.state.record_sei(messages)
+    }
+
+    pub(crate) fn record_thread_priority(&self, priority: MediaThreadPriority) {
+        self.state.record_thread_priority(priority);
+    }
+
+    pub(crate) fn update_stream_resolution(&self, caps: &str) -> Option<(String, String)> {
+        self.state.update_stream_resolution(caps)
+    }
+
     pub(crate) fn set_post_decode_queue(&self, queue: gst::Element) {
         self.state.set_post_decode_queue(queue);
     }
@@ -647,6 +891,16 @@ impl VideoLivenessMonitor {
         self.state.set_decoder(decoder);
     }
 
+    /// Counts a bus message from the video decoder that reports a decode error.
+    pub(crate) fn record_decoder_error(&self, source: Option<&gst::Object>) {
+        let from_decoder = source
+            .zip(self.state.decoder())
+            .is_some_and(|(source, decoder)| source == decoder.upcast_ref::<gst::Object>());
+        if from_decoder {
+            self.state.bitstream.record_decoder_error();
+        }
+    }
+
     pub(crate) fn log_first_encoded_once(&self) -> bool {
         self.state.log_first_encoded_once()
     }
@@ -655,6 +909,30 @@ impl VideoLivenessMonitor {
         self.state.requested_fps()
     }
 
+    pub(crate) fn resolution(&self) -> String {
+        self.state.resolution()
+    }
+
+    pub(crate) fn resolution_generation(&self) -> u64 {
+        self.state.resolution_generation.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn pipeline_stages(&self) -> PipelineStageTimer {
+        self.state.pipeline_stages.clone()
+    }
+
+    pub(crate) fn jitter_buffers(&self) -> JitterBufferMonitor {
+        self.state.jitter_buffers.clone()
+    }
+
+    pub(crate) fn av_sync(&self) -> AvSyncMonitor {
+        self.state.av_sync.clone()
+    }
+
+    pub(crate) fn audio_output(&self) -> AudioOutputMonitor {
+        self.state.audio_output.clone()
+    }
+
     pub(crate) fn warn_framerate_mismatch_once(&self) -> bool {
         self.state.warn_framerate_mismatch_once()
     }
@@ -731,7 +1009,10 @@ fn run_video_liveness_watchdog(
     event_sender: Option<Sender<Event>>,
 ) {
     let mut tracker = VideoStallTracker::default();
+    let mut backlog = QueueBacklogMonitor::default();
+    let mut last_recovery: Option<AppliedVideoRecovery> = None;
     let mut last_rate_at = Instant::now();
+    let mut rate_intervals = 0u32;
     let mut last_encoded_bytes_total = state.encoded_bytes_total.load(Ordering::Relaxed);
     let mut last_decoded_total = state.decoded_total.load(Ordering::Relaxed);
     let mut last_sink_total = state.sink_total.load(Ordering::Relaxed);
@@ -760,23 +1041,26 @@ fn run_video_liveness_watchdog(
                 decoded_fps: decoded_total.saturating_sub(last_decoded_total) as f64 / elapsed_secs,
                 sink_fps: sink_total.saturating_sub(last_sink_total) as f64 / elapsed_secs,
             };
-            update_native_stats_overlay(
-                &sink,
-                &state,
-                rates.encoded_kbps.round() as u32,
-                rates,
-                decoded_total,
-                sink_total,
-            );
-            emit_native_stats_event(
-                &event_sender,
-                &sink,
-                &state,
-                rates.encoded_kbps.round() as u32,
-                rates,
-                decoded_total,
-                sink_total,
-            );
+            rate_intervals = rate_intervals.wrapping_add(1);
+            if rate_intervals.is_multiple_of(state.stats_decimation()) {
+                update_native_stats_overlay(
+                    &sink,
+                    &state,
+                    rates.encoded_kbps.round() as u32,
+                    rates,
+                    decoded_total,
+                    sink_total,
+                );
+                emit_native_stats_event(
+                    &event_sender,
+                    &sink,
+                    &state,
+                    rates.encoded_kbps.round() as u32,
+                    rates,
+                    decoded_total,
+                    sink_total,
+                );
+            }
             last_encoded_bytes_total = encoded_bytes_total;
             last_decoded_total = decoded_total;
             last_sink_total = sink_total;
@@ -794,13 +1078,32 @@ fn run_video_liveness_watchdog(
         let decoded_age_ms = age_since_ms(now_ms, state.last_decoded_ms.load(Ordering::Relaxed));
         let sink_age_ms = age_since_ms(now_ms, last_sink_ms);
         let likely_stage = classify_video_stall(encoded_age_ms, decoded_age_ms, sink_age_ms);
+        // Skip-ahead is for a pipeline that is still presenting but falling
+        // behind; a stalled decoder also fills the queues, and the stall
+        // recovery below owns that case.
+        if sink_age_ms.is_some_and(|age| age <= 1_000) && !state.resolution_change_pending(now_ms) {
+            let compressed = queue_level(state.pre_decode_queue());
+            let decoded = queue_level(state.post_decode_queue());
+            match backlog.evaluate(now_ms, compressed, decoded) {
+                SkipAhead::None => {}
+                action => skip_ahead(&state, &event_sender, action, compressed, decoded),
+            }
+        }
+        // A resolution switch stalls the decoder while it reallocates its
+        // surfaces; flushing then would drop the new IDR and freeze the stream.
         let transition_stall = likely_stage == "decode-chain-stalled"
-            && encoded_age_ms.is_some_and(|age| age <= 1_000);
+            && encoded_age_ms.is_some_and(|age| age <= 1_000)
+            && !state.resolution_change_pending(now_ms);
 
         match tracker.evaluate(now_ms, last_sink_ms) {
             VideoStallAction::None => {}
             VideoStallAction::RequestKeyframe { attempt, stall_ms } => {
                 request_upstream_key_unit(&state, &event_sender);
+                last_recovery = Some(AppliedVideoRecovery {
+                    step: VideoRecoveryStep::Keyframe,
+                    attempt,
+                    likely_stage,
+                });
                 emit_video_stall_event(
                     &event_sender,
                     &sink,
@@ -813,6 +1116,11 @@ fn run_video_liveness_watchdog(
             }
             VideoStallAction::Resync { attempt, stall_ms } => {
                 request_upstream_key_unit(&state, &event_sender);
+                last_recovery = Some(AppliedVideoRecovery {
+                    step: VideoRecoveryStep::Resync,
+                    attempt,
+                    likely_stage,
+                });
                 emit_video_stall_event(
                     &event_sender,
                     &sink,
@@ -841,6 +1149,11 @@ fn run_video_liveness_watchdog(
                 if transition_stall && state.transition_flush_escalation_enabled() {
                     request_upstream_key_unit(&state, &event_sender);
                     perform_transition_flush(&state, &event_sender, TransitionFlushKind::Partial);
+                    last_recovery = Some(AppliedVideoRecovery {
+                        step: VideoRecoveryStep::PartialFlush,
+                        attempt,
+                        likely_stage,
+                    });
                 }
                 emit_video_stall_event(
                     &event_sender,
@@ -856,6 +1169,11 @@ fn run_video_liveness_watchdog(
                 if transition_stall && state.transition_flush_escalation_enabled() {
                     request_upstream_key_unit(&state, &event_sender);
                     perform_transition_flush(&state, &event_sender, TransitionFlushKind::Complete);
+                    last_recovery = Some(AppliedVideoRecovery {
+                        step: VideoRecoveryStep::DecoderReset,
+                        attempt,
+                        likely_stage,
+                    });
                 }
                 emit_video_stall_event(
                     &event_sender,
@@ -881,11 +1199,17 @@ fn run_video_liveness_watchdog(
                     &event_sender,
                     "error",
                     format!(
-                        "Native video stall recovery exhausted after {stall_ms}ms; stage={likely_stage} queueMode={} transitionFlushEscalation={}.",
+                        "Native video stall recovery exhausted after {stall_ms}ms; stage={likely_stage} queueMode={} transitionFlushEscalation={}. Requesting a full reconnect.",
                         state.queue_mode().as_str(),
                         state.transition_flush_escalation_enabled(),
                     ),
                 );
+                let recovery = AppliedVideoRecovery {
+                    step: VideoRecoveryStep::Reconnect,
+                    attempt,
+                    likely_stage,
+                };
+                emit_video_recovery_event(&event_sender, "escalated", Some(recovery), stall_ms);
                 if let Some(event_sender) = &event_sender {
                     let _ = event_sender.send(Event::Error {
                         code: "native-video-stall-fatal".to_owned(),
@@ -903,16 +1227,47 @@ fn run_video_liveness_watchdog(
                         &event_sender,
                     );
                 }
-                send_log(
+                emit_video_recovery_event(
                     &event_sender,
-                    "info",
-                    format!("Native video recovered after {stall_ms} ms."),
+                    "recovered",
+                    last_recovery.take(),
+                    stall_ms,
                 );
             }
         }
     }
 }
 
+fn emit_video_recovery_event(
+    event_sender: &Option<Sender<Event>>,
+    outcome: &'static str,
+    recovery: Option<AppliedVideoRecovery>,
+    stall_ms: u64,
+) {
+    let step = recovery.map(|recovery| recovery.step.as_str());
+    let attempt = recovery.map_or(0, |recovery| recovery.attempt);
+    let likely_stage = recovery.map_or("unknown", |recovery| recovery.likely_stage);
+    // The host logs the structured event; only fall back to a log line without one.
+    let Some(sender) = event_sender else {
+        send_log(
+            event_sender,
+            "info",
+            format!(
+                "Native video stall {outcome} after {stall_ms} ms; step={} attempt={attempt} stage={likely_stage}.",
+                step.unwrap_or("none"),
+            ),
+        );
+        return;
+    };
+    let _ = sender.send(Event::VideoRecovery(VideoRecoveryEvent {
+        outcome,
+        step,
+        recovery_attempt: attempt,
+        stall_ms,
+        likely_stage: likely_stage.to_owned(),
+    }));
+}
+
 fn maybe_recover_video_startup(
     state: &VideoLivenessState,
     pipeline: &gst::Pipeline,
@@ -1016,6 +1371,7 @@ fn perform_transition_flush(
         TransitionFlushKind::Partial | TransitionFlushKind::Complete
     ) {
         if let Some(queue) = state.pre_decode_queue() {
+            state.bitstream.expect_flush_discontinuity();
             flush_element(&queue);
             flushed.push("pre-decode queue");
         }
@@ -1051,11 +1407,19 @@ fn perform_transition_flush(
     match flush_kind {
         TransitionFlushKind::Partial => {
             state.increment_partial_flush_count();
-            state.set_queue_depth(2, "transition partial flush", event_sender);
+            state.set_queue_depth(
+                state.transition_flush_queue_depth(),
+                "transition partial flush",
+                event_sender,
+            );
         }
         TransitionFlushKind::Complete => {
             state.increment_complete_flush_count();
-            state.set_queue_depth(2, "transition complete flush", event_sender);
+            state.set_queue_depth(
+                state.transition_flush_queue_depth(),
+                "transition complete flush",
+                event_sender,
+            );
         }
     }
 
@@ -1069,6 +1433,51 @@ fn perform_transition_flush(
     );
 }
 
+fn queue_level(queue: Option<gst::Element>) -> QueueLevel {
+    queue
+        .filter(|queue| {
+            queue.find_property("current-level-buffers").is_some()
+                && queue.find_property("max-size-buffers").is_some()
+        })
+        .map(|queue| QueueLevel {
+            buffers: queue.property::<u32>("current-level-buffers"),
+            capacity: queue.property::<u32>("max-size-buffers"),
+        })
+        .unwrap_or_default()
+}
+
+fn skip_ahead(
+    state: &VideoLivenessState,
+    event_sender: &Option<Sender<Event>>,
+    action: SkipAhead,
+    compressed: QueueLevel,
+    decoded: QueueLevel,
+) {
+    let (queue, skipped, label) = match action {
+        SkipAhead::None => return,
+        SkipAhead::DropDecoded => (state.post_decode_queue(), decoded.buffers, "decoded"),
+        SkipAhead::DropToKeyframe => (state.pre_decode_queue(), compressed.buffers, "compressed"),
+    };
+    let Some(queue) = queue else {
+        return;
+    };
+    if action == SkipAhead::DropToKeyframe {
+        state.bitstream.expect_flush_discontinuity();
+    }
+    flush_element(&queue);
+    if action == SkipAhead::DropToKeyframe {
+        request_upstream_key_unit(state, event_sender);
+    }
+    state.record_skipped_ahead(skipped);
+    send_log(
+        event_sender,
+        "warn",
+        format!(
+            "Skipped ahead past {skipped} backed-up {label} video frame(s) to keep latency flat."
+        ),
+    );
+}
+
 fn flush_element(element: &gst::Element) {
     let _ = element.send_event(gst::event::FlushStart::new());
     let _ = element.send_event(gst::event::FlushStop::new(false));
@@ -1131,11 +1540,7 @@ fn emit_native_stats_event(
         .lock()
         .map(|codec| codec.clone())
         .unwrap_or_default();
-    let resolution = state
-        .resolution
-        .lock()
-        .map(|resolution| resolution.clone())
-        .unwrap_or_default();
+    let resolution = state.resolution();
     let hardware_acceleration = state
         .hardware_acceleration
         .lock()
@@ -1143,8 +1548,14 @@ fn emit_native_stats_event(
         .unwrap_or_default();
     let sink_stats = read_sink_stats(sink);
     let telemetry = state.transition_telemetry_snapshot();
+    let jitter = state.jitter_buffers.video_snapshot();
+    let av_sync = state.av_sync.snapshot();
+    let stage_report = state.pipeline_stages.take_report();
+    let bitstream = state.bitstream.snapshot();
+    let audio_output = state.audio_output.snapshot();
+    let encoder_clock = state.encoder_clock();
     let _ = event_sender.send(Event::Stats {
-        stats: crate::protocol::NativeStatsEvent {
+        stats: Box::new(crate::protocol::NativeStatsEvent {
             codec,
             resolution,
             hardware_acceleration,
@@ -1167,6 +1578,7 @@ fn emit_native_stats_event(
             present_pacing_changes: telemetry.present_pacing_changes,
             partial_flush_count: telemetry.partial_flush_count,
             complete_flush_count: telemetry.complete_flush_count,
+            frames_skipped_ahead: telemetry.frames_skipped_ahead,
             last_transition_type: telemetry
                 .last_transition
                 .as_ref()
@@ -1183,10 +1595,38 @@ fn emit_native_stats_event(
             finalized_streaming_features_summary: state.finalized_streaming_features_summary(),
             zero_copy_d3d11: state.zero_copy_d3d11(),
             zero_copy_d3d12: state.zero_copy_d3d12(),
-        },
+            pipeline_stage_summary: stage_report.summary,
+            decode_time_ms: stage_report.decode_ms,
+            render_time_ms: stage_report.render_ms,
+            keyframes_received: bitstream.keyframes,
+            keyframe_interval_ms: bitstream.keyframe_interval_ms,
+            damaged_frames: bitstream.damaged_frames,
+            decoder_errors: bitstream.decoder_errors,
+            hdr_metadata: state.hdr_metadata(),
+            encoder_timecode: encoder_clock.as_ref().map(|clock| clock.timecode.clone()),
+            encoder_latency_ms: encoder_clock.and_then(|clock| clock.latency_ms),
+            jitter_buffer_target_ms: state.jitter_buffers.target_ms(),
+            jitter_ms: jitter.map(|jitter| jitter.jitter_ms),
+            packets_received: jitter.map(|jitter| jitter.packets_received),
+            packets_lost: jitter.map(|jitter| jitter.packets_lost),
+            packets_late: jitter.map(|jitter| jitter.packets_late),
+            av_sync_offset_ms: av_sync.and_then(|av_sync| av_sync.offset_ms),
+            av_sync_audio_correction_ms: av_sync.map(|av_sync| av_sync.audio_correction_ms),
+            audio_output_latency_ms: audio_output.latency_ms,
+            audio_underruns: audio_output.underruns,
+            audio_codec: audio_output.codec,
+            audio_sample_rate: audio_output.sample_rate,
+            audio_channels: audio_output.channels,
+            memory_budget_mb: bytes_to_mb(state.memory_budget.budget_bytes()),
+            thread_priority_summary: state.thread_priority_summary(),
+        }),
     });
 }
 
+fn bytes_to_mb(bytes: u64) -> u32 {
+    u32::try_from(bytes / (1024 * 1024)).unwrap_or(u32::MAX)
+}
+
 fn update_native_stats_overlay(
     sink: &gst::Element,
     state: &VideoLivenessState,
@@ -1444,6 +1884,63 @@ pub(crate) fn watch_first_sink_buffer(
     });
 }
 
+/// Watches access units entering the decoder for keyframes and for units the
+/// depayloader rebuilt across lost packets.
+pub(crate) fn watch_bitstream_health(decoder: &gst::Element, video_liveness: VideoLivenessMonitor) {
+    let Some(pad) = decoder.static_pad("sink") else {
+        return;
+    };
+    pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+        if let Some(buffer) = info.buffer() {
+            let flags = buffer.flags();
+            video_liveness.state.bitstream.record_access_unit(
+                !flags.contains(gst::BufferFlags::DELTA_UNIT),
+                flags.contains(gst::BufferFlags::DISCONT),
+                flags.contains(gst::BufferFlags::CORRUPTED),
+                video_liveness.state.now_ms(),
+            );
+        }
+        gst::PadProbeReturn::Ok
+    });
+}
+
+/// Reads the SEI messages in front of each picture on the parser's output.
+pub(crate) fn watch_sei_messages(
+    parser: &gst::Element,
+    codec: SeiCodec,
+    event_sender: &Option<Sender<Event>>,
+    video_liveness: VideoLivenessMonitor,
+) {
+    let Some(pad) = parser.static_pad("src") else {
+        return;
+    };
+    let sender = event_sender.clone();
+    let sei_parser = Mutex::new(SeiParser::new(codec));
+    pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+        let Some(buffer) = info.buffer() else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let framing = NalFraming::from_stream_format(
+            pad.current_caps()
+                .as_ref()
+                .and_then(|caps| caps.structure(0))
+                .and_then(|structure| structure.get::<&str>("stream-format").ok()),
+        );
+        let (Ok(map), Ok(mut sei_parser)) = (buffer.map_readable(), sei_parser.lock()) else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let messages = sei_parser.parse_access_unit(map.as_slice(), framing);
+        if let Some(summary) = video_liveness.record_sei(&messages) {
+            send_log(
+                &sender,
+                "info",
+                format!("Stream HDR metadata from SEI: {summary}."),
+            );
+        }
+        gst::PadProbeReturn::Ok
+    });
+}
+
 pub(crate) fn watch_rtp_video_bitrate(
     pad: &gst::Pad,
     video_liveness: VideoLivenessMonitor,
@@ -1538,6 +2035,19 @@ pub(crate) fn watch_video_caps_transitions(
         let Ok(mut old_memory_mode) = last_memory_mode_for_probe.lock() else {
             return gst::PadProbeReturn::Ok;
         };
+        if source == "parser" && old_caps.as_ref() != Some(&caps) {
+            if let Some((previous, current)) = monitor.update_stream_resolution(&caps) {
+                if !previous.is_empty() {
+                    send_log(
+                        &sender,
+                        "info",
+                        format!(
+                            "Stream resolution changed {previous} -> {current}; decoder and sink renegotiate in place."
+                        ),
+                    );
+                }
+            }
+        }
 
         if old_caps.is_none() {
             *old_caps = Some(caps);
@@ -1641,6 +2151,7 @@ pub(crate) fn watch_video_sink_caps_transitions(
 pub(crate) fn install_present_limiter(
     sink: &gst::Element,
     present_max_fps: Arc<AtomicU32>,
+    render_fps_cap: Arc<AtomicU32>,
     event_sender: &Option<Sender<Event>>,
     video_liveness: Option<VideoLivenessMonitor>,
 ) {
@@ -1659,7 +2170,10 @@ pub(crate) fn install_present_limiter(
     }));
 
     sink_pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, _info| {
-        let target_fps = present_max_fps.load(Ordering::Relaxed);
+        let target_fps = present_fps_limit(
+            present_max_fps.load(Ordering::Relaxed),
+            render_fps_cap.load(Ordering::Relaxed),
+        );
         if target_fps == 0 {
             return gst::PadProbeReturn::Ok;
         }
@@ -1878,6 +2392,22 @@ pub(crate) fn caps_framerate_summary(caps: &str) -> Option<String> {
     Some(rest[..end].trim().to_owned())
 }
 
+pub(crate) fn caps_resolution_summary(caps: &str) -> Option<String> {
+    let width = caps_int_field(caps, "width")?;
+    let height = caps_int_field(caps, "height")?;
+    (width > 0 && height > 0).then(|| format!("{width}x{height}"))
+}
+
+fn caps_int_field(caps: &str, field: &str) -> Option<u32> {
+    let marker = format!(" {field}=(int)");
+    let start = caps.find(&marker)? + marker.len();
+    let rest = &caps[start..];
+    let end = rest
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
 pub(crate) fn memory_mode_from_caps(caps: &str) -> &'static str {
     if caps.contains("memory:D3D12Memory") {
         "D3D12Memory"
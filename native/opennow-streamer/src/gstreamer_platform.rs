@@ -142,6 +142,7 @@ pub(crate) fn update_external_renderer_surface(_surface: &NativeRenderSurface) {
 #[cfg(target_os = "windows")]
 pub(crate) mod win32_renderer_window {
     use crate::gstreamer_input::NativeWindowInputEvent;
+    use crate::memory_budget::SCRATCH_BUFFERS;
     use crate::protocol::NativeRenderRect;
     use crate::protocol::{NativeStreamerShortcutAction, NativeStreamerShortcutBindings};
     use crate::shortcuts::NativeShortcutMatcher;
@@ -885,7 +886,8 @@ pub(crate) mod win32_renderer_window {
             return;
         }
 
-        let mut buffer = vec![0u8; size as usize];
+        // Raw input arrives at the mouse polling rate, so reuse read buffers.
+        let mut buffer = SCRATCH_BUFFERS.take(size as usize);
         let read = GetRawInputData(
             raw_input,
             RID_INPUT,
@@ -0,0 +1,234 @@
+use crate::gstreamer_backend::send_log;
+use crate::gstreamer_config::{software_decode_low_delay, software_decode_threads};
+use crate::gstreamer_liveness::VideoLivenessMonitor;
+use crate::gstreamer_pipeline::{set_property_from_str_if_supported, set_property_if_supported};
+use crate::protocol::Event;
+use gst::prelude::*;
+use gstreamer as gst;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Instant;
+
+const DECODE_BUDGET_WINDOW_FRAMES: usize = 120;
+const DECODE_BUDGET_OVERRUN_WINDOWS: u32 = 3;
+const DECODE_BUDGET_PENDING_LIMIT: usize = 32;
+const RESOLUTION_LADDER: &[&str] = &[
+    "3840x2160",
+    "2560x1440",
+    "1920x1080",
+    "1600x900",
+    "1280x720",
+];
+
+/// Applies thread count and low-delay settings to whichever software decoder
+/// the chain resolved to (libav, dav1d, or OpenH264).
+pub(crate) fn configure_software_decoder(decoder: &gst::Element) {
+    let threads = software_decode_threads();
+    let low_delay = software_decode_low_delay();
+
+    // avdec_*: frame threading queues one frame per thread, so low-delay mode
+    // restricts libav to slice threading.
+    set_property_if_supported(decoder, "max-threads", threads as i32);
+    if low_delay {
+        set_property_from_str_if_supported(decoder, "thread-type", "slice");
+    }
+
+    // dav1ddec: a frame delay of 1 returns each picture as soon as it is decoded.
+    set_property_if_supported(decoder, "n-threads", threads);
+    if low_delay {
+        set_property_if_supported(decoder, "max-frame-delay", 1i64);
+    }
+}
+
+pub(crate) fn format_software_decode_settings() -> String {
+    let threads = software_decode_threads();
+    format!(
+        "Software decode settings: threads={}, lowDelay={}.",
+        if threads == 0 {
+            "auto".to_owned()
+        } else {
+            threads.to_string()
+        },
+        software_decode_low_delay()
+    )
+}
+
+/// Tracks per-frame decode time against the frame budget and reports once
+/// the decoder has been too slow for several windows. A resolution change
+/// starts the measurement over. Only the decoder's src-pad probe records, so
+/// relaxed atomics are enough and no frame waits on a lock.
+#[derive(Debug, Default)]
+pub(crate) struct DecodeBudgetTracker {
+    resolution_generation: AtomicU64,
+    window_sum_us: AtomicU64,
+    window_frames: AtomicUsize,
+    overrun_windows: AtomicU32,
+    reported: AtomicBool,
+}
+
+impl DecodeBudgetTracker {
+    /// `resolution_generation` changes whenever the stream resolution does.
+    pub(crate) fn record(
+        &self,
+        decode_ms: f64,
+        requested_fps: u32,
+        resolution_generation: u64,
+    ) -> Option<DecodeBudgetOverrun> {
+        if self
+            .resolution_generation
+            .swap(resolution_generation, Ordering::Relaxed)
+            != resolution_generation
+        {
+            self.window_sum_us.store(0, Ordering::Relaxed);
+            self.window_frames.store(0, Ordering::Relaxed);
+            self.overrun_windows.store(0, Ordering::Relaxed);
+            self.reported.store(false, Ordering::Relaxed);
+        }
+        if self.reported.load(Ordering::Relaxed) || requested_fps == 0 {
+            return None;
+        }
+        let decode_us = (decode_ms * 1000.0).round() as u64;
+        let sum_us = self.window_sum_us.fetch_add(decode_us, Ordering::Relaxed) + decode_us;
+        let frames = self.window_frames.fetch_add(1, Ordering::Relaxed) + 1;
+        if frames < DECODE_BUDGET_WINDOW_FRAMES {
+            return None;
+        }
+        self.window_sum_us.store(0, Ordering::Relaxed);
+        self.window_frames.store(0, Ordering::Relaxed);
+
+        let budget_ms = 1000.0 / f64::from(requested_fps);
+        let average_ms = sum_us as f64 / 1000.0 / frames as f64;
+        if average_ms <= budget_ms {
+            self.overrun_windows.store(0, Ordering::Relaxed);
+            return None;
+        }
+        if self.overrun_windows.fetch_add(1, Ordering::Relaxed) + 1 < DECODE_BUDGET_OVERRUN_WINDOWS
+        {
+            return None;
+        }
+        self.reported.store(true, Ordering::Relaxed);
+        Some(DecodeBudgetOverrun {
+            average_decode_ms: average_ms,
+            budget_ms,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DecodeBudgetOverrun {
+    pub(crate) average_decode_ms: f64,
+    pub(crate) budget_ms: f64,
+}
+
+/// Input timestamps waiting for their decoded frame. The sink probe fills the
+/// slots round-robin and the src probe claims the one with a matching PTS.
+struct PendingDecodes {
+    base: Instant,
+    next: AtomicUsize,
+    /// `(pts + 1, start in µs since base)`; a zero PTS word marks a free slot.
+    slots: [(AtomicU64, AtomicU64); DECODE_BUDGET_PENDING_LIMIT],
+}
+
+impl PendingDecodes {
+    fn new() -> Self {
+        Self {
+            base: Instant::now(),
+            next: AtomicUsize::new(0),
+            slots: std::array::from_fn(|_| (AtomicU64::new(0), AtomicU64::new(0))),
+        }
+    }
+
+    fn push(&self, pts: gst::ClockTime) {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.slots.len();
+        let (slot_pts, slot_start) = &self.slots[index];
+        slot_start.store(self.base.elapsed().as_micros() as u64, Ordering::Relaxed);
+        slot_pts.store(pts.nseconds().saturating_add(1), Ordering::Release);
+    }
+
+    /// Decode time in milliseconds for the frame with `pts`, if its input was seen.
+    fn take_elapsed_ms(&self, pts: gst::ClockTime) -> Option<f64> {
+        let key = pts.nseconds().saturating_add(1);
+        self.slots.iter().find_map(|(slot_pts, slot_start)| {
+            if slot_pts.load(Ordering::Acquire) != key {
+                return None;
+            }
+            let started_us = slot_start.load(Ordering::Relaxed);
+            slot_pts
+                .compare_exchange(key, 0, Ordering::AcqRel, Ordering::Relaxed)
+                .ok()?;
+            let now_us = self.base.elapsed().as_micros() as u64;
+            Some(now_us.saturating_sub(started_us) as f64 / 1000.0)
+        })
+    }
+}
+
+pub(crate) fn lower_resolution_suggestion(resolution: &str) -> Option<&'static str> {
+    let pixels = parse_resolution_pixels(resolution)?;
+    RESOLUTION_LADDER
+        .iter()
+        .copied()
+        .find(|candidate| parse_resolution_pixels(candidate).is_some_and(|value| value < pixels))
+}
+
+fn parse_resolution_pixels(resolution: &str) -> Option<u64> {
+    let (width, height) = resolution.trim().split_once('x')?;
+    Some(width.trim().parse::<u64>().ok()? * height.trim().parse::<u64>().ok()?)
+}
+
+pub(crate) fn watch_software_decode_budget(
+    decoder: &gst::Element,
+    requested_fps: Option<u32>,
+    video_liveness: VideoLivenessMonitor,
+    event_sender: &Option<Sender<Event>>,
+) {
+    let (Some(sink_pad), Some(src_pad), Some(requested_fps)) = (
+        decoder.static_pad("sink"),
+        decoder.static_pad("src"),
+        requested_fps,
+    ) else {
+        return;
+    };
+
+    let pending = Arc::new(PendingDecodes::new());
+    let input_pending = pending.clone();
+    sink_pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+        if let Some(pts) = info.buffer().and_then(|buffer| buffer.pts()) {
+            input_pending.push(pts);
+        }
+        gst::PadProbeReturn::Ok
+    });
+
+    let sender = event_sender.clone();
+    let tracker = DecodeBudgetTracker::default();
+    src_pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+        let Some(decode_ms) = info
+            .buffer()
+            .and_then(|buffer| buffer.pts())
+            .and_then(|pts| pending.take_elapsed_ms(pts))
+        else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let overrun = tracker.record(
+            decode_ms,
+            requested_fps,
+            video_liveness.resolution_generation(),
+        );
+        // The resolution string is only built for the one frame that reports.
+        if let Some(overrun) = overrun {
+            let resolution = video_liveness.resolution();
+            let advice = lower_resolution_suggestion(&resolution)
+                .map(|value| format!("lower the stream resolution to {value}"))
+                .unwrap_or_else(|| "lower the stream frame rate".to_owned());
+            send_log(
+                &sender,
+                "warn",
+                format!(
+                    "Software decode averages {:.1} ms per frame, above the {:.1} ms budget for {requested_fps} fps at {resolution}; {advice} or enable a hardware decoder.",
+                    overrun.average_decode_ms, overrun.budget_ms
+                ),
+            );
+        }
+        gst::PadProbeReturn::Ok
+    });
+}
@@ -0,0 +1,318 @@
+use crate::late_frames::LateFramePredictor;
+use gst::prelude::*;
+use gstreamer as gst;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub(crate) const PIPELINE_STAGE_NAMES: [&str; 4] = ["depacketize", "decode", "upload", "present"];
+const PIPELINE_CHECKPOINTS: usize = PIPELINE_STAGE_NAMES.len() + 1;
+/// Power of two so a PTS hash maps straight onto a slot.
+const PIPELINE_FRAME_SLOTS: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PipelineCheckpoint {
+    /// First RTP packet of a frame reaches the depayloader.
+    Received = 0,
+    /// Complete access unit leaves the pre-decode queue.
+    DecodeStart = 1,
+    /// Decoded picture leaves the decoder.
+    Decoded = 2,
+    /// Converted/uploaded picture enters the present queue.
+    Uploaded = 3,
+    /// Picture reaches the video sink.
+    Presented = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PipelineQueueStage {
+    Decode,
+    Upload,
+    Present,
+}
+
+/// Per-stage latency accumulated between two stats reports.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct PipelineStageWindow {
+    samples: u32,
+    totals_ms: [f64; PIPELINE_STAGE_NAMES.len()],
+    max_ms: [f64; PIPELINE_STAGE_NAMES.len()],
+}
+
+impl PipelineStageWindow {
+    pub(crate) fn record(&mut self, durations_ms: [f64; PIPELINE_STAGE_NAMES.len()]) {
+        self.samples = self.samples.saturating_add(1);
+        for (index, duration_ms) in durations_ms.into_iter().enumerate() {
+            self.totals_ms[index] += duration_ms;
+            self.max_ms[index] = self.max_ms[index].max(duration_ms);
+        }
+    }
+
+    pub(crate) fn average_ms(&self, stage: usize) -> Option<f64> {
+        (self.samples > 0).then(|| self.totals_ms[stage] / f64::from(self.samples))
+    }
+
+    pub(crate) fn report(&self, drops: PipelineQueueCounters) -> PipelineStageReport {
+        PipelineStageReport {
+            summary: self.summary(drops),
+            decode_ms: self.average_ms(1),
+            render_ms: self
+                .average_ms(2)
+                .zip(self.average_ms(3))
+                .map(|(upload, present)| upload + present),
+        }
+    }
+
+    pub(crate) fn summary(&self, drops: PipelineQueueCounters) -> Option<String> {
+        if self.samples == 0 {
+            return None;
+        }
+        let stages = PIPELINE_STAGE_NAMES
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                format!(
+                    "{name} {:.1}/{:.1}",
+                    self.average_ms(index).unwrap_or_default(),
+                    self.max_ms[index]
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(format!(
+            "stages avg/max ms: {stages}; drops upload={} present={} late={}; decode backpressure={}",
+            drops.upload_drops, drops.present_drops, drops.late_drops, drops.decode_backpressure
+        ))
+    }
+}
+
+/// Stage timings reported with each stats event. These are wall-clock
+/// times between pad probes, so GPU work queued by an element is only
+/// counted once a later element waits on it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct PipelineStageReport {
+    pub(crate) summary: Option<String>,
+    pub(crate) decode_ms: Option<f64>,
+    /// Upload/convert plus waiting in the present queue.
+    pub(crate) render_ms: Option<f64>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PipelineQueueCounters {
+    pub(crate) decode_backpressure: u64,
+    pub(crate) upload_drops: u64,
+    pub(crate) present_drops: u64,
+    pub(crate) late_drops: u64,
+}
+
+/// Converts checkpoint timestamps for one frame into per-stage durations.
+/// Frames that skipped a checkpoint (for example a path without an upload
+/// queue) collapse that stage into the next one instead of being discarded.
+pub(crate) fn stage_durations_ms(
+    checkpoints: &[Option<Instant>; PIPELINE_CHECKPOINTS],
+) -> Option<[f64; PIPELINE_STAGE_NAMES.len()]> {
+    let mut durations = [0.0; PIPELINE_STAGE_NAMES.len()];
+    let mut previous = checkpoints[0]?;
+    for (index, checkpoint) in checkpoints.iter().enumerate().skip(1) {
+        let Some(at) = *checkpoint else {
+            continue;
+        };
+        durations[index - 1] = at.saturating_duration_since(previous).as_secs_f64() * 1000.0;
+        previous = at;
+    }
+    checkpoints[PIPELINE_CHECKPOINTS - 1].map(|_| durations)
+}
+
+#[derive(Debug, Default)]
+struct PipelineStageState {
+    window: PipelineStageWindow,
+    late_frames: LateFramePredictor,
+}
+
+#[derive(Debug, Default)]
+struct FrameSlot {
+    /// PTS in nanoseconds plus one; 0 marks a free slot.
+    pts: AtomicU64,
+    /// Nanoseconds since the ring's epoch plus one; 0 means not reached yet.
+    checkpoints: [AtomicU64; PIPELINE_CHECKPOINTS],
+}
+
+/// In-flight frames keyed by PTS. Probes run once per RTP packet on the
+/// streaming threads, so stamping only touches atomics; two frames that hash
+/// to the same slot just lose one sample.
+#[derive(Debug)]
+struct FrameRing {
+    epoch: Instant,
+    slots: [FrameSlot; PIPELINE_FRAME_SLOTS],
+}
+
+impl Default for FrameRing {
+    fn default() -> Self {
+        Self {
+            epoch: Instant::now(),
+            slots: std::array::from_fn(|_| FrameSlot::default()),
+        }
+    }
+}
+
+impl FrameRing {
+    fn slot(&self, key: u64) -> &FrameSlot {
+        let hash = key.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        &self.slots[(hash >> (u64::BITS - PIPELINE_FRAME_SLOTS.trailing_zeros())) as usize]
+    }
+
+    fn offset(&self, at: Instant) -> u64 {
+        let nanos = at.saturating_duration_since(self.epoch).as_nanos();
+        u64::try_from(nanos).unwrap_or(u64::MAX - 1) + 1
+    }
+
+    fn instant(&self, offset: u64) -> Option<Instant> {
+        (offset != 0).then(|| self.epoch + Duration::from_nanos(offset - 1))
+    }
+
+    fn clear(&self) {
+        for slot in &self.slots {
+            slot.pts.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Follows each frame through the depacketize → decode → upload → present
+/// queues by PTS and reports how long it spent in every stage.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PipelineStageTimer {
+    frames: Arc<FrameRing>,
+    state: Arc<Mutex<PipelineStageState>>,
+    decode_backpressure: Arc<AtomicU64>,
+    upload_drops: Arc<AtomicU64>,
+    present_drops: Arc<AtomicU64>,
+    late_drops: Arc<AtomicU64>,
+}
+
+impl PipelineStageTimer {
+    pub(crate) fn reset(&self) {
+        self.frames.clear();
+        if let Ok(mut state) = self.state.lock() {
+            *state = PipelineStageState::default();
+        }
+        self.decode_backpressure.store(0, Ordering::Relaxed);
+        self.upload_drops.store(0, Ordering::Relaxed);
+        self.present_drops.store(0, Ordering::Relaxed);
+        self.late_drops.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn watch_pad(&self, pad: &gst::Pad, checkpoint: PipelineCheckpoint) {
+        let timer = self.clone();
+        pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+            if let Some(pts) = info.buffer().and_then(|buffer| buffer.pts()) {
+                timer.stamp(pts, checkpoint, Instant::now());
+            }
+            gst::PadProbeReturn::Ok
+        });
+    }
+
+    /// A full leaky queue drops its oldest buffer right after "overrun"; a
+    /// non-leaky one blocks its producer instead.
+    pub(crate) fn watch_queue(&self, queue: &gst::Element, stage: PipelineQueueStage) {
+        let counter = match stage {
+            PipelineQueueStage::Decode => self.decode_backpressure.clone(),
+            PipelineQueueStage::Upload => self.upload_drops.clone(),
+            PipelineQueueStage::Present => self.present_drops.clone(),
+        };
+        queue.connect("overrun", false, move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            None
+        });
+    }
+
+    fn stamp(&self, pts: gst::ClockTime, checkpoint: PipelineCheckpoint, at: Instant) {
+        let key = pts.nseconds().wrapping_add(1);
+        let slot = self.frames.slot(key);
+        let index = checkpoint as usize;
+        if checkpoint == PipelineCheckpoint::Received {
+            // Every RTP packet of a frame lands here; only the first one starts it.
+            if slot.pts.load(Ordering::Acquire) == key {
+                return;
+            }
+            for stamp in &slot.checkpoints {
+                stamp.store(0, Ordering::Relaxed);
+            }
+            slot.checkpoints[index].store(self.frames.offset(at), Ordering::Relaxed);
+            slot.pts.store(key, Ordering::Release);
+            return;
+        }
+
+        if slot.pts.load(Ordering::Acquire) != key {
+            return;
+        }
+        let _ = slot.checkpoints[index].compare_exchange(
+            0,
+            self.frames.offset(at),
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        );
+        if checkpoint != PipelineCheckpoint::Presented {
+            return;
+        }
+
+        // Free the slot first so a frame is only ever recorded once.
+        if slot
+            .pts
+            .compare_exchange(key, 0, Ordering::AcqRel, Ordering::Relaxed)
+            .is_err()
+        {
+            return;
+        }
+        let checkpoints = std::array::from_fn(|index| {
+            self.frames
+                .instant(slot.checkpoints[index].load(Ordering::Relaxed))
+        });
+        let Some(durations) = stage_durations_ms(&checkpoints) else {
+            return;
+        };
+        if let Ok(mut state) = self.state.lock() {
+            state.window.record(durations);
+            state.late_frames.observe(durations[2] + durations[3]);
+        }
+    }
+
+    /// Decides whether the frame leaving the present queue should be skipped
+    /// because `queued_behind` newer frames would otherwise be shown late.
+    pub(crate) fn should_drop_late(
+        &self,
+        frame_interval_ms: f64,
+        queued_behind: u32,
+        newest_only: bool,
+    ) -> bool {
+        let late = self.state.lock().is_ok_and(|state| {
+            state
+                .late_frames
+                .should_drop(frame_interval_ms, queued_behind, newest_only)
+        });
+        if late {
+            self.late_drops.fetch_add(1, Ordering::Relaxed);
+        }
+        late
+    }
+
+    pub(crate) fn counters(&self) -> PipelineQueueCounters {
+        PipelineQueueCounters {
+            decode_backpressure: self.decode_backpressure.load(Ordering::Relaxed),
+            upload_drops: self.upload_drops.load(Ordering::Relaxed),
+            present_drops: self.present_drops.load(Ordering::Relaxed),
+            late_drops: self.late_drops.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the timings for frames presented since the previous call.
+    pub(crate) fn take_report(&self) -> PipelineStageReport {
+        let Ok(window) = self
+            .state
+            .lock()
+            .map(|mut state| std::mem::take(&mut state.window))
+        else {
+            return PipelineStageReport::default();
+        };
+        window.report(self.counters())
+    }
+}
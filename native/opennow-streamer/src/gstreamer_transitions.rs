@@ -51,6 +51,7 @@ pub(crate) struct TransitionTelemetry {
     pub(crate) present_pacing_changes: u32,
     pub(crate) partial_flush_count: u32,
     pub(crate) complete_flush_count: u32,
+    pub(crate) frames_skipped_ahead: u64,
     pub(crate) last_transition: Option<TransitionSnapshot>,
 }
 
@@ -63,6 +64,7 @@ impl Default for TransitionTelemetry {
             present_pacing_changes: 0,
             partial_flush_count: 0,
             complete_flush_count: 0,
+            frames_skipped_ahead: 0,
             last_transition: None,
         }
     }
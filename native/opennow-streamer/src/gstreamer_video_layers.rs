@@ -0,0 +1,310 @@
+use crate::gstreamer_backend::send_log;
+use crate::protocol::{Event, NativeVideoLayer};
+use crate::video_layers::{
+    layer_for_incoming_pad, parse_rtpbin_recv_pad_name, rtcp_sender_ssrc, tmmbr_packet,
+};
+use gst::glib;
+use gst::glib::translate::ToGlibPtr;
+use gst::prelude::*;
+use gstreamer as gst;
+use gstreamer_webrtc as gst_webrtc;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, Weak};
+
+/// Routes every offered video layer into one decode chain through an
+/// `input-selector`. When the offer allows TMMBR and the `layer-pause` flag is
+/// on, every RTCP report also asks the server to pause the layers that are not
+/// selected and to resume the one that is, so idle layers cost no bandwidth.
+/// Otherwise every layer keeps arriving and the selector drops the rest.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct VideoLayerSelector {
+    state: Arc<Mutex<VideoLayerSelectorState>>,
+}
+
+#[derive(Debug, Default)]
+struct VideoLayerSelectorState {
+    layers: Vec<NativeVideoLayer>,
+    requested: Option<String>,
+    active: Option<String>,
+    selector: Option<gst::Element>,
+    attached: Vec<AttachedLayer>,
+    /// Bitrate the selected layer is resumed at, when pausing at the sender is negotiated.
+    resume_bitrate_bps: Option<u64>,
+    /// `rtpbin` and the id of the session the layer requests ride on.
+    rtp_session: Option<(gst::Element, u32)>,
+}
+
+#[derive(Debug)]
+struct AttachedLayer {
+    id: String,
+    sink_pad: gst::Pad,
+    ssrc: Option<u32>,
+}
+
+impl VideoLayerSelector {
+    pub(crate) fn set_layers(
+        &self,
+        layers: Vec<NativeVideoLayer>,
+        resume_bitrate_bps: Option<u64>,
+    ) {
+        if let Ok(mut state) = self.state.lock() {
+            if state
+                .requested
+                .as_ref()
+                .is_some_and(|requested| !layers.iter().any(|layer| &layer.id == requested))
+            {
+                state.requested = None;
+            }
+            state.layers = layers;
+            state.resume_bitrate_bps = resume_bitrate_bps;
+        }
+    }
+
+    pub(crate) fn is_layered(&self) -> bool {
+        self.state
+            .lock()
+            .map(|state| state.layers.len() > 1)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn has_selector(&self) -> bool {
+        self.state
+            .lock()
+            .map(|state| state.selector.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Creates the selector that feeds `chain_sink_pad`, the first element of
+    /// the video decode chain.
+    pub(crate) fn create_selector(
+        &self,
+        pipeline: &gst::Pipeline,
+        chain_sink_pad: &gst::Pad,
+    ) -> Result<gst::Pad, String> {
+        let selector = gst::ElementFactory::make("input-selector")
+            .name("opennow-video-layer-selector")
+            .property("sync-streams", false)
+            .property("cache-buffers", false)
+            .build()
+            .map_err(|error| format!("Failed to create input-selector: {error}"))?;
+        pipeline
+            .add(&selector)
+            .map_err(|error| format!("Failed to add video layer selector: {error}"))?;
+        let src_pad = selector
+            .static_pad("src")
+            .ok_or_else(|| "Video layer selector has no src pad.".to_owned())?;
+        src_pad
+            .link(chain_sink_pad)
+            .map_err(|error| format!("Failed to link video layer selector: {error:?}"))?;
+        selector
+            .sync_state_with_parent()
+            .map_err(|error| format!("Failed to sync video layer selector state: {error}"))?;
+        if let Ok(mut state) = self.state.lock() {
+            state.selector = Some(selector);
+        }
+        Ok(src_pad)
+    }
+
+    /// Links an incoming RTP video pad to the selector. The pad becomes active
+    /// when it carries the requested layer, or when it is the first one.
+    pub(crate) fn attach_pad(
+        &self,
+        src_pad: &gst::Pad,
+        event_sender: &Option<Sender<Event>>,
+    ) -> Result<(), String> {
+        let mid = src_pad
+            .find_property("transceiver")
+            .and_then(|_| {
+                src_pad.property::<Option<gst_webrtc::WebRTCRTPTransceiver>>("transceiver")
+            })
+            .and_then(|transceiver| transceiver.property::<Option<String>>("mid"));
+        let rtp_source = incoming_rtp_source(src_pad);
+        let Ok(mut state) = self.state.lock() else {
+            return Err("Video layer selector state is unavailable.".to_owned());
+        };
+        let Some(selector) = state.selector.clone() else {
+            return Err("Video layer selector has not been created.".to_owned());
+        };
+        let attached = state
+            .attached
+            .iter()
+            .map(|layer| layer.id.as_str())
+            .collect::<Vec<_>>();
+        let layer_id = layer_for_incoming_pad(&state.layers, &attached, mid.as_deref())
+            .unwrap_or_else(|| format!("pad{}", state.attached.len()));
+        let sink_pad = selector
+            .request_pad_simple("sink_%u")
+            .ok_or_else(|| "Video layer selector refused a sink pad.".to_owned())?;
+        src_pad
+            .link(&sink_pad)
+            .map_err(|error| format!("Failed to link video layer {layer_id}: {error:?}"))?;
+
+        let make_active =
+            state.attached.is_empty() || state.requested.as_deref() == Some(layer_id.as_str());
+        if make_active {
+            selector.set_property("active-pad", &sink_pad);
+            state.active = Some(layer_id.clone());
+        }
+        state.attached.push(AttachedLayer {
+            id: layer_id.clone(),
+            sink_pad,
+            ssrc: rtp_source.map(|(_, ssrc)| ssrc),
+        });
+        let hook_session = state.resume_bitrate_bps.is_some() && state.rtp_session.is_none();
+        drop(state);
+
+        if hook_session {
+            match rtp_source
+                .and_then(|(session_id, _)| rtpbin_for(src_pad).map(|rtpbin| (rtpbin, session_id)))
+            {
+                Some((rtpbin, session_id)) => {
+                    self.pause_inactive_layers_at_sender(rtpbin, session_id)
+                }
+                None => send_log(
+                    event_sender,
+                    "warn",
+                    "Native video layer RTP session is unavailable; inactive layers keep arriving."
+                        .to_owned(),
+                ),
+            }
+        }
+
+        send_log(
+            event_sender,
+            "info",
+            format!(
+                "Attached native video layer {layer_id}{} to the decode chain{}.",
+                mid.map(|mid| format!(" (mid {mid})")).unwrap_or_default(),
+                if make_active {
+                    " as the active layer"
+                } else {
+                    ""
+                }
+            ),
+        );
+        Ok(())
+    }
+
+    /// Switches the decode chain to `layer_id`. A layer that has not arrived
+    /// yet becomes active as soon as its pad is attached.
+    pub(crate) fn select(&self, layer_id: &str) -> Result<bool, String> {
+        let Ok(mut state) = self.state.lock() else {
+            return Err("Video layer selector state is unavailable.".to_owned());
+        };
+        if !state.layers.iter().any(|layer| layer.id == layer_id) {
+            return Err(format!("Video layer {layer_id} was not offered."));
+        }
+        state.requested = Some(layer_id.to_owned());
+        let pad = state
+            .attached
+            .iter()
+            .find(|layer| layer.id == layer_id)
+            .map(|layer| layer.sink_pad.clone());
+        let (Some(selector), Some(pad)) = (state.selector.clone(), pad) else {
+            return Ok(false);
+        };
+        selector.set_property("active-pad", &pad);
+        state.active = Some(layer_id.to_owned());
+        if let Some((rtpbin, session_id)) = state.rtp_session.clone() {
+            drop(state);
+            // Tell the server now instead of waiting for the next regular report.
+            if let Some(session) = internal_rtp_session(&rtpbin, session_id) {
+                session.emit_by_name::<()>("send-rtcp", &[&0u64]);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Appends the layer choice to every RTCP report the session sends.
+    fn pause_inactive_layers_at_sender(&self, rtpbin: gst::Element, session_id: u32) {
+        let Some(session) = internal_rtp_session(&rtpbin, session_id) else {
+            return;
+        };
+        let state = Arc::downgrade(&self.state);
+        session.connect("on-sending-rtcp", false, move |args| {
+            if let Some(buffer) = args.get(1) {
+                append_layer_requests(&state, buffer);
+            }
+            Some(false.to_value())
+        });
+        if let Ok(mut state) = self.state.lock() {
+            state.rtp_session = Some((rtpbin, session_id));
+        }
+    }
+}
+
+fn append_layer_requests(state: &Weak<Mutex<VideoLayerSelectorState>>, buffer: &glib::Value) {
+    let Some(state) = state.upgrade() else {
+        return;
+    };
+    let requests = {
+        let Ok(state) = state.lock() else {
+            return;
+        };
+        let Some(resume_bitrate_bps) = state.resume_bitrate_bps else {
+            return;
+        };
+        if state.attached.len() < 2 {
+            return;
+        }
+        state
+            .attached
+            .iter()
+            .filter_map(|layer| {
+                let bitrate = if state.active.as_deref() == Some(layer.id.as_str()) {
+                    resume_bitrate_bps
+                } else {
+                    0
+                };
+                layer.ssrc.map(|ssrc| (ssrc, bitrate))
+            })
+            .collect::<Vec<_>>()
+    };
+    if requests.is_empty() {
+        return;
+    }
+
+    // The session still holds the only reference while the signal runs,
+    // which is how it adds its own feedback packets to the same buffer.
+    let buffer = unsafe {
+        let ptr = glib::gobject_ffi::g_value_get_boxed(buffer.to_glib_none().0)
+            as *mut gst::ffi::GstBuffer;
+        if ptr.is_null()
+            || gst::ffi::gst_mini_object_is_writable(ptr as *const gst::ffi::GstMiniObject)
+                == glib::ffi::GFALSE
+        {
+            return;
+        }
+        gst::BufferRef::from_mut_ptr(ptr)
+    };
+    let mut header = [0u8; 8];
+    if buffer.copy_to_slice(0, &mut header).is_err() {
+        return;
+    }
+    let Some(sender_ssrc) = rtcp_sender_ssrc(&header) else {
+        return;
+    };
+    buffer.append_memory(gst::Memory::from_slice(tmmbr_packet(
+        sender_ssrc,
+        &requests,
+    )));
+}
+
+/// RTP session id and SSRC behind a `webrtcbin` source pad, read from the
+/// `rtpbin` pad it ghosts.
+fn incoming_rtp_source(src_pad: &gst::Pad) -> Option<(u32, u32)> {
+    let target = src_pad.downcast_ref::<gst::GhostPad>()?.target()?;
+    parse_rtpbin_recv_pad_name(target.name().as_str())
+}
+
+fn rtpbin_for(src_pad: &gst::Pad) -> Option<gst::Element> {
+    src_pad
+        .parent_element()?
+        .downcast::<gst::Bin>()
+        .ok()?
+        .by_name("rtpbin")
+}
+
+fn internal_rtp_session(rtpbin: &gst::Element, session_id: u32) -> Option<glib::Object> {
+    rtpbin.emit_by_name::<Option<glib::Object>>("get-internal-session", &[&session_id])
+}
@@ -10,19 +10,21 @@ pub const INPUT_MOUSE_BUTTON_DOWN: u32 = 8;
 pub const INPUT_MOUSE_BUTTON_UP: u32 = 9;
 pub const INPUT_MOUSE_WHEEL: u32 = 10;
 pub const INPUT_GAMEPAD: u32 = 12;
+pub const INPUT_HAPTICS_ENABLED: u32 = 13;
+pub const INPUT_LOCK_KEYS_SYNC: u32 = 19;
 
 pub const GAMEPAD_MAX_CONTROLLERS: u8 = 4;
 pub const GAMEPAD_PACKET_SIZE: usize = 38;
 pub const PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL: u32 = (1 << GAMEPAD_MAX_CONTROLLERS) - 1;
 pub const PARTIALLY_RELIABLE_HID_DEVICE_MASK_ALL: u32 = 0xFFFF_FFFF;
 
-const WRAPPER_LEGACY_INPUT: u8 = 0x21;
-const WRAPPER_SINGLE_INPUT: u8 = 0x22;
-const WRAPPER_PARTIALLY_RELIABLE_INPUT: u8 = 0x26;
-const WRAPPER_VERSION_MARKER: u8 = 0x23;
-const GAMEPAD_PAYLOAD_SIZE: u16 = 26;
-const GAMEPAD_INNER_SIZE: u16 = 20;
-const GAMEPAD_RESERVED_MARKER: u16 = 85;
+pub(crate) const WRAPPER_LEGACY_INPUT: u8 = 0x21;
+pub(crate) const WRAPPER_SINGLE_INPUT: u8 = 0x22;
+pub(crate) const WRAPPER_PARTIALLY_RELIABLE_INPUT: u8 = 0x26;
+pub(crate) const WRAPPER_VERSION_MARKER: u8 = 0x23;
+pub(crate) const GAMEPAD_PAYLOAD_SIZE: u16 = 26;
+pub(crate) const GAMEPAD_INNER_SIZE: u16 = 20;
+pub(crate) const GAMEPAD_RESERVED_MARKER: u16 = 85;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct KeyboardPayload {
@@ -144,6 +146,20 @@ impl InputEncoder {
         wrap_single_input(self.protocol_version, payload.timestamp_us, &bytes)
     }
 
+    pub fn encode_haptics_enabled(&self, enabled: bool, timestamp_us: u64) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(6);
+        put_u32_le(&mut bytes, INPUT_HAPTICS_ENABLED);
+        put_u16_be(&mut bytes, u16::from(enabled));
+        wrap_single_input(self.protocol_version, timestamp_us, &bytes)
+    }
+
+    pub fn encode_lock_keys_sync(&self, state: u8, timestamp_us: u64) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(5);
+        put_u32_le(&mut bytes, INPUT_LOCK_KEYS_SYNC);
+        bytes.push(state);
+        wrap_single_input(self.protocol_version, timestamp_us, &bytes)
+    }
+
     pub fn encode_gamepad_state(
         &mut self,
         bitmap: u16,
@@ -0,0 +1,342 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+use crate::input::{
+    GamepadInput, InputEncoder, KeyboardPayload, MouseButtonPayload, MouseMovePayload,
+    MouseWheelPayload,
+};
+use crate::webrtc_protocol::{decode_input_packet, InputMessage};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::Instant;
+
+const RECORDING_HEADER: &str = "# opennow input recording v1";
+/// Events are stored unwrapped so a recording replays against any server
+/// protocol version.
+const RECORDING_PROTOCOL_VERSION: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RecordedInput {
+    /// Microseconds since the recording started.
+    pub(crate) offset_us: u64,
+    pub(crate) partially_reliable: bool,
+    pub(crate) message: InputMessage,
+}
+
+/// Writes every input event sent to the server as one text line:
+/// `<offset_us> <r|p> <hex payload>`.
+pub(crate) struct InputRecorder<W: Write> {
+    writer: W,
+    started_at: Instant,
+    recorded: u64,
+}
+
+impl<W: Write> std::fmt::Debug for InputRecorder<W> {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("InputRecorder")
+            .field("recorded", &self.recorded)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> InputRecorder<W> {
+    pub(crate) fn new(mut writer: W) -> io::Result<Self> {
+        writeln!(writer, "{RECORDING_HEADER}")?;
+        Ok(Self {
+            writer,
+            started_at: Instant::now(),
+            recorded: 0,
+        })
+    }
+
+    /// Records an encoded packet on its way to the server. Heartbeats and
+    /// packets that do not decode are skipped; returns whether a line was
+    /// written.
+    pub(crate) fn record_packet(
+        &mut self,
+        payload: &[u8],
+        partially_reliable: bool,
+    ) -> io::Result<bool> {
+        let offset_us = self.started_at.elapsed().as_micros() as u64;
+        self.record_packet_at(offset_us, payload, partially_reliable)
+    }
+
+    fn record_packet_at(
+        &mut self,
+        offset_us: u64,
+        payload: &[u8],
+        partially_reliable: bool,
+    ) -> io::Result<bool> {
+        let Ok(packet) = decode_input_packet(payload) else {
+            return Ok(false);
+        };
+        if packet.message == InputMessage::Heartbeat {
+            return Ok(false);
+        }
+
+        let line = format_recorded_input(&RecordedInput {
+            offset_us,
+            partially_reliable,
+            message: packet.message,
+        });
+        writeln!(self.writer, "{line}")?;
+        self.recorded += 1;
+        Ok(true)
+    }
+}
+
+pub(crate) fn format_recorded_input(event: &RecordedInput) -> String {
+    let payload = event
+        .message
+        .encode(&mut InputEncoder::new(RECORDING_PROTOCOL_VERSION), false);
+    let mut line = format!(
+        "{} {} ",
+        event.offset_us,
+        if event.partially_reliable { 'p' } else { 'r' }
+    );
+    for byte in payload {
+        let _ = write!(line, "{byte:02x}");
+    }
+    line
+}
+
+/// Parses a recording. Blank lines and `#` comments are skipped; offsets must
+/// not go backwards.
+pub(crate) fn parse_input_recording(text: &str) -> Result<Vec<RecordedInput>, String> {
+    let mut events = Vec::new();
+    let mut last_offset_us = 0;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let event =
+            parse_recorded_input(line).map_err(|error| format!("line {}: {error}", index + 1))?;
+        if event.offset_us < last_offset_us {
+            return Err(format!(
+                "line {}: offset {} is earlier than the previous event ({last_offset_us}).",
+                index + 1,
+                event.offset_us
+            ));
+        }
+        last_offset_us = event.offset_us;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+fn parse_recorded_input(line: &str) -> Result<RecordedInput, String> {
+    let mut fields = line.split_whitespace();
+    let (Some(offset), Some(channel), Some(payload), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err("expected `<offset_us> <r|p> <hex payload>`.".to_owned());
+    };
+
+    let offset_us = offset
+        .parse::<u64>()
+        .map_err(|_| format!("invalid offset {offset:?}."))?;
+    let partially_reliable = match channel {
+        "r" => false,
+        "p" => true,
+        other => return Err(format!("invalid channel {other:?}.")),
+    };
+    let payload = decode_hex(payload).ok_or_else(|| "invalid hex payload.".to_owned())?;
+    let message = decode_input_packet(&payload)
+        .map_err(|error| error.to_string())?
+        .message;
+    Ok(RecordedInput {
+        offset_us,
+        partially_reliable,
+        message,
+    })
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+/// Re-encodes a recorded event for the live session, stamping it with the
+/// replay clock.
+pub(crate) fn encode_replayed_input(
+    encoder: &mut InputEncoder,
+    event: &RecordedInput,
+    timestamp_us: u64,
+) -> Vec<u8> {
+    with_timestamp(event.message, timestamp_us).encode(encoder, event.partially_reliable)
+}
+
+fn with_timestamp(message: InputMessage, timestamp_us: u64) -> InputMessage {
+    match message {
+        InputMessage::Heartbeat => InputMessage::Heartbeat,
+        InputMessage::KeyDown(payload) => InputMessage::KeyDown(KeyboardPayload {
+            timestamp_us,
+            ..payload
+        }),
+        InputMessage::KeyUp(payload) => InputMessage::KeyUp(KeyboardPayload {
+            timestamp_us,
+            ..payload
+        }),
+        InputMessage::MouseMove(payload) => InputMessage::MouseMove(MouseMovePayload {
+            timestamp_us,
+            ..payload
+        }),
+        InputMessage::MouseButtonDown(payload) => {
+            InputMessage::MouseButtonDown(MouseButtonPayload {
+                timestamp_us,
+                ..payload
+            })
+        }
+        InputMessage::MouseButtonUp(payload) => InputMessage::MouseButtonUp(MouseButtonPayload {
+            timestamp_us,
+            ..payload
+        }),
+        InputMessage::MouseWheel(payload) => InputMessage::MouseWheel(MouseWheelPayload {
+            timestamp_us,
+            ..payload
+        }),
+        InputMessage::Gamepad { bitmap, input } => InputMessage::Gamepad {
+            bitmap,
+            input: GamepadInput {
+                timestamp_us,
+                ..input
+            },
+        },
+        InputMessage::HapticsEnabled { enabled, .. } => InputMessage::HapticsEnabled {
+            enabled,
+            timestamp_us,
+        },
+        InputMessage::LockKeysSync { state, .. } => InputMessage::LockKeysSync {
+            state,
+            timestamp_us,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded_session() -> Vec<InputMessage> {
+        vec![
+            InputMessage::KeyDown(KeyboardPayload {
+                keycode: 0x57,
+                scancode: 0x11,
+                modifiers: 0,
+                timestamp_us: 1_000,
+            }),
+            InputMessage::MouseMove(MouseMovePayload {
+                dx: -12,
+                dy: 4,
+                timestamp_us: 1_500,
+            }),
+            InputMessage::Gamepad {
+                bitmap: 0b0001,
+                input: GamepadInput {
+                    controller_id: 0,
+                    buttons: 0x1000,
+                    left_trigger: 255,
+                    right_trigger: 0,
+                    left_stick_x: 12_000,
+                    left_stick_y: -3_000,
+                    right_stick_x: 0,
+                    right_stick_y: 0,
+                    connected: true,
+                    timestamp_us: 2_000,
+                },
+            },
+            InputMessage::KeyUp(KeyboardPayload {
+                keycode: 0x57,
+                scancode: 0x11,
+                modifiers: 0,
+                timestamp_us: 2_500,
+            }),
+        ]
+    }
+
+    fn message_timestamp(message: &InputMessage) -> u64 {
+        match message {
+            InputMessage::KeyDown(payload) | InputMessage::KeyUp(payload) => payload.timestamp_us,
+            InputMessage::MouseMove(payload) => payload.timestamp_us,
+            InputMessage::Gamepad { input, .. } => input.timestamp_us,
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn replays_a_recording_into_identical_packets() {
+        let mut live_encoder = InputEncoder::new(3);
+        let mut recorder = InputRecorder::new(Vec::new()).expect("recorder");
+        let mut sent = Vec::new();
+        for message in recorded_session() {
+            let partially_reliable = matches!(
+                message,
+                InputMessage::MouseMove(_) | InputMessage::Gamepad { .. }
+            );
+            let packet = message.encode(&mut live_encoder, partially_reliable);
+            assert!(recorder
+                .record_packet_at(message_timestamp(&message), &packet, partially_reliable)
+                .expect("record"));
+            sent.push(packet);
+        }
+        assert_eq!(recorder.recorded, 4);
+
+        let text = String::from_utf8(recorder.writer).expect("utf-8 recording");
+        assert!(text.starts_with(RECORDING_HEADER));
+        let events = parse_input_recording(&text).expect("recording parses");
+        assert_eq!(events.len(), sent.len());
+
+        let mut replay_encoder = InputEncoder::new(3);
+        let replayed = events
+            .iter()
+            .map(|event| encode_replayed_input(&mut replay_encoder, event, event.offset_us))
+            .collect::<Vec<_>>();
+        assert_eq!(replayed, sent);
+    }
+
+    #[test]
+    fn skips_heartbeats_and_undecodable_packets() {
+        let encoder = InputEncoder::new(3);
+        let mut recorder = InputRecorder::new(Vec::new()).expect("recorder");
+        assert!(!recorder
+            .record_packet_at(0, &encoder.encode_heartbeat(), false)
+            .expect("record"));
+        assert!(!recorder
+            .record_packet_at(0, &[0x63, 0x00, 0x00, 0x00], false)
+            .expect("record"));
+        assert_eq!(recorder.recorded, 0);
+    }
+
+    #[test]
+    fn rejects_malformed_recordings() {
+        let key = format_recorded_input(&RecordedInput {
+            offset_us: 20,
+            partially_reliable: false,
+            message: recorded_session()[0],
+        });
+        assert_eq!(
+            parse_input_recording(&format!("# comment\n\n{key}\n")).map(|events| events.len()),
+            Ok(1)
+        );
+
+        let earlier = key.replacen("20", "10", 1);
+        assert!(parse_input_recording(&format!("{key}\n{earlier}"))
+            .unwrap_err()
+            .starts_with("line 2:"));
+        for line in [
+            "20 r",
+            "x r 02000000",
+            "20 q 02000000",
+            "20 r 0200000",
+            "20 r 63000000",
+        ] {
+            assert!(parse_input_recording(line).is_err(), "{line}");
+        }
+    }
+}
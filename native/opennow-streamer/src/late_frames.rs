@@ -0,0 +1,112 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+//! Late-frame prediction for the present stage.
+//!
+//! The video sink runs with `sync=false`, so a slow upload/present stage does
+//! not miss vsync visibly; it lets decoded frames pile up in the present queue
+//! and every later frame inherits the delay. When a newer frame is already
+//! waiting and the measured cost says the older one would land after the next
+//! frame is due, the older one is skipped instead of shown.
+
+/// Weight of the newest sample in the smoothed upload+present cost.
+const COST_SMOOTHING: f64 = 0.2;
+const DEFAULT_FRAME_INTERVAL_MS: f64 = 1000.0 / 60.0;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct LateFramePredictor {
+    cost_ms: Option<f64>,
+}
+
+impl LateFramePredictor {
+    /// Feeds the upload+present time of a frame that reached the sink.
+    pub(crate) fn observe(&mut self, sample_ms: f64) {
+        if !sample_ms.is_finite() || sample_ms < 0.0 {
+            return;
+        }
+        self.cost_ms = Some(match self.cost_ms {
+            Some(cost) => cost + (sample_ms - cost) * COST_SMOOTHING,
+            None => sample_ms,
+        });
+    }
+
+    /// `queued_behind` is the number of newer frames already waiting in the
+    /// present queue. Showing this frame delays each of them by its cost, so
+    /// once draining the queue takes longer than one interval it is late.
+    /// `newest_only` treats any frame with a newer one waiting as late, which
+    /// is what low-latency mode wants regardless of the measured cost.
+    pub(crate) fn should_drop(
+        &self,
+        frame_interval_ms: f64,
+        queued_behind: u32,
+        newest_only: bool,
+    ) -> bool {
+        if queued_behind == 0 {
+            return false;
+        }
+        newest_only
+            || self
+                .cost_ms
+                .is_some_and(|cost| cost * f64::from(queued_behind + 1) > frame_interval_ms)
+    }
+}
+
+/// The present limiter's cap wins over the negotiated rate; 0 means unset.
+pub(crate) fn frame_interval_ms(present_max_fps: u32, requested_fps: Option<u32>) -> f64 {
+    [Some(present_max_fps), requested_fps]
+        .into_iter()
+        .flatten()
+        .find(|fps| *fps > 0)
+        .map(|fps| 1000.0 / f64::from(fps))
+        .unwrap_or(DEFAULT_FRAME_INTERVAL_MS)
+}
+
+/// The stricter of the present limiter and the user's render cap; 0 means unset.
+pub(crate) fn present_fps_limit(present_max_fps: u32, render_fps_cap: u32) -> u32 {
+    [present_max_fps, render_fps_cap]
+        .into_iter()
+        .filter(|fps| *fps > 0)
+        .min()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_only_when_a_newer_frame_waits_behind_a_slow_present() {
+        let mut predictor = LateFramePredictor::default();
+        assert!(!predictor.should_drop(8.3, 2, false));
+
+        predictor.observe(5.0);
+        assert!(!predictor.should_drop(8.3, 0, false));
+        assert!(predictor.should_drop(8.3, 1, false));
+        assert!(!predictor.should_drop(16.7, 1, false));
+
+        predictor.observe(f64::NAN);
+        predictor.observe(10.0);
+        assert_eq!(predictor.cost_ms, Some(6.0));
+    }
+
+    #[test]
+    fn newest_only_drops_any_frame_with_a_newer_one_waiting() {
+        let predictor = LateFramePredictor::default();
+        assert!(predictor.should_drop(16.7, 1, true));
+        assert!(!predictor.should_drop(16.7, 0, true));
+    }
+
+    #[test]
+    fn frame_interval_prefers_the_present_cap() {
+        assert_eq!(frame_interval_ms(120, Some(60)), 1000.0 / 120.0);
+        assert_eq!(frame_interval_ms(0, Some(60)), 1000.0 / 60.0);
+        assert_eq!(frame_interval_ms(0, None), DEFAULT_FRAME_INTERVAL_MS);
+    }
+
+    #[test]
+    fn render_cap_only_ever_lowers_the_present_limit() {
+        assert_eq!(present_fps_limit(0, 0), 0);
+        assert_eq!(present_fps_limit(0, 40), 40);
+        assert_eq!(present_fps_limit(165, 40), 40);
+        assert_eq!(present_fps_limit(30, 40), 30);
+    }
+}
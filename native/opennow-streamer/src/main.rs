@@ -1,24 +1,57 @@
 #![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
 mod backend;
+mod bitstream_dump;
+mod bitstream_health;
+mod crash;
+mod dualsense;
+mod flags;
+mod gamepad_slots;
+#[cfg(feature = "gstreamer")]
+mod gstreamer_audio_output;
+#[cfg(feature = "gstreamer")]
+mod gstreamer_av_sync;
 #[cfg(feature = "gstreamer")]
 mod gstreamer_backend;
 #[cfg(feature = "gstreamer")]
+mod gstreamer_bitstream_dump;
+#[cfg(feature = "gstreamer")]
 mod gstreamer_config;
+#[cfg(all(test, feature = "gstreamer"))]
+mod gstreamer_golden_frames;
 #[cfg(feature = "gstreamer")]
 mod gstreamer_input;
 #[cfg(feature = "gstreamer")]
+mod gstreamer_jitter;
+#[cfg(feature = "gstreamer")]
 mod gstreamer_liveness;
 #[cfg(feature = "gstreamer")]
 mod gstreamer_pipeline;
 #[cfg(feature = "gstreamer")]
 mod gstreamer_platform;
 #[cfg(feature = "gstreamer")]
+mod gstreamer_software_decode;
+#[cfg(feature = "gstreamer")]
+mod gstreamer_stages;
+#[cfg(feature = "gstreamer")]
 mod gstreamer_transitions;
+#[cfg(feature = "gstreamer")]
+mod gstreamer_video_layers;
 mod input;
+mod input_recording;
+mod late_frames;
+mod memory_budget;
 mod protocol;
-mod shortcuts;
+mod queue_backlog;
 mod sdp;
+mod sdp_model;
+mod sei;
+mod shortcuts;
+#[cfg(test)]
+mod test_support;
+mod thread_priority;
+mod video_layers;
+mod webrtc_protocol;
 
 use serde::Serialize;
 use serde_json::Value;
@@ -66,6 +99,7 @@ fn handle_command(
     command: CommandEnvelope,
     backend: &mut dyn NativeStreamerBackend,
 ) -> io::Result<bool> {
+    crash::record_log_line(format!("command {}", command.command_type));
     match command.command_type.as_str() {
         "hello" => {
             let requested = command.protocol_version.unwrap_or(0);
@@ -104,6 +138,15 @@ fn handle_command(
         "update-shortcuts" => {
             return write_reply(backend.update_shortcuts(command));
         }
+        "volume" => {
+            return write_reply(backend.update_volume(command));
+        }
+        "video-layer" => {
+            return write_reply(backend.select_video_layer(command));
+        }
+        "render-fps-cap" => {
+            return write_reply(backend.update_render_fps_cap(command));
+        }
         "stop" => {
             return write_reply(backend.stop(command));
         }
@@ -120,6 +163,7 @@ fn handle_command(
 }
 
 fn main() -> io::Result<()> {
+    crash::install_panic_hook();
     let stdin = io::stdin();
     let (event_sender, event_receiver) = mpsc::channel::<Event>();
     let event_writer = thread::spawn(move || {
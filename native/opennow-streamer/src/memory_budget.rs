@@ -0,0 +1,328 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+use crate::protocol::ColorQuality;
+use crate::sdp::parse_resolution;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+const MIB: u64 = 1024 * 1024;
+const DEFAULT_MEMORY_BUDGET_BYTES: u64 = 512 * MIB;
+const MIN_MEMORY_BUDGET_BYTES: u64 = 256 * MIB;
+const MAX_MEMORY_BUDGET_BYTES: u64 = 2048 * MIB;
+/// The streamer claims at most this fraction of currently free memory for media buffers.
+const AVAILABLE_MEMORY_SHARE_DIVISOR: u64 = 8;
+/// Queued decoded frames may use this fraction of the budget; the rest is
+/// headroom for the decoder's reference pictures and the sink's own pool.
+const QUEUED_FRAMES_BUDGET_DIVISOR: u64 = 4;
+
+/// Scratch buffers for per-event reads such as raw input. Decoded frames are
+/// not pooled here; they stay in the decoder's and sink's own buffer pools.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) static SCRATCH_BUFFERS: BufferPool = BufferPool::new(8);
+
+pub(crate) fn resolve_memory_budget_bytes(available_bytes: Option<u64>) -> u64 {
+    available_bytes
+        .map(|available| {
+            (available / AVAILABLE_MEMORY_SHARE_DIVISOR)
+                .clamp(MIN_MEMORY_BUDGET_BYTES, MAX_MEMORY_BUDGET_BYTES)
+        })
+        .unwrap_or(DEFAULT_MEMORY_BUDGET_BYTES)
+}
+
+/// Size of one decoded picture in the planar layout hardware decoders emit
+/// (NV12/P010 for 4:2:0, Y444/Y410-style for 4:4:4).
+pub(crate) fn decoded_frame_bytes(width: u32, height: u32, color_quality: ColorQuality) -> u64 {
+    let pixels = u64::from(width) * u64::from(height);
+    let samples = match color_quality {
+        ColorQuality::EightBit444 | ColorQuality::TenBit444 => pixels * 3,
+        ColorQuality::EightBit420 | ColorQuality::TenBit420 => pixels * 3 / 2,
+    };
+    if color_quality.bit_depth() > 8 {
+        samples * 2
+    } else {
+        samples
+    }
+}
+
+/// Caps a requested post-decode queue depth so queued frames stay within the budget.
+pub(crate) fn max_queued_decoded_frames(
+    budget_bytes: u64,
+    frame_bytes: u64,
+    requested: u32,
+) -> u32 {
+    if frame_bytes == 0 {
+        return requested;
+    }
+    let allowed = (budget_bytes / QUEUED_FRAMES_BUDGET_DIVISOR / frame_bytes).max(1);
+    requested.min(u32::try_from(allowed).unwrap_or(u32::MAX))
+}
+
+fn parse_meminfo_available_bytes(meminfo: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let value = line.strip_prefix("MemAvailable:")?;
+        let kib = value
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(kib * 1024)
+    })
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn available_system_memory_bytes() -> Option<u64> {
+    parse_meminfo_available_bytes(&std::fs::read_to_string("/proc/meminfo").ok()?)
+}
+
+#[cfg(windows)]
+pub(crate) fn available_system_memory_bytes() -> Option<u64> {
+    #[repr(C)]
+    struct MemoryStatusEx {
+        length: u32,
+        memory_load: u32,
+        total_phys: u64,
+        avail_phys: u64,
+        total_page_file: u64,
+        avail_page_file: u64,
+        total_virtual: u64,
+        avail_virtual: u64,
+        avail_extended_virtual: u64,
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GlobalMemoryStatusEx(buffer: *mut MemoryStatusEx) -> i32;
+    }
+
+    let mut status = MemoryStatusEx {
+        length: std::mem::size_of::<MemoryStatusEx>() as u32,
+        memory_load: 0,
+        total_phys: 0,
+        avail_phys: 0,
+        total_page_file: 0,
+        avail_page_file: 0,
+        total_virtual: 0,
+        avail_virtual: 0,
+        avail_extended_virtual: 0,
+    };
+    (unsafe { GlobalMemoryStatusEx(&mut status) } != 0).then_some(status.avail_phys)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub(crate) fn available_system_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Session-wide cap on how many decoded frames may queue after the decoder.
+///
+/// The budget comes from free system memory only. GPU memory is not queried
+/// and frames decoded into it are not tracked, so there is no in-use figure;
+/// the per-frame size is worked out from the negotiated resolution.
+#[derive(Debug)]
+pub(crate) struct MemoryBudget {
+    budget_bytes: AtomicU64,
+    frame_bytes: AtomicU64,
+    color_quality: Mutex<ColorQuality>,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self {
+            budget_bytes: AtomicU64::new(DEFAULT_MEMORY_BUDGET_BYTES),
+            frame_bytes: AtomicU64::new(0),
+            color_quality: Mutex::new(ColorQuality::EightBit420),
+        }
+    }
+}
+
+impl MemoryBudget {
+    pub(crate) fn configure(
+        &self,
+        budget_bytes: u64,
+        resolution: &str,
+        color_quality: ColorQuality,
+    ) {
+        self.budget_bytes.store(budget_bytes, Ordering::Relaxed);
+        if let Ok(mut current) = self.color_quality.lock() {
+            *current = color_quality;
+        }
+        self.update_resolution(resolution);
+    }
+
+    /// Re-sizes the per-frame estimate after the decoder renegotiates a new resolution.
+    pub(crate) fn update_resolution(&self, resolution: &str) {
+        let color_quality = self
+            .color_quality
+            .lock()
+            .map(|current| *current)
+            .unwrap_or(ColorQuality::EightBit420);
+        let frame_bytes = parse_resolution(resolution)
+            .map(|(width, height)| decoded_frame_bytes(width, height, color_quality))
+            .unwrap_or(0);
+        self.frame_bytes.store(frame_bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn budget_bytes(&self) -> u64 {
+        self.budget_bytes.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn max_queued_frames(&self, requested: u32) -> u32 {
+        max_queued_decoded_frames(
+            self.budget_bytes(),
+            self.frame_bytes.load(Ordering::Relaxed),
+            requested,
+        )
+    }
+}
+
+/// Recycles byte buffers for hot paths that would otherwise allocate per event.
+#[derive(Debug)]
+pub(crate) struct BufferPool {
+    free: Mutex<Vec<Vec<u8>>>,
+    max_pooled: usize,
+    retained_bytes: AtomicU64,
+}
+
+impl BufferPool {
+    pub(crate) const fn new(max_pooled: usize) -> Self {
+        Self {
+            free: Mutex::new(Vec::new()),
+            max_pooled,
+            retained_bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a zeroed buffer of `len` bytes that goes back to the pool on drop.
+    // Only the Windows raw input path draws from the pool so far.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) fn take(&self, len: usize) -> PooledBuffer<'_> {
+        let mut buffer = self
+            .free
+            .lock()
+            .ok()
+            .and_then(|mut free| free.pop())
+            .unwrap_or_default();
+        self.retained_bytes
+            .fetch_sub(buffer.capacity() as u64, Ordering::Relaxed);
+        buffer.clear();
+        buffer.resize(len, 0);
+        PooledBuffer { pool: self, buffer }
+    }
+
+    pub(crate) fn retained_bytes(&self) -> u64 {
+        self.retained_bytes.load(Ordering::Relaxed)
+    }
+
+    fn release(&self, buffer: Vec<u8>) {
+        let Ok(mut free) = self.free.lock() else {
+            return;
+        };
+        if free.len() < self.max_pooled {
+            self.retained_bytes
+                .fetch_add(buffer.capacity() as u64, Ordering::Relaxed);
+            free.push(buffer);
+        }
+    }
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+pub(crate) struct PooledBuffer<'a> {
+    pool: &'a BufferPool,
+    buffer: Vec<u8>,
+}
+
+impl Deref for PooledBuffer<'_> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl DerefMut for PooledBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        self.pool.release(std::mem::take(&mut self.buffer));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_budget_from_available_memory_within_bounds() {
+        assert_eq!(
+            resolve_memory_budget_bytes(None),
+            DEFAULT_MEMORY_BUDGET_BYTES
+        );
+        assert_eq!(
+            resolve_memory_budget_bytes(Some(1024 * MIB)),
+            MIN_MEMORY_BUDGET_BYTES
+        );
+        assert_eq!(resolve_memory_budget_bytes(Some(8192 * MIB)), 1024 * MIB);
+        assert_eq!(
+            resolve_memory_budget_bytes(Some(65536 * MIB)),
+            MAX_MEMORY_BUDGET_BYTES
+        );
+    }
+
+    #[test]
+    fn caps_queued_4k_hdr_frames_to_budget() {
+        let frame = decoded_frame_bytes(3840, 2160, ColorQuality::TenBit420);
+        assert_eq!(frame, 3840 * 2160 * 3);
+        assert_eq!(
+            decoded_frame_bytes(1920, 1080, ColorQuality::EightBit444),
+            1920 * 1080 * 3
+        );
+
+        assert_eq!(max_queued_decoded_frames(256 * MIB, frame, 8), 2);
+        assert_eq!(max_queued_decoded_frames(2048 * MIB, frame, 8), 8);
+        assert_eq!(max_queued_decoded_frames(MIB, frame, 8), 1);
+        assert_eq!(max_queued_decoded_frames(256 * MIB, 0, 3), 3);
+    }
+
+    #[test]
+    fn tracks_frame_size_across_resolution_changes() {
+        let budget = MemoryBudget::default();
+        budget.configure(256 * MIB, "3840x2160", ColorQuality::TenBit420);
+        assert_eq!(budget.max_queued_frames(8), 2);
+
+        budget.update_resolution("1920x1080");
+        assert_eq!(budget.max_queued_frames(8), 8);
+    }
+
+    #[test]
+    fn parses_available_memory_from_meminfo() {
+        let meminfo = "MemTotal:       32768000 kB\nMemFree:         1024000 kB\nMemAvailable:   16384000 kB\n";
+
+        assert_eq!(
+            parse_meminfo_available_bytes(meminfo),
+            Some(16_384_000 * 1024)
+        );
+        assert_eq!(parse_meminfo_available_bytes("MemTotal: 1 kB\n"), None);
+    }
+
+    #[test]
+    fn reuses_released_buffers() {
+        let pool = BufferPool::new(1);
+        {
+            let mut buffer = pool.take(64);
+            buffer[0] = 7;
+        }
+        assert_eq!(pool.retained_bytes(), 64);
+
+        let buffer = pool.take(16);
+        assert_eq!(buffer.len(), 16);
+        assert_eq!(buffer[0], 0);
+        assert!(buffer.capacity() >= 64);
+        assert_eq!(pool.retained_bytes(), 0);
+    }
+}
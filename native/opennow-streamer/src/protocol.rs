@@ -1,3 +1,4 @@
+use crate::input::GAMEPAD_MAX_CONTROLLERS;
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -28,7 +29,14 @@ pub struct CommandEnvelope {
     #[serde(default)]
     pub reason: Option<String>,
     #[serde(default)]
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
     pub shortcuts: Option<NativeStreamerShortcutBindings>,
+    #[serde(default)]
+    pub volume: Option<f64>,
+    #[serde(default)]
+    pub video_layer: Option<String>,
+    #[serde(default)]
+    pub render_fps_cap: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -36,6 +44,7 @@ pub struct NativeStreamerSessionContext {
     pub session: SessionInfo,
     pub settings: StreamSettings,
     #[serde(default)]
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
     pub shortcuts: NativeStreamerShortcutBindings,
 }
 
@@ -78,6 +87,99 @@ pub struct StreamSettings {
     #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
     #[serde(default)]
     pub native_transition_diagnostics: Option<NativeTransitionDiagnosticsSettings>,
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub ice_transport_policy: Option<String>,
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub custom_ice_servers: Vec<IceServerConfig>,
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub udp_port_range: Option<UdpPortRange>,
+    /// Create the input data channels at high priority so webrtcbin DSCP-marks the ICE socket.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub network_qos_marking: bool,
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub audio_output: Option<AudioOutputSettings>,
+    /// Player slots the native controller bridge may forward, indexed by slot.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub controller_slots: Option<Vec<bool>>,
+    /// `#rrggbb` lightbar color for controllers that have one.
+    #[cfg_attr(
+        not(all(feature = "gstreamer", feature = "dualsense")),
+        allow(dead_code)
+    )]
+    #[serde(default)]
+    pub controller_light_color: Option<String>,
+    /// Present at most this many decoded frames per second; 0 or unset renders every frame.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub render_fps_cap: Option<u32>,
+    /// Raise the decode and render threads; see `thread_priority`.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub media_thread_priority: bool,
+    /// Jitter buffer reorder window; `OPENNOW_NATIVE_JITTER_BUFFER_MS` still wins when set.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub jitter_buffer_ms: Option<u32>,
+    /// Competitive preset: no jitter buffer delay, a newest-frame-wins present
+    /// queue, eager late-frame drops and stats refreshed less often.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    #[serde(default)]
+    pub low_latency: bool,
+}
+
+#[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+impl StreamSettings {
+    /// Bit `n` allows player slot `n`; slots without an entry stay enabled.
+    pub fn controller_slot_mask(&self) -> u8 {
+        (0..GAMEPAD_MAX_CONTROLLERS).fold(0, |mask, slot| {
+            let enabled = self
+                .controller_slots
+                .as_ref()
+                .and_then(|slots| slots.get(slot as usize))
+                .copied()
+                .unwrap_or(true);
+            if enabled {
+                mask | (1 << slot)
+            } else {
+                mask
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+pub struct IceServerConfig {
+    pub urls: Vec<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub credential: Option<String>,
+}
+
+/// Audio sink tuning; `buffer_ms` of 0 keeps the platform default.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+pub struct AudioOutputSettings {
+    #[serde(default)]
+    pub exclusive: bool,
+    #[serde(default)]
+    pub buffer_ms: u32,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+pub struct UdpPortRange {
+    pub min: u16,
+    pub max: u16,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -289,6 +391,12 @@ pub struct NativeStreamerShortcutBindings {
     pub screenshot: String,
     #[serde(default)]
     pub toggle_recording: String,
+    #[serde(default)]
+    pub toggle_stream_mute: String,
+    #[serde(default)]
+    pub volume_up: String,
+    #[serde(default)]
+    pub volume_down: String,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
@@ -302,6 +410,9 @@ pub enum NativeStreamerShortcutAction {
     ToggleMicrophone,
     Screenshot,
     ToggleRecording,
+    ToggleStreamMute,
+    VolumeUp,
+    VolumeDown,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -327,6 +438,9 @@ pub struct NativeStreamerCapabilities {
         skip_serializing_if = "Vec::is_empty"
     )]
     pub video_backends: Vec<NativeVideoBackendCapability>,
+    /// Experimental flags set from the settings file or env, as `name=on|off (source)`.
+    #[serde(rename = "activeFlags", default, skip_serializing_if = "Vec::is_empty")]
+    pub active_flags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -349,6 +463,8 @@ pub struct NativeVideoBackendCapability {
 pub struct NativeVideoCodecCapability {
     pub codec: String,
     pub available: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub decoder: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -430,6 +546,19 @@ pub struct VideoStallEvent {
     pub recovery_attempt: u8,
 }
 
+/// Reports how a video stall ended: which recovery step was last applied
+/// before frames resumed, or that the watchdog escalated to a full reconnect.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoRecoveryEvent {
+    pub outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<&'static str>,
+    pub recovery_attempt: u8,
+    pub stall_ms: u64,
+    pub likely_stage: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct VideoTransitionEvent {
@@ -488,6 +617,7 @@ pub struct NativeStatsEvent {
     pub present_pacing_changes: u32,
     pub partial_flush_count: u32,
     pub complete_flush_count: u32,
+    pub frames_skipped_ahead: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_transition_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -498,6 +628,64 @@ pub struct NativeStatsEvent {
     pub finalized_streaming_features_summary: String,
     pub zero_copy_d3d11: bool,
     pub zero_copy_d3d12: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline_stage_summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decode_time_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub render_time_ms: Option<f64>,
+    pub keyframes_received: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyframe_interval_ms: Option<f64>,
+    pub damaged_frames: u64,
+    pub decoder_errors: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hdr_metadata: Option<String>,
+    /// Clock timestamp from the encoder's SEI, as `HH:MM:SS:FF`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoder_timecode: Option<String>,
+    /// Local wall clock minus the encoder's time code, when the encoder stamps wall-clock time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoder_latency_ms: Option<u64>,
+    pub jitter_buffer_target_ms: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jitter_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packets_received: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packets_lost: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packets_late: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub av_sync_offset_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub av_sync_audio_correction_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_output_latency_ms: Option<f64>,
+    pub audio_underruns: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_codec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_sample_rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_channels: Option<u32>,
+    pub memory_budget_mb: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_priority_summary: Option<String>,
+}
+
+/// One encoding the server offers for the video stream, either a simulcast
+/// `rid` or a separate video m-line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeVideoLayer {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -528,10 +716,21 @@ pub enum Event {
     },
     #[serde(rename = "video-stall")]
     VideoStall(VideoStallEvent),
+    #[serde(rename = "video-recovery")]
+    VideoRecovery(VideoRecoveryEvent),
     #[serde(rename = "video-transition")]
     VideoTransition { transition: VideoTransitionEvent },
+    #[serde(rename = "video-layers")]
+    VideoLayers {
+        layers: Vec<NativeVideoLayer>,
+        active: String,
+        /// Whether inactive layers are paused at the server over RTCP. When
+        /// false they keep arriving and are only dropped locally.
+        #[serde(rename = "pausedAtSender")]
+        paused_at_sender: bool,
+    },
     #[serde(rename = "stats")]
-    Stats { stats: NativeStatsEvent },
+    Stats { stats: Box<NativeStatsEvent> },
     #[serde(rename = "error")]
     Error { code: String, message: String },
 }
@@ -566,6 +765,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn controller_slot_mask_defaults_missing_slots_to_enabled() {
+        let settings = |slots: Value| -> StreamSettings {
+            serde_json::from_value(serde_json::json!({
+                "resolution": "1920x1080",
+                "fps": 60,
+                "maxBitrateMbps": 50,
+                "codec": "H264",
+                "colorQuality": "8bit_420",
+                "controllerSlots": slots,
+            }))
+            .expect("stream settings")
+        };
+
+        assert_eq!(settings(Value::Null).controller_slot_mask(), 0b1111);
+        assert_eq!(
+            settings(serde_json::json!([true, false])).controller_slot_mask(),
+            0b1101
+        );
+        assert_eq!(
+            settings(serde_json::json!([false, false, false, false])).controller_slot_mask(),
+            0
+        );
+    }
+
     #[test]
     fn input_packet_keeps_legacy_byte_array_payload() {
         let packet = NativeInputPacket {
@@ -620,6 +844,25 @@ mod tests {
         assert_eq!(value["lastTransitionType"], "high-fps-transition-risk");
     }
 
+    #[test]
+    fn video_recovery_event_serializes_as_flat_native_event() {
+        let event = Event::VideoRecovery(VideoRecoveryEvent {
+            outcome: "recovered",
+            step: Some("decoder-reset"),
+            recovery_attempt: 5,
+            stall_ms: 16_400,
+            likely_stage: "decode-chain-stalled".to_owned(),
+        });
+        let value = serde_json::to_value(event).expect("serializes");
+
+        assert_eq!(value["type"], "video-recovery");
+        assert_eq!(value["outcome"], "recovered");
+        assert_eq!(value["step"], "decoder-reset");
+        assert_eq!(value["recoveryAttempt"], 5);
+        assert_eq!(value["stallMs"], 16_400);
+        assert_eq!(value["likelyStage"], "decode-chain-stalled");
+    }
+
     #[test]
     fn video_transition_event_serializes_as_nested_transition_payload() {
         let event = Event::VideoTransition {
@@ -652,4 +895,33 @@ mod tests {
         assert_eq!(value["transition"]["transitionType"], "sink-caps-change");
         assert_eq!(value["transition"]["highFpsRisk"], true);
     }
+
+    #[test]
+    fn video_layers_event_omits_unknown_dimensions() {
+        let event = Event::VideoLayers {
+            layers: vec![
+                NativeVideoLayer {
+                    id: "h".to_owned(),
+                    mid: Some("1".to_owned()),
+                    width: Some(3840),
+                    height: Some(2160),
+                },
+                NativeVideoLayer {
+                    id: "l".to_owned(),
+                    mid: Some("1".to_owned()),
+                    width: None,
+                    height: None,
+                },
+            ],
+            active: "h".to_owned(),
+            paused_at_sender: true,
+        };
+        let value = serde_json::to_value(event).expect("serializes");
+
+        assert_eq!(value["type"], "video-layers");
+        assert_eq!(value["active"], "h");
+        assert_eq!(value["pausedAtSender"], true);
+        assert_eq!(value["layers"][0]["width"], 3840);
+        assert!(value["layers"][1].get("width").is_none());
+    }
 }
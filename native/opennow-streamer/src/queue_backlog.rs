@@ -0,0 +1,150 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+//! Skip-ahead policy for video queues that stay backed up.
+//!
+//! A decoder or GPU that runs slightly slower than the stream makes the
+//! queues fill and then stay full; every frame is shown, but later and later.
+//! Once a queue has been backed up for long enough, the client gives up on the
+//! queued frames and jumps to the newest one instead of playing catch-up.
+
+/// How long a queue has to stay backed up before frames are skipped.
+const BACKLOG_SUSTAIN_MS: u64 = 500;
+/// Minimum gap between skips so a keyframe request has time to arrive.
+const SKIP_COOLDOWN_MS: u64 = 2_000;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QueueLevel {
+    pub(crate) buffers: u32,
+    pub(crate) capacity: u32,
+}
+
+impl QueueLevel {
+    /// The compressed queue blocks its producer when full, so half full is
+    /// already a backlog; the leaky decoded queue only counts when full.
+    fn compressed_backed_up(self) -> bool {
+        self.buffers >= 2 && self.buffers.saturating_mul(2) >= self.capacity
+    }
+
+    fn decoded_backed_up(self) -> bool {
+        self.capacity >= 2 && self.buffers >= self.capacity
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SkipAhead {
+    None,
+    /// Drop the queued decoded frames; the next decoded one is shown.
+    DropDecoded,
+    /// Drop queued compressed frames and wait for a requested keyframe, the
+    /// next frame that decodes without the skipped references.
+    DropToKeyframe,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct QueueBacklogMonitor {
+    compressed_since_ms: Option<u64>,
+    decoded_since_ms: Option<u64>,
+    last_skip_ms: Option<u64>,
+}
+
+impl QueueBacklogMonitor {
+    pub(crate) fn evaluate(
+        &mut self,
+        now_ms: u64,
+        compressed: QueueLevel,
+        decoded: QueueLevel,
+    ) -> SkipAhead {
+        let track = |since: &mut Option<u64>, backed_up: bool| {
+            *since = backed_up.then(|| since.unwrap_or(now_ms));
+        };
+        track(
+            &mut self.compressed_since_ms,
+            compressed.compressed_backed_up(),
+        );
+        track(&mut self.decoded_since_ms, decoded.decoded_backed_up());
+
+        if self
+            .last_skip_ms
+            .is_some_and(|last| now_ms.saturating_sub(last) < SKIP_COOLDOWN_MS)
+        {
+            return SkipAhead::None;
+        }
+        let sustained = |since: Option<u64>| {
+            since.is_some_and(|at| now_ms.saturating_sub(at) >= BACKLOG_SUSTAIN_MS)
+        };
+        let action = if sustained(self.compressed_since_ms) {
+            SkipAhead::DropToKeyframe
+        } else if sustained(self.decoded_since_ms) {
+            SkipAhead::DropDecoded
+        } else {
+            return SkipAhead::None;
+        };
+        self.last_skip_ms = Some(now_ms);
+        self.compressed_since_ms = None;
+        self.decoded_since_ms = None;
+        action
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDLE: QueueLevel = QueueLevel {
+        buffers: 0,
+        capacity: 6,
+    };
+
+    #[test]
+    fn skips_only_after_a_sustained_backlog_and_then_cools_down() {
+        let mut monitor = QueueBacklogMonitor::default();
+        let full_decoded = QueueLevel {
+            buffers: 3,
+            capacity: 3,
+        };
+        assert_eq!(monitor.evaluate(0, IDLE, full_decoded), SkipAhead::None);
+        assert_eq!(monitor.evaluate(250, IDLE, full_decoded), SkipAhead::None);
+        assert_eq!(
+            monitor.evaluate(500, IDLE, full_decoded),
+            SkipAhead::DropDecoded
+        );
+        assert_eq!(monitor.evaluate(1_500, IDLE, full_decoded), SkipAhead::None);
+
+        let backed_up = QueueLevel {
+            buffers: 4,
+            capacity: 6,
+        };
+        let draining = QueueLevel {
+            buffers: 1,
+            capacity: 3,
+        };
+        assert_eq!(
+            monitor.evaluate(2_500, backed_up, draining),
+            SkipAhead::None
+        );
+        assert_eq!(
+            monitor.evaluate(3_000, backed_up, full_decoded),
+            SkipAhead::DropToKeyframe
+        );
+    }
+
+    #[test]
+    fn a_draining_queue_resets_the_backlog_timer() {
+        let mut monitor = QueueBacklogMonitor::default();
+        let backed_up = QueueLevel {
+            buffers: 3,
+            capacity: 6,
+        };
+        let single = QueueLevel {
+            buffers: 1,
+            capacity: 1,
+        };
+        assert_eq!(monitor.evaluate(0, backed_up, single), SkipAhead::None);
+        assert_eq!(monitor.evaluate(400, IDLE, single), SkipAhead::None);
+        assert_eq!(monitor.evaluate(800, backed_up, single), SkipAhead::None);
+        assert_eq!(
+            monitor.evaluate(1_300, backed_up, single),
+            SkipAhead::DropToKeyframe
+        );
+    }
+}
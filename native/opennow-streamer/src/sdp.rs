@@ -4,7 +4,8 @@ use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
 use crate::input::{PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL, PARTIALLY_RELIABLE_HID_DEVICE_MASK_ALL};
-use crate::protocol::{ColorQuality, VideoCodec};
+use crate::protocol::{ColorQuality, NativeVideoLayer, VideoCodec};
+use crate::sdp_model::SessionDescription;
 
 // Match the official web client's 240 FPS profile. Disabling split encode at
 // this frame rate can leave H265 streams smeared because the server/client
@@ -35,9 +36,151 @@ pub struct NvstParams {
     pub enable_partially_reliable_transfer_hid: Option<u32>,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct PreferCodecOptions {
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PreferCodecOptions<'a> {
     pub prefer_hevc_profile_id: Option<u8>,
+    pub decoder_support: Option<&'a DecoderProfileSupport>,
+}
+
+/// Profiles and tiers the local decoder advertises on its sink caps, in
+/// GStreamer caps vocabulary (`high`, `main-10`, ...). An empty list means the
+/// decoder does not restrict that field.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecoderProfileSupport {
+    pub profiles: Vec<String>,
+    pub tiers: Vec<String>,
+}
+
+/// Decoder support for every codec the native streamer can negotiate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecoderProfiles {
+    pub h264: DecoderProfileSupport,
+    pub h265: DecoderProfileSupport,
+    pub av1: DecoderProfileSupport,
+}
+
+impl DecoderProfiles {
+    pub fn for_codec(&self, codec: VideoCodec) -> &DecoderProfileSupport {
+        match codec {
+            VideoCodec::H264 => &self.h264,
+            VideoCodec::H265 => &self.h265,
+            VideoCodec::AV1 => &self.av1,
+        }
+    }
+}
+
+impl DecoderProfileSupport {
+    fn accepts(&self, profile: Option<&str>, tier: Option<&str>) -> bool {
+        let profile_ok = match profile {
+            Some(profile) if !self.profiles.is_empty() => decodable_as(profile)
+                .iter()
+                .any(|candidate| self.profiles.iter().any(|supported| supported == candidate)),
+            _ => true,
+        };
+        let tier_ok = match tier {
+            Some(tier) if !self.tiers.is_empty() => self
+                .tiers
+                .iter()
+                .any(|supported| supported == tier || (tier == "main" && supported == "high")),
+            _ => true,
+        };
+        profile_ok && tier_ok
+    }
+}
+
+/// Profiles whose decoders also decode `profile`, the profile itself first.
+fn decodable_as(profile: &str) -> &'static [&'static str] {
+    match profile {
+        "constrained-baseline" => &["constrained-baseline", "baseline", "main", "high"],
+        "baseline" => &["baseline"],
+        "extended" => &["extended"],
+        "progressive-high" | "constrained-high" => {
+            &["progressive-high", "constrained-high", "high"]
+        }
+        "high" => &["high", "professional"],
+        "high-10" => &["high-10"],
+        "high-4:2:2" => &["high-4:2:2"],
+        "high-4:4:4" => &["high-4:4:4"],
+        "main" => &["main", "high", "main-10"],
+        "main-10" => &["main-10"],
+        "main-still-picture" => &["main-still-picture", "main", "main-10"],
+        "main-444" => &["main-444", "main-444-10"],
+        "professional" => &["professional"],
+        _ => &[],
+    }
+}
+
+/// Maps an H.264 `profile-level-id` to its GStreamer caps profile name.
+pub fn h264_profile_name(profile_level_id: &str) -> Option<&'static str> {
+    let value = u32::from_str_radix(profile_level_id.get(..6)?, 16).ok()?;
+    let profile_idc = value >> 16;
+    let constraints = (value >> 8) & 0xff;
+    Some(match profile_idc {
+        66 if constraints & 0x40 != 0 => "constrained-baseline",
+        66 => "baseline",
+        77 => "main",
+        88 => "extended",
+        100 if constraints & 0x0c == 0x0c => "constrained-high",
+        100 if constraints & 0x08 != 0 => "progressive-high",
+        100 => "high",
+        110 => "high-10",
+        122 => "high-4:2:2",
+        244 => "high-4:4:4",
+        _ => return None,
+    })
+}
+
+/// Maps an H.265 `profile-id` to its GStreamer caps profile name. Range
+/// extensions (4) are reported as `main-444`, the family 4:4:4 streams use.
+pub fn h265_profile_name(profile_id: u32) -> Option<&'static str> {
+    Some(match profile_id {
+        1 => "main",
+        2 => "main-10",
+        3 => "main-still-picture",
+        4 => "main-444",
+        _ => return None,
+    })
+}
+
+fn av1_profile_name(profile: u32) -> Option<&'static str> {
+    Some(match profile {
+        0 => "main",
+        1 => "high",
+        2 => "professional",
+        _ => return None,
+    })
+}
+
+/// Profile and tier a payload type's fmtp line declares, in caps vocabulary.
+fn fmtp_profile(codec: VideoCodec, fmtp: &str) -> (Option<&'static str>, Option<&'static str>) {
+    match codec {
+        VideoCodec::H264 => (
+            fmtp.split(';')
+                .filter_map(|part| part.trim().split_once('='))
+                .find(|(key, _)| key.eq_ignore_ascii_case("profile-level-id"))
+                .and_then(|(_, value)| h264_profile_name(value.trim())),
+            None,
+        ),
+        VideoCodec::H265 => (
+            capture_numeric_param(fmtp, "profile-id").and_then(h265_profile_name),
+            capture_numeric_param(fmtp, "tier-flag")
+                .map(|tier| if tier == 1 { "high" } else { "main" }),
+        ),
+        VideoCodec::AV1 => (
+            capture_numeric_param(fmtp, "profile").and_then(av1_profile_name),
+            None,
+        ),
+    }
+}
+
+/// What `prefer_codec` did with the offer. `applied` is false when the codec
+/// was not offered, or when every payload type for it was rejected by the
+/// decoder, in which case `rejected` names those payloads and the offer is
+/// left untouched so the caller can fall back to another codec.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreferCodecOutcome {
+    pub applied: bool,
+    pub rejected: Vec<String>,
 }
 
 pub fn parse_resolution(value: &str) -> Option<(u32, u32)> {
@@ -70,80 +213,56 @@ pub fn extract_public_ip(host_or_ip: &str) -> Option<String> {
     None
 }
 
-pub fn fix_server_ip(sdp: &str, server_ip: &str) -> String {
+pub fn fix_server_ip(description: &mut SessionDescription, server_ip: &str) {
     let Some(ip) = extract_public_ip(server_ip) else {
-        return sdp.to_owned();
+        return;
     };
 
-    let fixed = sdp.replace("c=IN IP4 0.0.0.0", &format!("c=IN IP4 {ip}"));
     let candidate_re =
         Regex::new(r"(a=candidate:\S+\s+\d+\s+\w+\s+\d+\s+)0\.0\.0\.0(\s+)").expect("valid regex");
-    candidate_re
-        .replace_all(&fixed, format!("${{1}}{ip}${{2}}"))
-        .into_owned()
+    for line in description.lines_mut() {
+        if let Some(rest) = line.strip_prefix("c=IN IP4 0.0.0.0") {
+            *line = format!("c=IN IP4 {ip}{rest}");
+        } else if line.starts_with("a=candidate:") {
+            *line = candidate_re
+                .replace(line, format!("${{1}}{ip}${{2}}"))
+                .into_owned();
+        }
+    }
 }
 
-pub fn duplicate_session_webrtc_attributes_to_media(sdp: &str) -> String {
-    let ending = line_ending(sdp);
-    let lines = split_lines_lossless(sdp);
-    let first_media_index = lines.iter().position(|line| line.starts_with("m="));
-    let Some(first_media_index) = first_media_index else {
-        return sdp.to_owned();
-    };
+pub fn duplicate_session_webrtc_attributes_to_media(description: &mut SessionDescription) {
+    if description.media.is_empty() {
+        return;
+    }
 
-    let session_attributes: Vec<&str> = lines[..first_media_index]
+    let session_attributes: Vec<String> = description
+        .session
         .iter()
-        .copied()
-        .filter(|line| {
-            line.starts_with("a=ice-ufrag:")
-                || line.starts_with("a=ice-pwd:")
-                || line.starts_with("a=ice-options:")
-                || line.starts_with("a=fingerprint:")
-                || line.starts_with("a=setup:")
-        })
+        .filter(|line| line.starts_with("a=ice-options:") || is_media_transport_attribute(line))
+        .cloned()
         .collect();
-
     if session_attributes.is_empty() {
-        return sdp.to_owned();
+        return;
     }
 
-    let mut output: Vec<String> = lines[..first_media_index]
-        .iter()
-        .filter(|line| !is_media_transport_attribute(line))
-        .map(|line| (*line).to_owned())
-        .collect();
-
-    let mut index = first_media_index;
-    while index < lines.len() {
-        let section_start = index;
-        index += 1;
-        while index < lines.len() && !lines[index].starts_with("m=") {
-            index += 1;
-        }
-        let section = &lines[section_start..index];
-        let insert_index = section
+    description
+        .session
+        .retain(|line| !is_media_transport_attribute(line));
+    for section in &mut description.media {
+        let missing = session_attributes
             .iter()
-            .position(|line| line.starts_with("a="))
-            .unwrap_or(section.len());
-
-        for line in &section[..insert_index] {
-            output.push((*line).to_owned());
-        }
-        for attribute in &session_attributes {
-            let prefix = attribute
-                .split_once(':')
-                .map(|(prefix, _)| format!("{prefix}:"))
-                .unwrap_or_else(|| (*attribute).to_owned());
-            if !section.iter().any(|line| line.starts_with(&prefix)) {
-                output.push((*attribute).to_owned());
-            }
-        }
-        for line in &section[insert_index..] {
-            output.push((*line).to_owned());
-        }
+            .filter(|attribute| {
+                let prefix = attribute
+                    .split_once(':')
+                    .map_or(attribute.as_str(), |(prefix, _)| prefix);
+                let prefix = format!("{prefix}:");
+                !section.lines.iter().any(|line| line.starts_with(&prefix))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        section.insert_attributes(missing);
     }
-
-    output.join(ending)
 }
 
 pub fn summarize_media_transport_attributes(sdp: &str) -> String {
@@ -191,27 +310,20 @@ pub fn summarize_media_transport_attributes(sdp: &str) -> String {
     )
 }
 
-pub fn sanitize_ice_pwd_for_gstreamer(sdp: &str) -> (String, usize) {
-    let ending = line_ending(sdp);
+pub fn sanitize_ice_pwd_for_gstreamer(description: &mut SessionDescription) -> usize {
     let mut replacements = 0usize;
-    let lines = split_lines_lossless(sdp)
-        .into_iter()
-        .map(|line| {
-            let Some(value) = line.strip_prefix("a=ice-pwd:") else {
-                return line.to_owned();
-            };
-
-            let sanitized = sanitize_ice_pwd_value(value);
-            if sanitized == value {
-                return line.to_owned();
-            }
+    for line in description.lines_mut() {
+        let Some(value) = line.strip_prefix("a=ice-pwd:") else {
+            continue;
+        };
 
+        let sanitized = sanitize_ice_pwd_value(value);
+        if sanitized != value {
+            *line = format!("a=ice-pwd:{sanitized}");
             replacements += 1;
-            format!("a=ice-pwd:{sanitized}")
-        })
-        .collect::<Vec<_>>();
-
-    (lines.join(ending), replacements)
+        }
+    }
+    replacements
 }
 
 fn sanitize_ice_pwd_value(value: &str) -> String {
@@ -348,6 +460,69 @@ pub fn extract_negotiated_video_codec(sdp: &str) -> Option<VideoCodec> {
         })
 }
 
+/// Video layers the server offers to send: one per `a=rid` for rid-based
+/// simulcast, otherwise one per active video m-line.
+pub fn extract_video_layers(description: &SessionDescription) -> Vec<NativeVideoLayer> {
+    let mut layers = Vec::new();
+    let active_video_sections = description
+        .media
+        .iter()
+        .filter(|section| section.kind() == "video" && !section.is_rejected());
+
+    for (index, section) in active_video_sections.enumerate() {
+        let mid = section.attribute("mid").map(|mid| mid.trim().to_owned());
+        let rids = section
+            .attributes("rid")
+            .filter_map(parse_send_rid)
+            .collect::<Vec<_>>();
+        if rids.is_empty() {
+            layers.push(NativeVideoLayer {
+                id: mid.clone().unwrap_or_else(|| format!("video{}", index + 1)),
+                mid,
+                width: None,
+                height: None,
+            });
+        } else {
+            layers.extend(rids.into_iter().map(|layer| NativeVideoLayer {
+                mid: mid.clone(),
+                ..layer
+            }));
+        }
+    }
+
+    layers
+}
+
+/// Whether the offer accepts TMMBR (RFC 5104) for video, which lets the
+/// client ask the server to stop sending a layer without renegotiating.
+pub fn offers_video_tmmbr(description: &SessionDescription) -> bool {
+    description
+        .media
+        .iter()
+        .filter(|section| section.kind() == "video" && !section.is_rejected())
+        .flat_map(|section| section.attributes("rtcp-fb"))
+        .any(|value| {
+            value
+                .split_once(char::is_whitespace)
+                .is_some_and(|(_, feedback)| feedback.trim() == "ccm tmmbr")
+        })
+}
+
+fn parse_send_rid(value: &str) -> Option<NativeVideoLayer> {
+    let mut parts = value.split_whitespace();
+    let id = parts.next()?;
+    if parts.next() != Some("send") {
+        return None;
+    }
+    let params = parts.next().unwrap_or_default();
+    Some(NativeVideoLayer {
+        id: id.to_owned(),
+        mid: None,
+        width: capture_numeric_param(params, "max-width"),
+        height: capture_numeric_param(params, "max-height"),
+    })
+}
+
 fn line_ending(sdp: &str) -> &'static str {
     if sdp.contains("\r\n") {
         "\r\n"
@@ -371,87 +546,95 @@ fn split_lines_lossless(sdp: &str) -> Vec<&str> {
         .collect()
 }
 
-pub fn prefer_codec(sdp: &str, codec: VideoCodec, options: PreferCodecOptions) -> String {
-    let ending = line_ending(sdp);
-    let lines = split_lines_lossless(sdp);
+pub fn prefer_codec(
+    description: &mut SessionDescription,
+    codec: VideoCodec,
+    options: PreferCodecOptions,
+) -> PreferCodecOutcome {
     let target_codec = codec.as_str();
-    let mut in_video_section = false;
     let mut payload_types_by_codec: HashMap<String, Vec<String>> = HashMap::new();
     let mut codec_by_payload_type: HashMap<String, String> = HashMap::new();
     let mut rtx_apt_by_payload_type: HashMap<String, String> = HashMap::new();
     let mut fmtp_by_payload_type: HashMap<String, String> = HashMap::new();
-
-    for line in &lines {
-        if line.starts_with("m=video") {
-            in_video_section = true;
-            continue;
-        }
-        if line.starts_with("m=") && in_video_section {
-            in_video_section = false;
-        }
-        if !in_video_section || !line.starts_with("a=rtpmap:") {
-            continue;
-        }
-
-        let rest = line.strip_prefix("a=rtpmap:").unwrap_or_default();
-        let mut parts = rest.split_whitespace();
-        let Some(pt) = parts.next() else {
-            continue;
-        };
-        let Some(codec_part) = parts.next() else {
-            continue;
-        };
-        let codec_name = normalize_codec(codec_part.split('/').next().unwrap_or_default());
-        if codec_name.is_empty() {
-            continue;
-        }
-        payload_types_by_codec
-            .entry(codec_name.clone())
-            .or_default()
-            .push(pt.to_owned());
-        codec_by_payload_type.insert(pt.to_owned(), codec_name);
-    }
-
-    in_video_section = false;
     let apt_re = Regex::new(r"(?i)(?:^|;)\s*apt=(\d+)").expect("valid regex");
-    for line in &lines {
-        if line.starts_with("m=video") {
-            in_video_section = true;
-            continue;
-        }
-        if line.starts_with("m=") && in_video_section {
-            in_video_section = false;
-        }
-        if !in_video_section || !line.starts_with("a=fmtp:") {
-            continue;
-        }
 
-        let rest = line
-            .split_once(':')
-            .map(|(_, rest)| rest)
-            .unwrap_or_default();
-        let mut parts = rest.splitn(2, char::is_whitespace);
-        let Some(pt) = parts.next() else {
-            continue;
-        };
-        let params = parts.next().unwrap_or_default().trim();
-        if pt.is_empty() || params.is_empty() {
-            continue;
+    for section in description
+        .media
+        .iter()
+        .filter(|section| section.kind() == "video")
+    {
+        for rtpmap in section.attributes("rtpmap") {
+            let mut parts = rtpmap.split_whitespace();
+            let Some(pt) = parts.next() else {
+                continue;
+            };
+            let Some(codec_part) = parts.next() else {
+                continue;
+            };
+            let codec_name = normalize_codec(codec_part.split('/').next().unwrap_or_default());
+            if codec_name.is_empty() {
+                continue;
+            }
+            payload_types_by_codec
+                .entry(codec_name.clone())
+                .or_default()
+                .push(pt.to_owned());
+            codec_by_payload_type.insert(pt.to_owned(), codec_name);
         }
 
-        if let Some(captures) = apt_re.captures(params) {
-            if let Some(apt) = captures.get(1) {
-                rtx_apt_by_payload_type.insert(pt.to_owned(), apt.as_str().to_owned());
+        for fmtp in section.attributes("fmtp") {
+            let mut parts = fmtp.splitn(2, char::is_whitespace);
+            let Some(pt) = parts.next() else {
+                continue;
+            };
+            let params = parts.next().unwrap_or_default().trim();
+            if pt.is_empty() || params.is_empty() {
+                continue;
             }
+
+            if let Some(captures) = apt_re.captures(params) {
+                if let Some(apt) = captures.get(1) {
+                    rtx_apt_by_payload_type.insert(pt.to_owned(), apt.as_str().to_owned());
+                }
+            }
+            fmtp_by_payload_type.insert(pt.to_owned(), params.to_owned());
         }
-        fmtp_by_payload_type.insert(pt.to_owned(), params.to_owned());
     }
 
-    let Some(preferred_payloads) = payload_types_by_codec.get(target_codec) else {
-        return sdp.to_owned();
+    let Some(offered_payloads) = payload_types_by_codec.get(target_codec) else {
+        return PreferCodecOutcome::default();
     };
+
+    let mut rejected = Vec::new();
+    let preferred_payloads = offered_payloads
+        .iter()
+        .filter(|pt| {
+            let Some(support) = options.decoder_support else {
+                return true;
+            };
+            let fmtp = fmtp_by_payload_type
+                .get(*pt)
+                .map(String::as_str)
+                .unwrap_or_default();
+            let (profile, tier) = fmtp_profile(codec, fmtp);
+            let accepted = support.accepts(profile, tier);
+            if !accepted {
+                rejected.push(format!(
+                    "{pt} ({}{})",
+                    profile.unwrap_or("unknown profile"),
+                    tier.map(|tier| format!(", {tier} tier"))
+                        .unwrap_or_default()
+                ));
+            }
+            accepted
+        })
+        .cloned()
+        .collect::<Vec<_>>();
     if preferred_payloads.is_empty() {
-        return sdp.to_owned();
+        return PreferCodecOutcome {
+            applied: false,
+            rejected,
+        };
     }
 
     let mut ordered_preferred_payloads = preferred_payloads.clone();
@@ -493,71 +676,37 @@ pub fn prefer_codec(sdp: &str, codec: VideoCodec, options: PreferCodecOptions) -
         }
     }
 
-    let mut filtered = Vec::new();
-    in_video_section = false;
-
-    for line in lines {
-        if line.starts_with("m=video") {
-            in_video_section = true;
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            let header = &parts[..parts.len().min(3)];
-            let available: Vec<&str> = parts
-                .iter()
-                .skip(3)
-                .copied()
-                .filter(|pt| allowed.contains(*pt))
-                .collect();
-            let mut ordered = Vec::new();
-
-            for pt in &ordered_preferred_payloads {
-                if available.contains(&pt.as_str()) {
-                    ordered.push(pt.as_str());
-                }
-            }
-            for pt in available {
-                if !preferred.contains(pt) {
-                    ordered.push(pt);
-                }
-            }
-
-            if ordered.is_empty() {
-                filtered.push(line.to_owned());
-            } else {
-                filtered.push(
-                    header
-                        .iter()
-                        .chain(ordered.iter())
-                        .copied()
-                        .collect::<Vec<_>>()
-                        .join(" "),
-                );
+    for section in description.video_sections_mut() {
+        let available: Vec<String> = section
+            .formats()
+            .into_iter()
+            .filter(|pt| allowed.contains(*pt))
+            .map(ToOwned::to_owned)
+            .collect();
+        let mut ordered = Vec::new();
+
+        for pt in &ordered_preferred_payloads {
+            if available.contains(pt) {
+                ordered.push(pt.as_str());
             }
-            continue;
         }
-
-        if line.starts_with("m=") && in_video_section {
-            in_video_section = false;
-        }
-
-        if in_video_section
-            && (line.starts_with("a=rtpmap:")
-                || line.starts_with("a=fmtp:")
-                || line.starts_with("a=rtcp-fb:"))
-        {
-            let rest = line
-                .split_once(':')
-                .map(|(_, rest)| rest)
-                .unwrap_or_default();
-            let pt = rest.split_whitespace().next().unwrap_or_default();
-            if !pt.is_empty() && !allowed.contains(pt) {
-                continue;
+        for pt in &available {
+            if !preferred.contains(pt) {
+                ordered.push(pt.as_str());
             }
         }
 
-        filtered.push(line.to_owned());
+        if !ordered.is_empty() {
+            section.set_formats(&ordered);
+        }
+        section
+            .retain_payload_attributes(&["rtpmap", "fmtp", "rtcp-fb"], |pt| allowed.contains(pt));
     }
 
-    filtered.join(ending)
+    PreferCodecOutcome {
+        applied: true,
+        rejected,
+    }
 }
 
 fn capture_numeric_param(params: &str, key: &str) -> Option<u32> {
@@ -838,7 +987,12 @@ mod tests {
     #[test]
     fn fixes_connection_and_candidate_ips() {
         let offer = "v=0\nc=IN IP4 0.0.0.0\na=candidate:1 1 udp 1 0.0.0.0 49000 typ host\n";
-        let fixed = fix_server_ip(offer, "80-250-97-40.cloudmatchbeta.nvidiagrid.net");
+        let mut description = SessionDescription::parse(offer).expect("valid sdp");
+        fix_server_ip(
+            &mut description,
+            "80-250-97-40.cloudmatchbeta.nvidiagrid.net",
+        );
+        let fixed = description.to_string();
         assert!(fixed.contains("c=IN IP4 80.250.97.40"));
         assert!(fixed.contains("a=candidate:1 1 udp 1 80.250.97.40 49000 typ host"));
     }
@@ -861,7 +1015,9 @@ mod tests {
         ]
         .join("\n");
 
-        let normalized = duplicate_session_webrtc_attributes_to_media(&offer);
+        let mut description = SessionDescription::parse(&offer).expect("valid sdp");
+        duplicate_session_webrtc_attributes_to_media(&mut description);
+        let normalized = description.to_string();
         let session_part = normalized.split("\nm=").next().expect("session section");
         let media_sections = normalized
             .split("\nm=")
@@ -883,6 +1039,79 @@ mod tests {
         assert!(!session_part.contains("a=setup:actpass"));
     }
 
+    #[test]
+    fn extracts_simulcast_rids_as_video_layers() {
+        let offer = [
+            "v=0",
+            "m=audio 9 UDP/TLS/RTP/SAVPF 111",
+            "a=mid:0",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96",
+            "a=mid:1",
+            "a=rid:h send pt=96;max-width=3840;max-height=2160",
+            "a=rid:m send max-width=1920;max-height=1080",
+            "a=rid:r recv",
+            "a=simulcast:send h;m",
+        ]
+        .join("\r\n");
+
+        let layers = extract_video_layers(&SessionDescription::parse(&offer).expect("valid sdp"));
+
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].id, "h");
+        assert_eq!(layers[0].mid.as_deref(), Some("1"));
+        assert_eq!(
+            (layers[0].width, layers[0].height),
+            (Some(3840), Some(2160))
+        );
+        assert_eq!(layers[1].id, "m");
+        assert_eq!(
+            (layers[1].width, layers[1].height),
+            (Some(1920), Some(1080))
+        );
+    }
+
+    #[test]
+    fn extracts_active_video_m_lines_as_video_layers() {
+        let offer = [
+            "v=0",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96",
+            "a=mid:1",
+            "m=video 0 UDP/TLS/RTP/SAVPF 96",
+            "a=mid:2",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96",
+            "m=application 9 UDP/DTLS/SCTP webrtc-datachannel",
+            "a=mid:3",
+        ]
+        .join("\n");
+
+        let layers = extract_video_layers(&SessionDescription::parse(&offer).expect("valid sdp"));
+
+        assert_eq!(
+            layers
+                .iter()
+                .map(|layer| layer.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1", "video2"]
+        );
+        assert_eq!(layers[1].mid, None);
+        let audio_only = SessionDescription::parse("v=0\nm=audio 9 RTP 111\n").expect("valid sdp");
+        assert_eq!(extract_video_layers(&audio_only), Vec::new());
+    }
+
+    #[test]
+    fn detects_tmmbr_only_on_active_video_m_lines() {
+        let parse = |sdp: &str| SessionDescription::parse(sdp).expect("valid sdp");
+        let offer =
+            "v=0\nm=video 9 UDP/TLS/RTP/SAVPF 96\na=rtcp-fb:96 nack pli\na=rtcp-fb:96 ccm tmmbr\n";
+        assert!(offers_video_tmmbr(&parse(offer)));
+
+        let wildcard = "v=0\nm=video 9 UDP/TLS/RTP/SAVPF 96\na=rtcp-fb:* ccm tmmbr\n";
+        assert!(offers_video_tmmbr(&parse(wildcard)));
+
+        let fir_only = "v=0\nm=video 9 UDP/TLS/RTP/SAVPF 96\na=rtcp-fb:96 ccm fir\nm=audio 9 RTP 111\na=rtcp-fb:111 ccm tmmbr\n";
+        assert!(!offers_video_tmmbr(&parse(fir_only)));
+    }
+
     #[test]
     fn keeps_existing_media_webrtc_attributes() {
         let offer = [
@@ -896,7 +1125,9 @@ mod tests {
         ]
         .join("\n");
 
-        let normalized = duplicate_session_webrtc_attributes_to_media(&offer);
+        let mut description = SessionDescription::parse(&offer).expect("valid sdp");
+        duplicate_session_webrtc_attributes_to_media(&mut description);
+        let normalized = description.to_string();
 
         assert!(normalized.contains("a=ice-ufrag:media"));
         assert!(normalized.contains("a=ice-pwd:media-pass"));
@@ -936,7 +1167,9 @@ mod tests {
         ]
         .join("\n");
 
-        let (sanitized, replacements) = sanitize_ice_pwd_for_gstreamer(&sdp);
+        let mut description = SessionDescription::parse(&sdp).expect("valid sdp");
+        let replacements = sanitize_ice_pwd_for_gstreamer(&mut description);
+        let sanitized = description.to_string();
 
         assert_eq!(replacements, 1);
         assert!(sanitized.contains("a=ice-pwd:48ca4c4b199a454cb58a3d14739335a3"));
@@ -1126,13 +1359,16 @@ mod tests {
             "m=audio 9 UDP/TLS/RTP/SAVPF 111",
         ]
         .join("\n");
-        let filtered = prefer_codec(
-            &sdp,
+        let mut description = SessionDescription::parse(&sdp).expect("valid sdp");
+        prefer_codec(
+            &mut description,
             VideoCodec::H265,
             PreferCodecOptions {
                 prefer_hevc_profile_id: Some(1),
+                decoder_support: None,
             },
         );
+        let filtered = description.to_string();
         assert!(filtered.contains("m=video 9 UDP/TLS/RTP/SAVPF 98 99 101"));
         assert!(!filtered.contains("a=rtpmap:96 H264/90000"));
         assert!(filtered.contains("a=rtpmap:99 rtx/90000"));
@@ -1140,6 +1376,85 @@ mod tests {
         assert!(filtered.contains("m=audio 9 UDP/TLS/RTP/SAVPF 111"));
     }
 
+    #[test]
+    fn maps_fmtp_profiles_to_caps_names() {
+        assert_eq!(h264_profile_name("42e01f"), Some("constrained-baseline"));
+        assert_eq!(h264_profile_name("640c1f"), Some("constrained-high"));
+        assert_eq!(h264_profile_name("64001f"), Some("high"));
+        assert_eq!(h264_profile_name("f4001f"), Some("high-4:4:4"));
+        assert_eq!(h264_profile_name("zz"), None);
+        assert_eq!(h265_profile_name(2), Some("main-10"));
+        assert_eq!(h265_profile_name(9), None);
+    }
+
+    #[test]
+    fn drops_payloads_with_profiles_the_decoder_rejects() {
+        let sdp = [
+            "v=0",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96 97 98 99",
+            "a=rtpmap:96 H264/90000",
+            "a=fmtp:96 profile-level-id=f4001f;packetization-mode=1",
+            "a=rtpmap:97 rtx/90000",
+            "a=fmtp:97 apt=96",
+            "a=rtpmap:98 H264/90000",
+            "a=fmtp:98 profile-level-id=42e01f;packetization-mode=1",
+            "a=rtpmap:99 rtx/90000",
+            "a=fmtp:99 apt=98",
+        ]
+        .join("\n");
+        let support = DecoderProfileSupport {
+            profiles: vec!["main".to_owned(), "high".to_owned()],
+            tiers: Vec::new(),
+        };
+        let mut description = SessionDescription::parse(&sdp).expect("valid sdp");
+
+        let outcome = prefer_codec(
+            &mut description,
+            VideoCodec::H264,
+            PreferCodecOptions {
+                prefer_hevc_profile_id: None,
+                decoder_support: Some(&support),
+            },
+        );
+
+        assert!(outcome.applied);
+        assert_eq!(outcome.rejected, vec!["96 (high-4:4:4)".to_owned()]);
+        let filtered = description.to_string();
+        assert!(filtered.contains("m=video 9 UDP/TLS/RTP/SAVPF 98 99"));
+        assert!(!filtered.contains("a=fmtp:96"));
+        assert!(!filtered.contains("a=fmtp:97"));
+    }
+
+    #[test]
+    fn leaves_offer_untouched_when_no_profile_is_decodable() {
+        let sdp = [
+            "v=0",
+            "m=video 9 UDP/TLS/RTP/SAVPF 98 100",
+            "a=rtpmap:98 H265/90000",
+            "a=fmtp:98 profile-id=1;tier-flag=1;level-id=186",
+            "a=rtpmap:100 H264/90000",
+        ]
+        .join("\n");
+        let support = DecoderProfileSupport {
+            profiles: Vec::new(),
+            tiers: vec!["main".to_owned()],
+        };
+        let mut description = SessionDescription::parse(&sdp).expect("valid sdp");
+
+        let outcome = prefer_codec(
+            &mut description,
+            VideoCodec::H265,
+            PreferCodecOptions {
+                prefer_hevc_profile_id: Some(1),
+                decoder_support: Some(&support),
+            },
+        );
+
+        assert!(!outcome.applied);
+        assert_eq!(outcome.rejected, vec!["98 (main, high tier)".to_owned()]);
+        assert_eq!(description.to_string(), sdp);
+    }
+
     #[test]
     fn builds_nvst_sdp_with_core_attributes() {
         let nvst = build_nvst_sdp(&NvstParams {
@@ -0,0 +1,544 @@
+use std::fmt;
+
+/// An SDP blob split into its session-level lines and media sections.
+///
+/// Lines keep their original text, so parsing and serializing an unmodified
+/// description reproduces the input byte for byte (apart from blank lines).
+/// Transformations edit lines inside the section they belong to instead of
+/// scanning the whole blob for prefixes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionDescription {
+    pub session: Vec<String>,
+    pub media: Vec<MediaSection>,
+    line_ending: &'static str,
+    trailing_line_ending: bool,
+}
+
+/// One `m=` section. `lines[0]` is always the media line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaSection {
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SdpParseError {
+    Empty,
+    MissingVersion,
+    MalformedLine { line_number: usize, line: String },
+    MalformedMediaLine { line_number: usize, line: String },
+}
+
+impl fmt::Display for SdpParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "SDP is empty"),
+            Self::MissingVersion => write!(f, "SDP does not start with a v= line"),
+            Self::MalformedLine { line_number, line } => {
+                write!(
+                    f,
+                    "SDP line {line_number} is not a <type>=<value> line: {line:?}"
+                )
+            }
+            Self::MalformedMediaLine { line_number, line } => {
+                write!(f, "SDP line {line_number} is not a valid m= line: {line:?}")
+            }
+        }
+    }
+}
+
+fn is_sdp_line(line: &str) -> bool {
+    let bytes = line.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_lowercase() && bytes[1] == b'='
+}
+
+fn is_media_line(line: &str) -> bool {
+    let mut fields = line["m=".len()..].split(' ');
+    let media = fields.next().unwrap_or_default();
+    let port = fields.next().unwrap_or_default();
+    let port = port.split_once('/').map_or(port, |(port, _)| port);
+    let proto = fields.next().unwrap_or_default();
+    !media.is_empty()
+        && port.parse::<u16>().is_ok()
+        && !proto.is_empty()
+        && fields.next().is_some_and(|format| !format.is_empty())
+}
+
+/// Non-empty lines with their 1-based line numbers. CRLF counts as one
+/// line break, as does a lone CR or LF.
+fn numbered_lines(sdp: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut line_number = 1;
+    let mut rest = sdp;
+    while !rest.is_empty() {
+        let end = rest.find(['\r', '\n']).unwrap_or(rest.len());
+        let (line, tail) = rest.split_at(end);
+        if !line.is_empty() {
+            lines.push((line_number, line));
+        }
+        let separator = if tail.starts_with("\r\n") {
+            2
+        } else {
+            tail.len().min(1)
+        };
+        rest = &tail[separator..];
+        line_number += 1;
+    }
+    lines
+}
+
+impl SessionDescription {
+    pub fn parse(sdp: &str) -> Result<Self, SdpParseError> {
+        let line_ending = if sdp.contains("\r\n") { "\r\n" } else { "\n" };
+        let trailing_line_ending = sdp.ends_with('\n');
+        let mut lines = numbered_lines(sdp).into_iter();
+
+        let Some((_, first)) = lines.next() else {
+            return Err(SdpParseError::Empty);
+        };
+        if !first.starts_with("v=") {
+            return Err(SdpParseError::MissingVersion);
+        }
+
+        let mut description = Self {
+            session: vec![first.to_owned()],
+            media: Vec::new(),
+            line_ending,
+            trailing_line_ending,
+        };
+        for (line_number, line) in lines {
+            if !is_sdp_line(line) {
+                return Err(SdpParseError::MalformedLine {
+                    line_number,
+                    line: line.to_owned(),
+                });
+            }
+            if line.starts_with("m=") {
+                if !is_media_line(line) {
+                    return Err(SdpParseError::MalformedMediaLine {
+                        line_number,
+                        line: line.to_owned(),
+                    });
+                }
+                description.media.push(MediaSection {
+                    lines: vec![line.to_owned()],
+                });
+            } else if let Some(section) = description.media.last_mut() {
+                section.lines.push(line.to_owned());
+            } else {
+                description.session.push(line.to_owned());
+            }
+        }
+        Ok(description)
+    }
+
+    pub fn video_sections_mut(&mut self) -> impl Iterator<Item = &mut MediaSection> {
+        self.media
+            .iter_mut()
+            .filter(|section| section.kind() == "video")
+    }
+
+    /// Every line in document order, session lines first.
+    pub fn lines_mut(&mut self) -> impl Iterator<Item = &mut String> {
+        self.session.iter_mut().chain(
+            self.media
+                .iter_mut()
+                .flat_map(|section| section.lines.iter_mut()),
+        )
+    }
+}
+
+impl fmt::Display for SessionDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self
+            .session
+            .iter()
+            .chain(self.media.iter().flat_map(|section| section.lines.iter()));
+        if let Some(first) = lines.next() {
+            f.write_str(first)?;
+        }
+        for line in lines {
+            f.write_str(self.line_ending)?;
+            f.write_str(line)?;
+        }
+        if self.trailing_line_ending {
+            f.write_str(self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+fn attribute_values<'a>(lines: &'a [String], name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    lines.iter().filter_map(move |line| {
+        let rest = line.strip_prefix("a=")?.strip_prefix(name)?;
+        match rest.strip_prefix(':') {
+            Some(value) => Some(value),
+            None => rest.is_empty().then_some(""),
+        }
+    })
+}
+
+impl MediaSection {
+    fn media_fields(&self) -> impl Iterator<Item = &str> {
+        self.lines[0]["m=".len()..].split(' ')
+    }
+
+    pub fn kind(&self) -> &str {
+        self.media_fields().next().unwrap_or_default()
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.media_fields().nth(1) == Some("0")
+    }
+
+    pub fn formats(&self) -> Vec<&str> {
+        self.media_fields().skip(3).collect()
+    }
+
+    pub fn set_formats<S: AsRef<str>>(&mut self, formats: &[S]) {
+        let header = self.media_fields().take(3).collect::<Vec<_>>().join(" ");
+        let formats = formats
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.lines[0] = format!("m={header} {formats}");
+    }
+
+    /// Values of `a=<name>:<value>` lines, or `""` for flag attributes.
+    pub fn attributes<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        attribute_values(&self.lines[1..], name)
+    }
+
+    pub fn attribute<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        self.attributes(name).next()
+    }
+
+    /// Inserts attribute lines before the section's first existing attribute,
+    /// keeping `c=`/`b=` lines ahead of them as RFC 8866 orders them.
+    pub fn insert_attributes(&mut self, lines: impl IntoIterator<Item = String>) {
+        let index = self
+            .lines
+            .iter()
+            .position(|line| line.starts_with("a="))
+            .unwrap_or(self.lines.len());
+        self.lines.splice(index..index, lines);
+    }
+
+    /// Removes `a=<name>:<pt> ...` lines whose payload type fails `keep`.
+    pub fn retain_payload_attributes(&mut self, names: &[&str], keep: impl Fn(&str) -> bool) {
+        let media_line = self.lines.remove(0);
+        self.lines.retain(|line| {
+            let Some(rest) = line.strip_prefix("a=") else {
+                return true;
+            };
+            let Some((name, value)) = rest.split_once(':') else {
+                return true;
+            };
+            if !names.contains(&name) {
+                return true;
+            }
+            let pt = value.split_whitespace().next().unwrap_or_default();
+            pt.is_empty() || keep(pt)
+        });
+        self.lines.insert(0, media_line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::VideoCodec;
+    use crate::sdp::{
+        duplicate_session_webrtc_attributes_to_media, fix_server_ip, prefer_codec,
+        sanitize_ice_pwd_for_gstreamer, PreferCodecOptions,
+    };
+
+    /// Hand-written offers shaped like the ones GFN sends: CRLF with an
+    /// ICE-lite session and two data channels, and a bare LF variant with
+    /// session-level credentials. No real captures are checked in.
+    const SAMPLE_OFFERS: &[&str] = &[
+        concat!(
+            "v=0\r\n",
+            "o=- 4373647202393833435 2 IN IP4 127.0.0.1\r\n",
+            "s=-\r\n",
+            "t=0 0\r\n",
+            "a=group:BUNDLE 0 1 2 3\r\n",
+            "a=ice-options:trickle\r\n",
+            "a=ice-lite\r\n",
+            "m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n",
+            "c=IN IP4 0.0.0.0\r\n",
+            "a=mid:0\r\n",
+            "a=ice-ufrag:2efecf37\r\n",
+            "a=ice-pwd:26b335b8-99a8-4ffa-b9aa-f38ddad1e2b4\r\n",
+            "a=fingerprint:sha-256 94:6C:60:66:35:B9:F6:B4:BC:46:60:EF:81:AC:AB:87:A9:45:4A:09:92:E4:3E:16:28:7E:BD:6D:8C:1A:7D:6B\r\n",
+            "a=setup:actpass\r\n",
+            "a=rtcp-mux\r\n",
+            "a=rtpmap:111 OPUS/48000/2\r\n",
+            "a=fmtp:111 minptime=10;useinbandfec=1\r\n",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96 97 98 99 100\r\n",
+            "c=IN IP4 0.0.0.0\r\n",
+            "a=mid:1\r\n",
+            "a=ice-ufrag:2efecf37\r\n",
+            "a=ice-pwd:26b335b8-99a8-4ffa-b9aa-f38ddad1e2b4\r\n",
+            "a=setup:actpass\r\n",
+            "a=rtcp-mux\r\n",
+            "a=rtpmap:96 H264/90000\r\n",
+            "a=fmtp:96 profile-level-id=42e01f;packetization-mode=1\r\n",
+            "a=rtpmap:97 rtx/90000\r\n",
+            "a=fmtp:97 apt=96\r\n",
+            "a=rtpmap:98 H265/90000\r\n",
+            "a=fmtp:98 profile-id=1;level-id=186\r\n",
+            "a=rtcp-fb:98 nack pli\r\n",
+            "a=rtpmap:99 rtx/90000\r\n",
+            "a=fmtp:99 apt=98\r\n",
+            "a=rtpmap:100 flexfec-03/90000\r\n",
+            "a=ssrc:2132443110 cname:video\r\n",
+            "m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n",
+            "c=IN IP4 0.0.0.0\r\n",
+            "a=mid:2\r\n",
+            "a=sctp-port:5000\r\n",
+            "m=application 9 UDP/DTLS/SCTP webrtc-datachannel\r\n",
+            "c=IN IP4 0.0.0.0\r\n",
+            "a=mid:3\r\n",
+            "a=sctp-port:5000\r\n",
+        ),
+        concat!(
+            "v=0\n",
+            "o=- 0 0 IN IP4 0.0.0.0\n",
+            "s=-\n",
+            "c=IN IP4 0.0.0.0\n",
+            "t=0 0\n",
+            "a=ice-ufrag:user\n",
+            "a=ice-pwd:48ca4c4b-199a-454c-b58a-3d14739335a3\n",
+            "a=fingerprint:sha-256 AA:BB\n",
+            "a=setup:actpass\n",
+            "m=audio 9 UDP/TLS/RTP/SAVPF 111\n",
+            "a=mid:0\n",
+            "a=rtpmap:111 OPUS/48000/2\n",
+            "a=candidate:1 1 udp 2130706431 0.0.0.0 49003 typ host\n",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96 97\n",
+            "a=mid:1\n",
+            "a=rtpmap:96 AV1/90000\n",
+            "a=rtpmap:97 rtx/90000\n",
+            "a=fmtp:97 apt=96\n",
+            "a=framerate:120\n",
+            "m=application 0 UDP/DTLS/SCTP webrtc-datachannel\n",
+            "a=mid:2",
+        ),
+    ];
+
+    #[test]
+    fn round_trips_sample_offers() {
+        for offer in SAMPLE_OFFERS {
+            let description = SessionDescription::parse(offer).expect("sample offer parses");
+            assert_eq!(description.to_string(), *offer);
+        }
+    }
+
+    /// Deterministic xorshift so generated cases are reproducible without a
+    /// property-testing crate.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[(self.next() % items.len() as u64) as usize]
+        }
+    }
+
+    /// The error `parse` should report for `lines`, judged line by line.
+    fn first_error(lines: &[&str]) -> Option<SdpParseError> {
+        if !lines[0].starts_with("v=") {
+            return Some(SdpParseError::MissingVersion);
+        }
+        lines.iter().enumerate().skip(1).find_map(|(index, line)| {
+            let line_number = index + 1;
+            let line_owned = (*line).to_owned();
+            if !is_sdp_line(line) {
+                Some(SdpParseError::MalformedLine {
+                    line_number,
+                    line: line_owned,
+                })
+            } else if line.starts_with("m=") && !is_media_line(line) {
+                Some(SdpParseError::MalformedMediaLine {
+                    line_number,
+                    line: line_owned,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    #[test]
+    fn parses_generated_sdp_or_reports_the_first_bad_line() {
+        const LINES: &[&str] = &[
+            "o=- 0 0 IN IP4 0.0.0.0",
+            "s=-",
+            "c=IN IP4 0.0.0.0",
+            "a=ice-lite",
+            "a=rtpmap:96 H264/90000",
+            "a=fmtp:97 apt=96",
+            "m=video 9 UDP/TLS/RTP/SAVPF 96 97",
+            "m=audio 9 UDP/TLS/RTP/SAVPF 111",
+            "m=application 0 UDP/DTLS/SCTP webrtc-datachannel",
+            "m=video nine UDP/TLS/RTP/SAVPF 96",
+            "garbage",
+            "A=upper",
+            "x-vendor: value",
+        ];
+        const ENDINGS: &[&str] = &["\n", "\r\n", "\r"];
+
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        let mut parsed = 0;
+        for _ in 0..500 {
+            let ending = rng.pick(&["\n", "\r\n"]);
+            let line_count = 1 + rng.next() % 12;
+            let mut lines = vec![if rng.next().is_multiple_of(8) {
+                "s=-"
+            } else {
+                "v=0"
+            }];
+            lines.extend((1..line_count).map(|_| rng.pick(LINES)));
+            let mut sdp = lines.join(ending);
+            if rng.next().is_multiple_of(2) {
+                sdp.push_str(ending);
+            }
+
+            let expected = first_error(&lines);
+            match SessionDescription::parse(&sdp) {
+                Ok(description) => {
+                    assert_eq!(expected, None, "{sdp:?}");
+                    assert_eq!(description.to_string(), sdp);
+                    assert_eq!(
+                        description.media.len(),
+                        lines.iter().filter(|line| line.starts_with("m=")).count()
+                    );
+                    parsed += 1;
+                }
+                Err(error) => assert_eq!(Some(error), expected, "{sdp:?}"),
+            }
+
+            // Mixed line endings still number lines one break at a time.
+            let mut mixed = String::new();
+            for line in &lines {
+                mixed.push_str(line);
+                mixed.push_str(rng.pick(ENDINGS));
+            }
+            assert_eq!(
+                SessionDescription::parse(&mixed).err(),
+                expected,
+                "{mixed:?}"
+            );
+        }
+        assert!(parsed > 0, "no generated SDP was well formed");
+    }
+
+    fn apply_offer_transforms(description: &mut SessionDescription, codec: VideoCodec) {
+        fix_server_ip(description, "80-250-97-40.cloudmatchbeta.nvidiagrid.net");
+        duplicate_session_webrtc_attributes_to_media(description);
+        prefer_codec(
+            description,
+            codec,
+            PreferCodecOptions {
+                prefer_hevc_profile_id: Some(1),
+                decoder_support: None,
+            },
+        );
+        sanitize_ice_pwd_for_gstreamer(description);
+    }
+
+    #[test]
+    fn offer_transforms_are_idempotent_and_keep_sections_intact() {
+        for offer in SAMPLE_OFFERS {
+            for codec in [VideoCodec::H264, VideoCodec::H265, VideoCodec::AV1] {
+                let mut once = SessionDescription::parse(offer).expect("sample offer parses");
+                apply_offer_transforms(&mut once, codec);
+                let serialized = once.to_string();
+
+                let mut twice = SessionDescription::parse(&serialized).expect("output reparses");
+                assert_eq!(twice, once);
+                apply_offer_transforms(&mut twice, codec);
+                assert_eq!(twice.to_string(), serialized);
+
+                let original = SessionDescription::parse(offer).expect("sample offer parses");
+                assert_eq!(once.media.len(), original.media.len());
+                for (section, original) in once.media.iter().zip(&original.media) {
+                    assert_eq!(section.kind(), original.kind());
+                    assert_eq!(section.attribute("mid"), original.attribute("mid"));
+                    assert!(section
+                        .formats()
+                        .iter()
+                        .all(|format| original.formats().contains(format)));
+                }
+                assert!(!serialized.contains("c=IN IP4 0.0.0.0"));
+                assert!(!serialized.contains(" 0.0.0.0 49003"));
+                assert!(!once
+                    .session
+                    .iter()
+                    .any(|line| line.starts_with("a=ice-pwd:")));
+            }
+        }
+    }
+
+    #[test]
+    fn exposes_media_sections_and_attributes() {
+        let mut description = SessionDescription::parse(SAMPLE_OFFERS[0]).expect("parses");
+
+        assert!(description.session.iter().any(|line| line == "a=ice-lite"));
+        let video = description.video_sections_mut().next().expect("video");
+        assert_eq!(video.formats(), vec!["96", "97", "98", "99", "100"]);
+        assert_eq!(video.attribute("mid"), Some("1"));
+        assert_eq!(video.attributes("rtpmap").count(), 5);
+        assert_eq!(video.attribute("rtcp-mux"), Some(""));
+        assert_eq!(video.attribute("rtcp"), None);
+
+        video.set_formats(&["98", "99"]);
+        video.retain_payload_attributes(&["rtpmap", "fmtp", "rtcp-fb"], |pt| {
+            pt == "98" || pt == "99"
+        });
+        assert_eq!(video.lines[0], "m=video 9 UDP/TLS/RTP/SAVPF 98 99");
+        assert_eq!(video.attributes("rtpmap").count(), 2);
+        assert_eq!(video.attribute("ssrc"), Some("2132443110 cname:video"));
+        assert!(description.media[2].kind() == "application");
+        assert!(SessionDescription::parse(SAMPLE_OFFERS[1])
+            .expect("parses")
+            .media[2]
+            .is_rejected());
+    }
+
+    #[test]
+    fn rejects_malformed_sdp() {
+        assert_eq!(SessionDescription::parse(""), Err(SdpParseError::Empty));
+        assert_eq!(
+            SessionDescription::parse("\r\n\n"),
+            Err(SdpParseError::Empty)
+        );
+        assert_eq!(
+            SessionDescription::parse("o=- 0 0 IN IP4 0.0.0.0\nv=0"),
+            Err(SdpParseError::MissingVersion)
+        );
+        // The blank line still counts, and CRLF is one break.
+        assert_eq!(
+            SessionDescription::parse("v=0\r\ns=-\r\n\r\ngarbage\r\n"),
+            Err(SdpParseError::MalformedLine {
+                line_number: 4,
+                line: "garbage".to_owned()
+            })
+        );
+        assert_eq!(
+            SessionDescription::parse("v=0\nm=video nine UDP/TLS/RTP/SAVPF 96\n"),
+            Err(SdpParseError::MalformedMediaLine {
+                line_number: 2,
+                line: "m=video nine UDP/TLS/RTP/SAVPF 96".to_owned()
+            })
+        );
+        assert!(SessionDescription::parse("v=0\nm=video 9 UDP/TLS/RTP/SAVPF\n").is_err());
+    }
+}
@@ -0,0 +1,946 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+//! SEI messages carried in H.264 and H.265 access units.
+//!
+//! The HDR messages (mastering display colour volume, content light level)
+//! and the encoder's clock timestamps are read here. H.264 carries the
+//! clock timestamps in `pic_timing`, whose layout depends on the active SPS.
+//! H.265 moved them to the self-describing `time_code` message.
+
+const SEI_PIC_TIMING: u32 = 1;
+const SEI_TIME_CODE: u32 = 136;
+const SEI_MASTERING_DISPLAY_COLOUR_VOLUME: u32 = 137;
+const SEI_CONTENT_LIGHT_LEVEL_INFO: u32 = 144;
+
+const H264_NAL_SEI: u8 = 6;
+const H264_NAL_SPS: u8 = 7;
+const H265_NAL_PREFIX_SEI: u8 = 39;
+
+/// H.264 profiles whose SPS carries chroma format, bit depth and scaling lists.
+const H264_HIGH_PROFILES: [u32; 13] =
+    [100, 110, 122, 244, 44, 83, 86, 118, 128, 138, 139, 134, 135];
+/// `time_offset_length` when the SPS signals no HRD (H.264 E.2.2).
+const H264_DEFAULT_TIME_OFFSET_LENGTH: u8 = 24;
+
+const MILLIS_PER_DAY: u64 = 86_400_000;
+/// Wider gaps between the encoder's clock and ours mean the time code does
+/// not count wall-clock time, or the clocks are not in sync.
+const MAX_ENCODER_LATENCY_MS: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SeiCodec {
+    H264,
+    H265,
+}
+
+impl SeiCodec {
+    pub(crate) fn from_encoding(encoding: &str) -> Option<Self> {
+        match encoding.to_ascii_uppercase().as_str() {
+            "H264" => Some(Self::H264),
+            "H265" | "HEVC" => Some(Self::H265),
+            _ => None,
+        }
+    }
+
+    fn is_vcl(self, nal: &[u8]) -> bool {
+        match (self, nal.first()) {
+            (Self::H264, Some(header)) => (1..=5).contains(&(header & 0x1f)),
+            (Self::H265, Some(header)) => (header >> 1) & 0x3f < 32,
+            (_, None) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NalFraming {
+    /// Start-code delimited (`stream-format=byte-stream`).
+    AnnexB,
+    /// Four-byte big-endian lengths (`avc`, `avc3`, `hvc1`, `hev1`), the
+    /// size h264parse and h265parse write.
+    LengthPrefixed,
+}
+
+impl NalFraming {
+    pub(crate) fn from_stream_format(stream_format: Option<&str>) -> Self {
+        match stream_format {
+            Some("avc" | "avc3" | "hvc1" | "hev1") => Self::LengthPrefixed,
+            _ => Self::AnnexB,
+        }
+    }
+}
+
+/// Chromaticities in 0.00002 units and luminance in 0.0001 cd/m², as carried
+/// in the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MasteringDisplay {
+    pub(crate) primaries: [(u16, u16); 3],
+    pub(crate) white_point: (u16, u16),
+    pub(crate) max_luminance: u32,
+    pub(crate) min_luminance: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ContentLightLevel {
+    pub(crate) max_cll: u16,
+    pub(crate) max_fall: u16,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ClockTimestamp {
+    pub(crate) hours: u8,
+    pub(crate) minutes: u8,
+    pub(crate) seconds: u8,
+    pub(crate) n_frames: u16,
+}
+
+impl ClockTimestamp {
+    /// Milliseconds since midnight, counting `n_frames` at `fps`.
+    pub(crate) fn millis_of_day(&self, fps: u32) -> u64 {
+        let seconds =
+            (u64::from(self.hours) * 60 + u64::from(self.minutes)) * 60 + u64::from(self.seconds);
+        seconds * 1000 + u64::from(self.n_frames) * 1000 / u64::from(fps.max(1))
+    }
+
+    /// `HH:MM:SS:FF`, the way time codes are usually written.
+    pub(crate) fn label(&self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}:{:02}",
+            self.hours, self.minutes, self.seconds, self.n_frames
+        )
+    }
+}
+
+/// How far the local UTC time of day is ahead of the encoder's time code, for
+/// encoders that stamp wall-clock time. `None` when the gap is implausible.
+pub(crate) fn encoder_latency_ms(encoder_ms_of_day: u64, local_ms_of_day: u64) -> Option<u64> {
+    let latency = (local_ms_of_day % MILLIS_PER_DAY + MILLIS_PER_DAY
+        - encoder_ms_of_day % MILLIS_PER_DAY)
+        % MILLIS_PER_DAY;
+    (latency <= MAX_ENCODER_LATENCY_MS).then_some(latency)
+}
+
+/// Messages found ahead of the first picture of an access unit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SeiMessages {
+    pub(crate) mastering_display: Option<MasteringDisplay>,
+    pub(crate) content_light_level: Option<ContentLightLevel>,
+    pub(crate) clock_timestamp: Option<ClockTimestamp>,
+}
+
+impl SeiMessages {
+    pub(crate) fn hdr_metadata_summary(&self) -> Option<String> {
+        hdr_metadata_summary(
+            self.mastering_display.as_ref(),
+            self.content_light_level.as_ref(),
+        )
+    }
+}
+
+pub(crate) fn hdr_metadata_summary(
+    mastering: Option<&MasteringDisplay>,
+    light_level: Option<&ContentLightLevel>,
+) -> Option<String> {
+    let mastering = mastering.map(|mastering| {
+        format!(
+            "mastering {:.0}/{:.4} nits",
+            f64::from(mastering.max_luminance) / 10_000.0,
+            f64::from(mastering.min_luminance) / 10_000.0
+        )
+    });
+    let light_level = light_level.map(|light_level| {
+        format!(
+            "MaxCLL {} nits, MaxFALL {} nits",
+            light_level.max_cll, light_level.max_fall
+        )
+    });
+    let parts = [mastering, light_level]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Parts of an H.264 SPS that decide how `pic_timing` is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct H264PicTimingLayout {
+    /// Bit lengths of `cpb_removal_delay` and `dpb_output_delay` when the SPS
+    /// signals an HRD.
+    hrd_delay_lengths: Option<(u8, u8)>,
+    pic_struct_present: bool,
+    time_offset_length: u8,
+}
+
+/// Reads SEI messages from one stream. It keeps the H.264 SPS timing layout
+/// and the last clock timestamp, because later timestamps may only carry the
+/// fields that changed.
+#[derive(Debug)]
+pub(crate) struct SeiParser {
+    codec: SeiCodec,
+    h264_timing: Option<H264PicTimingLayout>,
+    clock: ClockTimestamp,
+}
+
+impl SeiParser {
+    pub(crate) fn new(codec: SeiCodec) -> Self {
+        Self {
+            codec,
+            h264_timing: None,
+            clock: ClockTimestamp::default(),
+        }
+    }
+
+    /// Reads the parameter sets and prefix SEI in front of the first slice.
+    /// The slice data itself is never scanned.
+    pub(crate) fn parse_access_unit(&mut self, data: &[u8], framing: NalFraming) -> SeiMessages {
+        let mut messages = SeiMessages::default();
+        match framing {
+            NalFraming::AnnexB => {
+                let mut next = find_start_code(data, 0);
+                while let Some(start) = next {
+                    if self.codec.is_vcl(&data[start..]) {
+                        break;
+                    }
+                    next = find_start_code(data, start);
+                    let end = next.map_or(data.len(), |next| next - 3);
+                    let mut nal = &data[start..end];
+                    // A four-byte start code leaves its leading zero here.
+                    while let [rest @ .., 0] = nal {
+                        nal = rest;
+                    }
+                    self.parse_nal(nal, &mut messages);
+                }
+            }
+            NalFraming::LengthPrefixed => {
+                let mut pos = 0;
+                while let Some(length) = data.get(pos..pos + 4) {
+                    let length = u32::from_be_bytes([length[0], length[1], length[2], length[3]]);
+                    let Some(nal) = data.get(pos + 4..pos + 4 + length as usize) else {
+                        break;
+                    };
+                    if self.codec.is_vcl(nal) {
+                        break;
+                    }
+                    self.parse_nal(nal, &mut messages);
+                    pos += 4 + length as usize;
+                }
+            }
+        }
+        messages
+    }
+
+    fn parse_nal(&mut self, nal: &[u8], messages: &mut SeiMessages) {
+        match self.codec {
+            SeiCodec::H264 => match nal.first().map(|header| header & 0x1f) {
+                Some(H264_NAL_SEI) => self.parse_sei(&rbsp(&nal[1..]), messages),
+                Some(H264_NAL_SPS) => {
+                    if let Some(layout) = parse_h264_sps_timing(&rbsp(&nal[1..])) {
+                        self.h264_timing = Some(layout);
+                    }
+                }
+                _ => {}
+            },
+            SeiCodec::H265 => {
+                if nal.len() > 2 && (nal[0] >> 1) & 0x3f == H265_NAL_PREFIX_SEI {
+                    self.parse_sei(&rbsp(&nal[2..]), messages);
+                }
+            }
+        }
+    }
+
+    fn parse_sei(&mut self, rbsp: &[u8], messages: &mut SeiMessages) {
+        let mut pos = 0;
+        // What is left after the last message is the 0x80 stop byte.
+        while rbsp.len() > pos + 1 {
+            let (Some(payload_type), Some(payload_size)) =
+                (sei_varint(rbsp, &mut pos), sei_varint(rbsp, &mut pos))
+            else {
+                return;
+            };
+            let Some(payload) = rbsp.get(pos..pos + payload_size as usize) else {
+                return;
+            };
+            pos += payload_size as usize;
+            match payload_type {
+                SEI_MASTERING_DISPLAY_COLOUR_VOLUME => {
+                    messages.mastering_display =
+                        parse_mastering_display(payload).or(messages.mastering_display);
+                }
+                SEI_CONTENT_LIGHT_LEVEL_INFO => {
+                    messages.content_light_level =
+                        parse_content_light_level(payload).or(messages.content_light_level);
+                }
+                SEI_PIC_TIMING if self.codec == SeiCodec::H264 => {
+                    if let Some(layout) = self.h264_timing {
+                        let timestamp = self.parse_h264_pic_timing(payload, layout);
+                        messages.clock_timestamp = timestamp.or(messages.clock_timestamp);
+                    }
+                }
+                SEI_TIME_CODE if self.codec == SeiCodec::H265 => {
+                    messages.clock_timestamp = self
+                        .parse_h265_time_code(payload)
+                        .or(messages.clock_timestamp);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn parse_h264_pic_timing(
+        &mut self,
+        payload: &[u8],
+        layout: H264PicTimingLayout,
+    ) -> Option<ClockTimestamp> {
+        let mut bits = BitReader::new(payload);
+        if let Some((cpb_removal_delay, dpb_output_delay)) = layout.hrd_delay_lengths {
+            bits.skip(u32::from(cpb_removal_delay) + u32::from(dpb_output_delay))?;
+        }
+        if !layout.pic_struct_present {
+            return None;
+        }
+        let num_clock_ts = match bits.bits(4)? {
+            0..=2 => 1,
+            3 | 4 | 7 => 2,
+            5 | 6 | 8 => 3,
+            _ => return None,
+        };
+        let mut latest = None;
+        for _ in 0..num_clock_ts {
+            if bits.flag()? {
+                latest =
+                    Some(self.read_clock_timestamp(&mut bits, 8, layout.time_offset_length)?);
+            }
+        }
+        latest
+    }
+
+    fn parse_h265_time_code(&mut self, payload: &[u8]) -> Option<ClockTimestamp> {
+        let mut bits = BitReader::new(payload);
+        let num_clock_ts = bits.bits(2)?;
+        let mut latest = None;
+        for _ in 0..num_clock_ts {
+            if bits.flag()? {
+                latest = Some(self.read_clock_timestamp(&mut bits, 9, 0)?);
+            }
+        }
+        latest
+    }
+
+    /// The clock timestamp shared by H.264 `pic_timing` and H.265
+    /// `time_code`. H.264 takes `time_offset_length` from the SPS; H.265
+    /// signals it inline and ignores the argument.
+    fn read_clock_timestamp(
+        &mut self,
+        bits: &mut BitReader<'_>,
+        n_frames_bits: u8,
+        time_offset_length: u8,
+    ) -> Option<ClockTimestamp> {
+        // ct_type (H.264 only), nuit_field_based_flag and counting_type.
+        bits.skip(if self.codec == SeiCodec::H264 { 8 } else { 6 })?;
+        let full_timestamp = bits.flag()?;
+        // discontinuity_flag and cnt_dropped_flag.
+        bits.skip(2)?;
+        self.clock.n_frames = bits.bits(n_frames_bits)? as u16;
+        if full_timestamp {
+            self.clock.seconds = bits.bits(6)? as u8;
+            self.clock.minutes = bits.bits(6)? as u8;
+            self.clock.hours = bits.bits(5)? as u8;
+        } else if bits.flag()? {
+            // Fields left out repeat the previous timestamp.
+            self.clock.seconds = bits.bits(6)? as u8;
+            if bits.flag()? {
+                self.clock.minutes = bits.bits(6)? as u8;
+                if bits.flag()? {
+                    self.clock.hours = bits.bits(5)? as u8;
+                }
+            }
+        }
+        let time_offset_length = match self.codec {
+            SeiCodec::H264 => u32::from(time_offset_length),
+            SeiCodec::H265 => bits.bits(5)?,
+        };
+        bits.skip(time_offset_length)?;
+        Some(self.clock)
+    }
+}
+
+fn parse_mastering_display(payload: &[u8]) -> Option<MasteringDisplay> {
+    let payload = payload.get(..24)?;
+    let u16_at = |index: usize| u16::from_be_bytes([payload[index], payload[index + 1]]);
+    let u32_at = |index: usize| {
+        u32::from_be_bytes([
+            payload[index],
+            payload[index + 1],
+            payload[index + 2],
+            payload[index + 3],
+        ])
+    };
+    Some(MasteringDisplay {
+        primaries: [
+            (u16_at(0), u16_at(2)),
+            (u16_at(4), u16_at(6)),
+            (u16_at(8), u16_at(10)),
+        ],
+        white_point: (u16_at(12), u16_at(14)),
+        max_luminance: u32_at(16),
+        min_luminance: u32_at(20),
+    })
+}
+
+fn parse_content_light_level(payload: &[u8]) -> Option<ContentLightLevel> {
+    let payload = payload.get(..4)?;
+    Some(ContentLightLevel {
+        max_cll: u16::from_be_bytes([payload[0], payload[1]]),
+        max_fall: u16::from_be_bytes([payload[2], payload[3]]),
+    })
+}
+
+/// Walks an H.264 SPS up to the VUI fields `pic_timing` depends on.
+fn parse_h264_sps_timing(rbsp: &[u8]) -> Option<H264PicTimingLayout> {
+    let mut bits = BitReader::new(rbsp);
+    let profile_idc = bits.bits(8)?;
+    // constraint_set flags and level_idc.
+    bits.skip(16)?;
+    bits.ue()?;
+    if H264_HIGH_PROFILES.contains(&profile_idc) {
+        let chroma_format_idc = bits.ue()?;
+        if chroma_format_idc == 3 {
+            bits.skip(1)?;
+        }
+        // Luma and chroma bit depth, then qpprime_y_zero_transform_bypass_flag.
+        bits.ue()?;
+        bits.ue()?;
+        bits.skip(1)?;
+        if bits.flag()? {
+            let lists = if chroma_format_idc == 3 { 12 } else { 8 };
+            for index in 0..lists {
+                if bits.flag()? {
+                    skip_scaling_list(&mut bits, if index < 6 { 16 } else { 64 })?;
+                }
+            }
+        }
+    }
+    bits.ue()?;
+    match bits.ue()? {
+        0 => {
+            bits.ue()?;
+        }
+        1 => {
+            bits.skip(1)?;
+            bits.se()?;
+            bits.se()?;
+            for _ in 0..bits.ue()? {
+                bits.se()?;
+            }
+        }
+        _ => {}
+    }
+    // max_num_ref_frames, gaps_in_frame_num_value_allowed_flag and the size.
+    bits.ue()?;
+    bits.skip(1)?;
+    bits.ue()?;
+    bits.ue()?;
+    if !bits.flag()? {
+        bits.skip(1)?;
+    }
+    bits.skip(1)?;
+    if bits.flag()? {
+        for _ in 0..4 {
+            bits.ue()?;
+        }
+    }
+
+    let no_vui = H264PicTimingLayout {
+        hrd_delay_lengths: None,
+        pic_struct_present: false,
+        time_offset_length: H264_DEFAULT_TIME_OFFSET_LENGTH,
+    };
+    if !bits.flag()? {
+        return Some(no_vui);
+    }
+    if bits.flag()? && bits.bits(8)? == 255 {
+        bits.skip(32)?;
+    }
+    if bits.flag()? {
+        bits.skip(1)?;
+    }
+    if bits.flag()? {
+        bits.skip(4)?;
+        if bits.flag()? {
+            bits.skip(24)?;
+        }
+    }
+    if bits.flag()? {
+        bits.ue()?;
+        bits.ue()?;
+    }
+    if bits.flag()? {
+        bits.skip(65)?;
+    }
+    let nal_hrd = if bits.flag()? {
+        Some(read_hrd_parameters(&mut bits)?)
+    } else {
+        None
+    };
+    let vcl_hrd = if bits.flag()? {
+        Some(read_hrd_parameters(&mut bits)?)
+    } else {
+        None
+    };
+    let hrd = nal_hrd.or(vcl_hrd);
+    if hrd.is_some() {
+        bits.skip(1)?;
+    }
+    Some(H264PicTimingLayout {
+        hrd_delay_lengths: hrd.map(|(cpb, dpb, _)| (cpb, dpb)),
+        pic_struct_present: bits.flag()?,
+        time_offset_length: hrd.map_or(no_vui.time_offset_length, |(_, _, offset)| offset),
+    })
+}
+
+/// Returns the `cpb_removal_delay`, `dpb_output_delay` and `time_offset`
+/// lengths.
+fn read_hrd_parameters(bits: &mut BitReader<'_>) -> Option<(u8, u8, u8)> {
+    let cpb_count = bits.ue()? + 1;
+    if cpb_count > 32 {
+        return None;
+    }
+    bits.skip(8)?;
+    for _ in 0..cpb_count {
+        bits.ue()?;
+        bits.ue()?;
+        bits.skip(1)?;
+    }
+    bits.skip(5)?;
+    let cpb_removal_delay = bits.bits(5)? as u8 + 1;
+    let dpb_output_delay = bits.bits(5)? as u8 + 1;
+    let time_offset = bits.bits(5)? as u8;
+    Some((cpb_removal_delay, dpb_output_delay, time_offset))
+}
+
+fn skip_scaling_list(bits: &mut BitReader<'_>, size: usize) -> Option<()> {
+    let (mut last, mut next) = (8i32, 8i32);
+    for _ in 0..size {
+        if next != 0 {
+            next = (last + bits.se()? + 256) % 256;
+        }
+        if next != 0 {
+            last = next;
+        }
+    }
+    Some(())
+}
+
+/// SEI payload types and sizes are sums of bytes, with 0xff meaning more.
+pub(crate) fn sei_varint(rbsp: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut value = 0u32;
+    loop {
+        let byte = *rbsp.get(*pos)?;
+        *pos += 1;
+        value = value.checked_add(u32::from(byte))?;
+        if byte != 0xff {
+            return Some(value);
+        }
+    }
+}
+
+/// Index just past the next `00 00 01` at or after `from`.
+fn find_start_code(data: &[u8], from: usize) -> Option<usize> {
+    data.get(from..)?
+        .windows(3)
+        .position(|window| window == [0, 0, 1])
+        .map(|index| from + index + 3)
+}
+
+/// Drops the emulation prevention bytes the encoder inserted after `00 00`.
+pub(crate) fn rbsp(payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len());
+    let mut zeros = 0;
+    for &byte in payload {
+        if zeros >= 2 && byte == 3 {
+            zeros = 0;
+            continue;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        out.push(byte);
+    }
+    out
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bits(&mut self, count: u8) -> Option<u32> {
+        let mut value = 0u32;
+        for _ in 0..count {
+            let byte = *self.data.get(self.pos / 8)?;
+            value = (value << 1) | u32::from((byte >> (7 - self.pos % 8)) & 1);
+            self.pos += 1;
+        }
+        Some(value)
+    }
+
+    fn flag(&mut self) -> Option<bool> {
+        self.bits(1).map(|bit| bit == 1)
+    }
+
+    fn skip(&mut self, count: u32) -> Option<()> {
+        let pos = self.pos + count as usize;
+        (pos <= self.data.len() * 8).then(|| self.pos = pos)
+    }
+
+    fn ue(&mut self) -> Option<u32> {
+        let mut leading_zeros = 0;
+        while !self.flag()? {
+            leading_zeros += 1;
+            if leading_zeros > 31 {
+                return None;
+            }
+        }
+        Some((1u32 << leading_zeros) - 1 + self.bits(leading_zeros)?)
+    }
+
+    fn se(&mut self) -> Option<i32> {
+        let value = self.ue()?;
+        let magnitude = value.div_ceil(2) as i32;
+        Some(if value % 2 == 1 {
+            magnitude
+        } else {
+            -magnitude
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct BitWriter {
+        bytes: Vec<u8>,
+        bits: usize,
+    }
+
+    impl BitWriter {
+        fn bits(&mut self, value: u32, count: u8) -> &mut Self {
+            for shift in (0..count).rev() {
+                if self.bits.is_multiple_of(8) {
+                    self.bytes.push(0);
+                }
+                if (value >> shift) & 1 == 1 {
+                    *self.bytes.last_mut().unwrap() |= 0x80 >> (self.bits % 8);
+                }
+                self.bits += 1;
+            }
+            self
+        }
+
+        fn ue(&mut self, value: u32) -> &mut Self {
+            let coded = value + 1;
+            let length = 32 - coded.leading_zeros() as u8;
+            self.bits(0, length - 1).bits(coded, length)
+        }
+
+        fn finish(&mut self) -> Vec<u8> {
+            self.bits(1, 1);
+            std::mem::take(&mut self.bytes)
+        }
+    }
+
+    /// Adds the emulation prevention bytes an encoder would.
+    fn escape(rbsp: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut zeros = 0;
+        for &byte in rbsp {
+            if zeros >= 2 && byte <= 3 {
+                out.push(3);
+                zeros = 0;
+            }
+            zeros = if byte == 0 { zeros + 1 } else { 0 };
+            out.push(byte);
+        }
+        out
+    }
+
+    fn sei_nal(header: &[u8], messages: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut rbsp = Vec::new();
+        for (payload_type, payload) in messages {
+            rbsp.push(*payload_type);
+            rbsp.push(payload.len() as u8);
+            rbsp.extend_from_slice(payload);
+        }
+        rbsp.push(0x80);
+        [header, &escape(&rbsp)].concat()
+    }
+
+    fn annex_b(nals: &[Vec<u8>]) -> Vec<u8> {
+        nals.iter()
+            .flat_map(|nal| [&[0, 0, 0, 1][..], nal].concat())
+            .collect()
+    }
+
+    fn mastering_display_payload() -> Vec<u8> {
+        let mut payload = Vec::new();
+        for value in [13250u16, 34500, 7500, 3000, 34000, 16000, 15635, 16450] {
+            payload.extend_from_slice(&value.to_be_bytes());
+        }
+        payload.extend_from_slice(&10_000_000u32.to_be_bytes());
+        payload.extend_from_slice(&50u32.to_be_bytes());
+        payload
+    }
+
+    /// High profile SPS with a NAL HRD (23-bit delays, 24-bit time offset)
+    /// and pic_struct_present_flag set.
+    fn h264_sps() -> Vec<u8> {
+        let mut sps = BitWriter::default();
+        sps.bits(100, 8).bits(0, 8).bits(51, 8).ue(0);
+        // 4:2:0, 8-bit, no scaling matrix.
+        sps.ue(1).ue(0).ue(0).bits(0, 1).bits(0, 1);
+        // log2_max_frame_num, poc type 0, refs, gaps, 1920x1088, frame_mbs_only, 8x8 inference.
+        sps.ue(0)
+            .ue(0)
+            .ue(2)
+            .ue(1)
+            .bits(0, 1)
+            .ue(119)
+            .ue(67)
+            .bits(1, 1)
+            .bits(1, 1);
+        // Cropped to 1080 lines.
+        sps.bits(1, 1).ue(0).ue(0).ue(0).ue(4);
+        // VUI: no aspect ratio, overscan, signal type or chroma location.
+        sps.bits(1, 1).bits(0, 4);
+        // 60 fps timing info.
+        sps.bits(1, 1).bits(1, 32).bits(120, 32).bits(1, 1);
+        // NAL HRD with one CPB.
+        sps.bits(1, 1)
+            .ue(0)
+            .bits(4, 4)
+            .bits(6, 4)
+            .ue(9999)
+            .ue(9999)
+            .bits(0, 1);
+        sps.bits(23, 5).bits(22, 5).bits(22, 5).bits(24, 5);
+        // No VCL HRD, low_delay_hrd_flag, pic_struct_present_flag, no bitstream restriction.
+        sps.bits(0, 1).bits(0, 1).bits(1, 1).bits(0, 1);
+        [&[0x67][..], &escape(&sps.finish())].concat()
+    }
+
+    #[test]
+    fn reads_hdr_metadata_and_h264_clock_timestamps() {
+        let mut pic_timing = BitWriter::default();
+        pic_timing.bits(0, 23).bits(2, 23);
+        // pic_struct frame, one full clock timestamp: 14:03:27 frame 31.
+        pic_timing
+            .bits(0, 4)
+            .bits(1, 1)
+            .bits(0, 2)
+            .bits(0, 1)
+            .bits(0, 5);
+        pic_timing.bits(1, 1).bits(0, 1).bits(0, 1).bits(31, 8);
+        pic_timing.bits(27, 6).bits(3, 6).bits(14, 5).bits(0, 24);
+        let pic_timing = pic_timing.finish();
+
+        let access_unit = annex_b(&[
+            vec![0x09, 0xf0],
+            h264_sps(),
+            vec![0x68, 0xee, 0x3c, 0x80],
+            sei_nal(
+                &[0x06],
+                &[
+                    (137, mastering_display_payload()),
+                    (144, vec![0x03, 0xe8, 0x01, 0x90]),
+                    (1, pic_timing),
+                ],
+            ),
+            vec![0x65, 0x88, 0x84, 0x00, 0x00, 0x01, 0x00],
+        ]);
+
+        let mut parser = SeiParser::new(SeiCodec::H264);
+        let messages = parser.parse_access_unit(&access_unit, NalFraming::AnnexB);
+        assert_eq!(
+            messages.hdr_metadata_summary().as_deref(),
+            Some("mastering 1000/0.0050 nits, MaxCLL 1000 nits, MaxFALL 400 nits")
+        );
+        assert_eq!(
+            messages
+                .mastering_display
+                .map(|mastering| mastering.white_point),
+            Some((15635, 16450))
+        );
+        let timestamp = messages.clock_timestamp.expect("clock timestamp");
+        assert_eq!(timestamp.label(), "14:03:27:31");
+        assert_eq!(timestamp.millis_of_day(60), 50_607_516);
+    }
+
+    #[test]
+    fn ignores_h264_pic_timing_until_an_sps_arrived() {
+        let access_unit = annex_b(&[sei_nal(&[0x06], &[(1, vec![0x00, 0x80])])]);
+        let mut parser = SeiParser::new(SeiCodec::H264);
+        assert_eq!(
+            parser.parse_access_unit(&access_unit, NalFraming::AnnexB),
+            SeiMessages::default()
+        );
+    }
+
+    #[test]
+    fn reads_h265_time_codes_and_keeps_omitted_fields() {
+        let time_code = |full: bool| {
+            let mut payload = BitWriter::default();
+            payload.bits(1, 2).bits(1, 1).bits(0, 1).bits(0, 5);
+            if full {
+                // 23:59:58 frame 119.
+                payload.bits(1, 1).bits(0, 2).bits(119, 9);
+                payload.bits(58, 6).bits(59, 6).bits(23, 5);
+            } else {
+                // Only the seconds changed.
+                payload.bits(0, 1).bits(0, 2).bits(2, 9);
+                payload.bits(1, 1).bits(59, 6).bits(0, 1);
+            }
+            payload.bits(0, 5).finish()
+        };
+        let nal = |full| sei_nal(&[0x4e, 0x01], &[(136, time_code(full))]);
+        let slice = vec![0x26, 0x01, 0xaf, 0x00];
+
+        let mut parser = SeiParser::new(SeiCodec::H265);
+        let first =
+            parser.parse_access_unit(&annex_b(&[nal(true), slice.clone()]), NalFraming::AnnexB);
+        assert_eq!(
+            first
+                .clock_timestamp
+                .map(|timestamp| timestamp.label())
+                .as_deref(),
+            Some("23:59:58:119")
+        );
+
+        // hvc1 framing: four-byte lengths instead of start codes.
+        let second = [nal(false), slice]
+            .iter()
+            .flat_map(|nal| [&(nal.len() as u32).to_be_bytes()[..], nal].concat())
+            .collect::<Vec<_>>();
+        let second = parser.parse_access_unit(&second, NalFraming::LengthPrefixed);
+        assert_eq!(
+            second
+                .clock_timestamp
+                .map(|timestamp| timestamp.label())
+                .as_deref(),
+            Some("23:59:59:02")
+        );
+        assert_eq!(second.hdr_metadata_summary(), None);
+    }
+
+    #[test]
+    fn stops_at_the_first_slice() {
+        let access_unit = annex_b(&[
+            vec![0x65, 0x88, 0x84],
+            sei_nal(&[0x06], &[(144, vec![0x03, 0xe8, 0x01, 0x90])]),
+        ]);
+        let mut parser = SeiParser::new(SeiCodec::H264);
+        assert_eq!(
+            parser
+                .parse_access_unit(&access_unit, NalFraming::AnnexB)
+                .content_light_level,
+            None
+        );
+    }
+
+    #[test]
+    fn strips_emulation_prevention_and_skips_unknown_payload_types() {
+        // Zero primaries force emulation prevention bytes into the payload.
+        let mut mastering = vec![0; 16];
+        mastering.extend_from_slice(&10_000_000u32.to_be_bytes());
+        mastering.extend_from_slice(&1u32.to_be_bytes());
+        // payload_type 0xff 0x05 is 260, written as a two-byte varint.
+        let mut rbsp = vec![0xff, 0x05, 2, 0xaa, 0xbb];
+        rbsp.extend_from_slice(&[137, 24]);
+        rbsp.extend_from_slice(&mastering);
+        rbsp.push(0x80);
+        let nal = [&[0x06][..], &escape(&rbsp)].concat();
+        assert!(nal.windows(3).any(|window| window == [0, 0, 3]));
+
+        let mut parser = SeiParser::new(SeiCodec::H264);
+        let messages = parser.parse_access_unit(&annex_b(&[nal]), NalFraming::AnnexB);
+        assert_eq!(
+            messages.mastering_display,
+            Some(MasteringDisplay {
+                primaries: [(0, 0); 3],
+                white_point: (0, 0),
+                max_luminance: 10_000_000,
+                min_luminance: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_truncated_and_malformed_input() {
+        let mut parser = SeiParser::new(SeiCodec::H264);
+        // A payload size running past the end of the NAL.
+        let truncated = annex_b(&[[&[0x06, 144, 24][..], &[0x03, 0xe8]].concat()]);
+        assert_eq!(
+            parser.parse_access_unit(&truncated, NalFraming::AnnexB),
+            SeiMessages::default()
+        );
+        // A light level message too short for its fields.
+        let short = annex_b(&[sei_nal(&[0x06], &[(144, vec![0x03])])]);
+        assert_eq!(
+            parser.parse_access_unit(&short, NalFraming::AnnexB),
+            SeiMessages::default()
+        );
+        // A length prefix longer than the buffer, and a partial prefix.
+        let overrun = [&100u32.to_be_bytes()[..], &[0x06, 144, 4]].concat();
+        assert_eq!(
+            parser.parse_access_unit(&overrun, NalFraming::LengthPrefixed),
+            SeiMessages::default()
+        );
+        assert_eq!(
+            parser.parse_access_unit(&[0, 0], NalFraming::LengthPrefixed),
+            SeiMessages::default()
+        );
+        for data in [&[][..], &[0, 0, 1], &[0, 0, 0, 1, 0x67], &[0xff; 7]] {
+            assert_eq!(
+                parser.parse_access_unit(data, NalFraming::AnnexB),
+                SeiMessages::default()
+            );
+        }
+        // An SPS cut off halfway leaves pic_timing disabled.
+        let sps = h264_sps();
+        let cut = annex_b(&[sps[..sps.len() / 2].to_vec()]);
+        parser.parse_access_unit(&cut, NalFraming::AnnexB);
+        assert_eq!(parser.h264_timing, None);
+    }
+
+    #[test]
+    fn reports_encoder_latency_only_for_wall_clock_time_codes() {
+        assert_eq!(encoder_latency_ms(50_607_516, 50_607_600), Some(84));
+        // Across midnight.
+        assert_eq!(encoder_latency_ms(86_399_950, 30), Some(80));
+        // A time code counting from the start of the stream.
+        assert_eq!(encoder_latency_ms(4_000, 50_607_600), None);
+        assert_eq!(encoder_latency_ms(50_607_700, 50_607_600), None);
+    }
+
+    #[test]
+    fn maps_stream_formats_to_nal_framing() {
+        assert_eq!(
+            NalFraming::from_stream_format(Some("avc")),
+            NalFraming::LengthPrefixed
+        );
+        assert_eq!(
+            NalFraming::from_stream_format(Some("hev1")),
+            NalFraming::LengthPrefixed
+        );
+        assert_eq!(
+            NalFraming::from_stream_format(Some("byte-stream")),
+            NalFraming::AnnexB
+        );
+        assert_eq!(NalFraming::from_stream_format(None), NalFraming::AnnexB);
+        assert_eq!(SeiCodec::from_encoding("hevc"), Some(SeiCodec::H265));
+        assert_eq!(SeiCodec::from_encoding("AV1"), None);
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
 use crate::protocol::{NativeStreamerShortcutAction, NativeStreamerShortcutBindings};
 
 const MODIFIER_SHIFT: u16 = 0x01;
@@ -113,6 +115,21 @@ impl NativeShortcutMatcher {
             NativeStreamerShortcutAction::ToggleRecording,
             &bindings.toggle_recording,
         );
+        append_binding(
+            &mut parsed,
+            NativeStreamerShortcutAction::ToggleStreamMute,
+            &bindings.toggle_stream_mute,
+        );
+        append_binding(
+            &mut parsed,
+            NativeStreamerShortcutAction::VolumeUp,
+            &bindings.volume_up,
+        );
+        append_binding(
+            &mut parsed,
+            NativeStreamerShortcutAction::VolumeDown,
+            &bindings.volume_down,
+        );
         Self { bindings: parsed }
     }
 
@@ -128,7 +145,7 @@ impl NativeShortcutMatcher {
             .find(|binding| {
                 binding.keycode == keycode
                     && binding.modifiers == modifiers
-                    && binding.scancode.map_or(true, |expected| expected == scancode)
+                    && binding.scancode.is_none_or(|expected| expected == scancode)
             })
             .map(|binding| binding.action)
     }
@@ -148,7 +165,11 @@ fn parse_binding(action: NativeStreamerShortcutAction, raw: &str) -> Option<Shor
     let mut modifiers = 0u16;
     let mut key = None;
 
-    for token in raw.split('+').map(str::trim).filter(|token| !token.is_empty()) {
+    for token in raw
+        .split('+')
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+    {
         match token.to_ascii_uppercase().as_str() {
             "CTRL" | "CONTROL" => modifiers |= MODIFIER_CTRL,
             "ALT" | "OPTION" => modifiers |= MODIFIER_ALT,
@@ -285,6 +306,9 @@ mod tests {
             toggle_microphone: "Ctrl+Shift+M".to_owned(),
             screenshot: "F11".to_owned(),
             toggle_recording: "F12".to_owned(),
+            toggle_stream_mute: "Ctrl+Shift+End".to_owned(),
+            volume_up: "Ctrl+Shift+PageUp".to_owned(),
+            volume_down: "Ctrl+Shift+PageDown".to_owned(),
         }
     }
 
@@ -310,7 +334,10 @@ mod tests {
             matcher.match_keydown(u16::from(b'Q'), 0, MODIFIER_CTRL | MODIFIER_SHIFT),
             Some(NativeStreamerShortcutAction::StopStream)
         );
-        assert_eq!(matcher.match_keydown(u16::from(b'Q'), 0, MODIFIER_CTRL), None);
+        assert_eq!(
+            matcher.match_keydown(u16::from(b'Q'), 0, MODIFIER_CTRL),
+            None
+        );
         assert_eq!(
             matcher.match_keydown(
                 u16::from(b'Q'),
@@ -321,6 +348,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn matches_navigation_key_volume_shortcuts() {
+        let matcher = NativeShortcutMatcher::from_bindings(&bindings());
+
+        assert_eq!(
+            matcher.match_keydown(VK_PRIOR, 0, MODIFIER_CTRL | MODIFIER_SHIFT),
+            Some(NativeStreamerShortcutAction::VolumeUp)
+        );
+        assert_eq!(
+            matcher.match_keydown(VK_NEXT, 0, MODIFIER_CTRL | MODIFIER_SHIFT),
+            Some(NativeStreamerShortcutAction::VolumeDown)
+        );
+        assert_eq!(
+            matcher.match_keydown(VK_END, 0, MODIFIER_CTRL | MODIFIER_SHIFT),
+            Some(NativeStreamerShortcutAction::ToggleStreamMute)
+        );
+    }
+
     #[test]
     fn ignores_invalid_bindings() {
         let matcher = NativeShortcutMatcher::from_bindings(&NativeStreamerShortcutBindings {
@@ -0,0 +1,726 @@
+//! A deterministic stand-in for the GFN side of a native session.
+//!
+//! `FakeGfnServer` writes offers shaped like the sample ones in
+//! `sdp_model` (ice-lite, per-media ICE credentials, one video m-line that
+//! carries every offered codec with RTX and FlexFEC, two SCTP data channels),
+//! checks the answer and NVST SDP a client sends back, and produces RTP and
+//! input handshake bytes. Session setup, codec negotiation, SSRC switches and
+//! reconnects can then be tested end to end without credentials or a network.
+
+use serde_json::{json, Value};
+
+use crate::protocol::{parse_command, CommandEnvelope, VideoCodec};
+use crate::sdp::extract_negotiated_video_codec;
+use crate::sdp_model::SessionDescription;
+
+pub(crate) const SERVER_HOST: &str = "80-250-97-40.cloudmatchbeta.nvidiagrid.net";
+pub(crate) const SERVER_IP: &str = "80.250.97.40";
+const SERVER_FINGERPRINT: &str = "sha-256 94:6C:60:66:35:B9:F6:B4:BC:46:60:EF:81:AC:AB:87:A9:45:4A:09:92:E4:3E:16:28:7E:BD:6D:8C:1A:7D:6B";
+const CLIENT_FINGERPRINT: &str = "sha-256 0F:1E:2D:3C:4B:5A:69:78:87:96:A5:B4:C3:D2:E1:F0:0F:1E:2D:3C:4B:5A:69:78:87:96:A5:B4:C3:D2:E1:F0";
+pub(crate) const FLEXFEC_PAYLOAD_TYPE: u8 = 120;
+
+/// One video codec in the offer; its RTX stream uses the next payload type.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OfferedCodec {
+    pub(crate) codec: VideoCodec,
+    pub(crate) payload_type: u8,
+    pub(crate) fmtp: &'static str,
+}
+
+impl OfferedCodec {
+    pub(crate) fn rtx_payload_type(self) -> u8 {
+        self.payload_type + 1
+    }
+}
+
+pub(crate) const H264_HIGH: OfferedCodec = OfferedCodec {
+    codec: VideoCodec::H264,
+    payload_type: 96,
+    fmtp: "profile-level-id=64001f;packetization-mode=1",
+};
+pub(crate) const H265_MAIN10: OfferedCodec = OfferedCodec {
+    codec: VideoCodec::H265,
+    payload_type: 98,
+    fmtp: "profile-id=2;level-id=186",
+};
+pub(crate) const AV1_MAIN: OfferedCodec = OfferedCodec {
+    codec: VideoCodec::AV1,
+    payload_type: 100,
+    fmtp: "profile=0",
+};
+
+/// What the server agreed to after reading the client's answer and NVST SDP.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NegotiatedSession {
+    pub(crate) codec: VideoCodec,
+    pub(crate) payload_type: u8,
+    pub(crate) viewport: (u32, u32),
+    pub(crate) max_fps: u32,
+    pub(crate) max_bitrate_kbps: u32,
+    pub(crate) bit_depth: u8,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FakeGfnServer {
+    codecs: Vec<OfferedCodec>,
+    /// Simulcast `rid`s with their max height; empty for a single video stream.
+    layers: Vec<(&'static str, u32)>,
+    /// Bumped on every reconnect: new session, ICE credentials and SSRCs.
+    generation: u32,
+    /// Bumped on every mid-stream resolution switch, which moves video to a new SSRC.
+    ssrc_epoch: u32,
+}
+
+impl FakeGfnServer {
+    pub(crate) fn new(codecs: &[OfferedCodec]) -> Self {
+        Self {
+            codecs: codecs.to_vec(),
+            layers: Vec::new(),
+            generation: 1,
+            ssrc_epoch: 0,
+        }
+    }
+
+    pub(crate) fn with_simulcast(mut self, layers: &[(&'static str, u32)]) -> Self {
+        self.layers = layers.to_vec();
+        self
+    }
+
+    pub(crate) fn session_id(&self) -> String {
+        format!("fake-session-{}", self.generation)
+    }
+
+    pub(crate) fn ice_ufrag(&self) -> String {
+        format!("2efe{:04x}", self.generation)
+    }
+
+    pub(crate) fn ice_pwd(&self) -> String {
+        format!("26b335b8-99a8-4ffa-b9aa-{:012x}", self.generation)
+    }
+
+    pub(crate) fn video_ssrc(&self, layer: usize) -> u32 {
+        0x7f10_0000 | (self.generation << 12) | (self.ssrc_epoch << 4) | layer as u32
+    }
+
+    /// The session dropped and signaling came back with a fresh offer.
+    pub(crate) fn reconnect(&mut self) {
+        self.generation += 1;
+        self.ssrc_epoch = 0;
+    }
+
+    /// The encoder changed resolution mid-stream: same m-line, new provisional SSRC.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    pub(crate) fn switch_resolution(&mut self) {
+        self.ssrc_epoch += 1;
+    }
+
+    pub(crate) fn offer_sdp(&self) -> String {
+        let credentials = [
+            format!("a=ice-ufrag:{}", self.ice_ufrag()),
+            format!("a=ice-pwd:{}", self.ice_pwd()),
+        ];
+        let mut lines = vec![
+            "v=0".to_owned(),
+            format!(
+                "o=- {} 2 IN IP4 127.0.0.1",
+                4_373_647_202_u64 + u64::from(self.generation)
+            ),
+            "s=-".to_owned(),
+            "t=0 0".to_owned(),
+            "a=group:BUNDLE 0 1 2 3".to_owned(),
+            "a=ice-options:trickle".to_owned(),
+            "a=ice-lite".to_owned(),
+            "m=audio 9 UDP/TLS/RTP/SAVPF 111".to_owned(),
+            "c=IN IP4 0.0.0.0".to_owned(),
+            "a=mid:0".to_owned(),
+        ];
+        lines.extend(credentials.iter().cloned());
+        lines.extend([
+            format!("a=fingerprint:{SERVER_FINGERPRINT}"),
+            "a=setup:actpass".to_owned(),
+            "a=rtcp-mux".to_owned(),
+            "a=rtpmap:111 OPUS/48000/2".to_owned(),
+            "a=fmtp:111 minptime=10;useinbandfec=1".to_owned(),
+        ]);
+
+        let mut formats = Vec::new();
+        for codec in &self.codecs {
+            formats.push(codec.payload_type.to_string());
+            formats.push(codec.rtx_payload_type().to_string());
+        }
+        formats.push(FLEXFEC_PAYLOAD_TYPE.to_string());
+        lines.push(format!("m=video 9 UDP/TLS/RTP/SAVPF {}", formats.join(" ")));
+        lines.extend(["c=IN IP4 0.0.0.0".to_owned(), "a=mid:1".to_owned()]);
+        lines.extend(credentials.iter().cloned());
+        lines.extend(["a=setup:actpass".to_owned(), "a=rtcp-mux".to_owned()]);
+        for codec in &self.codecs {
+            let (pt, rtx) = (codec.payload_type, codec.rtx_payload_type());
+            lines.extend([
+                format!("a=rtpmap:{pt} {}/90000", codec.codec.as_str()),
+                format!("a=fmtp:{pt} {}", codec.fmtp),
+                format!("a=rtcp-fb:{pt} nack pli"),
+                format!("a=rtpmap:{rtx} rtx/90000"),
+                format!("a=fmtp:{rtx} apt={pt}"),
+            ]);
+        }
+        lines.push(format!("a=rtpmap:{FLEXFEC_PAYLOAD_TYPE} flexfec-03/90000"));
+        if self.layers.is_empty() {
+            lines.push(format!("a=ssrc:{} cname:video", self.video_ssrc(0)));
+        } else {
+            for (rid, max_height) in &self.layers {
+                lines.push(format!("a=rid:{rid} send max-height={max_height}"));
+            }
+            let rids = self.layers.iter().map(|(rid, _)| *rid).collect::<Vec<_>>();
+            lines.push(format!("a=simulcast:send {}", rids.join(";")));
+        }
+
+        for mid in [2, 3] {
+            lines.extend([
+                "m=application 9 UDP/DTLS/SCTP webrtc-datachannel".to_owned(),
+                "c=IN IP4 0.0.0.0".to_owned(),
+                format!("a=mid:{mid}"),
+                "a=sctp-port:5000".to_owned(),
+            ]);
+        }
+
+        let mut sdp = lines.join("\r\n");
+        sdp.push_str("\r\n");
+        sdp
+    }
+
+    /// A command as the Electron main process sends it, carrying this
+    /// session's context and, for `offer`, the current offer SDP.
+    pub(crate) fn command(&self, id: &str, command_type: &str, settings: Value) -> CommandEnvelope {
+        let mut command = json!({
+            "id": id,
+            "type": command_type,
+            "context": {
+                "session": {
+                    "sessionId": self.session_id(),
+                    "serverIp": SERVER_HOST,
+                    "mediaConnectionInfo": { "ip": SERVER_IP, "port": 49003 },
+                },
+                "settings": settings,
+            },
+        });
+        if command_type == "offer" {
+            command["sdp"] = Value::String(self.offer_sdp());
+        }
+        parse_command(command).expect("fake GFN command parses")
+    }
+
+    /// Checks the client's answer against the current offer the way the
+    /// server would before starting media, and reads back the NVST settings.
+    pub(crate) fn accept_answer(
+        &self,
+        answer_sdp: &str,
+        nvst_sdp: &str,
+    ) -> Result<NegotiatedSession, String> {
+        let answer = SessionDescription::parse(answer_sdp).map_err(|error| error.to_string())?;
+        let video = answer
+            .media
+            .iter()
+            .find(|section| section.kind() == "video")
+            .ok_or("answer has no video m-line")?;
+        let payload_type = video
+            .formats()
+            .first()
+            .and_then(|format| format.parse::<u8>().ok())
+            .ok_or("answer video m-line has no payload type")?;
+        let offered = self
+            .codecs
+            .iter()
+            .find(|codec| codec.payload_type == payload_type)
+            .ok_or_else(|| {
+                format!(
+                    "answer selected payload type {payload_type}, which this offer does not carry"
+                )
+            })?;
+        if extract_negotiated_video_codec(answer_sdp) != Some(offered.codec) {
+            return Err(format!(
+                "answer maps payload type {payload_type} to a different codec"
+            ));
+        }
+
+        let nvst = |key: &str| {
+            nvst_sdp
+                .lines()
+                .find_map(|line| {
+                    line.strip_prefix("a=")?
+                        .strip_prefix(key)?
+                        .strip_prefix(':')
+                })
+                .map(str::trim)
+                .ok_or_else(|| format!("NVST SDP is missing {key}"))
+        };
+        let number = |key: &str| -> Result<u32, String> {
+            nvst(key)?
+                .parse()
+                .map_err(|_| format!("NVST {key} is not a number"))
+        };
+        for key in [
+            "general.iceUserNameFragment",
+            "general.icePassword",
+            "general.dtlsFingerprint",
+        ] {
+            if nvst(key)?.is_empty() {
+                return Err(format!("NVST SDP has an empty {key}"));
+            }
+        }
+        if nvst("general.iceUserNameFragment")? == self.ice_ufrag() {
+            return Err(
+                "NVST SDP carries the server's ICE credentials instead of the client's".to_owned(),
+            );
+        }
+
+        Ok(NegotiatedSession {
+            codec: offered.codec,
+            payload_type,
+            viewport: (
+                number("video.clientViewportWd")?,
+                number("video.clientViewportHt")?,
+            ),
+            max_fps: number("video.maxFPS")?,
+            max_bitrate_kbps: number("vqos.bw.maximumBitrateKbps")?,
+            bit_depth: number("video.bitDepth")? as u8,
+        })
+    }
+
+    /// An RTP packet for `layer` on the current SSRC.
+    #[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+    pub(crate) fn rtp_packet(&self, layer: usize, payload_type: u8, sequence: u16) -> Vec<u8> {
+        let mut packet = vec![0x80, payload_type & 0x7f];
+        packet.extend_from_slice(&sequence.to_be_bytes());
+        packet.extend_from_slice(&(u32::from(sequence) * 1500).to_be_bytes());
+        packet.extend_from_slice(&self.video_ssrc(layer).to_be_bytes());
+        packet.extend_from_slice(&[0; 4]);
+        packet
+    }
+
+    /// The first message on the input channel: the handshake word then the version.
+    pub(crate) fn input_handshake(version: u16) -> Vec<u8> {
+        let mut bytes = vec![0x0e, 0x02];
+        bytes.extend_from_slice(&version.to_le_bytes());
+        bytes
+    }
+}
+
+/// The answer a WebRTC stack returns for a prepared offer: the first
+/// remaining video payload type and its RTX, under the client's own credentials.
+pub(crate) fn client_answer(prepared_offer_sdp: &str, ufrag: &str) -> String {
+    let offer = SessionDescription::parse(prepared_offer_sdp).expect("prepared offer parses");
+    let video = offer
+        .media
+        .iter()
+        .find(|section| section.kind() == "video")
+        .expect("offer has video");
+    let payload_type = video.formats()[0].to_owned();
+    let rtpmap = video
+        .attributes("rtpmap")
+        .find(|value| value.split_whitespace().next() == Some(payload_type.as_str()))
+        .expect("chosen payload type has an rtpmap")
+        .to_owned();
+    let rtx = video.attributes("fmtp").find_map(|value| {
+        let (pt, params) = value.split_once(' ')?;
+        (params == format!("apt={payload_type}")).then(|| pt.to_owned())
+    });
+
+    let mut formats = vec![payload_type.clone()];
+    formats.extend(rtx.clone());
+    let mut lines = vec![
+        "v=0".to_owned(),
+        "o=- 1 2 IN IP4 127.0.0.1".to_owned(),
+        "s=-".to_owned(),
+        "t=0 0".to_owned(),
+        "a=group:BUNDLE 0 1 2 3".to_owned(),
+        "m=audio 9 UDP/TLS/RTP/SAVPF 111".to_owned(),
+        "a=mid:0".to_owned(),
+        format!("a=ice-ufrag:{ufrag}"),
+        "a=ice-pwd:client-password-0123456789ab".to_owned(),
+        format!("a=fingerprint:{CLIENT_FINGERPRINT}"),
+        "a=setup:active".to_owned(),
+        "a=recvonly".to_owned(),
+        "a=rtpmap:111 OPUS/48000/2".to_owned(),
+        "a=fmtp:111 minptime=10;useinbandfec=1".to_owned(),
+        format!("m=video 9 UDP/TLS/RTP/SAVPF {}", formats.join(" ")),
+        "a=mid:1".to_owned(),
+        "a=recvonly".to_owned(),
+        format!("a=rtpmap:{rtpmap}"),
+    ];
+    if let Some(rtx) = rtx {
+        lines.extend([
+            format!("a=rtpmap:{rtx} rtx/90000"),
+            format!("a=fmtp:{rtx} apt={payload_type}"),
+        ]);
+    }
+    for mid in [2, 3] {
+        lines.extend([
+            "m=application 9 UDP/DTLS/SCTP webrtc-datachannel".to_owned(),
+            format!("a=mid:{mid}"),
+            "a=sctp-port:5000".to_owned(),
+        ]);
+    }
+    lines.join("\r\n") + "\r\n"
+}
+
+/// Payload type, sequence number and SSRC of an RTP packet.
+#[cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+pub(crate) fn rtp_header(packet: &[u8]) -> Option<(u8, u16, u32)> {
+    if packet.len() < 12 || packet[0] >> 6 != 2 {
+        return None;
+    }
+    Some((
+        packet[1] & 0x7f,
+        u16::from_be_bytes([packet[2], packet[3]]),
+        u32::from_be_bytes([packet[8], packet[9], packet[10], packet[11]]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{prepare_native_offer, NativeStreamerBackend, StubBackend};
+    use crate::protocol::{Event, Response};
+    use crate::sdp::{
+        build_nvst_sdp_for_answer, munge_answer_sdp, DecoderProfileSupport, DecoderProfiles,
+    };
+    use crate::webrtc_protocol::negotiate_input_protocol_version;
+
+    fn settings(codec: &str, color_quality: &str) -> Value {
+        json!({
+            "resolution": "2560x1440",
+            "fps": 120,
+            "maxBitrateMbps": 75,
+            "codec": codec,
+            "colorQuality": color_quality,
+        })
+    }
+
+    fn negotiate(
+        server: &FakeGfnServer,
+        settings: Value,
+        decoder_profiles: &DecoderProfiles,
+    ) -> Result<NegotiatedSession, String> {
+        let command = server.command("offer-1", "offer", settings);
+        let context = command.context.expect("offer carries context");
+        let prepared = prepare_native_offer(
+            &context,
+            command.sdp.as_deref().unwrap_or_default(),
+            decoder_profiles,
+        )
+        .map_err(|error| format!("{error:?}"))?;
+        let answer = munge_answer_sdp(
+            &client_answer(&prepared.fixed_offer_sdp, "client1"),
+            prepared.nvst_params.max_bitrate_kbps,
+        );
+        let nvst = build_nvst_sdp_for_answer(&prepared.nvst_params, &answer)?;
+        server.accept_answer(&answer, &nvst)
+    }
+
+    #[test]
+    fn sets_up_a_session_through_the_command_protocol() {
+        let server = FakeGfnServer::new(&[H264_HIGH, H265_MAIN10])
+            .with_simulcast(&[("h", 1440), ("l", 720)]);
+        let mut backend = StubBackend::default();
+
+        let started =
+            backend.start(server.command("start-1", "start", settings("H265", "10bit_420")));
+        assert!(matches!(started.response, Some(Response::Ok { ref id }) if id == "start-1"));
+        assert!(started.events.iter().any(|event| matches!(
+            event,
+            Event::Status { status: "ready", message: Some(message) } if message.contains(&server.session_id())
+        )));
+
+        let offered =
+            backend.handle_offer(server.command("offer-1", "offer", settings("H265", "10bit_420")));
+        let layers = offered.events.iter().find_map(|event| match event {
+            Event::VideoLayers { layers, active, .. } => Some((
+                layers.iter().map(|layer| layer.height).collect::<Vec<_>>(),
+                active.clone(),
+            )),
+            _ => None,
+        });
+        assert_eq!(layers, Some((vec![Some(1440), Some(720)], "h".to_owned())));
+        assert!(
+            matches!(offered.response, Some(Response::Error { ref code, .. }) if code == "backend-unavailable")
+        );
+
+        let negotiated = negotiate(
+            &server,
+            settings("H265", "10bit_420"),
+            &DecoderProfiles::default(),
+        )
+        .expect("server accepts the answer");
+        assert_eq!(
+            negotiated,
+            NegotiatedSession {
+                codec: VideoCodec::H265,
+                payload_type: H265_MAIN10.payload_type,
+                viewport: (2560, 1440),
+                max_fps: 120,
+                max_bitrate_kbps: 75_000,
+                bit_depth: 10,
+            }
+        );
+        assert_eq!(
+            negotiate_input_protocol_version(&FakeGfnServer::input_handshake(3)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn negotiates_the_codec_the_decoder_can_play() {
+        let server = FakeGfnServer::new(&[AV1_MAIN, H265_MAIN10, H264_HIGH]);
+
+        let av1 = negotiate(
+            &server,
+            settings("AV1", "8bit_420"),
+            &DecoderProfiles::default(),
+        )
+        .expect("av1");
+        assert_eq!(
+            (av1.codec, av1.payload_type),
+            (VideoCodec::AV1, AV1_MAIN.payload_type)
+        );
+
+        // A decoder limited to 8-bit HEVC cannot take the server's Main10 payload.
+        let main_only = DecoderProfiles {
+            h265: DecoderProfileSupport {
+                profiles: vec!["main".to_owned()],
+                tiers: Vec::new(),
+            },
+            ..DecoderProfiles::default()
+        };
+        let fallback =
+            negotiate(&server, settings("H265", "10bit_420"), &main_only).expect("fallback");
+        assert_eq!(fallback.codec, VideoCodec::H264);
+
+        // Requesting a codec the server does not offer still yields an answer the server accepts.
+        let h264_only = FakeGfnServer::new(&[H264_HIGH]);
+        let negotiated = negotiate(
+            &h264_only,
+            settings("AV1", "8bit_420"),
+            &DecoderProfiles::default(),
+        )
+        .expect("h264");
+        assert_eq!(negotiated.codec, VideoCodec::H264);
+    }
+
+    /// Feeds the fake server's RTP through `rtpbin`, the element `webrtcbin`
+    /// receives with, using payload caps from the accepted offer the way
+    /// `webrtcbin` maps them from the SDP.
+    #[cfg(feature = "gstreamer")]
+    #[test]
+    fn keeps_receiving_video_across_a_mid_stream_ssrc_switch() {
+        use crate::gstreamer_pipeline::init_gstreamer;
+        use crate::video_layers::parse_rtpbin_recv_pad_name;
+        use gst::prelude::*;
+        use gstreamer as gst;
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+        use std::time::{Duration, Instant};
+
+        let mut server = FakeGfnServer::new(&[H264_HIGH, H265_MAIN10]);
+        let command = server.command("offer-1", "offer", settings("H265", "8bit_420"));
+        let prepared = prepare_native_offer(
+            &command.context.expect("context"),
+            command.sdp.as_deref().unwrap_or_default(),
+            &DecoderProfiles::default(),
+        )
+        .expect("valid offer");
+        let accepted = SessionDescription::parse(&prepared.fixed_offer_sdp).expect("parses");
+        let video = accepted
+            .media
+            .iter()
+            .find(|section| section.kind() == "video")
+            .expect("video");
+        let payload_caps = video
+            .attributes("rtpmap")
+            .filter_map(|value| {
+                let (pt, encoding) = value.split_once(' ')?;
+                let (name, clock_rate) = encoding.split_once('/')?;
+                let clock_rate = clock_rate.split('/').next()?.parse::<i32>().ok()?;
+                let caps = gst::Caps::builder("application/x-rtp")
+                    .field("media", "video")
+                    .field("payload", pt.parse::<i32>().ok()?)
+                    .field("clock-rate", clock_rate)
+                    .field("encoding-name", name.to_ascii_uppercase())
+                    .build();
+                Some((pt.parse::<u32>().ok()?, caps))
+            })
+            .collect::<HashMap<_, _>>();
+
+        init_gstreamer().expect("gstreamer init");
+        let pipeline = gst::Pipeline::new();
+        let (Ok(source), Ok(rtpbin)) = (
+            gst::ElementFactory::make("appsrc")
+                .property("is-live", true)
+                .property("do-timestamp", true)
+                .property_from_str("format", "time")
+                .property(
+                    "caps",
+                    gst::Caps::builder("application/x-rtp")
+                        .field("media", "video")
+                        .field("clock-rate", 90_000i32)
+                        .build(),
+                )
+                .build(),
+            gst::ElementFactory::make("rtpbin")
+                .property("latency", 0u32)
+                .build(),
+        ) else {
+            eprintln!("skipping: appsrc or rtpbin is not available here");
+            return;
+        };
+        rtpbin.connect("request-pt-map", false, move |args| {
+            let payload_type = args.get(2)?.get::<u32>().ok()?;
+            payload_caps.get(&payload_type).map(|caps| caps.to_value())
+        });
+
+        let received = Arc::new(Mutex::new(Vec::<(String, (u8, u16, u32))>::new()));
+        let weak_pipeline = pipeline.downgrade();
+        let pad_received = received.clone();
+        rtpbin.connect_pad_added(move |_, pad| {
+            let Some(pipeline) = weak_pipeline.upgrade() else {
+                return;
+            };
+            if !pad.name().starts_with("recv_rtp_src_") {
+                return;
+            }
+            let received = pad_received.clone();
+            pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+                let header = info
+                    .buffer()
+                    .and_then(|buffer| buffer.map_readable().ok())
+                    .and_then(|map| rtp_header(&map));
+                if let (Some(header), Ok(mut received)) = (header, received.lock()) {
+                    received.push((pad.name().to_string(), header));
+                }
+                gst::PadProbeReturn::Ok
+            });
+            let sink = gst::ElementFactory::make("fakesink")
+                .property("sync", false)
+                .property("async", false)
+                .build()
+                .expect("fakesink");
+            pipeline.add(&sink).expect("add fakesink");
+            sink.sync_state_with_parent().expect("sync fakesink");
+            pad.link(&sink.static_pad("sink").expect("fakesink pad"))
+                .expect("link received pad");
+        });
+        pipeline.add_many([&source, &rtpbin]).expect("add elements");
+        source
+            .static_pad("src")
+            .expect("appsrc pad")
+            .link(
+                &rtpbin
+                    .request_pad_simple("recv_rtp_sink_0")
+                    .expect("rtpbin session pad"),
+            )
+            .expect("link appsrc to rtpbin");
+        pipeline
+            .set_state(gst::State::Playing)
+            .expect("pipeline plays");
+
+        let push = |packet: Vec<u8>| {
+            let flow = source.emit_by_name::<gst::FlowReturn>(
+                "push-buffer",
+                &[&gst::Buffer::from_slice(packet)],
+            );
+            assert_eq!(flow, gst::FlowReturn::Ok);
+        };
+        let wait_for = |count: usize| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while received.lock().map(|received| received.len()).unwrap_or(0) < count
+                && Instant::now() < deadline
+            {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        let old_ssrc = server.video_ssrc(0);
+        push(server.rtp_packet(0, H265_MAIN10.payload_type, 1));
+        wait_for(1);
+        server.switch_resolution();
+        let new_ssrc = server.video_ssrc(0);
+        for (payload_type, sequence) in [
+            (H265_MAIN10.payload_type, 2),
+            (H265_MAIN10.rtx_payload_type(), 3),
+            (FLEXFEC_PAYLOAD_TYPE, 4),
+        ] {
+            push(server.rtp_packet(0, payload_type, sequence));
+        }
+        wait_for(4);
+        pipeline
+            .set_state(gst::State::Null)
+            .expect("pipeline stops");
+
+        let received = received.lock().expect("received packets").clone();
+        assert_ne!(new_ssrc, old_ssrc);
+        assert_eq!(
+            received
+                .iter()
+                .map(|(_, (payload_type, sequence, ssrc))| (*payload_type, *sequence, *ssrc))
+                .collect::<Vec<_>>(),
+            vec![
+                (H265_MAIN10.payload_type, 1, old_ssrc),
+                (H265_MAIN10.payload_type, 2, new_ssrc),
+                (H265_MAIN10.rtx_payload_type(), 3, new_ssrc),
+                (FLEXFEC_PAYLOAD_TYPE, 4, new_ssrc),
+            ],
+            "every packet after the SSRC switch must leave rtpbin"
+        );
+        for (pad_name, (payload_type, _, ssrc)) in &received {
+            assert_eq!(
+                parse_rtpbin_recv_pad_name(pad_name),
+                Some((0, *ssrc)),
+                "{pad_name} carries payload type {payload_type}"
+            );
+        }
+    }
+
+    #[test]
+    fn reconnect_renegotiates_with_the_new_session() {
+        let mut server = FakeGfnServer::new(&[H264_HIGH, H265_MAIN10])
+            .with_simulcast(&[("h", 1440), ("l", 720)]);
+        let prepare = |server: &FakeGfnServer| {
+            let command = server.command("offer", "offer", settings("H265", "8bit_420"));
+            prepare_native_offer(
+                &command.context.expect("context"),
+                command.sdp.as_deref().unwrap_or_default(),
+                &DecoderProfiles::default(),
+            )
+            .expect("valid offer")
+        };
+
+        let first = prepare(&server);
+        server.reconnect();
+        let second = prepare(&server);
+
+        assert_ne!(
+            first.nvst_params.credentials,
+            second.nvst_params.credentials
+        );
+        assert_eq!(second.nvst_params.credentials.ufrag, server.ice_ufrag());
+        assert_eq!(second.nvst_params.credentials.pwd, server.ice_pwd());
+        assert!(second
+            .fixed_offer_sdp
+            .contains(&format!("c=IN IP4 {SERVER_IP}")));
+        // A layer picked before the drop is still offered under the same id.
+        assert_eq!(first.video_layers, second.video_layers);
+        assert!(negotiate(
+            &server,
+            settings("H265", "8bit_420"),
+            &DecoderProfiles::default()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_answers_that_do_not_match_the_offer() {
+        let server = FakeGfnServer::new(&[H264_HIGH]);
+        let answer = client_answer(&server.offer_sdp(), "client1")
+            .replace("UDP/TLS/RTP/SAVPF 96", "UDP/TLS/RTP/SAVPF 102");
+        assert!(server.accept_answer(&answer, "").is_err());
+    }
+}
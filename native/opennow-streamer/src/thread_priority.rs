@@ -0,0 +1,250 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+//! Scheduling hints for the decode and render streaming threads.
+//!
+//! On weak CPUs frame pacing suffers when the decode or present thread waits
+//! behind background work. When enabled, both threads are raised (MMCSS
+//! "Games" on Windows, `SCHED_RR` or a lower nice value on Linux where the
+//! user is allowed to) for as long as they run the media chain, then put back
+//! before GStreamer hands them to other elements. Whatever the OS actually
+//! granted is reported back for diagnostics.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MediaThreadRole {
+    Decode,
+    Render,
+}
+
+impl MediaThreadRole {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Decode => "decode",
+            Self::Render => "render",
+        }
+    }
+
+    /// Presenting on time matters more than decoding ahead, so render ranks higher.
+    #[cfg(target_os = "linux")]
+    fn realtime_priority(self) -> i32 {
+        match self {
+            Self::Decode => 1,
+            Self::Render => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MediaThreadPriority {
+    pub(crate) role: MediaThreadRole,
+    /// Scheduling the OS granted, e.g. `SCHED_RR 2` or `default (not permitted)`.
+    pub(crate) scheduling: String,
+}
+
+pub(crate) fn format_thread_priority_summary(threads: &[MediaThreadPriority]) -> Option<String> {
+    if threads.is_empty() {
+        return None;
+    }
+    Some(
+        threads
+            .iter()
+            .map(|thread| format!("{} {}", thread.role.label(), thread.scheduling))
+            .collect::<Vec<_>>()
+            .join(" · "),
+    )
+}
+
+/// A thread raised by [`raise_current_thread`], with what it ran at before.
+pub(crate) struct RaisedThread {
+    pub(crate) priority: MediaThreadPriority,
+    previous: platform::PreviousScheduling,
+}
+
+impl RaisedThread {
+    /// Puts the thread back as it was. Like raising, this has to run on the
+    /// thread itself.
+    pub(crate) fn restore(self) {
+        platform::restore_current_thread(&self.previous);
+    }
+}
+
+/// Raises the calling thread. Meant to run from the streaming thread itself,
+/// since neither MMCSS nor `SCHED_RR` can target another one portably.
+pub(crate) fn raise_current_thread(role: MediaThreadRole) -> RaisedThread {
+    let (scheduling, previous) = platform::raise_current_thread(role);
+    RaisedThread {
+        priority: MediaThreadPriority { role, scheduling },
+        previous,
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::MediaThreadRole;
+    use std::os::raw::{c_int, c_uint};
+
+    const SCHED_RR: c_int = 2;
+    const PRIO_PROCESS: c_int = 0;
+    /// Unprivileged users may only lower their nice value this far when
+    /// RLIMIT_NICE allows it, which most desktop distributions do not.
+    const NICE_FALLBACK: c_int = -5;
+
+    #[repr(C)]
+    struct SchedParam {
+        sched_priority: c_int,
+    }
+
+    pub(super) struct PreviousScheduling {
+        policy: c_int,
+        priority: c_int,
+        nice: c_int,
+    }
+
+    unsafe extern "C" {
+        fn sched_setscheduler(pid: c_int, policy: c_int, param: *const SchedParam) -> c_int;
+        fn sched_getscheduler(pid: c_int) -> c_int;
+        fn sched_getparam(pid: c_int, param: *mut SchedParam) -> c_int;
+        fn setpriority(which: c_int, who: c_uint, prio: c_int) -> c_int;
+        fn getpriority(which: c_int, who: c_uint) -> c_int;
+        fn gettid() -> c_int;
+    }
+
+    pub(super) fn raise_current_thread(role: MediaThreadRole) -> (String, PreviousScheduling) {
+        let previous = current_scheduling();
+        (raise_scheduling(role), previous)
+    }
+
+    pub(super) fn restore_current_thread(previous: &PreviousScheduling) {
+        let param = SchedParam {
+            sched_priority: previous.priority,
+        };
+        let tid = unsafe { gettid() };
+        unsafe {
+            sched_setscheduler(0, previous.policy, &param);
+            setpriority(PRIO_PROCESS, tid as c_uint, previous.nice);
+        }
+    }
+
+    fn current_scheduling() -> PreviousScheduling {
+        let mut param = SchedParam { sched_priority: 0 };
+        // pid 0 is the calling thread for the sched_* calls.
+        let policy = unsafe { sched_getscheduler(0) }.max(0);
+        if unsafe { sched_getparam(0, &mut param) } != 0 {
+            param.sched_priority = 0;
+        }
+        let tid = unsafe { gettid() };
+        PreviousScheduling {
+            policy,
+            priority: param.sched_priority,
+            nice: unsafe { getpriority(PRIO_PROCESS, tid as c_uint) },
+        }
+    }
+
+    fn raise_scheduling(role: MediaThreadRole) -> String {
+        let param = SchedParam {
+            sched_priority: role.realtime_priority(),
+        };
+        if unsafe { sched_setscheduler(0, SCHED_RR, &param) } == 0 {
+            return format!("SCHED_RR {}", param.sched_priority);
+        }
+        let tid = unsafe { gettid() };
+        if unsafe { setpriority(PRIO_PROCESS, tid as c_uint, NICE_FALLBACK) } == 0 {
+            return format!("nice {NICE_FALLBACK}");
+        }
+        "default (not permitted)".to_owned()
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::MediaThreadRole;
+    use std::ffi::c_void;
+
+    type Handle = *mut c_void;
+
+    const AVRT_PRIORITY_NORMAL: i32 = 0;
+    const AVRT_PRIORITY_HIGH: i32 = 1;
+
+    /// The MMCSS registration, kept as an address so the handle can cross
+    /// the bus handler's `Send` bound.
+    pub(super) struct PreviousScheduling {
+        mmcss: Option<usize>,
+    }
+
+    #[link(name = "avrt")]
+    unsafe extern "system" {
+        fn AvSetMmThreadCharacteristicsW(task_name: *const u16, task_index: *mut u32) -> Handle;
+        fn AvSetMmThreadPriority(handle: Handle, priority: i32) -> i32;
+        fn AvRevertMmThreadCharacteristics(handle: Handle) -> i32;
+    }
+
+    pub(super) fn raise_current_thread(role: MediaThreadRole) -> (String, PreviousScheduling) {
+        let task: Vec<u16> = "Games".encode_utf16().chain(Some(0)).collect();
+        let mut task_index = 0u32;
+        let handle = unsafe { AvSetMmThreadCharacteristicsW(task.as_ptr(), &mut task_index) };
+        if handle.is_null() {
+            return (
+                "default (MMCSS unavailable)".to_owned(),
+                PreviousScheduling { mmcss: None },
+            );
+        }
+        let previous = PreviousScheduling {
+            mmcss: Some(handle as usize),
+        };
+        let (priority, label) = match role {
+            MediaThreadRole::Decode => (AVRT_PRIORITY_NORMAL, "normal"),
+            MediaThreadRole::Render => (AVRT_PRIORITY_HIGH, "high"),
+        };
+        if unsafe { AvSetMmThreadPriority(handle, priority) } == 0 {
+            return ("MMCSS Games".to_owned(), previous);
+        }
+        (format!("MMCSS Games {label}"), previous)
+    }
+
+    pub(super) fn restore_current_thread(previous: &PreviousScheduling) {
+        if let Some(handle) = previous.mmcss {
+            unsafe {
+                AvRevertMmThreadCharacteristics(handle as Handle);
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+mod platform {
+    use super::MediaThreadRole;
+
+    pub(super) struct PreviousScheduling;
+
+    pub(super) fn raise_current_thread(_role: MediaThreadRole) -> (String, PreviousScheduling) {
+        (
+            "default (unsupported platform)".to_owned(),
+            PreviousScheduling,
+        )
+    }
+
+    pub(super) fn restore_current_thread(_previous: &PreviousScheduling) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_lists_each_thread() {
+        let summary = format_thread_priority_summary(&[
+            MediaThreadPriority {
+                role: MediaThreadRole::Decode,
+                scheduling: "SCHED_RR 1".to_owned(),
+            },
+            MediaThreadPriority {
+                role: MediaThreadRole::Render,
+                scheduling: "default (not permitted)".to_owned(),
+            },
+        ]);
+        assert_eq!(
+            summary.as_deref(),
+            Some("decode SCHED_RR 1 · render default (not permitted)")
+        );
+        assert_eq!(format_thread_priority_summary(&[]), None);
+    }
+}
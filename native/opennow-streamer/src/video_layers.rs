@@ -0,0 +1,164 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+//! Matching incoming video streams to the layers the server offered, and the
+//! RTCP that asks the server to pause the layers the client is not showing.
+
+use crate::protocol::NativeVideoLayer;
+
+const RTCP_VERSION: u8 = 2;
+const RTCP_SENDER_REPORT: u8 = 200;
+const RTCP_RECEIVER_REPORT: u8 = 201;
+const RTCP_TRANSPORT_FEEDBACK: u8 = 205;
+const TMMBR_FORMAT: u8 = 3;
+const TMMBR_MANTISSA_BITS: u32 = 17;
+/// IPv4, UDP and RTP headers per packet, reported alongside each request.
+const TMMBR_PACKET_OVERHEAD_BYTES: u32 = 40;
+
+/// Picks the offered layer an incoming RTP pad carries: the first unattached
+/// layer on the pad's m-line, or the first unattached layer when the mid is
+/// unknown. Rid-based layers on one m-line arrive in the order the server
+/// listed them.
+pub(crate) fn layer_for_incoming_pad(
+    layers: &[NativeVideoLayer],
+    attached: &[&str],
+    mid: Option<&str>,
+) -> Option<String> {
+    let unattached = || {
+        layers
+            .iter()
+            .filter(|layer| !attached.contains(&layer.id.as_str()))
+    };
+    mid.and_then(|mid| {
+        unattached()
+            .find(|layer| layer.mid.as_deref() == Some(mid))
+            .map(|layer| layer.id.clone())
+    })
+    .or_else(|| unattached().next().map(|layer| layer.id.clone()))
+}
+
+/// Session id and SSRC from an `rtpbin` receive pad name such as
+/// `recv_rtp_src_0_2471253114_96`.
+pub(crate) fn parse_rtpbin_recv_pad_name(name: &str) -> Option<(u32, u32)> {
+    let mut parts = name.strip_prefix("recv_rtp_src_")?.split('_');
+    let session = parts.next()?.parse().ok()?;
+    let ssrc = parts.next()?.parse().ok()?;
+    Some((session, ssrc))
+}
+
+/// SSRC of whoever sends a compound RTCP packet, read from its leading
+/// sender or receiver report.
+pub(crate) fn rtcp_sender_ssrc(header: &[u8; 8]) -> Option<u32> {
+    if header[0] >> 6 != RTCP_VERSION
+        || !matches!(header[1], RTCP_SENDER_REPORT | RTCP_RECEIVER_REPORT)
+    {
+        return None;
+    }
+    Some(u32::from_be_bytes([
+        header[4], header[5], header[6], header[7],
+    ]))
+}
+
+/// A TMMBR packet (RFC 5104 §4.2.1) with one entry per `(ssrc, bitrate)`.
+/// A bitrate of zero asks the server to stop sending that SSRC.
+pub(crate) fn tmmbr_packet(sender_ssrc: u32, requests: &[(u32, u64)]) -> Vec<u8> {
+    let length_words = 2 + 2 * requests.len();
+    let mut packet = Vec::with_capacity((length_words + 1) * 4);
+    packet.push((RTCP_VERSION << 6) | TMMBR_FORMAT);
+    packet.push(RTCP_TRANSPORT_FEEDBACK);
+    packet.extend_from_slice(&(length_words as u16).to_be_bytes());
+    packet.extend_from_slice(&sender_ssrc.to_be_bytes());
+    // The media source field is unused for TMMBR.
+    packet.extend_from_slice(&0u32.to_be_bytes());
+    for &(ssrc, bitrate_bps) in requests {
+        packet.extend_from_slice(&ssrc.to_be_bytes());
+        packet.extend_from_slice(&tmmbr_bitrate_word(bitrate_bps).to_be_bytes());
+    }
+    packet
+}
+
+fn tmmbr_bitrate_word(bitrate_bps: u64) -> u32 {
+    let mut mantissa = bitrate_bps;
+    let mut exponent = 0u32;
+    while mantissa >= 1 << TMMBR_MANTISSA_BITS {
+        mantissa >>= 1;
+        exponent += 1;
+    }
+    (exponent << 26) | ((mantissa as u32) << 9) | TMMBR_PACKET_OVERHEAD_BYTES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_incoming_video_pads_to_offered_layers() {
+        let layer = |id: &str, mid: &str| NativeVideoLayer {
+            id: id.to_owned(),
+            mid: Some(mid.to_owned()),
+            width: None,
+            height: None,
+        };
+        let layers = vec![layer("h", "1"), layer("l", "1"), layer("2", "2")];
+
+        assert_eq!(
+            layer_for_incoming_pad(&layers, &[], Some("1")).as_deref(),
+            Some("h")
+        );
+        assert_eq!(
+            layer_for_incoming_pad(&layers, &["h"], Some("1")).as_deref(),
+            Some("l")
+        );
+        assert_eq!(
+            layer_for_incoming_pad(&layers, &[], Some("2")).as_deref(),
+            Some("2")
+        );
+        assert_eq!(
+            layer_for_incoming_pad(&layers, &["h"], None).as_deref(),
+            Some("l")
+        );
+        assert_eq!(
+            layer_for_incoming_pad(&layers, &["h", "l", "2"], None),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_session_and_ssrc_from_rtpbin_pad_names() {
+        assert_eq!(
+            parse_rtpbin_recv_pad_name("recv_rtp_src_0_2471253114_96"),
+            Some((0, 2_471_253_114))
+        );
+        assert_eq!(parse_rtpbin_recv_pad_name("src_0"), None);
+        assert_eq!(parse_rtpbin_recv_pad_name("recv_rtp_src_0_x_96"), None);
+    }
+
+    #[test]
+    fn reads_sender_ssrc_only_from_reports() {
+        assert_eq!(
+            rtcp_sender_ssrc(&[0x81, 201, 0, 7, 0x12, 0x34, 0x56, 0x78]),
+            Some(0x1234_5678)
+        );
+        assert_eq!(rtcp_sender_ssrc(&[0x81, 202, 0, 7, 1, 2, 3, 4]), None);
+        assert_eq!(rtcp_sender_ssrc(&[0x41, 201, 0, 7, 1, 2, 3, 4]), None);
+    }
+
+    #[test]
+    fn encodes_pause_and_resume_requests() {
+        let packet = tmmbr_packet(0xAABB_CCDD, &[(0x0102_0304, 0), (5, 50_000_000)]);
+
+        assert_eq!(packet.len(), 28);
+        assert_eq!(&packet[..4], &[0x83, 205, 0, 6]);
+        assert_eq!(&packet[4..8], &[0xAA, 0xBB, 0xCC, 0xDD]);
+        assert_eq!(&packet[8..12], &[0, 0, 0, 0]);
+        assert_eq!(&packet[12..16], &[1, 2, 3, 4]);
+        assert_eq!(u32::from_be_bytes(packet[16..20].try_into().unwrap()), 40);
+
+        let resume = u32::from_be_bytes(packet[24..28].try_into().unwrap());
+        let exponent = resume >> 26;
+        let mantissa = u64::from((resume >> 9) & ((1 << 17) - 1));
+        assert_eq!(resume & 0x1FF, 40);
+        assert!(mantissa < 1 << 17);
+        assert!((mantissa << exponent) <= 50_000_000);
+        assert!((mantissa + 1) << exponent > 50_000_000);
+    }
+}
@@ -0,0 +1,914 @@
+#![cfg_attr(not(feature = "gstreamer"), allow(dead_code))]
+
+use serde_json::Value;
+use std::fmt;
+
+use crate::input::{
+    GamepadInput, InputEncoder, KeyboardPayload, MouseButtonPayload, MouseMovePayload,
+    MouseWheelPayload, GAMEPAD_PACKET_SIZE, INPUT_GAMEPAD, INPUT_HAPTICS_ENABLED, INPUT_HEARTBEAT,
+    INPUT_KEY_DOWN, INPUT_KEY_UP, INPUT_LOCK_KEYS_SYNC, INPUT_MOUSE_BUTTON_DOWN,
+    INPUT_MOUSE_BUTTON_UP, INPUT_MOUSE_REL, INPUT_MOUSE_WHEEL, WRAPPER_LEGACY_INPUT,
+    WRAPPER_PARTIALLY_RELIABLE_INPUT, WRAPPER_SINGLE_INPUT, WRAPPER_VERSION_MARKER,
+};
+
+pub const CONTROL_CHANNEL_LABEL: &str = "control_channel";
+
+const HANDSHAKE_WORD: u16 = 526;
+const HANDSHAKE_MARKER: u8 = 0x0e;
+const SERVER_LEGACY_HAPTICS: u16 = 267;
+const SERVER_SUB_MESSAGE_WRAPPER: u8 = 34;
+const SERVER_OC_HAPTICS: u32 = 17;
+/// Server wrappers that carry acknowledgements the client does not act on.
+const SERVER_IGNORED_WRAPPERS: [u8; 5] = [32, 33, 35, 36, 255];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolError {
+    Truncated { needed: usize, available: usize },
+    UnknownInputType(u32),
+    UnknownWrapper(u8),
+    NotAHandshake,
+    UnsupportedHaptics(String),
+    InvalidControlMessage(String),
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated { needed, available } => {
+                write!(
+                    f,
+                    "message needs {needed} bytes but only {available} arrived"
+                )
+            }
+            Self::UnknownInputType(input_type) => write!(f, "unknown input type {input_type}"),
+            Self::UnknownWrapper(wrapper) => write!(f, "unknown input wrapper 0x{wrapper:02x}"),
+            Self::NotAHandshake => write!(f, "message is not an input handshake"),
+            Self::UnsupportedHaptics(reason) => write!(f, "unsupported haptics message: {reason}"),
+            Self::InvalidControlMessage(reason) => write!(f, "invalid control message: {reason}"),
+        }
+    }
+}
+
+/// Reads the protocol version from the server's input handshake. Servers send
+/// either the `0x020e` word followed by a little-endian version, or a bare
+/// word whose low byte is `0x0e`.
+pub fn negotiate_input_protocol_version(bytes: &[u8]) -> Option<u16> {
+    if bytes.len() < 2 {
+        return None;
+    }
+
+    let first_word = u16::from_le_bytes([bytes[0], bytes[1]]);
+    if first_word == HANDSHAKE_WORD {
+        return Some(if bytes.len() >= 4 {
+            u16::from_le_bytes([bytes[2], bytes[3]])
+        } else {
+            2
+        });
+    }
+
+    if bytes[0] == HANDSHAKE_MARKER {
+        return Some(first_word);
+    }
+
+    None
+}
+
+/// Version the input encoder speaks for a negotiated server version.
+pub fn encoder_protocol_version(negotiated: u16) -> u8 {
+    negotiated.min(u8::MAX as u16) as u8
+}
+
+/// Client-to-server events on the input channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMessage {
+    Heartbeat,
+    KeyDown(KeyboardPayload),
+    KeyUp(KeyboardPayload),
+    MouseMove(MouseMovePayload),
+    MouseButtonDown(MouseButtonPayload),
+    MouseButtonUp(MouseButtonPayload),
+    MouseWheel(MouseWheelPayload),
+    Gamepad { bitmap: u16, input: GamepadInput },
+    HapticsEnabled { enabled: bool, timestamp_us: u64 },
+    LockKeysSync { state: u8, timestamp_us: u64 },
+}
+
+impl InputMessage {
+    /// Encodes the event for the encoder's negotiated protocol version.
+    /// `partially_reliable` only changes gamepad framing.
+    pub fn encode(&self, encoder: &mut InputEncoder, partially_reliable: bool) -> Vec<u8> {
+        match *self {
+            Self::Heartbeat => encoder.encode_heartbeat(),
+            Self::KeyDown(payload) => encoder.encode_key_down(payload),
+            Self::KeyUp(payload) => encoder.encode_key_up(payload),
+            Self::MouseMove(payload) => encoder.encode_mouse_move(payload),
+            Self::MouseButtonDown(payload) => encoder.encode_mouse_button_down(payload),
+            Self::MouseButtonUp(payload) => encoder.encode_mouse_button_up(payload),
+            Self::MouseWheel(payload) => encoder.encode_mouse_wheel(payload),
+            Self::Gamepad { bitmap, input } => {
+                encoder.encode_gamepad_state(bitmap, input, partially_reliable)
+            }
+            Self::HapticsEnabled {
+                enabled,
+                timestamp_us,
+            } => encoder.encode_haptics_enabled(enabled, timestamp_us),
+            Self::LockKeysSync {
+                state,
+                timestamp_us,
+            } => encoder.encode_lock_keys_sync(state, timestamp_us),
+        }
+    }
+}
+
+/// Partially reliable framing of a gamepad event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartiallyReliableFrame {
+    pub controller_id: u8,
+    pub sequence: u16,
+}
+
+/// A decoded input packet: the event plus the envelope it arrived in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputPacket {
+    /// Envelope timestamp; `None` for unwrapped (protocol v2) packets.
+    pub envelope_timestamp_us: Option<u64>,
+    pub partially_reliable: Option<PartiallyReliableFrame>,
+    pub message: InputMessage,
+}
+
+pub fn decode_input_packet(bytes: &[u8]) -> Result<InputPacket, ProtocolError> {
+    if bytes.first() != Some(&WRAPPER_VERSION_MARKER) {
+        return Ok(InputPacket {
+            envelope_timestamp_us: None,
+            partially_reliable: None,
+            message: decode_input_message(bytes)?,
+        });
+    }
+
+    let mut reader = Reader::new(bytes);
+    reader.skip(1)?;
+    let timestamp_us = reader.u64_be()?;
+    let wrapper = reader.u8()?;
+    let (partially_reliable, payload) = match wrapper {
+        WRAPPER_SINGLE_INPUT => (None, reader.rest()),
+        WRAPPER_LEGACY_INPUT => (None, reader.sized_block()?),
+        WRAPPER_PARTIALLY_RELIABLE_INPUT => {
+            let frame = PartiallyReliableFrame {
+                controller_id: reader.u8()?,
+                sequence: reader.u16_be()?,
+            };
+            let inner = reader.u8()?;
+            if inner != WRAPPER_LEGACY_INPUT {
+                return Err(ProtocolError::UnknownWrapper(inner));
+            }
+            (Some(frame), reader.sized_block()?)
+        }
+        other => return Err(ProtocolError::UnknownWrapper(other)),
+    };
+
+    // Haptics and lock-key events only carry the envelope timestamp.
+    let message = match decode_input_message(payload)? {
+        InputMessage::HapticsEnabled { enabled, .. } => InputMessage::HapticsEnabled {
+            enabled,
+            timestamp_us,
+        },
+        InputMessage::LockKeysSync { state, .. } => InputMessage::LockKeysSync {
+            state,
+            timestamp_us,
+        },
+        message => message,
+    };
+    Ok(InputPacket {
+        envelope_timestamp_us: Some(timestamp_us),
+        partially_reliable,
+        message,
+    })
+}
+
+fn decode_input_message(payload: &[u8]) -> Result<InputMessage, ProtocolError> {
+    let mut reader = Reader::new(payload);
+    let input_type = reader.u32_le()?;
+    Ok(match input_type {
+        INPUT_HEARTBEAT => InputMessage::Heartbeat,
+        INPUT_KEY_DOWN | INPUT_KEY_UP => {
+            let keycode = reader.u16_be()?;
+            let modifiers = reader.u16_be()?;
+            let scancode = reader.u16_be()?;
+            let payload = KeyboardPayload {
+                keycode,
+                scancode,
+                modifiers,
+                timestamp_us: reader.u64_be()?,
+            };
+            if input_type == INPUT_KEY_DOWN {
+                InputMessage::KeyDown(payload)
+            } else {
+                InputMessage::KeyUp(payload)
+            }
+        }
+        INPUT_MOUSE_REL => {
+            let dx = reader.u16_be()? as i16;
+            let dy = reader.u16_be()? as i16;
+            reader.skip(6)?;
+            InputMessage::MouseMove(MouseMovePayload {
+                dx,
+                dy,
+                timestamp_us: reader.u64_be()?,
+            })
+        }
+        INPUT_MOUSE_BUTTON_DOWN | INPUT_MOUSE_BUTTON_UP => {
+            let button = reader.u8()?;
+            reader.skip(5)?;
+            let payload = MouseButtonPayload {
+                button,
+                timestamp_us: reader.u64_be()?,
+            };
+            if input_type == INPUT_MOUSE_BUTTON_DOWN {
+                InputMessage::MouseButtonDown(payload)
+            } else {
+                InputMessage::MouseButtonUp(payload)
+            }
+        }
+        INPUT_MOUSE_WHEEL => {
+            reader.skip(2)?;
+            let delta = reader.u16_be()? as i16;
+            reader.skip(6)?;
+            InputMessage::MouseWheel(MouseWheelPayload {
+                delta,
+                timestamp_us: reader.u64_be()?,
+            })
+        }
+        INPUT_GAMEPAD => {
+            reader.require(GAMEPAD_PACKET_SIZE - 4)?;
+            reader.skip(2)?;
+            let controller_id = reader.u16_le()? as u8;
+            let bitmap = reader.u16_le()?;
+            reader.skip(2)?;
+            let buttons = reader.u16_le()?;
+            let triggers = reader.u16_le()?;
+            let left_stick_x = reader.u16_le()? as i16;
+            let left_stick_y = reader.u16_le()? as i16;
+            let right_stick_x = reader.u16_le()? as i16;
+            let right_stick_y = reader.u16_le()? as i16;
+            reader.skip(6)?;
+            InputMessage::Gamepad {
+                bitmap,
+                input: GamepadInput {
+                    controller_id,
+                    buttons,
+                    left_trigger: triggers as u8,
+                    right_trigger: (triggers >> 8) as u8,
+                    left_stick_x,
+                    left_stick_y,
+                    right_stick_x,
+                    right_stick_y,
+                    connected: controller_id < 16 && bitmap & (1 << controller_id) != 0,
+                    timestamp_us: reader.u64_le()?,
+                },
+            }
+        }
+        INPUT_HAPTICS_ENABLED => InputMessage::HapticsEnabled {
+            enabled: reader.u16_be()? != 0,
+            timestamp_us: 0,
+        },
+        INPUT_LOCK_KEYS_SYNC => InputMessage::LockKeysSync {
+            state: reader.u8()?,
+            timestamp_us: 0,
+        },
+        other => return Err(ProtocolError::UnknownInputType(other)),
+    })
+}
+
+/// Rumble request for one controller. Magnitudes span the full `u16` range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HapticsCommand {
+    pub controller_id: u16,
+    pub weak_magnitude: u16,
+    pub strong_magnitude: u16,
+}
+
+/// Server-to-client messages on the reliable input channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerInputMessage {
+    Handshake {
+        version: u16,
+    },
+    Haptics(HapticsCommand),
+    /// Legacy haptics packet without a rumble payload.
+    HapticsIdle,
+    Ignored {
+        wrapper: u8,
+    },
+}
+
+/// Decodes a message from the reliable input channel. The server may repeat
+/// its handshake at any time; haptics and acknowledgements only follow once
+/// the first handshake completed.
+pub fn decode_server_input_message(
+    bytes: &[u8],
+    handshake_complete: bool,
+) -> Result<ServerInputMessage, ProtocolError> {
+    let mut reader = Reader::new(bytes);
+    reader.require(2)?;
+    if let Some(version) = negotiate_input_protocol_version(bytes) {
+        return Ok(ServerInputMessage::Handshake { version });
+    }
+    if !handshake_complete {
+        return Err(ProtocolError::NotAHandshake);
+    }
+
+    let first_word = u16::from_le_bytes([bytes[0], bytes[1]]);
+    if first_word == SERVER_LEGACY_HAPTICS {
+        reader.skip(2)?;
+        return decode_legacy_haptics(&mut reader);
+    }
+    match bytes[0] {
+        SERVER_SUB_MESSAGE_WRAPPER => {
+            reader.skip(1)?;
+            match reader.u32_le()? {
+                sub_type if sub_type == SERVER_LEGACY_HAPTICS as u32 => {
+                    decode_legacy_haptics(&mut reader)
+                }
+                SERVER_OC_HAPTICS => decode_oc_haptics(&mut reader),
+                other => Err(ProtocolError::UnsupportedHaptics(format!(
+                    "sub-message type {other}"
+                ))),
+            }
+        }
+        wrapper if SERVER_IGNORED_WRAPPERS.contains(&wrapper) => {
+            Ok(ServerInputMessage::Ignored { wrapper })
+        }
+        _ => decode_legacy_haptics(&mut reader),
+    }
+}
+
+fn decode_legacy_haptics(reader: &mut Reader<'_>) -> Result<ServerInputMessage, ProtocolError> {
+    reader.require(10)?;
+    let kind = reader.u16_le()?;
+    let length = reader.u16_le()?;
+    match kind {
+        0 => return Ok(ServerInputMessage::HapticsIdle),
+        1 if length >= 6 => {}
+        1 => {
+            return Err(ProtocolError::UnsupportedHaptics(format!(
+                "legacy payload length {length}"
+            )))
+        }
+        other => {
+            return Err(ProtocolError::UnsupportedHaptics(format!(
+                "legacy kind {other}"
+            )))
+        }
+    }
+    Ok(ServerInputMessage::Haptics(HapticsCommand {
+        controller_id: reader.u16_le()?,
+        weak_magnitude: reader.u16_le()?,
+        strong_magnitude: reader.u16_le()?,
+    }))
+}
+
+fn decode_oc_haptics(reader: &mut Reader<'_>) -> Result<ServerInputMessage, ProtocolError> {
+    reader.require(9)?;
+    let controller = reader.u8()?;
+    if !(6..10).contains(&controller) {
+        return Err(ProtocolError::UnsupportedHaptics(format!(
+            "controller byte {controller}"
+        )));
+    }
+    reader.skip(2)?;
+    let report_kind = reader.u8()?;
+    let flags = reader.u8()?;
+    if report_kind != 5 || flags & !1 != 0 {
+        return Err(ProtocolError::UnsupportedHaptics(format!(
+            "report kind {report_kind} flags 0x{flags:02x}"
+        )));
+    }
+    reader.skip(2)?;
+    Ok(ServerInputMessage::Haptics(HapticsCommand {
+        controller_id: u16::from(controller - 6),
+        weak_magnitude: u16::from(reader.u8()?) << 8,
+        strong_magnitude: u16::from(reader.u8()?) << 8,
+    }))
+}
+
+/// JSON messages on the server-created `control_channel`. Message shapes the
+/// client does not model yet are kept verbatim in `Other`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlMessage {
+    TimerNotification {
+        code: i64,
+        seconds_left: Option<u64>,
+    },
+    RequestKeyframe {
+        reason: String,
+        backlog_frames: u32,
+        attempt: u32,
+    },
+    Other(Value),
+}
+
+impl ControlMessage {
+    pub fn decode(text: &str) -> Result<Self, ProtocolError> {
+        let value: Value = serde_json::from_str(text)
+            .map_err(|error| ProtocolError::InvalidControlMessage(error.to_string()))?;
+        if let Some(timer) = value.get("timerNotification") {
+            let code = timer.get("code").and_then(Value::as_i64).ok_or_else(|| {
+                ProtocolError::InvalidControlMessage("timerNotification has no code".to_owned())
+            })?;
+            return Ok(Self::TimerNotification {
+                code,
+                seconds_left: timer
+                    .get("secondsLeft")
+                    .and_then(Value::as_f64)
+                    .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                    .map(|seconds| seconds.floor() as u64),
+            });
+        }
+        if value.get("type").and_then(Value::as_str) == Some("request_keyframe") {
+            let number = |key: &str| {
+                value
+                    .get(key)
+                    .and_then(Value::as_u64)
+                    .and_then(|number| u32::try_from(number).ok())
+                    .unwrap_or_default()
+            };
+            return Ok(Self::RequestKeyframe {
+                reason: value
+                    .get("reason")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_owned(),
+                backlog_frames: number("backlogFrames"),
+                attempt: number("attempt"),
+            });
+        }
+        Ok(Self::Other(value))
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn require(&self, len: usize) -> Result<(), ProtocolError> {
+        let available = self.bytes.len() - self.offset;
+        if available < len {
+            return Err(ProtocolError::Truncated {
+                needed: self.offset + len,
+                available: self.bytes.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ProtocolError> {
+        self.require(len)?;
+        let slice = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), ProtocolError> {
+        self.take(len).map(|_| ())
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.bytes[self.offset..];
+        self.offset = self.bytes.len();
+        rest
+    }
+
+    fn u8(&mut self) -> Result<u8, ProtocolError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16_be(&mut self) -> Result<u16, ProtocolError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u16_le(&mut self) -> Result<u16, ProtocolError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32_le(&mut self) -> Result<u32, ProtocolError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().expect("four bytes")))
+    }
+
+    fn u64_be(&mut self) -> Result<u64, ProtocolError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_be_bytes(bytes.try_into().expect("eight bytes")))
+    }
+
+    fn u64_le(&mut self) -> Result<u64, ProtocolError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("eight bytes")))
+    }
+
+    /// A block prefixed with its big-endian `u16` length.
+    fn sized_block(&mut self) -> Result<&'a [u8], ProtocolError> {
+        let len = self.u16_be()? as usize;
+        self.take(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyboard(timestamp_us: u64) -> KeyboardPayload {
+        KeyboardPayload {
+            keycode: 0x0041,
+            scancode: 0x001e,
+            modifiers: 0x0002,
+            timestamp_us,
+        }
+    }
+
+    fn gamepad() -> GamepadInput {
+        GamepadInput {
+            controller_id: 2,
+            buttons: 0x1001,
+            left_trigger: 12,
+            right_trigger: 34,
+            left_stick_x: -100,
+            left_stick_y: 100,
+            right_stick_x: -200,
+            right_stick_y: 200,
+            connected: true,
+            timestamp_us: 11,
+        }
+    }
+
+    #[test]
+    fn parses_input_handshake_versions() {
+        assert_eq!(
+            negotiate_input_protocol_version(&[0x0e, 0x02, 0x03, 0x00]),
+            Some(3)
+        );
+        assert_eq!(negotiate_input_protocol_version(&[0x0e, 0x02]), Some(2));
+        assert_eq!(
+            negotiate_input_protocol_version(&[0x0e, 0x03]),
+            Some(0x030e)
+        );
+        assert_eq!(negotiate_input_protocol_version(&[0x01, 0x02, 0x03]), None);
+        assert_eq!(negotiate_input_protocol_version(&[0x0e]), None);
+        assert_eq!(encoder_protocol_version(0x030e), u8::MAX);
+        assert_eq!(
+            decode_server_input_message(&[0x01, 0x02], false),
+            Err(ProtocolError::NotAHandshake)
+        );
+    }
+
+    #[test]
+    fn round_trips_every_input_message_in_both_protocol_generations() {
+        let messages = [
+            InputMessage::Heartbeat,
+            InputMessage::KeyDown(keyboard(7)),
+            InputMessage::KeyUp(keyboard(8)),
+            InputMessage::MouseMove(MouseMovePayload {
+                dx: -2,
+                dy: 300,
+                timestamp_us: 9,
+            }),
+            InputMessage::MouseButtonDown(MouseButtonPayload {
+                button: 1,
+                timestamp_us: 5,
+            }),
+            InputMessage::MouseButtonUp(MouseButtonPayload {
+                button: 3,
+                timestamp_us: 6,
+            }),
+            InputMessage::MouseWheel(MouseWheelPayload {
+                delta: -120,
+                timestamp_us: 6,
+            }),
+            InputMessage::Gamepad {
+                bitmap: 0b0100,
+                input: gamepad(),
+            },
+            InputMessage::HapticsEnabled {
+                enabled: true,
+                timestamp_us: 0,
+            },
+            InputMessage::LockKeysSync {
+                state: 0b101,
+                timestamp_us: 0,
+            },
+        ];
+
+        for version in [2, 3] {
+            for message in messages {
+                let mut encoder = InputEncoder::new(version);
+                let packet = decode_input_packet(&message.encode(&mut encoder, false))
+                    .unwrap_or_else(|error| panic!("{message:?} v{version}: {error}"));
+                assert_eq!(packet.message, message, "protocol v{version}");
+                let wrapped = version >= 3 && message != InputMessage::Heartbeat;
+                assert_eq!(packet.envelope_timestamp_us.is_some(), wrapped);
+            }
+        }
+    }
+
+    #[test]
+    fn restores_envelope_timestamps_for_flag_only_events() {
+        let encoder = InputEncoder::new(3);
+        let packet = decode_input_packet(&encoder.encode_lock_keys_sync(0b011, 42))
+            .expect("lock keys packet");
+        assert_eq!(
+            packet.message,
+            InputMessage::LockKeysSync {
+                state: 0b011,
+                timestamp_us: 42,
+            }
+        );
+    }
+
+    /// Envelope timestamp in the browser client traces, which stamps the
+    /// envelope with `performance.now()` (pinned to 1234.5 ms) rather than
+    /// the event's own timestamp.
+    const BROWSER_ENVELOPE_US: u64 = 1_234_500;
+
+    /// Packets produced by the browser client's `InputEncoder`
+    /// (`renderer/src/gfn/inputProtocol.ts`), the encoder live sessions use.
+    /// Each entry is the protocol version, whether the gamepad went out on
+    /// the partially reliable channel, and the bytes sent.
+    const BROWSER_CLIENT_TRACES: &[(u8, bool, &[u8])] = &[
+        (2, false, &[0x02, 0x00, 0x00, 0x00]),
+        (
+            2,
+            false,
+            &[
+                0x03, 0x00, 0x00, 0x00, 0x00, 0x41, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x07,
+            ],
+        ),
+        (
+            2,
+            false,
+            &[
+                0x0c, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x02, 0x00, 0x04, 0x04, 0x14, 0x00, 0x01, 0x10,
+                0x0c, 0x22, 0x9c, 0xff, 0x64, 0x00, 0x38, 0xff, 0xc8, 0x00, 0x00, 0x00, 0x55, 0x00,
+                0x00, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ],
+        ),
+        (3, false, &[0x02, 0x00, 0x00, 0x00]),
+        (
+            3,
+            false,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x22, 0x03, 0x00, 0x00, 0x00,
+                0x00, 0x41, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07,
+            ],
+        ),
+        (
+            3,
+            false,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x21, 0x00, 0x16, 0x07, 0x00,
+                0x00, 0x00, 0xff, 0xfe, 0x01, 0x2c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x09,
+            ],
+        ),
+        (
+            3,
+            false,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x22, 0x09, 0x00, 0x00, 0x00,
+                0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06,
+            ],
+        ),
+        (
+            3,
+            false,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x22, 0x0a, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0xff, 0x88, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x06,
+            ],
+        ),
+        (
+            3,
+            false,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x22, 0x0d, 0x00, 0x00, 0x00,
+                0x00, 0x01,
+            ],
+        ),
+        (
+            3,
+            false,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x22, 0x13, 0x00, 0x00, 0x00,
+                0x71,
+            ],
+        ),
+        (
+            3,
+            false,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x21, 0x00, 0x26, 0x0c, 0x00,
+                0x00, 0x00, 0x1a, 0x00, 0x02, 0x00, 0x04, 0x04, 0x14, 0x00, 0x01, 0x10, 0x0c, 0x22,
+                0x9c, 0xff, 0x64, 0x00, 0x38, 0xff, 0xc8, 0x00, 0x00, 0x00, 0x55, 0x00, 0x00, 0x00,
+                0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ],
+        ),
+        (
+            3,
+            true,
+            &[
+                0x23, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0xd6, 0x44, 0x26, 0x02, 0x00, 0x02, 0x21,
+                0x00, 0x26, 0x0c, 0x00, 0x00, 0x00, 0x1a, 0x00, 0x02, 0x00, 0x04, 0x04, 0x14, 0x00,
+                0x01, 0x10, 0x0c, 0x22, 0x9c, 0xff, 0x64, 0x00, 0x38, 0xff, 0xc8, 0x00, 0x00, 0x00,
+                0x55, 0x00, 0x00, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ],
+        ),
+    ];
+
+    #[test]
+    fn decodes_and_reproduces_browser_client_traces() {
+        let key = InputMessage::KeyDown(KeyboardPayload {
+            keycode: 0x0041,
+            scancode: 0,
+            modifiers: 0x0002,
+            timestamp_us: 7,
+        });
+        let pad = InputMessage::Gamepad {
+            bitmap: 0x0404,
+            input: gamepad(),
+        };
+        let expected = [
+            InputMessage::Heartbeat,
+            key,
+            pad,
+            InputMessage::Heartbeat,
+            key,
+            InputMessage::MouseMove(MouseMovePayload {
+                dx: -2,
+                dy: 300,
+                timestamp_us: 9,
+            }),
+            InputMessage::MouseButtonUp(MouseButtonPayload {
+                button: 3,
+                timestamp_us: 6,
+            }),
+            InputMessage::MouseWheel(MouseWheelPayload {
+                delta: -120,
+                timestamp_us: 6,
+            }),
+            InputMessage::HapticsEnabled {
+                enabled: true,
+                timestamp_us: BROWSER_ENVELOPE_US,
+            },
+            InputMessage::LockKeysSync {
+                state: 0x71,
+                timestamp_us: BROWSER_ENVELOPE_US,
+            },
+            pad,
+            pad,
+        ];
+
+        for (&(version, partially_reliable, trace), message) in
+            BROWSER_CLIENT_TRACES.iter().zip(expected)
+        {
+            let packet = decode_input_packet(trace)
+                .unwrap_or_else(|error| panic!("{message:?} v{version}: {error}"));
+            assert_eq!(packet.message, message, "protocol v{version}");
+            let wrapped = version >= 3 && message != InputMessage::Heartbeat;
+            assert_eq!(
+                packet.envelope_timestamp_us,
+                wrapped.then_some(BROWSER_ENVELOPE_US)
+            );
+
+            // The native encoder stamps the envelope with the event time
+            // instead, so only the bytes after the envelope timestamp match.
+            let mut encoder = InputEncoder::new(version);
+            if partially_reliable {
+                message.encode(&mut encoder, true);
+            }
+            let encoded = message.encode(&mut encoder, partially_reliable);
+            let skip = if wrapped { 9 } else { 0 };
+            assert_eq!(encoded.len(), trace.len(), "{message:?} v{version}");
+            assert_eq!(&encoded[skip..], &trace[skip..], "{message:?} v{version}");
+        }
+
+        let pr = decode_input_packet(BROWSER_CLIENT_TRACES[11].2).expect("gamepad packet");
+        assert_eq!(
+            pr.partially_reliable,
+            Some(PartiallyReliableFrame {
+                controller_id: 2,
+                sequence: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_and_unknown_input_packets() {
+        assert_eq!(
+            decode_input_packet(&[0x03, 0x00, 0x00, 0x00, 0x00]),
+            Err(ProtocolError::Truncated {
+                needed: 6,
+                available: 5
+            })
+        );
+        assert_eq!(
+            decode_input_packet(&[0x63, 0x00, 0x00, 0x00]),
+            Err(ProtocolError::UnknownInputType(0x63))
+        );
+        assert_eq!(
+            decode_input_packet(&[0x23, 0, 0, 0, 0, 0, 0, 0, 0, 0x30]),
+            Err(ProtocolError::UnknownWrapper(0x30))
+        );
+        assert!(matches!(
+            decode_input_packet(&[0x23, 0, 0, 0, 0, 0, 0, 0, 0, 0x21, 0x00, 0x16, 0x07]),
+            Err(ProtocolError::Truncated { .. })
+        ));
+    }
+
+    /// No server haptics traces are checked in; these follow the layouts the
+    /// browser client's parseLegacyHapticPacket() and parseOcHapticPacket() read.
+    #[test]
+    fn decodes_server_haptics_messages() {
+        let legacy = [
+            0x0b, 0x01, 0x01, 0x00, 0x06, 0x00, 0x01, 0x00, 0x00, 0x80, 0xff, 0xff,
+        ];
+        assert_eq!(
+            decode_server_input_message(&legacy, true),
+            Ok(ServerInputMessage::Haptics(HapticsCommand {
+                controller_id: 1,
+                weak_magnitude: 0x8000,
+                strong_magnitude: 0xffff,
+            }))
+        );
+
+        let oc = [
+            0x22, 0x11, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x05, 0x01, 0x00, 0x00, 0x40, 0x80,
+        ];
+        assert_eq!(
+            decode_server_input_message(&oc, true),
+            Ok(ServerInputMessage::Haptics(HapticsCommand {
+                controller_id: 1,
+                weak_magnitude: 0x4000,
+                strong_magnitude: 0x8000,
+            }))
+        );
+
+        let stop = [
+            0x0b, 0x01, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(
+            decode_server_input_message(&stop, true),
+            Ok(ServerInputMessage::HapticsIdle)
+        );
+        assert_eq!(
+            decode_server_input_message(&[0xff, 0x00], true),
+            Ok(ServerInputMessage::Ignored { wrapper: 0xff })
+        );
+        assert!(matches!(
+            decode_server_input_message(&[0x22, 0x63, 0x00, 0x00, 0x00], true),
+            Err(ProtocolError::UnsupportedHaptics(_))
+        ));
+    }
+
+    #[test]
+    fn decodes_control_channel_messages() {
+        let timer = ControlMessage::decode(r#"{"timerNotification":{"code":4,"secondsLeft":300}}"#)
+            .expect("timer notification");
+        assert_eq!(
+            timer,
+            ControlMessage::TimerNotification {
+                code: 4,
+                seconds_left: Some(300),
+            }
+        );
+
+        // As the browser client's requestDecoderKeyframe() sends it.
+        assert_eq!(
+            ControlMessage::decode(
+                r#"{"type":"request_keyframe","reason":"decoder_stall","backlogFrames":12,"attempt":2}"#
+            ),
+            Ok(ControlMessage::RequestKeyframe {
+                reason: "decoder_stall".to_owned(),
+                backlog_frames: 12,
+                attempt: 2,
+            })
+        );
+
+        assert!(matches!(
+            ControlMessage::decode(r#"{"streamingFeatures":{"hdr":true}}"#),
+            Ok(ControlMessage::Other(_))
+        ));
+        assert!(matches!(
+            ControlMessage::decode(r#"{"timerNotification":{}}"#),
+            Err(ProtocolError::InvalidControlMessage(_))
+        ));
+        assert!(ControlMessage::decode("not json").is_err());
+    }
+}
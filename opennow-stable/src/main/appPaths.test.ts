@@ -0,0 +1,76 @@
+import assert from "node:assert/strict";
+import { existsSync, mkdirSync, mkdtempSync, readdirSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import test from "node:test";
+
+import { planPathMigrations, resolveAppPathLayout, runPathMigrations, type AppPathInput } from "./appPaths";
+
+const linux: AppPathInput = {
+  platform: "linux",
+  argv: ["/opt/OpenNOW/opennow"],
+  env: {},
+  home: "/home/ada",
+  executableDir: "/opt/OpenNOW",
+  defaultUserData: "/home/ada/.config/OpenNOW",
+  fileExists: () => false,
+};
+
+test("linux separates cache and logs from config along the XDG base dirs", () => {
+  assert.deepEqual(resolveAppPathLayout(linux), {
+    mode: "xdg",
+    config: "/home/ada/.config/OpenNOW",
+    cache: "/home/ada/.cache/OpenNOW",
+    logs: "/home/ada/.local/state/OpenNOW",
+  });
+  const custom = resolveAppPathLayout({ ...linux, env: { XDG_CACHE_HOME: "/fast/cache", XDG_STATE_HOME: "relative/state" } });
+  assert.equal(custom.cache, "/fast/cache/OpenNOW");
+  assert.equal(custom.logs, "/home/ada/.local/state/OpenNOW");
+  assert.equal(resolveAppPathLayout({ ...linux, platform: "win32", defaultUserData: "C:\\AppData\\OpenNOW" }).mode, "default");
+});
+
+test("the flag or a marker next to the launched file switches to portable mode", () => {
+  assert.equal(resolveAppPathLayout({ ...linux, argv: [...linux.argv, "--portable"] }).config, "/opt/OpenNOW/OpenNOW Data/config");
+  const appImage = resolveAppPathLayout({
+    ...linux,
+    env: { APPIMAGE: "/home/ada/Apps/OpenNOW.AppImage" },
+    fileExists: (path) => path === "/home/ada/Apps/OpenNOW.portable",
+  });
+  assert.deepEqual(appImage, {
+    mode: "portable",
+    config: "/home/ada/Apps/OpenNOW Data/config",
+    cache: "/home/ada/Apps/OpenNOW Data/cache",
+    logs: "/home/ada/Apps/OpenNOW Data/logs",
+  });
+});
+
+test("existing caches and crash reports move out of the config directory once", () => {
+  const root = mkdtempSync(join(tmpdir(), "opennow-paths-"));
+  try {
+    const config = join(root, "config");
+    mkdirSync(join(config, "gfn-cache"), { recursive: true });
+    mkdirSync(join(config, "crash-reports"));
+    mkdirSync(join(config, "Cache"));
+    writeFileSync(join(config, "gfn-cache", "games.json"), "{}");
+    writeFileSync(join(config, "settings.json"), "{}");
+    const layout = { mode: "xdg" as const, config, cache: join(root, "cache"), logs: join(root, "state") };
+
+    runPathMigrations(planPathMigrations(layout, config));
+    assert.deepEqual(readdirSync(config), ["settings.json"]);
+    assert.ok(existsSync(join(root, "cache", "gfn-cache", "games.json")));
+    assert.ok(existsSync(join(root, "state", "crash-reports")));
+
+    mkdirSync(join(config, "GPUCache"));
+    mkdirSync(join(config, "Network"));
+    writeFileSync(join(config, "auth-state.json"), "{}");
+    writeFileSync(join(config, "Cookies"), "");
+    const portable = join(root, "portable");
+    mkdirSync(`${portable}.partial`);
+    writeFileSync(join(`${portable}.partial`, "stale.json"), "{}");
+    runPathMigrations(planPathMigrations({ mode: "portable", config: portable, cache: portable, logs: portable }, config));
+    assert.deepEqual(readdirSync(portable), ["settings.json"]);
+    assert.ok(!existsSync(`${portable}.partial`));
+  } finally {
+    rmSync(root, { recursive: true, force: true });
+  }
+});
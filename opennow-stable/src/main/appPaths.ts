@@ -0,0 +1,180 @@
+import { cpSync, existsSync, mkdirSync, renameSync, rmSync } from "node:fs";
+import { basename, dirname, isAbsolute, join } from "node:path";
+
+export const PORTABLE_FLAG = "--portable";
+/** An empty file with this name next to the executable turns portable mode on without the flag. */
+export const PORTABLE_MARKER_FILE = "OpenNOW.portable";
+const PORTABLE_DATA_DIRECTORY = "OpenNOW Data";
+
+/** Regenerable data that used to sit under userData and now goes to the cache directory. */
+const CACHE_ENTRIES = ["gfn-cache", "media-thumbs", "native-streamer"];
+const LOG_ENTRIES = ["crash-reports"];
+/** Chromium's HTTP cache; it moves through `disk-cache-dir`, so the old copy is just dropped. */
+const LEGACY_HTTP_CACHE = "Cache";
+/**
+ * Sign-in state and Chromium's cookie stores. A portable folder is meant to be
+ * carried around, so it starts signed out rather than with copied tokens.
+ */
+const AUTH_ENTRIES = ["auth-state.json", "active-session.json", "gfn-device-id.json", "Cookies", "Cookies-journal", "Network"];
+/** Left behind when a portable install copies the installed profile. */
+const PORTABLE_COPY_EXCLUDES = [
+  ...CACHE_ENTRIES,
+  ...LOG_ENTRIES,
+  ...AUTH_ENTRIES,
+  LEGACY_HTTP_CACHE,
+  "Code Cache",
+  "GPUCache",
+  "Crashpad",
+];
+
+export type AppPathMode = "default" | "xdg" | "portable";
+
+export interface AppPathLayout {
+  mode: AppPathMode;
+  /** Settings, auth state and Chromium profile data (Electron's userData) */
+  config: string;
+  /** Data that can be regenerated at any time */
+  cache: string;
+  /** Crash reports and dumps */
+  logs: string;
+}
+
+export interface AppPathInput {
+  platform: NodeJS.Platform;
+  argv: readonly string[];
+  env: NodeJS.ProcessEnv;
+  home: string;
+  executableDir: string;
+  /** Where Electron puts userData by default; already honours XDG_CONFIG_HOME on Linux */
+  defaultUserData: string;
+  fileExists: (path: string) => boolean;
+}
+
+export interface PathMigration {
+  from: string;
+  /** Null removes `from` */
+  to: string | null;
+  /** Copy instead of move, leaving `from` in place */
+  copy?: boolean;
+  /** Top-level entries of `from` left out of a copy */
+  exclude?: readonly string[];
+}
+
+/**
+ * Directory a portable install keeps its data next to. The electron-builder
+ * portable exe and AppImages run from a temporary mount, so both report where
+ * the file the user launched actually lives.
+ */
+export function portableRoot(env: NodeJS.ProcessEnv, executableDir: string): string {
+  if (env.PORTABLE_EXECUTABLE_DIR) {
+    return env.PORTABLE_EXECUTABLE_DIR;
+  }
+  return env.APPIMAGE ? dirname(env.APPIMAGE) : executableDir;
+}
+
+/** The XDG spec says relative paths in these variables are invalid and must be ignored. */
+function xdgBase(value: string | undefined, fallback: string): string {
+  return value && isAbsolute(value) ? value : fallback;
+}
+
+export function resolveAppPathLayout(input: AppPathInput): AppPathLayout {
+  const root = portableRoot(input.env, input.executableDir);
+  if (input.argv.includes(PORTABLE_FLAG) || input.fileExists(join(root, PORTABLE_MARKER_FILE))) {
+    const data = join(root, PORTABLE_DATA_DIRECTORY);
+    return { mode: "portable", config: join(data, "config"), cache: join(data, "cache"), logs: join(data, "logs") };
+  }
+  if (input.platform === "linux") {
+    const appName = basename(input.defaultUserData);
+    return {
+      mode: "xdg",
+      config: input.defaultUserData,
+      cache: join(xdgBase(input.env.XDG_CACHE_HOME, join(input.home, ".cache")), appName),
+      logs: join(xdgBase(input.env.XDG_STATE_HOME, join(input.home, ".local", "state")), appName),
+    };
+  }
+  return { mode: "default", config: input.defaultUserData, cache: input.defaultUserData, logs: input.defaultUserData };
+}
+
+/** Moves data written by older versions into the layout's directories. */
+export function planPathMigrations(layout: AppPathLayout, defaultUserData: string): PathMigration[] {
+  if (layout.mode === "portable") {
+    return [{ from: defaultUserData, to: layout.config, copy: true, exclude: PORTABLE_COPY_EXCLUDES }];
+  }
+  if (layout.mode === "default") {
+    return [];
+  }
+  return [
+    ...CACHE_ENTRIES.map((entry) => ({ from: join(defaultUserData, entry), to: join(layout.cache, entry) })),
+    ...LOG_ENTRIES.map((entry) => ({ from: join(defaultUserData, entry), to: join(layout.logs, entry) })),
+    { from: join(defaultUserData, LEGACY_HTTP_CACHE), to: null },
+  ];
+}
+
+/**
+ * Applies migrations whose source exists and whose target does not, so each
+ * runs once. Failures are logged and skipped; losing a cache is not fatal.
+ */
+export function runPathMigrations(migrations: readonly PathMigration[]): void {
+  for (const migration of migrations) {
+    const { from, to } = migration;
+    if (!existsSync(from) || (to !== null && existsSync(to))) {
+      continue;
+    }
+    try {
+      if (to === null) {
+        rmSync(from, { recursive: true, force: true });
+        console.log(`[AppPaths] Removed ${from}`);
+        continue;
+      }
+      mkdirSync(dirname(to), { recursive: true });
+      if (migration.copy) {
+        // Copied beside the target and renamed into place, so a copy cut short
+        // is redone on the next launch instead of counting as finished.
+        const staging = `${to}.partial`;
+        const excluded = new Set((migration.exclude ?? []).map((entry) => join(from, entry)));
+        rmSync(staging, { recursive: true, force: true });
+        cpSync(from, staging, { recursive: true, filter: (source) => !excluded.has(source) });
+        renameSync(staging, to);
+      } else {
+        moveSync(from, to);
+      }
+      console.log(`[AppPaths] ${migration.copy ? "Copied" : "Moved"} ${from} to ${to}`);
+    } catch (error) {
+      console.warn(`[AppPaths] Failed to migrate ${from}:`, error);
+    }
+  }
+}
+
+/** rename() cannot cross filesystems, which separate cache and config mounts often are. */
+function moveSync(from: string, to: string): void {
+  try {
+    renameSync(from, to);
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== "EXDEV") {
+      throw error;
+    }
+    cpSync(from, to, { recursive: true });
+    rmSync(from, { recursive: true, force: true });
+  }
+}
+
+let activeLayout: AppPathLayout | null = null;
+
+export function setAppPathLayout(layout: AppPathLayout): void {
+  activeLayout = layout;
+}
+
+function requireLayout(): AppPathLayout {
+  if (!activeLayout) {
+    throw new Error("App paths are read before main configured them");
+  }
+  return activeLayout;
+}
+
+export function getAppCacheDirectory(): string {
+  return requireLayout().cache;
+}
+
+export function getAppLogsDirectory(): string {
+  return requireLayout().logs;
+}
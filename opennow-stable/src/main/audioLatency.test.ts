@@ -0,0 +1,19 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { buildAudioLatencyCommandLine } from "./audioLatency";
+
+test("applies Chromium, PulseAudio and PipeWire latency hints on Linux", () => {
+  const commandLine = buildAudioLatencyCommandLine(10, "linux");
+
+  assert.deepEqual(commandLine.switches, { "audio-buffer-size": "480" });
+  assert.deepEqual(commandLine.env, { PULSE_LATENCY_MSEC: "10", PIPEWIRE_LATENCY: "480/48000" });
+});
+
+test("leaves the platform default alone when no buffer is requested", () => {
+  assert.deepEqual(buildAudioLatencyCommandLine(0, "linux"), { switches: {}, env: {} });
+});
+
+test("does not touch Windows shared-mode output", () => {
+  assert.deepEqual(buildAudioLatencyCommandLine(10, "win32"), { switches: {}, env: {} });
+});
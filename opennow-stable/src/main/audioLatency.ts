@@ -0,0 +1,27 @@
+export interface AudioLatencyCommandLine {
+  switches: Record<string, string>;
+  env: Record<string, string>;
+}
+
+const AUDIO_SAMPLE_RATE = 48_000;
+
+/**
+ * Startup switches and environment for a requested output buffer. Chromium
+ * sizes its Linux output stream from `audio-buffer-size` (frames), and libpulse
+ * and PipeWire read their latency hints from the environment, which the native
+ * streamer child process inherits as well. Windows shared-mode output is
+ * owned by the audio engine, so nothing is applied there.
+ */
+export function buildAudioLatencyCommandLine(bufferMs: number, platform: NodeJS.Platform): AudioLatencyCommandLine {
+  if (platform !== "linux" || !Number.isFinite(bufferMs) || bufferMs <= 0) {
+    return { switches: {}, env: {} };
+  }
+  const frames = Math.max(1, Math.round((bufferMs * AUDIO_SAMPLE_RATE) / 1000));
+  return {
+    switches: { "audio-buffer-size": String(frames) },
+    env: {
+      PULSE_LATENCY_MSEC: String(Math.round(bufferMs)),
+      PIPEWIRE_LATENCY: `${frames}/${AUDIO_SAMPLE_RATE}`,
+    },
+  };
+}
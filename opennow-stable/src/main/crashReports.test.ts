@@ -0,0 +1,54 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import {
+  buildCrashIssueUrl,
+  describeNativeCrashExit,
+  formatCrashReport,
+  isCrashProcessGoneReason,
+} from "./crashReports";
+
+test("formats crash reports with system info and redacted logs", () => {
+  const report = formatCrashReport({
+    source: "main",
+    reason: "uncaught exception: boom",
+    appVersion: "1.2.3",
+    details: "Error: boom\n    at main.js:1:1\n",
+    logs: ["connecting to 10.0.0.1", "user test@example.com signed in"],
+    timestamp: Date.UTC(2026, 0, 2, 3, 4, 5),
+  });
+
+  assert.match(report, /^OpenNOW Crash Report\nSource: main\nTimestamp: 2026-01-02T03:04:05\.000Z\nVersion: 1\.2\.3\n/);
+  assert.match(report, /\nReason: uncaught exception: boom\n/);
+  assert.match(report, /\n== Details ==\nError: boom\n {4}at main\.js:1:1\n/);
+  assert.ok(report.includes("connecting to [Redacted IP]"));
+  assert.ok(!report.includes("test@example.com"));
+});
+
+test("prefills the issue title from the report reason and truncates long reports", () => {
+  const report = `OpenNOW Crash Report\nReason: panic: index out of bounds\n${"x".repeat(10000)}`;
+  const url = new URL(buildCrashIssueUrl(report, 3));
+
+  assert.equal(url.origin + url.pathname, "https://github.com/OpenCloudGaming/OpenNOW/issues/new");
+  assert.equal(url.searchParams.get("title"), "Crash: panic: index out of bounds");
+  const body = url.searchParams.get("body") ?? "";
+  assert.ok(body.includes("3 crash reports were found"));
+  assert.ok(body.includes("[truncated]"));
+  assert.ok(body.length < 7000);
+});
+
+test("recognizes fatal native streamer exits", () => {
+  assert.equal(describeNativeCrashExit(null, "SIGSEGV"), "native streamer terminated by SIGSEGV");
+  assert.equal(describeNativeCrashExit(null, "SIGTERM"), null);
+  assert.equal(describeNativeCrashExit(0, null), null);
+  assert.equal(describeNativeCrashExit(101, null), null);
+  assert.equal(describeNativeCrashExit(3221225477, null), "native streamer exception 0xC0000005");
+  assert.equal(describeNativeCrashExit(-1073740791, null), "native streamer exception 0xC0000409");
+});
+
+test("ignores clean and user-initiated Electron process exits", () => {
+  assert.equal(isCrashProcessGoneReason("crashed"), true);
+  assert.equal(isCrashProcessGoneReason("oom"), true);
+  assert.equal(isCrashProcessGoneReason("clean-exit"), false);
+  assert.equal(isCrashProcessGoneReason("killed"), false);
+});
@@ -0,0 +1,156 @@
+import { existsSync, mkdirSync, readdirSync, readFileSync, renameSync, statSync, writeFileSync } from "node:fs";
+import { arch, release } from "node:os";
+import { join } from "node:path";
+
+import { redactSensitiveData } from "@shared/logger";
+
+export const CRASH_REPORTS_DIR_NAME = "crash-reports";
+/** Reports the user has already been prompted about move here so they are only offered once. */
+const REPORTED_DIR_NAME = "reported";
+const NEW_ISSUE_URL = "https://github.com/OpenCloudGaming/OpenNOW/issues/new";
+/** GitHub rejects very long prefilled issue URLs, so the report body is cut to fit. */
+const MAX_ISSUE_REPORT_LENGTH = 6000;
+const MAX_CRASH_LOG_LINES = 200;
+
+/** Native streamer panics exit with this code after its own hook has written a report. */
+const NATIVE_PANIC_EXIT_CODE = 101;
+const FATAL_SIGNALS = new Set(["SIGSEGV", "SIGBUS", "SIGILL", "SIGFPE", "SIGABRT", "SIGTRAP"]);
+/** Electron process-gone reasons that mean a crash rather than a normal or user-initiated exit. */
+const CRASH_PROCESS_GONE_REASONS = new Set(["crashed", "oom", "abnormal-exit", "launch-failed", "integrity-failure"]);
+
+export interface CrashReportInput {
+  source: string;
+  reason: string;
+  appVersion: string;
+  details?: string;
+  logs?: string[];
+  timestamp?: number;
+}
+
+export function resolveCrashReportsDirectory(logsDirectory: string): string {
+  return join(logsDirectory, CRASH_REPORTS_DIR_NAME);
+}
+
+export function formatCrashReport(input: CrashReportInput): string {
+  const lines = [
+    "OpenNOW Crash Report",
+    `Source: ${input.source}`,
+    `Timestamp: ${new Date(input.timestamp ?? Date.now()).toISOString()}`,
+    `Version: ${input.appVersion}`,
+    `System: ${process.platform} ${arch()} (${release()})`,
+    `Electron: ${process.versions.electron ?? "n/a"}`,
+    `Reason: ${input.reason}`,
+  ];
+  if (input.details) {
+    lines.push("", "== Details ==", input.details.trimEnd());
+  }
+  lines.push("", "== Recent log ==", ...(input.logs ?? []).slice(-MAX_CRASH_LOG_LINES));
+  return redactSensitiveData(`${lines.join("\n")}\n`);
+}
+
+/**
+ * Writes synchronously so it still completes from an uncaught exception handler.
+ * Returns the report path, or null when the directory is not writable.
+ */
+export function writeCrashReport(directory: string, input: CrashReportInput): string | null {
+  const timestamp = input.timestamp ?? Date.now();
+  const path = join(directory, `${input.source}-${timestamp}.txt`);
+  try {
+    mkdirSync(directory, { recursive: true });
+    writeFileSync(path, formatCrashReport({ ...input, timestamp }), "utf-8");
+    return path;
+  } catch (error) {
+    console.error("[CrashReports] Failed to write crash report:", error);
+    return null;
+  }
+}
+
+/** Reports from earlier runs that have not been offered to the user yet, newest first. */
+export function listPendingCrashReports(directory: string): string[] {
+  if (!existsSync(directory)) {
+    return [];
+  }
+  try {
+    return readdirSync(directory)
+      .filter((name) => name.endsWith(".txt"))
+      .map((name) => join(directory, name))
+      .sort((a, b) => statSync(b).mtimeMs - statSync(a).mtimeMs);
+  } catch (error) {
+    console.warn("[CrashReports] Failed to list crash reports:", error);
+    return [];
+  }
+}
+
+export function markCrashReportsReported(directory: string, paths: string[]): void {
+  const reportedDirectory = join(directory, REPORTED_DIR_NAME);
+  try {
+    mkdirSync(reportedDirectory, { recursive: true });
+    for (const path of paths) {
+      renameSync(path, join(reportedDirectory, path.slice(directory.length + 1)));
+    }
+  } catch (error) {
+    console.warn("[CrashReports] Failed to archive crash reports:", error);
+  }
+}
+
+export function readCrashReport(path: string): string {
+  try {
+    return readFileSync(path, "utf-8");
+  } catch {
+    return "";
+  }
+}
+
+function extractCrashReason(report: string): string {
+  const match = /^Reason: (.+)$/m.exec(report);
+  return match ? match[1].trim() : "unknown crash";
+}
+
+/** Builds a prefilled GitHub issue from the most recent report. */
+export function buildCrashIssueUrl(report: string, reportCount = 1): string {
+  const redacted = redactSensitiveData(report);
+  const trimmedReport = redacted.length > MAX_ISSUE_REPORT_LENGTH
+    ? `${redacted.slice(0, MAX_ISSUE_REPORT_LENGTH)}\n[truncated]`
+    : redacted.trimEnd();
+  const body = [
+    "**What were you doing when OpenNOW crashed?**",
+    "",
+    "",
+    reportCount > 1 ? `_${reportCount} crash reports were found; the most recent one is attached below._\n` : "",
+    "<details><summary>Crash report</summary>",
+    "",
+    "```",
+    trimmedReport,
+    "```",
+    "</details>",
+  ].join("\n");
+  const params = new URLSearchParams({
+    title: `Crash: ${extractCrashReason(redacted).slice(0, 120)}`,
+    body,
+    labels: "bug",
+  });
+  return `${NEW_ISSUE_URL}?${params.toString()}`;
+}
+
+export function isCrashProcessGoneReason(reason: string): boolean {
+  return CRASH_PROCESS_GONE_REASONS.has(reason);
+}
+
+/**
+ * Describes a native streamer exit caused by a fatal signal or an unhandled Windows
+ * exception (NTSTATUS error codes). Clean exits and panics, which write their own
+ * report, return null.
+ */
+export function describeNativeCrashExit(code: number | null, signal: string | null): string | null {
+  if (signal) {
+    return FATAL_SIGNALS.has(signal) ? `native streamer terminated by ${signal}` : null;
+  }
+  if (code === null || code === NATIVE_PANIC_EXIT_CODE) {
+    return null;
+  }
+  const status = code >>> 0;
+  if (status >= 0xc0000000) {
+    return `native streamer exception 0x${status.toString(16).toUpperCase().padStart(8, "0")}`;
+  }
+  return null;
+}
@@ -0,0 +1,126 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import {
+  applyWorkaroundEnv,
+  buildDriverWorkaroundReport,
+  buildWorkaroundCommandLine,
+  compareVersions,
+  detectWorkaroundEnvironment,
+  parseWorkaroundRuleSet,
+  type WorkaroundRuleSet,
+} from "./driverWorkarounds";
+
+const files: Record<string, string> = {
+  "/sys/module/nvidia/version": "535.183.01\n",
+  "/usr/share/gnome/gnome-version.xml": "<gnome-version><platform>44</platform><minor>3</minor></gnome-version>",
+};
+
+const ruleSet: WorkaroundRuleSet = {
+  revision: 1,
+  rules: [
+    {
+      id: "nvidia-vaapi-zero-copy",
+      description: "zero-copy",
+      match: { platform: ["linux"], vaapiDriver: "nvidia", nvidiaDriverBelow: "545" },
+      apply: { env: { OPENNOW_NATIVE_ZERO_COPY: "0" }, disableFeatures: ["AcceleratedVideoDecodeLinuxZeroCopyGL"] },
+    },
+    {
+      id: "gnome-wayland-before-45",
+      description: "gnome",
+      match: { displayServer: "wayland", desktop: "gnome", desktopVersionBelow: "45" },
+      apply: { env: { OPENNOW_OZONE_PLATFORM: "x11" } },
+    },
+    {
+      id: "kde-only",
+      description: "kde",
+      match: { desktop: "kde" },
+      apply: { switches: { "disable-gpu-vsync": true } },
+    },
+  ],
+};
+
+test("compares dotted driver versions numerically", () => {
+  assert.ok(compareVersions("535.183.01", "545") < 0);
+  assert.ok(compareVersions("550.54", "545") > 0);
+  assert.equal(compareVersions("45", "45.0"), 0);
+});
+
+test("detects desktop, driver and VA-API details on Linux only", () => {
+  const env = { XDG_CURRENT_DESKTOP: "ubuntu:GNOME", LIBVA_DRIVER_NAME: "NVIDIA" };
+  const environment = detectWorkaroundEnvironment("linux", env, "wayland", (path) => files[path] ?? null);
+  assert.deepEqual(environment, {
+    platform: "linux",
+    displayServer: "wayland",
+    desktops: ["ubuntu", "gnome"],
+    desktopVersion: "44.3",
+    nvidiaDriverVersion: "535.183.01",
+    vaapiDriver: "nvidia",
+  });
+  assert.equal(detectWorkaroundEnvironment("win32", env, null, (path) => files[path] ?? null).nvidiaDriverVersion, null);
+});
+
+test("reports matching rules and honours the override toggles", () => {
+  const environment = detectWorkaroundEnvironment(
+    "linux",
+    { XDG_CURRENT_DESKTOP: "GNOME", LIBVA_DRIVER_NAME: "nvidia" },
+    "wayland",
+    (path) => files[path] ?? null,
+  );
+  const report = buildDriverWorkaroundReport("/tmp/rules.json", ruleSet, environment, {
+    enabled: true,
+    disabledIds: ["gnome-wayland-before-45"],
+  });
+  assert.deepEqual(report.matched.map((workaround) => [workaround.id, workaround.applied]), [
+    ["nvidia-vaapi-zero-copy", true],
+    ["gnome-wayland-before-45", false],
+  ]);
+
+  const disabled = buildDriverWorkaroundReport("/tmp/rules.json", ruleSet, environment, { enabled: false, disabledIds: [] });
+  assert.ok(disabled.matched.every((workaround) => !workaround.applied));
+  assert.equal(disabled.restartRequired, false);
+
+  const launched = new Set(["nvidia-vaapi-zero-copy"]);
+  assert.equal(
+    buildDriverWorkaroundReport("/tmp/rules.json", ruleSet, environment, {
+      enabled: true,
+      disabledIds: ["gnome-wayland-before-45"],
+      appliedAtLaunch: launched,
+    }).restartRequired,
+    false,
+  );
+  assert.equal(
+    buildDriverWorkaroundReport("/tmp/rules.json", ruleSet, environment, {
+      enabled: true,
+      disabledIds: [],
+      appliedAtLaunch: launched,
+    }).restartRequired,
+    true,
+  );
+});
+
+test("reloaded rules replace launch-time variables but never the user's own", () => {
+  const env: NodeJS.ProcessEnv = { OPENNOW_NATIVE_ZERO_COPY: "0", OPENNOW_OZONE_PLATFORM: "wayland" };
+  applyWorkaroundEnv(env, { OPENNOW_OZONE_PLATFORM: "x11" }, new Set(["OPENNOW_NATIVE_ZERO_COPY"]));
+  assert.deepEqual(env, { OPENNOW_OZONE_PLATFORM: "wayland" });
+});
+
+test("unknown versions never satisfy a version bound", () => {
+  const environment = detectWorkaroundEnvironment("linux", { LIBVA_DRIVER_NAME: "nvidia" }, "x11", () => null);
+  const report = buildDriverWorkaroundReport("/tmp/rules.json", ruleSet, environment, { enabled: true, disabledIds: [] });
+  assert.deepEqual(report.matched, []);
+});
+
+test("merges rule actions and drops malformed rules from a rules file", () => {
+  const commandLine = buildWorkaroundCommandLine(ruleSet.rules.slice(0, 2));
+  assert.deepEqual(commandLine.env, { OPENNOW_NATIVE_ZERO_COPY: "0", OPENNOW_OZONE_PLATFORM: "x11" });
+  assert.deepEqual(commandLine.disableFeatures, ["AcceleratedVideoDecodeLinuxZeroCopyGL"]);
+
+  const parsed = parseWorkaroundRuleSet({
+    revision: 2,
+    rules: [ruleSet.rules[0], { id: "broken", description: "no match" }, { id: 3 }],
+  });
+  assert.equal(parsed?.revision, 2);
+  assert.deepEqual(parsed?.rules.map((rule) => rule.id), ["nvidia-vaapi-zero-copy"]);
+  assert.equal(parseWorkaroundRuleSet({ rules: [] }), null);
+});
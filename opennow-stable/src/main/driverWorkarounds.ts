@@ -0,0 +1,304 @@
+import { existsSync, readFileSync, watch } from "node:fs";
+import { join } from "node:path";
+
+import type { DriverWorkaroundReport } from "@shared/gfn";
+import bundledRules from "./driverWorkarounds.json";
+import type { LinuxDisplayServer } from "./linuxDisplayServer";
+
+/**
+ * Local override in the user data folder. Nothing downloads it; a file with a
+ * higher revision placed there by hand replaces the bundled rules as soon as it
+ * is written (see watchWorkaroundRules).
+ */
+export const DRIVER_WORKAROUNDS_FILE_NAME = "driver-workarounds.json";
+
+const NVIDIA_MODULE_VERSION_PATH = "/sys/module/nvidia/version";
+const GNOME_VERSION_PATH = "/usr/share/gnome/gnome-version.xml";
+/** Editors save in several writes; wait for them to settle before re-reading. */
+const RULES_RELOAD_DEBOUNCE_MS = 250;
+
+export interface WorkaroundEnvironment {
+  platform: NodeJS.Platform;
+  displayServer: LinuxDisplayServer | null;
+  /** Lowercased XDG_CURRENT_DESKTOP entries, e.g. ["ubuntu", "gnome"] */
+  desktops: string[];
+  desktopVersion: string | null;
+  nvidiaDriverVersion: string | null;
+  vaapiDriver: string | null;
+}
+
+export interface WorkaroundMatch {
+  platform?: NodeJS.Platform[];
+  displayServer?: LinuxDisplayServer;
+  desktop?: string;
+  desktopVersionBelow?: string;
+  nvidiaDriverBelow?: string;
+  vaapiDriver?: string;
+}
+
+export interface WorkaroundActions {
+  /** Set only when the user has not set the variable themselves */
+  env?: Record<string, string>;
+  enableFeatures?: string[];
+  disableFeatures?: string[];
+  switches?: Record<string, string | true>;
+}
+
+export interface WorkaroundRule {
+  id: string;
+  description: string;
+  match: WorkaroundMatch;
+  apply: WorkaroundActions;
+}
+
+export interface WorkaroundRuleSet {
+  revision: number;
+  rules: WorkaroundRule[];
+}
+
+export interface WorkaroundCommandLine {
+  env: Record<string, string>;
+  enableFeatures: string[];
+  disableFeatures: string[];
+  switches: Record<string, string | true>;
+}
+
+type ReadOptionalFile = (path: string) => string | null;
+
+function readOptionalFile(path: string): string | null {
+  try {
+    return existsSync(path) ? readFileSync(path, "utf-8") : null;
+  } catch {
+    return null;
+  }
+}
+
+/** Compares dotted numeric versions; missing components count as zero. */
+export function compareVersions(a: string, b: string): number {
+  const left = a.split(".").map((part) => Number.parseInt(part, 10) || 0);
+  const right = b.split(".").map((part) => Number.parseInt(part, 10) || 0);
+  for (let index = 0; index < Math.max(left.length, right.length); index += 1) {
+    const diff = (left[index] ?? 0) - (right[index] ?? 0);
+    if (diff !== 0) {
+      return diff;
+    }
+  }
+  return 0;
+}
+
+function detectDesktopVersion(desktops: string[], env: NodeJS.ProcessEnv, readFile: ReadOptionalFile): string | null {
+  if (desktops.includes("gnome")) {
+    const xml = readFile(GNOME_VERSION_PATH);
+    const platform = xml ? /<platform>\s*(\d+)\s*<\/platform>/.exec(xml)?.[1] : undefined;
+    if (platform) {
+      const minor = /<minor>\s*(\d+)\s*<\/minor>/.exec(xml ?? "")?.[1];
+      return minor ? `${platform}.${minor}` : platform;
+    }
+  }
+  if (desktops.includes("kde")) {
+    return env.KDE_SESSION_VERSION?.trim() || null;
+  }
+  return null;
+}
+
+/** Gathers what the rules match on. Runs before app.whenReady(), so it only reads env and sysfs. */
+export function detectWorkaroundEnvironment(
+  platform: NodeJS.Platform,
+  env: NodeJS.ProcessEnv,
+  displayServer: LinuxDisplayServer | null,
+  readFile: ReadOptionalFile = readOptionalFile,
+): WorkaroundEnvironment {
+  if (platform !== "linux") {
+    return { platform, displayServer, desktops: [], desktopVersion: null, nvidiaDriverVersion: null, vaapiDriver: null };
+  }
+  const desktops = (env.XDG_CURRENT_DESKTOP ?? "")
+    .split(":")
+    .map((entry) => entry.trim().toLowerCase())
+    .filter(Boolean);
+  return {
+    platform,
+    displayServer,
+    desktops,
+    desktopVersion: detectDesktopVersion(desktops, env, readFile),
+    nvidiaDriverVersion: readFile(NVIDIA_MODULE_VERSION_PATH)?.trim() || null,
+    vaapiDriver: env.LIBVA_DRIVER_NAME?.trim().toLowerCase() || null,
+  };
+}
+
+function isStringArray(value: unknown): value is string[] {
+  return Array.isArray(value) && value.every((entry) => typeof entry === "string");
+}
+
+function isStringRecord(value: unknown): value is Record<string, string> {
+  return Boolean(value) && typeof value === "object" && Object.values(value as object).every((entry) => typeof entry === "string");
+}
+
+function parseRule(raw: unknown): WorkaroundRule | null {
+  if (!raw || typeof raw !== "object") {
+    return null;
+  }
+  const candidate = raw as Partial<WorkaroundRule>;
+  if (typeof candidate.id !== "string" || !candidate.id || typeof candidate.description !== "string") {
+    return null;
+  }
+  const match = candidate.match && typeof candidate.match === "object" ? candidate.match : null;
+  const apply = candidate.apply && typeof candidate.apply === "object" ? candidate.apply : null;
+  if (!match || !apply) {
+    return null;
+  }
+  if (
+    (apply.env !== undefined && !isStringRecord(apply.env))
+    || (apply.enableFeatures !== undefined && !isStringArray(apply.enableFeatures))
+    || (apply.disableFeatures !== undefined && !isStringArray(apply.disableFeatures))
+    || (match.platform !== undefined && !isStringArray(match.platform))
+  ) {
+    return null;
+  }
+  return { id: candidate.id, description: candidate.description, match, apply };
+}
+
+/** Drops malformed rules instead of rejecting the whole file. */
+export function parseWorkaroundRuleSet(raw: unknown): WorkaroundRuleSet | null {
+  if (!raw || typeof raw !== "object") {
+    return null;
+  }
+  const { revision, rules } = raw as { revision?: unknown; rules?: unknown };
+  if (typeof revision !== "number" || !Array.isArray(rules)) {
+    return null;
+  }
+  return {
+    revision,
+    rules: rules.map(parseRule).filter((rule): rule is WorkaroundRule => rule !== null),
+  };
+}
+
+/** Uses the user data copy when it is newer than the bundled rules. */
+export function loadWorkaroundRules(userDataPath: string): { path: string; ruleSet: WorkaroundRuleSet } {
+  const path = join(userDataPath, DRIVER_WORKAROUNDS_FILE_NAME);
+  const bundled = parseWorkaroundRuleSet(bundledRules) ?? { revision: 0, rules: [] };
+  const source = readOptionalFile(path);
+  if (!source) {
+    return { path, ruleSet: bundled };
+  }
+  try {
+    const local = parseWorkaroundRuleSet(JSON.parse(source));
+    return { path, ruleSet: local && local.revision > bundled.revision ? local : bundled };
+  } catch (error) {
+    console.warn(`[Workarounds] Ignoring unreadable rules file ${path}:`, error);
+    return { path, ruleSet: bundled };
+  }
+}
+
+/**
+ * Calls `onChange` with freshly loaded rules whenever the user data copy is
+ * written, created or removed. The folder is watched rather than the file so
+ * a file placed there after launch is noticed too. Returns a stop function.
+ */
+export function watchWorkaroundRules(
+  userDataPath: string,
+  onChange: (ruleSet: WorkaroundRuleSet) => void,
+): () => void {
+  let timer: ReturnType<typeof setTimeout> | null = null;
+  try {
+    const watcher = watch(userDataPath, (_event, filename) => {
+      if (filename && filename.toString() !== DRIVER_WORKAROUNDS_FILE_NAME) {
+        return;
+      }
+      if (timer) {
+        clearTimeout(timer);
+      }
+      timer = setTimeout(() => {
+        timer = null;
+        onChange(loadWorkaroundRules(userDataPath).ruleSet);
+      }, RULES_RELOAD_DEBOUNCE_MS);
+    });
+    watcher.on("error", (error) => {
+      console.warn("[Workarounds] Stopped watching the rules file:", error);
+    });
+    return () => {
+      if (timer) {
+        clearTimeout(timer);
+      }
+      watcher.close();
+    };
+  } catch (error) {
+    console.warn(`[Workarounds] Cannot watch ${userDataPath} for rule changes:`, error);
+    return () => {};
+  }
+}
+
+function isBelow(actual: string | null, limit: string | undefined): boolean {
+  if (limit === undefined) {
+    return true;
+  }
+  return actual !== null && compareVersions(actual, limit) < 0;
+}
+
+export function ruleMatches(rule: WorkaroundRule, environment: WorkaroundEnvironment): boolean {
+  const { match } = rule;
+  return (match.platform === undefined || match.platform.includes(environment.platform))
+    && (match.displayServer === undefined || match.displayServer === environment.displayServer)
+    && (match.desktop === undefined || environment.desktops.includes(match.desktop.toLowerCase()))
+    && (match.vaapiDriver === undefined || match.vaapiDriver.toLowerCase() === environment.vaapiDriver)
+    && isBelow(environment.desktopVersion, match.desktopVersionBelow)
+    && isBelow(environment.nvidiaDriverVersion, match.nvidiaDriverBelow);
+}
+
+/**
+ * Lists every rule that matches this machine and whether it will be applied.
+ * `appliedAtLaunch` holds the ids whose Chromium switches are in effect; when
+ * the applied set has moved away from it, a restart is needed.
+ */
+export function buildDriverWorkaroundReport(
+  path: string,
+  ruleSet: WorkaroundRuleSet,
+  environment: WorkaroundEnvironment,
+  options: { enabled: boolean; disabledIds: string[]; appliedAtLaunch?: ReadonlySet<string> },
+): DriverWorkaroundReport {
+  const matched = ruleSet.rules
+    .filter((rule) => ruleMatches(rule, environment))
+    .map((rule) => ({
+      id: rule.id,
+      description: rule.description,
+      applied: options.enabled && !options.disabledIds.includes(rule.id),
+    }));
+  const applied = matched.filter((workaround) => workaround.applied).map((workaround) => workaround.id);
+  const launch = options.appliedAtLaunch;
+  return {
+    rulesPath: path,
+    rulesRevision: ruleSet.revision,
+    matched,
+    restartRequired: launch !== undefined
+      && (applied.length !== launch.size || applied.some((id) => !launch.has(id))),
+  };
+}
+
+export function buildWorkaroundCommandLine(rules: WorkaroundRule[]): WorkaroundCommandLine {
+  const commandLine: WorkaroundCommandLine = { env: {}, enableFeatures: [], disableFeatures: [], switches: {} };
+  for (const { apply } of rules) {
+    Object.assign(commandLine.env, apply.env);
+    Object.assign(commandLine.switches, apply.switches);
+    commandLine.enableFeatures.push(...(apply.enableFeatures ?? []));
+    commandLine.disableFeatures.push(...(apply.disableFeatures ?? []));
+  }
+  return commandLine;
+}
+
+/**
+ * Puts the current rules' environment into a native streamer's env. Variables
+ * the launch-time rules injected are dropped first, so a rule that no longer
+ * applies stops reaching new streamer processes; variables the user set
+ * themselves still win.
+ */
+export function applyWorkaroundEnv(
+  env: NodeJS.ProcessEnv,
+  current: Record<string, string>,
+  injectedAtLaunch: ReadonlySet<string>,
+): void {
+  for (const name of injectedAtLaunch) {
+    delete env[name];
+  }
+  for (const [name, value] of Object.entries(current)) {
+    env[name] ??= value;
+  }
+}
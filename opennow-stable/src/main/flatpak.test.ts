@@ -0,0 +1,27 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { isFlatpakSandbox, revealInFileManager } from "./flatpak";
+
+test("detects the sandbox from FLATPAK_ID or the info file", () => {
+  assert.equal(isFlatpakSandbox({ FLATPAK_ID: "com.zortos.opennow.stable" }, () => false), true);
+  assert.equal(isFlatpakSandbox({}, (path) => path === "/.flatpak-info"), true);
+  assert.equal(isFlatpakSandbox({ FLATPAK_ID: " " }, () => false), false);
+});
+
+test("inside the sandbox the containing folder opens instead of selecting the file", async () => {
+  const calls: string[] = [];
+  const shell = {
+    showItemInFolder: (path: string) => void calls.push(`show ${path}`),
+    openPath: async (path: string) => {
+      calls.push(`open ${path}`);
+      return "";
+    },
+  };
+  await revealInFileManager(shell, "/home/ada/Pictures/OpenNOW/Recordings/clip.mp4", false);
+  await revealInFileManager(shell, "/home/ada/Pictures/OpenNOW/Recordings/clip.mp4", true);
+  assert.deepEqual(calls, [
+    "show /home/ada/Pictures/OpenNOW/Recordings/clip.mp4",
+    "open /home/ada/Pictures/OpenNOW/Recordings",
+  ]);
+});
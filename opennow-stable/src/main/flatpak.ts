@@ -0,0 +1,42 @@
+import { existsSync } from "node:fs";
+import { dirname } from "node:path";
+
+/** Flatpak mounts this into every sandbox, including ones that do not export FLATPAK_ID. */
+const FLATPAK_INFO_PATH = "/.flatpak-info";
+
+export function isFlatpakSandbox(
+  env: NodeJS.ProcessEnv = process.env,
+  fileExists: (path: string) => boolean = existsSync,
+): boolean {
+  return Boolean(env.FLATPAK_ID?.trim()) || fileExists(FLATPAK_INFO_PATH);
+}
+
+/**
+ * Chromium only uses the FileChooser portal when it is at least version 3 and
+ * falls back to a GTK dialog otherwise. Inside the sandbox that dialog can only
+ * see the app's own files, so any portal version is better.
+ */
+export const FLATPAK_COMMAND_LINE_SWITCHES: Readonly<Record<string, string>> = {
+  "xdg-portal-required-version": "1",
+};
+
+export interface RevealShell {
+  showItemInFolder(fullPath: string): void;
+  openPath(path: string): Promise<string>;
+}
+
+/**
+ * `showItemInFolder` talks to org.freedesktop.FileManager1, which the sandbox
+ * does not expose. Opening the folder goes through xdg-open, which Flatpak
+ * routes to the OpenURI portal.
+ */
+export async function revealInFileManager(shell: RevealShell, filePath: string, sandboxed: boolean): Promise<void> {
+  if (!sandboxed) {
+    shell.showItemInFolder(filePath);
+    return;
+  }
+  const error = await shell.openPath(dirname(filePath));
+  if (error) {
+    console.warn(`[Flatpak] Failed to open ${dirname(filePath)}: ${error}`);
+  }
+}
@@ -0,0 +1,28 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { buildGamescopeCommandLine, detectGamescopeSession, STEAM_INPUT_DEVICES_ENV } from "./gamescope";
+
+const deck: NodeJS.ProcessEnv = {
+  GAMESCOPE_WAYLAND_DISPLAY: "gamescope-0",
+  SDL_GAMECONTROLLER_IGNORE_DEVICES: "0x28DE/0x1205,0x054C/0x0CE6",
+};
+
+test("auto mode detects gamescope and hands Steam Input pads to the native streamer", () => {
+  assert.equal(detectGamescopeSession("linux", { XDG_CURRENT_DESKTOP: "gamescope" }), true);
+  assert.equal(detectGamescopeSession("win32", deck), false);
+
+  const commandLine = buildGamescopeCommandLine("linux", deck, "auto");
+  assert.deepEqual(commandLine.status, { session: true, steamInputDevices: ["28de:1205", "054c:0ce6"] });
+  assert.deepEqual(commandLine.env, { [STEAM_INPUT_DEVICES_ENV]: "28de:1205,054c:0ce6" });
+});
+
+test("desktop Steam still gets Steam Input handling, and off disables everything", () => {
+  const desktop = buildGamescopeCommandLine("linux", { SDL_GAMECONTROLLER_IGNORE_DEVICES: "0x054c/0x0ce6" }, "auto");
+  assert.equal(desktop.status.session, false);
+  assert.deepEqual(desktop.env, { [STEAM_INPUT_DEVICES_ENV]: "054c:0ce6" });
+
+  const off = buildGamescopeCommandLine("linux", deck, "off");
+  assert.deepEqual(off.status, { session: true, steamInputDevices: [] });
+  assert.deepEqual(off.env, {});
+});
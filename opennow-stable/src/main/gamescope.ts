@@ -0,0 +1,41 @@
+import type { GamescopeCompatibility, GamescopeStatus } from "@shared/gfn";
+import { parseSteamInputDevices } from "@shared/steamInput";
+
+/** Read by the native streamer's DualSense bridge; see dualsense.rs. */
+export const STEAM_INPUT_DEVICES_ENV = "OPENNOW_STEAM_INPUT_DEVICES";
+
+export interface GamescopeCommandLine {
+  status: GamescopeStatus;
+  /** Environment for this process and the native streamer it spawns */
+  env: Record<string, string>;
+}
+
+/**
+ * Steam Deck Game Mode, or any app launched inside a gamescope session. Detection
+ * only drives Steam Input handling: neither path asks gamescope for HDR output or
+ * tearing, since the video sinks come from GStreamer and Chromium and expose no
+ * hook for gamescope's Wayland protocols.
+ */
+export function detectGamescopeSession(platform: NodeJS.Platform, env: NodeJS.ProcessEnv): boolean {
+  if (platform !== "linux") {
+    return false;
+  }
+  return Boolean(env.GAMESCOPE_WAYLAND_DISPLAY?.trim()) || env.XDG_CURRENT_DESKTOP?.trim().toLowerCase() === "gamescope";
+}
+
+export function buildGamescopeCommandLine(
+  platform: NodeJS.Platform,
+  env: NodeJS.ProcessEnv,
+  mode: GamescopeCompatibility,
+): GamescopeCommandLine {
+  const session = detectGamescopeSession(platform, env);
+  // Steam Input is independent of gamescope: desktop Steam sets this for non-Steam games too.
+  const steamInputDevices = mode === "off" ? [] : parseSteamInputDevices(env.SDL_GAMECONTROLLER_IGNORE_DEVICES);
+
+  const childEnv: Record<string, string> = {};
+  if (steamInputDevices.length > 0) {
+    childEnv[STEAM_INPUT_DEVICES_ENV] = steamInputDevices.join(",");
+  }
+
+  return { status: { session, steamInputDevices }, env: childEnv };
+}
@@ -10,6 +10,7 @@ import type {
   GameAccountConnectionsResult,
   GameAccountOperationResult,
 } from "@shared/gfn";
+import { normalizeProviderCode } from "@shared/gfn";
 import { cacheManager } from "../services/cacheManager";
 import { getAccountGamesCacheKeys, getLegacyTokenScopedAccountGamesCacheKeys } from "./games";
 import { buildGfnGraphQlHeaders, GFN_PLAY_ORIGIN, GFN_PLAY_REFERER, GFN_USER_AGENT } from "./clientHeaders";
@@ -95,6 +96,7 @@ interface AccountLinkLoginResult {
 }
 
 let staticDefinitionsCache: { definitions: AppStoreDefinition[]; fetchedAt: number } | null = null;
+const lastConnectionsByUserId = new Map<string, GameAccountConnectionsResult>();
 
 const fallbackDefinitions: AppStoreDefinition[] = [
   {
@@ -159,23 +161,6 @@ const fallbackDefinitions: AppStoreDefinition[] = [
   },
 ];
 
-function normalizeProviderCode(provider: string): string {
-  const normalized = provider.trim().toUpperCase().replace(/[\s-]+/g, "_");
-  switch (normalized) {
-    case "UBISOFT":
-    case "UBISOFT_CONNECT":
-      return "UPLAY";
-    case "BATTLE_NET":
-    case "BLIZZARD":
-      return "BATTLENET";
-    case "EPIC_GAMES":
-    case "EPIC_GAMES_STORE":
-      return "EPIC";
-    default:
-      return normalized;
-  }
-}
-
 function stableHashedUserId(value: string): string {
   return createHash("sha256").update(value).digest("hex");
 }
@@ -357,10 +342,12 @@ async function fetchConnectionsForSession(session: AuthSession): Promise<GameAcc
     fetchUserAccount(session),
   ]);
   const fetchedAt = Date.now();
-  return {
+  const result = {
     accounts: buildConnections(definitions, userAccount?.storesData, fetchedAt),
     fetchedAt,
   };
+  lastConnectionsByUserId.set(session.user.userId, result);
+  return result;
 }
 
 function wait(durationMs: number): Promise<void> {
@@ -644,6 +631,11 @@ export async function fetchGameAccountConnections(session: AuthSession): Promise
   return fetchConnectionsForSession(session);
 }
 
+/** Last connection state fetched for this account, without a network round trip. */
+export function peekCachedGameAccountConnections(session: AuthSession): GameAccountConnectionsResult | null {
+  return lastConnectionsByUserId.get(session.user.userId) ?? null;
+}
+
 export async function linkGameAccount(session: AuthSession, provider: string, proxyUrl?: string): Promise<GameAccountOperationResult> {
   const definition = await resolveProviderDefinition(provider);
   const normalizedProvider = normalizeProviderCode(definition.store);
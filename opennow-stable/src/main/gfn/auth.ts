@@ -1,4 +1,3 @@
-import { createServer } from "node:http";
 import { createHash, randomBytes } from "node:crypto";
 import { access, mkdir, readFile, writeFile } from "node:fs/promises";
 import { dirname } from "node:path";
@@ -14,6 +13,7 @@ import type {
   AuthDeviceLoginPollRequest,
   AuthDeviceLoginPollResult,
   AuthDeviceLoginStartRequest,
+  AuthRevokeResult,
   AuthSession,
   AuthSessionResult,
   AuthTokens,
@@ -23,16 +23,20 @@ import type {
   StreamRegion,
   SubscriptionInfo,
 } from "@shared/gfn";
+import { fetchWithTimeout, withTimeout } from "../services/requestTimeout";
 import {
   buildGfnLcarsHeaders,
   buildNvidiaAuthHeaders,
   GFN_USER_AGENT,
 } from "./clientHeaders";
+import { sendWithRetry } from "./httpClient";
+import { BROWSER_LOGIN_CANCELLED, waitForAuthorizationCode } from "./oauthLoopback";
 import { fetchSubscription, fetchDynamicRegions } from "./subscription";
 
 const SERVICE_URLS_ENDPOINT = "https://pcs.geforcenow.com/v1/serviceUrls";
 const TOKEN_ENDPOINT = "https://login.nvidia.com/token";
 const CLIENT_TOKEN_ENDPOINT = "https://login.nvidia.com/client_token";
+const OPENID_CONFIGURATION_ENDPOINT = "https://login.nvidia.com/.well-known/openid-configuration";
 const USERINFO_ENDPOINT = "https://login.nvidia.com/userinfo";
 const AUTH_ENDPOINT = "https://login.nvidia.com/authorize";
 const DEVICE_AUTHORIZE_ENDPOINT = "https://login.nvidia.com/device/authorize";
@@ -47,6 +51,9 @@ const STEAM_DECK_USER_AGENT =
 const REDIRECT_PORTS = [2259, 6460, 7119, 8870, 9096];
 const TOKEN_REFRESH_WINDOW_MS = 10 * 60 * 1000;
 const CLIENT_TOKEN_REFRESH_WINDOW_MS = 5 * 60 * 1000;
+/** Long enough for 2FA codes and security-key prompts in the browser. */
+const BROWSER_LOGIN_TIMEOUT_MS = 10 * 60 * 1000;
+const REVOCATION_TIMEOUT_MS = 8000;
 
 interface PersistedAuthState {
   sessions: AuthSession[];
@@ -223,7 +230,7 @@ function buildAuthHeadersForClient(
   return headers;
 }
 
-function buildAuthUrl(provider: LoginProvider, challenge: string, port: number): string {
+function buildAuthUrl(provider: LoginProvider, challenge: string, port: number, state: string): string {
   const redirectUri = `http://localhost:${port}`;
   const nonce = randomBytes(16).toString("hex");
   const params = new URLSearchParams({
@@ -234,6 +241,7 @@ function buildAuthUrl(provider: LoginProvider, challenge: string, port: number):
     redirect_uri: redirectUri,
     ui_locales: "en_US",
     nonce,
+    state,
     prompt: "select_account",
     code_challenge: challenge,
     code_challenge_method: "S256",
@@ -263,42 +271,6 @@ async function findAvailablePort(): Promise<number> {
   throw new Error("No available OAuth callback ports");
 }
 
-async function waitForAuthorizationCode(port: number, timeoutMs: number): Promise<string> {
-  return new Promise((resolve, reject) => {
-    const server = createServer((request, response) => {
-      const url = new URL(request.url ?? "/", `http://localhost:${port}`);
-      const code = url.searchParams.get("code");
-      const error = url.searchParams.get("error");
-
-      const html = `<!doctype html><html><head><meta charset="utf-8"><title>OpenNOW Login</title></head><body style="font-family:Segoe UI,Arial,sans-serif;background:#0b1220;color:#dbe7ff;display:flex;justify-content:center;align-items:center;height:100vh"><div style="background:#111a2c;padding:24px 28px;border:1px solid #30425f;border-radius:12px;max-width:460px"><h2 style="margin-top:0">OpenNOW Login</h2><p>${
-        code
-          ? "Login complete. You can close this window and return to OpenNOW Stable."
-          : "Login failed or was cancelled. You can close this window and return to OpenNOW Stable."
-      }</p></div></body></html>`;
-
-      response.statusCode = 200;
-      response.setHeader("Content-Type", "text/html; charset=utf-8");
-      response.end(html);
-
-      server.close(() => {
-        if (code) {
-          resolve(code);
-          return;
-        }
-        reject(new Error(error ?? "Authorization failed"));
-      });
-    });
-
-    server.listen(port, "127.0.0.1", () => {
-      const timer = setTimeout(() => {
-        server.close(() => reject(new Error("Timed out waiting for OAuth callback")));
-      }, timeoutMs);
-
-      server.once("close", () => clearTimeout(timer));
-    });
-  });
-}
-
 async function exchangeAuthorizationCode(code: string, verifier: string, port: number): Promise<AuthTokens> {
   const body = new URLSearchParams({
     grant_type: "authorization_code",
@@ -503,18 +475,79 @@ async function refreshWithClientToken(clientToken: string, userId: string, authC
 }
 
 function mergeTokenSnapshot(base: AuthTokens, refreshed: TokenResponse): AuthTokens {
+  // A rotated client token starts a fresh lifetime; keeping the old expiry would
+  // make the next refresh replace it early, or trust it past its real end.
+  const rotatedClientToken =
+    refreshed.client_token && refreshed.client_token !== base.clientToken ? refreshed.client_token : undefined;
   return {
     accessToken: refreshed.access_token,
     refreshToken: refreshed.refresh_token ?? base.refreshToken,
     idToken: refreshed.id_token,
     expiresAt: toExpiresAt(refreshed.expires_in),
     authClientId: base.authClientId ?? CLIENT_ID,
-    clientToken: refreshed.client_token ?? base.clientToken,
-    clientTokenExpiresAt: base.clientTokenExpiresAt,
+    clientToken: rotatedClientToken ?? base.clientToken,
+    clientTokenExpiresAt: rotatedClientToken
+      ? base.clientTokenLifetimeMs
+        ? Date.now() + base.clientTokenLifetimeMs
+        : undefined
+      : base.clientTokenExpiresAt,
     clientTokenLifetimeMs: base.clientTokenLifetimeMs,
   };
 }
 
+/** The RFC 7009 revocation endpoint the login host advertises, or null when it advertises none. */
+async function discoverRevocationEndpoint(): Promise<string | null> {
+  const response = await fetchWithTimeout(
+    OPENID_CONFIGURATION_ENDPOINT,
+    {},
+    REVOCATION_TIMEOUT_MS,
+    "OpenID configuration request",
+  );
+  if (!response.ok) {
+    throw new Error(`OpenID configuration request failed (${response.status})`);
+  }
+  const payload = await withTimeout(
+    response.json() as Promise<{ revocation_endpoint?: unknown }>,
+    REVOCATION_TIMEOUT_MS,
+    "OpenID configuration response",
+  );
+  return typeof payload.revocation_endpoint === "string" && payload.revocation_endpoint.startsWith("https://")
+    ? payload.revocation_endpoint
+    : null;
+}
+
+/** RFC 7009 revocation. Servers answer 200 for tokens they no longer know, so only transport or 4xx/5xx errors count as failures. */
+async function revokeToken(
+  endpoint: string,
+  token: string,
+  tokenTypeHint: "refresh_token" | "access_token",
+  authClientId = CLIENT_ID,
+): Promise<void> {
+  const body = new URLSearchParams({
+    token,
+    token_type_hint: tokenTypeHint,
+    client_id: authClientId,
+  });
+
+  const response = await fetchWithTimeout(
+    endpoint,
+    {
+      method: "POST",
+      headers: buildAuthHeadersForClient(authClientId, {
+        contentType: "application/x-www-form-urlencoded; charset=UTF-8",
+      }),
+      body,
+    },
+    REVOCATION_TIMEOUT_MS,
+    "Token revocation request",
+  );
+
+  if (!response.ok) {
+    const text = await withTimeout(response.text(), REVOCATION_TIMEOUT_MS, "Token revocation response").catch(() => "");
+    throw new Error(`Token revocation failed (${response.status}): ${text.slice(0, 400)}`);
+  }
+}
+
 function gravatarUrl(email: string, size = 80): string {
   const normalized = email.trim().toLowerCase();
   const hash = createHash("md5").update(normalized).digest("hex");
@@ -546,12 +579,12 @@ async function fetchUserInfo(tokens: AuthTokens): Promise<AuthUser> {
     }
   }
 
-  const response = await fetch(USERINFO_ENDPOINT, {
+  const response = await sendWithRetry(USERINFO_ENDPOINT, {
     headers: buildAuthHeadersForClient(tokens.authClientId, {
       bearerToken: tokens.accessToken,
       accept: "application/json",
     }),
-  });
+  }, fetch);
 
   if (!response.ok) {
     throw new Error(`User info failed (${response.status})`);
@@ -585,6 +618,9 @@ export class AuthService {
   private cachedVpcId: string | null = null;
   private deviceLoginAttempts = new Map<string, DeviceLoginAttempt>();
   private pendingDeviceLoginSessions = new Map<string, AuthSession>();
+  /** Refreshes in flight per account, so a rotated refresh token is never redeemed twice. */
+  private refreshesInFlight = new Map<string, Promise<AuthSessionResult>>();
+  private browserLoginAbort: AbortController | null = null;
 
   constructor(private readonly statePath: string) {}
 
@@ -683,12 +719,12 @@ export class AuthService {
 
     let response: Response;
     try {
-      response = await fetch(SERVICE_URLS_ENDPOINT, {
+      response = await sendWithRetry(SERVICE_URLS_ENDPOINT, {
         headers: {
           Accept: "application/json",
           "User-Agent": GFN_USER_AGENT,
         },
-      });
+      }, fetch);
     } catch (error) {
       console.warn("Failed to fetch providers, using default:", error);
       this.providers = [defaultProvider()];
@@ -842,6 +878,41 @@ export class AuthService {
     await this.persist();
   }
 
+  /**
+   * Signs out locally, then revokes every saved account's refresh and access
+   * tokens with NVIDIA. Local sign-out comes first so a slow or failing login
+   * host never leaves the tokens on disk; the result only reports how many
+   * tokens the server accepted.
+   */
+  async revokeAllSessions(): Promise<AuthRevokeResult> {
+    const savedTokens = [...this.sessions.values()].map((session) => session.tokens);
+    await this.logoutAll();
+
+    const endpoint = discoverRevocationEndpoint().then((url) => {
+      if (!url) {
+        throw new Error("The login service does not advertise a token revocation endpoint");
+      }
+      return url;
+    });
+    // Rejections are reported per token below; this only keeps an account-less call quiet.
+    endpoint.catch(() => undefined);
+    const requests: Promise<void>[] = [];
+    for (const tokens of savedTokens) {
+      if (tokens.refreshToken) {
+        const refreshToken = tokens.refreshToken;
+        requests.push(endpoint.then((url) => revokeToken(url, refreshToken, "refresh_token", tokens.authClientId)));
+      }
+      requests.push(endpoint.then((url) => revokeToken(url, tokens.accessToken, "access_token", tokens.authClientId)));
+    }
+
+    const results = await Promise.allSettled(requests);
+    const failures = results.filter((result): result is PromiseRejectedResult => result.status === "rejected");
+    for (const failure of failures) {
+      console.warn("auth: token revocation failed:", failure.reason);
+    }
+    return { revoked: results.length - failures.length, failed: failures.length };
+  }
+
   getSelectedProvider(): LoginProvider {
     return this.getSession()?.provider ?? this.selectedProvider;
   }
@@ -922,9 +993,7 @@ export class AuthService {
 
     let response: Response;
     try {
-      response = await fetch(`${base}v2/serverInfo`, {
-        headers,
-      });
+      response = await sendWithRetry(`${base}v2/serverInfo`, { headers }, fetch);
     } catch {
       return [];
     }
@@ -950,16 +1019,42 @@ export class AuthService {
     const provider = await this.selectLoginProvider(input.providerIdpId);
 
     const { verifier, challenge } = generatePkce();
+    const state = randomBytes(16).toString("hex");
     const port = await findAvailablePort();
-    const authUrl = buildAuthUrl(provider, challenge, port);
+    const authUrl = buildAuthUrl(provider, challenge, port, state);
 
-    const codePromise = waitForAuthorizationCode(port, 120000);
-    await shell.openExternal(authUrl);
-    const code = await codePromise;
+    // Only one browser sign-in can own the loopback listener at a time.
+    this.browserLoginAbort?.abort();
+    const abort = new AbortController();
+    this.browserLoginAbort = abort;
+    try {
+      const codePromise = waitForAuthorizationCode(port, state, BROWSER_LOGIN_TIMEOUT_MS, abort.signal);
+      try {
+        await shell.openExternal(authUrl);
+      } catch (error) {
+        abort.abort();
+        await codePromise.catch(() => undefined);
+        throw new Error(`Could not open the system browser for sign-in: ${error instanceof Error ? error.message : String(error)}`);
+      }
+      const code = await codePromise;
 
-    const initialTokens = await exchangeAuthorizationCode(code, verifier, port);
-    const session = await this.buildLoginSession(initialTokens, provider);
-    return this.saveLoginSession(session);
+      const initialTokens = await exchangeAuthorizationCode(code, verifier, port);
+      if (abort.signal.aborted) {
+        throw new Error(BROWSER_LOGIN_CANCELLED);
+      }
+      const session = await this.buildLoginSession(initialTokens, provider);
+      return this.saveLoginSession(session);
+    } finally {
+      if (this.browserLoginAbort === abort) {
+        this.browserLoginAbort = null;
+      }
+    }
+  }
+
+  /** Stops waiting for the browser; a redirect that arrives later finds no listener. */
+  cancelLogin(): void {
+    this.browserLoginAbort?.abort();
+    this.browserLoginAbort = null;
   }
 
   async startDeviceLogin(input: AuthDeviceLoginStartRequest): Promise<AuthDeviceLoginChallenge> {
@@ -1121,9 +1216,7 @@ export class AuthService {
     });
 
     try {
-      const response = await fetch(`${base}v2/serverInfo`, {
-        headers,
-      });
+      const response = await sendWithRetry(`${base}v2/serverInfo`, { headers }, fetch);
 
       if (!response.ok) {
         return null;
@@ -1191,6 +1284,36 @@ export class AuthService {
   async ensureValidSessionWithStatus(
     forceRefresh = false,
     expectedUserId?: string,
+  ): Promise<AuthSessionResult> {
+    const userId = this.getSession()?.user.userId;
+    if (!userId) {
+      return this.refreshSessionWithStatus(forceRefresh, expectedUserId);
+    }
+
+    // Refresh tokens may rotate on use. A second caller that started from the
+    // same snapshot would redeem the spent token and then log the account out.
+    // Callers share a refresh of the same kind; a forced and an unforced refresh
+    // run one after the other, so a forced caller never gets a stale result.
+    const key = `${userId}:${forceRefresh ? "forced" : "normal"}`;
+    const inFlight = this.refreshesInFlight.get(key);
+    if (inFlight) {
+      return inFlight;
+    }
+    const other = this.refreshesInFlight.get(`${userId}:${forceRefresh ? "normal" : "forced"}`);
+    const pending: Promise<AuthSessionResult> = (other ? other.catch(() => undefined) : Promise.resolve())
+      .then(() => this.refreshSessionWithStatus(forceRefresh, expectedUserId))
+      .finally(() => {
+        if (this.refreshesInFlight.get(key) === pending) {
+          this.refreshesInFlight.delete(key);
+        }
+      });
+    this.refreshesInFlight.set(key, pending);
+    return pending;
+  }
+
+  private async refreshSessionWithStatus(
+    forceRefresh: boolean,
+    expectedUserId: string | undefined,
   ): Promise<AuthSessionResult> {
     const currentSession = this.getSession();
     if (!currentSession) {
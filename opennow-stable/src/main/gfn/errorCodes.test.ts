@@ -19,3 +19,16 @@ test("SessionError explains insufficient playability as a membership upgrade req
   assert.equal(error.isSessionConflict(), false);
 });
 
+
+test("SessionError carries its category through serialization", () => {
+  const error = SessionError.fromResponse(200, JSON.stringify({
+    requestStatus: {
+      statusCode: 62,
+      statusDescription: "SERVER_SESSION_QUEUE_LENGTH_EXCEEDED",
+    },
+  }));
+
+  assert.equal(error.gfnErrorCode, GfnErrorCode.ServerSessionQueueLengthExceeded);
+  assert.equal(error.category, "capacity");
+  assert.equal(error.toJSON().category, "capacity");
+});
@@ -35,6 +35,7 @@ const DEFAULT_LIBRARY_SORT = "variants.gfn.library.lastPlayedDate:DESC,computedV
 const LIBRARY_GAMES_CACHE_SCOPE = "library:v2";
 const CATALOG_GAMES_CACHE_SCOPE = "catalog";
 const PUBLIC_GAMES_CACHE_KEY = "games:public:v2";
+const APP_METADATA_CACHE_SCOPE = "games:metadata:v1";
 const DEFAULT_CLOUDMATCH_BASE_URL = "https://prod.cloudmatchbeta.nvidiagrid.net/";
 const LIBRARY_APPS_FILTER = {
   variants: {
@@ -60,6 +61,12 @@ function publicGamesCacheKey(proxyUrl?: string): string {
   return proxyCachePart ? `${PUBLIC_GAMES_CACHE_KEY}:${proxyCachePart}` : PUBLIC_GAMES_CACHE_KEY;
 }
 
+function appMetadataCacheKey(vpcId: string, proxyUrl?: string): string {
+  const proxyCachePart = sessionProxyCacheKeyPart(proxyUrl);
+  const key = `${APP_METADATA_CACHE_SCOPE}:${vpcId}:${DEFAULT_LOCALE}`;
+  return proxyCachePart ? `${key}:${proxyCachePart}` : key;
+}
+
 function shouldBypassGamesCache(proxyUrl?: string): boolean {
   return sessionProxyHasCredentials(proxyUrl);
 }
@@ -724,11 +731,17 @@ async function enrichGamesWithMetadata(token: string, vpcId: string, games: Game
     return games;
   }
 
-  const chunkSize = 40;
-  const appById = new Map<string, AppData>();
+  // Metadata is per title, not per list, so the library, panels and refreshes
+  // share one cache and only ask for titles none of them has seen yet.
+  const useCache = !shouldBypassGamesCache(proxyUrl);
+  const cacheKey = appMetadataCacheKey(vpcId, proxyUrl);
+  const cached = useCache ? await cacheManager.loadFromCache<Record<string, AppData>>(cacheKey) : null;
+  const appById = new Map<string, AppData>(Object.entries(cached?.data ?? {}));
+  const missing = uuids.filter((uuid) => !appById.has(uuid));
 
-  for (let index = 0; index < uuids.length; index += chunkSize) {
-    const chunk = uuids.slice(index, index + chunkSize);
+  const chunkSize = 40;
+  for (let index = 0; index < missing.length; index += chunkSize) {
+    const chunk = missing.slice(index, index + chunkSize);
     const payload = await fetchAppMetaData(token, chunk, vpcId, proxyUrl);
     if (payload.errors?.length) {
       throw new Error(payload.errors.map((error) => error.message).join(", "));
@@ -738,6 +751,9 @@ async function enrichGamesWithMetadata(token: string, vpcId: string, games: Game
       appById.set(app.id, app);
     }
   }
+  if (useCache && missing.length > 0) {
+    await cacheManager.saveToCache(cacheKey, Object.fromEntries(appById));
+  }
 
   return dedupeGames(
     games.map((game) => {
@@ -1260,8 +1276,11 @@ export async function fetchLibraryGames(
   providerStreamingBaseUrl?: string,
   accountId?: string,
   proxyUrl?: string,
+  options: { refresh?: boolean } = {},
 ): Promise<GameInfo[]> {
-  const cached = await loadAccountScopedFromCache<GameInfo[]>(LIBRARY_GAMES_CACHE_SCOPE, accountId, token, providerStreamingBaseUrl, proxyUrl);
+  const cached = options.refresh
+    ? null
+    : await loadAccountScopedFromCache<GameInfo[]>(LIBRARY_GAMES_CACHE_SCOPE, accountId, token, providerStreamingBaseUrl, proxyUrl);
   if (cached) {
     return mergePublicGameVariants(cached.data, await fetchPublicGames(proxyUrl));
   }
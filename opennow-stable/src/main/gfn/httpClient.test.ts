@@ -0,0 +1,81 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import {
+  endpointKey,
+  getHttpTelemetrySnapshot,
+  isRetryableMethod,
+  resetHttpTelemetry,
+  retryDelayMs,
+  sendWithRetry,
+} from "./httpClient";
+
+test("honours Retry-After before falling back to jittered backoff", () => {
+  assert.equal(retryDelayMs(1, "2", () => 0.5), 2000);
+  assert.equal(retryDelayMs(1, "120", () => 0.5), 30_000);
+  assert.equal(retryDelayMs(1, new Date(11_000).toUTCString(), () => 0.5, 10_000), 1000);
+  assert.equal(retryDelayMs(1, null, () => 1), 500);
+  assert.equal(retryDelayMs(3, null, () => 1), 2000);
+  assert.equal(retryDelayMs(10, null, () => 1), 8000);
+  assert.equal(retryDelayMs(2, null, () => 0), 0);
+});
+
+test("folds ids out of endpoint keys", () => {
+  assert.equal(
+    endpointKey("https://prod.cloudmatchbeta.nvidiagrid.net/v2/session/3f2a91c0d1?keyboardLayout=en-US"),
+    "prod.cloudmatchbeta.nvidiagrid.net/v2/session/:id",
+  );
+  assert.equal(endpointKey("https://games.geforce.com/graphql?requestType=apps"), "games.geforce.com/graphql");
+});
+
+test("only retries GET and HEAD requests", () => {
+  assert.equal(isRetryableMethod(undefined), true);
+  assert.equal(isRetryableMethod("head"), true);
+  assert.equal(isRetryableMethod("DELETE"), false);
+  assert.equal(isRetryableMethod("PUT"), false);
+  assert.equal(isRetryableMethod("POST"), false);
+});
+
+test("retries rate-limited GETs and records telemetry", async () => {
+  resetHttpTelemetry();
+  const statuses = [429, 200];
+  const seenAgents: Array<string | null> = [];
+  const response = await sendWithRetry("https://example.test/v2/serverInfo", undefined, async (_input, init) => {
+    seenAgents.push(new Headers(init.headers).get("User-Agent"));
+    return new Response(null, { status: statuses.shift(), headers: { "Retry-After": "0" } });
+  });
+
+  assert.equal(response.status, 200);
+  assert.equal(seenAgents.length, 2);
+  assert.ok(seenAgents[0]?.includes("GFN-PC"));
+  const [entry] = getHttpTelemetrySnapshot();
+  assert.equal(entry.endpoint, "example.test/v2/serverInfo");
+  assert.equal(entry.requests, 2);
+  assert.equal(entry.retries, 1);
+  assert.equal(entry.rateLimited, 1);
+  assert.equal(entry.lastStatus, 200);
+});
+
+test("stops waiting to retry when the request is aborted", async () => {
+  resetHttpTelemetry();
+  const controller = new AbortController();
+  let calls = 0;
+  const pending = sendWithRetry("https://example.test/v2/serverInfo", { signal: controller.signal }, async () => {
+    calls += 1;
+    setTimeout(() => controller.abort(new Error("cancelled")), 0);
+    return new Response(null, { status: 503, headers: { "Retry-After": "30" } });
+  });
+  await assert.rejects(pending, /cancelled/);
+  assert.equal(calls, 1);
+});
+
+test("does not retry POSTs", async () => {
+  resetHttpTelemetry();
+  let calls = 0;
+  const response = await sendWithRetry("https://example.test/v2/session", { method: "POST" }, async () => {
+    calls += 1;
+    return new Response(null, { status: 503 });
+  });
+  assert.equal(response.status, 503);
+  assert.equal(calls, 1);
+});
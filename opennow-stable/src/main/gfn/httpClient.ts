@@ -0,0 +1,200 @@
+import { GFN_USER_AGENT } from "./clientHeaders";
+
+const MAX_ATTEMPTS = 3;
+const BACKOFF_BASE_MS = 500;
+const BACKOFF_CAP_MS = 8_000;
+const RETRY_AFTER_CAP_MS = 30_000;
+const LATENCY_SAMPLE_LIMIT = 50;
+
+/** Reads only; a retried PUT or DELETE can stop or change a session that already went through. */
+const RETRYABLE_METHODS = new Set(["GET", "HEAD"]);
+const RETRYABLE_STATUSES = new Set([429, 502, 503, 504]);
+
+export interface HttpEndpointTelemetry {
+  endpoint: string;
+  requests: number;
+  failures: number;
+  retries: number;
+  rateLimited: number;
+  lastStatus: number | null;
+  avgLatencyMs: number;
+  p95LatencyMs: number;
+}
+
+interface EndpointRecord {
+  requests: number;
+  failures: number;
+  retries: number;
+  rateLimited: number;
+  lastStatus: number | null;
+  latencies: number[];
+}
+
+const endpointRecords = new Map<string, EndpointRecord>();
+
+export function isRetryableMethod(method?: string): boolean {
+  return RETRYABLE_METHODS.has((method ?? "GET").toUpperCase());
+}
+
+export function isRetryableStatus(status: number): boolean {
+  return RETRYABLE_STATUSES.has(status);
+}
+
+/**
+ * Delay before retry `attempt` (1-based). A server Retry-After wins when
+ * present; otherwise full-jitter exponential backoff so parallel catalog
+ * requests do not retry in lockstep.
+ */
+export function retryDelayMs(
+  attempt: number,
+  retryAfter: string | null,
+  random: () => number = Math.random,
+  now: number = Date.now(),
+): number {
+  if (retryAfter) {
+    const seconds = Number(retryAfter);
+    const fromHeader = Number.isFinite(seconds) ? seconds * 1000 : Date.parse(retryAfter) - now;
+    if (Number.isFinite(fromHeader) && fromHeader >= 0) {
+      return Math.min(fromHeader, RETRY_AFTER_CAP_MS);
+    }
+  }
+  const ceiling = Math.min(BACKOFF_CAP_MS, BACKOFF_BASE_MS * 2 ** (attempt - 1));
+  return Math.round(random() * ceiling);
+}
+
+/** Groups URLs by host and path, folding ids and query strings so metrics stay bounded. */
+export function endpointKey(input: string): string {
+  try {
+    const url = new URL(input);
+    const path = url.pathname
+      .split("/")
+      .map((segment) => (/\d/.test(segment) && segment.length >= 6 ? ":id" : segment))
+      .join("/");
+    return `${url.host}${path}`;
+  } catch {
+    return "invalid-url";
+  }
+}
+
+function recordFor(endpoint: string): EndpointRecord {
+  let record = endpointRecords.get(endpoint);
+  if (!record) {
+    record = { requests: 0, failures: 0, retries: 0, rateLimited: 0, lastStatus: null, latencies: [] };
+    endpointRecords.set(endpoint, record);
+  }
+  return record;
+}
+
+function recordAttempt(endpoint: string, latencyMs: number, status: number | null): void {
+  const record = recordFor(endpoint);
+  record.requests += 1;
+  record.lastStatus = status;
+  if (status === null || status >= 500) record.failures += 1;
+  if (status === 429) record.rateLimited += 1;
+  record.latencies.push(latencyMs);
+  if (record.latencies.length > LATENCY_SAMPLE_LIMIT) {
+    record.latencies.shift();
+  }
+}
+
+export function getHttpTelemetrySnapshot(): HttpEndpointTelemetry[] {
+  return [...endpointRecords.entries()]
+    .map(([endpoint, record]) => {
+      const sorted = [...record.latencies].sort((a, b) => a - b);
+      const total = sorted.reduce((sum, value) => sum + value, 0);
+      return {
+        endpoint,
+        requests: record.requests,
+        failures: record.failures,
+        retries: record.retries,
+        rateLimited: record.rateLimited,
+        lastStatus: record.lastStatus,
+        avgLatencyMs: sorted.length > 0 ? Math.round(total / sorted.length) : 0,
+        p95LatencyMs: sorted.length > 0 ? sorted[Math.min(sorted.length - 1, Math.ceil(sorted.length * 0.95) - 1)] : 0,
+      };
+    })
+    .sort((a, b) => b.requests - a.requests);
+}
+
+export function resetHttpTelemetry(): void {
+  endpointRecords.clear();
+}
+
+export function formatHttpTelemetry(snapshot: HttpEndpointTelemetry[]): string {
+  if (snapshot.length === 0) {
+    return "HTTP endpoints: no requests recorded";
+  }
+  const lines = snapshot.map(
+    (entry) =>
+      `${entry.endpoint} n=${entry.requests} fail=${entry.failures} retry=${entry.retries} 429=${entry.rateLimited} ` +
+      `last=${entry.lastStatus ?? "error"} avg=${entry.avgLatencyMs}ms p95=${entry.p95LatencyMs}ms`,
+  );
+  return ["HTTP endpoints:", ...lines].join("\n");
+}
+
+function withDefaultUserAgent(init: RequestInit | undefined): RequestInit {
+  const headers = new Headers(init?.headers);
+  if (!headers.has("User-Agent")) {
+    headers.set("User-Agent", GFN_USER_AGENT);
+  }
+  return { ...init, headers };
+}
+
+/** Rejects with the signal's reason as soon as it aborts, so a cancelled request is not held for the backoff. */
+function sleep(ms: number, signal?: AbortSignal | null): Promise<void> {
+  return new Promise((resolve, reject) => {
+    if (signal?.aborted) {
+      reject(signal.reason);
+      return;
+    }
+    const onAbort = (): void => {
+      clearTimeout(timer);
+      reject(signal?.reason);
+    };
+    const timer = setTimeout(() => {
+      signal?.removeEventListener("abort", onAbort);
+      resolve();
+    }, ms);
+    signal?.addEventListener("abort", onAbort, { once: true });
+  });
+}
+
+/**
+ * Runs `send` with the shared User-Agent, records per-endpoint latency, and
+ * retries GET and HEAD requests on network errors, 429 and 5xx gateway errors.
+ * Connection reuse comes from the underlying fetch (undici or the Electron
+ * session), so callers should keep using one of those rather than new agents.
+ */
+export async function sendWithRetry(
+  input: string,
+  init: RequestInit | undefined,
+  send: (input: string, init: RequestInit) => Promise<Response>,
+): Promise<Response> {
+  const endpoint = endpointKey(input);
+  const requestInit = withDefaultUserAgent(init);
+  const attempts = isRetryableMethod(requestInit.method) ? MAX_ATTEMPTS : 1;
+
+  for (let attempt = 1; ; attempt += 1) {
+    const startedAt = performance.now();
+    let response: Response;
+    try {
+      response = await send(input, requestInit);
+    } catch (error) {
+      recordAttempt(endpoint, performance.now() - startedAt, null);
+      if (attempt >= attempts || requestInit.signal?.aborted) throw error;
+      recordFor(endpoint).retries += 1;
+      await sleep(retryDelayMs(attempt, null), requestInit.signal);
+      continue;
+    }
+
+    recordAttempt(endpoint, performance.now() - startedAt, response.status);
+    if (!isRetryableStatus(response.status) || attempt >= attempts || requestInit.signal?.aborted) {
+      return response;
+    }
+    recordFor(endpoint).retries += 1;
+    const delay = retryDelayMs(attempt, response.headers.get("retry-after"));
+    console.warn(`[HTTP] ${endpoint} returned ${response.status}; retrying in ${delay}ms (attempt ${attempt + 1}/${attempts})`);
+    await response.body?.cancel().catch(() => undefined);
+    await sleep(delay, requestInit.signal);
+  }
+}
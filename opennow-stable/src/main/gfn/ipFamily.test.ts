@@ -0,0 +1,34 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { iceCandidateFamily, isIceCandidateAllowed, signalingHostWithPort, socketFamilyOptions } from "./ipFamily";
+
+const v4Candidate = "candidate:1 1 udp 2122260223 192.0.2.10 49006 typ host generation 0";
+const v6Candidate = "candidate:2 1 udp 2122262783 2001:db8::10 49007 typ host generation 0";
+const mdnsCandidate = "candidate:3 1 udp 2122194687 0b6c3e1f-7f3d-4d8a.local 49008 typ host";
+
+test("adds the default port without mangling IPv6 signaling hosts", () => {
+  assert.equal(signalingHostWithPort("np-ams-06.cloudmatchbeta.nvidiagrid.net"), "np-ams-06.cloudmatchbeta.nvidiagrid.net:443");
+  assert.equal(signalingHostWithPort("203.0.113.5:49100"), "203.0.113.5:49100");
+  assert.equal(signalingHostWithPort("2001:db8::1"), "[2001:db8::1]:443");
+  assert.equal(signalingHostWithPort("[2001:db8::1]"), "[2001:db8::1]:443");
+  assert.equal(signalingHostWithPort("[2001:db8::1]:8443"), "[2001:db8::1]:8443");
+});
+
+test("reads the address family from ICE candidate lines", () => {
+  assert.equal(iceCandidateFamily(v4Candidate), 4);
+  assert.equal(iceCandidateFamily(v6Candidate), 6);
+  assert.equal(iceCandidateFamily(mdnsCandidate), null);
+});
+
+test("filters ICE candidates only when a family is forced", () => {
+  assert.equal(isIceCandidateAllowed(v6Candidate, "auto"), true);
+  assert.equal(isIceCandidateAllowed(v6Candidate, "ipv4"), false);
+  assert.equal(isIceCandidateAllowed(v4Candidate, "ipv6"), false);
+  assert.equal(isIceCandidateAllowed(mdnsCandidate, "ipv4"), true);
+});
+
+test("uses happy eyeballs unless a family is forced", () => {
+  assert.deepEqual(socketFamilyOptions("ipv6"), { family: 6 });
+  assert.equal(socketFamilyOptions("auto").autoSelectFamily, true);
+});
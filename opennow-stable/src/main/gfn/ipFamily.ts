@@ -0,0 +1,55 @@
+import { isIP } from "node:net";
+
+import type { NetworkIpFamily } from "@shared/gfn";
+
+// Matches the Chromium/Happy Eyeballs v2 default of starting the second
+// address family shortly after the first instead of waiting for a timeout.
+const HAPPY_EYEBALLS_ATTEMPT_DELAY_MS = 250;
+
+export interface SocketFamilyOptions {
+  family?: 4 | 6;
+  autoSelectFamily?: boolean;
+  autoSelectFamilyAttemptTimeout?: number;
+}
+
+/** Socket options for `net.connect`-based clients (ws, https). */
+export function socketFamilyOptions(family: NetworkIpFamily): SocketFamilyOptions {
+  switch (family) {
+    case "ipv4":
+      return { family: 4 };
+    case "ipv6":
+      return { family: 6 };
+    default:
+      return { autoSelectFamily: true, autoSelectFamilyAttemptTimeout: HAPPY_EYEBALLS_ATTEMPT_DELAY_MS };
+  }
+}
+
+/**
+ * Adds the default wss port to a bare signaling server. Unbracketed IPv6
+ * literals are bracketed first so their colons are not mistaken for a port.
+ */
+export function signalingHostWithPort(server: string): string {
+  const trimmed = server.trim();
+  if (isIP(trimmed) === 6) {
+    return `[${trimmed}]:443`;
+  }
+  if (trimmed.startsWith("[")) {
+    return trimmed.includes("]:") ? trimmed : `${trimmed}:443`;
+  }
+  return trimmed.includes(":") ? trimmed : `${trimmed}:443`;
+}
+
+/** Address family of an ICE candidate line, or null for mDNS/unparseable hosts. */
+export function iceCandidateFamily(candidate: string): 4 | 6 | null {
+  const address = candidate.trim().split(/\s+/)[4];
+  if (!address) return null;
+  const family = isIP(address);
+  return family === 4 || family === 6 ? family : null;
+}
+
+export function isIceCandidateAllowed(candidate: string, family: NetworkIpFamily): boolean {
+  if (family === "auto") return true;
+  const candidateFamily = iceCandidateFamily(candidate);
+  if (candidateFamily === null) return true;
+  return candidateFamily === (family === "ipv4" ? 4 : 6);
+}
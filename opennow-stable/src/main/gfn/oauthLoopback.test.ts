@@ -0,0 +1,35 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { BROWSER_LOGIN_CANCELLED, readLoopbackCallback, waitForAuthorizationCode } from "./oauthLoopback";
+
+test("the redirect with a matching state yields the code", () => {
+  assert.deepEqual(readLoopbackCallback("/?code=abc&state=s1", "s1"), { kind: "code", code: "abc" });
+});
+
+test("favicon requests and bare hits keep the listener waiting", () => {
+  assert.deepEqual(readLoopbackCallback("/favicon.ico", "s1"), { kind: "ignore" });
+  assert.deepEqual(readLoopbackCallback("/", "s1"), { kind: "ignore" });
+});
+
+test("a redirect for another request is rejected", () => {
+  const callback = readLoopbackCallback("/?code=abc&state=other", "s1");
+  assert.equal(callback.kind, "error");
+});
+
+test("a cancelled browser sign-in reads as a cancellation", () => {
+  const callback = readLoopbackCallback("/?error=access_denied&state=s1", "s1");
+  assert.deepEqual(callback, { kind: "error", message: "Sign-in was cancelled or denied in the browser." });
+});
+
+test("unknown errors carry the server's description", () => {
+  const callback = readLoopbackCallback("/?error=invalid_scope&error_description=Scope%20not%20allowed&state=s1", "s1");
+  assert.deepEqual(callback, { kind: "error", message: "Sign-in failed: Scope not allowed" });
+});
+
+test("aborting the wait rejects and frees the port", async () => {
+  const abort = new AbortController();
+  const pending = waitForAuthorizationCode(0, "s1", 60_000, abort.signal);
+  abort.abort();
+  await assert.rejects(pending, { message: BROWSER_LOGIN_CANCELLED });
+});
@@ -0,0 +1,122 @@
+import { createServer } from "node:http";
+
+/**
+ * Loopback redirect handling for the system-browser sign-in (RFC 8252 §7.3).
+ * The whole NVIDIA login, including 2FA codes and security-key prompts, runs
+ * in the user's browser; the app only waits here for the final redirect.
+ */
+
+export type LoopbackCallback =
+  | { kind: "code"; code: string }
+  | { kind: "error"; message: string }
+  | { kind: "ignore" };
+
+export const BROWSER_LOGIN_CANCELLED = "Sign-in was cancelled.";
+
+/** OAuth error codes a person can cause from the browser, worded for them rather than for a log. */
+export function authorizationErrorMessage(error: string, description?: string | null): string {
+  switch (error) {
+    case "access_denied":
+      return "Sign-in was cancelled or denied in the browser.";
+    case "login_required":
+    case "interaction_required":
+    case "consent_required":
+      return "NVIDIA needs you to finish signing in. Try again and complete every step in the browser.";
+    case "temporarily_unavailable":
+    case "server_error":
+      return "NVIDIA sign-in is temporarily unavailable. Try again in a moment.";
+    default:
+      return description ? `Sign-in failed: ${description}` : `Sign-in failed (${error}).`;
+  }
+}
+
+/**
+ * Browsers also ask the listener for favicons and may replay the redirect,
+ * so only a request to `/` that carries a code or an error ends the wait.
+ */
+export function readLoopbackCallback(requestUrl: string, expectedState: string): LoopbackCallback {
+  const url = new URL(requestUrl, "http://localhost");
+  if (url.pathname !== "/") {
+    return { kind: "ignore" };
+  }
+
+  const code = url.searchParams.get("code");
+  const error = url.searchParams.get("error");
+  if (!code && !error) {
+    return { kind: "ignore" };
+  }
+  if (url.searchParams.get("state") !== expectedState) {
+    return { kind: "error", message: "Sign-in response did not match this request. Start sign-in again." };
+  }
+  if (error) {
+    return { kind: "error", message: authorizationErrorMessage(error, url.searchParams.get("error_description")) };
+  }
+  return { kind: "code", code: code! };
+}
+
+function escapeHtml(text: string): string {
+  return text.replace(/[&<>"']/g, (char) => `&#${char.charCodeAt(0)};`);
+}
+
+function callbackPage(message: string): string {
+  return `<!doctype html><html><head><meta charset="utf-8"><title>OpenNOW Login</title></head><body style="font-family:Segoe UI,Arial,sans-serif;background:#0b1220;color:#dbe7ff;display:flex;justify-content:center;align-items:center;height:100vh"><div style="background:#111a2c;padding:24px 28px;border:1px solid #30425f;border-radius:12px;max-width:460px"><h2 style="margin-top:0">OpenNOW Login</h2><p>${escapeHtml(
+    message,
+  )}</p></div></body></html>`;
+}
+
+export function waitForAuthorizationCode(
+  port: number,
+  expectedState: string,
+  timeoutMs: number,
+  signal?: AbortSignal,
+): Promise<string> {
+  return new Promise((resolve, reject) => {
+    let settled = false;
+    const finish = (outcome: () => void): void => {
+      if (settled) {
+        return;
+      }
+      settled = true;
+      clearTimeout(timer);
+      signal?.removeEventListener("abort", onAbort);
+      server.close();
+      outcome();
+    };
+
+    const server = createServer((request, response) => {
+      const callback = readLoopbackCallback(request.url ?? "/", expectedState);
+      if (callback.kind === "ignore") {
+        response.statusCode = 404;
+        response.end();
+        return;
+      }
+
+      response.statusCode = 200;
+      response.setHeader("Content-Type", "text/html; charset=utf-8");
+      response.setHeader("Connection", "close");
+      response.end(
+        callbackPage(
+          callback.kind === "code"
+            ? "Login complete. You can close this window and return to OpenNOW Stable."
+            : `${callback.message} You can close this window and return to OpenNOW Stable.`,
+        ),
+      );
+
+      finish(() => (callback.kind === "code" ? resolve(callback.code) : reject(new Error(callback.message))));
+    });
+
+    const onAbort = (): void => finish(() => reject(new Error(BROWSER_LOGIN_CANCELLED)));
+    const timer = setTimeout(
+      () => finish(() => reject(new Error("Timed out waiting for the browser to finish sign-in."))),
+      timeoutMs,
+    );
+
+    if (signal?.aborted) {
+      onAbort();
+      return;
+    }
+    signal?.addEventListener("abort", onAbort, { once: true });
+    server.once("error", (error) => finish(() => reject(error)));
+    server.listen(port, "127.0.0.1");
+  });
+}
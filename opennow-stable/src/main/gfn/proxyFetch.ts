@@ -1,3 +1,4 @@
+import { sendWithRetry } from "./httpClient";
 import { normalizeSessionProxyUrl, sessionProxyPartitionForUrl } from "./proxyUrl";
 
 type ElectronSessionWithFetch = Electron.Session & {
@@ -11,7 +12,7 @@ export async function fetchWithOptionalProxy(
 ): Promise<Response> {
   const normalizedProxyUrl = normalizeSessionProxyUrl(proxyUrl);
   if (!normalizedProxyUrl) {
-    return fetch(input, init);
+    return sendWithRetry(input, init, fetch);
   }
 
   const { session: electronSession } = await import("electron");
@@ -19,7 +20,8 @@ export async function fetchWithOptionalProxy(
   await proxySession.setProxy({ proxyRules: normalizedProxyUrl });
 
   if (typeof proxySession.fetch === "function") {
-    return proxySession.fetch(input, init);
+    const sessionFetch = proxySession.fetch.bind(proxySession);
+    return sendWithRetry(input, init, sessionFetch);
   }
 
   throw new Error("Electron session fetch is unavailable for session proxy requests.");
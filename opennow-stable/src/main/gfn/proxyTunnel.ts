@@ -0,0 +1,273 @@
+import net, { isIP } from "node:net";
+import tls from "node:tls";
+
+import type { Settings } from "@shared/gfn";
+
+import type { SocketFamilyOptions } from "./ipFamily";
+import { normalizeSessionProxyUrl } from "./proxyUrl";
+
+const PROXY_CONNECT_TIMEOUT_MS = 10_000;
+const PROXY_HANDSHAKE_TIMEOUT_MS = 10_000;
+
+/**
+ * Picks the proxy a CLI tool would use for `targetUrl` from the usual
+ * environment variables, honouring NO_PROXY. Returns null when none applies.
+ */
+export function proxyFromEnvironment(targetUrl: string, env: NodeJS.ProcessEnv = process.env): string | null {
+  let target: URL;
+  try {
+    target = new URL(targetUrl);
+  } catch {
+    return null;
+  }
+
+  const secure = target.protocol === "https:" || target.protocol === "wss:";
+  const candidates = secure
+    ? [env.HTTPS_PROXY, env.https_proxy, env.ALL_PROXY, env.all_proxy]
+    : [env.HTTP_PROXY, env.http_proxy, env.ALL_PROXY, env.all_proxy];
+  const proxy = candidates.find((value) => value?.trim())?.trim();
+  if (!proxy) return null;
+
+  const noProxy = env.NO_PROXY ?? env.no_proxy ?? "";
+  return isNoProxyHost(target.hostname, noProxy) ? null : proxy;
+}
+
+/**
+ * Proxy for the signaling websocket: the session proxy when one is set,
+ * otherwise whatever the environment points `targetUrl` at. Only explicit
+ * URLs are used; PAC files and OS proxy settings are not consulted. An
+ * environment proxy that cannot be used throws rather than going direct.
+ */
+export function resolveSignalingProxyUrl(
+  settings: Pick<Settings, "signalingProxyEnabled" | "sessionProxyEnabled" | "sessionProxyUrl">,
+  targetUrl: string,
+  env: NodeJS.ProcessEnv = process.env,
+): string | null {
+  if (!settings.signalingProxyEnabled) return null;
+  const configured = settings.sessionProxyEnabled ? normalizeSessionProxyUrl(settings.sessionProxyUrl) : null;
+  if (configured) return configured;
+  const fromEnvironment = proxyFromEnvironment(targetUrl, env);
+  if (!fromEnvironment) return null;
+  if (/^socks5h:/i.test(fromEnvironment)) {
+    throw new Error("socks5h:// proxies are not supported for signaling; use socks5://, which also lets the proxy resolve hostnames.");
+  }
+  try {
+    return normalizeSessionProxyUrl(fromEnvironment);
+  } catch {
+    throw new Error("The HTTPS_PROXY/ALL_PROXY proxy cannot tunnel signaling. Use http://, https:// or socks5:// with a host and port.");
+  }
+}
+
+export function isNoProxyHost(hostname: string, noProxy: string): boolean {
+  const host = hostname.replace(/^\[|\]$/g, "").toLowerCase();
+  return noProxy
+    .split(/[\s,]+/)
+    .map((entry) => entry.trim().toLowerCase())
+    .filter(Boolean)
+    .some((entry) => {
+      if (entry === "*") return true;
+      const domain = entry.replace(/^\*?\./, "");
+      const bare = isIP(domain) ? domain : domain.replace(/:\d+$/, "").replace(/^\[|\]$/g, "");
+      return host === bare || host.endsWith(`.${bare}`);
+    });
+}
+
+export function buildHttpConnectRequest(targetHost: string, targetPort: number, proxy: URL): string {
+  const authority = isIP(targetHost) === 6 ? `[${targetHost}]:${targetPort}` : `${targetHost}:${targetPort}`;
+  const lines = [`CONNECT ${authority} HTTP/1.1`, `Host: ${authority}`];
+  if (proxy.username) {
+    const credentials = `${decodeURIComponent(proxy.username)}:${decodeURIComponent(proxy.password)}`;
+    lines.push(`Proxy-Authorization: Basic ${Buffer.from(credentials).toString("base64")}`);
+  }
+  return `${lines.join("\r\n")}\r\n\r\n`;
+}
+
+/** Status code from the first line of a CONNECT response, or null if malformed. */
+export function parseHttpConnectStatus(head: string): number | null {
+  const match = /^HTTP\/1\.[01] (\d{3})/.exec(head);
+  return match ? Number(match[1]) : null;
+}
+
+export function buildSocks5Greeting(withCredentials: boolean): Buffer {
+  return withCredentials ? Buffer.from([0x05, 0x02, 0x00, 0x02]) : Buffer.from([0x05, 0x01, 0x00]);
+}
+
+export function buildSocks5Auth(username: string, password: string): Buffer {
+  const user = Buffer.from(username);
+  const pass = Buffer.from(password);
+  return Buffer.concat([Buffer.from([0x01, user.length]), user, Buffer.from([pass.length]), pass]);
+}
+
+/** CONNECT request; hostnames are sent as-is so the proxy resolves them (socks5h semantics). */
+export function buildSocks5Connect(targetHost: string, targetPort: number): Buffer {
+  const family = isIP(targetHost);
+  let address: Buffer;
+  if (family === 4) {
+    address = Buffer.from([0x01, ...targetHost.split(".").map(Number)]);
+  } else if (family === 6) {
+    address = Buffer.concat([Buffer.from([0x04]), ipv6ToBytes(targetHost)]);
+  } else {
+    const name = Buffer.from(targetHost);
+    address = Buffer.concat([Buffer.from([0x03, name.length]), name]);
+  }
+  const port = Buffer.alloc(2);
+  port.writeUInt16BE(targetPort);
+  return Buffer.concat([Buffer.from([0x05, 0x01, 0x00]), address, port]);
+}
+
+/** Total length of a SOCKS5 CONNECT reply, or null until enough bytes have arrived. */
+export function socks5ReplyLength(reply: Buffer): number | null {
+  if (reply.length < 5) return null;
+  switch (reply[3]) {
+    case 0x01:
+      return 4 + 4 + 2;
+    case 0x04:
+      return 4 + 16 + 2;
+    case 0x03:
+      return 4 + 1 + reply[4] + 2;
+    default:
+      return 4;
+  }
+}
+
+function ipv6ToBytes(address: string): Buffer {
+  const [head, tail = ""] = address.split("::");
+  const headParts = head ? head.split(":") : [];
+  const tailParts = tail ? tail.split(":") : [];
+  const groups = address.includes("::")
+    ? [...headParts, ...Array<string>(8 - headParts.length - tailParts.length).fill("0"), ...tailParts]
+    : headParts;
+  const bytes = Buffer.alloc(16);
+  groups.forEach((group, index) => bytes.writeUInt16BE(parseInt(group, 16), index * 2));
+  return bytes;
+}
+
+/** Reads from `socket` until `complete` returns the byte count it needs. */
+function readHandshake(socket: net.Socket, complete: (data: Buffer) => number | null): Promise<Buffer> {
+  return new Promise((resolve, reject) => {
+    let buffered = Buffer.alloc(0);
+    const cleanup = (): void => {
+      socket.off("data", onData);
+      socket.off("error", onError);
+      socket.off("close", onClose);
+      socket.pause();
+    };
+    const onData = (chunk: Buffer): void => {
+      buffered = Buffer.concat([buffered, chunk]);
+      const needed = complete(buffered);
+      if (needed !== null && buffered.length >= needed) {
+        cleanup();
+        if (buffered.length > needed) {
+          socket.unshift(buffered.subarray(needed));
+        }
+        resolve(buffered.subarray(0, needed));
+      }
+    };
+    const onError = (error: Error): void => {
+      cleanup();
+      reject(error);
+    };
+    const onClose = (): void => {
+      cleanup();
+      reject(new Error("Proxy closed the connection during the handshake"));
+    };
+    socket.on("data", onData);
+    socket.once("error", onError);
+    socket.once("close", onClose);
+  });
+}
+
+function connectToProxy(proxy: URL, socketOptions: SocketFamilyOptions): Promise<net.Socket> {
+  return new Promise((resolve, reject) => {
+    const host = proxy.hostname.replace(/^\[|\]$/g, "");
+    const port = Number(proxy.port);
+    const socket =
+      proxy.protocol === "https:"
+        ? tls.connect({ ...socketOptions, host, port, servername: isIP(host) ? undefined : host })
+        : net.connect({ ...socketOptions, host, port });
+    // A proxy that drops SYNs would otherwise leave this pending until the OS gives up.
+    const timer = setTimeout(() => {
+      socket.destroy(new Error(`Timed out connecting to proxy ${proxy.host}`));
+    }, PROXY_CONNECT_TIMEOUT_MS);
+    const onError = (error: Error): void => {
+      clearTimeout(timer);
+      reject(error);
+    };
+    socket.once("error", onError);
+    socket.once(proxy.protocol === "https:" ? "secureConnect" : "connect", () => {
+      clearTimeout(timer);
+      socket.off("error", onError);
+      resolve(socket);
+    });
+  });
+}
+
+async function httpConnect(socket: net.Socket, proxy: URL, host: string, port: number): Promise<void> {
+  socket.write(buildHttpConnectRequest(host, port, proxy));
+  const response = await readHandshake(socket, (data) => {
+    const end = data.indexOf("\r\n\r\n");
+    return end === -1 ? null : end + 4;
+  });
+  const status = parseHttpConnectStatus(response.toString("latin1"));
+  if (status !== 200) {
+    throw new Error(`Proxy refused CONNECT to ${host}:${port} (status ${status ?? "unknown"})`);
+  }
+}
+
+async function socks5Connect(socket: net.Socket, proxy: URL, host: string, port: number): Promise<void> {
+  const withCredentials = proxy.username.length > 0;
+  socket.write(buildSocks5Greeting(withCredentials));
+  const [, method] = await readHandshake(socket, () => 2);
+  if (method === 0x02 && withCredentials) {
+    socket.write(buildSocks5Auth(decodeURIComponent(proxy.username), decodeURIComponent(proxy.password)));
+    const [, status] = await readHandshake(socket, () => 2);
+    if (status !== 0x00) {
+      throw new Error("SOCKS5 proxy rejected the credentials");
+    }
+  } else if (method !== 0x00) {
+    throw new Error("SOCKS5 proxy offered no supported authentication method");
+  }
+
+  socket.write(buildSocks5Connect(host, port));
+  const reply = await readHandshake(socket, socks5ReplyLength);
+  if (reply[1] !== 0x00) {
+    throw new Error(`SOCKS5 proxy refused CONNECT to ${host}:${port} (reply ${reply[1]})`);
+  }
+}
+
+/**
+ * Opens a TLS connection to `host:port` through an HTTP(S) CONNECT or SOCKS5
+ * proxy. The returned socket can be handed to ws as its `createConnection`.
+ */
+export async function openProxyTunnel(
+  proxyUrl: string,
+  host: string,
+  port: number,
+  socketOptions: SocketFamilyOptions,
+  tlsOptions: tls.ConnectionOptions = {},
+): Promise<tls.TLSSocket> {
+  const proxy = new URL(proxyUrl);
+  if (proxy.protocol === "socks4:") {
+    throw new Error("SOCKS4 proxies cannot tunnel signaling; use socks5:// or http://");
+  }
+
+  const socket = await connectToProxy(proxy, socketOptions);
+  socket.setTimeout(PROXY_HANDSHAKE_TIMEOUT_MS, () => socket.destroy(new Error("Proxy handshake timed out")));
+  try {
+    if (proxy.protocol === "socks5:") {
+      await socks5Connect(socket, proxy, host, port);
+    } else {
+      await httpConnect(socket, proxy, host, port);
+    }
+  } catch (error) {
+    socket.destroy();
+    throw error;
+  }
+  socket.setTimeout(0);
+
+  return tls.connect({
+    ...tlsOptions,
+    socket,
+    servername: isIP(host) ? undefined : host,
+  });
+}
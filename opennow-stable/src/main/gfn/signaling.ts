@@ -1,4 +1,5 @@
 import { randomBytes } from "node:crypto";
+import type { TLSSocket } from "node:tls";
 
 import WebSocket from "ws";
 
@@ -6,9 +7,13 @@ import type {
   IceCandidatePayload,
   KeyframeRequest,
   MainToRendererSignalingEvent,
+  NetworkIpFamily,
   SendAnswerRequest,
 } from "@shared/gfn";
 
+import { isIceCandidateAllowed, signalingHostWithPort, socketFamilyOptions } from "./ipFamily";
+import { openProxyTunnel } from "./proxyTunnel";
+
 const USER_AGENT =
   "Mozilla/5.0 (Windows NT 10.0; Win64; x64) Chrome/131.0.0.0 Safari/537.36";
 
@@ -42,12 +47,12 @@ export class GfnSignalingClient {
     private readonly signalingServer: string,
     private readonly sessionId: string,
     private readonly signalingUrl?: string,
+    private readonly ipFamily: NetworkIpFamily = "auto",
+    private readonly proxyUrl: string | null = null,
   ) {}
 
   private buildSignInUrl(): string {
-    const fallbackHost = this.signalingServer.includes(":")
-      ? this.signalingServer
-      : `${this.signalingServer}:443`;
+    const fallbackHost = signalingHostWithPort(this.signalingServer);
     const baseUrl = this.signalingUrl?.trim() || `wss://${fallbackHost}/nvst/`;
     const signInUrl = new URL(baseUrl);
 
@@ -118,6 +123,23 @@ export class GfnSignalingClient {
     });
   }
 
+  private async openTunnel(url: string, proxyUrl: string): Promise<TLSSocket> {
+    const target = new URL(url);
+    console.log("[Signaling] Tunneling through proxy:", new URL(proxyUrl).host);
+    try {
+      return await openProxyTunnel(
+        proxyUrl,
+        target.hostname.replace(/^\[|\]$/g, ""),
+        Number(target.port || 443),
+        socketFamilyOptions(this.ipFamily),
+        { rejectUnauthorized: false },
+      );
+    } catch (error) {
+      this.emit({ type: "error", message: `Signaling proxy failed: ${String(error)}` });
+      throw error;
+    }
+  }
+
   async connect(): Promise<void> {
     if (this.ws && this.ws.readyState === WebSocket.OPEN) {
       return;
@@ -131,11 +153,22 @@ export class GfnSignalingClient {
     console.log("[Signaling] Session ID:", this.sessionId);
     console.log("[Signaling] Protocol:", protocol);
 
+    const tunnel = this.proxyUrl ? await this.openTunnel(url, this.proxyUrl) : null;
+    if (tunnel && generation !== this.connectionGeneration) {
+      tunnel.destroy();
+      return;
+    }
+
     await new Promise<void>((resolve, reject) => {
       // Extract host:port for the Host header (matching Rust behavior)
       const urlHost = url.replace(/^wss?:\/\//, "").split("/")[0];
 
-      const ws = new WebSocket(url, protocol, {
+      // ws forwards unknown options to net.connect, which is where
+      // autoSelectFamily (happy eyeballs) and a forced family take effect.
+      // With a proxy the TLS socket is already tunneled, so ws just reuses it.
+      const options: WebSocket.ClientOptions & ReturnType<typeof socketFamilyOptions> = {
+        ...socketFamilyOptions(this.ipFamily),
+        ...(tunnel ? { createConnection: () => tunnel } : {}),
         rejectUnauthorized: false,
         headers: {
           Host: urlHost,
@@ -143,7 +176,8 @@ export class GfnSignalingClient {
           "User-Agent": USER_AGENT,
           "Sec-WebSocket-Key": randomBytes(16).toString("base64"),
         },
-      });
+      };
+      const ws = new WebSocket(url, protocol, options);
 
       this.ws = ws;
 
@@ -256,6 +290,10 @@ export class GfnSignalingClient {
     }
 
     if (typeof peerPayload.candidate === "string") {
+      if (!isIceCandidateAllowed(peerPayload.candidate, this.ipFamily)) {
+        console.log(`[Signaling] Ignoring remote ICE candidate outside forced ${this.ipFamily}: ${peerPayload.candidate}`);
+        return;
+      }
       const sdpMLineIndex =
         typeof peerPayload.sdpMLineIndex === "number" || peerPayload.sdpMLineIndex === null
           ? peerPayload.sdpMLineIndex
@@ -314,6 +352,10 @@ export class GfnSignalingClient {
       console.log(`[Signaling] Dropping TCP local ICE candidate: ${candidate.candidate}`);
       return;
     }
+    if (!isIceCandidateAllowed(candidate.candidate, this.ipFamily)) {
+      console.log(`[Signaling] Dropping local ICE candidate outside forced ${this.ipFamily}: ${candidate.candidate}`);
+      return;
+    }
 
     console.log(`[Signaling] Sending local ICE candidate: ${candidate.candidate} (sdpMid=${candidate.sdpMid})`);
     console.log(`[Signaling] Sending ICE peer_msg from=${this.peerId} to=${this.remotePeerId}`);
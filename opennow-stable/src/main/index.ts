@@ -2,12 +2,16 @@ import {
   app,
   BrowserWindow,
   clipboard,
+  crashReporter,
   ipcMain,
   dialog,
+  nativeTheme,
+  powerMonitor,
   shell,
   systemPreferences,
   session,
   protocol,
+  screen,
 } from "electron";
 import { fileURLToPath } from "node:url";
 import { dirname, join } from "node:path";
@@ -20,10 +24,18 @@ import { existsSync, readFileSync } from "node:fs";
 // F8  - Toggle mouse/pointer lock (handled in main process via IPC)
 
 import { IPC_CHANNELS } from "@shared/ipc";
-import { registerOpenNowMediaProtocol } from "./mediaPaths";
-import { initLogCapture, exportLogs } from "@shared/logger";
+import { registerOpenNowMediaProtocol, setMediaRootDirectory, wasMediaDirectoryPicked } from "./mediaPaths";
+import { initLogCapture, exportLogs, formatLogEntry, getLogCapture, parseLogFilter, tailLogs } from "@shared/logger";
 import { cacheManager } from "./services/cacheManager";
 import { refreshScheduler } from "./services/refreshScheduler";
+import { taskSupervisor } from "./services/taskSupervisor";
+import {
+  appendDiagnosticsSections,
+  formatCacheStats,
+  formatSessionTimeline,
+  formatTaskHealth,
+  type CacheStats,
+} from "./services/diagnosticsExport";
 import { cacheEventBus } from "./services/cacheEventBus";
 import {
   fetchMainGamesUncached,
@@ -34,6 +46,10 @@ import type {
   AppUpdaterState,
   SessionConflictChoice,
   Settings,
+  SessionTelemetryReport,
+  SessionTimeline,
+  StorageCategory,
+  StorageCategoryUsage,
   DirectLaunchRequest,
   PingResult,
   StreamRegion,
@@ -41,12 +57,26 @@ import type {
   ThankYouContributor,
   ThankYouDataResult,
   ThankYouSupporter,
+  UserTheme,
+  DriverWorkaroundReport,
+  GamescopeStatus,
+  RouteTraceResult,
+  PathMtuResult,
+  SystemUsageSample,
+  LogTailResult,
 } from "@shared/gfn";
 
-import { getSettingsManager, type SettingsManager } from "./settings";
-
-import { getActiveSessions } from "./gfn/cloudmatch";
+import {
+  getSettingsManager,
+  normalizeAudioBufferMs,
+  normalizeGamescopeCompatibility,
+  type SettingsManager,
+  type Settings as StoredSettings,
+} from "./settings";
+
+import { getActiveSessions, stopSession } from "./gfn/cloudmatch";
 import { AuthService } from "./gfn/auth";
+import { formatHttpTelemetry, getHttpTelemetrySnapshot } from "./gfn/httpClient";
 import {
   connectDiscordRpc,
   setActivity,
@@ -71,51 +101,207 @@ import {
   isSessionConflictError,
   showSessionConflictDialog as showSessionConflictDialogWithDeps,
 } from "./session/sessionConflict";
+import { isPermissionAllowed } from "./privacyGuard";
+import { ActiveSessionRegistry } from "./session/sessionRegistry";
 import { fetchWithTimeout, withTimeout } from "./services/requestTimeout";
 import {
   fetchPrintedWasteQueue,
   fetchPrintedWasteServerMapping,
+  resolvePrintedWasteSource,
 } from "./services/printedWaste";
 import { pingRegions } from "./services/regionPing";
+import { formatRouteTrace, traceRoute } from "./services/routeTrace";
+import { sampleSystemUsage } from "./services/systemUsage";
+import {
+  clearDirectory,
+  diskCacheSizeSwitch,
+  measureStorage,
+  trimDirectoryToBytes,
+  type StorageTargets,
+} from "./services/storageUsage";
+import { activeRecordingPaths, getRecordingsDirectory } from "./media/recordings";
+import { getThumbnailCacheDirectory } from "./media/thumbnails";
+import { probePathMtu } from "./services/pathMtu";
+import {
+  addSessionToTelemetry,
+  readTelemetryAggregate,
+  resolveTelemetryPath,
+  telemetryGpuFromInfo,
+  uploadPendingTelemetry,
+  writeTelemetryAggregate,
+} from "./services/sessionTelemetry";
 import {
   buildVideoAccelerationCommandLine,
   isAccelerationPreference,
   type BootstrapVideoPreferences,
 } from "./videoAcceleration";
+import { buildLinuxDisplayServerCommandLine, detectLinuxDisplayServer } from "./linuxDisplayServer";
+import { FLATPAK_COMMAND_LINE_SWITCHES, isFlatpakSandbox } from "./flatpak";
+import { buildGamescopeCommandLine } from "./gamescope";
+import {
+  applyWorkaroundEnv,
+  buildDriverWorkaroundReport,
+  buildWorkaroundCommandLine,
+  detectWorkaroundEnvironment,
+  loadWorkaroundRules,
+  watchWorkaroundRules,
+} from "./driverWorkarounds";
+import { buildAudioLatencyCommandLine } from "./audioLatency";
+import { loadUserTheme } from "./userTheme";
+import {
+  PinAttemptLimiter,
+  hashParentalPin,
+  loadPinAttemptState,
+  savePinAttemptState,
+  verifyParentalPin,
+} from "./parentalPin";
+import {
+  buildCrashIssueUrl,
+  isCrashProcessGoneReason,
+  listPendingCrashReports,
+  markCrashReportsReported,
+  readCrashReport,
+  resolveCrashReportsDirectory,
+  writeCrashReport,
+} from "./crashReports";
+import { NetworkChangeMonitor } from "./networkMonitor";
+import {
+  getAppLogsDirectory,
+  planPathMigrations,
+  resolveAppPathLayout,
+  runPathMigrations,
+  setAppPathLayout,
+} from "./appPaths";
+import {
+  findDisplayById,
+  findDisplayForRect,
+  MINI_PLAYER_MIN_WIDTH,
+  resolveMiniPlayerBounds,
+  resolveWindowPlacement,
+  toDisplayInfo,
+  type PlacementDisplay,
+} from "./windowPlacement";
 import { parseDirectLaunchArgs, type DirectLaunchArgs } from "@shared/directLaunch";
+import { parseUdpPortRange } from "@shared/iceConfig";
+import { isParentalProtectedSetting, isValidParentalPin } from "@shared/parentalControls";
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = dirname(__filename);
 
-// Configure Chromium video and WebRTC behavior before app.whenReady().
+// Config, cache and log locations have to settle before the crash reporter
+// starts or anything reads userData.
+const defaultUserData = app.getPath("userData");
+const appPathLayout = resolveAppPathLayout({
+  platform: process.platform,
+  argv: process.argv,
+  env: process.env,
+  home: app.getPath("home"),
+  executableDir: dirname(app.getPath("exe")),
+  defaultUserData,
+  fileExists: existsSync,
+});
+runPathMigrations(planPathMigrations(appPathLayout, defaultUserData));
+if (appPathLayout.mode !== "default") {
+  app.setPath("userData", appPathLayout.config);
+  app.setPath("logs", appPathLayout.logs);
+  app.setPath("crashDumps", join(appPathLayout.logs, "crash-dumps"));
+  app.commandLine.appendSwitch("disk-cache-dir", join(appPathLayout.cache, "http"));
+}
+setAppPathLayout(appPathLayout);
+console.log(
+  `[Main] App paths (${appPathLayout.mode}): config=${appPathLayout.config} cache=${appPathLayout.cache} logs=${appPathLayout.logs}`,
+);
 
-function loadBootstrapVideoPreferences(): BootstrapVideoPreferences {
-  const defaults: BootstrapVideoPreferences = {
-    decoderPreference: "auto",
-    encoderPreference: "auto",
+// Electron minidumps stay local in app.getPath("crashDumps"); nothing is uploaded.
+crashReporter.start({ uploadToServer: false });
+
+function recordCrash(source: string, reason: string, details?: string): void {
+  const logs = getLogCapture()?.getEntries().map(formatLogEntry) ?? [];
+  const path = writeCrashReport(resolveCrashReportsDirectory(getAppLogsDirectory()), {
+    source,
+    reason,
+    appVersion: getAppBuildInfo().displayVersion,
+    details,
+    logs,
+  });
+  if (path) {
+    console.error(`[CrashReports] Crash report written to ${path}`);
+  }
+}
+
+process.on("uncaughtException", (error) => {
+  console.error("[Main] Uncaught exception:", error);
+  // Electron keeps main running after an uncaught exception; stay up so a live stream is not cut off.
+  recordCrash("main", `uncaught exception: ${error.message}`, error.stack);
+});
+
+app.on("render-process-gone", (_event, _webContents, details) => {
+  if (isCrashProcessGoneReason(details.reason)) {
+    recordCrash("renderer", `renderer ${details.reason} (exit code ${details.exitCode})`);
+  }
+});
+
+app.on("child-process-gone", (_event, details) => {
+  if (isCrashProcessGoneReason(details.reason)) {
+    const name = details.name ?? details.serviceName ?? details.type;
+    recordCrash("child", `${name} ${details.reason} (exit code ${details.exitCode})`);
+  }
+});
+
+/** Offers reports left by a previous run once, then moves them out of the pending folder. */
+async function offerPendingCrashReports(): Promise<void> {
+  const directory = resolveCrashReportsDirectory(getAppLogsDirectory());
+  const reports = listPendingCrashReports(directory);
+  if (reports.length === 0) {
+    return;
+  }
+
+  const options: Electron.MessageBoxOptions = {
+    type: "warning",
+    buttons: ["Report on GitHub", "Open Crash Reports", "Dismiss"],
+    defaultId: 0,
+    cancelId: 2,
+    title: "OpenNOW Crashed",
+    message: "OpenNOW closed unexpectedly last time.",
+    detail:
+      "A crash report with a backtrace and recent logs was saved. Reporting it on GitHub opens a pre-filled issue you can review before submitting.",
   };
+  const result = mainWindow && !mainWindow.isDestroyed()
+    ? await dialog.showMessageBox(mainWindow, options)
+    : await dialog.showMessageBox(options);
+
+  if (result.response === 0) {
+    await shell.openExternal(buildCrashIssueUrl(readCrashReport(reports[0]), reports.length));
+  } else if (result.response === 1) {
+    await shell.openPath(directory);
+  }
+  markCrashReportsReported(directory, reports);
+}
+
+// Configure Chromium video and WebRTC behavior before app.whenReady().
+
+function loadBootstrapSettings(): Record<string, unknown> {
   try {
     const settingsPath = join(app.getPath("userData"), "settings.json");
     if (!existsSync(settingsPath)) {
-      return defaults;
+      return {};
     }
-    const parsed = JSON.parse(
-      readFileSync(settingsPath, "utf-8"),
-    ) as Partial<BootstrapVideoPreferences>;
-    return {
-      decoderPreference: isAccelerationPreference(parsed.decoderPreference)
-        ? parsed.decoderPreference
-        : defaults.decoderPreference,
-      encoderPreference: isAccelerationPreference(parsed.encoderPreference)
-        ? parsed.encoderPreference
-        : defaults.encoderPreference,
-    };
+    const parsed: unknown = JSON.parse(readFileSync(settingsPath, "utf-8"));
+    return parsed && typeof parsed === "object" ? (parsed as Record<string, unknown>) : {};
   } catch {
-    return defaults;
+    return {};
   }
 }
 
-const bootstrapVideoPrefs = loadBootstrapVideoPreferences();
+function loadBootstrapVideoPreferences(parsed: Record<string, unknown>): BootstrapVideoPreferences {
+  return {
+    decoderPreference: isAccelerationPreference(parsed.decoderPreference) ? parsed.decoderPreference : "auto",
+    encoderPreference: isAccelerationPreference(parsed.encoderPreference) ? parsed.encoderPreference : "auto",
+  };
+}
+
+const bootstrapSettings = loadBootstrapSettings();
+const bootstrapVideoPrefs = loadBootstrapVideoPreferences(bootstrapSettings);
 console.log(
   `[Main] Video acceleration preference: decode=${bootstrapVideoPrefs.decoderPreference}, encode=${bootstrapVideoPrefs.encoderPreference}`,
 );
@@ -126,12 +312,82 @@ const videoAccelerationCommandLine = buildVideoAccelerationCommandLine(
   process.arch,
 );
 
+// Workarounds are matched against the detected display server and may then override it,
+// so their environment has to land before the display server command line is built.
+const driverWorkaroundRules = loadWorkaroundRules(app.getPath("userData"));
+const driverWorkaroundEnvironment = detectWorkaroundEnvironment(
+  process.platform,
+  process.env,
+  detectLinuxDisplayServer(process.platform, process.env),
+);
+const driverWorkaroundReport: DriverWorkaroundReport = buildDriverWorkaroundReport(
+  driverWorkaroundRules.path,
+  driverWorkaroundRules.ruleSet,
+  driverWorkaroundEnvironment,
+  {
+    enabled: bootstrapSettings.driverWorkaroundsEnabled !== false,
+    disabledIds: Array.isArray(bootstrapSettings.disabledDriverWorkarounds)
+      ? bootstrapSettings.disabledDriverWorkarounds.filter((id): id is string => typeof id === "string")
+      : [],
+  },
+);
+const appliedWorkaroundIds = new Set(
+  driverWorkaroundReport.matched.filter((workaround) => workaround.applied).map((workaround) => workaround.id),
+);
+const driverWorkaroundCommandLine = buildWorkaroundCommandLine(
+  driverWorkaroundRules.ruleSet.rules.filter((rule) => appliedWorkaroundIds.has(rule.id)),
+);
+if (appliedWorkaroundIds.size > 0) {
+  console.log(`[Main] Driver workarounds applied: ${[...appliedWorkaroundIds].join(", ")}`);
+}
+// Remembered so a rules reload can take these back out of a new native streamer's env.
+const injectedWorkaroundEnv = new Set<string>();
+for (const [name, value] of Object.entries(driverWorkaroundCommandLine.env)) {
+  if (process.env[name] === undefined) {
+    process.env[name] = value;
+    injectedWorkaroundEnv.add(name);
+  }
+}
+// Replaced when the rules file changes while the app runs.
+let driverWorkaroundRuleSet = driverWorkaroundRules.ruleSet;
+let stopWatchingWorkaroundRules: (() => void) | null = null;
+
+const gamescopeCommandLine = buildGamescopeCommandLine(
+  process.platform,
+  process.env,
+  normalizeGamescopeCompatibility(bootstrapSettings.gamescopeCompatibility),
+);
+if (gamescopeCommandLine.status.session) {
+  console.log("[Main] Running inside gamescope.");
+}
+if (gamescopeCommandLine.status.steamInputDevices.length > 0) {
+  console.log(`[Main] Steam Input manages: ${gamescopeCommandLine.status.steamInputDevices.join(", ")}`);
+}
+for (const [name, value] of Object.entries(gamescopeCommandLine.env)) {
+  process.env[name] ??= value;
+}
+
+const linuxDisplayServerCommandLine = buildLinuxDisplayServerCommandLine(
+  process.platform,
+  process.env,
+);
+if (linuxDisplayServerCommandLine.displayServer) {
+  console.log(`[Main] Linux display server: ${linuxDisplayServerCommandLine.displayServer}`);
+}
+
 app.commandLine.appendSwitch(
   "enable-features",
-  videoAccelerationCommandLine.enableFeatures.join(","),
+  [
+    ...videoAccelerationCommandLine.enableFeatures,
+    ...linuxDisplayServerCommandLine.enableFeatures,
+    ...driverWorkaroundCommandLine.enableFeatures,
+  ].filter((feature) => !driverWorkaroundCommandLine.disableFeatures.includes(feature)).join(","),
 );
 
-app.commandLine.appendSwitch("disable-features", videoAccelerationCommandLine.disableFeatures.join(","));
+app.commandLine.appendSwitch(
+  "disable-features",
+  [...videoAccelerationCommandLine.disableFeatures, ...driverWorkaroundCommandLine.disableFeatures].join(","),
+);
 
 app.commandLine.appendSwitch(
   "force-fieldtrials",
@@ -141,7 +397,21 @@ app.commandLine.appendSwitch(
   ].join("/"),
 );
 
-for (const [name, value] of Object.entries(videoAccelerationCommandLine.switches)) {
+// Audio buffer changes take effect on the next launch.
+const audioLatencyCommandLine = buildAudioLatencyCommandLine(
+  normalizeAudioBufferMs(bootstrapSettings.audioBufferMs),
+  process.platform,
+);
+for (const [name, value] of Object.entries(audioLatencyCommandLine.env)) {
+  process.env[name] ??= value;
+}
+
+for (const [name, value] of Object.entries({
+  ...videoAccelerationCommandLine.switches,
+  ...linuxDisplayServerCommandLine.switches,
+  ...audioLatencyCommandLine.switches,
+  ...driverWorkaroundCommandLine.switches,
+})) {
   if (value === true) {
     app.commandLine.appendSwitch(name);
   } else {
@@ -157,6 +427,17 @@ app.commandLine.appendSwitch("disable-renderer-backgrounding");
 app.commandLine.appendSwitch("disable-backgrounding-occluded-windows");
 // Remove getUserMedia FPS cap (not strictly needed for receive-only but avoids potential limits)
 app.commandLine.appendSwitch("max-gum-fps", "999");
+// Chromium evicts its HTTP cache (mostly box art) past this size; changes apply on the next launch.
+const diskCacheSize = diskCacheSizeSwitch(bootstrapSettings.diskCacheLimitMb);
+if (diskCacheSize) {
+  app.commandLine.appendSwitch("disk-cache-size", diskCacheSize);
+}
+if (isFlatpakSandbox()) {
+  console.log("[Main] Running inside Flatpak; file dialogs go through the FileChooser portal.");
+  for (const [name, value] of Object.entries(FLATPAK_COMMAND_LINE_SWITCHES)) {
+    app.commandLine.appendSwitch(name, value);
+  }
+}
 
 // file:// in &lt;video&gt; is blocked by Chromium for renderer pages; use a privileged custom scheme.
 protocol.registerSchemesAsPrivileged([
@@ -174,20 +455,36 @@ protocol.registerSchemesAsPrivileged([
 
 let mainWindow: BrowserWindow | null = null;
 let rendererControlledFullscreen = false;
+/** Window state to restore when the mini player closes; null while the window is at normal size. */
+let miniPlayerRestore: {
+  bounds: Electron.Rectangle;
+  fullscreen: boolean;
+  maximized: boolean;
+  minimumSize: number[];
+} | null = null;
+const networkChangeMonitor = new NetworkChangeMonitor();
 let signalingCoordinator: SignalingCoordinator | null = null;
 let authService: AuthService;
 let settingsManager: SettingsManager;
 let appUpdater: AppUpdaterController | null = null;
 const EXPLICIT_SHUTDOWN_FORCE_EXIT_DELAY_MS = 2000;
+// Kept under the force-exit delay so a slow stop request never outlives the app.
+const SESSION_STOP_ON_EXIT_TIMEOUT_MS = 1500;
+const activeSessionRegistry = new ActiveSessionRegistry(join(app.getPath("userData"), "active-session.json"));
+let lastRouteTrace: RouteTraceResult | null = null;
+let lastSessionTimeline: SessionTimeline | null = null;
+let routeTraceInFlight: Promise<RouteTraceResult> | null = null;
 let isShutdownRequested = false;
 let isShutdownCleanupComplete = false;
 let isUpdaterInstallQuitInProgress = false;
 let explicitShutdownFallbackTimer: NodeJS.Timeout | null = null;
+let windowPlacementSaveTimer: NodeJS.Timeout | null = null;
 let directLaunchRequestSequence = 0;
 let pendingDirectLaunchRequest: DirectLaunchRequest | null = createDirectLaunchRequestFromArgv(process.argv);
 
 // Runtime pointer-lock state (updated by renderer)
 let isPointerLockActiveRuntime = false;
+let isStreamActiveRuntime = false;
 
 function createDirectLaunchRequest(args: DirectLaunchArgs): DirectLaunchRequest {
   return {
@@ -239,6 +536,9 @@ function runShutdownCleanup(reason = "app-quit"): void {
   console.log(`[Main] Running shutdown cleanup (${reason})`);
 
   refreshScheduler.stop();
+  taskSupervisor.stopAll();
+  stopWatchingWorkaroundRules?.();
+  stopWatchingWorkaroundRules = null;
   // Parity with soft-reset behavior: on full app quit, let process teardown close
   // signaling sockets naturally instead of emitting an explicit disconnect event
   // into the renderer during shutdown.
@@ -327,90 +627,284 @@ function requestAppShutdown(
  * reflects the user's actual game session state.
  */
 class DiscordStatusMonitor {
-  private timer: NodeJS.Timeout | null = null;
+  private readonly taskName = "discord-presence";
   private readonly intervalMs = 60 * 1000;
-  private isSyncing = false;
   private hasPerformedInitialSync = false;
 
   start(): void {
-    if (this.timer) return;
-    this.timer = setInterval(() => void this.sync(), this.intervalMs);
-    void this.sync();
+    if (taskSupervisor.has(this.taskName)) return;
+    taskSupervisor.spawn({
+      name: this.taskName,
+      intervalMs: this.intervalMs,
+      runImmediately: true,
+      run: () => this.sync(),
+    });
   }
 
   stop(): void {
-    if (this.timer) {
-      clearInterval(this.timer);
-      this.timer = null;
-    }
+    taskSupervisor.stop(this.taskName);
   }
 
   async sync(): Promise<void> {
-    if (this.isSyncing) return;
-    this.isSyncing = true;
-
-    try {
-      if (!settingsManager.get("discordRichPresence")) {
-        this.stop();
-        void clearActivity();
-        return;
-      }
+    if (!settingsManager.get("discordRichPresence")) {
+      this.stop();
+      void clearActivity();
+      return;
+    }
 
-      if (!isDiscordRpcConnected()) {
-        await connectDiscordRpc().catch(() => {});
-      }
+    if (!isDiscordRpcConnected()) {
+      await connectDiscordRpc().catch(() => {});
+    }
 
-      // On first run, always clear regardless of auth state — the app just started
-      // and any stale status from the previous session must be wiped.
-      if (!this.hasPerformedInitialSync) {
-        console.log("[DiscordRPC] Startup: clearing any stale Discord status.");
-        await clearActivity().catch(() => {});
-        this.hasPerformedInitialSync = true;
-      }
+    // On first run, always clear regardless of auth state — the app just started
+    // and any stale status from the previous session must be wiped.
+    if (!this.hasPerformedInitialSync) {
+      console.log("[DiscordRPC] Startup: clearing any stale Discord status.");
+      await clearActivity().catch(() => {});
+      this.hasPerformedInitialSync = true;
+    }
 
-      const token = await resolveJwt().catch(() => null);
-      if (!token) return;
+    const token = await resolveJwt().catch(() => null);
+    if (!token) return;
 
-      const provider = authService.getSelectedProvider();
-      const streamingBaseUrl = provider.streamingServiceUrl;
-      const activeSessions = await getActiveSessions(
-        token,
-        streamingBaseUrl,
-      ).catch(() => []);
+    const provider = authService.getSelectedProvider();
+    const streamingBaseUrl = provider.streamingServiceUrl;
+    const activeSessions = await getActiveSessions(
+      token,
+      streamingBaseUrl,
+    ).catch(() => []);
 
-      const activeSession = activeSessions.find((s) =>
-        [1, 2, 3].includes(s.status),
-      );
-      const currentActivity = getCurrentActivity();
+    const activeSession = activeSessions.find((s) =>
+      [1, 2, 3].includes(s.status),
+    );
+    const currentActivity = getCurrentActivity();
 
-      if (activeSession) {
-        const sessionAppId = activeSession.appId.toString();
+    if (activeSession) {
+      const sessionAppId = activeSession.appId.toString();
 
-        if (!currentActivity || currentActivity.appId !== sessionAppId) {
-          const title = sessionAppId;
-          const startTime = new Date();
-          void setActivity(title, startTime, sessionAppId);
-        }
-      } else if (currentActivity) {
-        console.log("[DiscordRPC] Monitor clearing stale status.");
-        void clearActivity();
+      if (!currentActivity || currentActivity.appId !== sessionAppId) {
+        const title = sessionAppId;
+        const startTime = new Date();
+        void setActivity(title, startTime, sessionAppId);
       }
-    } catch (err) {
-      console.warn("[DiscordRPC] Monitor sync failed:", (err as Error).message);
-    } finally {
-      this.isSyncing = false;
+    } else if (currentActivity) {
+      console.log("[DiscordRPC] Monitor clearing stale status.");
+      void clearActivity();
     }
   }
 }
 
 const discordMonitor = new DiscordStatusMonitor();
 
+const PIN_ATTEMPT_STATE_FILENAME = "parental-pin-attempts.json";
+
+/** The renderer only learns whether a parental PIN exists; the hash never leaves main. */
+function toRendererSettings(stored: StoredSettings): Settings {
+  const { parentalPinHash, ...settings } = stored;
+  return { ...settings, parentalPinSet: parentalPinHash !== "" };
+}
+
+/** The rules as they stand now, with the toggles as they are saved now. */
+function currentDriverWorkaroundReport(settingsManager: SettingsManager): DriverWorkaroundReport {
+  return buildDriverWorkaroundReport(driverWorkaroundRules.path, driverWorkaroundRuleSet, driverWorkaroundEnvironment, {
+    enabled: settingsManager.get("driverWorkaroundsEnabled"),
+    disabledIds: settingsManager.get("disabledDriverWorkarounds"),
+    appliedAtLaunch: appliedWorkaroundIds,
+  });
+}
+
+function currentDriverWorkaroundEnv(settingsManager: SettingsManager): Record<string, string> {
+  const applied = new Set(
+    currentDriverWorkaroundReport(settingsManager).matched
+      .filter((workaround) => workaround.applied)
+      .map((workaround) => workaround.id),
+  );
+  return buildWorkaroundCommandLine(driverWorkaroundRuleSet.rules.filter((rule) => applied.has(rule.id))).env;
+}
+
 function emitUpdaterStateToRenderer(state: AppUpdaterState): void {
   if (mainWindow && !mainWindow.isDestroyed()) {
     mainWindow.webContents.send(IPC_CHANNELS.APP_UPDATER_STATE_CHANGED, state);
   }
 }
 
+function getPlacementDisplays(): PlacementDisplay[] {
+  return screen.getAllDisplays().map((display) => ({
+    id: display.id,
+    bounds: display.bounds,
+    workArea: display.workArea,
+    scaleFactor: display.scaleFactor,
+    label: display.label,
+  }));
+}
+
+function getPrimaryPlacementDisplay(displays: PlacementDisplay[]): PlacementDisplay {
+  const primaryId = screen.getPrimaryDisplay().id;
+  return displays.find((display) => display.id === primaryId) ?? displays[0];
+}
+
+function saveWindowPlacement(): void {
+  if (!mainWindow || mainWindow.isDestroyed() || isMainWindowFullscreen() || mainWindow.isMinimized() || miniPlayerRestore) {
+    return;
+  }
+  const bounds = mainWindow.getNormalBounds();
+  const display = findDisplayForRect(getPlacementDisplays(), bounds);
+  settingsManager.setMultiple({
+    windowWidth: bounds.width,
+    windowHeight: bounds.height,
+    windowX: bounds.x,
+    windowY: bounds.y,
+    windowDisplayId: display?.id ?? null,
+  });
+}
+
+/** Dragging or resizing fires these events every frame; write settings once the window settles. */
+const WINDOW_PLACEMENT_SAVE_DELAY_MS = 500;
+
+function scheduleWindowPlacementSave(): void {
+  if (windowPlacementSaveTimer) {
+    clearTimeout(windowPlacementSaveTimer);
+  }
+  windowPlacementSaveTimer = setTimeout(() => {
+    windowPlacementSaveTimer = null;
+    saveWindowPlacement();
+  }, WINDOW_PLACEMENT_SAVE_DELAY_MS);
+}
+
+function flushWindowPlacementSave(): void {
+  if (!windowPlacementSaveTimer) {
+    return;
+  }
+  clearTimeout(windowPlacementSaveTimer);
+  windowPlacementSaveTimer = null;
+  saveWindowPlacement();
+}
+
+function migrateWindowIntoView(): void {
+  if (!mainWindow || mainWindow.isDestroyed() || isMainWindowFullscreen()) {
+    return;
+  }
+  const displays = getPlacementDisplays();
+  if (displays.length === 0) {
+    return;
+  }
+  const bounds = mainWindow.getBounds();
+  const placement = resolveWindowPlacement(
+    { ...bounds, displayId: settingsManager.get("windowDisplayId") },
+    displays,
+    getPrimaryPlacementDisplay(displays),
+  );
+  if (placement.x !== bounds.x || placement.y !== bounds.y) {
+    console.log("[Main] Window is no longer visible after a display change; moving it on-screen.");
+    mainWindow.setBounds(placement);
+  }
+}
+
+function isMainWindowFullscreen(): boolean {
+  if (!mainWindow || mainWindow.isDestroyed()) {
+    return false;
+  }
+  return mainWindow.isFullScreen() || mainWindow.isSimpleFullScreen();
+}
+
+function setMainWindowFullscreen(nextFullscreen: boolean): void {
+  if (!mainWindow || mainWindow.isDestroyed()) {
+    return;
+  }
+  if (nextFullscreen && !isMainWindowFullscreen()) {
+    const displays = getPlacementDisplays();
+    const target = findDisplayById(displays, settingsManager.get("fullscreenDisplayId"));
+    const current = findDisplayForRect(displays, mainWindow.getBounds());
+    if (target && target.id !== current?.id) {
+      const [width, height] = mainWindow.getSize();
+      mainWindow.setBounds(
+        resolveWindowPlacement({ width, height, x: null, y: null, displayId: target.id }, displays, target),
+      );
+    }
+  }
+  // macOS simple fullscreen skips the Spaces animation, which is what makes
+  // borderless toggles feel instant there. Other platforms are already borderless.
+  const useSimpleFullscreen =
+    process.platform === "darwin" && settingsManager.get("fullscreenMode") === "borderless";
+  if (useSimpleFullscreen || mainWindow.isSimpleFullScreen()) {
+    mainWindow.setSimpleFullScreen(nextFullscreen);
+  } else {
+    mainWindow.setFullScreen(nextFullscreen);
+  }
+  rendererControlledFullscreen = nextFullscreen;
+}
+
+function setMainWindowMiniPlayer(enabled: boolean): boolean {
+  const targetWindow = mainWindow;
+  if (!targetWindow || targetWindow.isDestroyed()) {
+    return false;
+  }
+  if (enabled === (miniPlayerRestore !== null)) {
+    return enabled;
+  }
+  if (enabled) {
+    const restore = {
+      bounds: targetWindow.getNormalBounds(),
+      fullscreen: isMainWindowFullscreen(),
+      maximized: targetWindow.isMaximized(),
+      minimumSize: targetWindow.getMinimumSize(),
+    };
+    miniPlayerRestore = restore;
+    const shrink = (): void => {
+      if (targetWindow.isDestroyed() || miniPlayerRestore !== restore) {
+        return;
+      }
+      if (targetWindow.isMaximized()) {
+        targetWindow.unmaximize();
+      }
+      const workArea = screen.getDisplayMatching(targetWindow.getBounds()).workArea;
+      targetWindow.setMinimumSize(MINI_PLAYER_MIN_WIDTH, Math.round((MINI_PLAYER_MIN_WIDTH * 9) / 16));
+      targetWindow.setBounds(resolveMiniPlayerBounds(workArea));
+      targetWindow.setAspectRatio(16 / 9);
+      targetWindow.setAlwaysOnTop(true, "floating");
+    };
+    // Animated fullscreen exits finish asynchronously; resizing mid-transition is ignored.
+    if (restore.fullscreen && !targetWindow.isSimpleFullScreen()) {
+      targetWindow.once("leave-full-screen", shrink);
+      setMainWindowFullscreen(false);
+    } else {
+      if (restore.fullscreen) {
+        setMainWindowFullscreen(false);
+      }
+      shrink();
+    }
+    return true;
+  }
+
+  const restore = miniPlayerRestore;
+  miniPlayerRestore = null;
+  targetWindow.setAlwaysOnTop(false);
+  targetWindow.setAspectRatio(0);
+  if (restore) {
+    const [minWidth = 0, minHeight = 0] = restore.minimumSize;
+    targetWindow.setMinimumSize(minWidth, minHeight);
+    targetWindow.setBounds(restore.bounds);
+    if (restore.maximized) {
+      targetWindow.maximize();
+    }
+    if (restore.fullscreen) {
+      setMainWindowFullscreen(true);
+    }
+  }
+  return false;
+}
+
+// Chromium only reads the range when a peer connection gathers candidates, so
+// changes apply from the next stream. {0, 0} lifts the restriction.
+function applyWebRtcUdpPortRange(rangeText: string): void {
+  if (!mainWindow || mainWindow.isDestroyed()) return;
+  const range = parseUdpPortRange(rangeText);
+  if (rangeText.trim() && !range) {
+    console.warn(`[WebRTC] Ignoring invalid UDP port range "${rangeText}"`);
+  }
+  mainWindow.webContents.setWebRTCUDPPortRange(range ?? { min: 0, max: 0 });
+}
+
 async function createMainWindow(): Promise<void> {
   const preloadMjsPath = join(__dirname, "../preload/index.mjs");
   const preloadJsPath = join(__dirname, "../preload/index.js");
@@ -419,10 +913,21 @@ async function createMainWindow(): Promise<void> {
     : preloadJsPath;
 
   const settings = settingsManager.getAll();
+  const displays = getPlacementDisplays();
+  const placement = resolveWindowPlacement(
+    {
+      width: settings.windowWidth,
+      height: settings.windowHeight,
+      x: settings.windowX,
+      y: settings.windowY,
+      displayId: settings.windowDisplayId,
+    },
+    displays,
+    getPrimaryPlacementDisplay(displays),
+  );
 
   mainWindow = new BrowserWindow({
-    width: settings.windowWidth || 1400,
-    height: settings.windowHeight || 900,
+    ...placement,
     minWidth: 1024,
     minHeight: 680,
     autoHideMenuBar: true,
@@ -435,6 +940,8 @@ async function createMainWindow(): Promise<void> {
     },
   });
 
+  applyWebRtcUdpPortRange(settings.webrtcUdpPortRange);
+
   if (process.platform === "win32") {
     // Keep native window fullscreen in sync with HTML fullscreen so Windows treats
     // stream playback like a real fullscreen window instead of only DOM fullscreen.
@@ -468,11 +975,32 @@ async function createMainWindow(): Promise<void> {
     isPointerLockActiveRuntime = Boolean(active);
   });
 
+  ipcMain.on(IPC_CHANNELS.STREAM_ACTIVE_CHANGE, (_ev, active: boolean) => {
+    isStreamActiveRuntime = Boolean(active);
+  });
+
   // Intercept Escape early to avoid Chromium exiting fullscreen before the
   // renderer can forward the key to the remote session. This is a best-effort
   // interception and is gated by the user's `allowEscapeToExitFullscreen` setting.
   mainWindow.webContents.on("before-input-event", (event, input) => {
     try {
+      // Alt+Enter toggles fullscreen without touching the stream; swallow it so
+      // the remote session does not also receive the chord. Outside a stream,
+      // or with the shortcut off, the key passes through untouched.
+      if (
+        isStreamActiveRuntime &&
+        settingsManager?.get("altEnterFullscreen") &&
+        input.type === "keyDown" &&
+        input.key === "Enter" &&
+        input.alt &&
+        !input.control &&
+        !input.meta &&
+        !input.isAutoRepeat
+      ) {
+        event.preventDefault();
+        mainWindow?.webContents.send(IPC_CHANNELS.FULLSCREEN_TOGGLE_REQUEST);
+        return;
+      }
       if (
         input.type === "keyDown" &&
         input.key === "Escape" &&
@@ -499,12 +1027,46 @@ async function createMainWindow(): Promise<void> {
     emitDirectLaunchRequest(pendingDirectLaunchRequest);
   }
 
+  screen.on("display-removed", migrateWindowIntoView);
+  screen.on("display-metrics-changed", migrateWindowIntoView);
+  networkChangeMonitor.start((change) => {
+    console.log(`[Network] Interfaces changed: +[${change.added.join(", ")}] -[${change.removed.join(", ")}]`);
+    if (mainWindow && !mainWindow.isDestroyed()) {
+      mainWindow.webContents.send(IPC_CHANNELS.NETWORK_CHANGED, change);
+    }
+  });
+
   mainWindow.on("closed", () => {
+    screen.off("display-removed", migrateWindowIntoView);
+    screen.off("display-metrics-changed", migrateWindowIntoView);
+    networkChangeMonitor.stop();
     mainWindow = null;
     rendererControlledFullscreen = false;
   });
 }
 
+/**
+ * Holds video thumbnails and the library cache to the same size limit that
+ * Chromium applies to its HTTP cache, evicting the oldest files first.
+ */
+async function trimAppCaches(): Promise<void> {
+  const limitMb = settingsManager.get("diskCacheLimitMb");
+  if (limitMb <= 0) {
+    return;
+  }
+  const maxBytes = limitMb * 1024 * 1024;
+  for (const directory of [getThumbnailCacheDirectory(), cacheManager.getCacheDirectory()]) {
+    try {
+      const removed = await trimDirectoryToBytes(directory, maxBytes);
+      if (removed > 0) {
+        console.log(`[Storage] Evicted ${removed} cached files from ${directory} to stay under ${limitMb} MB`);
+      }
+    } catch (error) {
+      console.warn(`[Storage] Failed to trim ${directory}:`, error);
+    }
+  }
+}
+
 async function resolveJwt(token?: string): Promise<string> {
   return authService.resolveJwtToken(token);
 }
@@ -798,9 +1360,11 @@ function registerIpcHandlers(): void {
     authService,
     settingsManager,
     resolveJwt,
+    isOnBatteryPower: () => powerMonitor.isOnBatteryPower(),
     setActivity,
     clearActivity,
     getMainWindow: () => mainWindow,
+    sessionRegistry: activeSessionRegistry,
   });
 
   signalingCoordinator = registerSignalingIpcHandlers({
@@ -808,6 +1372,8 @@ function registerIpcHandlers(): void {
     mainDir: __dirname,
     settingsManager,
     getMainWindow: () => mainWindow,
+    applyDriverWorkaroundEnv: (env) =>
+      applyWorkaroundEnv(env, currentDriverWorkaroundEnv(settingsManager), injectedWorkaroundEnv),
   });
 
   ipcMain.handle(IPC_CHANNELS.DISCORD_CLEAR_ACTIVITY, async () => {
@@ -817,10 +1383,7 @@ function registerIpcHandlers(): void {
   // Toggle fullscreen via IPC (for completeness)
   ipcMain.handle(IPC_CHANNELS.TOGGLE_FULLSCREEN, async () => {
     if (mainWindow && !mainWindow.isDestroyed()) {
-      const isFullScreen = mainWindow.isFullScreen();
-      const nextFullscreen = !isFullScreen;
-      mainWindow.setFullScreen(nextFullscreen);
-      rendererControlledFullscreen = nextFullscreen;
+      setMainWindowFullscreen(!isMainWindowFullscreen());
     }
   });
 
@@ -829,9 +1392,7 @@ function registerIpcHandlers(): void {
     async (_event, value: boolean) => {
       if (mainWindow && !mainWindow.isDestroyed()) {
         try {
-          const nextFullscreen = Boolean(value);
-          mainWindow.setFullScreen(nextFullscreen);
-          rendererControlledFullscreen = nextFullscreen;
+          setMainWindowFullscreen(Boolean(value));
         } catch (err) {
           console.warn("Failed to set fullscreen:", err);
         }
@@ -839,6 +1400,25 @@ function registerIpcHandlers(): void {
     },
   );
 
+  ipcMain.handle(IPC_CHANNELS.SET_MINI_PLAYER, async (_event, enabled: boolean): Promise<boolean> => {
+    return setMainWindowMiniPlayer(Boolean(enabled));
+  });
+
+  ipcMain.handle(IPC_CHANNELS.LIST_DISPLAYS, async () => {
+    const primaryId = screen.getPrimaryDisplay().id;
+    return getPlacementDisplays().map((display, index) => toDisplayInfo(display, primaryId, index));
+  });
+
+  // The renderer's battery profile follows the same power source as the render FPS cap.
+  ipcMain.handle(IPC_CHANNELS.POWER_SOURCE_GET, async (): Promise<boolean> => powerMonitor.isOnBatteryPower());
+  const emitPowerSource = (): void => {
+    if (mainWindow && !mainWindow.isDestroyed()) {
+      mainWindow.webContents.send(IPC_CHANNELS.POWER_SOURCE_CHANGED, powerMonitor.isOnBatteryPower());
+    }
+  };
+  powerMonitor.on("on-battery", emitPowerSource);
+  powerMonitor.on("on-ac", emitPowerSource);
+
   // Toggle pointer lock via IPC (F8 shortcut)
   ipcMain.handle(IPC_CHANNELS.TOGGLE_POINTER_LOCK, async () => {
     if (mainWindow && !mainWindow.isDestroyed()) {
@@ -881,6 +1461,7 @@ function registerIpcHandlers(): void {
           currentDisplayVersion: buildInfo.displayVersion,
           currentBuildNumber: buildInfo.buildNumber,
           updateSource: "github-releases",
+          channel: settingsManager.get("updateChannel"),
           canCheck: false,
           canDownload: false,
           canInstall: false,
@@ -902,6 +1483,7 @@ function registerIpcHandlers(): void {
           currentDisplayVersion: buildInfo.displayVersion,
           currentBuildNumber: buildInfo.buildNumber,
           updateSource: "github-releases",
+          channel: settingsManager.get("updateChannel"),
           canCheck: false,
           canDownload: false,
           canInstall: false,
@@ -923,6 +1505,7 @@ function registerIpcHandlers(): void {
           currentDisplayVersion: buildInfo.displayVersion,
           currentBuildNumber: buildInfo.buildNumber,
           updateSource: "github-releases",
+          channel: settingsManager.get("updateChannel"),
           canCheck: false,
           canDownload: false,
           canInstall: false,
@@ -944,6 +1527,7 @@ function registerIpcHandlers(): void {
           currentDisplayVersion: buildInfo.displayVersion,
           currentBuildNumber: buildInfo.buildNumber,
           updateSource: "github-releases",
+          channel: settingsManager.get("updateChannel"),
           canCheck: false,
           canDownload: false,
           canInstall: false,
@@ -956,10 +1540,69 @@ function registerIpcHandlers(): void {
 
   // Settings IPC handlers
   ipcMain.handle(IPC_CHANNELS.SETTINGS_GET, async (): Promise<Settings> => {
-    return settingsManager.getAll();
+    return toRendererSettings(settingsManager.getAll());
   });
 
+  // Re-read on every request so edits to theme.json apply without a restart.
+  ipcMain.handle(IPC_CHANNELS.USER_THEME_GET, async (): Promise<UserTheme> => {
+    return loadUserTheme(app.getPath("userData"));
+  });
+
+  ipcMain.handle(IPC_CHANNELS.DRIVER_WORKAROUNDS_GET, async (): Promise<DriverWorkaroundReport> => {
+    return currentDriverWorkaroundReport(settingsManager);
+  });
+
+  ipcMain.handle(IPC_CHANNELS.GAMESCOPE_STATUS_GET, async (): Promise<GamescopeStatus> => {
+    return gamescopeCommandLine.status;
+  });
+
+  const pinAttemptStatePath = join(app.getPath("userData"), PIN_ATTEMPT_STATE_FILENAME);
+  const parentalPinLimiter = new PinAttemptLimiter(
+    undefined,
+    undefined,
+    loadPinAttemptState(pinAttemptStatePath),
+    (state) => savePinAttemptState(pinAttemptStatePath, state),
+  );
+  const checkParentalPin = async (pin: string): Promise<boolean> => {
+    const storedHash = settingsManager.get("parentalPinHash");
+    if (!storedHash) {
+      return true;
+    }
+    if (!pin || parentalPinLimiter.isBlocked()) {
+      return false;
+    }
+    const matches = await verifyParentalPin(pin, storedHash).catch((error: unknown) => {
+      console.error("[Parental] PIN verification failed:", error);
+      return false;
+    });
+    parentalPinLimiter.record(matches);
+    return matches;
+  };
+
+  ipcMain.handle(IPC_CHANNELS.PARENTAL_VERIFY_PIN, async (_event, pin: string): Promise<boolean> => {
+    return checkParentalPin(pin);
+  });
+
+  ipcMain.handle(
+    IPC_CHANNELS.PARENTAL_SET_PIN,
+    async (_event, currentPin: string | null, nextPin: string | null): Promise<boolean | null> => {
+      if (!(await checkParentalPin(currentPin ?? ""))) {
+        return null;
+      }
+      if (nextPin !== null && !isValidParentalPin(nextPin)) {
+        throw new Error("Parental PIN must be 4 to 8 digits");
+      }
+      const nextHash = nextPin === null ? "" : await hashParentalPin(nextPin);
+      settingsManager.set("parentalPinHash", nextHash);
+      return nextHash !== "";
+    },
+  );
+
   ipcMain.handle(IPC_CHANNELS.CLIPBOARD_READ_TEXT, async (): Promise<string> => {
+    // Clipboard sync is opt-in; the renderer never gets clipboard text while it is off.
+    if (!settingsManager.get("clipboardPaste")) {
+      return "";
+    }
     return clipboard.readText();
   });
 
@@ -969,15 +1612,52 @@ function registerIpcHandlers(): void {
       _event: Electron.IpcMainInvokeEvent,
       key: K,
       value: Settings[K],
+      pin?: string,
     ) => {
-      settingsManager.set(key, value);
-      const appliedValue = settingsManager.get(key);
+      // The PIN hash only changes through PARENTAL_SET_PIN, which checks the current PIN first,
+      // and parentalPinSet is derived from it.
+      if (key === "parentalPinSet" || (key as string) === "parentalPinHash") {
+        return;
+      }
+      const storedKey = key as Exclude<K, "parentalPinSet">;
+      // The renderer mirrors protected values back after saving them with the PIN,
+      // so an unchanged value needs no PIN.
+      if (
+        isParentalProtectedSetting(storedKey)
+        && settingsManager.get(storedKey) !== value
+        && !(await checkParentalPin(pin ?? ""))
+      ) {
+        throw new Error("Parental PIN required");
+      }
+      if (key === "mediaDirectory" && value !== "" && value !== settingsManager.get(key) && !wasMediaDirectoryPicked(value as string)) {
+        throw new Error("The media folder can only be set from the folder picker");
+      }
+      settingsManager.set(storedKey, value as StoredSettings[typeof storedKey]);
+      const appliedValue = settingsManager.get(storedKey);
       // React to certain setting changes immediately in main process
       try {
         if (key === "autoCheckForUpdates") {
           appUpdater?.setAutomaticChecksEnabled(appliedValue as boolean);
         }
+        if (key === "updateChannel") {
+          appUpdater?.setChannel(appliedValue as Settings["updateChannel"]);
+        }
+        if (key === "appTheme") {
+          nativeTheme.themeSource = appliedValue as Settings["appTheme"];
+        }
         signalingCoordinator?.applySettingsChange(key, appliedValue);
+        if (key === "webrtcUdpPortRange") {
+          applyWebRtcUdpPortRange(appliedValue as string);
+        }
+        if (key === "mediaDirectory") {
+          setMediaRootDirectory(appliedValue as string);
+        }
+        if (key === "diskCacheLimitMb") {
+          void trimAppCaches();
+        }
+        if (key === "logFilter") {
+          getLogCapture()?.setFilter(parseLogFilter(appliedValue as string));
+        }
         if (key === "discordRichPresence") {
           if (appliedValue) {
             void connectDiscordRpc().then(() => discordMonitor.start());
@@ -992,9 +1672,15 @@ function registerIpcHandlers(): void {
     },
   );
 
-  ipcMain.handle(IPC_CHANNELS.SETTINGS_RESET, async (): Promise<Settings> => {
-    const resetSettings = settingsManager.reset();
+  ipcMain.handle(IPC_CHANNELS.SETTINGS_RESET, async (_event, pin?: string): Promise<Settings> => {
+    if (!(await checkParentalPin(pin ?? ""))) {
+      throw new Error("Parental PIN required");
+    }
+    const resetSettings = toRendererSettings(settingsManager.reset());
     appUpdater?.setAutomaticChecksEnabled(resetSettings.autoCheckForUpdates);
+    appUpdater?.setChannel(resetSettings.updateChannel);
+    setMediaRootDirectory(resetSettings.mediaDirectory);
+    nativeTheme.themeSource = resetSettings.appTheme;
     signalingCoordinator?.stopNativeStreamer("settings reset");
     signalingCoordinator?.resetNativeStreamerContext();
     return resetSettings;
@@ -1086,11 +1772,28 @@ function registerIpcHandlers(): void {
     },
   );
 
+  ipcMain.handle(IPC_CHANNELS.LOGS_TAIL, async (_event, afterSequence: number): Promise<LogTailResult> => {
+    return tailLogs(getLogCapture(), Number(afterSequence) || 0);
+  });
+
   // Logs export IPC handler
   ipcMain.handle(
     IPC_CHANNELS.LOGS_EXPORT,
     async (_event, format: "text" | "json" = "text"): Promise<string> => {
-      return exportLogs(format);
+      const httpTelemetry = getHttpTelemetrySnapshot();
+      const cacheStats: CacheStats = { ...cacheManager.getCounters(), ...refreshScheduler.getStats() };
+      const taskHealth = taskSupervisor.getHealth();
+      return appendDiagnosticsSections(exportLogs(format), format, [
+        { key: "httpTelemetry", text: formatHttpTelemetry(httpTelemetry), data: httpTelemetry },
+        { key: "cacheStats", text: formatCacheStats(cacheStats), data: cacheStats },
+        { key: "backgroundTasks", text: formatTaskHealth(taskHealth), data: taskHealth },
+        ...(lastRouteTrace
+          ? [{ key: "routeTrace", text: formatRouteTrace(lastRouteTrace), data: lastRouteTrace }]
+          : []),
+        ...(lastSessionTimeline
+          ? [{ key: "sessionTimeline", text: formatSessionTimeline(lastSessionTimeline), data: lastSessionTimeline }]
+          : []),
+      ]);
     },
   );
 
@@ -1105,11 +1808,58 @@ function registerIpcHandlers(): void {
     await refreshScheduler.manualRefresh();
   });
 
+  ipcMain.handle(IPC_CHANNELS.BACKGROUND_WORK_SET_PAUSED, async (_event, paused: boolean): Promise<void> => {
+    refreshScheduler.setPaused(paused === true);
+  });
+
   ipcMain.handle(IPC_CHANNELS.CACHE_DELETE_ALL, async (): Promise<void> => {
     await cacheManager.deleteAll();
     console.log("[IPC] Cache deletion completed successfully");
   });
 
+  const storageTargets = (): StorageTargets => {
+    const crashReportsDir = resolveCrashReportsDirectory(getAppLogsDirectory());
+    return {
+      images: {
+        directory: getThumbnailCacheDirectory(),
+        extraBytes: () => session.defaultSession.getCacheSize(),
+        clear: async () => {
+          await session.defaultSession.clearCache();
+          await clearDirectory(getThumbnailCacheDirectory());
+        },
+      },
+      library: {
+        directory: cacheManager.getCacheDirectory(),
+        clear: () => cacheManager.deleteEntries(),
+      },
+      recordings: {
+        directory: getRecordingsDirectory(),
+        clear: () => clearDirectory(getRecordingsDirectory(), activeRecordingPaths()),
+      },
+      logs: {
+        directory: crashReportsDir,
+        clear: () => clearDirectory(crashReportsDir),
+      },
+    };
+  };
+
+  ipcMain.handle(IPC_CHANNELS.STORAGE_USAGE, async (): Promise<StorageCategoryUsage[]> => {
+    return measureStorage(storageTargets());
+  });
+
+  ipcMain.handle(
+    IPC_CHANNELS.STORAGE_CLEAR,
+    async (_event, category: StorageCategory): Promise<StorageCategoryUsage[]> => {
+      const targets = storageTargets();
+      if (!Object.hasOwn(targets, category)) {
+        throw new Error(`Unknown storage category: ${String(category)}`);
+      }
+      await targets[category].clear();
+      console.log(`[Storage] Cleared ${category}`);
+      return measureStorage(targets);
+    },
+  );
+
   ipcMain.handle(
     IPC_CHANNELS.COMMUNITY_GET_THANKS,
     async (): Promise<ThankYouDataResult> => {
@@ -1124,23 +1874,98 @@ function registerIpcHandlers(): void {
     },
   );
 
-  // PrintedWaste queue API — fetched from main process so User-Agent can be set
+  // One trace at a time: a second request while probing gets the running one.
+  ipcMain.handle(IPC_CHANNELS.ROUTE_TRACE, async (_event, target: string): Promise<RouteTraceResult> => {
+    routeTraceInFlight ??= traceRoute(target)
+      .then((result) => {
+        lastRouteTrace = result;
+        return result;
+      })
+      .finally(() => {
+        routeTraceInFlight = null;
+      });
+    return routeTraceInFlight;
+  });
+
+  ipcMain.handle(IPC_CHANNELS.PATH_MTU_PROBE, async (_event, target: string): Promise<PathMtuResult> => {
+    return probePathMtu(target);
+  });
+
+  ipcMain.handle(IPC_CHANNELS.SYSTEM_USAGE, async (): Promise<SystemUsageSample> => {
+    return sampleSystemUsage();
+  });
+
+  // PrintedWaste queue API — fetched from main process so User-Agent can be set.
+  // Hiding the server selector turns the integration off, so nothing is sent.
+  const printedWasteSource = () => {
+    if (settingsManager.get("hideServerSelector")) {
+      throw new Error("PrintedWaste integration is disabled in settings");
+    }
+    return resolvePrintedWasteSource(
+      settingsManager.get("queueServiceUrl"),
+      settingsManager.get("queueServicePublicKey"),
+    );
+  };
+
   ipcMain.handle(IPC_CHANNELS.PRINTEDWASTE_QUEUE_FETCH, async () => {
-    return fetchPrintedWasteQueue(app.getVersion());
+    return fetchPrintedWasteQueue(app.getVersion(), printedWasteSource());
   });
 
   ipcMain.handle(IPC_CHANNELS.PRINTEDWASTE_SERVER_MAPPING_FETCH, async () => {
-    return fetchPrintedWasteServerMapping(app.getVersion());
+    return fetchPrintedWasteServerMapping(app.getVersion(), printedWasteSource());
   });
 
-  // Save window size when it changes
-  mainWindow?.on("resize", () => {
-    if (mainWindow && !mainWindow.isDestroyed()) {
-      const [width, height] = mainWindow.getSize();
-      settingsManager.set("windowWidth", width);
-      settingsManager.set("windowHeight", height);
-    }
+  ipcMain.handle(IPC_CHANNELS.SESSION_TIMELINE_RECORD, async (_event, timeline: SessionTimeline): Promise<void> => {
+    lastSessionTimeline = timeline;
+  });
+
+  ipcMain.handle(IPC_CHANNELS.SESSION_TIMELINE_GET, async (): Promise<SessionTimeline | null> => {
+    return lastSessionTimeline;
   });
+
+  ipcMain.handle(
+    IPC_CHANNELS.TELEMETRY_RECORD_SESSION,
+    async (_event, report: SessionTelemetryReport): Promise<void> => {
+      const mode = settingsManager.get("telemetryMode");
+      if (mode === "off") {
+        return;
+      }
+      const path = resolveTelemetryPath(app.getPath("userData"));
+      const gpuInfo = await app.getGPUInfo("basic").catch(() => null);
+      let aggregate = addSessionToTelemetry(
+        readTelemetryAggregate(path),
+        {
+          appVersion: app.getVersion(),
+          platform: process.platform,
+          arch: process.arch,
+          ...telemetryGpuFromInfo(gpuInfo),
+        },
+        report,
+        { queueUpload: mode === "upload" },
+      );
+      writeTelemetryAggregate(path, aggregate);
+      if (mode !== "upload") {
+        return;
+      }
+
+      const endpoint = settingsManager.get("telemetryEndpoint").trim();
+      if (!endpoint) {
+        console.warn("[Telemetry] Upload enabled without an endpoint; keeping sessions queued locally");
+        return;
+      }
+      try {
+        aggregate = await uploadPendingTelemetry(endpoint, aggregate, app.getVersion());
+        writeTelemetryAggregate(path, aggregate);
+      } catch (error) {
+        console.warn("[Telemetry] Upload failed; sessions stay queued for the next attempt:", error);
+      }
+    },
+  );
+
+  // Save window size, position, and monitor when they change
+  mainWindow?.on("resize", scheduleWindowPlacementSave);
+  mainWindow?.on("move", scheduleWindowPlacementSave);
+  mainWindow?.on("close", flushWindowPlacementSave);
 }
 
 const gotSingleInstanceLock = app.requestSingleInstanceLock();
@@ -1171,9 +1996,16 @@ app.whenReady().then(async () => {
   await authService.initialize();
 
   settingsManager = getSettingsManager();
+  getLogCapture()?.setFilter(parseLogFilter(settingsManager.get("logFilter")));
+  setMediaRootDirectory(settingsManager.get("mediaDirectory"));
+  reconcileLeftoverSession();
+  void trimAppCaches();
+  // Keeps native window chrome and prefers-color-scheme in step with the app theme.
+  nativeTheme.themeSource = settingsManager.get("appTheme");
   appUpdater = createAppUpdaterController({
     onStateChanged: emitUpdaterStateToRenderer,
     automaticChecksEnabled: settingsManager.get("autoCheckForUpdates"),
+    channel: settingsManager.get("updateChannel"),
     onBeforeQuitAndInstall: () => {
       isUpdaterInstallQuitInProgress = true;
       clearExplicitShutdownFallback();
@@ -1189,46 +2021,33 @@ app.whenReady().then(async () => {
   }
 
   // Set up permission handlers for getUserMedia, fullscreen, pointer lock
+  const privacyPolicy = () => ({ microphoneBlocked: settingsManager.get("microphoneBlocked") });
   session.defaultSession.setPermissionRequestHandler(
-    (webContents, permission, callback) => {
-      const allowedPermissions = new Set([
-        "media",
-        "microphone",
-        "fullscreen",
-        "automatic-fullscreen",
-        "pointerLock",
-        "keyboardLock",
-        "speaker-selection",
-      ]);
-
-      if (allowedPermissions.has(permission)) {
-        callback(true);
-        return;
-      }
-
-      callback(false);
+    (_webContents, permission, callback, details) => {
+      const mediaTypes = "mediaTypes" in details ? details.mediaTypes : undefined;
+      callback(isPermissionAllowed(permission, privacyPolicy(), mediaTypes));
     },
   );
 
   session.defaultSession.setPermissionCheckHandler(
-    (_webContents, permission, _requestingOrigin) => {
-      const allowedPermissions = new Set([
-        "media",
-        "microphone",
-        "fullscreen",
-        "automatic-fullscreen",
-        "pointerLock",
-        "keyboardLock",
-        "speaker-selection",
-      ]);
-
-      return allowedPermissions.has(permission);
+    (_webContents, permission, _requestingOrigin, details) => {
+      const mediaType = details.mediaType;
+      const mediaTypes = mediaType === "audio" || mediaType === "video" ? [mediaType] : undefined;
+      return isPermissionAllowed(permission, privacyPolicy(), mediaTypes);
     },
   );
 
   registerOpenNowMediaProtocol();
   registerIpcHandlers();
 
+  stopWatchingWorkaroundRules = watchWorkaroundRules(app.getPath("userData"), (ruleSet) => {
+    driverWorkaroundRuleSet = ruleSet;
+    console.log(`[Main] Driver workaround rules reloaded (revision ${ruleSet.revision})`);
+    if (mainWindow && !mainWindow.isDestroyed()) {
+      mainWindow.webContents.send(IPC_CHANNELS.DRIVER_WORKAROUNDS_CHANGED, currentDriverWorkaroundReport(settingsManager));
+    }
+  });
+
   refreshScheduler.initialize(
     fetchMainGamesUncached,
     fetchLibraryGamesUncached,
@@ -1267,6 +2086,7 @@ app.whenReady().then(async () => {
 
   await createMainWindow();
   appUpdater.initialize();
+  void offerPendingCrashReports();
 
   app.on("activate", async () => {
     if (isShutdownRequested) {
@@ -1283,7 +2103,56 @@ app.on("window-all-closed", () => {
   requestAppShutdown({ reason: "window-all-closed" });
 });
 
-app.on("before-quit", () => {
+function stopActiveSessionOnExit(): boolean {
+  if (isUpdaterInstallQuitInProgress || !settingsManager?.get("endSessionOnExit")) {
+    return false;
+  }
+  const session = activeSessionRegistry.take();
+  if (!session) {
+    return false;
+  }
+
+  console.log(`[Main] Ending session ${session.sessionId} before exit`);
+  void withTimeout(
+    resolveJwt().then((token) => stopSession({ ...session, token })),
+    SESSION_STOP_ON_EXIT_TIMEOUT_MS,
+    "Session stop on exit",
+  )
+    .then(() => activeSessionRegistry.settle(session.sessionId))
+    .catch((error) => console.warn("[Main] Failed to end session on exit:", error))
+    .finally(() => app.quit());
+  return true;
+}
+
+/** Ends a session that a crashed or killed run could not stop on exit. */
+function reconcileLeftoverSession(): void {
+  const leftover = activeSessionRegistry.restore();
+  if (!leftover) {
+    return;
+  }
+  if (!settingsManager.get("endSessionOnExit")) {
+    activeSessionRegistry.settle(leftover.sessionId);
+    return;
+  }
+  console.log(`[Main] Ending session ${leftover.sessionId} left running by the previous run`);
+  void resolveJwt()
+    .then((token) => stopSession({ ...leftover, token }))
+    .catch((error) => console.warn("[Main] Failed to end the previous run's session:", error))
+    // A session that is already gone fails to stop too; either way it is not retried.
+    .finally(() => activeSessionRegistry.settle(leftover.sessionId));
+}
+
+for (const signal of ["SIGINT", "SIGTERM"] as const) {
+  process.once(signal, () => {
+    requestAppShutdown({ reason: signal.toLowerCase(), forceExitFallback: true });
+  });
+}
+
+app.on("before-quit", (event) => {
+  if (stopActiveSessionOnExit()) {
+    event.preventDefault();
+    return;
+  }
   isShutdownRequested = true;
   runShutdownCleanup(
     isUpdaterInstallQuitInProgress
@@ -34,6 +34,7 @@ import { fetchPersistentStorageLocations, resetPersistentStorage } from "../gfn/
 import {
   fetchGameAccountConnections,
   linkGameAccount,
+  peekCachedGameAccountConnections,
   resyncGameAccount,
   unlinkGameAccount,
 } from "../gfn/accountConnections";
@@ -135,6 +136,10 @@ export function registerAccountCatalogIpcHandlers(
     },
   );
 
+  ipcMain.handle(IPC_CHANNELS.AUTH_LOGIN_CANCEL, async () => {
+    authService.cancelLogin();
+  });
+
   ipcMain.handle(
     IPC_CHANNELS.AUTH_DEVICE_LOGIN_START,
     async (_event, payload: AuthDeviceLoginStartRequest) => {
@@ -171,6 +176,10 @@ export function registerAccountCatalogIpcHandlers(
     await authService.logoutAll();
   });
 
+  ipcMain.handle(IPC_CHANNELS.AUTH_REVOKE_ALL, async () => {
+    return authService.revokeAllSessions();
+  });
+
   ipcMain.handle(IPC_CHANNELS.AUTH_GET_SAVED_ACCOUNTS, async () => {
     return authService.getSavedAccounts();
   });
@@ -263,6 +272,11 @@ export function registerAccountCatalogIpcHandlers(
     return fetchGameAccountConnections(session);
   });
 
+  ipcMain.handle(IPC_CHANNELS.GAME_ACCOUNTS_PEEK, () => {
+    const session = authService.getSession();
+    return session ? peekCachedGameAccountConnections(session) : null;
+  });
+
   ipcMain.handle(
     IPC_CHANNELS.GAME_ACCOUNT_LINK,
     async (_event, payload: GameAccountOperationRequest) => {
@@ -318,7 +332,7 @@ export function registerAccountCatalogIpcHandlers(
       const streamingBaseUrl =
         payload?.providerStreamingBaseUrl ??
         authService.getSelectedProvider().streamingServiceUrl;
-      if (savedSession) {
+      if (savedSession && !payload?.refresh) {
         const tokens = savedSessionTokens(savedSession);
         if (tokens) {
           const userId = payload.userId ?? tokens.userId;
@@ -335,7 +349,7 @@ export function registerAccountCatalogIpcHandlers(
         payload,
         { networkRequired: true },
       );
-      return fetchLibraryGames(token, resolvedBaseUrl, userId, proxyUrl);
+      return fetchLibraryGames(token, resolvedBaseUrl, userId, proxyUrl, { refresh: payload?.refresh });
     },
   );
 
@@ -16,9 +16,13 @@ import type {
   ScreenshotSaveRequest,
 } from "@shared/gfn";
 import {
+  getMediaRootDirectory,
   getTrustedVideoPlaybackFileUrl,
+  isUnsafeMediaDirectory,
+  recordPickedMediaDirectory,
   resolveTrustedOpenNowMediaPath,
 } from "../mediaPaths";
+import { isFlatpakSandbox, revealInFileManager } from "../flatpak";
 import {
   deleteScreenshot,
   listScreenshots,
@@ -48,6 +52,8 @@ export interface MediaIpcHandlerDeps {
 }
 
 export function registerMediaIpcHandlers(deps: MediaIpcHandlerDeps): void {
+  const sandboxed = isFlatpakSandbox();
+
   deps.ipcMain.handle(
     IPC_CHANNELS.SCREENSHOT_SAVE,
     async (_event, input: ScreenshotSaveRequest): Promise<ScreenshotEntry> => {
@@ -109,6 +115,39 @@ export function registerMediaIpcHandlers(deps: MediaIpcHandlerDeps): void {
     },
   );
 
+  deps.ipcMain.handle(
+    IPC_CHANNELS.SETTINGS_SELECT_MEDIA_DIRECTORY,
+    async (): Promise<string | null> => {
+      const options: Electron.OpenDialogOptions = {
+        title: "Choose where OpenNOW saves screenshots and recordings",
+        defaultPath: getMediaRootDirectory(),
+        properties: ["openDirectory", "createDirectory"],
+      };
+      const mainWindow = deps.getMainWindow();
+      const result =
+        mainWindow && !mainWindow.isDestroyed()
+          ? await deps.dialog.showOpenDialog(mainWindow, options)
+          : await deps.dialog.showOpenDialog(options);
+      const directory = result.canceled ? null : result.filePaths[0] ?? null;
+      if (!directory) {
+        return null;
+      }
+      if (isUnsafeMediaDirectory(directory)) {
+        const warning: Electron.MessageBoxOptions = {
+          type: "warning",
+          message: "Choose a dedicated folder",
+          detail: "A drive root or your home folder cannot hold OpenNOW screenshots and recordings. Pick or create a folder inside it instead.",
+        };
+        await (mainWindow && !mainWindow.isDestroyed()
+          ? deps.dialog.showMessageBox(mainWindow, warning)
+          : deps.dialog.showMessageBox(warning));
+        return null;
+      }
+      recordPickedMediaDirectory(directory);
+      return directory;
+    },
+  );
+
   deps.ipcMain.handle(
     IPC_CHANNELS.RECORDING_BEGIN,
     async (
@@ -157,7 +196,7 @@ export function registerMediaIpcHandlers(deps: MediaIpcHandlerDeps): void {
   deps.ipcMain.handle(
     IPC_CHANNELS.RECORDING_SHOW_IN_FOLDER,
     async (_event, id: string): Promise<void> => {
-      deps.shell.showItemInFolder(await getRecordingFilePath(id));
+      await revealInFileManager(deps.shell, await getRecordingFilePath(id), sandboxed);
     },
   );
 
@@ -185,7 +224,7 @@ export function registerMediaIpcHandlers(deps: MediaIpcHandlerDeps): void {
       try {
         const fpReal = await resolveTrustedOpenNowMediaPath(rawFp);
         if (!fpReal) return;
-        deps.shell.showItemInFolder(fpReal);
+        await revealInFileManager(deps.shell, fpReal, sandboxed);
       } catch {
         return;
       }
@@ -10,6 +10,7 @@ import type {
   SessionStopRequest,
 } from "@shared/gfn";
 import { formatErrorChainForLog } from "@shared/networkError";
+import { applyRenderFpsCapToStreamSettings } from "@shared/renderFpsCap";
 import type { AuthService } from "../gfn/auth";
 import {
   claimSession,
@@ -31,6 +32,7 @@ import {
   shouldForceNewSession,
 } from "../session/cloudGsyncSettings";
 import { stopActiveSessionsForCreate } from "../session/sessionLifecycle";
+import type { ActiveSessionRegistry } from "../session/sessionRegistry";
 import {
   selectLaunchingSession,
   selectReadySessionToClaim,
@@ -40,7 +42,9 @@ export interface SessionIpcHandlerDeps extends SessionConflictDialogDeps {
   ipcMain: IpcMain;
   authService: AuthService;
   settingsManager: SettingsManager;
+  sessionRegistry: ActiveSessionRegistry;
   resolveJwt(token?: string): Promise<string>;
+  isOnBatteryPower(): boolean;
   setActivity(gameName: string, startTimestamp: Date, appId?: string): Promise<void>;
   clearActivity(): Promise<void>;
 }
@@ -50,11 +54,18 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
     ipcMain,
     authService,
     settingsManager,
+    sessionRegistry,
     resolveJwt,
+    isOnBatteryPower,
     setActivity,
     clearActivity,
   } = deps;
 
+  const remember = (session: SessionInfo): SessionInfo => {
+    sessionRegistry.record(session);
+    return session;
+  };
+
   ipcMain.handle(
     IPC_CHANNELS.CREATE_SESSION,
     async (_event, payload: SessionCreateRequest) => {
@@ -65,9 +76,18 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
       const forceNewSession = shouldForceNewSession(
         payload.existingSessionStrategy,
       );
-      const resolvedSettings = await resolveSessionCloudGsyncSettings(
-        payload.settings,
+      const requestedSettings = payload.settings;
+      const cappedSettings = applyRenderFpsCapToStreamSettings(
+        requestedSettings,
+        settingsManager.getAll(),
+        isOnBatteryPower(),
       );
+      if (cappedSettings.fps !== requestedSettings.fps) {
+        console.log(
+          `[CreateSession] Requesting ${cappedSettings.fps} fps instead of ${requestedSettings.fps} fps for the render FPS cap.`,
+        );
+      }
+      const resolvedSettings = await resolveSessionCloudGsyncSettings(cappedSettings);
       const resolvedPayload: SessionCreateRequest = {
         ...payload,
         settings: resolvedSettings,
@@ -156,7 +176,7 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
               payload.appId,
             );
           }
-          return preChecked;
+          return remember(preChecked);
         }
       }
 
@@ -181,7 +201,7 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
             payload.appId,
           );
         }
-        return sessionResult;
+        return remember(sessionResult);
       } catch (error) {
         if (
           !forceNewSession &&
@@ -200,7 +220,7 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
                 payload.appId,
               );
             }
-            return fallback;
+            return remember(fallback);
           }
         }
         rethrowSerializedSessionError(error);
@@ -219,7 +239,7 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
           streamingBaseUrl:
             payload.streamingBaseUrl ??
             authService.getSelectedProvider().streamingServiceUrl,
-        });
+        }).then(remember);
       } catch (error) {
         rethrowSerializedSessionError(error);
       }
@@ -256,6 +276,7 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
             payload.streamingBaseUrl ??
             authService.getSelectedProvider().streamingServiceUrl,
         });
+        sessionRegistry.clear(payload.sessionId);
         void clearActivity();
         return result;
       } catch (error) {
@@ -291,7 +312,7 @@ export function registerSessionIpcHandlers(deps: SessionIpcHandlerDeps): void {
           token,
           streamingBaseUrl,
           settings: resolvedSettings,
-        });
+        }).then(remember);
       } catch (error) {
         rethrowSerializedSessionError(error);
       }
@@ -0,0 +1,36 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { buildLinuxDisplayServerCommandLine, detectLinuxDisplayServer } from "./linuxDisplayServer";
+
+test("selects native Wayland when WAYLAND_DISPLAY is present", () => {
+  const commandLine = buildLinuxDisplayServerCommandLine("linux", {
+    WAYLAND_DISPLAY: "wayland-0",
+    DISPLAY: ":0",
+  });
+
+  assert.equal(commandLine.displayServer, "wayland");
+  assert.equal(commandLine.switches["ozone-platform"], "wayland");
+  assert.ok(commandLine.enableFeatures.includes("WaylandFractionalScaleV1"));
+});
+
+test("falls back to X11 without a Wayland socket", () => {
+  assert.equal(detectLinuxDisplayServer("linux", { DISPLAY: ":0" }), "x11");
+});
+
+test("honors an explicit X11 override on Wayland sessions", () => {
+  const commandLine = buildLinuxDisplayServerCommandLine("linux", {
+    WAYLAND_DISPLAY: "wayland-0",
+    OPENNOW_OZONE_PLATFORM: "x11",
+  });
+
+  assert.equal(commandLine.displayServer, "x11");
+  assert.deepEqual(commandLine.enableFeatures, []);
+});
+
+test("does not add display server switches outside Linux", () => {
+  const commandLine = buildLinuxDisplayServerCommandLine("win32", { WAYLAND_DISPLAY: "wayland-0" });
+
+  assert.equal(commandLine.displayServer, null);
+  assert.deepEqual(commandLine.switches, {});
+});
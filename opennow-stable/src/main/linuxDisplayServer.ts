@@ -0,0 +1,57 @@
+export type LinuxDisplayServer = "wayland" | "x11";
+
+export interface LinuxDisplayServerCommandLine {
+  displayServer: LinuxDisplayServer | null;
+  enableFeatures: string[];
+  switches: Record<string, string | true>;
+}
+
+export const OZONE_PLATFORM_OVERRIDE_ENV = "OPENNOW_OZONE_PLATFORM";
+
+function hasValue(value: string | undefined): boolean {
+  return Boolean(value?.trim());
+}
+
+export function detectLinuxDisplayServer(
+  platform: NodeJS.Platform,
+  env: NodeJS.ProcessEnv,
+): LinuxDisplayServer | null {
+  if (platform !== "linux") {
+    return null;
+  }
+
+  const override = env[OZONE_PLATFORM_OVERRIDE_ENV]?.trim().toLowerCase();
+  if (override === "wayland" || override === "x11") {
+    return override;
+  }
+
+  if (hasValue(env.WAYLAND_DISPLAY)) {
+    return "wayland";
+  }
+  if (hasValue(env.DISPLAY)) {
+    return "x11";
+  }
+  return env.XDG_SESSION_TYPE?.trim().toLowerCase() === "wayland" ? "wayland" : null;
+}
+
+export function buildLinuxDisplayServerCommandLine(
+  platform: NodeJS.Platform,
+  env: NodeJS.ProcessEnv,
+): LinuxDisplayServerCommandLine {
+  const displayServer = detectLinuxDisplayServer(platform, env);
+  const enableFeatures: string[] = [];
+  const switches: Record<string, string | true> = {};
+
+  if (displayServer === "wayland") {
+    // Running natively on Wayland lets Chromium use wp_relative_pointer and
+    // pointer-constraints for pointer lock, wp_fractional_scale for HiDPI output,
+    // and zwp_idle_inhibit for power save blockers instead of going through XWayland.
+    switches["ozone-platform"] = "wayland";
+    switches["enable-wayland-ime"] = true;
+    enableFeatures.push("WaylandWindowDecorations", "WaylandFractionalScaleV1");
+  } else if (displayServer === "x11") {
+    switches["ozone-platform"] = "x11";
+  }
+
+  return { displayServer, enableFeatures, switches };
+}
@@ -55,6 +55,27 @@ test("disables Linux auto updates instead of selecting deb on systems without dp
   assert.match(support.message ?? "", /Download the AppImage/);
 });
 
+test("defers to the package manager inside Flatpak and Snap sandboxes", () => {
+  const flatpak = getLinuxUpdaterSupport({
+    platform: "linux",
+    env: { APPIMAGE: "/home/user/OpenNOW.AppImage" },
+    fileExists: (path) => path === "/.flatpak-info",
+    commandExists: noCommands,
+  });
+  assert.equal(flatpak.supported, false);
+  assert.equal(flatpak.packageKind, "flatpak");
+  assert.match(flatpak.message ?? "", /flatpak update/);
+
+  const snap = getLinuxUpdaterSupport({
+    platform: "linux",
+    env: { SNAP: "/snap/opennow/12" },
+    fileExists: () => false,
+    commandExists: (command) => command === "dpkg",
+  });
+  assert.equal(snap.supported, false);
+  assert.equal(snap.packageKind, "snap");
+});
+
 test("does not apply Linux package checks to other platforms", () => {
   const support = getLinuxUpdaterSupport({
     platform: "darwin",
@@ -1,7 +1,8 @@
 import { accessSync, constants, readFileSync } from "node:fs";
 import { delimiter, isAbsolute, join } from "node:path";
+import { isFlatpakSandbox } from "./flatpak";
 
-export type LinuxUpdaterPackageKind = "appimage" | "deb" | "native" | "unsupported";
+export type LinuxUpdaterPackageKind = "appimage" | "deb" | "flatpak" | "snap" | "native" | "unsupported";
 
 export interface LinuxUpdaterSupport {
   packageKind: LinuxUpdaterPackageKind;
@@ -12,6 +13,7 @@ export interface LinuxUpdaterSupport {
 interface LinuxUpdaterSupportOptions {
   platform?: NodeJS.Platform;
   env?: NodeJS.ProcessEnv;
+  fileExists?: (path: string) => boolean;
   commandExists?: (command: string, env: NodeJS.ProcessEnv) => boolean;
   readOsRelease?: () => string | null;
 }
@@ -19,6 +21,10 @@ interface LinuxUpdaterSupportOptions {
 const UNSUPPORTED_LINUX_UPDATER_MESSAGE =
   "Automatic Linux updates are not available for this install on this system. Download the AppImage from GitHub Releases, or use a Debian/Ubuntu package on a Debian-compatible system with dpkg or apt.";
 
+const FLATPAK_UPDATER_MESSAGE =
+  "OpenNOW was installed with Flatpak. Update it from your software center or with `flatpak update`.";
+const SNAP_UPDATER_MESSAGE = "OpenNOW was installed as a Snap. Snaps update automatically, or run `snap refresh`.";
+
 function hasValue(value: string | undefined): boolean {
   return Boolean(value?.trim());
 }
@@ -107,6 +113,22 @@ export function getLinuxUpdaterSupport(options: LinuxUpdaterSupportOptions = {})
   }
 
   const env = options.env ?? process.env;
+  // Sandboxed packages are replaced by their package manager; an in-place update would be undone or fail.
+  if (isFlatpakSandbox(env, options.fileExists)) {
+    return {
+      packageKind: "flatpak",
+      supported: false,
+      message: FLATPAK_UPDATER_MESSAGE,
+    };
+  }
+  if (hasValue(env.SNAP)) {
+    return {
+      packageKind: "snap",
+      supported: false,
+      message: SNAP_UPDATER_MESSAGE,
+    };
+  }
+
   if (hasValue(env.APPIMAGE)) {
     return {
       packageKind: "appimage",
@@ -1,4 +1,3 @@
-import { app } from "electron";
 import { Buffer } from "node:buffer";
 import { randomUUID } from "node:crypto";
 import { createWriteStream } from "node:fs";
@@ -18,6 +17,7 @@ import {
   dataUrlToBuffer,
   sanitizeTitleForFileName,
 } from "./mediaFiles";
+import { getMediaRootDirectory } from "../mediaPaths";
 
 const RECORDING_LIMIT = 20;
 
@@ -30,7 +30,7 @@ interface ActiveRecording {
 const activeRecordings = new Map<string, ActiveRecording>();
 
 export function getRecordingsDirectory(): string {
-  return join(app.getPath("pictures"), "OpenNOW", "Recordings");
+  return join(getMediaRootDirectory(), "Recordings");
 }
 
 export async function ensureRecordingsDirectory(): Promise<string> {
@@ -229,6 +229,11 @@ export async function deleteRecording(
   await unlink(join(dir, `${stem}-thumb.jpg`)).catch(() => undefined);
 }
 
+/** In-progress recordings stay; their temp files are still being written. */
+export function activeRecordingPaths(): Set<string> {
+  return new Set([...activeRecordings.values()].map((rec) => rec.tempPath));
+}
+
 export async function getRecordingFilePath(id: string): Promise<string> {
   assertSafeRecordingId(id);
   const dir = await ensureRecordingsDirectory();
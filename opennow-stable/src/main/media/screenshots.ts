@@ -13,6 +13,7 @@ import {
   dataUrlToBuffer,
   sanitizeTitleForFileName,
 } from "./mediaFiles";
+import { getMediaRootDirectory } from "../mediaPaths";
 
 const SCREENSHOT_LIMIT = 60;
 
@@ -22,7 +23,7 @@ export interface ScreenshotSaveAsDeps {
 }
 
 export function getScreenshotDirectory(): string {
-  return join(app.getPath("pictures"), "OpenNOW", "Screenshots");
+  return join(getMediaRootDirectory(), "Screenshots");
 }
 
 export async function ensureScreenshotDirectory(): Promise<string> {
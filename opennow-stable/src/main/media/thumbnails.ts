@@ -1,12 +1,12 @@
-import { app } from "electron";
 import { spawn } from "node:child_process";
 import { createHash } from "node:crypto";
 import { join } from "node:path";
 import { copyFile, mkdir, readFile, stat, unlink, writeFile } from "node:fs/promises";
+import { getAppCacheDirectory } from "../appPaths";
 import { buildImageDataUrl } from "./mediaFiles";
 
 export function getThumbnailCacheDirectory(): string {
-  return join(app.getPath("userData"), "media-thumbs");
+  return join(getAppCacheDirectory(), "media-thumbs");
 }
 
 export async function ensureThumbnailCacheDirectory(): Promise<string> {
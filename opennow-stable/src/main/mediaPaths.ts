@@ -1,6 +1,6 @@
 import { app, protocol } from "electron";
 import { createReadStream } from "node:fs";
-import { join, resolve, relative } from "node:path";
+import { isAbsolute, join, parse, resolve, relative } from "node:path";
 import { Readable } from "node:stream";
 import { realpath, stat } from "node:fs/promises";
 import { isPlayableVideoFilePath } from "@shared/mediaPlayback";
@@ -11,6 +11,41 @@ const OPENNOW_MEDIA_HOST = "opennow";
 
 let openNowMediaProtocolHandleInstalled = false;
 
+let customMediaRoot: string | null = null;
+const pickedMediaDirectories = new Set<string>();
+
+/**
+ * A drive or filesystem root, or the home folder itself. Media listing and
+ * deletion work on the whole media root, so those are never accepted.
+ */
+export function isUnsafeMediaDirectory(directory: string): boolean {
+  const resolved = resolve(directory);
+  return resolved === parse(resolved).root || resolved === resolve(app.getPath("home"));
+}
+
+/** Remembers a folder the user picked in the folder dialog this run. */
+export function recordPickedMediaDirectory(directory: string): void {
+  pickedMediaDirectories.add(resolve(directory));
+}
+
+/** The renderer may only set the media folder to one main handed out from the folder dialog. */
+export function wasMediaDirectoryPicked(directory: string): boolean {
+  return pickedMediaDirectories.has(resolve(directory));
+}
+
+/** Follows the `mediaDirectory` setting; anything but a safe absolute path means the default. */
+export function setMediaRootDirectory(directory: string): void {
+  customMediaRoot = directory && isAbsolute(directory) && !isUnsafeMediaDirectory(directory) ? directory : null;
+}
+
+/**
+ * Screenshots and recordings live under this folder. Inside Flatpak a folder
+ * picked through the FileChooser portal stays reachable across restarts.
+ */
+export function getMediaRootDirectory(): string {
+  return customMediaRoot ?? join(app.getPath("pictures"), "OpenNOW");
+}
+
 function videoMimeTypeForPath(filePath: string): string {
   const lower = filePath.toLowerCase();
   if (lower.endsWith(".webm")) return "video/webm";
@@ -49,12 +84,12 @@ function parseByteRangeHeader(rangeHeader: string, fileSize: number): { start: n
 }
 
 /**
- * Resolve a user-supplied path to a real path under Pictures/OpenNOW, or null if unsafe / missing.
+ * Resolve a user-supplied path to a real path under the media root, or null if unsafe / missing.
  */
 export async function resolveTrustedOpenNowMediaPath(rawFp: string): Promise<string | null> {
   if (typeof rawFp !== "string" || rawFp.length > MAX_MEDIA_PATH_LENGTH) return null;
   try {
-    const allowedRoot = resolve(join(app.getPath("pictures"), "OpenNOW"));
+    const allowedRoot = resolve(getMediaRootDirectory());
     const fpResolved = resolve(rawFp);
     const allowedRootReal = await realpath(allowedRoot).catch(() => allowedRoot);
     const fpReal = await realpath(fpResolved).catch(() => fpResolved);
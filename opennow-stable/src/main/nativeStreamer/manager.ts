@@ -44,6 +44,8 @@ import {
   type NativeStreamerResponse,
 } from "@shared/nativeStreamer";
 import type { NativeStreamerShortcutBindings } from "@shared/gfn";
+import { getAppCacheDirectory, getAppLogsDirectory } from "../appPaths";
+import { describeNativeCrashExit, resolveCrashReportsDirectory, writeCrashReport } from "../crashReports";
 
 type NativeStreamerCommandInput = NativeStreamerCommand extends infer T
   ? T extends NativeStreamerCommand
@@ -66,6 +68,9 @@ interface NativeStreamerManagerOptions extends NativeStreamerCallbacks {
   getCloudGsyncMode(): NativeStreamerFeatureMode;
   getD3dFullscreenMode(): NativeStreamerFeatureMode;
   getExternalRendererEnabled(): boolean;
+  getExperimentalFlags(): string;
+  /** Rules may have been reloaded since launch, so this runs for every spawn */
+  applyDriverWorkaroundEnv(env: NodeJS.ProcessEnv): void;
 }
 
 interface PendingRequest {
@@ -216,7 +221,7 @@ function configureBundledGstreamerRuntime(
     env.GIO_MODULE_DIR = gioModulesDir;
     env.GIO_EXTRA_MODULES = gioModulesDir;
   }
-  const registryDir = join(app.getPath("userData"), "native-streamer", "gstreamer");
+  const registryDir = join(getAppCacheDirectory(), "native-streamer", "gstreamer");
   const registryPath = join(registryDir, `${nativeStreamerPlatformKey()}-registry.bin`);
   mkdirSync(registryDir, { recursive: true });
   env.GST_REGISTRY = registryPath;
@@ -286,6 +291,8 @@ function formatVideoBackendName(backend: string | undefined): string {
       return "V4L2";
     case "vulkan":
       return "Vulkan";
+    case "nvdec":
+      return "NVDEC";
     case "software":
       return "Software";
     default:
@@ -422,7 +429,7 @@ function materializePackagedNativeStreamerCache(
 
   const sourceDirectory = dirname(sourceExecutablePath);
   const cacheDirectory = join(
-    app.getPath("userData"),
+    getAppCacheDirectory(),
     "native-streamer",
     "runtime",
     safePathSegment(app.getVersion()),
@@ -498,6 +505,7 @@ export class NativeStreamerManager {
   private queuedRemoteIceSessionId: string | null = null;
   private queuedRemoteIce: IceCandidatePayload[] = [];
   private lastSurface: NativeRenderSurface | null = null;
+  private lastVolume: number | null = null;
   private surfaceUpdateInFlight = false;
   private surfaceUpdateQueued = false;
 
@@ -641,6 +649,7 @@ export class NativeStreamerManager {
         activeVideoBackend,
         codecSummary,
         zeroCopySummary,
+        activeFlags: this.capabilities?.activeFlags,
         gstreamerRuntime: effectiveRuntime,
         message: gstreamerAvailable
           ? `${effectiveRuntime.message} Video path: ${formatVideoBackendName(activeVideoBackend?.backend)}.`
@@ -761,6 +770,48 @@ export class NativeStreamerManager {
     });
   }
 
+  updateVolume(volume: number): void {
+    this.lastVolume = Math.max(0, Math.min(1, volume));
+    this.sendVolume();
+  }
+
+  private sendVolume(): void {
+    if (!this.child || this.lastVolume === null) {
+      return;
+    }
+
+    void this.request({
+      type: "volume",
+      volume: this.lastVolume,
+    }, CONTROL_TIMEOUT_MS).catch((error) => {
+      console.warn("[NativeStreamer] Failed to update native output volume:", error);
+    });
+  }
+
+  async selectVideoLayer(layerId: string): Promise<void> {
+    if (!this.child || !this.activeSessionId) {
+      throw new Error("Native streamer is not running");
+    }
+
+    await this.request({
+      type: "video-layer",
+      videoLayer: layerId,
+    }, CONTROL_TIMEOUT_MS);
+  }
+
+  updateRenderFpsCap(renderFpsCap: number): void {
+    if (!this.child || !this.activeSessionId) {
+      return;
+    }
+
+    void this.request({
+      type: "render-fps-cap",
+      renderFpsCap,
+    }, CONTROL_TIMEOUT_MS).catch((error) => {
+      console.warn("[NativeStreamer] Failed to update native render FPS cap:", error);
+    });
+  }
+
   async stop(reason = "stopped"): Promise<void> {
     const child = this.child;
     this.activeSessionId = null;
@@ -856,7 +907,9 @@ export class NativeStreamerManager {
     const childEnv: NodeJS.ProcessEnv = {
       ...process.env,
       OPENNOW_NATIVE_STREAMER_PROTOCOL: String(NATIVE_STREAMER_PROTOCOL_VERSION),
+      OPENNOW_CRASH_DIR: resolveCrashReportsDirectory(getAppLogsDirectory()),
     };
+    this.options.applyDriverWorkaroundEnv(childEnv);
     delete childEnv.OPENNOW_NATIVE_VIDEO_API;
     delete childEnv.OPENNOW_NATIVE_VIDEO_BACKEND;
     if (videoBackendPreference !== "auto") {
@@ -870,6 +923,12 @@ export class NativeStreamerManager {
     if (backendPreference !== "auto") {
       childEnv.OPENNOW_NATIVE_STREAMER_BACKEND = backendPreference;
     }
+    const experimentalFlags = this.options.getExperimentalFlags().trim();
+    if (experimentalFlags) {
+      childEnv.OPENNOW_NATIVE_SETTINGS_FLAGS = experimentalFlags;
+    } else {
+      delete childEnv.OPENNOW_NATIVE_SETTINGS_FLAGS;
+    }
     const runtimeStatus = configureBundledGstreamerRuntime(childEnv, executablePath);
     this.gstreamerRuntime = runtimeStatus;
     if (runtimeStatus.bundled) {
@@ -910,6 +969,17 @@ export class NativeStreamerManager {
 
     child.once("exit", (code, signal) => {
       const reason = signal ? `signal ${signal}` : `exit code ${code ?? "unknown"}`;
+      const crash = describeNativeCrashExit(code, signal);
+      if (crash && this.child === child) {
+        // Hard crashes never reach the streamer's panic hook, so the host records them instead.
+        writeCrashReport(resolveCrashReportsDirectory(getAppLogsDirectory()), {
+          source: "native-streamer",
+          reason: crash,
+          appVersion: app.getVersion(),
+          details: this.activeSessionId ? "A native streaming session was active." : undefined,
+          logs: this.stderrTail,
+        });
+      }
       this.handleProcessExit(reason);
     });
 
@@ -925,6 +995,9 @@ export class NativeStreamerManager {
 
     this.capabilities = response.capabilities;
     console.log("[NativeStreamer] Capabilities:", response.capabilities);
+    if (response.capabilities.activeFlags?.length) {
+      console.log("[NativeStreamer] Experimental flags:", response.capabilities.activeFlags.join(", "));
+    }
     if (response.capabilities.protocolVersion !== NATIVE_STREAMER_PROTOCOL_VERSION) {
       throw new Error(
         `Native streamer reported protocolVersion=${response.capabilities.protocolVersion}, expected ${NATIVE_STREAMER_PROTOCOL_VERSION}.`,
@@ -932,6 +1005,7 @@ export class NativeStreamerManager {
     }
     this.assertBackendPreference(response.capabilities, backendPreference);
     await this.flushSurfaceUpdate();
+    this.sendVolume();
   }
 
   private assertBackendPreference(
@@ -1228,6 +1302,17 @@ export class NativeStreamerManager {
       return;
     }
 
+    if (message.type === "video-recovery") {
+      const summary = `[NativeStreamer] Video stall ${message.outcome} after ${message.stallMs}ms: step=${message.step ?? "none"} attempt=${message.recoveryAttempt} stage=${message.likelyStage}`;
+      if (message.outcome === "recovered") {
+        console.log(summary);
+      } else {
+        console.warn(summary);
+      }
+      this.options.emit({ type: "log", message: summary });
+      return;
+    }
+
     if (message.type === "video-transition") {
       const transition = message.transition;
       const summary = transition.summary ?? `${transition.transitionType} @ ${transition.atMs}ms`;
@@ -1243,6 +1328,22 @@ export class NativeStreamerManager {
       return;
     }
 
+    if (message.type === "video-layers") {
+      const summary = message.layers
+        .map((layer) => (layer.width && layer.height ? `${layer.id}=${layer.width}x${layer.height}` : layer.id))
+        .join(", ");
+      console.log(
+        `[NativeStreamer] Server offers video layers: ${summary}; active ${message.active}; ${message.pausedAtSender ? "inactive layers paused over RTCP" : "all layers keep arriving"}`,
+      );
+      this.options.emit({
+        type: "native-stream-layers",
+        layers: message.layers,
+        active: message.active,
+        pausedAtSender: message.pausedAtSender,
+      });
+      return;
+    }
+
     if (message.type === "stats") {
       this.options.emit({
         type: "native-stream-stats",
@@ -0,0 +1,26 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { diffNetworkAddresses, networkAddressSignature } from "./networkMonitor";
+
+test("ignores loopback and link-local addresses in the interface signature", () => {
+  const signature = networkAddressSignature({
+    lo: [{ address: "127.0.0.1", netmask: "255.0.0.0", family: "IPv4", mac: "00:00:00:00:00:00", internal: true, cidr: "127.0.0.1/8" }],
+    wlan0: [
+      { address: "192.168.1.20", netmask: "255.255.255.0", family: "IPv4", mac: "aa:bb:cc:dd:ee:ff", internal: false, cidr: "192.168.1.20/24" },
+      { address: "fe80::1", netmask: "ffff:ffff:ffff:ffff::", family: "IPv6", mac: "aa:bb:cc:dd:ee:ff", internal: false, cidr: "fe80::1/64", scopeid: 3 },
+    ],
+  });
+
+  assert.deepEqual(signature, ["wlan0/IPv4/192.168.1.20"]);
+});
+
+test("reports interfaces added and removed when docking", () => {
+  const change = diffNetworkAddresses(
+    ["wlan0/IPv4/192.168.1.20"],
+    ["eth0/IPv4/10.0.0.5", "wlan0/IPv4/192.168.1.20"],
+  );
+
+  assert.deepEqual(change, { added: ["eth0/IPv4/10.0.0.5"], removed: [] });
+  assert.equal(diffNetworkAddresses(["eth0/IPv4/10.0.0.5"], ["eth0/IPv4/10.0.0.5"]), null);
+});
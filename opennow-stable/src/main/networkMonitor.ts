@@ -0,0 +1,59 @@
+import { networkInterfaces, type NetworkInterfaceInfo } from "node:os";
+
+import type { NetworkChangeEvent } from "@shared/gfn";
+import { taskSupervisor } from "./services/taskSupervisor";
+
+const NETWORK_POLL_INTERVAL_MS = 2000;
+const NETWORK_POLL_TASK = "network-monitor";
+
+/**
+ * Flattens the routable addresses of every interface into sorted
+ * `name/family/address` entries. Loopback and IPv6 link-local addresses are
+ * skipped: they never carry the stream and churn on some drivers.
+ */
+export function networkAddressSignature(
+  interfaces: NodeJS.Dict<NetworkInterfaceInfo[]>,
+): string[] {
+  const entries: string[] = [];
+  for (const [name, addresses] of Object.entries(interfaces)) {
+    for (const info of addresses ?? []) {
+      if (info.internal) continue;
+      if (info.family === "IPv6" && info.address.toLowerCase().startsWith("fe80:")) continue;
+      entries.push(`${name}/${info.family}/${info.address}`);
+    }
+  }
+  return entries.sort();
+}
+
+export function diffNetworkAddresses(previous: string[], next: string[]): NetworkChangeEvent | null {
+  const before = new Set(previous);
+  const after = new Set(next);
+  const added = next.filter((entry) => !before.has(entry));
+  const removed = previous.filter((entry) => !after.has(entry));
+  return added.length > 0 || removed.length > 0 ? { added, removed } : null;
+}
+
+/** Polls the OS interface table; Electron has no cross-platform interface-change event. */
+export class NetworkChangeMonitor {
+  private signature: string[] = [];
+
+  start(onChange: (change: NetworkChangeEvent) => void): void {
+    this.signature = networkAddressSignature(networkInterfaces());
+    taskSupervisor.spawn({
+      name: NETWORK_POLL_TASK,
+      intervalMs: NETWORK_POLL_INTERVAL_MS,
+      run: () => {
+        const next = networkAddressSignature(networkInterfaces());
+        const change = diffNetworkAddresses(this.signature, next);
+        this.signature = next;
+        if (change) {
+          onChange(change);
+        }
+      },
+    });
+  }
+
+  stop(): void {
+    taskSupervisor.stop(NETWORK_POLL_TASK);
+  }
+}
@@ -0,0 +1,55 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import {
+  PARENTAL_PIN_HASH_SUPPORTED,
+  PinAttemptLimiter,
+  type PinAttemptState,
+  hashParentalPin,
+  parseParentalPinHash,
+  verifyParentalPin,
+} from "./parentalPin";
+
+test("pin hashes round-trip and reject the wrong pin", { skip: !PARENTAL_PIN_HASH_SUPPORTED }, async () => {
+  const encoded = await hashParentalPin("4821");
+  assert.match(encoded, /^\$argon2id\$v=19\$m=\d+,t=\d+,p=\d+\$/);
+  assert.notEqual(encoded, await hashParentalPin("4821"));
+  assert.equal(await verifyParentalPin("4821", encoded), true);
+  assert.equal(await verifyParentalPin("4822", encoded), false);
+});
+
+test("malformed stored hashes never verify", async () => {
+  assert.equal(parseParentalPinHash("plain-text-pin"), null);
+  assert.equal(await verifyParentalPin("1234", "$argon2i$v=19$m=1,t=1,p=1$AAAA$AAAA"), false);
+  assert.deepEqual(parseParentalPinHash("$argon2id$v=19$m=19456,t=2,p=1$AAAA$AAAA")?.memory, 19456);
+});
+
+test("limiter blocks after repeated misses until the cooldown passes", () => {
+  const limiter = new PinAttemptLimiter(3, 1_000);
+  limiter.record(false, 0);
+  limiter.record(false, 0);
+  assert.equal(limiter.isBlocked(0), false);
+  limiter.record(false, 0);
+  assert.equal(limiter.isBlocked(500), true);
+  assert.equal(limiter.isBlocked(1_000), false);
+  limiter.record(false, 1_000);
+  assert.equal(limiter.isBlocked(1_500), true);
+  limiter.record(true, 2_000);
+  assert.equal(limiter.isBlocked(2_000), false);
+});
+
+test("limiter keeps a lockout across restarts through its saved state", () => {
+  let saved: PinAttemptState = { failures: 0, blockedUntil: 0 };
+  const first = new PinAttemptLimiter(2, 1_000, saved, (state) => { saved = state; }, 0);
+  first.record(false, 0);
+  first.record(false, 0);
+  assert.deepEqual(saved, { failures: 2, blockedUntil: 1_000 });
+
+  const restarted = new PinAttemptLimiter(2, 1_000, saved, undefined, 100);
+  assert.equal(restarted.isBlocked(500), true);
+  assert.equal(restarted.isBlocked(1_000), false);
+
+  // A lockout saved far in the future is capped at one cooldown from now.
+  const skewed = new PinAttemptLimiter(2, 1_000, { failures: 2, blockedUntil: 1_000_000 }, undefined, 0);
+  assert.equal(skewed.isBlocked(1_000), false);
+});
@@ -0,0 +1,159 @@
+import crypto from "node:crypto";
+import { existsSync, readFileSync, writeFileSync } from "node:fs";
+
+interface Argon2Parameters {
+  message: string | Buffer;
+  nonce: Buffer;
+  parallelism: number;
+  tagLength: number;
+  /** KiB */
+  memory: number;
+  passes: number;
+}
+
+type Argon2 = (
+  algorithm: "argon2id",
+  parameters: Argon2Parameters,
+  callback: (error: Error | null, derivedKey: Buffer) => void,
+) => void;
+
+/** node:crypto gained argon2 in Node 24.7; @types/node here predates it. */
+const argon2 = (crypto as unknown as { argon2?: Argon2 }).argon2;
+
+export const PARENTAL_PIN_HASH_SUPPORTED = typeof argon2 === "function";
+
+const DEFAULT_PARAMETERS = { memory: 19_456, passes: 2, parallelism: 1, tagLength: 32 } as const;
+const SALT_BYTES = 16;
+
+function derive(pin: string, salt: Buffer, parameters: Omit<Argon2Parameters, "message" | "nonce">): Promise<Buffer> {
+  if (!argon2) {
+    return Promise.reject(new Error("Argon2 is not available in this runtime"));
+  }
+  return new Promise((resolve, reject) => {
+    argon2("argon2id", { ...parameters, message: pin, nonce: salt }, (error, derivedKey) => {
+      if (error) {
+        reject(error);
+      } else {
+        resolve(derivedKey);
+      }
+    });
+  });
+}
+
+function toBase64(buffer: Buffer): string {
+  return buffer.toString("base64").replace(/=+$/, "");
+}
+
+/** Encodes the hash in the PHC string format so the cost can change without breaking stored PINs. */
+export async function hashParentalPin(pin: string): Promise<string> {
+  const salt = crypto.randomBytes(SALT_BYTES);
+  const { memory, passes, parallelism } = DEFAULT_PARAMETERS;
+  const hash = await derive(pin, salt, DEFAULT_PARAMETERS);
+  return `$argon2id$v=19$m=${memory},t=${passes},p=${parallelism}$${toBase64(salt)}$${toBase64(hash)}`;
+}
+
+interface ParsedPinHash {
+  memory: number;
+  passes: number;
+  parallelism: number;
+  salt: Buffer;
+  hash: Buffer;
+}
+
+export function parseParentalPinHash(encoded: string): ParsedPinHash | null {
+  const match = /^\$argon2id\$v=19\$m=(\d+),t=(\d+),p=(\d+)\$([A-Za-z0-9+/]+)\$([A-Za-z0-9+/]+)$/.exec(encoded);
+  if (!match) {
+    return null;
+  }
+  const [, memory, passes, parallelism, salt, hash] = match;
+  return {
+    memory: Number(memory),
+    passes: Number(passes),
+    parallelism: Number(parallelism),
+    salt: Buffer.from(salt, "base64"),
+    hash: Buffer.from(hash, "base64"),
+  };
+}
+
+export async function verifyParentalPin(pin: string, encoded: string): Promise<boolean> {
+  const parsed = parseParentalPinHash(encoded);
+  if (!parsed || parsed.hash.length === 0) {
+    return false;
+  }
+  const candidate = await derive(pin, parsed.salt, {
+    memory: parsed.memory,
+    passes: parsed.passes,
+    parallelism: parsed.parallelism,
+    tagLength: parsed.hash.length,
+  });
+  return crypto.timingSafeEqual(candidate, parsed.hash);
+}
+
+export interface PinAttemptState {
+  failures: number;
+  /** Epoch ms; 0 when not blocked */
+  blockedUntil: number;
+}
+
+/**
+ * Slows down guessing a 4-digit PIN: after a few misses every attempt waits out a cooldown.
+ * The state is handed to `onChange` after every attempt so a restart does not clear a lockout.
+ */
+export class PinAttemptLimiter {
+  private failures: number;
+  private blockedUntil: number;
+
+  constructor(
+    private readonly maxFailures = 5,
+    private readonly cooldownMs = 30_000,
+    initial: PinAttemptState = { failures: 0, blockedUntil: 0 },
+    private readonly onChange?: (state: PinAttemptState) => void,
+    now = Date.now(),
+  ) {
+    this.failures = initial.failures;
+    // A clock set back must not stretch a lockout past one cooldown.
+    this.blockedUntil = Math.min(initial.blockedUntil, now + cooldownMs);
+  }
+
+  isBlocked(now = Date.now()): boolean {
+    return now < this.blockedUntil;
+  }
+
+  record(success: boolean, now = Date.now()): void {
+    if (success) {
+      this.failures = 0;
+      this.blockedUntil = 0;
+    } else {
+      this.failures += 1;
+      if (this.failures >= this.maxFailures) {
+        this.blockedUntil = now + this.cooldownMs;
+      }
+    }
+    this.onChange?.({ failures: this.failures, blockedUntil: this.blockedUntil });
+  }
+}
+
+export function loadPinAttemptState(path: string): PinAttemptState {
+  try {
+    if (existsSync(path)) {
+      const parsed = JSON.parse(readFileSync(path, "utf-8")) as Partial<PinAttemptState>;
+      const failures = Number(parsed.failures);
+      const blockedUntil = Number(parsed.blockedUntil);
+      return {
+        failures: Number.isFinite(failures) && failures > 0 ? Math.floor(failures) : 0,
+        blockedUntil: Number.isFinite(blockedUntil) && blockedUntil > 0 ? blockedUntil : 0,
+      };
+    }
+  } catch (error) {
+    console.warn("[Parental] Failed to read PIN attempt state:", error);
+  }
+  return { failures: 0, blockedUntil: 0 };
+}
+
+export function savePinAttemptState(path: string, state: PinAttemptState): void {
+  try {
+    writeFileSync(path, JSON.stringify(state), "utf-8");
+  } catch (error) {
+    console.warn("[Parental] Failed to save PIN attempt state:", error);
+  }
+}
@@ -0,0 +1,49 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { SessionPowerInhibitor, type PowerSaveBlockerApi } from "./powerInhibit";
+
+function createFakeBlocker(): PowerSaveBlockerApi & { started: Set<number>; starts: number } {
+  let nextId = 1;
+  const started = new Set<number>();
+  return {
+    started,
+    starts: 0,
+    start(type) {
+      assert.equal(type, "prevent-display-sleep");
+      this.starts += 1;
+      const id = nextId++;
+      started.add(id);
+      return id;
+    },
+    stop(id) {
+      return started.delete(id);
+    },
+    isStarted(id) {
+      return started.has(id);
+    },
+  };
+}
+
+test("holds a single display sleep blocker while a session is active", () => {
+  const blocker = createFakeBlocker();
+  const inhibitor = new SessionPowerInhibitor(blocker);
+
+  inhibitor.acquire("session a");
+  inhibitor.acquire("session a");
+
+  assert.equal(blocker.starts, 1);
+  assert.equal(inhibitor.isActive(), true);
+});
+
+test("releases the blocker on disconnect and tolerates repeated releases", () => {
+  const blocker = createFakeBlocker();
+  const inhibitor = new SessionPowerInhibitor(blocker);
+
+  inhibitor.acquire("session a");
+  inhibitor.release("signaling disconnect");
+  inhibitor.release("signaling disconnect");
+
+  assert.equal(blocker.started.size, 0);
+  assert.equal(inhibitor.isActive(), false);
+});
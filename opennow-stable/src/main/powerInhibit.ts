@@ -0,0 +1,54 @@
+export interface PowerSaveBlockerApi {
+  start(type: "prevent-app-suspension" | "prevent-display-sleep"): number;
+  stop(id: number): boolean;
+  isStarted(id: number): boolean;
+}
+
+/**
+ * Keeps the system awake and the screen unblanked while a stream is active.
+ * Electron maps "prevent-display-sleep" to SetThreadExecutionState on Windows,
+ * an IOPMAssertion on macOS, and an Inhibit call on org.freedesktop.ScreenSaver
+ * over the session bus on Linux.
+ *
+ * Chromium has no org.freedesktop.portal.Inhibit backend, and the portal keeps
+ * an inhibition only while the caller's own D-Bus connection stays open. Electron
+ * gives main no such connection. In Flatpak the ScreenSaver talk-name is enough
+ * for GNOME, KDE and other desktops that export that interface. Desktops that
+ * only offer the portal do not get the inhibition.
+ */
+export class SessionPowerInhibitor {
+  private blockerId: number | null = null;
+  private reason: string | null = null;
+
+  constructor(private readonly blocker: PowerSaveBlockerApi) {}
+
+  acquire(reason: string): void {
+    if (this.blockerId !== null && this.blocker.isStarted(this.blockerId)) {
+      return;
+    }
+    try {
+      this.blockerId = this.blocker.start("prevent-display-sleep");
+      this.reason = reason;
+      console.log(`[PowerInhibit] Sleep and screen blanking inhibited (${reason}).`);
+    } catch (error) {
+      this.blockerId = null;
+      console.warn("[PowerInhibit] Failed to inhibit sleep:", (error as Error).message);
+    }
+  }
+
+  release(reason: string): void {
+    if (this.blockerId === null) {
+      return;
+    }
+    if (this.blocker.isStarted(this.blockerId)) {
+      this.blocker.stop(this.blockerId);
+    }
+    console.log(`[PowerInhibit] Released inhibition from ${this.reason ?? "session"} (${reason}).`);
+    this.blockerId = null;
+    this.reason = null;
+  }
+
+  isActive(): boolean {
+    return this.blockerId !== null && this.blocker.isStarted(this.blockerId);
+  }
+}
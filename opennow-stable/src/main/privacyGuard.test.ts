@@ -0,0 +1,19 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { isPermissionAllowed } from "./privacyGuard";
+
+test("only microphone capture is granted, and the kill switch refuses it too", () => {
+  const open = { microphoneBlocked: false };
+  const blocked = { microphoneBlocked: true };
+
+  assert.equal(isPermissionAllowed("media", open, ["audio"]), true);
+  assert.equal(isPermissionAllowed("media", open), true);
+  assert.equal(isPermissionAllowed("media", open, ["audio", "video"]), false);
+  assert.equal(isPermissionAllowed("display-capture", open), false);
+  assert.equal(isPermissionAllowed("clipboard-read", open), false);
+
+  assert.equal(isPermissionAllowed("media", blocked, ["audio"]), false);
+  assert.equal(isPermissionAllowed("microphone", blocked), false);
+  assert.equal(isPermissionAllowed("pointerLock", blocked), true);
+});
@@ -0,0 +1,39 @@
+/** Permissions the renderer may hold; everything else is refused. */
+const ALLOWED_PERMISSIONS = new Set([
+  "media",
+  "microphone",
+  "fullscreen",
+  "automatic-fullscreen",
+  "pointerLock",
+  "keyboardLock",
+  "speaker-selection",
+]);
+
+const CAPTURE_PERMISSIONS = new Set(["media", "microphone"]);
+
+export interface PrivacyPolicy {
+  /** Kill switch: no microphone capture at all, whatever the microphone mode says */
+  microphoneBlocked: boolean;
+}
+
+/**
+ * The only local capture OpenNOW needs is the microphone for voice chat, so
+ * camera and screen requests are refused even though they share the "media"
+ * permission, and the kill switch refuses the microphone too.
+ */
+export function isPermissionAllowed(
+  permission: string,
+  policy: PrivacyPolicy,
+  mediaTypes: readonly string[] = [],
+): boolean {
+  if (!ALLOWED_PERMISSIONS.has(permission)) {
+    return false;
+  }
+  if (!CAPTURE_PERMISSIONS.has(permission)) {
+    return true;
+  }
+  if (policy.microphoneBlocked) {
+    return false;
+  }
+  return mediaTypes.every((type) => type === "audio");
+}
@@ -0,0 +1,27 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { normalizeReleaseNotes, releaseNotesToText } from "./releaseNotes";
+
+test("release note HTML becomes readable plain text", () => {
+  assert.equal(
+    releaseNotesToText("<h2>What&#39;s new</h2>\n<ul>\n<li>Faster <code>launch</code></li>\n<li>Fix &lt;Esc&gt; &amp; more</li>\n</ul>"),
+    "What's new\n• Faster launch\n• Fix <Esc> & more",
+  );
+  assert.ok(releaseNotesToText("x".repeat(5000)).endsWith("…"));
+});
+
+test("a single note and a full changelog normalize to per-version entries", () => {
+  assert.deepEqual(normalizeReleaseNotes("<p>Fixes</p>", "1.2.0"), [{ version: "1.2.0", notes: "Fixes" }]);
+  assert.deepEqual(
+    normalizeReleaseNotes(
+      [
+        { version: "1.2.0", note: "<p>Newest</p>" },
+        { version: "1.1.0", note: null },
+      ],
+      "1.2.0",
+    ),
+    [{ version: "1.2.0", notes: "Newest" }],
+  );
+  assert.deepEqual(normalizeReleaseNotes(null, "1.2.0"), []);
+});
@@ -0,0 +1,45 @@
+import type { AppUpdateReleaseNote } from "@shared/gfn";
+
+/** GitHub release notes are too long for a dialog past this; the release page has the rest. */
+const MAX_NOTES_LENGTH = 4000;
+
+const HTML_ENTITIES: Record<string, string> = {
+  "&nbsp;": " ",
+  "&lt;": "<",
+  "&gt;": ">",
+  "&quot;": "\"",
+  "&#39;": "'",
+  "&amp;": "&",
+};
+
+/**
+ * The GitHub provider hands over release notes as rendered HTML. The dialog
+ * shows text only, which also keeps release-page markup out of the renderer.
+ */
+export function releaseNotesToText(html: string): string {
+  const text = html
+    .replace(/<br\s*\/?>/gi, "\n")
+    .replace(/<li[^>]*>/gi, "\n• ")
+    .replace(/<\/(p|div|h[1-6]|ul|ol|pre|blockquote)>/gi, "\n")
+    .replace(/<[^>]*>/g, "")
+    .replace(/&(nbsp|lt|gt|quot|#39|amp);/g, (entity) => HTML_ENTITIES[entity] ?? entity)
+    .replace(/[ \t]+\n/g, "\n")
+    .replace(/\n{2,}(?=• )/g, "\n")
+    .replace(/\n{3,}/g, "\n\n")
+    .trim();
+  return text.length > MAX_NOTES_LENGTH ? `${text.slice(0, MAX_NOTES_LENGTH).trimEnd()}…` : text;
+}
+
+/**
+ * electron-updater reports a single string for one release, or one entry per
+ * version when `fullChangelog` is on. Versions without notes are dropped.
+ */
+export function normalizeReleaseNotes(
+  notes: string | ReadonlyArray<{ version: string; note?: string | null }> | null | undefined,
+  version: string,
+): AppUpdateReleaseNote[] {
+  const entries = typeof notes === "string" ? [{ version, note: notes }] : notes ?? [];
+  return entries
+    .map((entry) => ({ version: entry.version, notes: releaseNotesToText(entry.note ?? "") }))
+    .filter((entry) => entry.notes !== "");
+}
@@ -1,7 +1,7 @@
-import { app } from "electron";
 import { mkdir, readFile, writeFile, unlink, readdir, rm } from "node:fs/promises";
 import { existsSync } from "node:fs";
 import { join } from "node:path";
+import { getAppCacheDirectory } from "../appPaths";
 
 interface CacheMetadata {
   timestamp: number;
@@ -18,12 +18,21 @@ const CACHE_TTL_MS = 12 * 60 * 60 * 1000;
 
 const THUMBNAILS_DIRECTORY = "media-thumbs";
 
+export interface CacheCounters {
+  hits: number;
+  staleHits: number;
+  misses: number;
+  writes: number;
+  invalidations: number;
+}
+
 class CacheManager {
-  private cacheDir: string;
   private initialized: boolean = false;
+  private counters: CacheCounters = { hits: 0, staleHits: 0, misses: 0, writes: 0, invalidations: 0 };
 
-  constructor() {
-    this.cacheDir = join(app.getPath("userData"), CACHE_DIRECTORY);
+  /** Resolved lazily; the cache location is only known once main has set up the app paths. */
+  private get cacheDir(): string {
+    return join(getAppCacheDirectory(), CACHE_DIRECTORY);
   }
 
   async initialize(): Promise<void> {
@@ -56,6 +65,7 @@ class CacheManager {
 
     if (!existsSync(filePath)) {
       console.log(`[CACHE] Cache miss (file not found): ${key}`);
+      this.counters.misses += 1;
       return null;
     }
 
@@ -65,6 +75,7 @@ class CacheManager {
 
       if (!parsed.metadata || typeof parsed.metadata.expiresAt !== "number") {
         console.warn(`[CACHE] Cache corrupted (invalid metadata): ${key}`);
+        this.counters.misses += 1;
         await this.invalidateCache(key);
         return null;
       }
@@ -72,15 +83,18 @@ class CacheManager {
       const now = Date.now();
       const ageSeconds = Math.round((now - parsed.metadata.timestamp) / 1000);
       if (now > parsed.metadata.expiresAt) {
+        this.counters.staleHits += 1;
         console.log(
           `[CACHE] Cache hit (stale): ${key} (age: ${ageSeconds}s, expired ${Math.round((now - parsed.metadata.expiresAt) / 1000)}s ago)`,
         );
       } else {
+        this.counters.hits += 1;
         console.log(`[CACHE] Cache hit: ${key} (age: ${ageSeconds}s)`);
       }
       return parsed;
     } catch (error) {
       console.error(`[CACHE] Error reading cache file: ${key}`, error);
+      this.counters.misses += 1;
       try {
         await this.invalidateCache(key);
       } catch (deleteError) {
@@ -108,6 +122,7 @@ class CacheManager {
 
     try {
       await writeFile(filePath, JSON.stringify(cached, null, 2), "utf-8");
+      this.counters.writes += 1;
       console.log(`[CACHE] Saved to cache: ${key}`);
     } catch (error) {
       console.error(`[CACHE] Error writing cache file: ${key}`, error);
@@ -125,6 +140,7 @@ class CacheManager {
 
     try {
       await unlink(filePath);
+      this.counters.invalidations += 1;
       console.log(`[CACHE] Invalidated cache: ${key}`);
     } catch (error) {
       console.error(`[CACHE] Error deleting cache file: ${key}`, error);
@@ -161,6 +177,26 @@ class CacheManager {
     }
   }
 
+  /** Deletes the cached game and library lists, leaving media thumbnails alone. */
+  async deleteEntries(): Promise<void> {
+    if (!this.initialized) {
+      console.warn(`[CACHE] Cache not initialized, skipping deleteEntries`);
+      return;
+    }
+
+    const files = await readdir(this.cacheDir);
+    for (const file of files) {
+      const filePath = join(this.cacheDir, file);
+      try {
+        await unlink(filePath);
+        console.log(`[CACHE] Deleted cache file: ${file}`);
+      } catch (err) {
+        console.error(`[CACHE] Error deleting cache file: ${file}`, err);
+      }
+    }
+    console.log(`[CACHE] Cleared all cache files in ${this.cacheDir}`);
+  }
+
   async deleteAll(): Promise<void> {
     if (!this.initialized) {
       console.warn(`[CACHE] Cache not initialized, skipping deleteAll`);
@@ -168,20 +204,10 @@ class CacheManager {
     }
 
     try {
-      const files = await readdir(this.cacheDir);
-      for (const file of files) {
-        const filePath = join(this.cacheDir, file);
-        try {
-          await unlink(filePath);
-          console.log(`[CACHE] Deleted cache file: ${file}`);
-        } catch (err) {
-          console.error(`[CACHE] Error deleting cache file: ${file}`, err);
-        }
-      }
-      console.log(`[CACHE] Cleared all cache files in ${this.cacheDir}`);
+      await this.deleteEntries();
 
       // Also remove the thumbnail cache directory created by main process
-      const thumbsDir = join(app.getPath("userData"), THUMBNAILS_DIRECTORY);
+      const thumbsDir = join(getAppCacheDirectory(), THUMBNAILS_DIRECTORY);
       try {
         await rm(thumbsDir, { recursive: true, force: true });
         console.log(`[CACHE] Removed thumbnail cache directory: ${thumbsDir}`);
@@ -222,9 +248,17 @@ class CacheManager {
     }
   }
 
+  getCacheDirectory(): string {
+    return this.cacheDir;
+  }
+
   getCacheTtlMs(): number {
     return CACHE_TTL_MS;
   }
+
+  getCounters(): CacheCounters {
+    return { ...this.counters };
+  }
 }
 
 export const cacheManager = new CacheManager();
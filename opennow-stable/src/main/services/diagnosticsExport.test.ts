@@ -0,0 +1,83 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { appendDiagnosticsSections, formatCacheStats, formatSessionTimeline, formatTaskHealth } from "./diagnosticsExport";
+
+const stats = {
+  hits: 6,
+  staleHits: 2,
+  misses: 2,
+  writes: 3,
+  invalidations: 1,
+  refreshCount: 1,
+  lastRefreshAt: Date.UTC(2026, 0, 2, 3, 4, 5),
+  lastRefreshDurationMs: 840,
+  lastRefreshError: null,
+};
+
+test("summarizes cache hit rate and the last background refresh", () => {
+  const text = formatCacheStats(stats);
+  assert.match(text, /lookups=10 hit=6 stale=2 miss=2 hitRate=80%/);
+  assert.match(text, /lastRefresh=2026-01-02T03:04:05\.000Z \(840ms\)$/);
+  assert.match(formatCacheStats({ ...stats, lastRefreshAt: null }), /lastRefresh=never/);
+});
+
+test("appends sections in the export's format", () => {
+  const sections = [{ key: "cacheStats", text: "Game cache:", data: { hits: 1 } }];
+  assert.match(appendDiagnosticsSections("logs", "text", sections), /^logs\n=+\nGame cache:\n$/);
+  assert.deepEqual(JSON.parse(appendDiagnosticsSections(JSON.stringify({ entryCount: 0 }), "json", sections)), {
+    entryCount: 0,
+    cacheStats: { hits: 1 },
+  });
+  assert.equal(appendDiagnosticsSections("No logs captured", "json", sections), "No logs captured");
+});
+
+test("lists background task health with the failure streak of failing tasks", () => {
+  const healthy = {
+    name: "network-monitor",
+    state: "scheduled" as const,
+    intervalMs: 2000,
+    runs: 12,
+    failures: 0,
+    consecutiveFailures: 0,
+    lastSuccessAt: Date.UTC(2026, 0, 2, 3, 4, 5),
+    lastFailureAt: null,
+    lastError: null,
+    nextRunAt: null,
+  };
+  const failing = {
+    ...healthy,
+    name: "game-cache-refresh",
+    state: "backoff" as const,
+    runs: 3,
+    failures: 2,
+    consecutiveFailures: 2,
+    lastSuccessAt: null,
+    lastFailureAt: Date.UTC(2026, 0, 2, 3, 4, 5),
+    lastError: "fetch failed",
+  };
+  const text = formatTaskHealth([healthy, failing]);
+  assert.match(text, /^network-monitor: state=scheduled runs=12 lastSuccess=2026-01-02T03:04:05\.000Z$/m);
+  assert.match(text, /^game-cache-refresh: state=backoff runs=3 lastSuccess=never failures=2 streak=2 .* error=fetch failed$/m);
+  assert.equal(formatTaskHealth([]), "Background tasks:\nnone running");
+});
+
+test("lists timeline events as offsets from the stream start", () => {
+  const startedAtMs = Date.UTC(2026, 0, 2, 3, 4, 5);
+  const text = formatSessionTimeline({
+    startedAtMs,
+    endedAtMs: null,
+    events: [
+      { atMs: startedAtMs, kind: "session", code: "streamStarted" },
+      { atMs: startedAtMs + 754_400, kind: "resolution", code: "resolutionChanged", params: { from: "1920x1080", to: "1280x720" } },
+    ],
+  });
+  assert.equal(
+    text,
+    [
+      "Session timeline (started 2026-01-02T03:04:05.000Z, still running):",
+      "+00:00 session    streamStarted",
+      "+12:34 resolution resolutionChanged from=1920x1080 to=1280x720",
+    ].join("\n"),
+  );
+});
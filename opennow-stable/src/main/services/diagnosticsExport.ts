@@ -0,0 +1,70 @@
+import type { SessionTimeline } from "@shared/gfn";
+import { formatTimelineEventForLog, formatTimelineOffset } from "@shared/sessionTimeline";
+import type { CacheCounters } from "./cacheManager";
+import type { RefreshStats } from "./refreshScheduler";
+import type { TaskHealth } from "./taskSupervisor";
+
+export type CacheStats = CacheCounters & RefreshStats;
+
+export interface DiagnosticsSection {
+  /** Property name used in JSON exports */
+  key: string;
+  /** Block appended to text exports */
+  text: string;
+  data: unknown;
+}
+
+/** Appends main-process diagnostics to a log export in the export's own format. */
+export function appendDiagnosticsSections(
+  exported: string,
+  format: "text" | "json",
+  sections: DiagnosticsSection[],
+): string {
+  if (format === "text") {
+    const separator = "=".repeat(60);
+    return sections.reduce((text, section) => `${text}\n${separator}\n${section.text}\n`, exported);
+  }
+  try {
+    const parsed = JSON.parse(exported) as Record<string, unknown>;
+    for (const section of sections) {
+      parsed[section.key] = section.data;
+    }
+    return JSON.stringify(parsed, null, 2);
+  } catch {
+    return exported;
+  }
+}
+
+export function formatCacheStats(stats: CacheStats): string {
+  const lookups = stats.hits + stats.staleHits + stats.misses;
+  const hitRate = lookups > 0 ? Math.round(((stats.hits + stats.staleHits) / lookups) * 100) : 0;
+  const lastRefresh = stats.lastRefreshAt
+    ? `${new Date(stats.lastRefreshAt).toISOString()} (${stats.lastRefreshDurationMs ?? "?"}ms)`
+    : "never";
+  return [
+    "Game cache:",
+    `lookups=${lookups} hit=${stats.hits} stale=${stats.staleHits} miss=${stats.misses} hitRate=${hitRate}%`,
+    `writes=${stats.writes} invalidations=${stats.invalidations} refreshes=${stats.refreshCount}`,
+    `lastRefresh=${lastRefresh}${stats.lastRefreshError ? ` error=${stats.lastRefreshError}` : ""}`,
+  ].join("\n");
+}
+
+export function formatTaskHealth(tasks: TaskHealth[]): string {
+  const time = (at: number | null): string => (at ? new Date(at).toISOString() : "never");
+  const lines = tasks.map((task) => {
+    const failures = task.failures > 0
+      ? ` failures=${task.failures} streak=${task.consecutiveFailures} lastFailure=${time(task.lastFailureAt)} error=${task.lastError}`
+      : "";
+    return `${task.name}: state=${task.state} runs=${task.runs} lastSuccess=${time(task.lastSuccessAt)}${failures}`;
+  });
+  return ["Background tasks:", ...(lines.length > 0 ? lines : ["none running"])].join("\n");
+}
+
+/** Offsets from the stream start, so the moment the stream degraded reads at a glance. */
+export function formatSessionTimeline(timeline: SessionTimeline): string {
+  const ended = timeline.endedAtMs === null ? "still running" : `ended ${new Date(timeline.endedAtMs).toISOString()}`;
+  const lines = timeline.events.map(
+    (event) => `${formatTimelineOffset(timeline.startedAtMs, event.atMs)} ${event.kind.padEnd(10)} ${formatTimelineEventForLog(event)}`,
+  );
+  return [`Session timeline (started ${new Date(timeline.startedAtMs).toISOString()}, ${ended}):`, ...lines].join("\n");
+}
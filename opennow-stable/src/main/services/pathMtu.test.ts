@@ -0,0 +1,27 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { findPathMtu, mtuProbeCommand } from "./pathMtu";
+
+test("the largest unfragmented candidate is the path MTU", async () => {
+  const probed: number[] = [];
+  const mtu = await findPathMtu(async (payload) => {
+    probed.push(payload);
+    return payload + 28 <= 1420;
+  });
+  assert.equal(mtu, 1420);
+  assert.equal(probed.length, 13);
+  assert.equal(Math.max(...probed), 1472);
+});
+
+test("a path that drops every probe reports no MTU", async () => {
+  assert.equal(await findPathMtu(async () => false), null);
+});
+
+test("each platform sets don't-fragment with its own ping flags", () => {
+  assert.deepEqual(mtuProbeCommand("linux", "203.0.113.9", 1372).args, [
+    "-c", "1", "-W", "1", "-M", "do", "-s", "1372", "203.0.113.9",
+  ]);
+  assert.ok(mtuProbeCommand("win32", "203.0.113.9", 1372).args.includes("-f"));
+  assert.ok(mtuProbeCommand("darwin", "203.0.113.9", 1372).args.includes("-D"));
+});
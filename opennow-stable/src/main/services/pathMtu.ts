@@ -0,0 +1,89 @@
+import { execFile } from "node:child_process";
+import { promises as dns } from "node:dns";
+import { isIP } from "node:net";
+import type { PathMtuResult } from "@shared/gfn";
+
+/** IPv4 (20) plus ICMP (8) header bytes on top of the ping payload. */
+const ICMP_IPV4_OVERHEAD = 28;
+/**
+ * Ethernet, PPPoE, common VPN/tunnel sizes and IPv4's 576 floor. Probing this
+ * ladder all at once finishes within one ping timeout, where a binary search
+ * would wait out every failed step in turn.
+ */
+export const PATH_MTU_CANDIDATES = [1500, 1492, 1480, 1460, 1440, 1420, 1400, 1380, 1360, 1280, 1200, 1024, 576];
+const PROBE_TIMEOUT_MS = 2500;
+
+export type MtuProbe = (payloadBytes: number) => Promise<boolean>;
+
+export interface ProbeCommand {
+  file: string;
+  args: string[];
+}
+
+/** One echo request with the don't-fragment bit set, in each platform's ping dialect. */
+export function mtuProbeCommand(platform: NodeJS.Platform, address: string, payloadBytes: number): ProbeCommand {
+  const size = String(payloadBytes);
+  if (platform === "win32") {
+    return { file: "ping", args: ["-n", "1", "-w", "1000", "-f", "-l", size, address] };
+  }
+  if (platform === "darwin") {
+    return { file: "ping", args: ["-c", "1", "-t", "1", "-D", "-s", size, address] };
+  }
+  return { file: "ping", args: ["-c", "1", "-W", "1", "-M", "do", "-s", size, address] };
+}
+
+/**
+ * Largest candidate that crossed the path unfragmented. When even the 576
+ * probe fails, ICMP is filtered on the path and nothing can be learned.
+ */
+export async function findPathMtu(probe: MtuProbe): Promise<number | null> {
+  const results = await Promise.all(PATH_MTU_CANDIDATES.map((mtu) => probe(mtu - ICMP_IPV4_OVERHEAD)));
+  const passed = PATH_MTU_CANDIDATES.filter((_, index) => results[index]);
+  return passed.length > 0 ? Math.max(...passed) : null;
+}
+
+function runProbe(command: ProbeCommand): Promise<boolean> {
+  return new Promise((resolve, reject) => {
+    execFile(command.file, command.args, { timeout: PROBE_TIMEOUT_MS, windowsHide: true }, (error, stdout) => {
+      if ((error as NodeJS.ErrnoException | null)?.code === "ENOENT") {
+        reject(error);
+        return;
+      }
+      // Windows ping exits 0 on "Packet needs to be fragmented but DF set", so read the reply too.
+      resolve(!error && !/fragment|unreachable|timed out/i.test(stdout));
+    });
+  });
+}
+
+/** Paths rarely change within an app run, so reconnects reuse the first measurement. */
+const cache = new Map<string, Promise<PathMtuResult>>();
+
+export function probePathMtu(target: string, platform: NodeJS.Platform = process.platform): Promise<PathMtuResult> {
+  const key = target.trim();
+  const cached = cache.get(key);
+  if (cached) {
+    return cached;
+  }
+  const pending = measure(key, platform);
+  cache.set(key, pending);
+  return pending;
+}
+
+async function measure(target: string, platform: NodeJS.Platform): Promise<PathMtuResult> {
+  // The header budget above is IPv4's, so hostnames resolve to an IPv4 address and IPv6-only servers are skipped.
+  let address = target;
+  if (isIP(target) !== 4) {
+    try {
+      ({ address } = await dns.lookup(target, { family: 4 }));
+    } catch {
+      return { target, mtu: null, error: "No IPv4 address to probe" };
+    }
+  }
+  let mtu: number | null;
+  try {
+    mtu = await findPathMtu((payload) => runProbe(mtuProbeCommand(platform, address, payload)));
+  } catch {
+    return { target: address, mtu: null, error: "ping is not installed" };
+  }
+  return mtu === null ? { target: address, mtu: null, error: "ICMP probes were blocked" } : { target: address, mtu };
+}
@@ -0,0 +1,79 @@
+import assert from "node:assert/strict";
+import { generateKeyPairSync, sign } from "node:crypto";
+import test from "node:test";
+
+import {
+  PrintedWasteCache,
+  resolvePrintedWasteSource,
+  verifyPrintedWasteSignature,
+} from "./printedWaste";
+
+test("cache reuses fresh data and serves stale data while backing off", async () => {
+  let now = 0;
+  let calls = 0;
+  const cache = new PrintedWasteCache<string>(60_000, () => now);
+  const succeed = (value: string) => async () => {
+    calls += 1;
+    return value;
+  };
+  const fail = async (): Promise<string> => {
+    calls += 1;
+    throw new Error("HTTP 503");
+  };
+
+  assert.equal(await cache.get(succeed("a")), "a");
+  now = 30_000;
+  assert.equal(await cache.get(succeed("b")), "a");
+  assert.equal(calls, 1);
+
+  now = 61_000;
+  assert.equal(await cache.get(fail), "a");
+  now = 64_000;
+  assert.equal(await cache.get(fail), "a");
+  assert.equal(calls, 2);
+
+  now = 66_000;
+  assert.equal(await cache.get(fail), "a");
+  now = 72_000;
+  assert.equal(await cache.get(fail), "a");
+  assert.equal(calls, 3);
+
+  now = 76_000;
+  assert.equal(await cache.get(succeed("c")), "c");
+  assert.equal(calls, 4);
+});
+
+test("cold cache surfaces the failure and then refuses until the backoff ends", async () => {
+  let now = 0;
+  const cache = new PrintedWasteCache<string>(60_000, () => now);
+  await assert.rejects(cache.get(async () => {
+    throw new Error("HTTP 429");
+  }), /HTTP 429/);
+  now = 1_000;
+  await assert.rejects(cache.get(async () => "late"), /retrying in 4s/);
+  now = 5_000;
+  assert.equal(await cache.get(async () => "ok"), "ok");
+});
+
+test("mirrors serve the PrintedWaste paths and must come with a key", () => {
+  assert.equal(resolvePrintedWasteSource("", "").queueUrl, "https://api.printedwaste.com/gfn/queue/");
+  assert.deepEqual(resolvePrintedWasteSource(" https://queue.example.org/pw/ ", "a2V5"), {
+    queueUrl: "https://queue.example.org/pw/gfn/queue/",
+    serverMappingUrl: "https://queue.example.org/pw/config/GFN_SERVERID_TO_REGION_MAPPING",
+    publicKey: "a2V5",
+  });
+  assert.throws(() => resolvePrintedWasteSource("http://queue.example.org", "a2V5"), /https/);
+  assert.throws(() => resolvePrintedWasteSource("https://queue.example.org", ""), /public key/);
+});
+
+test("server mapping signature covers the exact body", () => {
+  const { publicKey, privateKey } = generateKeyPairSync("ed25519");
+  const rawKey = publicKey.export({ format: "der", type: "spki" }).subarray(-32).toString("base64");
+  const body = '{"status":true,"data":{"NP-AMS-08":{"nuked":false}}}';
+  const signature = sign(null, Buffer.from(body), privateKey).toString("base64");
+
+  assert.equal(verifyPrintedWasteSignature(body, signature, rawKey), true);
+  assert.equal(verifyPrintedWasteSignature(body.replace("false", "true"), signature, rawKey), false);
+  assert.equal(verifyPrintedWasteSignature(body, null, rawKey), false);
+  assert.equal(verifyPrintedWasteSignature(body, signature, "bm90IGEga2V5"), false);
+});
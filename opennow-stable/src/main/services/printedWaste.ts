@@ -2,18 +2,219 @@ import type {
   PrintedWasteQueueData,
   PrintedWasteServerMapping,
 } from "@shared/gfn";
+import { createPublicKey, verify } from "node:crypto";
 import { fetchWithTimeout, withTimeout } from "./requestTimeout";
 
 const PRINTEDWASTE_TIMEOUT_MS = 7000;
 const PRINTEDWASTE_QUEUE_URL = "https://api.printedwaste.com/gfn/queue/";
 const PRINTEDWASTE_SERVER_MAPPING_URL =
   "https://remote.printedwaste.com/config/GFN_SERVERID_TO_REGION_MAPPING";
+const PRINTEDWASTE_SIGNATURE_HEADER = "X-OpenNOW-Signature";
+/** DER prefix that wraps a raw 32-byte Ed25519 key as SubjectPublicKeyInfo. */
+const ED25519_SPKI_PREFIX = Buffer.from("302a300506032b6570032100", "hex");
+/** The queue modal refreshes every two minutes; anything newer is reused. */
+const PRINTEDWASTE_QUEUE_TTL_MS = 60_000;
+const PRINTEDWASTE_SERVER_MAPPING_TTL_MS = 10 * 60_000;
+const PRINTEDWASTE_BACKOFF_BASE_MS = 5_000;
+const PRINTEDWASTE_BACKOFF_MAX_MS = 5 * 60_000;
 
-export async function fetchPrintedWasteQueue(
+/** Error carrying the server's Retry-After hint for rate-limited requests. */
+class PrintedWasteHttpError extends Error {
+  constructor(
+    message: string,
+    readonly retryAfterMs?: number,
+  ) {
+    super(message);
+  }
+}
+
+function retryAfterMs(response: Response): number | undefined {
+  const seconds = Number(response.headers.get("Retry-After"));
+  return Number.isFinite(seconds) && seconds > 0 ? seconds * 1000 : undefined;
+}
+
+/**
+ * Caches one PrintedWaste response. Fresh values are reused for `ttlMs`;
+ * failures back off exponentially and keep serving the last good value, so
+ * an outage or rate limit degrades to slightly old queue data instead of
+ * errors. Only a cold cache surfaces the failure.
+ */
+export class PrintedWasteCache<T> {
+  private value: T | undefined;
+  private fetchedAtMs = 0;
+  private failures = 0;
+  private retryAtMs = 0;
+  private inFlight: Promise<T> | null = null;
+
+  constructor(
+    private readonly ttlMs: number,
+    private readonly now: () => number = Date.now,
+  ) {}
+
+  async get(load: () => Promise<T>): Promise<T> {
+    const now = this.now();
+    if (this.value !== undefined && now - this.fetchedAtMs < this.ttlMs) {
+      return this.value;
+    }
+    if (now < this.retryAtMs) {
+      if (this.value !== undefined) {
+        return this.value;
+      }
+      throw new Error(
+        `PrintedWaste unavailable; retrying in ${Math.ceil((this.retryAtMs - now) / 1000)}s`,
+      );
+    }
+    this.inFlight ??= this.refresh(load).finally(() => {
+      this.inFlight = null;
+    });
+    return this.inFlight;
+  }
+
+  private async refresh(load: () => Promise<T>): Promise<T> {
+    try {
+      const value = await load();
+      this.value = value;
+      this.fetchedAtMs = this.now();
+      this.failures = 0;
+      this.retryAtMs = 0;
+      return value;
+    } catch (error) {
+      this.failures += 1;
+      const backoffMs = Math.min(
+        PRINTEDWASTE_BACKOFF_BASE_MS * 2 ** (this.failures - 1),
+        PRINTEDWASTE_BACKOFF_MAX_MS,
+      );
+      const hintMs =
+        error instanceof PrintedWasteHttpError ? (error.retryAfterMs ?? 0) : 0;
+      this.retryAtMs = this.now() + Math.max(backoffMs, hintMs);
+      if (this.value !== undefined) {
+        console.warn(
+          "[PrintedWaste] Refresh failed, serving cached data:",
+          error,
+        );
+        return this.value;
+      }
+      throw error;
+    }
+  }
+}
+
+/** Where queue data comes from: PrintedWaste itself or a community mirror. */
+export interface PrintedWasteSource {
+  queueUrl: string;
+  serverMappingUrl: string;
+  /** Base64 raw Ed25519 key the server mapping must be signed with, if any. */
+  publicKey: string;
+}
+
+/**
+ * Mirrors serve the same two paths under their own base URL. A mirror
+ * decides which zones are hidden as nuked, so its server mapping is only
+ * used when signed with the configured key.
+ */
+export function resolvePrintedWasteSource(
+  baseUrl: string,
+  publicKey: string,
+): PrintedWasteSource {
+  const base = baseUrl.trim();
+  const key = publicKey.trim();
+  if (!base) {
+    return {
+      queueUrl: PRINTEDWASTE_QUEUE_URL,
+      serverMappingUrl: PRINTEDWASTE_SERVER_MAPPING_URL,
+      publicKey: key,
+    };
+  }
+  const url = new URL(base);
+  if (url.protocol !== "https:") {
+    throw new Error("Queue service URL must use https");
+  }
+  if (!key) {
+    throw new Error("Queue service mirrors need a public key to verify the server mapping");
+  }
+  const root = url.href.replace(/\/+$/, "");
+  return {
+    queueUrl: `${root}/gfn/queue/`,
+    serverMappingUrl: `${root}/config/GFN_SERVERID_TO_REGION_MAPPING`,
+    publicKey: key,
+  };
+}
+
+/** Checks a base64 Ed25519 signature over the exact response body. */
+export function verifyPrintedWasteSignature(
+  body: string,
+  signature: string | null,
+  publicKey: string,
+): boolean {
+  if (!signature) {
+    return false;
+  }
+  try {
+    const rawKey = Buffer.from(publicKey, "base64");
+    if (rawKey.length !== 32) {
+      return false;
+    }
+    const key = createPublicKey({
+      key: Buffer.concat([ED25519_SPKI_PREFIX, rawKey]),
+      format: "der",
+      type: "spki",
+    });
+    return verify(
+      null,
+      Buffer.from(body, "utf8"),
+      key,
+      Buffer.from(signature, "base64"),
+    );
+  } catch {
+    return false;
+  }
+}
+
+let cachedSourceKey = "";
+let queueCache = new PrintedWasteCache<PrintedWasteQueueData>(
+  PRINTEDWASTE_QUEUE_TTL_MS,
+);
+let serverMappingCache = new PrintedWasteCache<PrintedWasteServerMapping>(
+  PRINTEDWASTE_SERVER_MAPPING_TTL_MS,
+);
+
+/** Switching sources must not serve one source's data as the other's. */
+function selectSource(source: PrintedWasteSource): void {
+  const key = JSON.stringify(source);
+  if (key === cachedSourceKey) {
+    return;
+  }
+  cachedSourceKey = key;
+  queueCache = new PrintedWasteCache(PRINTEDWASTE_QUEUE_TTL_MS);
+  serverMappingCache = new PrintedWasteCache(
+    PRINTEDWASTE_SERVER_MAPPING_TTL_MS,
+  );
+}
+
+export function fetchPrintedWasteQueue(
   appVersion: string,
+  source: PrintedWasteSource,
+): Promise<PrintedWasteQueueData> {
+  selectSource(source);
+  return queueCache.get(() => requestPrintedWasteQueue(appVersion, source));
+}
+
+export function fetchPrintedWasteServerMapping(
+  appVersion: string,
+  source: PrintedWasteSource,
+): Promise<PrintedWasteServerMapping> {
+  selectSource(source);
+  return serverMappingCache.get(() =>
+    requestPrintedWasteServerMapping(appVersion, source),
+  );
+}
+
+async function requestPrintedWasteQueue(
+  appVersion: string,
+  source: PrintedWasteSource,
 ): Promise<PrintedWasteQueueData> {
   const response = await fetchWithTimeout(
-    PRINTEDWASTE_QUEUE_URL,
+    source.queueUrl,
     {
       headers: {
         "User-Agent": `opennow/${appVersion}`,
@@ -24,7 +225,10 @@ export async function fetchPrintedWasteQueue(
     "PrintedWaste queue request",
   );
   if (!response.ok) {
-    throw new Error(`PrintedWaste API returned HTTP ${response.status}`);
+    throw new PrintedWasteHttpError(
+      `PrintedWaste API returned HTTP ${response.status}`,
+      retryAfterMs(response),
+    );
   }
 
   const body = await withTimeout(
@@ -97,11 +301,12 @@ export async function fetchPrintedWasteQueue(
   return normalizedData;
 }
 
-export async function fetchPrintedWasteServerMapping(
+async function requestPrintedWasteServerMapping(
   appVersion: string,
+  source: PrintedWasteSource,
 ): Promise<PrintedWasteServerMapping> {
   const response = await fetchWithTimeout(
-    PRINTEDWASTE_SERVER_MAPPING_URL,
+    source.serverMappingUrl,
     {
       headers: {
         "User-Agent": `opennow/${appVersion}`,
@@ -112,16 +317,28 @@ export async function fetchPrintedWasteServerMapping(
     "PrintedWaste server mapping request",
   );
   if (!response.ok) {
-    throw new Error(
+    throw new PrintedWasteHttpError(
       `PrintedWaste server mapping returned HTTP ${response.status}`,
+      retryAfterMs(response),
     );
   }
 
-  const body = await withTimeout(
-    response.json() as Promise<unknown>,
+  const text = await withTimeout(
+    response.text(),
     PRINTEDWASTE_TIMEOUT_MS,
-    "PrintedWaste server mapping response parse",
+    "PrintedWaste server mapping response read",
   );
+  if (
+    source.publicKey &&
+    !verifyPrintedWasteSignature(
+      text,
+      response.headers.get(PRINTEDWASTE_SIGNATURE_HEADER),
+      source.publicKey,
+    )
+  ) {
+    throw new Error("PrintedWaste server mapping signature did not verify");
+  }
+  const body = JSON.parse(text) as unknown;
   if (!body || typeof body !== "object" || Array.isArray(body)) {
     throw new Error("PrintedWaste server mapping response was not an object");
   }
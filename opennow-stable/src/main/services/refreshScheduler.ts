@@ -3,6 +3,9 @@ import { getAccountGamesCacheKeys } from "../gfn/games";
 import { sessionProxyHasCredentials } from "../gfn/proxyUrl";
 import { cacheEventBus } from "./cacheEventBus";
 import { cacheManager } from "./cacheManager";
+import { taskSupervisor } from "./taskSupervisor";
+
+const REFRESH_TASK = "game-cache-refresh";
 
 export interface RefreshAuthContext {
   token: string;
@@ -18,14 +21,23 @@ type FetchFunction<T> = (
 ) => Promise<T>;
 type PublicFetchFunction = (proxyUrl?: string) => Promise<GameInfo[]>;
 
+export interface RefreshStats {
+  refreshCount: number;
+  lastRefreshAt: number | null;
+  lastRefreshDurationMs: number | null;
+  lastRefreshError: string | null;
+}
+
 class RefreshScheduler {
-  private refreshTimer: ReturnType<typeof setInterval> | null = null;
   private isRefreshing: boolean = false;
+  private paused: boolean = false;
+  private refreshDeferred: boolean = false;
   private authContext: RefreshAuthContext | null = null;
   private fetchMainGamesUncached: FetchFunction<GameInfo[]> | null = null;
   private fetchLibraryGamesUncached: FetchFunction<GameInfo[]> | null = null;
   private fetchPublicGamesUncached: PublicFetchFunction | null = null;
   private refreshIntervalMs: number = 12 * 60 * 60 * 1000;
+  private stats: RefreshStats = { refreshCount: 0, lastRefreshAt: null, lastRefreshDurationMs: null, lastRefreshError: null };
 
   initialize(
     fetchMainGamesUncached: FetchFunction<GameInfo[]>,
@@ -44,7 +56,7 @@ class RefreshScheduler {
   }
 
   start(): void {
-    if (this.refreshTimer) {
+    if (taskSupervisor.has(REFRESH_TASK)) {
       console.warn(`[CACHE] RefreshScheduler already started`);
       return;
     }
@@ -55,44 +67,61 @@ class RefreshScheduler {
     }
 
     console.log(`[CACHE] Starting RefreshScheduler`);
-    void this.performRefresh();
-    this.refreshTimer = setInterval(() => {
-      void this.performRefresh();
-    }, this.refreshIntervalMs);
-    this.refreshTimer.unref?.();
+    this.spawnRefreshTask();
   }
 
   stop(): void {
-    if (!this.refreshTimer) {
+    if (!taskSupervisor.has(REFRESH_TASK)) {
       console.log(`[CACHE] RefreshScheduler already stopped`);
       return;
     }
 
-    clearInterval(this.refreshTimer);
-    this.refreshTimer = null;
+    taskSupervisor.stop(REFRESH_TASK);
     console.log(`[CACHE] RefreshScheduler stopped`);
   }
 
-  async performRefresh(options: { force?: boolean } = {}): Promise<void> {
+  /** Scheduled refreshes report failures to the supervisor so they are retried sooner than the next interval. */
+  private spawnRefreshTask(): void {
+    taskSupervisor.spawn({
+      name: REFRESH_TASK,
+      intervalMs: this.refreshIntervalMs,
+      runImmediately: true,
+      run: async () => {
+        const error = await this.performRefresh();
+        if (error) {
+          throw new Error(error);
+        }
+      },
+    });
+  }
+
+  /** Resolves with the first error of the cycle, or null when it succeeded or was skipped. */
+  async performRefresh(options: { force?: boolean } = {}): Promise<string | null> {
     if (this.isRefreshing) {
       console.log(`[CACHE] Refresh already in progress, skipping`);
-      return;
+      return null;
+    }
+
+    if (this.paused && options.force !== true) {
+      console.log(`[CACHE] Background work paused while streaming, deferring refresh`);
+      this.refreshDeferred = true;
+      return null;
     }
 
     if (!this.authContext) {
       console.log(`[CACHE] Auth context not available, skipping refresh`);
-      return;
+      return null;
     }
 
     if (!this.fetchMainGamesUncached || !this.fetchLibraryGamesUncached || !this.fetchPublicGamesUncached) {
       console.error(`[CACHE] Fetch functions not available`);
-      return;
+      return null;
     }
 
     const { token, userId, providerStreamingBaseUrl, proxyUrl } = this.authContext;
     if (sessionProxyHasCredentials(proxyUrl)) {
       console.log("[CACHE] Credentialed proxy configured, skipping background game cache refresh");
-      return;
+      return null;
     }
 
     const cacheKeys = getAccountGamesCacheKeys(userId, providerStreamingBaseUrl, proxyUrl);
@@ -108,7 +137,7 @@ class RefreshScheduler {
 
     if (!mainNeedsRefresh && !libraryNeedsRefresh && !publicNeedsRefresh) {
       console.log("[CACHE] All game caches are fresh, skipping background refresh");
-      return;
+      return null;
     }
 
     this.isRefreshing = true;
@@ -154,7 +183,7 @@ class RefreshScheduler {
 
       const results = await Promise.allSettled(refreshTasks);
 
-      let hasErrors = false;
+      let firstError: string | null = null;
       const taskNames: string[] = [];
       if (mainNeedsRefresh) taskNames.push("main");
       if (libraryNeedsRefresh) taskNames.push("library");
@@ -163,48 +192,76 @@ class RefreshScheduler {
       for (let i = 0; i < results.length; i += 1) {
         const result = results[i];
         if (result.status === "rejected") {
-          hasErrors = true;
           const name = taskNames[i] ?? "unknown";
+          const message = result.reason instanceof Error ? result.reason.message : String(result.reason);
+          firstError ??= `games:${name}: ${message}`;
           console.error(`[CACHE] Refresh failed for ${name} games:`, result.reason);
           cacheEventBus.emit("cache:refresh-error", {
             key: `games:${name}`,
-            error: result.reason instanceof Error ? result.reason.message : String(result.reason),
+            error: message,
           });
         }
       }
 
       const duration = Date.now() - startTime;
       console.log(`[CACHE] Refresh cycle completed in ${duration}ms`);
-
-      if (!hasErrors) {
+      this.stats = {
+        refreshCount: this.stats.refreshCount + 1,
+        lastRefreshAt: startTime,
+        lastRefreshDurationMs: duration,
+        lastRefreshError: firstError,
+      };
+
+      if (!firstError) {
         cacheEventBus.emit("cache:refresh-success");
       }
+      return firstError;
     } catch (error) {
       console.error(`[CACHE] Refresh cycle error:`, error);
+      const message = error instanceof Error ? error.message : "Unknown error";
+      this.stats = {
+        ...this.stats,
+        lastRefreshAt: startTime,
+        lastRefreshError: message,
+      };
       cacheEventBus.emit("cache:refresh-error", {
         key: "refresh-cycle",
-        error: error instanceof Error ? error.message : "Unknown error",
+        error: message,
       });
+      return message;
     } finally {
       this.isRefreshing = false;
     }
   }
 
+  getStats(): RefreshStats {
+    return { ...this.stats };
+  }
+
   async manualRefresh(): Promise<void> {
     console.log(`[CACHE] Manual refresh requested`);
     await this.performRefresh({ force: true });
   }
 
+  /** Holds scheduled refreshes while a stream is running; a refresh that came due runs on resume. */
+  setPaused(paused: boolean): void {
+    if (this.paused === paused) {
+      return;
+    }
+    this.paused = paused;
+    console.log(`[CACHE] Background refresh ${paused ? "paused" : "resumed"}`);
+    if (!paused && this.refreshDeferred) {
+      this.refreshDeferred = false;
+      void this.performRefresh();
+    }
+  }
+
   setRefreshInterval(intervalMs: number): void {
     console.log(`[CACHE] Refresh interval updated: ${this.refreshIntervalMs}ms -> ${intervalMs}ms`);
     this.refreshIntervalMs = intervalMs;
 
-    if (this.refreshTimer) {
-      clearInterval(this.refreshTimer);
-      this.refreshTimer = setInterval(() => {
-        void this.performRefresh();
-      }, this.refreshIntervalMs);
-      this.refreshTimer.unref?.();
+    if (taskSupervisor.has(REFRESH_TASK)) {
+      this.spawnRefreshTask();
     }
   }
 }
@@ -0,0 +1,26 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { mapWithConcurrency, medianPingMs } from "./regionPing";
+
+test("median ping ignores a single slow sample", () => {
+  assert.equal(medianPingMs([]), null);
+  assert.equal(medianPingMs([18, 240, 21]), 21);
+  assert.equal(medianPingMs([20, 25]), 23);
+});
+
+test("probes keep their order and never exceed the concurrency cap", async () => {
+  let running = 0;
+  let peak = 0;
+  const results = await mapWithConcurrency([30, 10, 20, 5, 15], 2, async (delayMs) => {
+    running += 1;
+    peak = Math.max(peak, running);
+    await new Promise<void>((resolve) => setTimeout(resolve, delayMs));
+    running -= 1;
+    return delayMs * 2;
+  });
+
+  assert.deepEqual(results, [60, 20, 40, 10, 30]);
+  assert.equal(peak, 2);
+  assert.deepEqual(await mapWithConcurrency([], 4, async () => 1), []);
+});
@@ -1,3 +1,4 @@
+import { promises as dns } from "node:dns";
 import * as net from "node:net";
 import type { PingResult, StreamRegion } from "@shared/gfn";
 
@@ -32,53 +33,77 @@ export async function tcpPing(
   });
 }
 
-export async function pingRegions(regions: StreamRegion[]): Promise<PingResult[]> {
-  const pingPromises = regions.map(async (region) => {
-    try {
-      const url = new URL(region.url);
-      const hostname = url.hostname;
-      const port = url.protocol === "https:" ? 443 : 80;
+/** Zone lists run to dozens of hosts; probing all at once skews every sample. */
+const PING_CONCURRENCY = 6;
+const PING_SAMPLES = 3;
+const PING_TIMEOUT_MS = 3000;
+
+/** Median of the successful samples, so one slow connect does not move the result. */
+export function medianPingMs(samples: number[]): number | null {
+  if (samples.length === 0) {
+    return null;
+  }
+  const sorted = [...samples].sort((a, b) => a - b);
+  const mid = Math.floor(sorted.length / 2);
+  const median = sorted.length % 2 === 1 ? sorted[mid]! : (sorted[mid - 1]! + sorted[mid]!) / 2;
+  return Math.round(median);
+}
 
-      const validPings: number[] = [];
+export async function mapWithConcurrency<T, R>(
+  items: readonly T[],
+  limit: number,
+  task: (item: T) => Promise<R>,
+): Promise<R[]> {
+  const results = new Array<R>(items.length);
+  let next = 0;
+  const worker = async (): Promise<void> => {
+    while (next < items.length) {
+      const index = next++;
+      results[index] = await task(items[index]!);
+    }
+  };
+  await Promise.all(Array.from({ length: Math.min(Math.max(limit, 1), items.length) }, worker));
+  return results;
+}
 
-      // Warm-up ping (result discarded) to prime the TCP path before measuring.
-      // The first cold-start connect includes DNS resolution and TCP SYN overhead
-      // which inflates subsequent measurements if not accounted for.
-      await tcpPing(hostname, port, 3000);
+async function pingRegion(region: StreamRegion): Promise<PingResult> {
+  let hostname: string;
+  let port: number;
+  try {
+    const url = new URL(region.url);
+    hostname = url.hostname;
+    port = url.protocol === "https:" ? 443 : 80;
+  } catch {
+    return { url: region.url, pingMs: null, error: "Invalid URL" };
+  }
 
-      // Run 3 measured ping tests with a brief delay between each to allow
-      // the previous socket to fully close before opening the next connection.
-      for (let i = 0; i < 3; i++) {
-        if (i > 0) {
-          await new Promise<void>((resolve) => setTimeout(resolve, 100));
-        }
-        const pingMs = await tcpPing(hostname, port, 3000);
-        if (pingMs !== null) {
-          validPings.push(pingMs);
-        }
-      }
+  // Resolve once up front so DNS time never lands in a sample.
+  let address: string;
+  try {
+    ({ address } = await dns.lookup(hostname));
+  } catch {
+    return { url: region.url, pingMs: null, error: "DNS lookup failed" };
+  }
 
-      // Calculate average of successful pings
-      if (validPings.length > 0) {
-        const avgPing = Math.round(
-          validPings.reduce((a, b) => a + b, 0) / validPings.length,
-        );
-        return { url: region.url, pingMs: avgPing };
-      } else {
-        return {
-          url: region.url,
-          pingMs: null,
-          error: "All ping tests failed",
-        };
-      }
-    } catch {
-      return {
-        url: region.url,
-        pingMs: null,
-        error: "Invalid URL",
-      };
+  // Brief delay between samples lets the previous socket fully close before
+  // the next connection opens.
+  const validPings: number[] = [];
+  for (let i = 0; i < PING_SAMPLES; i++) {
+    if (i > 0) {
+      await new Promise<void>((resolve) => setTimeout(resolve, 100));
     }
-  });
+    const pingMs = await tcpPing(address, port, PING_TIMEOUT_MS);
+    if (pingMs !== null) {
+      validPings.push(pingMs);
+    }
+  }
 
-  return Promise.all(pingPromises);
+  const pingMs = medianPingMs(validPings);
+  return pingMs === null
+    ? { url: region.url, pingMs: null, error: "All ping tests failed" }
+    : { url: region.url, pingMs };
+}
+
+export async function pingRegions(regions: StreamRegion[]): Promise<PingResult[]> {
+  return mapWithConcurrency(regions, PING_CONCURRENCY, pingRegion);
 }
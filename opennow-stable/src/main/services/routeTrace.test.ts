@@ -0,0 +1,67 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { formatRouteTrace, parseTraceOutput, traceCommand } from "./routeTrace";
+
+test("traceroute output becomes hops with samples and loss", () => {
+  const output = [
+    "traceroute to 203.0.113.9 (203.0.113.9), 30 hops max, 60 byte packets",
+    " 1  192.168.1.1  0.512 ms  0.430 ms  0.401 ms",
+    " 2  * * *",
+    " 3  10.20.0.1  8.911 ms *  9.204 ms",
+    " 4  203.0.113.9  21.003 ms  20.871 ms  21.442 ms",
+  ].join("\n");
+
+  assert.deepEqual(parseTraceOutput(output), [
+    { hop: 1, address: "192.168.1.1", rttsMs: [0.512, 0.43, 0.401], lossPercent: 0 },
+    { hop: 2, address: null, rttsMs: [], lossPercent: 100 },
+    { hop: 3, address: "10.20.0.1", rttsMs: [8.911, 9.204], lossPercent: 33 },
+    { hop: 4, address: "203.0.113.9", rttsMs: [21.003, 20.871, 21.442], lossPercent: 0 },
+  ]);
+});
+
+test("tracert output parses the same way, including sub-millisecond hops", () => {
+  const output = [
+    "",
+    "Tracing route to 203.0.113.9 over a maximum of 30 hops",
+    "",
+    "  1    <1 ms    <1 ms    <1 ms  192.168.1.1",
+    "  2     *        *        *     Request timed out.",
+    "  3    12 ms    11 ms    13 ms  203.0.113.9",
+    "",
+    "Trace complete.",
+  ].join("\r\n");
+
+  assert.deepEqual(parseTraceOutput(output), [
+    { hop: 1, address: "192.168.1.1", rttsMs: [1, 1, 1], lossPercent: 0 },
+    { hop: 2, address: null, rttsMs: [], lossPercent: 100 },
+    { hop: 3, address: "203.0.113.9", rttsMs: [12, 11, 13], lossPercent: 0 },
+  ]);
+});
+
+test("each platform gets its own tool and the address is the last argument", () => {
+  assert.equal(traceCommand("win32", "203.0.113.9").file, "tracert");
+  assert.deepEqual(traceCommand("linux", "2001:db8::1").args.slice(0, 2), ["-6", "-n"]);
+  assert.equal(traceCommand("darwin", "2001:db8::1").file, "traceroute6");
+  assert.equal(traceCommand("linux", "203.0.113.9").args.at(-1), "203.0.113.9");
+});
+
+test("exported route traces list every hop", () => {
+  const text = formatRouteTrace({
+    target: "media.example",
+    address: "203.0.113.9",
+    startedAt: "2026-05-10T20:00:00.000Z",
+    hops: [
+      { hop: 1, address: "192.168.1.1", rttsMs: [1, 2], lossPercent: 33 },
+      { hop: 2, address: null, rttsMs: [], lossPercent: 100 },
+    ],
+  });
+  assert.equal(
+    text,
+    [
+      "Route trace to media.example (203.0.113.9) at 2026-05-10T20:00:00.000Z:",
+      " 1 192.168.1.1 1ms 2ms loss=33%",
+      " 2 * - loss=100%",
+    ].join("\n"),
+  );
+});
@@ -0,0 +1,138 @@
+import { execFile } from "node:child_process";
+import { promises as dns } from "node:dns";
+import { isIP } from "node:net";
+import type { RouteTraceHop, RouteTraceResult } from "@shared/gfn";
+
+const MAX_HOPS = 30;
+const PROBES_PER_HOP = 3;
+/** Thirty silent hops at three one-second probes each is the worst case. */
+const TRACE_TIMEOUT_MS = 120_000;
+
+const HOSTNAME_PATTERN = /^[a-zA-Z0-9](?:[a-zA-Z0-9.-]*[a-zA-Z0-9])?$/;
+
+export interface TraceCommand {
+  file: string;
+  args: string[];
+}
+
+/** The system tool for each platform, with DNS lookups off so hops print as addresses. */
+export function traceCommand(platform: NodeJS.Platform, address: string): TraceCommand {
+  const ipv6 = isIP(address) === 6;
+  if (platform === "win32") {
+    return {
+      file: "tracert",
+      args: ["-d", "-h", String(MAX_HOPS), "-w", "1000", ...(ipv6 ? ["-6"] : ["-4"]), address],
+    };
+  }
+  return {
+    file: ipv6 && platform === "darwin" ? "traceroute6" : "traceroute",
+    args: [
+      ...(ipv6 && platform !== "darwin" ? ["-6"] : []),
+      "-n",
+      "-q",
+      String(PROBES_PER_HOP),
+      "-w",
+      "1",
+      "-m",
+      String(MAX_HOPS),
+      address,
+    ],
+  };
+}
+
+const HOP_LINE = /^\s*(\d+)\s+(.*)$/;
+const RTT_SAMPLE = /<?(\d+(?:\.\d+)?)\s*ms/g;
+
+function firstAddress(tokens: string[]): string | null {
+  for (const token of tokens) {
+    const candidate = token.replace(/^\(|\)$/g, "").replace(/^\[|\]$/g, "");
+    if (isIP(candidate)) {
+      return candidate;
+    }
+  }
+  return null;
+}
+
+/**
+ * Reads hop lines from traceroute (Unix) or tracert (Windows) output. Each
+ * `*` is a lost probe; a line with only stars is a router that stays silent.
+ */
+export function parseTraceOutput(output: string): RouteTraceHop[] {
+  const hops: RouteTraceHop[] = [];
+  for (const line of output.split(/\r?\n/)) {
+    const match = HOP_LINE.exec(line);
+    if (!match) {
+      continue;
+    }
+    const hop = Number(match[1]);
+    const rest = match[2]!;
+    const rttsMs = [...rest.matchAll(RTT_SAMPLE)].map((sample) => Number(sample[1]));
+    const lost = rest.split(/\s+/).filter((token) => token === "*").length;
+    const probes = rttsMs.length + lost;
+    if (probes === 0) {
+      continue;
+    }
+    hops.push({
+      hop,
+      address: firstAddress(rest.split(/\s+/)),
+      rttsMs,
+      lossPercent: Math.round((lost / probes) * 100),
+    });
+  }
+  return hops;
+}
+
+function execTrace(command: TraceCommand): Promise<string> {
+  return new Promise((resolve, reject) => {
+    execFile(command.file, command.args, { timeout: TRACE_TIMEOUT_MS, windowsHide: true }, (error, stdout) => {
+      // traceroute exits non-zero when the last hop never answers, which still leaves a usable route.
+      if (error && !stdout) {
+        reject(error);
+        return;
+      }
+      resolve(stdout);
+    });
+  });
+}
+
+export async function traceRoute(target: string, platform: NodeJS.Platform = process.platform): Promise<RouteTraceResult> {
+  const startedAt = new Date().toISOString();
+  const trimmed = target.trim();
+  if (!isIP(trimmed) && !HOSTNAME_PATTERN.test(trimmed)) {
+    return { target, address: null, startedAt, hops: [], error: "Invalid target" };
+  }
+
+  let address: string;
+  try {
+    ({ address } = await dns.lookup(trimmed));
+  } catch {
+    return { target, address: null, startedAt, hops: [], error: "DNS lookup failed" };
+  }
+
+  const command = traceCommand(platform, address);
+  try {
+    const hops = parseTraceOutput(await execTrace(command));
+    return { target, address, startedAt, hops };
+  } catch (error) {
+    const code = (error as NodeJS.ErrnoException).code;
+    return {
+      target,
+      address,
+      startedAt,
+      hops: [],
+      error: code === "ENOENT" ? `${command.file} is not installed` : "Route trace failed",
+    };
+  }
+}
+
+export function formatRouteTrace(result: RouteTraceResult): string {
+  const lines = [`Route trace to ${result.target}${result.address && result.address !== result.target ? ` (${result.address})` : ""} at ${result.startedAt}:`];
+  if (result.error) {
+    lines.push(`error=${result.error}`);
+  }
+  for (const hop of result.hops) {
+    const rtts = hop.rttsMs.length > 0 ? hop.rttsMs.map((rtt) => `${rtt}ms`).join(" ") : "-";
+    lines.push(`${String(hop.hop).padStart(2)} ${hop.address ?? "*"} ${rtts} loss=${hop.lossPercent}%`);
+  }
+  return lines.join("\n");
+}
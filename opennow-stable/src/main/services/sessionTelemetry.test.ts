@@ -0,0 +1,85 @@
+import assert from "node:assert/strict";
+import { mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import test from "node:test";
+
+import type { SessionTelemetryReport } from "@shared/gfn";
+import {
+  addSessionToTelemetry,
+  emptyTelemetryAggregate,
+  readTelemetryAggregate,
+  telemetryGpuFromInfo,
+  writeTelemetryAggregate,
+} from "./sessionTelemetry";
+
+const environment = {
+  appVersion: "1.2.3",
+  platform: "linux",
+  arch: "x64",
+  gpuVendorId: "0x10de",
+  gpuDeviceId: "0x2684",
+  gpuDriver: "550.54",
+};
+
+function report(overrides: Partial<SessionTelemetryReport> = {}): SessionTelemetryReport {
+  return {
+    clientMode: "web",
+    decoderBackend: "VAAPI",
+    codec: "H265",
+    averageFps: 60,
+    durationSeconds: 600,
+    reconnectCount: 0,
+    errorCodes: [],
+    ...overrides,
+  };
+}
+
+test("picks the active GPU from Electron's basic GPU info", () => {
+  assert.deepEqual(
+    telemetryGpuFromInfo({
+      gpuDevice: [
+        { vendorId: 0x8086, deviceId: 0x9a49, active: false },
+        { vendorId: 0x10de, deviceId: 0x2684, active: true, driverVersion: "550.54" },
+      ],
+    }),
+    { gpuVendorId: "0x10de", gpuDeviceId: "0x2684", gpuDriver: "550.54" },
+  );
+  assert.deepEqual(telemetryGpuFromInfo(null), { gpuVendorId: "unknown", gpuDeviceId: "unknown", gpuDriver: "unknown" });
+});
+
+test("sessions with the same environment and decoder share a bucket", () => {
+  let aggregate = emptyTelemetryAggregate();
+  aggregate = addSessionToTelemetry(aggregate, environment, report(), { queueUpload: false });
+  aggregate = addSessionToTelemetry(
+    aggregate,
+    environment,
+    report({ averageFps: 30, durationSeconds: 1200, reconnectCount: 2, errorCodes: ["0xC0F22226"] }),
+    { queueUpload: true },
+  );
+  aggregate = addSessionToTelemetry(aggregate, environment, report({ codec: "AV1" }), { queueUpload: false });
+
+  assert.equal(aggregate.buckets.length, 2);
+  const [h265] = aggregate.buckets;
+  assert.equal(h265.sessions, 2);
+  assert.equal(h265.totalMinutes, 30);
+  assert.equal(h265.fpsMinutes / h265.totalMinutes, 40);
+  assert.equal(h265.reconnects, 2);
+  assert.deepEqual(h265.errors, { "0xC0F22226": 1 });
+
+  assert.equal(aggregate.pending.length, 1, "only sessions recorded in upload mode are queued");
+  assert.equal(aggregate.pending[0].sessions, 1);
+});
+
+test("aggregate survives a round trip and ignores unreadable files", () => {
+  const dir = mkdtempSync(join(tmpdir(), "opennow-telemetry-"));
+  try {
+    const path = join(dir, "session-telemetry.json");
+    assert.deepEqual(readTelemetryAggregate(path), emptyTelemetryAggregate());
+    const aggregate = addSessionToTelemetry(emptyTelemetryAggregate(), environment, report(), { queueUpload: true });
+    writeTelemetryAggregate(path, aggregate);
+    assert.deepEqual(readTelemetryAggregate(path), aggregate);
+  } finally {
+    rmSync(dir, { recursive: true, force: true });
+  }
+});
@@ -0,0 +1,202 @@
+import { existsSync, mkdirSync, readFileSync, writeFileSync } from "node:fs";
+import { dirname, join } from "node:path";
+
+import type { SessionTelemetryReport } from "@shared/gfn";
+import { redactSensitiveData } from "@shared/logger";
+import { fetchWithTimeout } from "./requestTimeout";
+
+export const TELEMETRY_FILE_NAME = "session-telemetry.json";
+const TELEMETRY_SCHEMA_VERSION = 1;
+const TELEMETRY_UPLOAD_TIMEOUT_MS = 7000;
+const MAX_LABEL_LENGTH = 64;
+/** Caps distinct error codes per bucket so a misbehaving session cannot bloat the file. */
+const MAX_ERROR_CODES_PER_BUCKET = 32;
+
+/** Machine and build facts shared by every session recorded on this install. */
+export interface TelemetryEnvironment {
+  appVersion: string;
+  platform: string;
+  arch: string;
+  gpuVendorId: string;
+  gpuDeviceId: string;
+  gpuDriver: string;
+}
+
+/** All sessions that share an environment, client mode, decoder and codec, summed. */
+export interface TelemetryBucket extends TelemetryEnvironment {
+  clientMode: SessionTelemetryReport["clientMode"];
+  decoderBackend: string;
+  codec: string;
+  sessions: number;
+  totalMinutes: number;
+  /** Average fps weighted by minutes played; divide by totalMinutes for the bucket average. */
+  fpsMinutes: number;
+  reconnects: number;
+  errors: Record<string, number>;
+}
+
+export interface TelemetryAggregate {
+  version: number;
+  buckets: TelemetryBucket[];
+  /** Sessions recorded since the last successful upload; only these are sent. */
+  pending: TelemetryBucket[];
+}
+
+export function emptyTelemetryAggregate(): TelemetryAggregate {
+  return { version: TELEMETRY_SCHEMA_VERSION, buckets: [], pending: [] };
+}
+
+export function resolveTelemetryPath(userDataPath: string): string {
+  return join(userDataPath, TELEMETRY_FILE_NAME);
+}
+
+function hexId(value: unknown): string {
+  return typeof value === "number" && Number.isFinite(value) ? `0x${value.toString(16).padStart(4, "0")}` : "unknown";
+}
+
+/** Picks the active GPU out of Electron's `app.getGPUInfo("basic")` result. */
+export function telemetryGpuFromInfo(info: unknown): Pick<TelemetryEnvironment, "gpuVendorId" | "gpuDeviceId" | "gpuDriver"> {
+  const devices = (info as { gpuDevice?: unknown } | null)?.gpuDevice;
+  const list = Array.isArray(devices) ? (devices as Array<Record<string, unknown>>) : [];
+  const device = list.find((entry) => entry.active === true) ?? list[0];
+  return {
+    gpuVendorId: hexId(device?.vendorId),
+    gpuDeviceId: hexId(device?.deviceId),
+    gpuDriver: typeof device?.driverVersion === "string" && device.driverVersion ? device.driverVersion : "unknown",
+  };
+}
+
+function label(value: string): string {
+  const trimmed = redactSensitiveData(value.trim()).slice(0, MAX_LABEL_LENGTH);
+  return trimmed || "unknown";
+}
+
+function sameBucket(a: TelemetryBucket, b: TelemetryBucket): boolean {
+  return a.appVersion === b.appVersion
+    && a.platform === b.platform
+    && a.arch === b.arch
+    && a.gpuVendorId === b.gpuVendorId
+    && a.gpuDeviceId === b.gpuDeviceId
+    && a.gpuDriver === b.gpuDriver
+    && a.clientMode === b.clientMode
+    && a.decoderBackend === b.decoderBackend
+    && a.codec === b.codec;
+}
+
+function mergeBucket(buckets: TelemetryBucket[], session: TelemetryBucket): TelemetryBucket[] {
+  const index = buckets.findIndex((bucket) => sameBucket(bucket, session));
+  if (index < 0) {
+    return [...buckets, session];
+  }
+  const current = buckets[index];
+  const errors = { ...current.errors };
+  for (const [code, count] of Object.entries(session.errors)) {
+    if (code in errors || Object.keys(errors).length < MAX_ERROR_CODES_PER_BUCKET) {
+      errors[code] = (errors[code] ?? 0) + count;
+    }
+  }
+  const merged: TelemetryBucket = {
+    ...current,
+    sessions: current.sessions + session.sessions,
+    totalMinutes: current.totalMinutes + session.totalMinutes,
+    fpsMinutes: current.fpsMinutes + session.fpsMinutes,
+    reconnects: current.reconnects + session.reconnects,
+    errors,
+  };
+  return buckets.map((bucket, i) => (i === index ? merged : bucket));
+}
+
+/**
+ * Folds one session into the aggregate. Only coarse labels and counters are kept:
+ * no account, session, server or network identifiers ever reach the file.
+ */
+export function addSessionToTelemetry(
+  aggregate: TelemetryAggregate,
+  environment: TelemetryEnvironment,
+  report: SessionTelemetryReport,
+  options: { queueUpload: boolean },
+): TelemetryAggregate {
+  const minutes = Math.max(0, Math.round((report.durationSeconds / 60) * 10) / 10);
+  const errors: Record<string, number> = {};
+  for (const code of report.errorCodes.slice(0, MAX_ERROR_CODES_PER_BUCKET)) {
+    const key = label(code);
+    errors[key] = (errors[key] ?? 0) + 1;
+  }
+  const session: TelemetryBucket = {
+    ...environment,
+    clientMode: report.clientMode === "native" ? "native" : "web",
+    decoderBackend: label(report.decoderBackend),
+    codec: label(report.codec),
+    sessions: 1,
+    totalMinutes: minutes,
+    fpsMinutes: Math.max(0, report.averageFps) * minutes,
+    reconnects: Math.max(0, Math.round(report.reconnectCount)),
+    errors,
+  };
+  return {
+    version: TELEMETRY_SCHEMA_VERSION,
+    buckets: mergeBucket(aggregate.buckets, session),
+    pending: options.queueUpload ? mergeBucket(aggregate.pending, session) : aggregate.pending,
+  };
+}
+
+export function readTelemetryAggregate(path: string): TelemetryAggregate {
+  if (!existsSync(path)) {
+    return emptyTelemetryAggregate();
+  }
+  try {
+    const parsed = JSON.parse(readFileSync(path, "utf-8")) as Partial<TelemetryAggregate>;
+    if (parsed.version !== TELEMETRY_SCHEMA_VERSION) {
+      return emptyTelemetryAggregate();
+    }
+    return {
+      version: TELEMETRY_SCHEMA_VERSION,
+      buckets: Array.isArray(parsed.buckets) ? parsed.buckets : [],
+      pending: Array.isArray(parsed.pending) ? parsed.pending : [],
+    };
+  } catch (error) {
+    console.warn("[Telemetry] Ignoring unreadable telemetry file:", error);
+    return emptyTelemetryAggregate();
+  }
+}
+
+export function writeTelemetryAggregate(path: string, aggregate: TelemetryAggregate): void {
+  try {
+    mkdirSync(dirname(path), { recursive: true });
+    writeFileSync(path, `${JSON.stringify(aggregate, null, 2)}\n`, "utf-8");
+  } catch (error) {
+    console.warn("[Telemetry] Failed to write telemetry file:", error);
+  }
+}
+
+/** Sends the pending buckets and returns the aggregate with them cleared. Throws on failure. */
+export async function uploadPendingTelemetry(
+  endpoint: string,
+  aggregate: TelemetryAggregate,
+  appVersion: string,
+): Promise<TelemetryAggregate> {
+  if (aggregate.pending.length === 0) {
+    return aggregate;
+  }
+  const url = new URL(endpoint);
+  if (url.protocol !== "https:") {
+    throw new Error("Telemetry endpoint must use https");
+  }
+  const response = await fetchWithTimeout(
+    url.toString(),
+    {
+      method: "POST",
+      headers: {
+        "User-Agent": `opennow/${appVersion}`,
+        "Content-Type": "application/json",
+      },
+      body: JSON.stringify({ version: TELEMETRY_SCHEMA_VERSION, buckets: aggregate.pending }),
+    },
+    TELEMETRY_UPLOAD_TIMEOUT_MS,
+    "Telemetry upload",
+  );
+  if (!response.ok) {
+    throw new Error(`Telemetry endpoint returned HTTP ${response.status}`);
+  }
+  return { ...aggregate, pending: [] };
+}
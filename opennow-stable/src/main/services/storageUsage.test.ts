@@ -0,0 +1,72 @@
+import assert from "node:assert/strict";
+import { mkdir, mkdtemp, readdir, rm, utimes, writeFile } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import test from "node:test";
+
+import {
+  clearDirectory,
+  directorySizeBytes,
+  diskCacheSizeSwitch,
+  measureStorage,
+  normalizeDiskCacheLimitMb,
+  trimDirectoryToBytes,
+} from "./storageUsage";
+
+test("sizes directories recursively and clears all but the kept entries", async () => {
+  const root = await mkdtemp(join(tmpdir(), "opennow-storage-"));
+  try {
+    await mkdir(join(root, "nested"));
+    await writeFile(join(root, "a.json"), "x".repeat(100));
+    await writeFile(join(root, "nested", "b.txt"), "y".repeat(50));
+    await writeFile(join(root, "recording.part"), "z".repeat(10));
+    assert.equal(await directorySizeBytes(root), 160);
+    assert.equal(await directorySizeBytes(join(root, "missing")), 0);
+
+    const usage = await measureStorage({
+      images: { directory: root, extraBytes: async () => 1000, clear: async () => {} },
+      library: { directory: join(root, "nested"), clear: async () => {} },
+      recordings: { directory: join(root, "missing"), clear: async () => {} },
+      logs: { directory: root, extraBytes: async () => { throw new Error("unavailable"); }, clear: async () => {} },
+    });
+    assert.deepEqual(usage.map(({ category, bytes }) => [category, bytes]), [
+      ["images", 1160],
+      ["library", 50],
+      ["recordings", 0],
+      ["logs", 160],
+    ]);
+
+    await clearDirectory(root, new Set([join(root, "recording.part")]));
+    assert.deepEqual(await readdir(root), ["recording.part"]);
+  } finally {
+    await rm(root, { recursive: true, force: true });
+  }
+});
+
+test("the disk cache limit is clamped to a sane range and 0 keeps Chromium's sizing", () => {
+  assert.equal(diskCacheSizeSwitch(0), null);
+  assert.equal(diskCacheSizeSwitch(undefined), null);
+  assert.equal(diskCacheSizeSwitch(512), String(512 * 1024 * 1024));
+  assert.equal(diskCacheSizeSwitch(1), String(64 * 1024 * 1024));
+  assert.equal(normalizeDiskCacheLimitMb("512"), 512);
+  assert.equal(normalizeDiskCacheLimitMb(-5), 0);
+  assert.equal(normalizeDiskCacheLimitMb(1e9), 8192);
+});
+
+test("trimming evicts the oldest files until the directory fits", async () => {
+  const root = await mkdtemp(join(tmpdir(), "opennow-storage-"));
+  try {
+    await mkdir(join(root, "nested"));
+    await writeFile(join(root, "old.jpg"), "a".repeat(40));
+    await writeFile(join(root, "nested", "middle.jpg"), "b".repeat(40));
+    await writeFile(join(root, "new.jpg"), "c".repeat(40));
+    await utimes(join(root, "old.jpg"), 1, 1);
+    await utimes(join(root, "nested", "middle.jpg"), 2, 2);
+
+    assert.equal(await trimDirectoryToBytes(root, 50), 2);
+    assert.deepEqual((await readdir(root)).sort(), ["nested", "new.jpg"]);
+    assert.equal(await trimDirectoryToBytes(root, 50), 0);
+  } finally {
+    await rm(root, { recursive: true, force: true });
+  }
+});
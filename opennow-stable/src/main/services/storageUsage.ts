@@ -0,0 +1,121 @@
+import { lstat, readdir, rm, unlink } from "node:fs/promises";
+import { join } from "node:path";
+import type { StorageCategory, StorageCategoryUsage } from "@shared/gfn";
+
+const BYTES_PER_MB = 1024 * 1024;
+/** Below this Chromium evicts so eagerly that box art refetches on every visit. */
+const MIN_DISK_CACHE_MB = 64;
+const MAX_DISK_CACHE_MB = 8192;
+
+export interface StorageCategoryTarget {
+  directory: string;
+  /** Bytes held outside `directory`, e.g. Chromium's HTTP cache */
+  extraBytes?: () => Promise<number>;
+  clear: () => Promise<void>;
+}
+
+export type StorageTargets = Record<StorageCategory, StorageCategoryTarget>;
+
+/** Total size of the files under `path`; a missing directory is empty and symlinks are not followed. */
+export async function directorySizeBytes(path: string): Promise<number> {
+  let names: string[];
+  try {
+    names = await readdir(path);
+  } catch {
+    return 0;
+  }
+  const sizes = await Promise.all(names.map(async (name) => {
+    const entry = join(path, name);
+    try {
+      const info = await lstat(entry);
+      return info.isDirectory() ? directorySizeBytes(entry) : info.isFile() ? info.size : 0;
+    } catch {
+      return 0;
+    }
+  }));
+  return sizes.reduce((total, size) => total + size, 0);
+}
+
+/** Removes everything inside `path` except the entries in `keep`, leaving the directory itself. */
+export async function clearDirectory(path: string, keep: ReadonlySet<string> = new Set()): Promise<void> {
+  let names: string[];
+  try {
+    names = await readdir(path);
+  } catch {
+    return;
+  }
+  await Promise.all(names
+    .filter((name) => !keep.has(join(path, name)))
+    .map((name) => rm(join(path, name), { recursive: true, force: true })));
+}
+
+export async function measureStorage(targets: StorageTargets): Promise<StorageCategoryUsage[]> {
+  return Promise.all((Object.keys(targets) as StorageCategory[]).map(async (category) => {
+    const target = targets[category];
+    const [bytes, extraBytes] = await Promise.all([
+      directorySizeBytes(target.directory),
+      target.extraBytes?.().catch(() => 0) ?? Promise.resolve(0),
+    ]);
+    return { category, bytes: bytes + extraBytes, path: target.directory };
+  }));
+}
+
+/** The cache size limit in MB clamped to a sane range, or 0 for automatic sizing. */
+export function normalizeDiskCacheLimitMb(limitMb: unknown): number {
+  const value = Number(limitMb);
+  if (!Number.isFinite(value) || value <= 0) {
+    return 0;
+  }
+  return Math.min(MAX_DISK_CACHE_MB, Math.max(MIN_DISK_CACHE_MB, Math.round(value)));
+}
+
+/**
+ * Value for Chromium's `disk-cache-size` switch, which makes the HTTP cache
+ * evict past that size. Null keeps Chromium's own sizing.
+ */
+export function diskCacheSizeSwitch(limitMb: unknown): string | null {
+  const clampedMb = normalizeDiskCacheLimitMb(limitMb);
+  return clampedMb > 0 ? String(clampedMb * BYTES_PER_MB) : null;
+}
+
+async function listFiles(path: string): Promise<Array<{ path: string; size: number; mtimeMs: number }>> {
+  let names: string[];
+  try {
+    names = await readdir(path);
+  } catch {
+    return [];
+  }
+  const entries = await Promise.all(names.map(async (name) => {
+    const entry = join(path, name);
+    try {
+      const info = await lstat(entry);
+      if (info.isDirectory()) {
+        return listFiles(entry);
+      }
+      return info.isFile() ? [{ path: entry, size: info.size, mtimeMs: info.mtimeMs }] : [];
+    } catch {
+      return [];
+    }
+  }));
+  return entries.flat();
+}
+
+/** Deletes the least recently written files under `path` until it holds at most `maxBytes`. */
+export async function trimDirectoryToBytes(path: string, maxBytes: number): Promise<number> {
+  const files = await listFiles(path);
+  let total = files.reduce((sum, file) => sum + file.size, 0);
+  let removed = 0;
+  for (const file of files.sort((a, b) => a.mtimeMs - b.mtimeMs)) {
+    if (total <= maxBytes) {
+      break;
+    }
+    try {
+      await unlink(file.path);
+      total -= file.size;
+      removed += 1;
+    } catch {
+      // Already gone or in use; the next trim gets another chance.
+    }
+  }
+  return removed;
+}
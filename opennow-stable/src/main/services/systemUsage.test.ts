@@ -0,0 +1,27 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { cpuBusyPercent, hottestCpuZoneC, parseNvidiaSmi } from "./systemUsage";
+
+test("cpu load is the busy share of the time between two readings", () => {
+  assert.equal(cpuBusyPercent({ idle: 1000, total: 2000 }, { idle: 1300, total: 3000 }), 70);
+  assert.equal(cpuBusyPercent({ idle: 1000, total: 2000 }, { idle: 1000, total: 2000 }), null);
+});
+
+test("temperature is the hottest cpu or soc zone, ignoring battery and skin sensors", () => {
+  assert.equal(
+    hottestCpuZoneC([
+      ["x86_pkg_temp\n", "81000\n"],
+      ["acpitz\n", "54000\n"],
+      ["B0D4\n", "95000\n"],
+      ["battery\n", "38000\n"],
+    ]),
+    81,
+  );
+  assert.equal(hottestCpuZoneC([["battery", "38000"]]), null);
+});
+
+test("nvidia-smi fields the driver does not report read as unavailable", () => {
+  assert.deepEqual(parseNvidiaSmi("87, 72\n"), { gpuPercent: 87, gpuTemperatureC: 72 });
+  assert.deepEqual(parseNvidiaSmi("[N/A], 65\r\n"), { gpuPercent: null, gpuTemperatureC: 65 });
+});
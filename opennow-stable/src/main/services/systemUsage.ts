@@ -0,0 +1,219 @@
+import { spawn, type ChildProcess } from "node:child_process";
+import { promises as fs } from "node:fs";
+import { cpus } from "node:os";
+import { join } from "node:path";
+import type { SystemUsageSample } from "@shared/gfn";
+
+/** The stats panel polls while it is open; closer requests share one reading. */
+const MIN_SAMPLE_INTERVAL_MS = 2000;
+/** Older CPU baselines would average load over time the panel was closed. */
+const CPU_BASELINE_MAX_AGE_MS = 10_000;
+/** Window measured when there is no usable baseline, e.g. when the panel opens. */
+const CPU_FRESH_WINDOW_MS = 500;
+const NVIDIA_SMI_LOOP_SECONDS = 2;
+/** The nvidia-smi loop is stopped once samples have not been asked for this long. */
+const NVIDIA_SMI_IDLE_STOP_MS = CPU_BASELINE_MAX_AGE_MS;
+const THERMAL_ROOT = "/sys/class/thermal";
+const DRM_ROOT = "/sys/class/drm";
+/**
+ * Thermal zone types that track the CPU or SoC die. Zones for batteries,
+ * chargers and skin sensors read well below the die and would hide throttling.
+ */
+const CPU_THERMAL_ZONE_PATTERN = /x86_pkg_temp|cpu|soc|tcpu|k10temp|acpitz/i;
+
+export interface CpuTimes {
+  idle: number;
+  total: number;
+}
+
+export function readCpuTimes(): CpuTimes {
+  let idle = 0;
+  let total = 0;
+  for (const cpu of cpus()) {
+    const { user, nice, sys, irq } = cpu.times;
+    idle += cpu.times.idle;
+    total += user + nice + sys + irq + cpu.times.idle;
+  }
+  return { idle, total };
+}
+
+/** Busy share of all cores between two readings, or null before time has passed. */
+export function cpuBusyPercent(previous: CpuTimes, next: CpuTimes): number | null {
+  const total = next.total - previous.total;
+  if (total <= 0) {
+    return null;
+  }
+  const busy = total - (next.idle - previous.idle);
+  return Math.min(100, Math.max(0, Math.round((busy / total) * 100)));
+}
+
+/**
+ * First GPU line of `nvidia-smi --query-gpu=utilization.gpu,temperature.gpu
+ * --format=csv,noheader,nounits`. Fields the driver does not support read "[N/A]".
+ */
+export function parseNvidiaSmi(stdout: string): { gpuPercent: number | null; gpuTemperatureC: number | null } {
+  const [utilization, temperature] = (stdout.split(/\r?\n/)[0] ?? "").split(",").map((field) => Number(field.trim()));
+  return {
+    gpuPercent: Number.isFinite(utilization) ? utilization : null,
+    gpuTemperatureC: Number.isFinite(temperature) ? temperature : null,
+  };
+}
+
+/** Hottest CPU/SoC zone from `[type, millidegrees]` pairs read out of sysfs. */
+export function hottestCpuZoneC(zones: ReadonlyArray<[type: string, milliC: string]>): number | null {
+  let hottest: number | null = null;
+  for (const [type, raw] of zones) {
+    const milliC = Number(raw.trim());
+    if (!CPU_THERMAL_ZONE_PATTERN.test(type.trim()) || !Number.isFinite(milliC) || milliC <= 0) {
+      continue;
+    }
+    const celsius = Math.round(milliC / 1000);
+    hottest = hottest === null ? celsius : Math.max(hottest, celsius);
+  }
+  return hottest;
+}
+
+async function readSysfsEntries(root: string, prefix: string, files: string[]): Promise<string[][]> {
+  let names: string[];
+  try {
+    names = (await fs.readdir(root)).filter((name) => name.startsWith(prefix));
+  } catch {
+    return [];
+  }
+  const entries = await Promise.all(names.map(async (name) => {
+    try {
+      return await Promise.all(files.map((file) => fs.readFile(join(root, name, file), "utf8")));
+    } catch {
+      return null;
+    }
+  }));
+  return entries.filter((entry): entry is string[] => entry !== null);
+}
+
+async function readLinuxTemperatureC(): Promise<number | null> {
+  const zones = await readSysfsEntries(THERMAL_ROOT, "thermal_zone", ["type", "temp"]);
+  return hottestCpuZoneC(zones as Array<[string, string]>);
+}
+
+/** AMD and some Intel drivers expose a busy percentage per DRM card. */
+async function readLinuxGpuBusyPercent(): Promise<number | null> {
+  const cards = await readSysfsEntries(DRM_ROOT, "card", ["device/gpu_busy_percent"]);
+  const values = cards.map(([raw]) => Number(raw.trim())).filter(Number.isFinite);
+  return values.length > 0 ? Math.max(...values) : null;
+}
+
+/** Set once nvidia-smi is missing or exits without a reading, so it is not spawned again. */
+let nvidiaSmiUnavailable = false;
+let nvidiaSmi: ChildProcess | null = null;
+let nvidiaSmiReading: ReturnType<typeof parseNvidiaSmi> | null = null;
+let nvidiaSmiIdleTimer: NodeJS.Timeout | null = null;
+
+function stopNvidiaSmi(): void {
+  nvidiaSmiIdleTimer = null;
+  nvidiaSmi?.kill();
+  nvidiaSmi = null;
+  nvidiaSmiReading = null;
+}
+
+/**
+ * Starting nvidia-smi costs far more than a reading, so one `--loop` process
+ * reports every couple of seconds while samples keep coming and is stopped
+ * when they stop. The first sample after it starts has no GPU reading yet.
+ */
+function readNvidiaSmi(): ReturnType<typeof parseNvidiaSmi> | null {
+  if (nvidiaSmiUnavailable) {
+    return null;
+  }
+  if (!nvidiaSmi) {
+    const child = spawn(
+      "nvidia-smi",
+      [
+        "--id=0",
+        "--query-gpu=utilization.gpu,temperature.gpu",
+        "--format=csv,noheader,nounits",
+        `--loop=${NVIDIA_SMI_LOOP_SECONDS}`,
+      ],
+      { stdio: ["ignore", "pipe", "ignore"], windowsHide: true },
+    );
+    let pending = "";
+    let reported = false;
+    child.stdout?.setEncoding("utf8");
+    child.stdout?.on("data", (chunk: string) => {
+      const lines = (pending + chunk).split(/\r?\n/);
+      pending = lines.pop() ?? "";
+      const latest = lines.filter((line) => line.trim()).at(-1);
+      if (latest && nvidiaSmi === child) {
+        reported = true;
+        nvidiaSmiReading = parseNvidiaSmi(latest);
+      }
+    });
+    child.on("error", () => {
+      nvidiaSmiUnavailable = true;
+      if (nvidiaSmi === child) {
+        nvidiaSmi = null;
+      }
+    });
+    child.on("exit", () => {
+      if (nvidiaSmi !== child) {
+        return;
+      }
+      nvidiaSmiUnavailable ||= !reported;
+      nvidiaSmi = null;
+      nvidiaSmiReading = null;
+    });
+    nvidiaSmi = child;
+  }
+  if (nvidiaSmiIdleTimer) {
+    clearTimeout(nvidiaSmiIdleTimer);
+  }
+  nvidiaSmiIdleTimer = setTimeout(stopNvidiaSmi, NVIDIA_SMI_IDLE_STOP_MS);
+  nvidiaSmiIdleTimer.unref();
+  return nvidiaSmiReading;
+}
+
+async function sample(baseline: CpuTimes | null, platform: NodeJS.Platform): Promise<{ sample: SystemUsageSample; cpu: CpuTimes }> {
+  const previousCpu = baseline ?? readCpuTimes();
+  const [temperatureC, sysfsGpuPercent, nvidia] = await Promise.all([
+    platform === "linux" ? readLinuxTemperatureC() : Promise.resolve(null),
+    platform === "linux" ? readLinuxGpuBusyPercent() : Promise.resolve(null),
+    platform === "darwin" ? null : readNvidiaSmi(),
+    baseline ? Promise.resolve() : new Promise<void>((resolve) => setTimeout(resolve, CPU_FRESH_WINDOW_MS)),
+  ]);
+  const cpu = readCpuTimes();
+  return {
+    cpu,
+    sample: {
+      cpuPercent: cpuBusyPercent(previousCpu, cpu),
+      gpuPercent: nvidia?.gpuPercent ?? sysfsGpuPercent,
+      temperatureC,
+      gpuTemperatureC: nvidia?.gpuTemperatureC ?? null,
+      sampledAt: Date.now(),
+    },
+  };
+}
+
+let lastCpu: CpuTimes | null = null;
+let lastSample: SystemUsageSample | null = null;
+let inFlight: Promise<SystemUsageSample> | null = null;
+
+/**
+ * CPU load since the previous call plus GPU load and temperatures where the
+ * platform exposes them without elevated rights: sysfs on Linux and nvidia-smi
+ * on NVIDIA systems. Anything unavailable is null.
+ */
+export function sampleSystemUsage(platform: NodeJS.Platform = process.platform): Promise<SystemUsageSample> {
+  const age = lastSample ? Date.now() - lastSample.sampledAt : Infinity;
+  if (lastSample && age < MIN_SAMPLE_INTERVAL_MS) {
+    return Promise.resolve(lastSample);
+  }
+  inFlight ??= sample(age < CPU_BASELINE_MAX_AGE_MS ? lastCpu : null, platform)
+    .then((result) => {
+      lastCpu = result.cpu;
+      lastSample = result.sample;
+      return result.sample;
+    })
+    .finally(() => {
+      inFlight = null;
+    });
+  return inFlight;
+}
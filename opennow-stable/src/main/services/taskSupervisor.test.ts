@@ -0,0 +1,76 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { TaskSupervisor, restartDelayMs } from "./taskSupervisor";
+
+const flush = (): Promise<void> => new Promise((resolve) => setImmediate(resolve));
+
+test("restart backoff doubles and never exceeds the task's interval", () => {
+  assert.equal(restartDelayMs(1, 60 * 60 * 1000), 5_000);
+  assert.equal(restartDelayMs(2, 60 * 60 * 1000), 10_000);
+  assert.equal(restartDelayMs(3, 60 * 60 * 1000), 20_000);
+  assert.equal(restartDelayMs(20, 60 * 60 * 1000), 10 * 60 * 1000);
+  assert.equal(restartDelayMs(4, 30_000), 30_000);
+  assert.equal(restartDelayMs(1, 2_000), 5_000);
+});
+
+test("a failed run is retried with backoff and success clears the streak", async (t) => {
+  t.mock.timers.enable({ apis: ["setTimeout"] });
+  const supervisor = new TaskSupervisor(() => 1_000);
+  let calls = 0;
+  supervisor.spawn({
+    name: "refresh",
+    intervalMs: 60 * 60 * 1000,
+    runImmediately: true,
+    run: async () => {
+      calls += 1;
+      if (calls === 1) {
+        throw new Error("offline");
+      }
+    },
+  });
+
+  t.mock.timers.tick(0);
+  await flush();
+  let [health] = supervisor.getHealth();
+  assert.equal(health?.state, "backoff");
+  assert.equal(health?.lastError, "offline");
+  assert.equal(health?.nextRunAt, 1_000 + 5_000);
+
+  t.mock.timers.tick(5_000);
+  await flush();
+  [health] = supervisor.getHealth();
+  assert.equal(calls, 2);
+  assert.equal(health?.state, "scheduled");
+  assert.equal(health?.failures, 1);
+  assert.equal(health?.consecutiveFailures, 0);
+  assert.equal(health?.lastSuccessAt, 1_000);
+  supervisor.stopAll();
+});
+
+test("stopping a task mid-run keeps it from being rescheduled", async (t) => {
+  t.mock.timers.enable({ apis: ["setTimeout"] });
+  const supervisor = new TaskSupervisor();
+  let release: () => void = () => {};
+  let calls = 0;
+  supervisor.spawn({
+    name: "sync",
+    intervalMs: 1_000,
+    runImmediately: true,
+    run: () => {
+      calls += 1;
+      return new Promise<void>((resolve) => {
+        release = resolve;
+      });
+    },
+  });
+  t.mock.timers.tick(0);
+  assert.equal(supervisor.getHealth()[0]?.state, "running");
+
+  supervisor.stop("sync");
+  release();
+  await flush();
+  t.mock.timers.tick(5_000);
+  assert.equal(calls, 1);
+  assert.equal(supervisor.has("sync"), false);
+});
@@ -0,0 +1,141 @@
+/** First retry after a failed run; doubles with each consecutive failure. */
+const RESTART_BASE_DELAY_MS = 5_000;
+const RESTART_MAX_DELAY_MS = 10 * 60 * 1000;
+
+export type TaskState = "scheduled" | "running" | "backoff";
+
+export interface SupervisedTask {
+  name: string;
+  intervalMs: number;
+  /** Run once as soon as the task is spawned instead of waiting a full interval. */
+  runImmediately?: boolean;
+  run: () => Promise<void> | void;
+}
+
+export interface TaskHealth {
+  name: string;
+  state: TaskState;
+  intervalMs: number;
+  runs: number;
+  failures: number;
+  consecutiveFailures: number;
+  lastSuccessAt: number | null;
+  lastFailureAt: number | null;
+  lastError: string | null;
+  nextRunAt: number | null;
+}
+
+interface TaskEntry {
+  task: SupervisedTask;
+  health: TaskHealth;
+  timer: NodeJS.Timeout | null;
+}
+
+/**
+ * Delay before retrying a task that just failed. Capped at the task's own
+ * interval, so a failing task is retried no later than it would have run anyway,
+ * except that no retry comes sooner than 5s: a task on a 2s interval
+ * backs off to 5s rather than retrying on its normal cadence.
+ */
+export function restartDelayMs(consecutiveFailures: number, intervalMs: number): number {
+  const exponent = Math.max(0, consecutiveFailures - 1);
+  const delay = RESTART_BASE_DELAY_MS * 2 ** Math.min(exponent, 16);
+  return Math.min(delay, RESTART_MAX_DELAY_MS, Math.max(intervalMs, RESTART_BASE_DELAY_MS));
+}
+
+/**
+ * Owns the main process's long-running periodic work. A run that throws or
+ * rejects is logged and retried with backoff instead of leaving a dead timer
+ * or an uncaught exception behind, and every task's health is kept for
+ * diagnostics exports.
+ */
+export class TaskSupervisor {
+  private readonly tasks = new Map<string, TaskEntry>();
+
+  constructor(private readonly now: () => number = Date.now) {}
+
+  /** Starts supervising `task`, replacing any running task with the same name. */
+  spawn(task: SupervisedTask): void {
+    this.stop(task.name);
+    const entry: TaskEntry = {
+      task,
+      timer: null,
+      health: {
+        name: task.name,
+        state: "scheduled",
+        intervalMs: task.intervalMs,
+        runs: 0,
+        failures: 0,
+        consecutiveFailures: 0,
+        lastSuccessAt: null,
+        lastFailureAt: null,
+        lastError: null,
+        nextRunAt: null,
+      },
+    };
+    this.tasks.set(task.name, entry);
+    this.schedule(entry, task.runImmediately ? 0 : task.intervalMs);
+  }
+
+  stop(name: string): void {
+    const entry = this.tasks.get(name);
+    if (!entry) {
+      return;
+    }
+    if (entry.timer) {
+      clearTimeout(entry.timer);
+    }
+    this.tasks.delete(name);
+  }
+
+  stopAll(): void {
+    for (const name of [...this.tasks.keys()]) {
+      this.stop(name);
+    }
+  }
+
+  has(name: string): boolean {
+    return this.tasks.has(name);
+  }
+
+  getHealth(): TaskHealth[] {
+    return [...this.tasks.values()].map((entry) => ({ ...entry.health }));
+  }
+
+  private schedule(entry: TaskEntry, delayMs: number): void {
+    entry.health.nextRunAt = this.now() + delayMs;
+    entry.timer = setTimeout(() => void this.execute(entry), delayMs);
+    entry.timer.unref?.();
+  }
+
+  private async execute(entry: TaskEntry): Promise<void> {
+    entry.timer = null;
+    entry.health.state = "running";
+    entry.health.nextRunAt = null;
+    entry.health.runs += 1;
+
+    let delayMs = entry.task.intervalMs;
+    try {
+      await entry.task.run();
+      entry.health.state = "scheduled";
+      entry.health.consecutiveFailures = 0;
+      entry.health.lastSuccessAt = this.now();
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      entry.health.state = "backoff";
+      entry.health.failures += 1;
+      entry.health.consecutiveFailures += 1;
+      entry.health.lastFailureAt = this.now();
+      entry.health.lastError = message;
+      delayMs = restartDelayMs(entry.health.consecutiveFailures, entry.task.intervalMs);
+      console.warn(`[Tasks] ${entry.task.name} failed (${entry.health.consecutiveFailures} in a row), retrying in ${delayMs}ms:`, message);
+    }
+
+    // The task may have been stopped or replaced while it was running.
+    if (this.tasks.get(entry.task.name) === entry) {
+      this.schedule(entry, delayMs);
+    }
+  }
+}
+
+export const taskSupervisor = new TaskSupervisor();
@@ -0,0 +1,66 @@
+import assert from "node:assert/strict";
+import { mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import test from "node:test";
+
+import type { SessionInfo } from "@shared/gfn";
+import { ActiveSessionRegistry } from "./sessionRegistry";
+
+function session(sessionId: string): SessionInfo {
+  return {
+    sessionId,
+    status: 2,
+    zone: "NP-AMS-08",
+    streamingBaseUrl: "https://np-ams-08.cloudmatchbeta.nvidiagrid.net/",
+    serverIp: "80.250.97.40",
+    signalingServer: "80.250.97.40:443",
+    signalingUrl: "wss://80.250.97.40:443/nvst/",
+    iceServers: [],
+    clientId: "client",
+    deviceId: "device",
+  };
+}
+
+test("registry hands the latest session to a single teardown", () => {
+  const registry = new ActiveSessionRegistry();
+  registry.record(session("a"));
+  registry.record(session("b"));
+  registry.clear("a");
+
+  assert.deepEqual(registry.take(), {
+    sessionId: "b",
+    zone: "NP-AMS-08",
+    streamingBaseUrl: "https://np-ams-08.cloudmatchbeta.nvidiagrid.net/",
+    serverIp: "80.250.97.40",
+    clientId: "client",
+    deviceId: "device",
+  });
+  assert.equal(registry.take(), null);
+
+  registry.record(session("c"));
+  registry.clear("c");
+  assert.equal(registry.take(), null);
+});
+
+test("registry keeps the session on disk until it is stopped", () => {
+  const dir = mkdtempSync(join(tmpdir(), "opennow-session-"));
+  try {
+    const path = join(dir, "active-session.json");
+    const registry = new ActiveSessionRegistry(path);
+    registry.record(session("a"));
+    assert.equal(registry.take()?.sessionId, "a");
+
+    const nextRun = new ActiveSessionRegistry(path);
+    assert.equal(nextRun.restore()?.sessionId, "a");
+    nextRun.record(session("a"));
+    nextRun.settle("a");
+    assert.equal(nextRun.restore()?.sessionId, "a");
+
+    nextRun.clear("a");
+    assert.equal(nextRun.restore(), null);
+    assert.equal(new ActiveSessionRegistry(path).restore(), null);
+  } finally {
+    rmSync(dir, { recursive: true, force: true });
+  }
+});
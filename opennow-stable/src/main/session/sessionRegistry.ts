@@ -0,0 +1,102 @@
+import { existsSync, mkdirSync, readFileSync, rmSync, writeFileSync } from "node:fs";
+import { dirname } from "node:path";
+
+import type { SessionInfo, SessionStopRequest } from "@shared/gfn";
+
+export type RegisteredSession = Omit<SessionStopRequest, "token">;
+
+function isRegisteredSession(value: unknown): value is RegisteredSession {
+  if (!value || typeof value !== "object") {
+    return false;
+  }
+  const candidate = value as Partial<RegisteredSession>;
+  return typeof candidate.sessionId === "string" && candidate.sessionId.length > 0;
+}
+
+/**
+ * The session this app instance created or claimed, kept in the main process
+ * so it can still be ended after the renderer has gone away on exit.
+ *
+ * With a path the session is also written to disk until it is stopped, so a
+ * run that crashed or was killed leaves it behind for the next start.
+ */
+export class ActiveSessionRegistry {
+  private active: RegisteredSession | null = null;
+
+  constructor(private readonly path: string | null = null) {}
+
+  record(session: SessionInfo): void {
+    this.active = {
+      sessionId: session.sessionId,
+      zone: session.zone,
+      streamingBaseUrl: session.streamingBaseUrl,
+      serverIp: session.serverIp,
+      clientId: session.clientId,
+      deviceId: session.deviceId,
+    };
+    this.persist(this.active);
+  }
+
+  clear(sessionId: string): void {
+    if (this.active?.sessionId === sessionId) {
+      this.active = null;
+    }
+    this.settle(sessionId);
+  }
+
+  /**
+   * Hands the session to exactly one caller, so teardown runs once. The copy on
+   * disk stays until the caller settles it.
+   */
+  take(): RegisteredSession | null {
+    const active = this.active;
+    this.active = null;
+    return active;
+  }
+
+  /** The session a previous run left on disk, or null. */
+  restore(): RegisteredSession | null {
+    if (!this.path || !existsSync(this.path)) {
+      return null;
+    }
+    try {
+      const parsed: unknown = JSON.parse(readFileSync(this.path, "utf-8"));
+      if (isRegisteredSession(parsed)) {
+        return parsed;
+      }
+    } catch (error) {
+      console.warn("[SessionRegistry] Ignoring unreadable session file:", error);
+    }
+    this.remove();
+    return null;
+  }
+
+  /** Forgets the copy on disk once the session has been stopped, unless it was recorded again since. */
+  settle(sessionId: string): void {
+    if (this.active?.sessionId === sessionId) {
+      return;
+    }
+    const persisted = this.restore();
+    if (persisted?.sessionId === sessionId) {
+      this.remove();
+    }
+  }
+
+  private persist(session: RegisteredSession): void {
+    if (!this.path) {
+      return;
+    }
+    try {
+      mkdirSync(dirname(this.path), { recursive: true });
+      writeFileSync(this.path, `${JSON.stringify(session, null, 2)}\n`, "utf-8");
+    } catch (error) {
+      console.warn("[SessionRegistry] Failed to write session file:", error);
+    }
+  }
+
+  private remove(): void {
+    if (this.path) {
+      rmSync(this.path, { force: true });
+    }
+  }
+}
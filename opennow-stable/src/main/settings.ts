@@ -15,13 +15,31 @@ import type {
   NativeStreamerFeatureMode,
   NativeTransitionDiagnostics,
   AppAccentColor,
+  AppTheme,
+  FullscreenMode,
+  IceTransportPolicy,
+  NetworkIpFamily,
+  TelemetryMode,
+  AudioOutputMode,
+  GameStreamOverride,
+  StatsPanelField,
+  StreamProfileOverride,
+  UpdateChannel,
+  GamescopeCompatibility,
 } from "@shared/gfn";
 import {
   DEFAULT_KEYBOARD_LAYOUT,
+  DEFAULT_STATS_PANEL_FIELDS,
   getDefaultStreamPreferences,
+  normalizeControllerSlotsEnabled,
+  normalizeStatsPanelFields,
   normalizeStreamClientModeForPlatform,
   normalizeStreamPreferences,
 } from "@shared/gfn";
+import { normalizeGameStreamOverrides, normalizeStreamProfileOverride } from "@shared/gameStreamOverrides";
+import { normalizePlayCurfew } from "@shared/playCurfew";
+import { normalizeRenderFpsCap } from "@shared/renderFpsCap";
+import { normalizeDiskCacheLimitMb } from "./services/storageUsage";
 
 export interface Settings {
   /** Video resolution (e.g., "1920x1080") */
@@ -32,6 +50,12 @@ export interface Settings {
   posterSizeScale: number;
   /** Target FPS (30, 60, 120, etc.) */
   fps: number;
+  /** Client-side render FPS cap on AC power; decoded frames above it are dropped (0 = off) */
+  renderFpsCap: number;
+  /** Client-side render FPS cap on battery power (0 = off) */
+  renderFpsCapOnBattery: number;
+  /** Request the capped frame rate from the server instead of only decimating locally */
+  renderFpsCapRequestServer: boolean;
   /** Maximum bitrate in Mbps (cap at 150) */
   maxBitrateMbps: number;
   /** Recording video bitrate in Mbps (null = MediaRecorder auto, cap at 200) */
@@ -44,6 +68,11 @@ export interface Settings {
   nativeVideoBackend: NativeVideoBackendPreference;
   /** Optional path to a custom native streamer executable */
   nativeStreamerExecutablePath: string;
+  /**
+   * Comma-separated experimental native flags, e.g. "vulkan-video,!av1".
+   * OPENNOW_FLAGS in the environment overrides individual entries.
+   */
+  nativeExperimentalFlags: string;
   /** Native-only override for Cloud G-Sync / VRR display detection */
   nativeCloudGsyncMode: NativeStreamerFeatureMode;
   /** Native D3D sink fullscreen presentation override */
@@ -58,22 +87,82 @@ export interface Settings {
   decoderPreference: VideoAccelerationPreference;
   /** Preferred video encode acceleration mode */
   encoderPreference: VideoAccelerationPreference;
+  /** Apply known driver/compositor workarounds at startup */
+  driverWorkaroundsEnabled: boolean;
+  /** Workaround rule ids the user switched off */
+  disabledDriverWorkarounds: string[];
   /** Color quality (bit depth + chroma subsampling) */
   colorQuality: ColorQuality;
+  /** Per-game resolution/FPS/HDR/codec overrides, keyed by catalog game id */
+  gameStreamOverrides: Record<string, GameStreamOverride>;
+  /** Use the battery profile for new sessions while unplugged */
+  batteryProfileEnabled: boolean;
+  /** Battery percent at or below which the battery profile applies (100 = as soon as unplugged) */
+  batteryProfileBelowPercent: number;
+  /** Resolution/FPS/HDR/codec used instead of the settings above while the battery profile applies */
+  batteryStreamProfile: StreamProfileOverride;
   /** Preferred region URL (empty = auto) */
   region: string;
   /** Enable the optional proxy for Nvidia games catalog, session creation, and queue polling */
   sessionProxyEnabled: boolean;
   /** Optional proxy used for Nvidia games catalog, session creation, and queue polling */
   sessionProxyUrl: string;
+  /** Tunnel the signaling websocket through the session proxy (or HTTPS_PROXY/ALL_PROXY) */
+  signalingProxyEnabled: boolean;
+  /** ICE transport policy; "relay" forces traffic through a TURN server */
+  iceTransportPolicy: IceTransportPolicy;
+  /** Custom STUN/TURN server URLs, comma or whitespace separated */
+  customIceServerUrls: string;
+  /** Username for the custom TURN servers */
+  customIceUsername: string;
+  /** Credential for the custom TURN servers */
+  customIceCredential: string;
+  /** Local WebRTC UDP port range as "min-max" (empty = unrestricted) */
+  webrtcUdpPortRange: string;
+  /** Address family for signaling and ICE ("auto" = happy eyeballs) */
+  networkIpFamily: NetworkIpFamily;
+  /** Probe the path MTU and size video packets to fit */
+  pathMtuProbe: boolean;
+  /** DSCP-mark input traffic for QoS-aware routers */
+  networkQosMarking: boolean;
+  /** Native jitter buffer reorder window in ms */
+  jitterBufferMs: number;
+  /** Runtime log filter directives */
+  logFilter: string;
+  /** Chromium disk cache cap in MB, read at launch */
+  diskCacheLimitMb: number;
+  /** Screenshot and recording root; empty for the default */
+  mediaDirectory: string;
+  /** Raise the native media threads */
+  mediaThreadPriority: boolean;
+  /** Competitive preset layered over the stream, input and audio settings */
+  lowLatencyMode: boolean;
+  /** Re-publish the stream to a local WHIP endpoint for spectators */
+  restreamEnabled: boolean;
+  /** WHIP ingest URL for spectators */
+  restreamWhipUrl: string;
+  /** Spectator video height cap (0 = source) */
+  restreamMaxHeight: number;
   /** Enable clipboard paste into stream */
   clipboardPaste: boolean;
+  /** Privacy kill switch: never capture the microphone, whatever the microphone mode */
+  microphoneBlocked: boolean;
   /** Enable experimental gyroscope controller input mapping */
   enableGyroscopeControls: boolean;
+  /** Which of the four GFN player slots local controllers may occupy */
+  controllerSlotsEnabled: boolean[];
+  /** gamescope and Steam Input handling, read at launch */
+  gamescopeCompatibility: GamescopeCompatibility;
   /** Mouse sensitivity multiplier */
   mouseSensitivity: number;
   /** Software mouse acceleration strength percentage (1-150) */
   mouseAcceleration: number;
+  /** Ask for unadjusted pointer-lock movement so OS acceleration stays out of the deltas */
+  mouseRawInput: boolean;
+  /** Gamepad mouse mode pointer speed in pixels per second at full right-stick deflection */
+  gamepadMouseSpeed: number;
+  /** Gamepad mouse mode response curve (0 = linear, 100 = most precise near center) */
+  gamepadMouseAcceleration: number;
   /** Toggle stats overlay shortcut */
   shortcutToggleStats: string;
   /** Toggle pointer lock shortcut */
@@ -90,6 +179,14 @@ export interface Settings {
   shortcutScreenshot: string;
   /** Toggle stream recording shortcut */
   shortcutToggleRecording: string;
+  /** Mute/unmute stream audio shortcut */
+  shortcutToggleStreamMute: string;
+  /** Raise stream volume shortcut */
+  shortcutVolumeUp: string;
+  /** Lower stream volume shortcut */
+  shortcutVolumeDown: string;
+  /** Toggle text input mode (IME/international text) shortcut */
+  shortcutToggleTextInput: string;
   /** How often to re-show the session timer while streaming (0 = off) */
   sessionClockShowEveryMinutes: number;
   /** How long the session timer stays visible when it appears */
@@ -98,6 +195,16 @@ export interface Settings {
   microphoneMode: MicrophoneMode;
   /** Preferred microphone device ID (empty = default) */
   microphoneDeviceId: string;
+  /** WASAPI exclusive output for the native streamer on Windows */
+  audioOutputMode: AudioOutputMode;
+  /** Requested audio output buffer in ms (0 = platform default) */
+  audioBufferMs: number;
+  /** Stream playback volume in percent (client-side, not the OS mixer) */
+  streamVolume: number;
+  /** Stream audio muted */
+  streamMuted: boolean;
+  /** Briefly lower stream volume while an in-stream notice pops up */
+  duckOnNotifications: boolean;
   /** Hide stream buttons (mic/fullscreen/end-session) while streaming */
   hideStreamButtons: boolean;
   /** Show the Anti-AFK indicator badge while streaming */
@@ -106,21 +213,56 @@ export interface Settings {
   showStatsOnLaunch: boolean;
   /** Skip the free-tier queue server selection modal and launch with default routing */
   hideServerSelector: boolean;
+  /** HTTPS base URL of a queue service mirror (empty = PrintedWaste) */
+  queueServiceUrl: string;
+  /** Raw Ed25519 public key, base64, that signs the queue service's server mapping */
+  queueServicePublicKey: string;
   /** Desktop UI accent preset */
   appAccentColor: AppAccentColor;
+  /** Dark, light, or follow the OS color scheme */
+  appTheme: AppTheme;
   /** Use the large-screen controller-oriented shell and library layout */
   controllerMode: boolean;
   /** Automatically enter fullscreen when launching a stream */
   autoFullScreen: boolean;
+  /** macOS only: borderless uses simple fullscreen, exclusive the native Spaces fullscreen */
+  fullscreenMode: FullscreenMode;
+  /** Alt+Enter toggles fullscreen while streaming */
+  altEnterFullscreen: boolean;
   favoriteGameIds: string[];
   /** Enable the live elapsed session counter */
   sessionCounterEnabled: boolean;
   /** Also show the session-limit countdown in the stats overlay while streaming */
   showSessionTimeRemainingInStatsOverlay: boolean;
+  /** Metrics in the collapsed stats panel, in display order */
+  statsPanelFields: StatsPanelField[];
+  /** Stats panel size in percent */
+  statsPanelScale: number;
+  /** Stats panel background opacity in percent */
+  statsPanelOpacity: number;
+  /** Stop the stream after this many minutes without input (0 = never) */
+  idleDisconnectMinutes: number;
+  /** Remind after this many minutes of play in one session (0 = never) */
+  playReminderMinutes: number;
+  /** Re-sync the library from the service this often in the background (0 = never) */
+  librarySyncMinutes: number;
+  /** Local "HH:MM" bedtime for streaming (empty = none) */
+  playCurfew: string;
+  /** End the stream when the play reminder or bedtime is reached instead of only reminding */
+  playLimitDisconnect: boolean;
+  /** End the cloud session when the app quits instead of keeping it to resume */
+  endSessionOnExit: boolean;
   /** Window width */
   windowWidth: number;
   /** Window height */
   windowHeight: number;
+  /** Last window position (null = let the OS place the window) */
+  windowX: number | null;
+  windowY: number | null;
+  /** Display the window was last on */
+  windowDisplayId: number | null;
+  /** Display used for fullscreen streaming (null = the display the window is on) */
+  fullscreenDisplayId: number | null;
   /** Keyboard layout for mapping physical keys inside the remote session */
   keyboardLayout: KeyboardLayout;
   /** In-game language setting (sent to GFN servers via languageCode parameter) */
@@ -133,8 +275,22 @@ export interface Settings {
   nativeTransitionDiagnostics?: NativeTransitionDiagnostics;
   /** Show the currently streaming game as Discord Rich Presence activity */
   discordRichPresence: boolean;
+  /** Opt-in anonymous session quality telemetry: off, local file only, or local plus upload */
+  telemetryMode: TelemetryMode;
+  /** HTTPS endpoint that receives uploaded telemetry aggregates */
+  telemetryEndpoint: string;
+  /** Argon2id hash of the parental PIN in PHC format (empty = no PIN); set only through the parental IPC */
+  parentalPinHash: string;
+  /** Require the parental PIN to launch games */
+  parentalLockLaunch: boolean;
+  /** Require the parental PIN to open settings */
+  parentalLockSettings: boolean;
+  /** Highest content rating age shown in the library (null = no filtering) */
+  parentalMaxAge: number | null;
   /** Automatically check GitHub Releases for app updates in the background */
   autoCheckForUpdates: boolean;
+  /** Updater release channel */
+  updateChannel: UpdateChannel;
   /** When true, pressing Escape will exit fullscreen; when false Escape is sent to the game while pointer-locked */
   allowEscapeToExitFullscreen?: boolean;
 }
@@ -146,8 +302,9 @@ const LEGACY_STOP_SHORTCUTS = new Set(["META+SHIFT+Q", "CMD+SHIFT+Q"]);
 const LEGACY_ANTI_AFK_SHORTCUTS = new Set(["META+SHIFT+F10", "CMD+SHIFT+F10", "CTRL+SHIFT+F10"]);
 const DEFAULT_STREAM_PREFERENCES = getDefaultStreamPreferences();
 
-const NATIVE_VIDEO_BACKEND_PREFERENCES = new Set<NativeVideoBackendPreference>(["auto", "d3d11", "d3d12"]);
-const APP_ACCENT_COLORS = new Set<AppAccentColor>(["green", "blue", "violet", "amber", "rose"]);
+const NATIVE_VIDEO_BACKEND_PREFERENCES = new Set<NativeVideoBackendPreference>(["auto", "d3d11", "d3d12", "nvdec"]);
+const APP_ACCENT_COLORS = new Set<AppAccentColor>(["green", "blue", "violet", "amber", "rose", "custom"]);
+const APP_THEMES = new Set<AppTheme>(["dark", "light", "system"]);
 
 function normalizeNativeVideoBackendPreference(raw: unknown): NativeVideoBackendPreference {
   return NATIVE_VIDEO_BACKEND_PREFERENCES.has(raw as NativeVideoBackendPreference)
@@ -159,6 +316,115 @@ function normalizeAppAccentColor(raw: unknown): AppAccentColor {
   return APP_ACCENT_COLORS.has(raw as AppAccentColor) ? (raw as AppAccentColor) : "green";
 }
 
+function normalizeAppTheme(raw: unknown): AppTheme {
+  return APP_THEMES.has(raw as AppTheme) ? (raw as AppTheme) : "dark";
+}
+
+function normalizeFullscreenMode(raw: unknown): FullscreenMode {
+  return raw === "exclusive" ? "exclusive" : "borderless";
+}
+
+function normalizeIceTransportPolicy(raw: unknown): IceTransportPolicy {
+  return raw === "relay" ? "relay" : "all";
+}
+
+function normalizeNetworkIpFamily(raw: unknown): NetworkIpFamily {
+  return raw === "ipv4" || raw === "ipv6" ? raw : "auto";
+}
+
+function normalizeTelemetryMode(raw: unknown): TelemetryMode {
+  return raw === "local" || raw === "upload" ? raw : "off";
+}
+
+function normalizeIdleDisconnectMinutes(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value) || value <= 0) {
+    return 0;
+  }
+  return Math.min(120, Math.round(value));
+}
+
+function normalizePlayReminderMinutes(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value) || value <= 0) {
+    return 0;
+  }
+  return Math.min(480, Math.round(value));
+}
+
+function normalizeLibrarySyncMinutes(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value) || value <= 0) {
+    return 0;
+  }
+  return Math.max(5, Math.min(240, Math.round(value)));
+}
+
+function normalizeAudioOutputMode(raw: unknown): AudioOutputMode {
+  return raw === "exclusive" ? "exclusive" : "shared";
+}
+
+/** Installs that never picked a channel stay on the one their running version came from. */
+function normalizeUpdateChannel(raw: unknown): UpdateChannel {
+  if (raw === "beta" || raw === "stable") {
+    return raw;
+  }
+  return app.getVersion().includes("-") ? "beta" : "stable";
+}
+
+/** Also read from the raw settings file at bootstrap; a saved "on" from older builds means "auto". */
+export function normalizeGamescopeCompatibility(raw: unknown): GamescopeCompatibility {
+  return raw === "off" ? "off" : "auto";
+}
+
+export function normalizeAudioBufferMs(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value) || value <= 0) {
+    return 0;
+  }
+  return Math.min(100, Math.round(value));
+}
+
+function normalizeJitterBufferMs(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value) || value < 0) {
+    return 2;
+  }
+  return Math.min(50, Math.round(value));
+}
+
+function normalizeBatteryProfileBelowPercent(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value)) {
+    return 100;
+  }
+  return Math.min(100, Math.max(5, Math.round(value / 5) * 5));
+}
+
+function normalizeStreamVolume(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value)) {
+    return 100;
+  }
+  return Math.max(0, Math.min(100, Math.round(value)));
+}
+
+function normalizeStatsPanelScale(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value)) {
+    return 100;
+  }
+  return Math.max(80, Math.min(150, Math.round(value)));
+}
+
+function normalizeStatsPanelOpacity(raw: unknown): number {
+  const value = Number(raw);
+  if (!Number.isFinite(value)) {
+    return 100;
+  }
+  return Math.max(20, Math.min(100, Math.round(value)));
+}
+
 function normalizeRecordingBitrateMbps(raw: unknown): number | null {
   if (raw === null || raw === undefined) {
     return null;
@@ -175,12 +441,16 @@ const DEFAULT_SETTINGS: Settings = {
   aspectRatio: "16:9",
   posterSizeScale: 1,
   fps: 60,
+  renderFpsCap: 0,
+  renderFpsCapOnBattery: 0,
+  renderFpsCapRequestServer: false,
   maxBitrateMbps: 75,
   recordingBitrateMbps: null,
   streamClientMode: "web",
   nativeStreamerBackend: "gstreamer",
   nativeVideoBackend: "auto",
   nativeStreamerExecutablePath: "",
+  nativeExperimentalFlags: "",
   nativeCloudGsyncMode: "auto",
   nativeD3dFullscreenMode: "auto",
   nativeExternalRenderer: true,
@@ -188,14 +458,44 @@ const DEFAULT_SETTINGS: Settings = {
   codec: DEFAULT_STREAM_PREFERENCES.codec,
   decoderPreference: "auto",
   encoderPreference: "auto",
+  driverWorkaroundsEnabled: true,
+  disabledDriverWorkarounds: [],
   colorQuality: DEFAULT_STREAM_PREFERENCES.colorQuality,
+  gameStreamOverrides: {},
+  batteryProfileEnabled: false,
+  batteryProfileBelowPercent: 100,
+  batteryStreamProfile: { resolution: "1280x720", fps: 30, hdr: false },
   region: "",
   sessionProxyEnabled: false,
   sessionProxyUrl: "",
+  signalingProxyEnabled: false,
+  iceTransportPolicy: "all",
+  customIceServerUrls: "",
+  customIceUsername: "",
+  customIceCredential: "",
+  webrtcUdpPortRange: "",
+  networkIpFamily: "auto",
+  pathMtuProbe: false,
+  networkQosMarking: false,
+  jitterBufferMs: 2,
+  logFilter: "",
+  diskCacheLimitMb: 0,
+  mediaDirectory: "",
+  mediaThreadPriority: false,
+  lowLatencyMode: false,
+  restreamEnabled: false,
+  restreamWhipUrl: "",
+  restreamMaxHeight: 720,
   clipboardPaste: false,
+  microphoneBlocked: false,
   enableGyroscopeControls: false,
+  controllerSlotsEnabled: [true, true, true, true],
+  gamescopeCompatibility: "auto",
   mouseSensitivity: 1,
   mouseAcceleration: 1,
+  mouseRawInput: true,
+  gamepadMouseSpeed: 1200,
+  gamepadMouseAcceleration: 50,
   shortcutToggleStats: "F3",
   shortcutTogglePointerLock: "F8",
   shortcutToggleFullscreen: "F10",
@@ -204,29 +504,63 @@ const DEFAULT_SETTINGS: Settings = {
   shortcutToggleMicrophone: defaultMicShortcut,
   shortcutScreenshot: "F11",
   shortcutToggleRecording: "F12",
+  shortcutToggleStreamMute: "Ctrl+Shift+End",
+  shortcutVolumeUp: "Ctrl+Shift+PageUp",
+  shortcutVolumeDown: "Ctrl+Shift+PageDown",
+  shortcutToggleTextInput: "Ctrl+Shift+T",
   microphoneMode: "disabled",
   microphoneDeviceId: "",
+  audioOutputMode: "shared",
+  audioBufferMs: 0,
+  streamVolume: 100,
+  streamMuted: false,
+  duckOnNotifications: false,
   hideStreamButtons: false,
   showAntiAfkIndicator: true,
   showStatsOnLaunch: false,
   hideServerSelector: false,
+  queueServiceUrl: "",
+  queueServicePublicKey: "",
   appAccentColor: "green",
+  appTheme: "dark",
   controllerMode: false,
   autoFullScreen: false,
+  fullscreenMode: "borderless",
+  altEnterFullscreen: true,
   favoriteGameIds: [],
   sessionCounterEnabled: false,
   showSessionTimeRemainingInStatsOverlay: false,
+  statsPanelFields: [...DEFAULT_STATS_PANEL_FIELDS],
+  statsPanelScale: 100,
+  statsPanelOpacity: 100,
+  idleDisconnectMinutes: 0,
+  playReminderMinutes: 0,
+  librarySyncMinutes: 30,
+  playCurfew: "",
+  playLimitDisconnect: false,
+  endSessionOnExit: false,
   sessionClockShowEveryMinutes: 60,
   sessionClockShowDurationSeconds: 30,
   windowWidth: 1400,
   windowHeight: 900,
+  windowX: null,
+  windowY: null,
+  windowDisplayId: null,
+  fullscreenDisplayId: null,
   keyboardLayout: DEFAULT_KEYBOARD_LAYOUT,
   gameLanguage: "en_US",
   enableL4S: false,
   enableCloudGsync: false,
   nativeTransitionDiagnostics: undefined,
   discordRichPresence: false,
+  telemetryMode: "off",
+  telemetryEndpoint: "",
+  parentalPinHash: "",
+  parentalLockLaunch: true,
+  parentalLockSettings: true,
+  parentalMaxAge: null,
   autoCheckForUpdates: true,
+  updateChannel: "stable",
   allowEscapeToExitFullscreen: false,
 };
 
@@ -245,7 +579,7 @@ export class SettingsManager {
   private load(): Settings {
     try {
       if (!existsSync(this.settingsPath)) {
-        const defaults = { ...DEFAULT_SETTINGS };
+        const defaults = { ...DEFAULT_SETTINGS, updateChannel: normalizeUpdateChannel(undefined) };
         this.enforceCompatibility(defaults);
         return defaults;
       }
@@ -275,6 +609,41 @@ export class SettingsManager {
         migrated = true;
       }
 
+      const appThemeBefore = merged.appTheme;
+      merged.appTheme = normalizeAppTheme(merged.appTheme);
+      if (merged.appTheme !== appThemeBefore) {
+        migrated = true;
+      }
+
+      const fullscreenModeBefore = merged.fullscreenMode;
+      merged.fullscreenMode = normalizeFullscreenMode(merged.fullscreenMode);
+      if (merged.fullscreenMode !== fullscreenModeBefore) {
+        migrated = true;
+      }
+
+      const iceTransportPolicyBefore = merged.iceTransportPolicy;
+      merged.iceTransportPolicy = normalizeIceTransportPolicy(merged.iceTransportPolicy);
+      if (merged.iceTransportPolicy !== iceTransportPolicyBefore) {
+        migrated = true;
+      }
+
+      const networkIpFamilyBefore = merged.networkIpFamily;
+      merged.networkIpFamily = normalizeNetworkIpFamily(merged.networkIpFamily);
+      if (merged.networkIpFamily !== networkIpFamilyBefore) {
+        migrated = true;
+      }
+
+      merged.telemetryMode = normalizeTelemetryMode(merged.telemetryMode);
+      // There is no default endpoint, so sharing without one would only ever queue locally.
+      if (merged.telemetryMode === "upload" && !String(merged.telemetryEndpoint ?? "").trim()) {
+        merged.telemetryMode = "local";
+        migrated = true;
+      }
+      if (merged.parentalMaxAge !== null && !(typeof merged.parentalMaxAge === "number" && Number.isFinite(merged.parentalMaxAge))) {
+        merged.parentalMaxAge = null;
+        migrated = true;
+      }
+
       // Migrate legacy boolean accelerator setting to percentage slider.
       if (typeof (parsed as { mouseAcceleration?: unknown }).mouseAcceleration === "boolean") {
         merged.mouseAcceleration = (parsed as { mouseAcceleration?: boolean }).mouseAcceleration ? 100 : 1;
@@ -293,6 +662,99 @@ export class SettingsManager {
       if (merged.recordingBitrateMbps !== recordingBitrateBefore) {
         migrated = true;
       }
+      const idleDisconnectBefore = merged.idleDisconnectMinutes;
+      merged.idleDisconnectMinutes = normalizeIdleDisconnectMinutes(merged.idleDisconnectMinutes);
+      if (merged.idleDisconnectMinutes !== idleDisconnectBefore) {
+        migrated = true;
+      }
+      const playReminderBefore = merged.playReminderMinutes;
+      merged.playReminderMinutes = normalizePlayReminderMinutes(merged.playReminderMinutes);
+      if (merged.playReminderMinutes !== playReminderBefore) {
+        migrated = true;
+      }
+      const librarySyncBefore = merged.librarySyncMinutes;
+      merged.librarySyncMinutes = normalizeLibrarySyncMinutes(merged.librarySyncMinutes);
+      if (merged.librarySyncMinutes !== librarySyncBefore) {
+        migrated = true;
+      }
+      const playCurfewBefore = merged.playCurfew;
+      merged.playCurfew = normalizePlayCurfew(merged.playCurfew);
+      if (merged.playCurfew !== playCurfewBefore) {
+        migrated = true;
+      }
+      const diskCacheLimitBefore = merged.diskCacheLimitMb;
+      merged.diskCacheLimitMb = normalizeDiskCacheLimitMb(merged.diskCacheLimitMb);
+      if (merged.diskCacheLimitMb !== diskCacheLimitBefore) {
+        migrated = true;
+      }
+      const audioOutputModeBefore = merged.audioOutputMode;
+      merged.audioOutputMode = normalizeAudioOutputMode(merged.audioOutputMode);
+      if (merged.audioOutputMode !== audioOutputModeBefore) {
+        migrated = true;
+      }
+      const updateChannelBefore = merged.updateChannel;
+      // Read the saved value, not the merged one, so a missing channel is seeded from the version.
+      merged.updateChannel = normalizeUpdateChannel(parsedSettings.updateChannel);
+      if (merged.updateChannel !== updateChannelBefore) {
+        migrated = true;
+      }
+      const gamescopeCompatibilityBefore = merged.gamescopeCompatibility;
+      merged.gamescopeCompatibility = normalizeGamescopeCompatibility(merged.gamescopeCompatibility);
+      if (merged.gamescopeCompatibility !== gamescopeCompatibilityBefore) {
+        migrated = true;
+      }
+      const audioBufferBefore = merged.audioBufferMs;
+      merged.audioBufferMs = normalizeAudioBufferMs(merged.audioBufferMs);
+      if (merged.audioBufferMs !== audioBufferBefore) {
+        migrated = true;
+      }
+      const jitterBufferBefore = merged.jitterBufferMs;
+      merged.jitterBufferMs = normalizeJitterBufferMs(merged.jitterBufferMs);
+      if (merged.jitterBufferMs !== jitterBufferBefore) {
+        migrated = true;
+      }
+      const renderFpsCapBefore = `${merged.renderFpsCap}/${merged.renderFpsCapOnBattery}`;
+      merged.renderFpsCap = normalizeRenderFpsCap(merged.renderFpsCap);
+      merged.renderFpsCapOnBattery = normalizeRenderFpsCap(merged.renderFpsCapOnBattery);
+      if (`${merged.renderFpsCap}/${merged.renderFpsCapOnBattery}` !== renderFpsCapBefore) {
+        migrated = true;
+      }
+      const streamVolumeBefore = merged.streamVolume;
+      merged.streamVolume = normalizeStreamVolume(merged.streamVolume);
+      if (merged.streamVolume !== streamVolumeBefore) {
+        migrated = true;
+      }
+      const controllerSlotsBefore = JSON.stringify(merged.controllerSlotsEnabled);
+      merged.controllerSlotsEnabled = normalizeControllerSlotsEnabled(merged.controllerSlotsEnabled);
+      if (JSON.stringify(merged.controllerSlotsEnabled) !== controllerSlotsBefore) {
+        migrated = true;
+      }
+      const statsPanelFieldsBefore = JSON.stringify(merged.statsPanelFields);
+      merged.statsPanelFields = normalizeStatsPanelFields(merged.statsPanelFields);
+      if (JSON.stringify(merged.statsPanelFields) !== statsPanelFieldsBefore) {
+        migrated = true;
+      }
+      const statsPanelScaleBefore = merged.statsPanelScale;
+      merged.statsPanelScale = normalizeStatsPanelScale(merged.statsPanelScale);
+      if (merged.statsPanelScale !== statsPanelScaleBefore) {
+        migrated = true;
+      }
+      const statsPanelOpacityBefore = merged.statsPanelOpacity;
+      merged.statsPanelOpacity = normalizeStatsPanelOpacity(merged.statsPanelOpacity);
+      if (merged.statsPanelOpacity !== statsPanelOpacityBefore) {
+        migrated = true;
+      }
+      const gameStreamOverridesBefore = JSON.stringify(merged.gameStreamOverrides);
+      merged.gameStreamOverrides = normalizeGameStreamOverrides(merged.gameStreamOverrides);
+      if (JSON.stringify(merged.gameStreamOverrides) !== gameStreamOverridesBefore) {
+        migrated = true;
+      }
+      const batteryProfileBefore = JSON.stringify([merged.batteryProfileBelowPercent, merged.batteryStreamProfile]);
+      merged.batteryProfileBelowPercent = normalizeBatteryProfileBelowPercent(merged.batteryProfileBelowPercent);
+      merged.batteryStreamProfile = normalizeStreamProfileOverride(merged.batteryStreamProfile);
+      if (JSON.stringify([merged.batteryProfileBelowPercent, merged.batteryStreamProfile]) !== batteryProfileBefore) {
+        migrated = true;
+      }
       if (migrated) {
         writeFileSync(this.settingsPath, JSON.stringify(merged, null, 2), "utf-8");
       }
@@ -427,7 +889,16 @@ export class SettingsManager {
    * Reset all settings to defaults
    */
   reset(): Settings {
-    this.settings = { ...DEFAULT_SETTINGS };
+    // Parental controls survive a reset; otherwise resetting would be a way around the PIN.
+    const { parentalPinHash, parentalLockLaunch, parentalLockSettings, parentalMaxAge } = this.settings;
+    this.settings = {
+      ...DEFAULT_SETTINGS,
+      updateChannel: normalizeUpdateChannel(undefined),
+      parentalPinHash,
+      parentalLockLaunch,
+      parentalLockSettings,
+      parentalMaxAge,
+    };
     this.enforceCompatibility(this.settings);
     this.save();
     return { ...this.settings };
@@ -437,7 +908,7 @@ export class SettingsManager {
    * Get the default settings
    */
   getDefaults(): Settings {
-    const defaults = { ...DEFAULT_SETTINGS };
+    const defaults = { ...DEFAULT_SETTINGS, updateChannel: normalizeUpdateChannel(undefined) };
     this.enforceCompatibility(defaults);
     return defaults;
   }
@@ -1,5 +1,6 @@
-import { BrowserWindow, type IpcMain } from "electron";
+import { BrowserWindow, powerMonitor, powerSaveBlocker, type IpcMain } from "electron";
 import { IPC_CHANNELS } from "@shared/ipc";
+import { resolveRenderFpsCap } from "@shared/renderFpsCap";
 import type {
   IceCandidatePayload,
   KeyframeRequest,
@@ -13,11 +14,14 @@ import type {
   Settings,
   SignalingConnectRequest,
 } from "@shared/gfn";
+import { signalingHostWithPort } from "../gfn/ipFamily";
+import { resolveSignalingProxyUrl } from "../gfn/proxyTunnel";
 import { GfnSignalingClient } from "../gfn/signaling";
 import { NativeStreamerManager } from "../nativeStreamer/manager";
 import { normalizeNativeInputPacket } from "../nativeStreamer/input";
 import { normalizeNativeRenderSurface } from "../nativeStreamer/surface";
 import { getNativeCloudGsyncCapabilities } from "../nativeCloudGsync";
+import { SessionPowerInhibitor } from "../powerInhibit";
 import type { SettingsManager } from "../settings";
 
 export interface SignalingCoordinatorDeps {
@@ -25,6 +29,8 @@ export interface SignalingCoordinatorDeps {
   mainDir: string;
   settingsManager: SettingsManager;
   getMainWindow(): BrowserWindow | null;
+  /** Brings a native streamer's env in line with the current driver workaround rules */
+  applyDriverWorkaroundEnv(env: NodeJS.ProcessEnv): void;
 }
 
 export class SignalingCoordinator {
@@ -33,6 +39,7 @@ export class SignalingCoordinator {
   private nativeStreamerManager: NativeStreamerManager | null = null;
   private nativeStreamerContext: NativeStreamerSessionContext | null = null;
   private nativeStreamerFallbackSessionId: string | null = null;
+  private readonly powerInhibitor = new SessionPowerInhibitor(powerSaveBlocker);
 
   constructor(private readonly deps: SignalingCoordinatorDeps) {}
 
@@ -131,6 +138,20 @@ export class SignalingCoordinator {
       },
     );
 
+    ipcMain.on(IPC_CHANNELS.NATIVE_UPDATE_VOLUME, (_event, volume: number) => {
+      if (!this.isNativeStreamerSelected() || !Number.isFinite(volume)) {
+        return;
+      }
+      this.getNativeStreamerManager().updateVolume(volume);
+    });
+
+    ipcMain.handle(IPC_CHANNELS.NATIVE_SELECT_VIDEO_LAYER, async (_event, layerId: string) => {
+      if (!this.isNativeStreamerSelected() || typeof layerId !== "string" || !layerId) {
+        throw new Error("Video layers can only be switched on the native streamer");
+      }
+      await this.getNativeStreamerManager().selectVideoLayer(layerId);
+    });
+
     ipcMain.handle(
       IPC_CHANNELS.REQUEST_KEYFRAME,
       async (_event, payload: KeyframeRequest) => {
@@ -157,6 +178,10 @@ export class SignalingCoordinator {
       );
       return capabilities;
     });
+
+    // Handhelds move between profiles mid-session when unplugged or docked.
+    powerMonitor.on("on-battery", () => this.applyRenderFpsCap());
+    powerMonitor.on("on-ac", () => this.applyRenderFpsCap());
   }
 
   disconnectForShutdown(options: {
@@ -172,6 +197,7 @@ export class SignalingCoordinator {
     this.nativeStreamerManager = null;
     this.nativeStreamerContext = null;
     this.nativeStreamerFallbackSessionId = null;
+    this.powerInhibitor.release(options.reason);
   }
 
   stopNativeStreamer(reason: string): void {
@@ -245,11 +271,43 @@ export class SignalingCoordinator {
     if (key === "maxBitrateMbps") {
       this.updateNativeStreamerBitrateSetting(value);
     }
+    if (key === "renderFpsCap" || key === "renderFpsCapOnBattery") {
+      this.applyRenderFpsCap();
+    }
+  }
+
+  private withRenderFpsCap(context: NativeStreamerSessionContext): NativeStreamerSessionContext {
+    const renderFpsCap = resolveRenderFpsCap(
+      this.deps.settingsManager.getAll(),
+      powerMonitor.isOnBatteryPower(),
+      context.settings.fps,
+    );
+    return { ...context, settings: { ...context.settings, renderFpsCap } };
+  }
+
+  /** Re-resolves the cap for the active native session after a power or settings change. */
+  private applyRenderFpsCap(): void {
+    const context = this.nativeStreamerContext;
+    if (!context) {
+      return;
+    }
+    const next = this.withRenderFpsCap(context);
+    const renderFpsCap = next.settings.renderFpsCap ?? 0;
+    if (renderFpsCap === (context.settings.renderFpsCap ?? 0)) {
+      return;
+    }
+    this.nativeStreamerContext = next;
+    console.log(
+      `[NativeStreamer] Render FPS cap ${renderFpsCap > 0 ? `set to ${renderFpsCap} fps` : "removed"} (${powerMonitor.isOnBatteryPower() ? "battery" : "AC"} power).`,
+    );
+    this.nativeStreamerManager?.updateRenderFpsCap(renderFpsCap);
   }
 
   private async connectSignaling(payload: SignalingConnectRequest): Promise<void> {
     const nextKey = `${payload.sessionId}|${payload.signalingServer}|${payload.signalingUrl ?? ""}`;
-    this.nativeStreamerContext = payload.nativeStreamer ?? null;
+    this.nativeStreamerContext = payload.nativeStreamer
+      ? this.withRenderFpsCap(payload.nativeStreamer)
+      : null;
     this.nativeStreamerFallbackSessionId = null;
     if (this.nativeStreamerContext) {
       console.log(
@@ -258,6 +316,7 @@ export class SignalingCoordinator {
           sessionId: this.nativeStreamerContext.session.sessionId,
           resolution: this.nativeStreamerContext.settings.resolution,
           fps: this.nativeStreamerContext.settings.fps,
+          renderFpsCap: this.nativeStreamerContext.settings.renderFpsCap,
           codec: this.nativeStreamerContext.settings.codec,
           negotiatedStreamProfile:
             this.nativeStreamerContext.session.negotiatedStreamProfile,
@@ -282,10 +341,14 @@ export class SignalingCoordinator {
     await this.resetNativeStreamerForSignalingReconnect();
     await this.prepareNativeStreamerBeforeSignaling();
 
+    const settings = this.deps.settingsManager.getAll();
+    const signalingTarget = payload.signalingUrl?.trim() || `wss://${signalingHostWithPort(payload.signalingServer)}/`;
     this.signalingClient = new GfnSignalingClient(
       payload.signalingServer,
       payload.sessionId,
       payload.signalingUrl,
+      settings.networkIpFamily,
+      resolveSignalingProxyUrl(settings, signalingTarget),
     );
     this.signalingClientKey = nextKey;
     this.signalingClient.onEvent((event) => this.routeSignalingEvent(event));
@@ -297,8 +360,10 @@ export class SignalingCoordinator {
         .catch(() => undefined);
       this.signalingClient = null;
       this.signalingClientKey = null;
+      this.powerInhibitor.release("signaling connect failed");
       throw error;
     }
+    this.powerInhibitor.acquire(`session ${payload.sessionId}`);
   }
 
   private async disconnectSignaling(): Promise<void> {
@@ -308,6 +373,7 @@ export class SignalingCoordinator {
     this.signalingClient?.disconnect();
     this.signalingClient = null;
     this.signalingClientKey = null;
+    this.powerInhibitor.release("signaling disconnect");
   }
 
   private emitToRenderer(event: MainToRendererSignalingEvent): void {
@@ -330,6 +396,9 @@ export class SignalingCoordinator {
       getD3dFullscreenMode: () =>
         this.deps.settingsManager?.get("nativeD3dFullscreenMode") ?? "auto",
       getExternalRendererEnabled: () => true,
+      getExperimentalFlags: () =>
+        this.deps.settingsManager?.get("nativeExperimentalFlags") ?? "",
+      applyDriverWorkaroundEnv: (env) => this.deps.applyDriverWorkaroundEnv(env),
       emit: (event) => this.emitToRenderer(event),
       sendAnswer: async (payload) => {
         if (!this.signalingClient) {
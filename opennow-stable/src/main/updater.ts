@@ -4,7 +4,8 @@ import type { AppUpdater, ProgressInfo, UpdateDownloadedEvent, UpdateInfo } from
 
 import { getAppBuildInfo } from "./appBuildInfo";
 import { getLinuxUpdaterSupport } from "./linuxUpdaterSupport";
-import type { AppUpdaterState } from "@shared/gfn";
+import { normalizeReleaseNotes } from "./releaseNotes";
+import type { AppUpdaterState, UpdateChannel } from "@shared/gfn";
 
 const { autoUpdater } = electronUpdater;
 
@@ -17,6 +18,7 @@ export interface AppUpdaterController {
   dispose(): void;
   getState(): AppUpdaterState;
   setAutomaticChecksEnabled(enabled: boolean): AppUpdaterState;
+  setChannel(channel: UpdateChannel): AppUpdaterState;
   checkForUpdates(source?: "auto" | "manual"): Promise<AppUpdaterState>;
   downloadUpdate(): Promise<AppUpdaterState>;
   quitAndInstall(): Promise<AppUpdaterState>;
@@ -25,14 +27,11 @@ export interface AppUpdaterController {
 interface AppUpdaterControllerOptions {
   onStateChanged: (state: AppUpdaterState) => void;
   automaticChecksEnabled: boolean;
+  channel: UpdateChannel;
   onBeforeQuitAndInstall?: () => void;
   onQuitAndInstallError?: () => void;
 }
 
-function isPrereleaseVersion(version: string): boolean {
-  return version.includes("-");
-}
-
 function pickRuntimeToken(): string | null {
   for (const key of UPDATER_TOKEN_ENV_KEYS) {
     const value = process.env[key]?.trim();
@@ -73,76 +72,75 @@ function getUpdateVersion(info: UpdateInfo | UpdateDownloadedEvent | null | unde
   return (info as { version?: string } | null | undefined)?.version;
 }
 
-function createDisabledState(buildInfo: ReturnType<typeof getAppBuildInfo>, message: string): AppUpdaterState {
-  return {
+function createDisabledController(
+  buildInfo: ReturnType<typeof getAppBuildInfo>,
+  options: AppUpdaterControllerOptions,
+  message: string,
+): AppUpdaterController {
+  let disabledState: AppUpdaterState = {
     status: "disabled",
     currentVersion: buildInfo.version,
     currentDisplayVersion: buildInfo.displayVersion,
     currentBuildNumber: buildInfo.buildNumber,
     updateSource: "github-releases",
+    channel: options.channel,
     message,
     canCheck: false,
     canDownload: false,
     canInstall: false,
     isPackaged: app.isPackaged,
   };
+  return {
+    initialize() {
+      options.onStateChanged(disabledState);
+    },
+    dispose() {},
+    getState() {
+      return disabledState;
+    },
+    setAutomaticChecksEnabled() {
+      return disabledState;
+    },
+    setChannel(channel: UpdateChannel) {
+      disabledState = { ...disabledState, channel };
+      options.onStateChanged(disabledState);
+      return disabledState;
+    },
+    async checkForUpdates() {
+      return disabledState;
+    },
+    async downloadUpdate() {
+      return disabledState;
+    },
+    async quitAndInstall() {
+      return disabledState;
+    },
+  };
 }
 
 export function createAppUpdaterController(options: AppUpdaterControllerOptions): AppUpdaterController {
   const buildInfo = getAppBuildInfo();
   const currentVersion = buildInfo.version;
   if (!app.isPackaged) {
-    const disabledState = createDisabledState(buildInfo, "Auto-updates are only available in packaged builds.");
-    return {
-      initialize() {
-        options.onStateChanged(disabledState);
-      },
-      dispose() {},
-      getState() {
-        return disabledState;
-      },
-      setAutomaticChecksEnabled() {
-        return disabledState;
-      },
-      async checkForUpdates() {
-        return disabledState;
-      },
-      async downloadUpdate() {
-        return disabledState;
-      },
-      async quitAndInstall() {
-        return disabledState;
-      },
-    };
+    return createDisabledController(buildInfo, options, "Auto-updates are only available in packaged builds.");
+  }
+
+  // The portable exe has no installer to hand an update to.
+  if (process.env.PORTABLE_EXECUTABLE_DIR) {
+    return createDisabledController(
+      buildInfo,
+      options,
+      "The portable build does not update itself. Download the new portable release from GitHub Releases.",
+    );
   }
 
   const linuxUpdaterSupport = getLinuxUpdaterSupport();
   if (!linuxUpdaterSupport.supported) {
-    const disabledState = createDisabledState(
+    return createDisabledController(
       buildInfo,
+      options,
       linuxUpdaterSupport.message ?? "Auto-updates are not available for this Linux install.",
     );
-    return {
-      initialize() {
-        options.onStateChanged(disabledState);
-      },
-      dispose() {},
-      getState() {
-        return disabledState;
-      },
-      setAutomaticChecksEnabled() {
-        return disabledState;
-      },
-      async checkForUpdates() {
-        return disabledState;
-      },
-      async downloadUpdate() {
-        return disabledState;
-      },
-      async quitAndInstall() {
-        return disabledState;
-      },
-    };
   }
 
   const updater: AppUpdater = autoUpdater;
@@ -154,12 +152,20 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
     };
   }
 
+  // Integrity: electron-updater checks each download against the sha512 in the
+  // release's latest*.yml, which comes from the same GitHub release. Nothing
+  // else is verified: the Windows build sets no publisherName, so there is no
+  // Authenticode check; the macOS build is not signed, so Squirrel.Mac has no
+  // signature to compare; AppImage and deb updates have no signature at all.
+  // Without a signature check an update only installs when the user clicks
+  // restart, never silently on quit.
   updater.autoDownload = false;
   updater.autoInstallOnAppQuit = false;
   updater.autoRunAppAfterInstall = true;
-  updater.allowPrerelease = isPrereleaseVersion(currentVersion);
+  updater.allowPrerelease = options.channel === "beta";
   updater.allowDowngrade = false;
-  updater.fullChangelog = false;
+  // Notes for every version since the installed one, not just the newest.
+  updater.fullChangelog = true;
 
   let disposed = false;
   let startupTimer: NodeJS.Timeout | null = null;
@@ -167,6 +173,7 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
   let checkInFlight = false;
   let downloadInFlight = false;
   let automaticChecksEnabled = options.automaticChecksEnabled;
+  let channel = options.channel;
   let availableUpdateInfo: UpdateInfo | null = null;
   let downloadedUpdateInfo: UpdateInfo | null = null;
 
@@ -180,6 +187,7 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
 
   let state: AppUpdaterState = {
     ...baseState,
+    channel,
     status: "idle",
     canCheck: true,
     canDownload: false,
@@ -204,6 +212,7 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
       ...state,
       ...patch,
       ...baseState,
+      channel,
     });
     emitState();
   };
@@ -249,6 +258,7 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
       status: "available",
       availableVersion: info.version,
       downloadedVersion: undefined,
+      releaseNotes: normalizeReleaseNotes(info.releaseNotes, info.version),
       progress: undefined,
       lastCheckedAt: Date.now(),
       message: `OpenNOW ${info.version} is available. Download when ready.`,
@@ -262,6 +272,7 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
       status: "not-available",
       availableVersion: undefined,
       downloadedVersion: undefined,
+      releaseNotes: undefined,
       progress: undefined,
       lastCheckedAt: Date.now(),
       message: "OpenNOW is up to date.",
@@ -329,6 +340,24 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
       scheduleAutomaticChecks();
       return state;
     },
+    setChannel(nextChannel: UpdateChannel) {
+      if (nextChannel === channel) {
+        return state;
+      }
+      channel = nextChannel;
+      updater.allowPrerelease = channel === "beta";
+      // An update found on the other channel no longer applies; a downloaded one still installs.
+      if (state.status === "available" || state.status === "not-available") {
+        availableUpdateInfo = null;
+        updateState({ status: "idle", availableVersion: undefined, releaseNotes: undefined, message: undefined });
+      } else {
+        updateState({});
+      }
+      if (automaticChecksEnabled) {
+        void controller.checkForUpdates("auto");
+      }
+      return state;
+    },
     async checkForUpdates(source: "auto" | "manual" = "manual") {
       if (disposed || checkInFlight || downloadInFlight) {
         return state;
@@ -361,6 +390,10 @@ export function createAppUpdaterController(options: AppUpdaterControllerOptions)
         updateState({});
       }
 
+      // Background checks fetch the update too, so Restart to Install is ready without another wait.
+      if (source === "auto" && state.status === "available") {
+        return controller.downloadUpdate();
+      }
       return state;
     },
     async downloadUpdate() {
@@ -0,0 +1,19 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { normalizeThemeHexColor, parseUserTheme } from "./userTheme";
+
+test("normalizes short and long hex accents", () => {
+  assert.equal(normalizeThemeHexColor("#2DD4BF"), "#2dd4bf");
+  assert.equal(normalizeThemeHexColor(" 0af "), "#00aaff");
+  assert.equal(normalizeThemeHexColor("#12345"), null);
+  assert.equal(normalizeThemeHexColor("teal"), null);
+  assert.equal(normalizeThemeHexColor(42), null);
+});
+
+test("ignores theme files without a usable accent", () => {
+  assert.deepEqual(parseUserTheme({ accent: "#ff8800" }), { accent: "#ff8800" });
+  assert.deepEqual(parseUserTheme({ accent: "orange" }), { accent: null });
+  assert.deepEqual(parseUserTheme(["#ff8800"]), { accent: null });
+  assert.deepEqual(parseUserTheme(null), { accent: null });
+});
@@ -0,0 +1,42 @@
+import { existsSync, readFileSync } from "node:fs";
+import { join } from "node:path";
+
+import type { UserTheme } from "@shared/gfn";
+
+export const USER_THEME_FILE_NAME = "theme.json";
+
+/** Accepts #rgb or #rrggbb and returns lowercase #rrggbb. */
+export function normalizeThemeHexColor(raw: unknown): string | null {
+  if (typeof raw !== "string") {
+    return null;
+  }
+  const match = /^#?([0-9a-f]{3}|[0-9a-f]{6})$/i.exec(raw.trim());
+  if (!match) {
+    return null;
+  }
+  const digits = match[1].length === 3
+    ? match[1].split("").map((digit) => `${digit}${digit}`).join("")
+    : match[1];
+  return `#${digits.toLowerCase()}`;
+}
+
+/** Reads the fields OpenNOW understands from a parsed theme file. */
+export function parseUserTheme(raw: unknown): Pick<UserTheme, "accent"> {
+  if (!raw || typeof raw !== "object") {
+    return { accent: null };
+  }
+  return { accent: normalizeThemeHexColor((raw as { accent?: unknown }).accent) };
+}
+
+export function loadUserTheme(userDataPath: string): UserTheme {
+  const path = join(userDataPath, USER_THEME_FILE_NAME);
+  if (!existsSync(path)) {
+    return { path, accent: null };
+  }
+  try {
+    return { path, ...parseUserTheme(JSON.parse(readFileSync(path, "utf-8"))) };
+  } catch (error) {
+    console.warn(`[Theme] Ignoring unreadable theme file ${path}:`, error);
+    return { path, accent: null };
+  }
+}
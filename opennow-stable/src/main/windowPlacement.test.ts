@@ -0,0 +1,68 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import {
+  findDisplayForRect,
+  resolveMiniPlayerBounds,
+  resolveWindowPlacement,
+  type PlacementDisplay,
+} from "./windowPlacement";
+
+const primary: PlacementDisplay = {
+  id: 1,
+  bounds: { x: 0, y: 0, width: 1920, height: 1080 },
+  workArea: { x: 0, y: 0, width: 1920, height: 1040 },
+  scaleFactor: 1,
+};
+
+const secondary: PlacementDisplay = {
+  id: 2,
+  bounds: { x: 1920, y: 0, width: 2560, height: 1440 },
+  workArea: { x: 1920, y: 0, width: 2560, height: 1400 },
+  scaleFactor: 1.5,
+};
+
+test("restores a saved window position that is still visible", () => {
+  const placement = resolveWindowPlacement(
+    { width: 1400, height: 900, x: 2100, y: 100, displayId: 2 },
+    [primary, secondary],
+    primary,
+  );
+
+  assert.deepEqual(placement, { x: 2100, y: 100, width: 1400, height: 900 });
+});
+
+test("moves the window onto the primary display when its monitor was unplugged", () => {
+  const placement = resolveWindowPlacement(
+    { width: 1400, height: 900, x: 2100, y: 100, displayId: 2 },
+    [primary],
+    primary,
+  );
+
+  assert.deepEqual(placement, { x: 260, y: 70, width: 1400, height: 900 });
+});
+
+test("centers on the remembered display when no position was saved", () => {
+  const placement = resolveWindowPlacement(
+    { width: 1400, height: 900, x: null, y: null, displayId: 2 },
+    [primary, secondary],
+    primary,
+  );
+
+  assert.equal(findDisplayForRect([primary, secondary], placement)?.id, 2);
+});
+
+test("shrinks oversized windows to fit the target work area", () => {
+  const placement = resolveWindowPlacement(
+    { width: 4000, height: 3000, x: null, y: null, displayId: null },
+    [primary],
+    primary,
+  );
+
+  assert.deepEqual(placement, { x: 0, y: 0, width: 1920, height: 1040 });
+});
+
+test("docks the mini player in the bottom-right corner of the work area", () => {
+  assert.deepEqual(resolveMiniPlayerBounds(secondary.workArea), { x: 3984, y: 1114, width: 480, height: 270 });
+  assert.deepEqual(resolveMiniPlayerBounds({ x: 0, y: 0, width: 640, height: 480 }), { x: 304, y: 284, width: 320, height: 180 });
+});
@@ -0,0 +1,118 @@
+import type { DisplayInfo, DisplayRect } from "@shared/gfn";
+
+export interface SavedWindowPlacement {
+  width: number;
+  height: number;
+  x: number | null;
+  y: number | null;
+  displayId: number | null;
+}
+
+export interface PlacementDisplay {
+  id: number;
+  bounds: DisplayRect;
+  workArea: DisplayRect;
+  scaleFactor: number;
+  label?: string;
+}
+
+export const DEFAULT_WINDOW_WIDTH = 1400;
+export const DEFAULT_WINDOW_HEIGHT = 900;
+export const MINI_PLAYER_WIDTH = 480;
+export const MINI_PLAYER_MIN_WIDTH = 240;
+const MINI_PLAYER_MARGIN_PX = 16;
+const MIN_VISIBLE_EDGE_PX = 64;
+
+function intersectionArea(a: DisplayRect, b: DisplayRect): number {
+  const width = Math.min(a.x + a.width, b.x + b.width) - Math.max(a.x, b.x);
+  const height = Math.min(a.y + a.height, b.y + b.height) - Math.max(a.y, b.y);
+  return width > 0 && height > 0 ? width * height : 0;
+}
+
+function isReachable(rect: DisplayRect, display: PlacementDisplay): boolean {
+  const visible = intersectionArea(rect, display.workArea);
+  return visible >= Math.min(rect.width, MIN_VISIBLE_EDGE_PX) * Math.min(rect.height, MIN_VISIBLE_EDGE_PX);
+}
+
+function centerIn(area: DisplayRect, width: number, height: number): DisplayRect {
+  const fittedWidth = Math.min(width, area.width);
+  const fittedHeight = Math.min(height, area.height);
+  return {
+    x: Math.round(area.x + (area.width - fittedWidth) / 2),
+    y: Math.round(area.y + (area.height - fittedHeight) / 2),
+    width: fittedWidth,
+    height: fittedHeight,
+  };
+}
+
+export function findDisplayById(
+  displays: readonly PlacementDisplay[],
+  displayId: number | null | undefined,
+): PlacementDisplay | null {
+  if (displayId === null || displayId === undefined) {
+    return null;
+  }
+  return displays.find((display) => display.id === displayId) ?? null;
+}
+
+export function findDisplayForRect(
+  displays: readonly PlacementDisplay[],
+  rect: DisplayRect,
+): PlacementDisplay | null {
+  let best: PlacementDisplay | null = null;
+  let bestArea = 0;
+  for (const display of displays) {
+    const area = intersectionArea(rect, display.bounds);
+    if (area > bestArea) {
+      best = display;
+      bestArea = area;
+    }
+  }
+  return best;
+}
+
+/**
+ * Restores the saved window bounds when they are still visible, otherwise moves
+ * the window onto the remembered monitor (or the primary one) so hot-unplugged
+ * displays never leave it off-screen.
+ */
+export function resolveWindowPlacement(
+  saved: SavedWindowPlacement,
+  displays: readonly PlacementDisplay[],
+  primary: PlacementDisplay,
+): DisplayRect {
+  const width = saved.width > 0 ? saved.width : DEFAULT_WINDOW_WIDTH;
+  const height = saved.height > 0 ? saved.height : DEFAULT_WINDOW_HEIGHT;
+
+  if (saved.x !== null && saved.y !== null) {
+    const rect = { x: saved.x, y: saved.y, width, height };
+    if (displays.some((display) => isReachable(rect, display))) {
+      return rect;
+    }
+  }
+
+  const target = findDisplayById(displays, saved.displayId) ?? primary;
+  return centerIn(target.workArea, width, height);
+}
+
+export function toDisplayInfo(display: PlacementDisplay, primaryId: number, index: number): DisplayInfo {
+  return {
+    id: display.id,
+    label: display.label?.trim() || `Display ${index + 1}`,
+    bounds: { ...display.bounds },
+    scaleFactor: display.scaleFactor,
+    primary: display.id === primaryId,
+  };
+}
+
+/** 16:9 window in the bottom-right corner of the work area, at most half its width. */
+export function resolveMiniPlayerBounds(workArea: DisplayRect, width = MINI_PLAYER_WIDTH): DisplayRect {
+  const fittedWidth = Math.max(MINI_PLAYER_MIN_WIDTH, Math.min(width, Math.floor(workArea.width / 2)));
+  const height = Math.round((fittedWidth * 9) / 16);
+  return {
+    x: workArea.x + workArea.width - fittedWidth - MINI_PLAYER_MARGIN_PX,
+    y: workArea.y + workArea.height - height - MINI_PLAYER_MARGIN_PX,
+    width: fittedWidth,
+    height,
+  };
+}
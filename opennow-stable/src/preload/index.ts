@@ -9,12 +9,14 @@ import type {
   AuthSession,
   AuthSessionRequest,
   DirectLaunchRequest,
+  DriverWorkaroundReport,
   GamesFetchRequest,
   CatalogBrowseRequest,
   ResolveLaunchIdRequest,
   ResolveStoreUrlRequest,
   RegionsFetchRequest,
   MainToRendererSignalingEvent,
+  NetworkChangeEvent,
   OpenNowApi,
   SavedAccount,
   SessionAdReportRequest,
@@ -51,6 +53,10 @@ import type {
   GameAccountOperationRequest,
   GameAccountConnectionsResult,
   GameAccountOperationResult,
+  SessionTelemetryReport,
+  SessionTimeline,
+  StorageCategory,
+  StorageCategoryUsage,
 } from "@shared/gfn";
 import { parseSerializedSessionErrorTransport } from "@shared/sessionError";
 
@@ -76,6 +82,7 @@ const api: OpenNowApi = {
   getLoginProviders: () => ipcRenderer.invoke(IPC_CHANNELS.AUTH_GET_PROVIDERS),
   getRegions: (input: RegionsFetchRequest = {}) => ipcRenderer.invoke(IPC_CHANNELS.AUTH_GET_REGIONS, input),
   login: (input: AuthLoginRequest) => ipcRenderer.invoke(IPC_CHANNELS.AUTH_LOGIN, input),
+  cancelLogin: () => ipcRenderer.invoke(IPC_CHANNELS.AUTH_LOGIN_CANCEL),
   startDeviceLogin: (input: AuthDeviceLoginStartRequest) =>
     ipcRenderer.invoke(IPC_CHANNELS.AUTH_DEVICE_LOGIN_START, input),
   pollDeviceLogin: (input: AuthDeviceLoginPollRequest) =>
@@ -86,6 +93,7 @@ const api: OpenNowApi = {
     ipcRenderer.invoke(IPC_CHANNELS.AUTH_DEVICE_LOGIN_CANCEL, input),
   logout: () => ipcRenderer.invoke(IPC_CHANNELS.AUTH_LOGOUT),
   logoutAll: () => ipcRenderer.invoke(IPC_CHANNELS.AUTH_LOGOUT_ALL),
+  revokeAllSessions: () => ipcRenderer.invoke(IPC_CHANNELS.AUTH_REVOKE_ALL),
   getSavedAccounts: (): Promise<SavedAccount[]> => ipcRenderer.invoke(IPC_CHANNELS.AUTH_GET_SAVED_ACCOUNTS),
   switchAccount: (userId: string): Promise<AuthSession> =>
     ipcRenderer.invoke(IPC_CHANNELS.AUTH_SWITCH_ACCOUNT, userId),
@@ -98,6 +106,8 @@ const api: OpenNowApi = {
     ipcRenderer.invoke(IPC_CHANNELS.PERSISTENT_STORAGE_RESET, input),
   fetchGameAccountConnections: (): Promise<GameAccountConnectionsResult> =>
     ipcRenderer.invoke(IPC_CHANNELS.GAME_ACCOUNTS_FETCH),
+  peekGameAccountConnections: (): Promise<GameAccountConnectionsResult | null> =>
+    ipcRenderer.invoke(IPC_CHANNELS.GAME_ACCOUNTS_PEEK),
   linkGameAccount: (input: GameAccountOperationRequest): Promise<GameAccountOperationResult> =>
     ipcRenderer.invoke(IPC_CHANNELS.GAME_ACCOUNT_LINK, input),
   unlinkGameAccount: (input: GameAccountOperationRequest): Promise<GameAccountOperationResult> =>
@@ -150,6 +160,11 @@ const api: OpenNowApi = {
   updateNativeShortcuts: (shortcuts) => {
     ipcRenderer.send(IPC_CHANNELS.NATIVE_UPDATE_SHORTCUTS, shortcuts);
   },
+  updateNativeVolume: (volume: number) => {
+    ipcRenderer.send(IPC_CHANNELS.NATIVE_UPDATE_VOLUME, volume);
+  },
+  selectNativeVideoLayer: (layerId: string) =>
+    ipcRenderer.invoke(IPC_CHANNELS.NATIVE_SELECT_VIDEO_LAYER, layerId),
   requestKeyframe: (input: KeyframeRequest) =>
     ipcRenderer.invoke(IPC_CHANNELS.REQUEST_KEYFRAME, input),
   onSignalingEvent: (listener: (event: MainToRendererSignalingEvent) => void) => {
@@ -164,9 +179,9 @@ const api: OpenNowApi = {
   },
   onToggleFullscreen: (listener: () => void) => {
     const wrapped = () => listener();
-    ipcRenderer.on("app:toggle-fullscreen", wrapped);
+    ipcRenderer.on(IPC_CHANNELS.FULLSCREEN_TOGGLE_REQUEST, wrapped);
     return () => {
-      ipcRenderer.off("app:toggle-fullscreen", wrapped);
+      ipcRenderer.off(IPC_CHANNELS.FULLSCREEN_TOGGLE_REQUEST, wrapped);
     };
   },
   quitApp: () => ipcRenderer.invoke(IPC_CHANNELS.QUIT_APP),
@@ -186,25 +201,57 @@ const api: OpenNowApi = {
   },
   toggleFullscreen: () => ipcRenderer.invoke(IPC_CHANNELS.TOGGLE_FULLSCREEN),
   setFullscreen: (v: boolean) => ipcRenderer.invoke(IPC_CHANNELS.SET_FULLSCREEN, v),
+  setMiniPlayer: (enabled: boolean) => ipcRenderer.invoke(IPC_CHANNELS.SET_MINI_PLAYER, enabled),
   togglePointerLock: () => ipcRenderer.invoke(IPC_CHANNELS.TOGGLE_POINTER_LOCK),
   getSettings: () => ipcRenderer.invoke(IPC_CHANNELS.SETTINGS_GET),
-  setSetting: <K extends keyof Settings>(key: K, value: Settings[K]) =>
-    ipcRenderer.invoke(IPC_CHANNELS.SETTINGS_SET, key, value),
-  resetSettings: () => ipcRenderer.invoke(IPC_CHANNELS.SETTINGS_RESET),
+  getUserTheme: () => ipcRenderer.invoke(IPC_CHANNELS.USER_THEME_GET),
+  getDriverWorkarounds: () => ipcRenderer.invoke(IPC_CHANNELS.DRIVER_WORKAROUNDS_GET),
+  onDriverWorkaroundsChanged: (listener: (report: DriverWorkaroundReport) => void) => {
+    const wrapped = (_event: Electron.IpcRendererEvent, report: DriverWorkaroundReport) => listener(report);
+    ipcRenderer.on(IPC_CHANNELS.DRIVER_WORKAROUNDS_CHANGED, wrapped);
+    return () => {
+      ipcRenderer.off(IPC_CHANNELS.DRIVER_WORKAROUNDS_CHANGED, wrapped);
+    };
+  },
+  getGamescopeStatus: () => ipcRenderer.invoke(IPC_CHANNELS.GAMESCOPE_STATUS_GET),
+  verifyParentalPin: (pin: string) => ipcRenderer.invoke(IPC_CHANNELS.PARENTAL_VERIFY_PIN, pin),
+  setParentalPin: (currentPin: string | null, nextPin: string | null) =>
+    ipcRenderer.invoke(IPC_CHANNELS.PARENTAL_SET_PIN, currentPin, nextPin),
+  setSetting: <K extends keyof Settings>(key: K, value: Settings[K], pin?: string) =>
+    ipcRenderer.invoke(IPC_CHANNELS.SETTINGS_SET, key, value, pin),
+  resetSettings: (pin?: string) => ipcRenderer.invoke(IPC_CHANNELS.SETTINGS_RESET, pin),
   selectNativeStreamerExecutable: () => ipcRenderer.invoke(IPC_CHANNELS.SETTINGS_SELECT_NATIVE_STREAMER_EXECUTABLE),
+  selectMediaDirectory: () => ipcRenderer.invoke(IPC_CHANNELS.SETTINGS_SELECT_MEDIA_DIRECTORY),
   getNativeStreamerStatus: () => ipcRenderer.invoke(IPC_CHANNELS.NATIVE_STREAMER_STATUS),
   getNativeCloudGsyncCapabilities: () => ipcRenderer.invoke(IPC_CHANNELS.NATIVE_CLOUD_GSYNC_CAPABILITIES),
+  listDisplays: () => ipcRenderer.invoke(IPC_CHANNELS.LIST_DISPLAYS),
   notifyPointerLockChange: (active: boolean) => ipcRenderer.send(IPC_CHANNELS.POINTER_LOCK_CHANGE, active),
+  notifyStreamActiveChange: (active: boolean) => ipcRenderer.send(IPC_CHANNELS.STREAM_ACTIVE_CHANGE, active),
   onExternalEscape: (listener: () => void) => {
     const wrapped = () => listener();
     ipcRenderer.on(IPC_CHANNELS.EXTERNAL_ESCAPE, wrapped);
     return () => ipcRenderer.off(IPC_CHANNELS.EXTERNAL_ESCAPE, wrapped);
   },
+  onNetworkChanged: (listener: (change: NetworkChangeEvent) => void) => {
+    const wrapped = (_event: Electron.IpcRendererEvent, change: NetworkChangeEvent) => listener(change);
+    ipcRenderer.on(IPC_CHANNELS.NETWORK_CHANGED, wrapped);
+    return () => ipcRenderer.off(IPC_CHANNELS.NETWORK_CHANGED, wrapped);
+  },
+  isOnBatteryPower: (): Promise<boolean> => ipcRenderer.invoke(IPC_CHANNELS.POWER_SOURCE_GET),
+  onPowerSourceChanged: (listener: (onBattery: boolean) => void) => {
+    const wrapped = (_event: Electron.IpcRendererEvent, onBattery: boolean) => listener(onBattery);
+    ipcRenderer.on(IPC_CHANNELS.POWER_SOURCE_CHANGED, wrapped);
+    return () => ipcRenderer.off(IPC_CHANNELS.POWER_SOURCE_CHANGED, wrapped);
+  },
   openExternalUrl: (url: string): Promise<void> => ipcRenderer.invoke(IPC_CHANNELS.OPEN_EXTERNAL_URL, url),
   getMicrophonePermission: () => ipcRenderer.invoke(IPC_CHANNELS.MICROPHONE_PERMISSION_GET),
   readClipboardText: (): Promise<string> => ipcRenderer.invoke(IPC_CHANNELS.CLIPBOARD_READ_TEXT),
   exportLogs: (format?: "text" | "json") => ipcRenderer.invoke(IPC_CHANNELS.LOGS_EXPORT, format),
+  getLogTail: (afterSequence: number) => ipcRenderer.invoke(IPC_CHANNELS.LOGS_TAIL, afterSequence),
   pingRegions: (regions: StreamRegion[]) => ipcRenderer.invoke(IPC_CHANNELS.PING_REGIONS, regions),
+  traceRoute: (target: string) => ipcRenderer.invoke(IPC_CHANNELS.ROUTE_TRACE, target),
+  probePathMtu: (target: string) => ipcRenderer.invoke(IPC_CHANNELS.PATH_MTU_PROBE, target),
+  getSystemUsage: () => ipcRenderer.invoke(IPC_CHANNELS.SYSTEM_USAGE),
   saveScreenshot: (input: ScreenshotSaveRequest) => ipcRenderer.invoke(IPC_CHANNELS.SCREENSHOT_SAVE, input),
   listScreenshots: () => ipcRenderer.invoke(IPC_CHANNELS.SCREENSHOT_LIST),
   deleteScreenshot: (input: ScreenshotDeleteRequest) => ipcRenderer.invoke(IPC_CHANNELS.SCREENSHOT_DELETE, input),
@@ -243,12 +290,23 @@ const api: OpenNowApi = {
     ipcRenderer.invoke(IPC_CHANNELS.MEDIA_REGEN_THUMBNAIL, input),
   deleteCache: (): Promise<void> =>
     ipcRenderer.invoke(IPC_CHANNELS.CACHE_DELETE_ALL),
+  getStorageUsage: (): Promise<StorageCategoryUsage[]> => ipcRenderer.invoke(IPC_CHANNELS.STORAGE_USAGE),
+  clearStorage: (category: StorageCategory): Promise<StorageCategoryUsage[]> =>
+    ipcRenderer.invoke(IPC_CHANNELS.STORAGE_CLEAR, category),
+  setBackgroundWorkPaused: (paused: boolean): Promise<void> =>
+    ipcRenderer.invoke(IPC_CHANNELS.BACKGROUND_WORK_SET_PAUSED, paused),
   fetchPrintedWasteQueue: (): Promise<PrintedWasteQueueData> =>
     ipcRenderer.invoke(IPC_CHANNELS.PRINTEDWASTE_QUEUE_FETCH),
   fetchPrintedWasteServerMapping: (): Promise<PrintedWasteServerMapping> =>
     ipcRenderer.invoke(IPC_CHANNELS.PRINTEDWASTE_SERVER_MAPPING_FETCH),
   getThanksData: (): Promise<ThankYouDataResult> => ipcRenderer.invoke(IPC_CHANNELS.COMMUNITY_GET_THANKS),
   clearDiscordActivity: (): Promise<void> => ipcRenderer.invoke(IPC_CHANNELS.DISCORD_CLEAR_ACTIVITY),
+  recordSessionTelemetry: (report: SessionTelemetryReport): Promise<void> =>
+    ipcRenderer.invoke(IPC_CHANNELS.TELEMETRY_RECORD_SESSION, report),
+  recordSessionTimeline: (timeline: SessionTimeline): Promise<void> =>
+    ipcRenderer.invoke(IPC_CHANNELS.SESSION_TIMELINE_RECORD, timeline),
+  getLastSessionTimeline: (): Promise<SessionTimeline | null> =>
+    ipcRenderer.invoke(IPC_CHANNELS.SESSION_TIMELINE_GET),
 };
 
 contextBridge.exposeInMainWorld("openNow", api);
@@ -17,6 +17,7 @@ import type {
   LoginProvider,
   MainToRendererSignalingEvent,
   NativeStreamerShortcutAction,
+  NativeVideoLayer,
   SessionInfo,
   SessionStopRequest,
   SavedAccount,
@@ -26,19 +27,39 @@ import type {
   StreamSettings,
   StreamRegion,
   PrintedWasteQueueData,
+  RouteTraceResult,
 } from "@shared/gfn";
 import {
   buildNativeStreamerSessionContext,
   DEFAULT_KEYBOARD_LAYOUT,
+  DEFAULT_STATS_PANEL_FIELDS,
   getDefaultStreamPreferences,
   isGameInLibrary,
+  isOwnedVariant,
   isSessionAdsRequired,
   resolveEntitledStreamProfile,
   SAFE_FALLBACK_STREAM_PROFILE,
 } from "@shared/gfn";
+import { applyGameStreamOverride } from "@shared/gameStreamOverrides";
+import { applyLowLatencyPreset } from "@shared/lowLatencyPreset";
+import { parseCustomIceServers, parseUdpPortRange } from "@shared/iceConfig";
+import { getLogCapture, parseLogFilter } from "@shared/logger";
+import { isGameAllowedForAge, isParentalProtectedSetting } from "@shared/parentalControls";
 import { GfnWebRtcClient } from "./gfn/webrtcClient";
+import type { OnScreenKeyboardCode } from "./gfn/onScreenKeyboard";
+import { requestPointerLockMovement } from "./gfn/mouseFeel";
+import { normalizeWhipEndpoint, WhipPublisher } from "./gfn/whipPublisher";
+import { SessionTelemetryTracker } from "./lib/sessionTelemetry";
+import { SessionTimelineRecorder } from "./lib/sessionTimeline";
+import {
+  loadServerQualityHistory,
+  recordServerQuality,
+  saveServerQualityHistory,
+  ServerQualityTracker,
+} from "./lib/serverQuality";
 import { formatShortcutForDisplay, isShortcutMatch, normalizeShortcut } from "./shortcuts";
-import { dispatchStreamShortcutAction } from "./streamShortcutActions";
+import { appEvents, decodeStateChanged, decodeStateFromStats, type DecodeStateEvent } from "./lib/appEvents";
+import { selectPowerProfile, watchPowerState, type PowerProfile, type PowerState } from "./lib/powerProfile";
 import { useElapsedSeconds } from "./utils/useElapsedSeconds";
 import { useQueueAdRuntime } from "./hooks/useQueueAdRuntime";
 import { usePlaytime } from "./utils/usePlaytime";
@@ -46,6 +67,7 @@ import { createStreamDiagnosticsStore } from "./utils/streamDiagnosticsStore";
 import type {
   LaunchErrorState,
   LocalSessionTimerWarningState,
+  PlayTimeReminderState,
   StreamLoadingStatus,
   StreamStatus,
   StreamWarningState,
@@ -60,6 +82,7 @@ import {
 import { loadStoredCodecResults, saveStoredCodecResults, testCodecSupport, type CodecTestResult } from "./lib/codecDiagnostics";
 import {
   areStringArraysEqual,
+  collectGameGenres,
   defaultVariantId,
   findSessionContextForAppId,
   getSelectedVariant,
@@ -69,7 +92,21 @@ import {
   parseNumericId,
   sortLibraryGames,
 } from "./lib/gameCatalog";
-import { chooseAccountLinked, getEpicOwnershipLaunchError } from "./lib/launchOwnership";
+import { getIdleDisconnectState, latestGamepadTimestamp } from "./lib/idleDisconnect";
+import {
+  BackgroundTaskScheduler,
+  artworkPrefetchUrls,
+  estimateScreenfulGameCount,
+  prefetchArtwork,
+} from "./lib/backgroundTasks";
+import { getPlayTimeDeadline, hasPlayTimeLimit, nextPlayTimeReminder } from "./lib/playTimeLimits";
+import {
+  clampStreamVolume,
+  getEffectiveStreamVolume,
+  NOTIFICATION_DUCK_MS,
+  stepStreamVolume,
+} from "./lib/streamVolume";
+import { chooseAccountLinked, getAccountLinkRequirement, getEpicOwnershipLaunchError } from "./lib/launchOwnership";
 import { hasAnyEligiblePrintedWasteZone, isAllianceStreamingBaseUrl } from "./lib/printedWaste";
 import {
   mergePolledSessionState,
@@ -80,6 +117,7 @@ import { clearRuntimeSnapshot, loadRuntimeSnapshot, saveRuntimeSnapshot, type Ru
 import {
   getSessionLimitSecondsForTier,
   getLocalSessionTimerWarning,
+  getPlayTimeWarning,
   hasCrossedWarningThreshold,
   shouldShowFreeTierSessionWarnings,
   warningMessage,
@@ -88,12 +126,13 @@ import {
 import {
   isSessionInQueue,
   isSessionReadyForConnect,
+  orderActiveSessions,
   streamStatusToLoadingStage,
   toLaunchErrorState,
   toLoadingStatus,
 } from "./lib/sessionState";
 import { defaultDiagnostics, mergeNativeStreamStats } from "./lib/streamDiagnostics";
-import { applyAccentColor } from "./lib/uiCustomization";
+import { applyAccentColor, applyAppTheme, resolveAppTheme } from "./lib/uiCustomization";
 import { useTranslation } from "./i18n";
 
 // UI Components
@@ -102,9 +141,12 @@ import { Navbar } from "./components/Navbar";
 import { HomePage } from "./components/HomePage";
 import { LibraryPage } from "./components/LibraryPage";
 import { SettingsPage } from "./components/SettingsPage";
+import { ParentalPinPrompt, type ParentalPinPurpose } from "./components/ParentalPinPrompt";
 import { SettingsModalHost } from "./components/SettingsModalHost";
 import { StreamLoading } from "./components/StreamLoading";
 import { StreamView } from "./components/StreamView";
+import type { StatsPanelLayout } from "./components/StreamStatsHud";
+import type { RouteTraceState } from "./components/RouteTracePanel";
 import { QueueServerSelectModal } from "./components/QueueServerSelectModal";
 import { pageTransition } from "./components/MotionProvider";
 
@@ -249,10 +291,14 @@ function isNvidiaProvider(provider: LoginProvider | null | undefined): boolean {
 const SESSION_READY_POLL_INTERVAL_MS = 2000;
 const SESSION_AD_POLL_INTERVAL_MS = 30000;
 const PLAYTIME_RESYNC_INTERVAL_MS = 5 * 60 * 1000;
+const SESSION_TIMELINE_SEND_INTERVAL_MS = 5000;
 const FREE_TIER_30_MIN_WARNING_SECONDS = 30 * 60;
 const FREE_TIER_15_MIN_WARNING_SECONDS = 15 * 60;
+const FREE_TIER_5_MIN_WARNING_SECONDS = 5 * 60;
 const FREE_TIER_FINAL_MINUTE_WARNING_SECONDS = 60;
 const STREAM_WARNING_VISIBILITY_MS = 15 * 1000;
+/** Quiet period after the last volume change before it is written to settings. */
+const STREAM_VOLUME_PERSIST_DELAY_MS = 400;
 
 type AppPage = "home" | "library" | "settings";
 type ExitPromptState = { open: boolean; gameTitle: string };
@@ -269,6 +315,10 @@ const SIGNALING_RECOVERY_ATTEMPT_DELAYS_MS = [0, 3000] as const;
 const SIGNALING_RECOVERY_STABLE_RESET_DELAY_MS = 15000;
 const SIGNALING_REMOTE_ICE_GRACE_MS = 5000;
 const ICE_DISCONNECTED_RECOVERY_GRACE_MS = 7000;
+// After a local interface change, an ICE disconnect is almost certainly the old
+// path going away, so resume immediately instead of waiting out the grace period.
+const NETWORK_CHANGE_RECOVERY_WINDOW_MS = 15000;
+const NETWORK_CHANGE_NOTICE_MS = 4000;
 
 const isMac = navigator.platform.toLowerCase().includes("mac");
 
@@ -315,6 +365,10 @@ const DEFAULT_SHORTCUTS = {
   shortcutToggleMicrophone: "Ctrl+Shift+M",
   shortcutScreenshot: "F11",
   shortcutToggleRecording: "F12",
+  shortcutToggleStreamMute: "Ctrl+Shift+End",
+  shortcutVolumeUp: "Ctrl+Shift+PageUp",
+  shortcutVolumeDown: "Ctrl+Shift+PageDown",
+  shortcutToggleTextInput: "Ctrl+Shift+T",
 } as const;
 
 
@@ -348,11 +402,14 @@ export function App(): JSX.Element {
   const [providerIdpId, setProviderIdpId] = useState("");
   const [isLoggingIn, setIsLoggingIn] = useState(false);
   const [activeLoginMode, setActiveLoginMode] = useState<"oauth" | "qr" | null>(null);
+  // Guests browse the public catalog and queue times; anything that needs a token sends them to sign in.
+  const [guestMode, setGuestMode] = useState(false);
+  const [loginPrompt, setLoginPrompt] = useState<string | null>(null);
   const [loginError, setLoginError] = useState<string | null>(null);
   const [qrLoginChallenge, setQrLoginChallenge] = useState<AuthDeviceLoginChallenge | null>(null);
   const [isInitializing, setIsInitializing] = useState(true);
   const [startupStatusMessage, setStartupStatusMessage] = useState(() => t("auth.status.restoringSavedSession"));
-  const [startupRefreshNotice, setStartupRefreshNotice] = useState<{
+  const [appNotice, setAppNotice] = useState<{
     tone: "success" | "warn";
     text: string;
   } | null>(null);
@@ -362,6 +419,7 @@ export function App(): JSX.Element {
   const [pageBeforeSettings, setPageBeforeSettings] = useState<AppPage>("home");
   const [settingsMounted, setSettingsMounted] = useState(false);
   const [sessionFullscreen, setSessionFullscreenState] = useState(false);
+  const [miniPlayer, setMiniPlayer] = useState(false);
 
   // Games State
   const [games, setGames] = useState<GameInfo[]>([]);
@@ -388,12 +446,16 @@ export function App(): JSX.Element {
     aspectRatio: "16:9",
     posterSizeScale: 1,
     fps: 60,
+    renderFpsCap: 0,
+    renderFpsCapOnBattery: 0,
+    renderFpsCapRequestServer: false,
     maxBitrateMbps: 75,
     recordingBitrateMbps: null,
     streamClientMode: "web",
     nativeStreamerBackend: "gstreamer",
     nativeVideoBackend: "auto",
     nativeStreamerExecutablePath: "",
+    nativeExperimentalFlags: "",
     nativeCloudGsyncMode: "auto",
     nativeD3dFullscreenMode: "auto",
     nativeExternalRenderer: true,
@@ -401,14 +463,44 @@ export function App(): JSX.Element {
     codec: DEFAULT_STREAM_PREFERENCES.codec,
     decoderPreference: "auto",
     encoderPreference: "auto",
+    driverWorkaroundsEnabled: true,
+    disabledDriverWorkarounds: [],
     colorQuality: DEFAULT_STREAM_PREFERENCES.colorQuality,
+    gameStreamOverrides: {},
+    batteryProfileEnabled: false,
+    batteryProfileBelowPercent: 100,
+    batteryStreamProfile: { resolution: "1280x720", fps: 30, hdr: false },
     region: "",
     sessionProxyEnabled: false,
     sessionProxyUrl: "",
+    signalingProxyEnabled: false,
+    iceTransportPolicy: "all",
+    customIceServerUrls: "",
+    customIceUsername: "",
+    customIceCredential: "",
+    webrtcUdpPortRange: "",
+    networkIpFamily: "auto",
+    pathMtuProbe: false,
+    networkQosMarking: false,
+    jitterBufferMs: 2,
+    logFilter: "",
+    diskCacheLimitMb: 0,
+    mediaDirectory: "",
+    mediaThreadPriority: false,
+    lowLatencyMode: false,
+    restreamEnabled: false,
+    restreamWhipUrl: "",
+    restreamMaxHeight: 720,
     clipboardPaste: false,
+    microphoneBlocked: false,
     enableGyroscopeControls: false,
+    controllerSlotsEnabled: [true, true, true, true],
+    gamescopeCompatibility: "auto",
     mouseSensitivity: 1,
     mouseAcceleration: 1,
+    mouseRawInput: true,
+    gamepadMouseSpeed: 1200,
+    gamepadMouseAcceleration: 50,
     shortcutToggleStats: DEFAULT_SHORTCUTS.shortcutToggleStats,
     shortcutTogglePointerLock: DEFAULT_SHORTCUTS.shortcutTogglePointerLock,
     shortcutToggleFullscreen: DEFAULT_SHORTCUTS.shortcutToggleFullscreen,
@@ -417,30 +509,66 @@ export function App(): JSX.Element {
     shortcutToggleMicrophone: DEFAULT_SHORTCUTS.shortcutToggleMicrophone,
     shortcutScreenshot: DEFAULT_SHORTCUTS.shortcutScreenshot,
     shortcutToggleRecording: DEFAULT_SHORTCUTS.shortcutToggleRecording,
+    shortcutToggleStreamMute: DEFAULT_SHORTCUTS.shortcutToggleStreamMute,
+    shortcutVolumeUp: DEFAULT_SHORTCUTS.shortcutVolumeUp,
+    shortcutVolumeDown: DEFAULT_SHORTCUTS.shortcutVolumeDown,
+    shortcutToggleTextInput: DEFAULT_SHORTCUTS.shortcutToggleTextInput,
     microphoneMode: "disabled",
     microphoneDeviceId: "",
+    audioOutputMode: "shared",
+    audioBufferMs: 0,
+    streamVolume: 100,
+    streamMuted: false,
+    duckOnNotifications: false,
     hideStreamButtons: false,
     showAntiAfkIndicator: true,
     showStatsOnLaunch: false,
     hideServerSelector: false,
+    queueServiceUrl: "",
+    queueServicePublicKey: "",
     appAccentColor: "green",
+    appTheme: "dark",
     controllerMode: false,
     autoFullScreen: false,
+    fullscreenMode: "borderless",
+    altEnterFullscreen: true,
     favoriteGameIds: [],
     sessionCounterEnabled: false,
     showSessionTimeRemainingInStatsOverlay: false,
+    statsPanelFields: [...DEFAULT_STATS_PANEL_FIELDS],
+    statsPanelScale: 100,
+    statsPanelOpacity: 100,
+    idleDisconnectMinutes: 0,
+    playReminderMinutes: 0,
+    librarySyncMinutes: 30,
+    playCurfew: "",
+    playLimitDisconnect: false,
+    endSessionOnExit: false,
     sessionClockShowEveryMinutes: 60,
     sessionClockShowDurationSeconds: 30,
     windowWidth: 1400,
     windowHeight: 900,
+    windowX: null,
+    windowY: null,
+    windowDisplayId: null,
+    fullscreenDisplayId: null,
     keyboardLayout: DEFAULT_KEYBOARD_LAYOUT,
     gameLanguage: "en_US",
     enableL4S: false,
     enableCloudGsync: false,
     discordRichPresence: false,
+    telemetryMode: "off",
+    telemetryEndpoint: "",
+    parentalPinSet: false,
+    parentalLockLaunch: true,
+    parentalLockSettings: true,
+    parentalMaxAge: null,
     autoCheckForUpdates: true,
+    updateChannel: "stable",
   });
   const [settingsLoaded, setSettingsLoaded] = useState(false);
+  // What sessions actually use: the stored settings with the low-latency preset on top.
+  const effectiveSettings = useMemo(() => applyLowLatencyPreset(settings), [settings]);
   const activeSessionProxyUrl = useMemo(
     () => getEnabledSessionProxyUrl(settings),
     [settings.sessionProxyEnabled, settings.sessionProxyUrl],
@@ -459,17 +587,33 @@ export function App(): JSX.Element {
   const [showStatsOverlay, setShowStatsOverlay] = useState(false);
   const [antiAfkEnabled, setAntiAfkEnabled] = useState(false);
   const [antiAfkAckNonce, setAntiAfkAckNonce] = useState(0);
+  const [textInputActive, setTextInputActive] = useState(false);
+  const [onScreenKeyboardActive, setOnScreenKeyboardActive] = useState(false);
+  const [gamepadMouseActive, setGamepadMouseActive] = useState(false);
+  const [clipboardSentAtMs, setClipboardSentAtMs] = useState<number | null>(null);
+  const [latencyProbeActive, setLatencyProbeActive] = useState(false);
   const [exitPrompt, setExitPrompt] = useState<ExitPromptState>({ open: false, gameTitle: t("app.labels.game") });
   const [streamingGame, setStreamingGame] = useState<GameInfo | null>(null);
   const [streamingStore, setStreamingStore] = useState<string | null>(null);
   const [queuePosition, setQueuePosition] = useState<number | undefined>();
   const [navbarActiveSession, setNavbarActiveSession] = useState<ActiveSessionInfo | null>(null);
+  // Every queued or ready session on the account; navbarActiveSession is the one picked from it.
+  const [navbarActiveSessions, setNavbarActiveSessions] = useState<ActiveSessionInfo[]>([]);
   const [isResumingNavbarSession, setIsResumingNavbarSession] = useState(false);
   const [isTerminatingNavbarSession, setIsTerminatingNavbarSession] = useState(false);
   const [accountToRemove, setAccountToRemove] = useState<string | null>(null);
   const [removeAccountConfirmOpen, setRemoveAccountConfirmOpen] = useState(false);
   const [logoutConfirmOpen, setLogoutConfirmOpen] = useState(false);
+  const [logoutRevokesTokens, setLogoutRevokesTokens] = useState(false);
+  const [parentalPrompt, setParentalPrompt] = useState<{
+    purpose: ParentalPinPurpose;
+    resolve: (pin: string | null) => void;
+  } | null>(null);
+  // PIN entered to open settings, sent along with protected changes until settings close.
+  const parentalPinRef = useRef<string | null>(null);
   const [launchError, setLaunchError] = useState<LaunchErrorState | null>(null);
+  const [accountLinkBusy, setAccountLinkBusy] = useState(false);
+  const [sessionRequeue, setSessionRequeue] = useState<{ game: GameInfo; variantId?: string } | null>(null);
   const [pendingDirectLaunchRequest, setPendingDirectLaunchRequest] = useState<DirectLaunchRequest | null>(null);
   const [queueModalGame, setQueueModalGame] = useState<GameInfo | null>(null);
   const [queueModalData, setQueueModalData] = useState<PrintedWasteQueueData | null>(null);
@@ -477,6 +621,8 @@ export function App(): JSX.Element {
   const [remoteStreamWarning, setRemoteStreamWarning] = useState<StreamWarningState | null>(null);
   const [localSessionTimerWarning, setLocalSessionTimerWarning] = useState<LocalSessionTimerWarningState | null>(null);
   const previousFreeTierRemainingSecondsRef = useRef<number | null>(null);
+  const [idleDisconnectSecondsLeft, setIdleDisconnectSecondsLeft] = useState<number | null>(null);
+  const [playTimeReminder, setPlayTimeReminder] = useState<PlayTimeReminderState | null>(null);
 
   const { playtime, startSession: startPlaytimeSession, endSession: endPlaytimeSession } = usePlaytime();
   const sessionElapsedSeconds = useElapsedSeconds(sessionStartedAtMs, streamStatus === "streaming");
@@ -502,16 +648,117 @@ export function App(): JSX.Element {
 
     return getLocalSessionTimerWarning(t, localSessionTimerWarning.stage, freeTierSessionRemainingSeconds);
   }, [freeTierSessionRemainingSeconds, localSessionTimerWarning, locale, t]);
+  const idleDisconnectWarning = useMemo<StreamWarningState | null>(() => {
+    if (idleDisconnectSecondsLeft === null) {
+      return null;
+    }
+    return {
+      code: 2,
+      message: t("session.warnings.idleDisconnectSoon"),
+      tone: "critical",
+      secondsLeft: idleDisconnectSecondsLeft,
+    };
+  }, [idleDisconnectSecondsLeft, locale, t]);
+  // Fixed for the whole run: Steam decides which pads it manages before launching us.
+  const steamInputDevicesRef = useRef<readonly string[]>([]);
+  useEffect(() => {
+    window.openNow.getGamescopeStatus()
+      .then((status) => {
+        steamInputDevicesRef.current = status.steamInputDevices;
+      })
+      .catch((error) => console.warn("[Input] Failed to read gamescope status:", error));
+  }, []);
+  const [routeTraceResult, setRouteTraceResult] = useState<RouteTraceResult | null>(null);
+  const [routeTraceRunning, setRouteTraceRunning] = useState(false);
+  // The media server is the far end of the game stream; the signaling host is only a fallback.
+  const routeTraceTarget = session?.mediaConnectionInfo?.ip ?? session?.serverIp ?? null;
+  useEffect(() => {
+    setRouteTraceResult(null);
+  }, [routeTraceTarget]);
+  // Filled in whenever the probe finishes; an offer never waits for it, so a
+  // result only shapes the negotiations that come after it.
+  const pathMtuRef = useRef<number | null>(null);
+  useEffect(() => {
+    pathMtuRef.current = null;
+    if (!routeTraceTarget || !settings.pathMtuProbe) {
+      return;
+    }
+    let cancelled = false;
+    void window.openNow.probePathMtu(routeTraceTarget)
+      .then((result) => {
+        if (result.mtu === null) {
+          console.warn(`[Network] Path MTU to ${result.target} unknown: ${result.error ?? "no reply"}`);
+        }
+        if (!cancelled) {
+          pathMtuRef.current = result.mtu;
+        }
+      })
+      .catch((error) => {
+        console.warn("[Network] Path MTU probe failed:", error);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [routeTraceTarget, settings.pathMtuProbe]);
+  const handleRunRouteTrace = useCallback(() => {
+    if (!routeTraceTarget) {
+      return;
+    }
+    setRouteTraceRunning(true);
+    window.openNow.traceRoute(routeTraceTarget)
+      .then(setRouteTraceResult)
+      .catch((error) => {
+        console.warn("[Diagnostics] Route trace failed:", error);
+      })
+      .finally(() => setRouteTraceRunning(false));
+  }, [routeTraceTarget]);
+  const routeTrace = useMemo<RouteTraceState | null>(
+    () => (routeTraceTarget
+      ? { result: routeTraceResult, running: routeTraceRunning, onRun: handleRunRouteTrace }
+      : null),
+    [handleRunRouteTrace, routeTraceResult, routeTraceRunning, routeTraceTarget],
+  );
+  const statsPanelLayout = useMemo<StatsPanelLayout>(() => ({
+    fields: settings.statsPanelFields,
+    scalePercent: settings.statsPanelScale,
+    opacityPercent: settings.statsPanelOpacity,
+  }), [settings.statsPanelFields, settings.statsPanelOpacity, settings.statsPanelScale]);
+  const playTimeWarning = useMemo(
+    () => (playTimeReminder ? getPlayTimeWarning(t, playTimeReminder) : null),
+    [locale, playTimeReminder, t],
+  );
   const streamWarning = useMemo(() => {
+    if (idleDisconnectWarning) {
+      return idleDisconnectWarning;
+    }
+    if (playTimeWarning?.tone === "critical") {
+      return playTimeWarning;
+    }
     if (visibleLocalSessionTimerWarning?.tone === "critical") {
       return visibleLocalSessionTimerWarning;
     }
-    return remoteStreamWarning ?? visibleLocalSessionTimerWarning;
-  }, [remoteStreamWarning, visibleLocalSessionTimerWarning]);
+    return remoteStreamWarning ?? playTimeWarning ?? visibleLocalSessionTimerWarning;
+  }, [idleDisconnectWarning, playTimeWarning, remoteStreamWarning, visibleLocalSessionTimerWarning]);
+  const [notificationDucked, setNotificationDucked] = useState(false);
+  // Countdown notices re-render every second; only a new notice should duck.
+  const streamNoticeKey = streamWarning ? `${streamWarning.code}:${streamWarning.tone}` : null;
+
+  useEffect(() => {
+    if (!streamNoticeKey || !settings.duckOnNotifications) {
+      return;
+    }
+    setNotificationDucked(true);
+    const timer = window.setTimeout(() => setNotificationDucked(false), NOTIFICATION_DUCK_MS);
+    return () => {
+      window.clearTimeout(timer);
+      setNotificationDucked(false);
+    };
+  }, [settings.duckOnNotifications, streamNoticeKey]);
 
   const codecTestPromiseRef = useRef<Promise<CodecTestResult[] | null> | null>(null);
   const codecStartupTestAttemptedRef = useRef(false);
   const navbarSessionActionInFlightRef = useRef<"resume" | "terminate" | null>(null);
+  const selectedNavbarSessionIdRef = useRef<string | null>(null);
   const nativeStreamingRef = useRef(false);
   const handleStreamShortcutActionRef = useRef<((action: NativeStreamerShortcutAction) => void) | null>(null);
   const streamingGameRef = useRef<GameInfo | null>(null);
@@ -550,29 +797,165 @@ export function App(): JSX.Element {
     await testPromise;
   }, []);
 
-  const [streamVolume, setStreamVolume] = useState(1);
+  const [nativeVideoLayers, setNativeVideoLayers] = useState<{
+    layers: NativeVideoLayer[];
+    active: string;
+    pausedAtSender: boolean;
+  } | null>(null);
+  const activeNativeVideoLayer = nativeVideoLayers?.active;
+  const handleNativeVideoLayerChange = useCallback((layerId: string): void => {
+    const previous = activeNativeVideoLayer;
+    setNativeVideoLayers((current) => (current ? { ...current, active: layerId } : current));
+    void window.openNow.selectNativeVideoLayer(layerId).catch((error: unknown) => {
+      console.warn("[App] Failed to switch native video layer:", error);
+      // Put the chip back unless the user has already picked another layer.
+      setNativeVideoLayers((current) =>
+        current && previous && current.active === layerId ? { ...current, active: previous } : current,
+      );
+    });
+  }, [activeNativeVideoLayer]);
+
+  const streamOutputVolume = getEffectiveStreamVolume(settings.streamVolume, settings.streamMuted, notificationDucked);
   const [streamMicLevel, setStreamMicLevel] = useState(1);
   // Refs
   const videoRef = useRef<HTMLVideoElement | null>(null);
   const audioRef = useRef<HTMLAudioElement | null>(null);
   const clientRef = useRef<GfnWebRtcClient | null>(null);
+  const lastDecodeStateRef = useRef<DecodeStateEvent | null>(null);
   const isStreamingRef = useRef(streamStatus === "streaming");
 
   useEffect(() => {
     isStreamingRef.current = streamStatus === "streaming";
+    if (streamStatus !== "streaming") {
+      setTextInputActive(false);
+      setOnScreenKeyboardActive(false);
+      setGamepadMouseActive(false);
+      setLatencyProbeActive(false);
+    }
   }, [streamStatus]);
 
   useEffect(() => {
-    if (streamStatus === "streaming" && audioRef.current) {
-      setStreamVolume(audioRef.current.volume);
+    clientRef.current?.setTextInputMode(textInputActive);
+  }, [textInputActive]);
+
+  useEffect(() => {
+    clientRef.current?.setLatencyProbeEnabled(latencyProbeActive);
+  }, [latencyProbeActive]);
+
+  const sessionTelemetryRef = useRef(new SessionTelemetryTracker());
+  const serverQualityRef = useRef(new ServerQualityTracker());
+  // Zone routing URL the current session was created on; claimed sessions fall back to their streaming base URL.
+  const launchRoutingUrlRef = useRef<string | null>(null);
+  const sessionTimelineRef = useRef(new SessionTimelineRecorder());
+  // The effect below re-runs on unrelated changes; each launch error is recorded once.
+  const notedLaunchErrorRef = useRef<LaunchErrorState | null>(null);
+
+  useEffect(() => {
+    const tracker = sessionTelemetryRef.current;
+    const serverQuality = serverQualityRef.current;
+    const timeline = sessionTimelineRef.current;
+    return diagnosticsStore.subscribe(() => {
+      const snapshot = diagnosticsStore.getSnapshot();
+      tracker.observe(snapshot);
+      serverQuality.observe(snapshot);
+      timeline.observe(snapshot, Date.now());
+    });
+  }, [diagnosticsStore]);
+
+  // Main keeps the latest timeline so a log export mid-stream or after it includes it.
+  // Events can arrive on every stats poll, so a running timeline is sent at most
+  // every few seconds; the finished one goes out right away.
+  useEffect(() => {
+    const timeline = sessionTimelineRef.current;
+    let timer: number | null = null;
+    let lastSentAtMs = 0;
+    const send = (): void => {
+      timer = null;
+      const snapshot = timeline.getSnapshot();
+      if (!snapshot) {
+        return;
+      }
+      lastSentAtMs = Date.now();
+      void window.openNow.recordSessionTimeline(snapshot).catch((error) => {
+        console.warn("[SessionTimeline] Failed to record timeline:", error);
+      });
+    };
+    const unsubscribe = timeline.subscribe(() => {
+      if (timeline.getSnapshot()?.endedAtMs !== null) {
+        if (timer !== null) window.clearTimeout(timer);
+        send();
+        return;
+      }
+      if (timer === null) {
+        timer = window.setTimeout(send, Math.max(0, lastSentAtMs + SESSION_TIMELINE_SEND_INTERVAL_MS - Date.now()));
+      }
+    });
+    return () => {
+      unsubscribe();
+      if (timer !== null) window.clearTimeout(timer);
+    };
+  }, []);
+
+  const sessionStreamingBaseUrl = session?.streamingBaseUrl;
+  useEffect(() => {
+    const tracker = sessionTelemetryRef.current;
+    const serverQuality = serverQualityRef.current;
+    const timeline = sessionTimelineRef.current;
+    if (streamStatus === "streaming") {
+      tracker.start(nativeStreamingRef.current ? "native" : "web", Date.now());
+      timeline.start(Date.now());
+      serverQuality.start(launchRoutingUrlRef.current ?? sessionStreamingBaseUrl, settings.fps, Date.now());
+      return;
     }
-  }, [streamStatus]);
+    if (launchError && launchError !== notedLaunchErrorRef.current) {
+      notedLaunchErrorRef.current = launchError;
+      if (launchError.codeLabel) {
+        tracker.noteError(launchError.codeLabel, nativeStreamingRef.current ? "native" : "web", Date.now());
+        timeline.note("session", "launchError", { code: launchError.codeLabel }, Date.now());
+      }
+    }
+    if (streamStatus === "idle") {
+      timeline.finish(Date.now());
+      const report = tracker.finish(Date.now());
+      if (report && settings.telemetryMode !== "off") {
+        void window.openNow.recordSessionTelemetry(report).catch((error) => {
+          console.warn("[Telemetry] Failed to record session:", error);
+        });
+      }
+      // Kept locally regardless of the telemetry mode; it only steers Auto server selection.
+      launchRoutingUrlRef.current = null;
+      const serverSession = serverQuality.finish(Date.now());
+      if (serverSession) {
+        saveServerQualityHistory(
+          recordServerQuality(loadServerQualityHistory(), serverSession.key, serverSession.sample, Date.now()),
+        );
+      }
+    }
+  }, [launchError, sessionStreamingBaseUrl, settings.fps, settings.telemetryMode, streamStatus]);
+
+  const handleSendText = useCallback((text: string): void => {
+    clientRef.current?.sendText(text);
+  }, []);
+
+  const handleCloseTextInput = useCallback((): void => {
+    setTextInputActive(false);
+  }, []);
+
+  const handleSendKey = useCallback((code: OnScreenKeyboardCode): void => {
+    clientRef.current?.sendKeyTap(code);
+  }, []);
+
+  const handleCloseOnScreenKeyboard = useCallback((): void => {
+    setOnScreenKeyboardActive(false);
+  }, []);
+
   useEffect(() => {
     if (audioRef.current) {
-      audioRef.current.volume = streamVolume;
+      audioRef.current.volume = streamOutputVolume;
     }
-    clientRef.current?.setOutputVolume(streamVolume);
-  }, [streamVolume]);
+    clientRef.current?.setOutputVolume(streamOutputVolume);
+    window.openNow.updateNativeVolume(streamOutputVolume);
+  }, [streamOutputVolume]);
   const sessionRef = useRef<SessionInfo | null>(null);
   const hasInitializedRef = useRef(false);
   const regionsRequestRef = useRef(0);
@@ -594,6 +977,8 @@ export function App(): JSX.Element {
   const hasConfirmedRemoteIceRef = useRef(false);
   const latestIceConnectionStateRef = useRef<RTCIceConnectionState>("new");
   const iceDisconnectedRecoveryTimerRef = useRef<number | null>(null);
+  const lastNetworkChangeAtRef = useRef<number | null>(null);
+  const [networkChangeNoticeVisible, setNetworkChangeNoticeVisible] = useState(false);
   const pendingControlledDisconnectsRef = useRef(0);
   const storePanelsLoadedContextRef = useRef("");
   const storePanelsLoadIdRef = useRef(0);
@@ -670,6 +1055,7 @@ export function App(): JSX.Element {
     setLocalSessionTimerWarning(null);
     resetStatsOverlayToPreference();
     nativeStreamingRef.current = false;
+    setNativeVideoLayers(null);
     diagnosticsStore.set(defaultDiagnostics());
 
     if (!options?.keepStreamingContext) {
@@ -689,15 +1075,45 @@ export function App(): JSX.Element {
     clearRuntimeSnapshot();
   }, [diagnosticsStore, resetStatsOverlayToPreference, settings.discordRichPresence]);
 
-  const buildCurrentStreamSettings = useCallback((subscriptionOverride?: SubscriptionInfo | null): StreamSettings => {
+  // Battery profile: picks the stream settings for the next session and says so when it flips.
+  const [powerState, setPowerState] = useState<PowerState | null>(null);
+  useEffect(() => watchPowerState(setPowerState), []);
+  const powerProfile: PowerProfile = powerState ? selectPowerProfile(settings, powerState) : "performance";
+  const lastPowerProfileRef = useRef<PowerProfile | null>(null);
+  const lastPowerStateRef = useRef<PowerState | null>(null);
+  useEffect(() => {
+    if (!powerState) return;
+    const previous = lastPowerProfileRef.current;
+    lastPowerProfileRef.current = powerProfile;
+    // Editing the battery profile settings can flip the profile too; only the power source or charge level announces it.
+    const powerStateChanged = lastPowerStateRef.current !== powerState;
+    lastPowerStateRef.current = powerState;
+    if (previous === null || previous === powerProfile || !powerStateChanged) return;
+    setAppNotice(powerProfile === "battery"
+      ? {
+        tone: "warn",
+        text: powerState.batteryPercent === null
+          ? t("app.powerProfile.switchedToBatteryNoLevel")
+          : t("app.powerProfile.switchedToBattery", { percent: powerState.batteryPercent }),
+      }
+      : { tone: "success", text: t("app.powerProfile.switchedToPerformance") });
+  }, [powerProfile, powerState, t]);
+
+  // Settings the current session was created or claimed with; the offer is munged to match them.
+  const sessionStreamSettingsRef = useRef<StreamSettings | null>(null);
+
+  /** Global settings, then the battery profile, then the game's own overrides, clamped to the plan's entitlements. */
+  const buildCurrentStreamSettings = useCallback((subscriptionOverride?: SubscriptionInfo | null, gameId?: string): StreamSettings => {
     const currentSubscription = subscriptionOverride === undefined ? subscriptionInfo : subscriptionOverride;
+    const batteryProfile = powerProfile === "battery" ? settings.batteryStreamProfile : undefined;
+    const gameOverride = gameId ? settings.gameStreamOverrides[gameId] : undefined;
     const entitledProfile = resolveEntitledStreamProfile(currentSubscription?.entitledResolutions ?? [], {
-      resolution: settings.resolution,
-      fps: settings.fps,
+      resolution: gameOverride?.resolution ?? batteryProfile?.resolution ?? settings.resolution,
+      fps: gameOverride?.fps ?? batteryProfile?.fps ?? settings.fps,
     });
     const streamProfile = entitledProfile ?? SAFE_FALLBACK_STREAM_PROFILE;
 
-    return {
+    const streamSettings: StreamSettings = {
       resolution: streamProfile.resolution,
       fps: streamProfile.fps,
       maxBitrateMbps: settings.maxBitrateMbps,
@@ -706,25 +1122,54 @@ export function App(): JSX.Element {
       keyboardLayout: settings.keyboardLayout,
       gameLanguage: settings.gameLanguage,
       enableL4S: settings.enableL4S,
-      enableCloudGsync: settings.enableCloudGsync,
+      enableCloudGsync: effectiveSettings.enableCloudGsync,
       clientMode: settings.streamClientMode,
       nativeStreamerBackend: "gstreamer",
       nativeCloudGsyncMode: settings.nativeCloudGsyncMode,
       nativeTransitionDiagnostics: settings.nativeTransitionDiagnostics,
+      iceTransportPolicy: settings.iceTransportPolicy,
+      customIceServers: parseCustomIceServers(settings),
+      udpPortRange: parseUdpPortRange(settings.webrtcUdpPortRange) ?? undefined,
+      networkQosMarking: settings.networkQosMarking,
+      jitterBufferMs: settings.jitterBufferMs,
+      mediaThreadPriority: settings.mediaThreadPriority,
+      lowLatency: settings.lowLatencyMode,
+      audioOutput: { exclusive: settings.audioOutputMode === "exclusive", bufferMs: effectiveSettings.audioBufferMs },
+      controllerSlots: settings.controllerSlotsEnabled,
     };
+    // Resolution and FPS already went through the entitlement check above.
+    return applyGameStreamOverride(
+      applyGameStreamOverride(streamSettings, batteryProfile && { hdr: batteryProfile.hdr, codec: batteryProfile.codec }),
+      gameOverride && { hdr: gameOverride.hdr, codec: gameOverride.codec },
+    );
   }, [
+    effectiveSettings.audioBufferMs,
+    effectiveSettings.enableCloudGsync,
+    powerProfile,
+    settings.audioOutputMode,
+    settings.batteryStreamProfile,
     settings.codec,
     settings.colorQuality,
-    settings.enableCloudGsync,
+    settings.controllerSlotsEnabled,
+    settings.customIceCredential,
+    settings.customIceServerUrls,
+    settings.customIceUsername,
     settings.enableL4S,
     settings.fps,
     settings.gameLanguage,
+    settings.gameStreamOverrides,
+    settings.iceTransportPolicy,
+    settings.jitterBufferMs,
     settings.keyboardLayout,
+    settings.lowLatencyMode,
     settings.maxBitrateMbps,
+    settings.mediaThreadPriority,
     settings.nativeCloudGsyncMode,
     settings.nativeTransitionDiagnostics,
+    settings.networkQosMarking,
     settings.resolution,
     settings.streamClientMode,
+    settings.webrtcUdpPortRange,
     subscriptionInfo?.entitledResolutions,
   ]);
 
@@ -838,8 +1283,12 @@ export function App(): JSX.Element {
     sessionRef.current = session;
   }, [session]);
 
+  // Queued sessions have no rig yet, so they must not become the resume target of the snapshot.
+  const resumableNavbarSession =
+    navbarActiveSession && isSessionReadyForConnect(navbarActiveSession.status) ? navbarActiveSession : null;
+
   useEffect(() => {
-    const streamIsActive = streamStatus !== "idle" || session !== null || navbarActiveSession !== null;
+    const streamIsActive = streamStatus !== "idle" || session !== null || resumableNavbarSession !== null;
     if (!streamIsActive) {
       runtimeSnapshotRef.current = null;
       clearRuntimeSnapshot();
@@ -850,10 +1299,10 @@ export function App(): JSX.Element {
       version: 1,
       updatedAt: Date.now(),
       streamStatus,
-      sessionId: session?.sessionId ?? navbarActiveSession?.sessionId ?? null,
+      sessionId: session?.sessionId ?? resumableNavbarSession?.sessionId ?? null,
       sessionAppId:
         (Number.isFinite(signalingRecoveryRef.current.appId ?? NaN) ? signalingRecoveryRef.current.appId : null) ??
-        (navbarActiveSession ? navbarActiveSession.appId : null),
+        (resumableNavbarSession ? resumableNavbarSession.appId : null),
       streamingGameId: streamingGame?.id ?? null,
       streamingStore: streamingStore ?? null,
       recoveryAppId: signalingRecoveryRef.current.appId,
@@ -868,24 +1317,24 @@ export function App(): JSX.Element {
           clientId: session.clientId,
           deviceId: session.deviceId,
         }
-        : (navbarActiveSession?.sessionId && navbarActiveSession.serverIp)
+        : (resumableNavbarSession?.sessionId && resumableNavbarSession.serverIp)
           ? {
-            sessionId: navbarActiveSession.sessionId,
-            serverIp: navbarActiveSession.serverIp,
-            streamingBaseUrl: navbarActiveSession.streamingBaseUrl,
-            signalingUrl: navbarActiveSession.signalingUrl,
-            appId: Number.isFinite(navbarActiveSession.appId) ? navbarActiveSession.appId : undefined,
+            sessionId: resumableNavbarSession.sessionId,
+            serverIp: resumableNavbarSession.serverIp,
+            streamingBaseUrl: resumableNavbarSession.streamingBaseUrl,
+            signalingUrl: resumableNavbarSession.signalingUrl,
+            appId: Number.isFinite(resumableNavbarSession.appId) ? resumableNavbarSession.appId : undefined,
           }
           : null,
     };
 
     runtimeSnapshotRef.current = snapshot;
     saveRuntimeSnapshot(snapshot);
-  }, [navbarActiveSession, session, streamStatus, streamingGame?.id, streamingStore]);
+  }, [resumableNavbarSession, session, streamStatus, streamingGame?.id, streamingStore]);
 
   const persistRuntimeSnapshotNow = useCallback((): void => {
     const latestSession = sessionRef.current;
-    const latestNavbarSession = navbarActiveSession;
+    const latestNavbarSession = resumableNavbarSession;
     const hasActiveContext =
       streamStatusRef.current !== "idle" || latestSession !== null || latestNavbarSession !== null;
     if (!hasActiveContext) {
@@ -929,7 +1378,7 @@ export function App(): JSX.Element {
 
     runtimeSnapshotRef.current = snapshot;
     saveRuntimeSnapshot(snapshot);
-  }, [navbarActiveSession, streamingGame?.id, streamingStore]);
+  }, [resumableNavbarSession, streamingGame?.id, streamingStore]);
 
   useEffect(() => {
     const onBeforeUnload = (): void => {
@@ -947,21 +1396,42 @@ export function App(): JSX.Element {
 
   // Broadcast minimal session/loading state for UI listeners.
   useEffect(() => {
-    const detail = {
+    appEvents.emit("session:update", {
       status: streamStatus,
       queuePosition,
       launchError: launchError ? { title: launchError.title, description: launchError.description, stage: launchError.stage, codeLabel: launchError.codeLabel } : null,
       gameTitle: streamingGame?.title ?? null,
       gameCover: streamingGame?.imageUrl ?? null,
       platformStore: streamingStore ?? null,
-    };
-    try {
-      window.dispatchEvent(new CustomEvent("opennow:session-update", { detail }));
-    } catch {
-      // ignore
-    }
+    });
   }, [streamStatus, queuePosition, launchError, streamingGame, streamingStore]);
 
+  useEffect(() => {
+    if (launchError) {
+      appEvents.emit("app:error", {
+        stage: launchError.stage,
+        title: launchError.title,
+        description: launchError.description,
+        codeLabel: launchError.codeLabel,
+      });
+    }
+  }, [launchError]);
+
+  // One window listener feeds gamepad hotplug to every page through the event bus.
+  useEffect(() => {
+    const forward = (change: "connected" | "disconnected") => (event: GamepadEvent) => {
+      appEvents.emit("input:device", { kind: "gamepad", change, index: event.gamepad.index, id: event.gamepad.id });
+    };
+    const onConnected = forward("connected");
+    const onDisconnected = forward("disconnected");
+    window.addEventListener("gamepadconnected", onConnected);
+    window.addEventListener("gamepaddisconnected", onDisconnected);
+    return () => {
+      window.removeEventListener("gamepadconnected", onConnected);
+      window.removeEventListener("gamepaddisconnected", onDisconnected);
+    };
+  }, []);
+
   // Derived state
   const selectedProvider = useMemo(() => {
     return providers.find((p) => p.idpId === providerIdpId) ?? authSession?.provider ?? null;
@@ -1054,17 +1524,13 @@ export function App(): JSX.Element {
     try {
       const activeSessions = await window.openNow.getActiveSessions(token, streamingBaseUrl);
       const snapshot = runtimeSnapshotRef.current;
-      const resumableSessions = activeSessions.filter((entry) => entry.status === 3 || entry.status === 2);
-      const candidate =
-        (snapshot?.sessionId
-          ? resumableSessions.find((entry) => entry.sessionId === snapshot.sessionId)
-          : undefined) ??
-        (snapshot?.sessionAppId !== null && snapshot?.sessionAppId !== undefined
-          ? resumableSessions.find((entry) => entry.appId === snapshot.sessionAppId)
-          : undefined) ??
-        resumableSessions[0] ??
-        null;
-      setNavbarActiveSession(candidate);
+      const trackedSessions = orderActiveSessions(
+        activeSessions,
+        [selectedNavbarSessionIdRef.current, snapshot?.sessionId],
+        snapshot?.sessionAppId,
+      );
+      setNavbarActiveSessions(trackedSessions);
+      setNavbarActiveSession(trackedSessions[0] ?? null);
     } catch (error) {
       console.warn("Failed to refresh active sessions:", error);
     }
@@ -1072,6 +1538,15 @@ export function App(): JSX.Element {
 
   const storePanelGames = useMemo(() => flattenStorePanelGames(storePanels), [storePanels]);
   const allKnownGames = useMemo(() => [...games, ...libraryGames, ...storePanelGames], [games, libraryGames, storePanelGames]);
+  const overrideGames = useMemo(() => {
+    const byId = new Map<string, { id: string; title: string }>();
+    for (const game of libraryGames.length > 0 ? libraryGames : allKnownGames) {
+      if (!byId.has(game.id)) {
+        byId.set(game.id, { id: game.id, title: game.title });
+      }
+    }
+    return [...byId.values()].sort((a, b) => a.title.localeCompare(b.title));
+  }, [allKnownGames, libraryGames]);
 
   const gameTitleByAppId = useMemo(() => {
     const titles = new Map<number, string>();
@@ -1126,10 +1601,10 @@ export function App(): JSX.Element {
   }, [authSession]);
 
   useEffect(() => {
-    if (!startupRefreshNotice) return;
-    const timer = window.setTimeout(() => setStartupRefreshNotice(null), 7000);
+    if (!appNotice) return;
+    const timer = window.setTimeout(() => setAppNotice(null), 7000);
     return () => window.clearTimeout(timer);
-  }, [startupRefreshNotice]);
+  }, [appNotice]);
 
   useEffect(() => {
     if (!authSession || streamStatus !== "idle") {
@@ -1171,7 +1646,24 @@ export function App(): JSX.Element {
     const toggleMicrophone = parseWithFallback(settings.shortcutToggleMicrophone, DEFAULT_SHORTCUTS.shortcutToggleMicrophone);
     const screenshot = parseWithFallback(settings.shortcutScreenshot, DEFAULT_SHORTCUTS.shortcutScreenshot);
     const recording = parseWithFallback(settings.shortcutToggleRecording, DEFAULT_SHORTCUTS.shortcutToggleRecording);
-    return { toggleStats, togglePointerLock, toggleFullscreen, stopStream, toggleAntiAfk, toggleMicrophone, screenshot, recording };
+    const toggleStreamMute = parseWithFallback(settings.shortcutToggleStreamMute, DEFAULT_SHORTCUTS.shortcutToggleStreamMute);
+    const volumeUp = parseWithFallback(settings.shortcutVolumeUp, DEFAULT_SHORTCUTS.shortcutVolumeUp);
+    const volumeDown = parseWithFallback(settings.shortcutVolumeDown, DEFAULT_SHORTCUTS.shortcutVolumeDown);
+    const toggleTextInput = parseWithFallback(settings.shortcutToggleTextInput, DEFAULT_SHORTCUTS.shortcutToggleTextInput);
+    return {
+      toggleStats,
+      togglePointerLock,
+      toggleFullscreen,
+      stopStream,
+      toggleAntiAfk,
+      toggleMicrophone,
+      screenshot,
+      recording,
+      toggleStreamMute,
+      volumeUp,
+      volumeDown,
+      toggleTextInput,
+    };
   }, [
     settings.shortcutToggleStats,
     settings.shortcutTogglePointerLock,
@@ -1181,6 +1673,10 @@ export function App(): JSX.Element {
     settings.shortcutToggleMicrophone,
     settings.shortcutScreenshot,
     settings.shortcutToggleRecording,
+    settings.shortcutToggleStreamMute,
+    settings.shortcutVolumeUp,
+    settings.shortcutVolumeDown,
+    settings.shortcutToggleTextInput,
   ]);
 
   const nativeStreamerShortcuts = useMemo(() => ({
@@ -1192,10 +1688,13 @@ export function App(): JSX.Element {
     toggleMicrophone: shortcuts.toggleMicrophone.canonical,
     screenshot: "",
     toggleRecording: "",
+    toggleStreamMute: shortcuts.toggleStreamMute.canonical,
+    volumeUp: shortcuts.volumeUp.canonical,
+    volumeDown: shortcuts.volumeDown.canonical,
   }), [shortcuts]);
 
   const buildSignalingConnectRequest = useCallback((activeSession: SessionInfo): SignalingConnectRequest => {
-    const streamSettings = buildCurrentStreamSettings();
+    const streamSettings = sessionStreamSettingsRef.current ?? buildCurrentStreamSettings();
     return {
       sessionId: activeSession.sessionId,
       signalingServer: activeSession.signalingServer,
@@ -1269,30 +1768,98 @@ export function App(): JSX.Element {
     return () => document.removeEventListener("fullscreenchange", handleFullscreenChange);
   }, []);
 
-  const requestPointerLockCapture = useCallback(async (target: HTMLVideoElement) => {
-    const lockTarget = (target.parentElement as HTMLElement | null) ?? target;
-    const requestPointerLockCompat = async (
-      options?: { unadjustedMovement?: boolean },
-    ): Promise<void> => {
-      const maybePromise = lockTarget.requestPointerLock(options as any) as unknown;
-      if (maybePromise && typeof (maybePromise as Promise<void>).then === "function") {
-        await (maybePromise as Promise<void>);
+  const applyMiniPlayer = useCallback(async (enabled: boolean) => {
+    const active = await window.openNow.setMiniPlayer(enabled).catch(() => false);
+    clientRef.current?.setInputPaused(active);
+    setMiniPlayer(active);
+  }, []);
+
+  const handleToggleMiniPlayer = useCallback(() => {
+    void applyMiniPlayer(!miniPlayer);
+  }, [applyMiniPlayer, miniPlayer]);
+
+  // Controllers drive the on-screen keyboard instead of the game while it is open.
+  useEffect(() => {
+    clientRef.current?.setInputPaused(onScreenKeyboardActive || miniPlayer);
+  }, [miniPlayer, onScreenKeyboardActive]);
+
+  // The kill switch closes a capture that is already running, not just future ones.
+  useEffect(() => {
+    if (settings.microphoneBlocked) {
+      clientRef.current?.stopMicrophone();
+    }
+  }, [settings.microphoneBlocked]);
+
+  useEffect(() => {
+    clientRef.current?.setGamepadMouseTuning({
+      speed: settings.gamepadMouseSpeed,
+      accelerationPercent: settings.gamepadMouseAcceleration,
+    });
+  }, [settings.gamepadMouseAcceleration, settings.gamepadMouseSpeed]);
+
+  const restreamPublisherRef = useRef<WhipPublisher | null>(null);
+  const restreamMaxHeightRef = useRef(settings.restreamMaxHeight);
+  const restreamEndpoint = settings.restreamEnabled && settings.streamClientMode !== "native"
+    ? normalizeWhipEndpoint(settings.restreamWhipUrl)
+    : null;
+
+  // Spectator re-stream: publish the received tracks to the LAN WHIP ingest while streaming.
+  useEffect(() => {
+    if (!isStreaming || !restreamEndpoint) {
+      return;
+    }
+    let publisher: WhipPublisher | null = null;
+    // Settle first so typing the URL does not open a connection per keystroke.
+    const timer = window.setTimeout(() => {
+      const video = videoRef.current?.srcObject;
+      const audio = audioRef.current?.srcObject;
+      const tracks = [
+        ...(video instanceof MediaStream ? video.getVideoTracks() : []),
+        ...(audio instanceof MediaStream ? audio.getAudioTracks() : []),
+      ].filter((track) => track.readyState === "live");
+      if (tracks.length === 0) {
+        return;
+      }
+      publisher = new WhipPublisher({ endpoint: restreamEndpoint, maxHeight: restreamMaxHeightRef.current });
+      restreamPublisherRef.current = publisher;
+      publisher.start(tracks).catch((error) => {
+        console.warn("[Restream] Failed to publish to WHIP endpoint:", error);
+      });
+    }, 1000);
+    return () => {
+      window.clearTimeout(timer);
+      if (publisher) {
+        if (restreamPublisherRef.current === publisher) {
+          restreamPublisherRef.current = null;
+        }
+        void publisher.stop();
       }
     };
+  }, [isStreaming, restreamEndpoint]);
+
+  useEffect(() => {
+    restreamMaxHeightRef.current = settings.restreamMaxHeight;
+    restreamPublisherRef.current?.setMaxHeight(settings.restreamMaxHeight).catch((error) => {
+      console.warn("[Restream] Failed to apply resolution cap:", error);
+    });
+  }, [settings.restreamMaxHeight]);
+
+  // Leaving the stream (or losing it) always restores the full window and input.
+  useEffect(() => {
+    if (miniPlayer && streamStatus !== "streaming") {
+      void applyMiniPlayer(false);
+    }
+  }, [applyMiniPlayer, miniPlayer, streamStatus]);
+
+  const requestPointerLockCapture = useCallback(async (target: HTMLVideoElement) => {
+    const lockTarget = (target.parentElement as HTMLElement | null) ?? target;
 
     if (settings.autoFullScreen && !(sessionFullscreen || document.fullscreenElement)) {
       await setSessionFullscreen(true);
     }
 
-    await requestPointerLockCompat({ unadjustedMovement: true })
-      .catch((err: DOMException) => {
-        if (err.name === "NotSupportedError") {
-          return requestPointerLockCompat();
-        }
-        throw err;
-      })
-      .catch(() => {});
-  }, [sessionFullscreen, setSessionFullscreen, settings.autoFullScreen]);
+    await requestPointerLockMovement(lockTarget, effectiveSettings.mouseRawInput).catch(() => {});
+  }, [effectiveSettings.mouseRawInput, sessionFullscreen, setSessionFullscreen, settings.autoFullScreen]);
 
   const handleRequestPointerLock = useCallback(() => {
     if (videoRef.current) {
@@ -1346,6 +1913,12 @@ export function App(): JSX.Element {
     return () => unsubscribe();
   }, [toggleSessionFullscreen]);
 
+  // Main only claims Alt+Enter while a stream is up; otherwise the key goes to the page.
+  const streamActiveForShortcuts = streamStatus === "streaming";
+  useEffect(() => {
+    window.openNow.notifyStreamActiveChange(streamActiveForShortcuts);
+  }, [streamActiveForShortcuts]);
+
   const autoFullscreenRequestedRef = useRef(false);
 
   useEffect(() => {
@@ -1455,6 +2028,8 @@ export function App(): JSX.Element {
 
     if (hasCrossedWarningThreshold(previousSeconds, freeTierSessionRemainingSeconds, FREE_TIER_FINAL_MINUTE_WARNING_SECONDS)) {
       setLocalSessionTimerWarning({ stage: "free-tier-final-minute", shownAtMs: Date.now() });
+    } else if (hasCrossedWarningThreshold(previousSeconds, freeTierSessionRemainingSeconds, FREE_TIER_5_MIN_WARNING_SECONDS)) {
+      setLocalSessionTimerWarning({ stage: "free-tier-5m", shownAtMs: Date.now() });
     } else if (hasCrossedWarningThreshold(previousSeconds, freeTierSessionRemainingSeconds, FREE_TIER_15_MIN_WARNING_SECONDS)) {
       setLocalSessionTimerWarning({ stage: "free-tier-15m", shownAtMs: Date.now() });
     } else if (hasCrossedWarningThreshold(previousSeconds, freeTierSessionRemainingSeconds, FREE_TIER_30_MIN_WARNING_SECONDS)) {
@@ -1485,15 +2060,68 @@ export function App(): JSX.Element {
     return () => window.clearTimeout(timer);
   }, [remoteStreamWarning]);
 
+  const [customAccentHex, setCustomAccentHex] = useState<string | null>(null);
+
   useEffect(() => {
-    applyAccentColor(settings.appAccentColor);
+    // Re-read the theme file whenever the accent changes so picking "custom"
+    // again picks up edits made while the app was open.
+    let cancelled = false;
+    void window.openNow.getUserTheme()
+      .then((theme) => {
+        if (!cancelled) {
+          setCustomAccentHex(theme.accent);
+        }
+      })
+      .catch((error) => {
+        console.warn("Failed to load user theme:", error);
+      });
+    return () => {
+      cancelled = true;
+    };
   }, [settings.appAccentColor]);
 
+  useEffect(() => {
+    applyAccentColor(settings.appAccentColor, customAccentHex);
+  }, [customAccentHex, settings.appAccentColor]);
+
+  useEffect(() => {
+    const query = window.matchMedia("(prefers-color-scheme: dark)");
+    const apply = (): void => applyAppTheme(resolveAppTheme(settings.appTheme, query.matches));
+    apply();
+    if (settings.appTheme !== "system") {
+      return;
+    }
+    query.addEventListener("change", apply);
+    return () => query.removeEventListener("change", apply);
+  }, [settings.appTheme]);
+
+  const promptParentalPin = useCallback((purpose: ParentalPinPurpose): Promise<string | null> => {
+    return new Promise((resolve) => {
+      setParentalPrompt({ purpose, resolve });
+    });
+  }, []);
+
   // Save settings when changed
   const updateSetting = useCallback(async <K extends keyof Settings>(key: K, value: Settings[K]) => {
     setSettings((prev) => ({ ...prev, [key]: value }));
     if (settingsLoaded) {
-      await window.openNow.setSetting(key, value);
+      const isProtected = isParentalProtectedSetting(key);
+      try {
+        await window.openNow.setSetting(key, value, isProtected ? parentalPinRef.current ?? undefined : undefined);
+      } catch (error) {
+        if (!isProtected) {
+          throw error;
+        }
+        // Main needs the parental PIN for this one; ask for it, or put the saved value back.
+        const pin = await promptParentalPin("change");
+        if (pin === null) {
+          const stored = await window.openNow.getSettings();
+          setSettings((prev) => ({ ...prev, [key]: stored[key] }));
+          return;
+        }
+        parentalPinRef.current = pin;
+        await window.openNow.setSetting(key, value, pin);
+      }
     }
     // If a running client exists, push certain settings live
     if (key === "mouseSensitivity") {
@@ -1510,6 +2138,13 @@ export function App(): JSX.Element {
         // ignore
       }
     }
+    if (key === "controllerSlotsEnabled") {
+      try {
+        (clientRef.current as any)?.setControllerSlotsEnabled?.(value as boolean[]);
+      } catch {
+        // ignore
+      }
+    }
     if (key === "autoFullScreen") {
       try {
         (clientRef.current as any)?.setAutoFullScreen?.(value as boolean);
@@ -1524,7 +2159,20 @@ export function App(): JSX.Element {
         // ignore
       }
     }
-  }, [settingsLoaded]);
+  }, [promptParentalPin, settingsLoaded]);
+
+  useEffect(() => {
+    getLogCapture()?.setFilter(parseLogFilter(settings.logFilter));
+  }, [settings.logFilter]);
+
+  // Raw input follows the low-latency preset as well as its own setting.
+  useEffect(() => {
+    try {
+      (clientRef.current as any)?.setMouseRawInput?.(effectiveSettings.mouseRawInput);
+    } catch {
+      // ignore
+    }
+  }, [effectiveSettings.mouseRawInput]);
 
   useEffect(() => {
     if (!settingsLoaded || !subscriptionInfo) {
@@ -1554,6 +2202,39 @@ export function App(): JSX.Element {
     void updateSetting("mouseSensitivity", value);
   }, [updateSetting]);
 
+  // Dragging a volume slider fires on every step; apply each step locally and
+  // write the value to disk once the slider settles.
+  const streamVolumePersistTimerRef = useRef<number | null>(null);
+  const pendingStreamVolumeRef = useRef<number | null>(null);
+
+  const flushStreamVolume = useCallback(() => {
+    if (streamVolumePersistTimerRef.current !== null) {
+      window.clearTimeout(streamVolumePersistTimerRef.current);
+      streamVolumePersistTimerRef.current = null;
+    }
+    const pending = pendingStreamVolumeRef.current;
+    pendingStreamVolumeRef.current = null;
+    if (pending !== null && settingsLoaded) {
+      void window.openNow.setSetting("streamVolume", pending);
+    }
+  }, [settingsLoaded]);
+
+  const handleStreamVolumeChange = useCallback((value: number) => {
+    const volume = clampStreamVolume(value);
+    setSettings((prev) => (prev.streamVolume === volume ? prev : { ...prev, streamVolume: volume }));
+    pendingStreamVolumeRef.current = volume;
+    if (streamVolumePersistTimerRef.current !== null) {
+      window.clearTimeout(streamVolumePersistTimerRef.current);
+    }
+    streamVolumePersistTimerRef.current = window.setTimeout(flushStreamVolume, STREAM_VOLUME_PERSIST_DELAY_MS);
+  }, [flushStreamVolume]);
+
+  useEffect(() => flushStreamVolume, [flushStreamVolume]);
+
+  const handleToggleStreamMute = useCallback(() => {
+    void updateSetting("streamMuted", !settings.streamMuted);
+  }, [settings.streamMuted, updateSetting]);
+
   const handleToggleFavoriteGame = useCallback((gameId: string): void => {
     const favorites = settings.favoriteGameIds;
     const exists = favorites.includes(gameId);
@@ -1573,6 +2254,23 @@ export function App(): JSX.Element {
     });
   }, [persistRuntimeSnapshotNow]);
 
+  useEffect(() => {
+    // The privacy indicator reports per session, so a past paste must not carry into the next one.
+    if (streamStatus === "idle") {
+      setClipboardSentAtMs(null);
+    }
+  }, [streamStatus]);
+
+  const streamPrivacy = useMemo(() => ({
+    clipboardSentAtMs,
+    onBlockMicrophone: () => {
+      void updateSetting("microphoneBlocked", true);
+    },
+    onStopClipboardSync: () => {
+      void updateSetting("clipboardPaste", false);
+    },
+  }), [clipboardSentAtMs, updateSetting]);
+
   const handleMicrophoneModeChange = useCallback((value: import("@shared/gfn").MicrophoneMode) => {
     // Keep UI responsive while still surfacing persistence failures.
     void updateSetting("microphoneMode", value).catch((error) => {
@@ -1785,13 +2483,13 @@ export function App(): JSX.Element {
         const persistedSession = sessionResult.session;
 
         if (sessionResult.refresh.outcome === "refreshed") {
-          setStartupRefreshNotice({
+          setAppNotice({
             tone: "success",
             text: t("auth.status.sessionRestoredTokenRefreshed"),
           });
           setStartupStatusMessage(t("auth.status.tokenRefreshedLoadingAccount"));
         } else if (sessionResult.refresh.outcome === "failed") {
-          setStartupRefreshNotice({
+          setAppNotice({
             tone: "warn",
             text: t("auth.status.tokenRefreshFailedUsingSaved"),
           });
@@ -1860,11 +2558,15 @@ export function App(): JSX.Element {
     void initialize();
   }, [hydrateCatalogSnapshot, loadSessionRuntimeData, resetStorePanels, t]);
 
+  const browserLoginCancelledRef = useRef(false);
+
   // Login handler
   const handleLogin = useCallback(async () => {
+    browserLoginCancelledRef.current = false;
     setIsLoggingIn(true);
     setActiveLoginMode("oauth");
     setLoginError(null);
+    setLoginPrompt(null);
     if (qrLoginChallenge) {
       void window.openNow.cancelDeviceLogin({ attemptId: qrLoginChallenge.attemptId });
     }
@@ -1876,13 +2578,32 @@ export function App(): JSX.Element {
       await refreshSavedAccounts();
       await loadSessionRuntimeData(session);
     } catch (error) {
-      setLoginError(error instanceof Error ? error.message : t("errors.loginFailed"));
+      if (!browserLoginCancelledRef.current) {
+        setLoginError(error instanceof Error ? error.message : t("errors.loginFailed"));
+      }
     } finally {
       setIsLoggingIn(false);
       setActiveLoginMode(null);
     }
   }, [loadSessionRuntimeData, providerIdpId, qrLoginChallenge, refreshSavedAccounts, t]);
 
+  const handleBrowseAsGuest = useCallback(() => {
+    setLoginError(null);
+    setLoginPrompt(null);
+    setCurrentPage("home");
+    setGuestMode(true);
+  }, []);
+
+  const handleGuestSignIn = useCallback((prompt: string | null = null) => {
+    setLoginPrompt(prompt);
+    setGuestMode(false);
+  }, []);
+
+  const handleCancelLogin = useCallback(() => {
+    browserLoginCancelledRef.current = true;
+    void window.openNow.cancelLogin();
+  }, []);
+
   const qrLoginAttemptRef = useRef(0);
   const completingQrLoginRef = useRef(false);
 
@@ -2068,7 +2789,15 @@ export function App(): JSX.Element {
     runtimeDataLoadIdRef.current += 1;
     resetStorePanels();
     clearCatalogSnapshot();
-    await window.openNow.logoutAll();
+    let revokeFailedCount = 0;
+    if (logoutRevokesTokens) {
+      const result = await window.openNow.revokeAllSessions();
+      revokeFailedCount = result.failed;
+    } else {
+      await window.openNow.logoutAll();
+    }
+    // Shown on the sign-in screen the user lands on next.
+    setLoginError(revokeFailedCount > 0 ? t("auth.accounts.signOutEverywhereFailed", { count: revokeFailedCount }) : null);
     setAuthSession(null);
     setSavedAccounts([]);
     setGames([]);
@@ -2088,17 +2817,23 @@ export function App(): JSX.Element {
     setSelectedGameId("");
     setIsLoadingCatalog(false);
     setIsLoadingLibrary(false);
-  }, [resetLaunchRuntime, resetStorePanels]);
+  }, [logoutRevokesTokens, resetLaunchRuntime, resetStorePanels, t]);
 
   // Logout handler
   const handleLogout = useCallback(() => {
+    setLogoutRevokesTokens(false);
+    setLogoutConfirmOpen(true);
+  }, []);
+
+  const handleSignOutEverywhere = useCallback(() => {
+    setLogoutRevokesTokens(true);
     setLogoutConfirmOpen(true);
   }, []);
 
   // Load games handler
   const loadGames = useCallback(async (
     targetSource: "main" | "library",
-    options?: { background?: boolean },
+    options?: { background?: boolean; refresh?: boolean; signal?: AbortSignal },
   ) => {
     const setLoading = targetSource === "main" ? setIsLoadingCatalog : setIsLoadingLibrary;
     if (!options?.background) {
@@ -2109,7 +2844,7 @@ export function App(): JSX.Element {
       const userId = authSession?.user.userId;
       const baseUrl = effectiveStreamingBaseUrl;
       const proxyUrl = activeSessionProxyUrl;
-      if (!token || !userId) {
+      if (!token || !userId || options?.signal?.aborted) {
         return;
       }
 
@@ -2134,7 +2869,17 @@ export function App(): JSX.Element {
         return;
       }
 
-      const result = await window.openNow.fetchLibraryGames({ token, userId, providerStreamingBaseUrl: baseUrl, proxyUrl });
+      const result = await window.openNow.fetchLibraryGames({
+        token,
+        userId,
+        providerStreamingBaseUrl: baseUrl,
+        proxyUrl,
+        refresh: options?.refresh,
+      });
+      // The request itself can't be cancelled across IPC, so an aborted sync just drops its result.
+      if (options?.signal?.aborted) {
+        return;
+      }
       setLibraryGames(result);
       setSelectedGameId((previous) => result.some((game) => game.id === previous) ? previous : (result[0]?.id ?? ""));
       applyVariantSelections(result);
@@ -2204,6 +2949,30 @@ export function App(): JSX.Element {
     });
   }, [libraryGames, storePanelGames]);
 
+  useEffect(() => {
+    if (!guestMode || authSession) {
+      return;
+    }
+    let cancelled = false;
+    setIsLoadingCatalog(true);
+    void window.openNow.fetchPublicGames().then((publicGames) => {
+      if (cancelled) return;
+      setGames(publicGames);
+      setCatalogTotalCount(publicGames.length);
+      setCatalogSupportedCount(publicGames.length);
+      setSelectedGameId((previous) => publicGames.some((game) => game.id === previous) ? previous : (publicGames[0]?.id ?? ""));
+      applyVariantSelections(publicGames);
+    }).catch((error) => {
+      console.error("Public catalog load failed:", error);
+      if (!cancelled) setGames([]);
+    }).finally(() => {
+      if (!cancelled) setIsLoadingCatalog(false);
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [applyVariantSelections, authSession, guestMode]);
+
   useEffect(() => {
     if (!authSession || currentPage !== "home" || settings.controllerMode || isInitializing) {
       return;
@@ -2404,7 +3173,8 @@ export function App(): JSX.Element {
         }
 
         const launchSubscription = await resolveSubscriptionInfoForLaunch();
-        const streamSettings = buildCurrentStreamSettings(launchSubscription);
+        const streamSettings = buildCurrentStreamSettings(launchSubscription, matchedContext?.game.id);
+        sessionStreamSettingsRef.current = streamSettings;
         const claimed = await window.openNow.claimSession({
           token,
           streamingBaseUrl: effectiveStreamingBaseUrl,
@@ -2472,7 +3242,14 @@ export function App(): JSX.Element {
       while (recoveryState.attemptCount < SIGNALING_RECOVERY_ATTEMPT_DELAYS_MS.length) {
         const attemptIndex = recoveryState.attemptCount;
         recoveryState.attemptCount += 1;
+        sessionTelemetryRef.current.noteReconnect();
         const attemptNumber = recoveryState.attemptCount;
+        sessionTimelineRef.current.note(
+          "reconnect",
+          "reconnectAttempt",
+          { attempt: attemptNumber, total: SIGNALING_RECOVERY_ATTEMPT_DELAYS_MS.length, reason },
+          Date.now(),
+        );
         const attemptDelayMs = SIGNALING_RECOVERY_ATTEMPT_DELAYS_MS[attemptIndex] ?? 0;
 
         console.warn(
@@ -2555,7 +3332,8 @@ export function App(): JSX.Element {
           }
 
           const recoverySubscription = await resolveSubscriptionInfoForLaunch();
-          const recoveryStreamSettings = buildCurrentStreamSettings(recoverySubscription);
+          const recoveryStreamSettings = buildCurrentStreamSettings(recoverySubscription, streamingGameRef.current?.id);
+          sessionStreamSettingsRef.current = recoveryStreamSettings;
           const claimed = await window.openNow.claimSession({
             token,
             streamingBaseUrl: effectiveStreamingBaseUrl,
@@ -2618,6 +3396,33 @@ export function App(): JSX.Element {
     resolveSubscriptionInfoForLaunch,
   ]);
 
+  // Chromium keeps gathering candidates on new interfaces and trickles them to
+  // the server, so docking usually migrates the stream on its own. If the old
+  // path drops first, resume the same session right away; the fresh offer
+  // restarts ICE with new credentials.
+  useEffect(() => {
+    return window.openNow.onNetworkChanged((change) => {
+      if (streamStatusRef.current !== "streaming" || appUnloadingRef.current) {
+        return;
+      }
+      console.log("[Network] Local interfaces changed during stream:", change);
+      lastNetworkChangeAtRef.current = performance.now();
+      setNetworkChangeNoticeVisible(true);
+      const iceState = latestIceConnectionStateRef.current;
+      if (iceState === "disconnected" || iceState === "failed") {
+        void attemptSessionRecovery("Network changed").catch((error) => {
+          console.error("[Recovery] Network-change recovery failed:", error);
+        });
+      }
+    });
+  }, [attemptSessionRecovery]);
+
+  useEffect(() => {
+    if (!networkChangeNoticeVisible) return;
+    const timer = window.setTimeout(() => setNetworkChangeNoticeVisible(false), NETWORK_CHANGE_NOTICE_MS);
+    return () => window.clearTimeout(timer);
+  }, [networkChangeNoticeVisible]);
+
   const handleExpectedNativeSessionClose = useCallback((reason: string): void => {
     console.log("[Recovery] Treating signaling close as ended session:", reason);
     const activeGameId = streamingGameRef.current?.id;
@@ -2642,6 +3447,7 @@ export function App(): JSX.Element {
         return null;
       }
 
+      lastDecodeStateRef.current = null;
       clientRef.current = new GfnWebRtcClient({
         videoElement: videoRef.current,
         audioElement: audioRef.current,
@@ -2650,9 +3456,23 @@ export function App(): JSX.Element {
         microphoneDeviceId: settings.microphoneDeviceId || undefined,
         mouseSensitivity: settings.mouseSensitivity,
         mouseAcceleration: settings.mouseAcceleration,
+        mouseRawInput: effectiveSettings.mouseRawInput,
+        gamepadMouseTuning: {
+          speed: settings.gamepadMouseSpeed,
+          accelerationPercent: settings.gamepadMouseAcceleration,
+        },
         keyboardLayout: settings.keyboardLayout,
+        controllerSlotsEnabled: settings.controllerSlotsEnabled,
+        steamInputDevices: steamInputDevicesRef.current,
         onLog: (line: string) => console.log(`[WebRTC] ${line}`),
-        onStats: (stats) => diagnosticsStore.set(stats),
+        onStats: (stats) => {
+          diagnosticsStore.set(stats);
+          const decodeState = decodeStateFromStats(stats);
+          if (decodeStateChanged(lastDecodeStateRef.current, decodeState)) {
+            lastDecodeStateRef.current = decodeState;
+            appEvents.emit("decode:state", decodeState);
+          }
+        },
         onTimeWarning: (warning) => {
           setRemoteStreamWarning({
             code: warning.code,
@@ -2664,6 +3484,13 @@ export function App(): JSX.Element {
         onMicStateChange: (state) => {
           console.log(`[App] Mic state: ${state.state}${state.deviceLabel ? ` (${state.deviceLabel})` : ""}`);
         },
+        onGamepadMouseModeChange: setGamepadMouseActive,
+        onOnScreenKeyboardChord: () => {
+          // The native streamer owns input while it runs; keys go through the web client only.
+          if (streamStatusRef.current === "streaming" && !nativeStreamingRef.current) {
+            setOnScreenKeyboardActive((prev) => !prev);
+          }
+        },
         onIceConnectionStateChange: (iceState) => {
           latestIceConnectionStateRef.current = iceState;
           if (iceDisconnectedRecoveryTimerRef.current !== null) {
@@ -2683,6 +3510,18 @@ export function App(): JSX.Element {
             });
             return;
           }
+          const networkChangedAt = lastNetworkChangeAtRef.current;
+          if (
+            iceState === "disconnected" &&
+            networkChangedAt !== null &&
+            performance.now() - networkChangedAt < NETWORK_CHANGE_RECOVERY_WINDOW_MS
+          ) {
+            console.warn("[Recovery] ICE disconnected after a network change; resuming immediately");
+            void attemptSessionRecovery("Network changed").catch((error) => {
+              console.error("[Recovery] Network-change recovery failed:", error);
+            });
+            return;
+          }
           if (iceState === "disconnected") {
             iceDisconnectedRecoveryTimerRef.current = window.setTimeout(() => {
               iceDisconnectedRecoveryTimerRef.current = null;
@@ -2700,9 +3539,9 @@ export function App(): JSX.Element {
           }
         },
       });
-      clientRef.current.setOutputVolume(streamVolume);
+      clientRef.current.setOutputVolume(streamOutputVolume);
       clientRef.current.setMicrophoneLevel(streamMicLevel);
-      if (settings.microphoneMode !== "disabled") {
+      if (settings.microphoneMode !== "disabled" && !settings.microphoneBlocked) {
         void clientRef.current.startMicrophone();
       }
       return clientRef.current;
@@ -2789,13 +3628,21 @@ export function App(): JSX.Element {
           const client = ensureWebRtcClient();
 
           if (client) {
+            const pathMtu = pathMtuRef.current;
+            const offerSettings = sessionStreamSettingsRef.current
+              ?? buildCurrentStreamSettings(undefined, streamingGameRef.current?.id);
             await client.handleOffer(event.sdp, activeSession, {
-              codec: settings.codec,
-              colorQuality: settings.colorQuality,
-              resolution: settings.resolution,
-              fps: settings.fps,
-              maxBitrateKbps: settings.maxBitrateMbps * 1000,
+              codec: offerSettings.codec,
+              colorQuality: offerSettings.colorQuality,
+              resolution: offerSettings.resolution,
+              fps: offerSettings.fps,
+              maxBitrateKbps: offerSettings.maxBitrateMbps * 1000,
               nativeTransitionDiagnostics: settings.nativeTransitionDiagnostics,
+              iceTransportPolicy: settings.iceTransportPolicy,
+              customIceServers: parseCustomIceServers(settings),
+              pathMtu,
+              qosMarking: settings.networkQosMarking,
+              lowLatency: settings.lowLatencyMode,
             });
             setLaunchError(null);
             setStreamStatus("streaming");
@@ -2821,10 +3668,13 @@ export function App(): JSX.Element {
         } else if (event.type === "native-shortcut") {
           handleStreamShortcutActionRef.current?.(event.action);
         } else if (event.type === "native-stream-stats") {
-          diagnosticsStore.set(mergeNativeStreamStats(
-            diagnosticsStore.getSnapshot(),
-            event.stats,
-          ));
+          const stats = mergeNativeStreamStats(diagnosticsStore.getSnapshot(), event.stats);
+          diagnosticsStore.set(stats);
+          const decodeState = decodeStateFromStats(stats);
+          if (decodeStateChanged(lastDecodeStateRef.current, decodeState)) {
+            lastDecodeStateRef.current = decodeState;
+            appEvents.emit("decode:state", decodeState);
+          }
         } else if (event.type === "native-stream-transition") {
           diagnosticsStore.set({
             ...diagnosticsStore.getSnapshot(),
@@ -2835,10 +3685,13 @@ export function App(): JSX.Element {
             nativeQueueMode: event.transition.queueMode,
             lagReasonDetail: event.transition.summary ?? "Native video transition detected",
           });
+        } else if (event.type === "native-stream-layers") {
+          setNativeVideoLayers({ layers: event.layers, active: event.active, pausedAtSender: event.pausedAtSender });
         } else if (event.type === "native-stream-stopped") {
           const reason = event.reason ?? "Native streamer stopped";
           console.warn("[App] Native streamer stopped:", reason);
           nativeStreamingRef.current = false;
+          setNativeVideoLayers(null);
           nativeInputProtocolVersionRef.current = null;
           clientRef.current?.dispose();
           clientRef.current = null;
@@ -2999,10 +3852,38 @@ export function App(): JSX.Element {
     });
 
     return () => unsubscribe();
-  }, [attemptSessionRecovery, diagnosticsStore, handleExpectedNativeSessionClose, refreshNavbarActiveSession, resetLaunchRuntime, scheduleStableRecoveryReset, settings, streamMicLevel, streamVolume, t]);
+  }, [attemptSessionRecovery, buildCurrentStreamSettings, diagnosticsStore, effectiveSettings, handleExpectedNativeSessionClose, refreshNavbarActiveSession, resetLaunchRuntime, scheduleStableRecoveryReset, settings, streamMicLevel, streamOutputVolume, t]);
 
   // Play game handler
+  const requestParentalUnlock = useCallback(async (purpose: ParentalPinPurpose): Promise<boolean> => {
+    const locked = settings.parentalPinSet
+      && (purpose === "launch" ? settings.parentalLockLaunch : settings.parentalLockSettings);
+    if (!locked) {
+      return true;
+    }
+    const pin = await promptParentalPin(purpose);
+    if (pin !== null && purpose === "settings") {
+      parentalPinRef.current = pin;
+    }
+    return pin !== null;
+  }, [promptParentalPin, settings.parentalLockLaunch, settings.parentalLockSettings, settings.parentalPinSet]);
+
+  const handleParentalPromptResult = useCallback((pin: string | null): void => {
+    parentalPrompt?.resolve(pin);
+    setParentalPrompt(null);
+  }, [parentalPrompt]);
+
+  useEffect(() => {
+    if (currentPage !== "settings") {
+      parentalPinRef.current = null;
+    }
+  }, [currentPage]);
+
   const handlePlayGame = useCallback(async (game: GameInfo, options?: { bypassGuards?: boolean; streamingBaseUrl?: string; variantId?: string }) => {
+    if (!authSession) {
+      handleGuestSignIn(t("auth.guest.signInToPlay", { title: game.title }));
+      return;
+    }
     if (!selectedProvider) return;
 
     console.log("handlePlayGame entry", {
@@ -3021,8 +3902,48 @@ export function App(): JSX.Element {
       return;
     }
 
+    // Direct launches and deep links skip the filtered catalog, so the age limit is checked here too.
+    if (!isGameAllowedForAge(game, settings.parentalMaxAge)) {
+      setLaunchError({
+        stage: "queue",
+        title: t("errors.parentalAgeBlockedTitle"),
+        description: t("errors.parentalAgeBlockedDescription", { value: game.title }),
+      });
+      return;
+    }
+
     const selectedVariantId = options?.variantId ?? variantByGameId[game.id] ?? defaultVariantId(game);
     const selectedVariant = getSelectedVariant(game, selectedVariantId);
+    if (selectedVariant && !isOwnedVariant(selectedVariant)) {
+      // Only the cached connection state is consulted so the launch never waits
+      // on the account service; an empty cache is filled for the next launch.
+      const accountLink = await window.openNow.peekGameAccountConnections()
+        .then((result) => {
+          if (!result) {
+            void window.openNow.fetchGameAccountConnections().catch((error: unknown) => {
+              console.warn("Account connection prefetch failed:", error);
+            });
+            return null;
+          }
+          return getAccountLinkRequirement(selectedVariant, result.accounts);
+        })
+        .catch((error: unknown) => {
+          console.warn("Account connection lookup failed, launching without it:", error);
+          return null;
+        });
+      if (accountLink) {
+        setStreamingGame(game);
+        setStreamingStore(selectedVariant.store);
+        setLaunchError({
+          stage: "queue",
+          title: t("streamLoading.accountLink.title", { provider: accountLink.label }),
+          description: t("streamLoading.accountLink.description", { provider: accountLink.label }),
+          accountLink,
+        });
+        return;
+      }
+    }
+
     const epicOwnershipError = getEpicOwnershipLaunchError(selectedVariant);
     if (epicOwnershipError) {
       setStreamingGame(game);
@@ -3140,9 +4061,14 @@ export function App(): JSX.Element {
 
       const sessionProxyUrl = activeSessionProxyUrl;
       const launchSubscription = await resolveSubscriptionInfoForLaunch();
-      const streamSettings = buildCurrentStreamSettings(launchSubscription);
+      const streamSettings = buildCurrentStreamSettings(launchSubscription, game.id);
+      sessionStreamSettingsRef.current = streamSettings;
+      if (settings.gameStreamOverrides[game.id]) {
+        console.log(`[Launch] Applying stream overrides for ${game.title}:`, JSON.stringify(settings.gameStreamOverrides[game.id]));
+      }
 
       // Create new session
+      launchRoutingUrlRef.current = options?.streamingBaseUrl || null;
       const newSession = await window.openNow.createSession({
         token: token || undefined,
         streamingBaseUrl: options?.streamingBaseUrl || effectiveStreamingBaseUrl,
@@ -3299,18 +4225,36 @@ export function App(): JSX.Element {
     buildSignalingConnectRequest,
     claimAndConnectSession,
     effectiveStreamingBaseUrl,
+    handleGuestSignIn,
     refreshNavbarActiveSession,
     resetSignalingRecoveryState,
     resetLaunchRuntime,
     resetStatsOverlayToPreference,
     resolveSubscriptionInfoForLaunch,
     selectedProvider,
+    settings.gameStreamOverrides,
+    settings.parentalMaxAge,
     streamStatus,
     t,
     variantByGameId,
     warmNativeStreamerForLaunch,
   ]);
 
+  // An armed requeue starts a fresh launch of the same game once the session ends.
+  useEffect(() => {
+    if (!sessionRequeue || streamStatus !== "idle" || launchInFlightRef.current) return;
+    const { game, variantId } = sessionRequeue;
+    setSessionRequeue(null);
+    void handlePlayGame(game, { variantId });
+  }, [handlePlayGame, sessionRequeue, streamStatus]);
+
+  const handleToggleSessionRequeue = useCallback(() => {
+    setSessionRequeue((current) => {
+      if (current || !streamingGame) return null;
+      return { game: streamingGame, variantId: variantByGameId[streamingGame.id] };
+    });
+  }, [streamingGame, variantByGameId]);
+
   useEffect(() => {
     const request = pendingDirectLaunchRequest;
     if (!request || handledDirectLaunchIdsRef.current.has(request.id)) return;
@@ -3383,6 +4327,9 @@ export function App(): JSX.Element {
         return;
       }
 
+      if (!(await requestParentalUnlock("launch")) || cancelled) {
+        return;
+      }
       void handlePlayGame(target.game, { variantId: target.variantId });
     };
 
@@ -3406,6 +4353,7 @@ export function App(): JSX.Element {
     isResumingNavbarSession,
     isTerminatingNavbarSession,
     pendingDirectLaunchRequest,
+    requestParentalUnlock,
     streamStatus,
     t,
     variantByGameId,
@@ -3413,10 +4361,14 @@ export function App(): JSX.Element {
 
   // Gate handler: shows queue server modal for FREE-tier users before launching
   const handleInitiatePlay = useCallback(async (game: GameInfo) => {
+    if (!(await requestParentalUnlock("launch"))) {
+      return;
+    }
     const effectiveTier = normalizeMembershipTier(
       subscriptionInfo?.membershipTier ?? authSession?.user.membershipTier,
     );
-    const isFreeUser = effectiveTier === "FREE";
+    // Guests get the free-tier queue picker too, since queue times are public; confirming asks them to sign in.
+    const isFreeUser = effectiveTier === "FREE" || !authSession;
     const activeProvider = authSession?.provider ?? selectedProvider;
     const isNvidiaAccount = isNvidiaProvider(activeProvider);
     const isAllianceServer = isAllianceStreamingBaseUrl(effectiveStreamingBaseUrl);
@@ -3476,7 +4428,7 @@ export function App(): JSX.Element {
       return;
     }
     void handlePlayGame(game);
-  }, [subscriptionInfo, authSession, selectedProvider, settings.hideServerSelector, streamStatus, handlePlayGame, effectiveStreamingBaseUrl]);
+  }, [subscriptionInfo, authSession, selectedProvider, settings.hideServerSelector, streamStatus, handlePlayGame, effectiveStreamingBaseUrl, requestParentalUnlock]);
 
   const handleQueueModalConfirm = useCallback((zoneUrl: string | null) => {
     const game = queueModalGame;
@@ -3498,6 +4450,13 @@ export function App(): JSX.Element {
     });
   }, []);
 
+  const handleOpenErrorHelp = useCallback((url: string | undefined): void => {
+    if (!url) return;
+    void window.openNow.openExternalUrl(url).catch((error) => {
+      console.error("Failed to open error help URL:", error);
+    });
+  }, []);
+
   const handleBuyGame = useCallback((game: GameInfo, selectedVariantId?: string): void => {
     const selectedVariant = getSelectedVariant(game, selectedVariantId ?? defaultVariantId(game));
     const localStoreUrl = selectedVariant?.storeUrl
@@ -3571,12 +4530,14 @@ export function App(): JSX.Element {
           />
           <div className="logout-confirm-card">
             <div className="logout-confirm-kicker">{t("auth.accounts.kicker")}</div>
-            <h3 className="logout-confirm-title">{t("auth.accounts.signOutAllTitle")}</h3>
+            <h3 className="logout-confirm-title">
+              {logoutRevokesTokens ? t("auth.accounts.signOutEverywhereTitle") : t("auth.accounts.signOutAllTitle")}
+            </h3>
             <p className="logout-confirm-text">
-              {t("auth.accounts.signOutAllDescription")}
+              {logoutRevokesTokens ? t("auth.accounts.signOutEverywhereDescription") : t("auth.accounts.signOutAllDescription")}
             </p>
             <p className="logout-confirm-subtext">
-              {t("auth.accounts.signOutAllSubtext")}
+              {logoutRevokesTokens ? t("auth.accounts.signOutEverywhereSubtext") : t("auth.accounts.signOutAllSubtext")}
             </p>
             <div className="logout-confirm-actions">
               <button
@@ -3593,7 +4554,7 @@ export function App(): JSX.Element {
                   void confirmLogout();
                 }}
               >
-                {t("auth.accounts.signOutAll")}
+                {logoutRevokesTokens ? t("auth.accounts.signOutEverywhere") : t("auth.accounts.signOutAll")}
               </button>
             </div>
             <div className="logout-confirm-hint">
@@ -3656,10 +4617,18 @@ export function App(): JSX.Element {
       )
     : null;
 
+  const handleSelectNavbarSession = useCallback((sessionId: string) => {
+    const selected = navbarActiveSessions.find((entry) => entry.sessionId === sessionId);
+    if (!selected) return;
+    selectedNavbarSessionIdRef.current = sessionId;
+    setNavbarActiveSession(selected);
+  }, [navbarActiveSessions]);
+
   const handleResumeFromNavbar = useCallback(async () => {
     if (
       !selectedProvider
       || !navbarActiveSession
+      || !isSessionReadyForConnect(navbarActiveSession.status)
       || isResumingNavbarSession
       || isTerminatingNavbarSession
       || navbarSessionActionInFlightRef.current
@@ -3819,6 +4788,25 @@ export function App(): JSX.Element {
     void refreshNavbarActiveSession();
   }, [markExplicitSignalingShutdown, refreshNavbarActiveSession, resetLaunchRuntime]);
 
+  const handleLinkAccountFromLaunchError = useCallback(async () => {
+    const accountLink = launchError?.accountLink;
+    if (!accountLink || accountLinkBusy) return;
+    setAccountLinkBusy(true);
+    try {
+      await window.openNow.linkGameAccount({ provider: accountLink.provider, proxyUrl: activeSessionProxyUrl });
+      await handleDismissLaunchError();
+      void loadGames("library", { background: true });
+    } catch (error) {
+      console.error("[Launch] Account linking failed:", error);
+      const message = error instanceof Error && error.message ? error.message : String(error);
+      setLaunchError((previous) => previous
+        ? { ...previous, description: t("streamLoading.accountLink.failed", { provider: accountLink.label, error: message }) }
+        : previous);
+    } finally {
+      setAccountLinkBusy(false);
+    }
+  }, [accountLinkBusy, activeSessionProxyUrl, handleDismissLaunchError, launchError, loadGames, t]);
+
   const releasePointerLockIfNeeded = useCallback(async () => {
     if (document.pointerLockElement) {
       clientRef.current?.suppressNextSyntheticEscapeOnPointerLockLoss();
@@ -3837,6 +4825,7 @@ export function App(): JSX.Element {
     const loadingPhases: StreamStatus[] = ["queue", "setup", "starting", "connecting"];
     if (loadingPhases.includes(streamStatus)) {
       launchAbortRef.current = true;
+      setSessionRequeue(null);
       await handleStopStream();
       return;
     }
@@ -3847,9 +4836,113 @@ export function App(): JSX.Element {
       return;
     }
 
+    setSessionRequeue(null);
     await handleStopStream();
   }, [handleStopStream, releasePointerLockIfNeeded, requestExitPrompt, streamStatus, streamingGame?.title, t]);
 
+  const handleStopStreamRef = useRef(handleStopStream);
+  useEffect(() => {
+    handleStopStreamRef.current = handleStopStream;
+  }, [handleStopStream]);
+
+  // Idle auto-disconnect. Only real keyboard, pointer and controller input
+  // resets the timer; the anti-AFK pulse is synthetic, so an opted-in anti-AFK
+  // session is left alone instead. Native streams take input in their own window.
+  useEffect(() => {
+    const timeoutMinutes = settings.idleDisconnectMinutes;
+    if (!isStreaming || timeoutMinutes <= 0 || antiAfkEnabled || nativeStreamingRef.current) {
+      setIdleDisconnectSecondsLeft(null);
+      return;
+    }
+
+    let lastInputAt = Date.now();
+    let lastGamepadTimestamp = latestGamepadTimestamp(navigator.getGamepads());
+    const markInput = (): void => {
+      lastInputAt = Date.now();
+    };
+    const inputEvents = ["keydown", "pointerdown", "pointermove", "wheel", "touchstart"] as const;
+    for (const type of inputEvents) {
+      window.addEventListener(type, markInput, { capture: true, passive: true });
+    }
+
+    const interval = window.setInterval(() => {
+      const gamepadTimestamp = latestGamepadTimestamp(navigator.getGamepads());
+      if (gamepadTimestamp > lastGamepadTimestamp) {
+        lastGamepadTimestamp = gamepadTimestamp;
+        markInput();
+      }
+
+      const idle = getIdleDisconnectState(Date.now() - lastInputAt, timeoutMinutes);
+      if (idle.phase === "expired") {
+        window.clearInterval(interval);
+        setIdleDisconnectSecondsLeft(null);
+        console.log(`[Session] No input for ${timeoutMinutes} minutes; disconnecting`);
+        setSessionRequeue(null);
+        void handleStopStreamRef.current();
+        return;
+      }
+      setIdleDisconnectSecondsLeft(idle.phase === "warning" ? idle.secondsLeft : null);
+    }, 1000);
+
+    return () => {
+      window.clearInterval(interval);
+      for (const type of inputEvents) {
+        window.removeEventListener(type, markInput, { capture: true });
+      }
+      setIdleDisconnectSecondsLeft(null);
+    };
+  }, [antiAfkEnabled, isStreaming, settings.idleDisconnectMinutes]);
+
+  // Play time reminders: escalate towards the per-session limit or bedtime,
+  // then either end the stream or keep nagging while the player runs over.
+  useEffect(() => {
+    const limits = { reminderMinutes: settings.playReminderMinutes, curfew: settings.playCurfew };
+    if (!isStreaming || sessionStartedAtMs === null || !hasPlayTimeLimit(limits)) {
+      setPlayTimeReminder(null);
+      return;
+    }
+
+    let previousSecondsLeft: number | null = null;
+    let shown: { reminder: PlayTimeReminderState; shownAtMs: number } | null = null;
+    const tick = (): void => {
+      const now = Date.now();
+      const deadline = getPlayTimeDeadline(sessionStartedAtMs, now, limits);
+      if (!deadline) {
+        return;
+      }
+      const secondsLeft = Math.ceil((deadline.atMs - now) / 1000);
+      const stage = nextPlayTimeReminder(previousSecondsLeft, secondsLeft);
+      previousSecondsLeft = secondsLeft;
+
+      if (stage === "over" && settings.playLimitDisconnect) {
+        window.clearInterval(interval);
+        setPlayTimeReminder(null);
+        console.log(`[Session] Play time limit (${deadline.reason}) reached; disconnecting`);
+        setSessionRequeue(null);
+        void handleStopStreamRef.current();
+        return;
+      }
+      if (stage) {
+        shown = { reminder: { stage, reason: deadline.reason }, shownAtMs: now };
+      }
+      if (shown?.reminder.stage === "final-minute") {
+        setPlayTimeReminder({ ...shown.reminder, secondsLeft });
+        return;
+      }
+      if (shown && now - shown.shownAtMs >= STREAM_WARNING_VISIBILITY_MS) {
+        shown = null;
+      }
+      setPlayTimeReminder(shown?.reminder ?? null);
+    };
+
+    const interval = window.setInterval(tick, 1000);
+    tick();
+    return () => {
+      window.clearInterval(interval);
+      setPlayTimeReminder(null);
+    };
+  }, [isStreaming, sessionStartedAtMs, settings.playCurfew, settings.playLimitDisconnect, settings.playReminderMinutes]);
+
   const handleStreamShortcutAction = useCallback((action: NativeStreamerShortcutAction): void => {
     switch (action) {
       case "toggleStats":
@@ -3890,11 +4983,30 @@ export function App(): JSX.Element {
       case "screenshot":
       case "toggleRecording":
         if (streamStatus === "streaming" && !nativeStreamingRef.current) {
-          dispatchStreamShortcutAction(action);
+          appEvents.emit("stream:shortcut", action);
+        }
+        return;
+      case "toggleStreamMute":
+        if (streamStatus === "streaming") {
+          handleToggleStreamMute();
+        }
+        return;
+      case "volumeUp":
+      case "volumeDown":
+        if (streamStatus === "streaming") {
+          handleStreamVolumeChange(stepStreamVolume(settings.streamVolume, action === "volumeUp" ? 1 : -1));
         }
         return;
     }
-  }, [handlePromptedStopStream, requestPointerLockCapture, streamStatus, toggleSessionFullscreen]);
+  }, [
+    handlePromptedStopStream,
+    handleStreamVolumeChange,
+    handleToggleStreamMute,
+    requestPointerLockCapture,
+    settings.streamVolume,
+    streamStatus,
+    toggleSessionFullscreen,
+  ]);
 
   useEffect(() => {
     handleStreamShortcutActionRef.current = handleStreamShortcutAction;
@@ -3945,6 +5057,7 @@ export function App(): JSX.Element {
               const text = await readStreamClipboardText();
               if (text) {
                 client.sendText(text);
+                setClipboardSentAtMs(Date.now());
               }
               return;
             } catch (error) {
@@ -4009,6 +5122,41 @@ export function App(): JSX.Element {
         if (streamStatus === "streaming") {
           clientRef.current?.toggleMicrophone();
         }
+        return;
+      }
+
+      if (isShortcutMatch(e, shortcuts.toggleStreamMute)) {
+        e.preventDefault();
+        e.stopPropagation();
+        e.stopImmediatePropagation();
+        handleStreamShortcutAction("toggleStreamMute");
+        return;
+      }
+
+      if (isShortcutMatch(e, shortcuts.volumeUp)) {
+        e.preventDefault();
+        e.stopPropagation();
+        e.stopImmediatePropagation();
+        handleStreamShortcutAction("volumeUp");
+        return;
+      }
+
+      if (isShortcutMatch(e, shortcuts.volumeDown)) {
+        e.preventDefault();
+        e.stopPropagation();
+        e.stopImmediatePropagation();
+        handleStreamShortcutAction("volumeDown");
+        return;
+      }
+
+      if (isShortcutMatch(e, shortcuts.toggleTextInput)) {
+        e.preventDefault();
+        e.stopPropagation();
+        e.stopImmediatePropagation();
+        // The native streamer owns input while it runs; text goes through the web client only.
+        if (streamStatus === "streaming" && !nativeStreamingRef.current) {
+          setTextInputActive((prev) => !prev);
+        }
       }
     };
 
@@ -4020,6 +5168,7 @@ export function App(): JSX.Element {
     handleExitPromptCancel,
     handleExitPromptConfirm,
     handlePromptedStopStream,
+    handleStreamShortcutAction,
     requestPointerLockCapture,
     settings.clipboardPaste,
     shortcuts,
@@ -4027,21 +5176,113 @@ export function App(): JSX.Element {
     toggleSessionFullscreen,
   ]);
 
-  const filteredGames = games;
+  const parentalMaxAge = settings.parentalMaxAge;
+  // Store panels need an account, so guests get the plain catalog grid even in controller mode.
+  const isGuestCatalog = guestMode && !authSession;
+  const filteredGames = useMemo(() => {
+    // The public catalog is fetched whole, so guests search it locally instead of through the catalog API.
+    const query = isGuestCatalog ? searchQuery.trim() : "";
+    const searched = query ? games.filter((game) => matchesGameSearch(game, query)) : games;
+    return parentalMaxAge === null ? searched : searched.filter((game) => isGameAllowedForAge(game, parentalMaxAge));
+  }, [games, isGuestCatalog, parentalMaxAge, searchQuery]);
+  const visibleFeaturedGames = useMemo(
+    () => (parentalMaxAge === null ? featuredGames : featuredGames.filter((game) => isGameAllowedForAge(game, parentalMaxAge))),
+    [featuredGames, parentalMaxAge],
+  );
+  const visibleStorePanels = useMemo(
+    () => (parentalMaxAge === null
+      ? storePanels
+      : storePanels.map((panel) => ({
+        ...panel,
+        sections: panel.sections.map((section) => ({
+          ...section,
+          games: section.games.filter((game) => isGameAllowedForAge(game, parentalMaxAge)),
+        })),
+      }))),
+    [parentalMaxAge, storePanels],
+  );
+
+  const libraryGenreOptions = useMemo(
+    () => collectGameGenres(
+      parentalMaxAge === null ? libraryGames : libraryGames.filter((game) => isGameAllowedForAge(game, parentalMaxAge)),
+    ),
+    [libraryGames, parentalMaxAge],
+  );
 
   const filteredLibraryGames = useMemo(() => {
     const query = searchQuery.trim();
     const searched = query ? libraryGames.filter((game) => matchesGameSearch(game, query)) : libraryGames;
+    const allowed = parentalMaxAge === null
+      ? searched
+      : searched.filter((game) => isGameAllowedForAge(game, parentalMaxAge));
     return sortLibraryGames(
-      searched,
+      allowed,
       catalogSelectedSortId === "relevance" ? "last_played" : catalogSelectedSortId,
       playtime,
     );
-  }, [libraryGames, searchQuery, catalogSelectedSortId, playtime]);
+  }, [libraryGames, searchQuery, catalogSelectedSortId, parentalMaxAge, playtime]);
+
+  const backgroundTasksRef = useRef(new BackgroundTaskScheduler());
+  const loadGamesRef = useRef(loadGames);
+  useEffect(() => {
+    loadGamesRef.current = loadGames;
+  }, [loadGames]);
+
+  useEffect(() => {
+    const scheduler = backgroundTasksRef.current;
+    return () => scheduler.dispose();
+  }, []);
+
+  // Prefetching and library syncs would compete with the stream for bandwidth, here and in the main process.
+  const backgroundWorkPaused = streamStatus !== "idle";
+  useEffect(() => {
+    backgroundTasksRef.current.setPaused(backgroundWorkPaused);
+    void window.openNow.setBackgroundWorkPaused(backgroundWorkPaused).catch((error) => {
+      console.warn("Failed to update background work state:", error);
+    });
+  }, [backgroundWorkPaused]);
+
+  useEffect(() => {
+    const scheduler = backgroundTasksRef.current;
+    const screenful = estimateScreenfulGameCount(window.innerWidth, window.innerHeight, settings.posterSizeScale);
+    const urls = artworkPrefetchUrls(filteredLibraryGames, screenful);
+    scheduler.register({
+      id: "artwork-prefetch",
+      intervalMs: null,
+      run: (signal) => prefetchArtwork(urls, signal),
+    });
+    scheduler.trigger("artwork-prefetch");
+  }, [filteredLibraryGames, settings.posterSizeScale]);
+
+  const librarySyncUserId = authSession?.user.userId ?? null;
+  useEffect(() => {
+    const scheduler = backgroundTasksRef.current;
+    if (!librarySyncUserId || settings.librarySyncMinutes <= 0) {
+      scheduler.unregister("library-sync");
+      return;
+    }
+    scheduler.register({
+      id: "library-sync",
+      intervalMs: settings.librarySyncMinutes * 60_000,
+      run: (signal) => loadGamesRef.current("library", { background: true, refresh: true, signal }),
+    });
+    return () => scheduler.unregister("library-sync");
+  }, [librarySyncUserId, settings.librarySyncMinutes]);
 
   const activeSessionAppIds = useMemo(
-    () => (navbarActiveSession ? [navbarActiveSession.appId] : []),
-    [navbarActiveSession?.appId],
+    () => (navbarActiveSession ? navbarActiveSessions.map((entry) => entry.appId) : []),
+    [navbarActiveSession, navbarActiveSessions],
+  );
+
+  const navbarSessionChoices = useMemo(
+    () => (navbarActiveSession
+      ? navbarActiveSessions.map((entry) => ({
+        sessionId: entry.sessionId,
+        title: gameTitleByAppId.get(entry.appId)?.trim() || null,
+        queued: !isSessionReadyForConnect(entry.status),
+      }))
+      : []),
+    [gameTitleByAppId, navbarActiveSession, navbarActiveSessions],
   );
 
   const activeSessionGameTitle = useMemo(() => {
@@ -4056,23 +5297,26 @@ export function App(): JSX.Element {
     return null;
   }, [gameTitleByAppId, navbarActiveSession, session?.sessionId, streamingGame?.title]);
 
+  const openPage = useCallback(async (page: AppPage): Promise<void> => {
+    if (page === "settings" && currentPage !== "settings") {
+      if (!(await requestParentalUnlock("settings"))) {
+        return;
+      }
+      setPageBeforeSettings(currentPage);
+    }
+    setCurrentPage(page);
+  }, [currentPage, requestParentalUnlock]);
+
   const navigateControllerPage = useCallback((direction: -1 | 1): void => {
     const pages: AppPage[] = ["library", "home", "settings"];
     const currentIndex = Math.max(0, pages.indexOf(currentPage));
     const nextIndex = (currentIndex + direction + pages.length) % pages.length;
-    const nextPage = pages[nextIndex];
-    if (nextPage === "settings" && currentPage !== "settings") {
-      setPageBeforeSettings(currentPage);
-    }
-    setCurrentPage(nextPage);
-  }, [currentPage]);
+    void openPage(pages[nextIndex]);
+  }, [currentPage, openPage]);
 
   const handleNavigate = useCallback((page: AppPage): void => {
-    if (page === "settings" && currentPage !== "settings") {
-      setPageBeforeSettings(currentPage);
-    }
-    setCurrentPage(page);
-  }, [currentPage]);
+    void openPage(page);
+  }, [openPage]);
 
   const handleCloseSettings = useCallback((): void => {
     setCurrentPage(pageBeforeSettings);
@@ -4091,7 +5335,7 @@ export function App(): JSX.Element {
   const mainPage: AppPage = currentPage === "settings" ? pageBeforeSettings : currentPage;
 
   // Show login screen if not authenticated
-  if (!authSession) {
+  if (!authSession && !guestMode) {
     return (
       <>
         <LoginScreen
@@ -4099,6 +5343,9 @@ export function App(): JSX.Element {
           selectedProviderId={providerIdpId}
           onProviderChange={setProviderIdpId}
           onLogin={handleLogin}
+          onCancelLogin={handleCancelLogin}
+          onBrowseAsGuest={handleBrowseAsGuest}
+          prompt={loginPrompt}
           onQrLogin={handleQrLogin}
           onCancelQrLogin={handleCancelQrLogin}
           isLoading={isLoggingIn}
@@ -4107,6 +5354,7 @@ export function App(): JSX.Element {
           statusMessage={startupStatusMessage}
           qrLoginChallenge={qrLoginChallenge}
           isQrLoginPending={activeLoginMode === "qr" && !qrLoginChallenge}
+          isBrowserLoginPending={activeLoginMode === "oauth"}
         />
       </>
     );
@@ -4138,6 +5386,10 @@ export function App(): JSX.Element {
               toggleMicrophone: formatShortcutForDisplay(settings.shortcutToggleMicrophone, isMac),
               screenshot: shortcuts.screenshot.canonical,
               recording: shortcuts.recording.canonical,
+              toggleStreamMute: formatShortcutForDisplay(settings.shortcutToggleStreamMute, isMac),
+              volumeUp: formatShortcutForDisplay(settings.shortcutVolumeUp, isMac),
+              volumeDown: formatShortcutForDisplay(settings.shortcutVolumeDown, isMac),
+              toggleTextInput: formatShortcutForDisplay(settings.shortcutToggleTextInput, isMac),
             }}
             hideStreamButtons={settings.hideStreamButtons}
             serverRegion={session?.serverIp}
@@ -4148,11 +5400,22 @@ export function App(): JSX.Element {
             sessionStartedAtMs={sessionStartedAtMs}
             sessionCounterEnabled={settings.sessionCounterEnabled}
             showSessionTimeRemainingInStatsOverlay={settings.showSessionTimeRemainingInStatsOverlay}
+            statsPanelLayout={statsPanelLayout}
+            routeTrace={routeTrace}
+            sessionTimeline={sessionTimelineRef.current}
             sessionTimeRemainingSeconds={sessionTimeRemainingSeconds}
             sessionClockShowEveryMinutes={settings.sessionClockShowEveryMinutes}
             sessionClockShowDurationSeconds={settings.sessionClockShowDurationSeconds}
             streamWarning={streamWarning}
+            networkChangeNotice={networkChangeNoticeVisible}
+            sessionRequeue={
+              streamingGame && freeTierSessionRemainingSeconds !== null && freeTierSessionRemainingSeconds <= FREE_TIER_5_MIN_WARNING_SECONDS
+                ? { armed: sessionRequeue !== null, onToggle: handleToggleSessionRequeue }
+                : null
+            }
             isFullscreen={sessionFullscreen || !!document.fullscreenElement}
+            miniPlayer={miniPlayer}
+            onToggleMiniPlayer={settings.streamClientMode === "native" ? undefined : handleToggleMiniPlayer}
             isConnecting={streamStatus === "connecting"}
             isStreaming={isStreaming}
             recordingBitrateMbps={settings.recordingBitrateMbps}
@@ -4171,10 +5434,18 @@ export function App(): JSX.Element {
             }}
             mouseSensitivity={settings.mouseSensitivity}
             onMouseSensitivityChange={handleMouseSensitivityChange}
+            streamVolume={settings.streamVolume}
+            streamMuted={settings.streamMuted}
+            onStreamVolumeChange={handleStreamVolumeChange}
+            onStreamVolumeCommit={flushStreamVolume}
+            onToggleStreamMute={handleToggleStreamMute}
+            videoLayers={nativeVideoLayers}
+            onVideoLayerChange={handleNativeVideoLayerChange}
             mouseAcceleration={settings.mouseAcceleration}
             onMouseAccelerationChange={handleMouseAccelerationChange}
             microphoneMode={settings.microphoneMode}
             onMicrophoneModeChange={handleMicrophoneModeChange}
+            privacy={streamPrivacy}
             onScreenshotShortcutChange={(value) => {
               void updateSetting("shortcutScreenshot", value);
             }}
@@ -4191,6 +5462,15 @@ export function App(): JSX.Element {
               void releasePointerLockIfNeeded();
             }}
             allowEscapeToExitFullscreen={settings.allowEscapeToExitFullscreen}
+            textInputActive={textInputActive}
+            onSendText={handleSendText}
+            onCloseTextInput={handleCloseTextInput}
+            onScreenKeyboardActive={onScreenKeyboardActive}
+            gamepadMouseActive={gamepadMouseActive}
+            onSendKey={handleSendKey}
+            onCloseOnScreenKeyboard={handleCloseOnScreenKeyboard}
+            latencyProbeActive={latencyProbeActive}
+            onLatencyProbeChange={setLatencyProbeActive}
           />
         )}
         {showDesktopLaunchLoading && (
@@ -4211,6 +5491,22 @@ export function App(): JSX.Element {
                     title: launchError.title,
                     description: launchError.description,
                     code: launchError.codeLabel,
+                    steps: launchError.steps,
+                    help: launchError.helpLink
+                      ? {
+                          label: launchError.helpLink.label,
+                          onClick: () => handleOpenErrorHelp(launchError.helpLink?.url),
+                        }
+                      : undefined,
+                    action: launchError.accountLink
+                      ? {
+                          label: t(accountLinkBusy ? "streamLoading.accountLink.linking" : "streamLoading.accountLink.action", {
+                            provider: launchError.accountLink.label,
+                          }),
+                          busy: accountLinkBusy,
+                          onClick: () => void handleLinkAccountFromLaunchError(),
+                        }
+                      : undefined,
                   }
                 : undefined
             }
@@ -4230,18 +5526,21 @@ export function App(): JSX.Element {
   // Main app layout
   return (
     <div className={`app-container${settings.controllerMode ? " app-container--controller" : ""}`} style={getAppStyle(settings.posterSizeScale)}>
-      {startupRefreshNotice && (
-        <div className={`auth-refresh-notice auth-refresh-notice--${startupRefreshNotice.tone}`}>
-          {startupRefreshNotice.text}
+      {appNotice && (
+        <div className={`auth-refresh-notice auth-refresh-notice--${appNotice.tone}`}>
+          {appNotice.text}
         </div>
       )}
       <Navbar
         currentPage={currentPage}
         onNavigate={handleNavigate}
-        user={authSession.user}
+        user={authSession?.user ?? null}
+        onSignIn={authSession ? undefined : () => handleGuestSignIn()}
         subscription={subscriptionInfo}
         activeSession={navbarActiveSession}
         activeSessionGameTitle={activeSessionGameTitle}
+        activeSessionChoices={navbarSessionChoices}
+        onSelectSession={handleSelectNavbarSession}
         isResumingSession={isResumingNavbarSession}
         isTerminatingSession={isTerminatingNavbarSession}
         onResumeSession={() => {
@@ -4276,7 +5575,7 @@ export function App(): JSX.Element {
                 searchQuery={searchQuery}
                 onSearchChange={setSearchQuery}
                 onPlayGame={handleInitiatePlay}
-                isLoading={settings.controllerMode ? isLoadingStorePanels : isLoadingCatalog}
+                isLoading={settings.controllerMode && !isGuestCatalog ? isLoadingStorePanels : isLoadingCatalog}
                 selectedGameId={selectedGameId}
                 onSelectGame={setSelectedGameId}
                 selectedVariantByGameId={variantByGameId}
@@ -4289,9 +5588,9 @@ export function App(): JSX.Element {
                 onSortChange={setCatalogSelectedSortId}
                 totalCount={catalogTotalCount}
                 supportedCount={catalogSupportedCount}
-                controllerMode={settings.controllerMode}
-                storePanels={storePanels}
-                storeHeroGames={featuredGames}
+                controllerMode={settings.controllerMode && !isGuestCatalog}
+                storePanels={visibleStorePanels}
+                storeHeroGames={visibleFeaturedGames}
                 activeSessionAppIds={activeSessionAppIds}
                 onBuyGame={handleBuyGame}
                 onPreviousControllerPage={() => navigateControllerPage(-1)}
@@ -4311,11 +5610,12 @@ export function App(): JSX.Element {
                 selectedVariantByGameId={variantByGameId}
                 onSelectGameVariant={handleSelectGameVariant}
                 libraryCount={libraryGames.length}
+                genreOptions={libraryGenreOptions}
                 sortOptions={catalogSortOptions.filter((option) => option.id !== "relevance")}
                 selectedSortId={catalogSelectedSortId === "relevance" ? "last_played" : catalogSelectedSortId}
                 onSortChange={setCatalogSelectedSortId}
                 controllerMode={settings.controllerMode}
-                featuredGames={featuredGames.length > 0 ? featuredGames : games}
+                featuredGames={visibleFeaturedGames.length > 0 ? visibleFeaturedGames : filteredGames}
                 activeSessionAppIds={activeSessionAppIds}
                 onBuyGame={handleBuyGame}
                 onPreviousControllerPage={() => navigateControllerPage(-1)}
@@ -4338,12 +5638,19 @@ export function App(): JSX.Element {
             codecTesting={codecTesting}
             onRunCodecTest={runCodecTest}
             onSettingChange={updateSetting}
+            onStreamVolumeChange={handleStreamVolumeChange}
+            onStreamVolumeCommit={flushStreamVolume}
             onClose={handleCloseSettings}
+            onSignOutEverywhere={handleSignOutEverywhere}
+            overrideGames={overrideGames}
           />
         )}
       </SettingsModalHost>
       {logoutConfirmModal}
       {removeAccountConfirmModal}
+      {parentalPrompt && (
+        <ParentalPinPrompt purpose={parentalPrompt.purpose} onResult={handleParentalPromptResult} />
+      )}
       {queueModalGame && streamStatus === "idle" && (
         <QueueServerSelectModal
           game={queueModalGame}
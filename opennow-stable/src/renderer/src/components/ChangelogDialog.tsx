@@ -0,0 +1,65 @@
+import { useEffect, useRef } from "react";
+import type { JSX } from "react";
+import { createPortal } from "react-dom";
+import type { AppUpdateReleaseNote } from "@shared/gfn";
+import { useTranslation } from "../i18n";
+
+/** Release notes for every version between the installed one and the pending update. */
+export function ChangelogDialog({
+  notes,
+  onClose,
+}: {
+  notes: AppUpdateReleaseNote[];
+  onClose: () => void;
+}): JSX.Element | null {
+  const { t } = useTranslation();
+  const closeRef = useRef<HTMLButtonElement>(null);
+
+  useEffect(() => {
+    closeRef.current?.focus();
+  }, []);
+
+  if (typeof document === "undefined") {
+    return null;
+  }
+
+  return createPortal(
+    <div className="logout-confirm" role="dialog" aria-modal="true" aria-label={t("settings.about.changelogTitle")}>
+      <button
+        type="button"
+        className="logout-confirm-backdrop"
+        onClick={onClose}
+        aria-label={t("settings.about.close")}
+      />
+      <div
+        className="logout-confirm-card changelog-card"
+        onKeyDown={(event) => {
+          if (event.key === "Escape") {
+            onClose();
+          }
+        }}
+      >
+        <h3 className="logout-confirm-title">{t("settings.about.changelogTitle")}</h3>
+        <div className="changelog-notes">
+          {notes.map((entry) => (
+            <section key={entry.version} className="changelog-entry">
+              <h4 className="changelog-version">{entry.version}</h4>
+              <p className="logout-confirm-text changelog-text">{entry.notes}</p>
+            </section>
+          ))}
+        </div>
+        <div className="logout-confirm-actions">
+          <button
+            ref={closeRef}
+            type="button"
+            className="logout-confirm-btn logout-confirm-btn-confirm"
+            onClick={onClose}
+          >
+            {t("settings.about.close")}
+          </button>
+        </div>
+      </div>
+    </div>,
+    document.body,
+  );
+}
@@ -0,0 +1,179 @@
+import { X } from "lucide-react";
+import type { JSX } from "react";
+import type { GameStreamOverride, StreamProfileOverride, VideoCodec } from "@shared/gfn";
+import { useTranslation } from "../i18n";
+
+type OverrideField = "resolution" | "fps" | "hdr" | "codec";
+
+/**
+ * Lists the per-game stream overrides and lets the user add one for a library
+ * game. Every field defaults to "use global setting"; main applies the rest
+ * when the game's session is created.
+ */
+export function GameStreamOverrides({
+  overrides,
+  games,
+  resolutions,
+  fpsOptions,
+  codecs,
+  onChange,
+}: {
+  overrides: Record<string, GameStreamOverride>;
+  games: ReadonlyArray<{ id: string; title: string }>;
+  resolutions: ReadonlyArray<{ value: string; label: string }>;
+  fpsOptions: readonly number[];
+  codecs: readonly VideoCodec[];
+  onChange: (next: Record<string, GameStreamOverride>) => void;
+}): JSX.Element {
+  const { t } = useTranslation();
+  const entries = Object.entries(overrides).sort(([, a], [, b]) => a.title.localeCompare(b.title));
+  const addableGames = games.filter((game) => !(game.id in overrides));
+
+  const removeOverride = (gameId: string): void => {
+    const next = { ...overrides };
+    delete next[gameId];
+    onChange(next);
+  };
+
+  const addOverride = (gameId: string): void => {
+    const game = games.find((candidate) => candidate.id === gameId);
+    if (game) {
+      onChange({ ...overrides, [game.id]: { title: game.title } });
+    }
+  };
+
+  return (
+    <div className="game-overrides">
+      {entries.length === 0 && (
+        <span className="settings-subtle-hint">{t("settings.video.gameOverridesEmpty")}</span>
+      )}
+      {entries.map(([gameId, override]) => (
+        <div key={gameId} className="game-overrides-entry">
+          <div className="game-overrides-entry-head">
+            <span className="game-overrides-title" title={override.title}>{override.title}</span>
+            <button
+              type="button"
+              className="game-overrides-remove"
+              onClick={() => removeOverride(gameId)}
+              aria-label={t("settings.video.gameOverrideRemove", { title: override.title })}
+            >
+              <X size={14} />
+            </button>
+          </div>
+          <StreamOverrideFields
+            override={override}
+            resolutions={resolutions}
+            fpsOptions={fpsOptions}
+            codecs={codecs}
+            onChange={(next) => onChange({ ...overrides, [gameId]: { ...next, title: override.title } })}
+          />
+        </div>
+      ))}
+      {addableGames.length > 0 && (
+        <select
+          className="settings-storage-select"
+          aria-label={t("settings.video.gameOverrideAdd")}
+          value=""
+          onChange={(event) => addOverride(event.target.value)}
+        >
+          <option value="">{t("settings.video.gameOverrideAdd")}</option>
+          {addableGames.map((game) => (
+            <option key={game.id} value={game.id}>{game.title}</option>
+          ))}
+        </select>
+      )}
+    </div>
+  );
+}
+
+/**
+ * The resolution, FPS, HDR and codec selects for one override. An empty choice
+ * clears the field so the global setting applies.
+ */
+export function StreamOverrideFields({
+  override,
+  resolutions,
+  fpsOptions,
+  codecs,
+  onChange,
+}: {
+  override: StreamProfileOverride;
+  resolutions: ReadonlyArray<{ value: string; label: string }>;
+  fpsOptions: readonly number[];
+  codecs: readonly VideoCodec[];
+  onChange: (next: StreamProfileOverride) => void;
+}): JSX.Element {
+  const { t } = useTranslation();
+
+  const updateField = (field: OverrideField, rawValue: string): void => {
+    const next: StreamProfileOverride = { ...override };
+    if (!rawValue) {
+      delete next[field];
+    } else if (field === "resolution") {
+      next.resolution = rawValue;
+    } else if (field === "fps") {
+      next.fps = Number.parseInt(rawValue, 10);
+    } else if (field === "hdr") {
+      next.hdr = rawValue === "on";
+    } else {
+      next.codec = rawValue as VideoCodec;
+    }
+    onChange(next);
+  };
+
+  const defaultLabel = t("settings.video.gameOverrideDefault");
+
+  return (
+    <div className="game-overrides-fields">
+      <select
+        className="settings-storage-select"
+        aria-label={t("settings.video.resolution")}
+        value={override.resolution ?? ""}
+        onChange={(event) => updateField("resolution", event.target.value)}
+      >
+        <option value="">{t("settings.video.resolution")}: {defaultLabel}</option>
+        {override.resolution && !resolutions.some((option) => option.value === override.resolution) && (
+          <option value={override.resolution}>{override.resolution}</option>
+        )}
+        {resolutions.map((option) => (
+          <option key={option.value} value={option.value}>{option.label}</option>
+        ))}
+      </select>
+      <select
+        className="settings-storage-select"
+        aria-label={t("settings.video.fps")}
+        value={override.fps?.toString() ?? ""}
+        onChange={(event) => updateField("fps", event.target.value)}
+      >
+        <option value="">{t("settings.video.fps")}: {defaultLabel}</option>
+        {override.fps !== undefined && !fpsOptions.includes(override.fps) && (
+          <option value={override.fps}>{override.fps} FPS</option>
+        )}
+        {fpsOptions.map((fps) => (
+          <option key={fps} value={fps}>{fps} FPS</option>
+        ))}
+      </select>
+      <select
+        className="settings-storage-select"
+        aria-label={t("settings.video.gameOverrideHdr")}
+        value={override.hdr === undefined ? "" : override.hdr ? "on" : "off"}
+        onChange={(event) => updateField("hdr", event.target.value)}
+      >
+        <option value="">{t("settings.video.gameOverrideHdr")}: {defaultLabel}</option>
+        <option value="on">{t("settings.video.gameOverrideHdrOn")}</option>
+        <option value="off">{t("settings.video.gameOverrideHdrOff")}</option>
+      </select>
+      <select
+        className="settings-storage-select"
+        aria-label={t("settings.video.codec")}
+        value={override.codec ?? ""}
+        onChange={(event) => updateField("codec", event.target.value)}
+      >
+        <option value="">{t("settings.video.codec")}: {defaultLabel}</option>
+        {codecs.map((codec) => (
+          <option key={codec} value={codec}>{codec}</option>
+        ))}
+      </select>
+    </div>
+  );
+}
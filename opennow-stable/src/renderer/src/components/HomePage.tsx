@@ -7,6 +7,7 @@ import type { CatalogFilterGroup, CatalogSortOption, GameInfo, GamePanelResult,
 import { getStoreDisplayName, getStoreIconComponent } from "./GameCard";
 import { GameCardListItem, useCatalogCardActionsRef } from "./GameCardListItem";
 import { useTranslation } from "../i18n";
+import { appEvents } from "../lib/appEvents";
 import { controllerButton, readControllerGamepadButtons } from "../utils/controllerGamepad";
 import { pageTransition, panelSpring } from "./MotionProvider";
 
@@ -483,13 +484,17 @@ export const HomePage = memo(function HomePage({
       if (!hasConnectedPad) stopGamepadNavigation();
     };
 
-    window.addEventListener("gamepadconnected", startGamepadNavigation);
-    window.addEventListener("gamepaddisconnected", handleDisconnect);
+    const unsubscribe = appEvents.on("input:device", (event) => {
+      if (event.change === "connected") {
+        startGamepadNavigation();
+      } else {
+        handleDisconnect();
+      }
+    });
     startGamepadNavigation();
 
     return () => {
-      window.removeEventListener("gamepadconnected", startGamepadNavigation);
-      window.removeEventListener("gamepaddisconnected", handleDisconnect);
+      unsubscribe();
       stopGamepadNavigation();
     };
   }, [controllerMode, controllerSearchOpen, onNextControllerPage, onPreviousControllerPage]);
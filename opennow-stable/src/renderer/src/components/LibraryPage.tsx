@@ -1,4 +1,4 @@
-import { Library, Search, Clock, Gamepad2, Loader2, ArrowUpDown, MoreHorizontal, Menu } from "lucide-react";
+import { Library, Search, Clock, Gamepad2, Loader2, ArrowUpDown, MoreHorizontal, Menu, Tag } from "lucide-react";
 import { memo, useEffect, useMemo, useRef, useState } from "react";
 import type { JSX } from "react";
 import { AnimatePresence, m } from "motion/react";
@@ -6,6 +6,8 @@ import type { CatalogSortOption, GameInfo } from "@shared/gfn";
 import { getStoreDisplayName, getStoreIconComponent } from "./GameCard";
 import { GameCardListItem, useCatalogCardActionsRef } from "./GameCardListItem";
 import { useTranslation } from "../i18n";
+import { appEvents } from "../lib/appEvents";
+import { matchesGameGenre } from "../lib/gameCatalog";
 import { formatCatalogLastPlayed } from "../utils/lastPlayedFormat";
 import { controllerButton, readControllerGamepadButtons } from "../utils/controllerGamepad";
 import { pageTransition, panelSpring } from "./MotionProvider";
@@ -40,6 +42,8 @@ export interface LibraryPageProps {
   selectedVariantByGameId: Record<string, string>;
   onSelectGameVariant: (gameId: string, variantId: string) => void;
   libraryCount: number;
+  /** Genres across the whole library, so searching does not hide genre choices. */
+  genreOptions: string[];
   sortOptions: CatalogSortOption[];
   selectedSortId: string;
   onSortChange: (sortId: string) => void;
@@ -195,6 +199,7 @@ export const LibraryPage = memo(function LibraryPage({
   selectedVariantByGameId,
   onSelectGameVariant,
   libraryCount,
+  genreOptions,
   sortOptions,
   selectedSortId,
   onSortChange,
@@ -216,6 +221,7 @@ export const LibraryPage = memo(function LibraryPage({
   const [controllerStoreFilterOpen, setControllerStoreFilterOpen] = useState(false);
   const [controllerSearchOpen, setControllerSearchOpen] = useState(false);
   const [focusedControllerStoreFilterIndex, setFocusedControllerStoreFilterIndex] = useState(0);
+  const [genreFilter, setGenreFilter] = useState("");
   const controllerSearchInputRef = useRef<HTMLInputElement | null>(null);
   const gamepadPreviousButtonsRef = useRef(0);
   const gamepadLastMoveAtRef = useRef(0);
@@ -248,9 +254,15 @@ export const LibraryPage = memo(function LibraryPage({
     () => getControllerStoreFilterItems(games, t("library.allStores")),
     [games, t],
   );
+  const genreFilteredGames = useMemo(
+    () => (genreFilter ? games.filter((game) => matchesGameGenre(game, genreFilter)) : games),
+    [games, genreFilter],
+  );
   const controllerGames = useMemo(
-    () => controllerStoreFilterId === "library" ? games : games.filter((game) => gameMatchesStoreFilter(game, controllerStoreFilterId)),
-    [controllerStoreFilterId, games],
+    () => controllerStoreFilterId === "library"
+      ? genreFilteredGames
+      : genreFilteredGames.filter((game) => gameMatchesStoreFilter(game, controllerStoreFilterId)),
+    [controllerStoreFilterId, genreFilteredGames],
   );
   const controllerFeaturedGames = useMemo(
     () => getControllerFeaturedGames(featuredGames, controllerGames),
@@ -527,19 +539,29 @@ export const LibraryPage = memo(function LibraryPage({
       if (!hasConnectedPad) stopGamepadNavigation();
     };
 
-    window.addEventListener("gamepadconnected", startGamepadNavigation);
-    window.addEventListener("gamepaddisconnected", handleDisconnect);
+    const unsubscribe = appEvents.on("input:device", (event) => {
+      if (event.change === "connected") {
+        startGamepadNavigation();
+      } else {
+        handleDisconnect();
+      }
+    });
     startGamepadNavigation();
 
     return () => {
-      window.removeEventListener("gamepadconnected", startGamepadNavigation);
-      window.removeEventListener("gamepaddisconnected", handleDisconnect);
+      unsubscribe();
       stopGamepadNavigation();
     };
   }, [controllerMode, controllerSearchOpen, onNextControllerPage, onPreviousControllerPage]);
 
+  useEffect(() => {
+    if (genreFilter && !genreOptions.includes(genreFilter)) {
+      setGenreFilter("");
+    }
+  }, [genreFilter, genreOptions]);
+
   const libraryGridItems = useMemo(
-    () => games.map((game) => (
+    () => genreFilteredGames.map((game) => (
       <div key={game.id} className="library-game-wrapper">
         <GameCardListItem
           game={game}
@@ -555,7 +577,7 @@ export const LibraryPage = memo(function LibraryPage({
         )}
       </div>
     )),
-    [catalogActionsRef, games, selectedGameId, selectedVariantByGameId, t],
+    [catalogActionsRef, genreFilteredGames, selectedGameId, selectedVariantByGameId, t],
   );
 
   if (controllerMode) {
@@ -840,6 +862,20 @@ export const LibraryPage = memo(function LibraryPage({
           </select>
         </label>
 
+        {genreOptions.length > 0 && (
+          <label className="library-sort" title={t("library.genreFilter")}>
+            <Tag size={14} />
+            <select value={genreFilter} onChange={(e) => setGenreFilter(e.target.value)} aria-label={t("library.genreFilter")}>
+              <option value="">{t("library.allGenres")}</option>
+              {genreOptions.map((genre) => (
+                <option key={genre} value={genre}>
+                  {genre}
+                </option>
+              ))}
+            </select>
+          </label>
+        )}
+
         <span className="library-count">{t("library.gameCount", { count: libraryCount })}</span>
       </header>
 
@@ -855,11 +891,11 @@ export const LibraryPage = memo(function LibraryPage({
             <h3>{t("library.empty.libraryEmpty")}</h3>
             <p>{t("library.empty.ownedGamesAppearHere")}</p>
           </div>
-        ) : games.length === 0 ? (
+        ) : genreFilteredGames.length === 0 ? (
           <div className="library-empty-state">
             <Search className="library-empty-icon" size={44} />
             <h3>{t("library.empty.noGamesFound")}</h3>
-            <p>{t("library.empty.noGamesMatch", { query: searchQuery })}</p>
+            <p>{t("library.empty.noGamesMatch", { query: [searchQuery.trim(), genreFilter].filter(Boolean).join(" · ") })}</p>
           </div>
         ) : (
           <div className="game-grid">
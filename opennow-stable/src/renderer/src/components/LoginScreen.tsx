@@ -11,14 +11,20 @@ export interface LoginScreenProps {
   selectedProviderId: string;
   onProviderChange: (id: string) => void;
   onLogin: () => void;
+  onCancelLogin: () => void;
+  /** Skip sign-in and browse the public catalog */
+  onBrowseAsGuest: () => void;
   onQrLogin: () => void;
   onCancelQrLogin: () => void;
   isLoading: boolean;
   error: string | null;
+  /** Why sign-in was asked for, e.g. a guest trying to launch a game */
+  prompt?: string | null;
   isInitializing?: boolean;
   statusMessage?: string;
   qrLoginChallenge?: AuthDeviceLoginChallenge | null;
   isQrLoginPending?: boolean;
+  isBrowserLoginPending?: boolean;
 }
 
 export function LoginScreen({
@@ -26,14 +32,18 @@ export function LoginScreen({
   selectedProviderId,
   onProviderChange,
   onLogin,
+  onCancelLogin,
+  onBrowseAsGuest,
   onQrLogin,
   onCancelQrLogin,
   isLoading,
   error,
+  prompt = null,
   isInitializing = false,
   statusMessage,
   qrLoginChallenge,
   isQrLoginPending = false,
+  isBrowserLoginPending = false,
 }: LoginScreenProps): JSX.Element {
   const { t } = useTranslation();
   const [isDropdownOpen, setIsDropdownOpen] = useState(false);
@@ -125,6 +135,13 @@ export function LoginScreen({
             </div>
           )}
 
+          {!isInitializing && prompt && (
+            <div className="login-status" role="status" aria-live="polite">
+              <span className="login-status-dot" />
+              {prompt}
+            </div>
+          )}
+
           {isInitializing && statusMessage && (
             <div className="login-status" role="status" aria-live="polite">
               <span className="login-status-dot" />
@@ -200,6 +217,20 @@ export function LoginScreen({
             </div>
           )}
 
+          {isBrowserLoginPending && (
+            <div className="login-status login-status--browser" role="status" aria-live="polite">
+              <span className="login-status-dot" />
+              <span>{t("auth.browser.waiting")}</span>
+              <button
+                className="login-secondary-button"
+                onClick={onCancelLogin}
+                type="button"
+              >
+                {t("auth.actions.cancelBrowserLogin")}
+              </button>
+            </div>
+          )}
+
           <div className="login-actions">
             <button
               className={`login-button ${isLoading || isInitializing ? "loading" : ""}`}
@@ -228,6 +259,14 @@ export function LoginScreen({
               <QrCode size={18} />
               <span>{t("auth.actions.signInWithQr")}</span>
             </button>
+            <button
+              className="login-link-button"
+              onClick={onBrowseAsGuest}
+              disabled={isLoading || isInitializing || isQrLoginActive}
+              type="button"
+            >
+              {t("auth.actions.browseAsGuest")}
+            </button>
           </div>
         </div>
 
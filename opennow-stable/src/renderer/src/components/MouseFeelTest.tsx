@@ -0,0 +1,150 @@
+import { useEffect, useRef, useState } from "react";
+import type { JSX } from "react";
+import { MouseDeltaFilter, applyMouseFeel, requestPointerLockMovement, type PointerMovementMode } from "../gfn/mouseFeel";
+import { quantizeMouseDeltaWithResidual } from "../gfn/webrtcClient";
+import { useTranslation } from "../i18n";
+
+interface MouseFeelSample {
+  rawX: number;
+  rawY: number;
+  sentX: number;
+  sentY: number;
+  /** Position of the probe dot as a percentage of the pad, driven by the sent deltas. */
+  dotX: number;
+  dotY: number;
+}
+
+const IDLE_SAMPLE: MouseFeelSample = { rawX: 0, rawY: 0, sentX: 0, sentY: 0, dotX: 50, dotY: 50 };
+
+/**
+ * Locks the pointer inside a small pad and runs its movement through the same
+ * filter, sensitivity and acceleration steps the stream client uses, so the
+ * numbers shown are the deltas a game would receive.
+ */
+export function MouseFeelTest({
+  sensitivity,
+  accelerationPercent,
+  rawInput,
+}: {
+  sensitivity: number;
+  accelerationPercent: number;
+  rawInput: boolean;
+}): JSX.Element {
+  const { t } = useTranslation();
+  const padRef = useRef<HTMLDivElement | null>(null);
+  const feelRef = useRef({ sensitivity, accelerationPercent });
+  const [mode, setMode] = useState<PointerMovementMode | null>(null);
+  const [locked, setLocked] = useState(false);
+  const [sample, setSample] = useState<MouseFeelSample>(IDLE_SAMPLE);
+
+  useEffect(() => {
+    feelRef.current = { sensitivity, accelerationPercent };
+  }, [sensitivity, accelerationPercent]);
+
+  useEffect(() => {
+    const pad = padRef.current;
+    if (!pad) return;
+
+    const filter = new MouseDeltaFilter();
+    let residualX = 0;
+    let residualY = 0;
+    let next = IDLE_SAMPLE;
+    let frame: number | null = null;
+
+    const publish = (): void => {
+      frame = null;
+      setSample(next);
+    };
+
+    const handleLockChange = (): void => {
+      const active = document.pointerLockElement === pad;
+      setLocked(active);
+      filter.reset();
+      residualX = 0;
+      residualY = 0;
+      if (!active) {
+        setMode(null);
+      }
+    };
+
+    const handleMove = (event: MouseEvent): void => {
+      if (document.pointerLockElement !== pad) return;
+      if (!filter.update(event.movementX, event.movementY, event.timeStamp)) return;
+
+      const { sensitivity: scale, accelerationPercent: accel } = feelRef.current;
+      const adjusted = applyMouseFeel(filter.getX(), filter.getY(), scale, accel);
+      const x = quantizeMouseDeltaWithResidual(residualX + adjusted.dx);
+      const y = quantizeMouseDeltaWithResidual(residualY + adjusted.dy);
+      residualX = x.residual;
+      residualY = y.residual;
+
+      const rect = pad.getBoundingClientRect();
+      next = {
+        rawX: event.movementX,
+        rawY: event.movementY,
+        sentX: x.send,
+        sentY: y.send,
+        dotX: Math.max(0, Math.min(100, next.dotX + (x.send / Math.max(1, rect.width)) * 100)),
+        dotY: Math.max(0, Math.min(100, next.dotY + (y.send / Math.max(1, rect.height)) * 100)),
+      };
+      if (frame === null) {
+        frame = window.requestAnimationFrame(publish);
+      }
+    };
+
+    document.addEventListener("pointerlockchange", handleLockChange);
+    pad.addEventListener("mousemove", handleMove);
+    return () => {
+      document.removeEventListener("pointerlockchange", handleLockChange);
+      pad.removeEventListener("mousemove", handleMove);
+      if (frame !== null) {
+        window.cancelAnimationFrame(frame);
+      }
+      if (document.pointerLockElement === pad) {
+        document.exitPointerLock();
+      }
+    };
+  }, []);
+
+  const handleClick = (): void => {
+    const pad = padRef.current;
+    if (!pad || document.pointerLockElement === pad) return;
+    void requestPointerLockMovement(pad, rawInput)
+      .then(setMode)
+      .catch(() => setMode(null));
+  };
+
+  const modeLabel = mode === "raw"
+    ? t("settings.input.mouseFeelTest.modeRaw")
+    : mode === "accelerated"
+      ? t("settings.input.mouseFeelTest.modeAccelerated")
+      : null;
+
+  return (
+    <div className="mouse-feel-test">
+      <div
+        ref={padRef}
+        className={`mouse-feel-test-pad${locked ? " mouse-feel-test-pad--locked" : ""}`}
+        role="button"
+        tabIndex={0}
+        aria-label={t("settings.input.mouseFeelTest.title")}
+        onClick={handleClick}
+      >
+        <span className="mouse-feel-test-dot" style={{ left: `${sample.dotX}%`, top: `${sample.dotY}%` }} />
+        {!locked && <span className="mouse-feel-test-prompt">{t("settings.input.mouseFeelTest.prompt")}</span>}
+      </div>
+      <div className="mouse-feel-test-readout">
+        <span>{t("settings.input.mouseFeelTest.raw", { x: sample.rawX, y: sample.rawY })}</span>
+        <span>{t("settings.input.mouseFeelTest.sent", { x: sample.sentX, y: sample.sentY })}</span>
+        {modeLabel && (
+          <span className={`settings-value-badge${mode === "accelerated" ? " mouse-feel-test-mode--accelerated" : ""}`}>
+            {modeLabel}
+          </span>
+        )}
+      </div>
+      {mode === "accelerated" && rawInput && (
+        <span className="settings-subtle-hint">{t("settings.input.mouseFeelTest.rawUnavailable")}</span>
+      )}
+    </div>
+  );
+}
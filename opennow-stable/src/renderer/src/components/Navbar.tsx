@@ -4,6 +4,8 @@ import { useEffect, useRef, useState, type JSX } from "react";
 import { createPortal } from "react-dom";
 import { useTranslation } from "../i18n";
 import { OpenNowLogoMark } from "./OpenNowLogoMark";
+import { isSessionReadyForConnect } from "../lib/sessionState";
+import { classifyMembershipTier } from "../utils/streamQualityPresets";
 
 interface NavbarProps {
   currentPage: "home" | "library" | "settings";
@@ -12,6 +14,9 @@ interface NavbarProps {
   subscription: SubscriptionInfo | null;
   activeSession: ActiveSessionInfo | null;
   activeSessionGameTitle: string | null;
+  /** Every queued or ready session on the account, offered as a switcher when there is more than one */
+  activeSessionChoices?: NavbarSessionChoice[];
+  onSelectSession?: (sessionId: string) => void;
   isResumingSession: boolean;
   isTerminatingSession: boolean;
   onResumeSession: () => void;
@@ -21,16 +26,22 @@ interface NavbarProps {
   onRemoveAccount: (userId: string) => void;
   onAddAccount: () => void;
   onLogoutAll: () => void;
+  /** Shown for guests browsing without an account */
+  onSignIn?: () => void;
   controllerMode?: boolean;
 }
 
+export interface NavbarSessionChoice {
+  sessionId: string;
+  title: string | null;
+  queued: boolean;
+}
+
 type NavbarModalType = "time" | "storage" | null;
 
 function getTierDisplay(tier: string): { labelKey: string; className: string } {
-  const t = tier.toUpperCase();
-  if (t === "ULTIMATE") return { labelKey: "app.labels.ultimate", className: "tier-ultimate" };
-  if (t === "PRIORITY" || t === "PERFORMANCE") return { labelKey: "app.labels.priority", className: "tier-priority" };
-  return { labelKey: "app.labels.free", className: "tier-free" };
+  const tierClass = classifyMembershipTier(tier) ?? "free";
+  return { labelKey: `app.labels.${tierClass}`, className: `tier-${tierClass}` };
 }
 
 export function Navbar({
@@ -40,6 +51,8 @@ export function Navbar({
   subscription,
   activeSession,
   activeSessionGameTitle,
+  activeSessionChoices = [],
+  onSelectSession,
   isResumingSession,
   isTerminatingSession,
   onResumeSession,
@@ -49,6 +62,7 @@ export function Navbar({
   onRemoveAccount,
   onAddAccount,
   onLogoutAll,
+  onSignIn,
   controllerMode = false,
 }: NavbarProps): JSX.Element {
   const { t } = useTranslation();
@@ -147,6 +161,7 @@ export function Navbar({
   const firstEntitlementStart = formatDateTime(subscription?.firstEntitlementStartDateTime);
   const modalTitle = modalType === "time" ? t("navbar.playtimeDetails") : t("navbar.storageDetails");
   const activeSessionTitle = activeSessionGameTitle?.trim() || null;
+  const activeSessionQueued = activeSession ? !isSessionReadyForConnect(activeSession.status) : false;
   const activeUserId = user?.userId ?? null;
 
   useEffect(() => {
@@ -321,18 +336,39 @@ export function Navbar({
       <div className="navbar-right">
         {activeSession && !controllerMode && (
           <div className="navbar-session-actions">
+            {activeSessionChoices.length > 1 && onSelectSession && (
+              <select
+                className="navbar-session-switch"
+                aria-label={t("session.switchActiveCloudSession")}
+                title={t("session.switchActiveCloudSession")}
+                value={activeSession.sessionId}
+                onChange={(e) => onSelectSession(e.target.value)}
+                disabled={isResumingSession || isTerminatingSession}
+              >
+                {activeSessionChoices.map((choice) => {
+                  const title = choice.title ?? t("session.thisSession");
+                  return (
+                    <option key={choice.sessionId} value={choice.sessionId}>
+                      {choice.queued ? t("session.queuedSessionOption", { title }) : title}
+                    </option>
+                  );
+                })}
+              </select>
+            )}
             <button
               type="button"
               className={`navbar-session-resume${isResumingSession ? " is-loading" : ""}`}
               title={
-                activeSession.serverIp
-                  ? activeSessionTitle
-                    ? t("session.resumeActiveCloudSessionTitle", { title: activeSessionTitle })
-                    : t("session.resumeActiveCloudSession")
-                  : t("session.activeSessionMissingServerAddress")
+                activeSessionQueued
+                  ? t("session.activeSessionQueued")
+                  : activeSession.serverIp
+                    ? activeSessionTitle
+                      ? t("session.resumeActiveCloudSessionTitle", { title: activeSessionTitle })
+                      : t("session.resumeActiveCloudSession")
+                    : t("session.activeSessionMissingServerAddress")
               }
               onClick={onResumeSession}
-              disabled={isResumingSession || isTerminatingSession || !activeSession.serverIp}
+              disabled={isResumingSession || isTerminatingSession || activeSessionQueued || !activeSession.serverIp}
             >
               {isResumingSession ? <Loader2 size={14} className="navbar-session-resume-spin" /> : <PlayCircle size={14} />}
               <span className="navbar-session-resume-text">{t("app.actions.resume")}</span>
@@ -518,6 +554,11 @@ export function Navbar({
           <div className="navbar-guest">
             <User size={14} />
             <span>{t("auth.accounts.guest")}</span>
+            {onSignIn && (
+              <button type="button" className="navbar-guest-signin" onClick={onSignIn}>
+                {t("auth.actions.signIn")}
+              </button>
+            )}
           </div>
         )}
       </div>
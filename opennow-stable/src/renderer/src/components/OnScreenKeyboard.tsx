@@ -0,0 +1,149 @@
+import { useCallback, useEffect, useRef, useState, type JSX } from "react";
+
+import {
+  moveOnScreenKeyboardFocus,
+  ON_SCREEN_KEYBOARD_ROWS,
+  onScreenKeyboardText,
+  OnScreenKeyboardGamepad,
+  type OnScreenKeyboardAction,
+  type OnScreenKeyboardCode,
+  type OnScreenKeyboardFocus,
+  type OnScreenKeyboardKey,
+} from "../gfn/onScreenKeyboard";
+import { useTranslation } from "../i18n";
+
+interface OnScreenKeyboardProps {
+  onSendText: (text: string) => void;
+  onSendKey: (code: OnScreenKeyboardCode) => void;
+  onClose: () => void;
+}
+
+/** Every connected pad drives the keyboard; whoever picks it up can type. */
+function readPads(): { buttons: boolean[]; axes: number[] } {
+  const buttons: boolean[] = [];
+  let axes: number[] = [0, 0];
+  for (const gamepad of navigator.getGamepads?.() ?? []) {
+    if (!gamepad?.connected) continue;
+    gamepad.buttons.forEach((button, index) => {
+      buttons[index] = Boolean(buttons[index] || button.pressed);
+    });
+    if (Math.hypot(gamepad.axes[0] ?? 0, gamepad.axes[1] ?? 0) > Math.hypot(axes[0], axes[1])) {
+      axes = [gamepad.axes[0] ?? 0, gamepad.axes[1] ?? 0];
+    }
+  }
+  return { buttons, axes };
+}
+
+export function OnScreenKeyboard({ onSendText, onSendKey, onClose }: OnScreenKeyboardProps): JSX.Element {
+  const { t } = useTranslation();
+  const [focus, setFocus] = useState<OnScreenKeyboardFocus>({ row: 1, col: 1 });
+  const [shifted, setShifted] = useState(false);
+  const focusRef = useRef(focus);
+  const shiftedRef = useRef(shifted);
+  focusRef.current = focus;
+  shiftedRef.current = shifted;
+
+  const pressKey = useCallback((key: OnScreenKeyboardKey): void => {
+    switch (key.kind) {
+      case "char":
+        onSendText(onScreenKeyboardText(key.value, shiftedRef.current));
+        setShifted(false);
+        return;
+      case "space":
+        onSendText(" ");
+        return;
+      case "code":
+        onSendKey(key.code);
+        return;
+      case "shift":
+        setShifted((prev) => !prev);
+        return;
+      case "close":
+        onClose();
+    }
+  }, [onClose, onSendKey, onSendText]);
+
+  const applyAction = useCallback((action: OnScreenKeyboardAction): void => {
+    switch (action.type) {
+      case "move":
+        setFocus((prev) => moveOnScreenKeyboardFocus(prev, action.direction));
+        return;
+      case "press": {
+        const { row, col } = focusRef.current;
+        pressKey(ON_SCREEN_KEYBOARD_ROWS[row][col]);
+        return;
+      }
+      case "backspace":
+        onSendKey("Backspace");
+        return;
+      case "space":
+        onSendText(" ");
+        return;
+      case "shift":
+        setShifted((prev) => !prev);
+        return;
+      case "enter":
+        onSendKey("Enter");
+        return;
+      case "close":
+        onClose();
+    }
+  }, [onClose, onSendKey, onSendText, pressKey]);
+
+  useEffect(() => {
+    const gamepad = new OnScreenKeyboardGamepad();
+    let frame = 0;
+    const poll = (): void => {
+      for (const action of gamepad.update(readPads(), performance.now())) {
+        applyAction(action);
+      }
+      frame = window.requestAnimationFrame(poll);
+    };
+    frame = window.requestAnimationFrame(poll);
+    return () => window.cancelAnimationFrame(frame);
+  }, [applyAction]);
+
+  const keyLabel = (key: OnScreenKeyboardKey): string => {
+    switch (key.kind) {
+      case "char":
+        return onScreenKeyboardText(key.value, shifted);
+      case "code":
+        return t(`stream.onScreenKeyboard.keys.${key.code.toLowerCase()}`);
+      case "space":
+        return t("stream.onScreenKeyboard.keys.space");
+      case "shift":
+        return t("stream.onScreenKeyboard.keys.shift");
+      case "close":
+        return t("stream.onScreenKeyboard.keys.close");
+    }
+  };
+
+  return (
+    <div className="sv-osk" role="dialog" aria-label={t("stream.onScreenKeyboard.label")}>
+      {ON_SCREEN_KEYBOARD_ROWS.map((row, rowIndex) => (
+        <div key={rowIndex} className="sv-osk-row">
+          {row.map((key, colIndex) => {
+            const focused = focus.row === rowIndex && focus.col === colIndex;
+            const active = key.kind === "shift" && shifted;
+            return (
+              <button
+                key={colIndex}
+                type="button"
+                tabIndex={-1}
+                className={`sv-osk-key sv-osk-key--${key.kind}${focused ? " is-focused" : ""}${active ? " is-active" : ""}`}
+                onMouseDown={(e) => e.preventDefault()}
+                onClick={() => {
+                  setFocus({ row: rowIndex, col: colIndex });
+                  pressKey(key);
+                }}
+              >
+                {keyLabel(key)}
+              </button>
+            );
+          })}
+        </div>
+      ))}
+      <div className="sv-osk-hint">{t("stream.onScreenKeyboard.hint")}</div>
+    </div>
+  );
+}
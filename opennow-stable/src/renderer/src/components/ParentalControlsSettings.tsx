@@ -0,0 +1,164 @@
+import { Lock } from "lucide-react";
+import { useState } from "react";
+import type { JSX } from "react";
+import type { Settings } from "@shared/gfn";
+import { PARENTAL_MAX_AGE_OPTIONS, isValidParentalPin } from "@shared/parentalControls";
+import { useTranslation } from "../i18n";
+
+type ParentalSettingKey = "parentalPinSet" | "parentalLockLaunch" | "parentalLockSettings" | "parentalMaxAge";
+
+/**
+ * PIN management and the launch/settings locks. The hash itself stays in main;
+ * this only mirrors whether a PIN is set into local settings state.
+ */
+export function ParentalControlsSettings({
+  settings,
+  onChange,
+}: {
+  settings: Pick<Settings, ParentalSettingKey>;
+  onChange: <K extends ParentalSettingKey>(key: K, value: Settings[K]) => void;
+}): JSX.Element {
+  const { t } = useTranslation();
+  const [currentPin, setCurrentPin] = useState("");
+  const [nextPin, setNextPin] = useState("");
+  const [message, setMessage] = useState<{ tone: "error" | "success"; text: string } | null>(null);
+  const [busy, setBusy] = useState(false);
+  const hasPin = settings.parentalPinSet;
+
+  // While a PIN is set, main only accepts parental changes together with it. Without
+  // one typed here, the settings update asks for it.
+  const changeLocked = async <K extends Exclude<ParentalSettingKey, "parentalPinSet">>(
+    key: K,
+    value: Settings[K],
+  ): Promise<void> => {
+    if (hasPin && currentPin) {
+      const saved = await window.openNow.setSetting(key, value, currentPin).then(() => true, () => false);
+      if (!saved) {
+        setMessage({ tone: "error", text: t("settings.parental.pinWrong") });
+        return;
+      }
+    }
+    onChange(key, value);
+  };
+
+  const submit = async (clear: boolean): Promise<void> => {
+    if (!clear && !isValidParentalPin(nextPin)) {
+      setMessage({ tone: "error", text: t("settings.parental.pinInvalid") });
+      return;
+    }
+    setBusy(true);
+    try {
+      const pinSet = await window.openNow.setParentalPin(hasPin ? currentPin : null, clear ? null : nextPin);
+      if (pinSet === null) {
+        setMessage({ tone: "error", text: t("settings.parental.pinWrong") });
+        return;
+      }
+      onChange("parentalPinSet", pinSet);
+      setCurrentPin("");
+      setNextPin("");
+      setMessage({ tone: "success", text: t(clear ? "settings.parental.pinRemoved" : "settings.parental.pinSaved") });
+    } catch (error) {
+      console.warn("Failed to update parental PIN:", error);
+      setMessage({ tone: "error", text: t("settings.parental.pinSaveFailed") });
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <>
+      <div className="settings-row settings-row--column">
+        <label className="settings-label settings-label--with-icon">
+          <Lock size={15} className="settings-label-icon" />
+          {hasPin ? t("settings.parental.pinSet") : t("settings.parental.pinNotSet")}
+        </label>
+        <div className="settings-input-group">
+          {hasPin && (
+            <input
+              type="password"
+              inputMode="numeric"
+              autoComplete="off"
+              className="settings-text-input settings-text-input--narrow"
+              placeholder={t("settings.parental.currentPin")}
+              aria-label={t("settings.parental.currentPin")}
+              value={currentPin}
+              onChange={(event) => setCurrentPin(event.target.value)}
+            />
+          )}
+          <input
+            type="password"
+            inputMode="numeric"
+            autoComplete="off"
+            className="settings-text-input settings-text-input--narrow"
+            placeholder={t("settings.parental.newPin")}
+            aria-label={t("settings.parental.newPin")}
+            value={nextPin}
+            onChange={(event) => setNextPin(event.target.value)}
+          />
+          <button type="button" className="settings-chip" disabled={busy} onClick={() => void submit(false)}>
+            <span>{hasPin ? t("settings.parental.changePin") : t("settings.parental.setPin")}</span>
+          </button>
+          {hasPin && (
+            <button type="button" className="settings-chip" disabled={busy} onClick={() => void submit(true)}>
+              <span>{t("settings.parental.removePin")}</span>
+            </button>
+          )}
+        </div>
+        {message && (
+          <span className={message.tone === "error" ? "settings-input-hint" : "settings-subtle-hint"}>{message.text}</span>
+        )}
+        <span className="settings-subtle-hint">{t("settings.parental.pinHint")}</span>
+      </div>
+
+      <div className="settings-row">
+        <label className="settings-label">{t("settings.parental.lockLaunch")}</label>
+        <label className="settings-toggle">
+          <input
+            type="checkbox"
+            checked={settings.parentalLockLaunch}
+            disabled={!hasPin}
+            onChange={(event) => void changeLocked("parentalLockLaunch", event.target.checked)}
+          />
+          <span className="settings-toggle-track" />
+        </label>
+      </div>
+
+      <div className="settings-row">
+        <label className="settings-label">{t("settings.parental.lockSettings")}</label>
+        <label className="settings-toggle">
+          <input
+            type="checkbox"
+            checked={settings.parentalLockSettings}
+            disabled={!hasPin}
+            onChange={(event) => void changeLocked("parentalLockSettings", event.target.checked)}
+          />
+          <span className="settings-toggle-track" />
+        </label>
+      </div>
+
+      <div className="settings-row settings-row--column">
+        <label className="settings-label">{t("settings.parental.maxAge")}</label>
+        <div className="settings-chip-row">
+          <button
+            type="button"
+            className={`settings-chip ${settings.parentalMaxAge === null ? "active" : ""}`}
+            onClick={() => void changeLocked("parentalMaxAge", null)}
+          >
+            <span>{t("settings.parental.maxAgeOff")}</span>
+          </button>
+          {PARENTAL_MAX_AGE_OPTIONS.map((age) => (
+            <button
+              key={age}
+              type="button"
+              className={`settings-chip ${settings.parentalMaxAge === age ? "active" : ""}`}
+              onClick={() => void changeLocked("parentalMaxAge", age)}
+            >
+              <span>{t("settings.parental.maxAgeValue", { age })}</span>
+            </button>
+          ))}
+        </div>
+        <span className="settings-subtle-hint">{t("settings.parental.maxAgeHint")}</span>
+      </div>
+    </>
+  );
+}
@@ -0,0 +1,99 @@
+import { useEffect, useRef, useState } from "react";
+import type { FormEvent, JSX } from "react";
+import { createPortal } from "react-dom";
+import { useTranslation } from "../i18n";
+
+export type ParentalPinPurpose = "launch" | "settings" | "change";
+
+/** Asks for the parental PIN and hands it back once main has checked it; null when cancelled. */
+export function ParentalPinPrompt({
+  purpose,
+  onResult,
+}: {
+  purpose: ParentalPinPurpose;
+  onResult: (pin: string | null) => void;
+}): JSX.Element | null {
+  const { t } = useTranslation();
+  const [pin, setPin] = useState("");
+  const [checking, setChecking] = useState(false);
+  const [rejected, setRejected] = useState(false);
+  const inputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    inputRef.current?.focus();
+  }, []);
+
+  const handleSubmit = async (event: FormEvent): Promise<void> => {
+    event.preventDefault();
+    if (!pin || checking) {
+      return;
+    }
+    setChecking(true);
+    const unlocked = await window.openNow.verifyParentalPin(pin).catch(() => false);
+    setChecking(false);
+    if (unlocked) {
+      onResult(pin);
+      return;
+    }
+    setRejected(true);
+    setPin("");
+    inputRef.current?.focus();
+  };
+
+  if (typeof document === "undefined") {
+    return null;
+  }
+
+  return createPortal(
+    <div className="logout-confirm" role="dialog" aria-modal="true" aria-label={t("parentalPin.title")}>
+      <button
+        type="button"
+        className="logout-confirm-backdrop"
+        onClick={() => onResult(null)}
+        aria-label={t("app.actions.cancel")}
+      />
+      <form
+        className="logout-confirm-card"
+        onSubmit={(event) => void handleSubmit(event)}
+        onKeyDown={(event) => {
+          if (event.key === "Escape") {
+            onResult(null);
+          }
+        }}
+      >
+        <h3 className="logout-confirm-title">{t("parentalPin.title")}</h3>
+        <p className="logout-confirm-text">
+          {t(`parentalPin.${purpose}Description`)}
+        </p>
+        <input
+          ref={inputRef}
+          type="password"
+          inputMode="numeric"
+          autoComplete="off"
+          className="settings-text-input parental-pin-input"
+          aria-label={t("parentalPin.title")}
+          value={pin}
+          onChange={(event) => setPin(event.target.value)}
+        />
+        {rejected && <p className="settings-input-hint">{t("parentalPin.rejected")}</p>}
+        <div className="logout-confirm-actions">
+          <button
+            type="button"
+            className="logout-confirm-btn logout-confirm-btn-cancel"
+            onClick={() => onResult(null)}
+          >
+            {t("app.actions.cancel")}
+          </button>
+          <button
+            type="submit"
+            className="logout-confirm-btn logout-confirm-btn-confirm"
+            disabled={!pin || checking}
+          >
+            {t("parentalPin.unlock")}
+          </button>
+        </div>
+      </form>
+    </div>,
+    document.body,
+  );
+}
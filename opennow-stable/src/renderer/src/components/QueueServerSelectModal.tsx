@@ -5,6 +5,7 @@ import {
   loadStoredPrintedWastePingResults,
   saveStoredPrintedWastePingResults,
 } from "../utils/pingResultsStorage";
+import { loadServerQualityHistory, serverQualityKey, serverQualityPenalty } from "../lib/serverQuality";
 
 // ── Constants / helpers ───────────────────────────────────────────────────────
 
@@ -63,6 +64,9 @@ const REGION_ORDER = ["US", "CA", "EU", "JP", "KR", "THAI", "MY"];
 const QUEUE_REFRESH_INTERVAL_MS = 2 * 60 * 1000;
 const AUTO_PING_WEIGHT = 0.75;
 const AUTO_QUEUE_WEIGHT = 0.25;
+// Added on top of the ping/queue score, so a zone with a full penalty from
+// past sessions loses to one with twice its ping.
+const AUTO_HISTORY_WEIGHT = 0.5;
 
 // ── Types ─────────────────────────────────────────────────────────────────────
 
@@ -106,7 +110,7 @@ export function QueueServerSelectModal({ game, initialQueueData = null, onConfir
         const data = await window.openNow.fetchPrintedWasteQueue();
         if (!cancelled) setQueueData(data);
       } catch {
-        if (!cancelled) setFetchError("Could not load queue data. You can still launch with default routing.");
+        if (!cancelled) setFetchError("Queue info unavailable. You can still launch with default routing.");
       } finally {
         if (!cancelled) setQueueLoading(false);
       }
@@ -282,7 +286,10 @@ export function QueueServerSelectModal({ game, initialQueueData = null, onConfir
 
   // ── Recommendations ───────────────────────────────────────────────────────
 
-  // Auto: weighted lowest score with strict ping preference (75% ping + 25% queue).
+  const qualityHistory = useMemo(() => loadServerQualityHistory(), []);
+
+  // Auto: weighted lowest score with strict ping preference (75% ping + 25% queue),
+  // plus a penalty for zones that streamed badly in past sessions.
   // Falls back to queue-only
   // when ping data isn't in yet.
   const autoZone = useMemo<ZoneInfo | null>(() => {
@@ -291,15 +298,20 @@ export function QueueServerSelectModal({ game, initialQueueData = null, onConfir
     const pool     = withPing.length > 0 ? withPing : zones;
     const maxPing  = Math.max(...pool.map((z) => z.pingMs ?? 999), 1);
     const maxQueue = Math.max(...pool.map((z) => z.queuePosition), 1);
+    const scoreOf = (z: ZoneInfo): number => {
+      const key = serverQualityKey(z.routingUrl);
+      const penalty = key ? serverQualityPenalty(qualityHistory[key]) : 0;
+      return ((z.pingMs ?? maxPing) / maxPing) * AUTO_PING_WEIGHT + (z.queuePosition / maxQueue) * AUTO_QUEUE_WEIGHT + penalty * AUTO_HISTORY_WEIGHT;
+    };
     return pool.reduce((best, z) => {
-      const score = ((z.pingMs ?? maxPing) / maxPing) * AUTO_PING_WEIGHT + (z.queuePosition / maxQueue) * AUTO_QUEUE_WEIGHT;
-      const bScore = ((best.pingMs ?? maxPing) / maxPing) * AUTO_PING_WEIGHT + (best.queuePosition / maxQueue) * AUTO_QUEUE_WEIGHT;
+      const score = scoreOf(z);
+      const bScore = scoreOf(best);
       if (score === bScore && z.pingMs !== null && best.pingMs !== null) {
         return z.pingMs < best.pingMs ? z : best;
       }
       return score < bScore ? z : best;
     }, pool[0]!);
-  }, [zones]);
+  }, [zones, qualityHistory]);
 
   // Closest: lowest latency. Only available after pings complete.
   const closestZone = useMemo<ZoneInfo | null>(() => {
@@ -0,0 +1,57 @@
+import { Loader, Route } from "lucide-react";
+import type { JSX } from "react";
+import type { RouteTraceResult } from "@shared/gfn";
+import { useTranslation } from "../i18n";
+import { findLatencyJumpHop, routeHopAverageMs } from "../lib/routeTrace";
+import { getPacketLossColor, getRttColor } from "../utils/streamDiagnosticsFormat";
+
+export interface RouteTraceState {
+  result: RouteTraceResult | null;
+  running: boolean;
+  onRun: () => void;
+}
+
+export function RouteTracePanel({ result, running, onRun }: RouteTraceState): JSX.Element {
+  const { t } = useTranslation();
+  const averages = result?.hops.map(routeHopAverageMs) ?? [];
+  const maxAverageMs = Math.max(1, ...averages.map((value) => value ?? 0));
+  const jumpHop = result ? findLatencyJumpHop(result.hops) : null;
+
+  return (
+    <div className="sv-route">
+      <button type="button" className="sv-stats-advanced-toggle sv-route-run" onClick={onRun} disabled={running}>
+        {running ? <Loader size={11} className="sv-route-spin" /> : <Route size={11} />}
+        {running ? t("stream.routeTrace.running") : result ? t("stream.routeTrace.rerun") : t("stream.routeTrace.run")}
+      </button>
+      {result?.error && <p className="sv-stats-foot">{t("stream.routeTrace.failed", { error: result.error })}</p>}
+      {result && result.hops.length > 0 && (
+        <ol className="sv-route-hops" aria-label={t("stream.routeTrace.label", { target: result.address ?? result.target })}>
+          {result.hops.map((hop, index) => {
+            const averageMs = averages[index] ?? null;
+            return (
+              <li key={hop.hop} className={`sv-route-hop${hop.hop === jumpHop ? " sv-route-hop--jump" : ""}`}>
+                <span className="sv-route-hop-num">{hop.hop}</span>
+                <span className="sv-route-hop-addr" title={hop.address ?? undefined}>{hop.address ?? "*"}</span>
+                <span className="sv-route-hop-bar" aria-hidden>
+                  <span
+                    style={{
+                      width: `${averageMs === null ? 0 : Math.max(4, (averageMs / maxAverageMs) * 100)}%`,
+                      background: averageMs === null ? undefined : getRttColor(averageMs),
+                    }}
+                  />
+                </span>
+                <span className="sv-route-hop-ms" style={{ color: averageMs === null ? undefined : getRttColor(averageMs) }}>
+                  {averageMs === null ? "--" : `${averageMs.toFixed(0)}ms`}
+                </span>
+                <span className="sv-route-hop-loss" style={{ color: getPacketLossColor(hop.lossPercent) }}>
+                  {hop.lossPercent}%
+                </span>
+              </li>
+            );
+          })}
+        </ol>
+      )}
+      {jumpHop !== null && <p className="sv-stats-foot">{t("stream.routeTrace.jump", { hop: jumpHop })}</p>}
+    </div>
+  );
+}
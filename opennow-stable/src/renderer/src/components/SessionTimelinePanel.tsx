@@ -0,0 +1,99 @@
+import { useState, useSyncExternalStore } from "react";
+import type { JSX } from "react";
+import type { SessionTimeline, SessionTimelineEvent, SessionTimelineEventKind } from "@shared/gfn";
+import { formatTimelineOffset } from "@shared/sessionTimeline";
+import { useTranslation } from "../i18n";
+
+type TranslateFunction = typeof import("../i18n").t;
+
+export interface SessionTimelineSource {
+  getSnapshot: () => SessionTimeline | null;
+  subscribe: (listener: () => void) => () => void;
+}
+
+function getEventColor(kind: SessionTimelineEventKind): string {
+  switch (kind) {
+    case "reconnect":
+    case "degraded":
+      return "var(--error)";
+    case "connection":
+    case "decoder":
+      return "var(--warning)";
+    case "resolution":
+      return "var(--accent)";
+    case "session":
+      return "var(--success)";
+    default:
+      return "var(--ink-muted)";
+  }
+}
+
+function describeEvent(t: TranslateFunction, event: SessionTimelineEvent): string {
+  // Diagnostic codes such as lag reasons and recovery actions stay untranslated, like the rest of the stats panel.
+  const params = Object.fromEntries(
+    Object.entries(event.params ?? {}).map(([key, value]) => [key, typeof value === "string" ? value.replace(/_/g, " ") : value]),
+  );
+  return t(`stream.timeline.event.${event.code}`, params);
+}
+
+export function SessionTimelinePanel({
+  source,
+  defaultOpen = false,
+}: {
+  source: SessionTimelineSource;
+  defaultOpen?: boolean;
+}): JSX.Element | null {
+  const { t } = useTranslation();
+  const timeline = useSyncExternalStore(source.subscribe, source.getSnapshot, source.getSnapshot);
+  const [open, setOpen] = useState(defaultOpen);
+  if (!timeline || timeline.events.length === 0) {
+    return null;
+  }
+
+  const lastAtMs = timeline.endedAtMs ?? timeline.events[timeline.events.length - 1].atMs;
+  const spanMs = Math.max(1, lastAtMs - timeline.startedAtMs);
+
+  return (
+    <div className="sv-timeline">
+      <button
+        type="button"
+        className="sv-stats-advanced-toggle"
+        onClick={() => setOpen((value) => !value)}
+        aria-expanded={open}
+      >
+        {open ? t("stream.timeline.hide") : t("stream.timeline.show", { count: timeline.events.length })}
+      </button>
+      {open && (
+        <>
+          <div className="sv-timeline-strip" aria-hidden>
+            {timeline.events.map((event, index) => (
+              <span
+                key={index}
+                className="sv-timeline-mark"
+                title={`${formatTimelineOffset(timeline.startedAtMs, event.atMs)} ${describeEvent(t, event)}`}
+                style={{
+                  left: `${((event.atMs - timeline.startedAtMs) / spanMs) * 100}%`,
+                  background: getEventColor(event.kind),
+                }}
+              />
+            ))}
+          </div>
+          <ol className="sv-timeline-events" aria-label={t("stream.timeline.label")}>
+            {timeline.events.map((event, index) => {
+              const detail = describeEvent(t, event);
+              return (
+                <li key={index} className="sv-timeline-event">
+                  <span className="sv-timeline-offset">{formatTimelineOffset(timeline.startedAtMs, event.atMs)}</span>
+                  <span className="sv-timeline-kind" style={{ color: getEventColor(event.kind) }}>
+                    {t(`stream.timeline.kind.${event.kind}`)}
+                  </span>
+                  <span className="sv-timeline-detail" title={detail}>{detail}</span>
+                </li>
+              );
+            })}
+          </ol>
+        </>
+      )}
+    </div>
+  );
+}
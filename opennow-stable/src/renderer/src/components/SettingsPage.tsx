@@ -1,9 +1,10 @@
-import { Globe, Check, Search, X, Loader, Zap, Mic, FileDown, Wifi, Trash2, Heart, Users, ExternalLink, Monitor, Keyboard, Download, RefreshCcw, Info, Cpu, AlertTriangle, MapPin, ScanLine, Gauge, Film, SlidersHorizontal, HardDrive } from "lucide-react";
+import { Globe, Check, Search, X, Loader, Zap, Mic, FileDown, Wifi, Trash2, Heart, Users, ExternalLink, Monitor, Keyboard, Download, RefreshCcw, Info, Cpu, AlertTriangle, MapPin, ScanLine, Gauge, BatteryLow, Film, SlidersHorizontal, HardDrive, Lock, KeyRound, LogOut, ChevronLeft, ChevronRight, Bug } from "lucide-react";
 import { useState, useCallback, useMemo, useEffect, useRef } from "react";
 import type { JSX } from "react";
 
 import type {
   Settings,
+  AuthTokens,
   StreamRegion,
   VideoCodec,
   ColorQuality,
@@ -19,10 +20,20 @@ import type {
   ThankYouContributor,
   ThankYouSupporter,
   AppUpdaterState,
+  DisplayInfo,
   NativeStreamerStatus,
   NativeVideoBackendCapability,
   NativeVideoBackendPreference,
   GameAccountConnection,
+  AppTheme,
+  UserTheme,
+  DriverWorkaroundReport,
+  StatsPanelField,
+  StorageCategory,
+  UpdateChannel,
+  GamescopeCompatibility,
+  GamescopeStatus,
+  SessionTimeline,
 } from "@shared/gfn";
 import {
   createUnsupportedNativeStreamerStatus,
@@ -31,19 +42,39 @@ import {
   colorQualityRequiresHevc,
   getSafeFallbackEntitledResolutions,
   keyboardLayoutOptions,
+  normalizeCustomResolution,
   resolveEntitledStreamProfile,
+  STATS_PANEL_FIELDS,
   USER_FACING_COLOR_QUALITY_OPTIONS,
   USER_FACING_VIDEO_CODEC_OPTIONS,
 } from "@shared/gfn";
+import { RENDER_FPS_CAP_OPTIONS } from "@shared/renderFpsCap";
+import { estimateLowLatencyGain } from "@shared/lowLatencyPreset";
 import { formatShortcutForDisplay, normalizeShortcut, shortcutFromKeyboardEvent } from "../shortcuts";
+import { GAMEPAD_MOUSE_SPEED_MAX, GAMEPAD_MOUSE_SPEED_MIN } from "../gfn/gamepadMouse";
+import { RESTREAM_MAX_HEIGHT_OPTIONS } from "../gfn/whipPublisher";
 import { getCodecDecodeBadgeState, shouldShowLinuxHardwareCodecHint, type CodecTestResult } from "../lib/codecDiagnostics";
 import { getAccentColorOption, getAccentColorOptions } from "../lib/uiCustomization";
 import { useTranslation } from "../i18n";
+import { useLogTail } from "../hooks/useLogTail";
+import { useStorageUsage } from "../hooks/useStorageUsage";
+import { ChangelogDialog } from "./ChangelogDialog";
+import { GameStreamOverrides, StreamOverrideFields } from "./GameStreamOverrides";
+import { MouseFeelTest } from "./MouseFeelTest";
+import { ParentalControlsSettings } from "./ParentalControlsSettings";
+import { SessionTimelinePanel, type SessionTimelineSource } from "./SessionTimelinePanel";
 import {
   clearStoredRegionPingResults,
   loadStoredRegionPingResults,
   saveStoredRegionPingResults,
 } from "../utils/pingResultsStorage";
+import {
+  TIER_STREAM_PRESETS,
+  classifyMembershipTier,
+  formatTierPresetLabel,
+  getTierPresetBlock,
+  type TierStreamPreset,
+} from "../utils/streamQualityPresets";
 
 interface SettingsPageProps {
   settings: Settings;
@@ -52,7 +83,15 @@ interface SettingsPageProps {
   codecTesting: boolean;
   onRunCodecTest: () => Promise<void>;
   onSettingChange: <K extends keyof Settings>(key: K, value: Settings[K]) => void;
+  /** Applies a stream volume right away and saves it once the slider settles */
+  onStreamVolumeChange: (value: number) => void;
+  /** Saves a pending stream volume immediately, e.g. when the slider is released */
+  onStreamVolumeCommit: () => void;
   onClose: () => void;
+  /** Opens the sign-out confirmation in token-revoking mode */
+  onSignOutEverywhere: () => void;
+  /** Games offered when adding a per-game stream override */
+  overrideGames?: ReadonlyArray<{ id: string; title: string }>;
 }
 
 type SettingsNavItem = {
@@ -70,7 +109,7 @@ type ThanksLoadState = "idle" | "loading" | "loaded" | "error";
 type StorageResetState = "idle" | "resetting" | "success" | "error";
 type GameAccountBusyAction = "link" | "unlink" | "resync";
 
-type SettingsSectionId = "account" | "stream" | "native-streamer" | "game" | "audio" | "input" | "interface" | "about" | "thanks";
+type SettingsSectionId = "account" | "stream" | "native-streamer" | "game" | "audio" | "input" | "interface" | "storage" | "debug" | "about" | "thanks";
 type SettingsSearchScopeId =
   | "account-storage"
   | "stream-region"
@@ -81,6 +120,8 @@ type SettingsSearchScopeId =
   | "audio"
   | "input"
   | "interface"
+  | "storage"
+  | "debug"
   | "about"
   | "thanks";
 
@@ -100,6 +141,16 @@ const SETTINGS_SCOPE_SEARCH_TERMS: Record<SettingsSearchScopeId, readonly string
     "link accounts",
     "unlink accounts",
     "sync library",
+    "sign in",
+    "sign out",
+    "sign out everywhere",
+    "revoke",
+    "token",
+    "expiry",
+    "devices",
+    "parental controls",
+    "pin",
+    "age rating",
     "steam",
     "epic",
     "ubisoft",
@@ -135,12 +186,48 @@ const SETTINGS_SCOPE_SEARCH_TERMS: Record<SettingsSearchScopeId, readonly string
     "quality",
     "codec",
     "fps",
+    "fps cap",
+    "battery",
+    "battery profile",
+    "power",
+    "low latency",
+    "competitive",
+    "esports",
+    "handheld",
     "resolution",
+    "custom resolution",
+    "ultrawide",
+    "4k",
+    "presets",
+    "membership",
+    "per game",
+    "hdr",
     "bitrate",
+    "bandwidth",
+    "metered",
+    "data cap",
     "aspect ratio",
     "l4s",
+    "ice",
+    "stun",
+    "turn",
+    "relay",
+    "udp port",
+    "ipv4",
+    "ipv6",
+    "mtu",
+    "fragmentation",
+    "vpn",
+    "qos",
+    "dscp",
+    "proxy",
+    "socks5",
     "cloud gsync",
     "video acceleration",
+    "restream",
+    "whip",
+    "spectator",
+    "watch party",
   ],
   "stream-codec-diagnostics": [
     "stream",
@@ -162,6 +249,9 @@ const SETTINGS_SCOPE_SEARCH_TERMS: Record<SettingsSearchScopeId, readonly string
     "dx11",
     "dx12",
     "cloud gsync",
+    "thread priority",
+    "frame pacing",
+    "mmcss",
     "diagnostics",
     "stats",
     "overlay",
@@ -176,7 +266,21 @@ const SETTINGS_SCOPE_SEARCH_TERMS: Record<SettingsSearchScopeId, readonly string
     "bug",
   ],
   game: ["game", "language", "keyboard layout", "store", "launch"],
-  audio: ["audio", "microphone", "mic", "push to talk", "voice activity"],
+  audio: [
+    "audio",
+    "microphone",
+    "mic",
+    "push to talk",
+    "voice activity",
+    "audio latency",
+    "buffer",
+    "exclusive",
+    "wasapi",
+    "pipewire",
+    "volume",
+    "mute",
+    "duck",
+  ],
   input: [
     "input",
     "mouse",
@@ -190,6 +294,9 @@ const SETTINGS_SCOPE_SEARCH_TERMS: Record<SettingsSearchScopeId, readonly string
     "gyro",
     "gyroscope",
     "motion controls",
+    "steam deck",
+    "steam input",
+    "gamescope",
     "anti afk",
     "pointer lock",
     "recording",
@@ -204,23 +311,38 @@ const SETTINGS_SCOPE_SEARCH_TERMS: Record<SettingsSearchScopeId, readonly string
     "app language",
     "accent color",
     "theme color",
+    "theme",
+    "light mode",
+    "dark mode",
     "overlay",
     "library",
     "fullscreen",
     "discord",
     "rich presence",
     "poster",
+    "library sync",
+    "background",
     "session timer",
+    "stats panel",
+    "statistics",
     "session time left",
     "session countdown",
     "free tier time",
     "priority time",
     "ultimate time",
+    "idle",
+    "reminder",
+    "bedtime",
+    "time limit",
+    "afk",
+    "auto disconnect",
     "counter",
     "controller",
     "gamepad",
     "big picture",
   ],
+  storage: ["storage", "disk", "space", "cache", "clear", "images", "library", "recordings", "crash reports"],
+  debug: ["debug", "logs", "log level", "log filter", "verbose", "trace", "tail", "console"],
   about: ["about", "update", "version", "logs", "cache", "download"],
   thanks: ["thanks", "contributors", "supporters", "sponsors", "community"],
 };
@@ -251,6 +373,7 @@ const nativeVideoBackendOptions: { value: NativeVideoBackendPreference; label: s
   { value: "auto", label: "Auto", description: "Pick the default native path for the session" },
   { value: "d3d12", label: "DirectX 12", description: "Use the D3D12 decoder and renderer" },
   { value: "d3d11", label: "DirectX 11", description: "Use the D3D11 decoder and renderer" },
+  { value: "nvdec", label: "NVIDIA NVDEC", description: "Use NVIDIA's CUDA decoder with a D3D11 renderer" },
 ];
 
 const APP_LANGUAGE_LABELS: Record<string, string> = {
@@ -268,7 +391,6 @@ const APP_LANGUAGE_LABELS: Record<string, string> = {
   ro: "Română",
 };
 
-const accentColorOptions = getAccentColorOptions();
 
 function getAppLanguageLabel(locale: string): string {
   return APP_LANGUAGE_LABELS[locale] ?? locale.toUpperCase();
@@ -288,6 +410,8 @@ function formatNativeVideoBackendName(backend: string | undefined): string {
       return "V4L2";
     case "vulkan":
       return "Vulkan";
+    case "nvdec":
+      return "NVDEC";
     case "software":
       return "Software";
     default:
@@ -386,7 +510,21 @@ const STATIC_FPS_PRESETS: FpsPreset[] = [
   { value: 360 },
 ];
 
+const AUDIO_BUFFER_MS_PRESETS = [0, 5, 10, 20, 40] as const;
+const JITTER_BUFFER_MS_PRESETS = [0, 2, 5, 10, 25, 50] as const;
+const DISK_CACHE_LIMIT_MB_PRESETS = [0, 256, 512, 1024, 2048] as const;
+const UPDATE_CHANNELS: UpdateChannel[] = ["stable", "beta"];
+const GAMESCOPE_COMPATIBILITY_MODES: GamescopeCompatibility[] = ["auto", "off"];
+const STORAGE_CATEGORIES: readonly StorageCategory[] = ["images", "library", "recordings", "logs"];
+const MAX_BITRATE_PRESETS_MBPS = [10, 25, 50, 75] as const;
+const APP_THEME_OPTIONS: ReadonlyArray<{ value: AppTheme; labelKey: string }> = [
+  { value: "dark", labelKey: "settings.interface.themeDark" },
+  { value: "light", labelKey: "settings.interface.themeLight" },
+  { value: "system", labelKey: "settings.interface.themeSystem" },
+];
+
 const isMac = navigator.platform.toLowerCase().includes("mac");
+const isLinux = navigator.platform.toLowerCase().includes("linux");
 const isWindows = isNativeStreamerSupportedPlatform(`${navigator.platform} ${navigator.userAgent}`);
 const shortcutExamples = "Examples: F3, Ctrl+Shift+Q, Ctrl+Shift+K";
 const shortcutDefaults = {
@@ -398,6 +536,10 @@ const shortcutDefaults = {
   shortcutToggleMicrophone: "Ctrl+Shift+M",
   shortcutScreenshot: "F11",
   shortcutToggleRecording: "F12",
+  shortcutToggleStreamMute: "Ctrl+Shift+End",
+  shortcutVolumeUp: "Ctrl+Shift+PageUp",
+  shortcutVolumeDown: "Ctrl+Shift+PageDown",
+  shortcutToggleTextInput: "Ctrl+Shift+T",
 } as const;
 
 /** Canonical shortcut for toggling the stream sidebar (must match StreamView key handler). */
@@ -650,6 +792,11 @@ function getUpdaterBadgeLabel(state: AppUpdaterState): string {
   }
 }
 
+function formatCredentialExpiry(expiresAt: number | undefined): string | null {
+  if (!expiresAt || !Number.isFinite(expiresAt)) return null;
+  return new Date(expiresAt).toLocaleString();
+}
+
 function formatGameAccountSyncDate(value: string | undefined): string | null {
   if (!value) return null;
   const date = new Date(value);
@@ -667,10 +814,11 @@ function saveCachedEntitledResolutions(cache: EntitledResolutionsCache): void {
 
 /* ── Component ────────────────────────────────────────────────────── */
 
-export function SettingsPage({ settings, regions, onSettingChange, codecResults, codecTesting, onRunCodecTest, onClose }: SettingsPageProps): JSX.Element {
+export function SettingsPage({ settings, regions, onSettingChange, onStreamVolumeChange, onStreamVolumeCommit, codecResults, codecTesting, onRunCodecTest, onClose, onSignOutEverywhere, overrideGames = [] }: SettingsPageProps): JSX.Element {
   const { locale, availableLocales, setLocale, t } = useTranslation();
   const [savedIndicator, setSavedIndicator] = useState(false);
   const [activeSection, setActiveSection] = useState<SettingsSectionId>("stream");
+  const [logTailQuery, setLogTailQuery] = useState("");
   const [thanksData, setThanksData] = useState<ThankYouDataResult | null>(null);
   const [thanksLoadState, setThanksLoadState] = useState<ThanksLoadState>("idle");
   const [thanksFetchError, setThanksFetchError] = useState<string | null>(null);
@@ -754,6 +902,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
   const [toggleMicrophoneInput, setToggleMicrophoneInput] = useState(settings.shortcutToggleMicrophone);
   const [screenshotInput, setScreenshotInput] = useState(settings.shortcutScreenshot);
   const [recordingInput, setRecordingInput] = useState(settings.shortcutToggleRecording);
+  const [streamMuteInput, setStreamMuteInput] = useState(settings.shortcutToggleStreamMute);
+  const [volumeUpInput, setVolumeUpInput] = useState(settings.shortcutVolumeUp);
+  const [volumeDownInput, setVolumeDownInput] = useState(settings.shortcutVolumeDown);
+  const [textInputModeInput, setTextInputModeInput] = useState(settings.shortcutToggleTextInput);
   const [toggleStatsError, setToggleStatsError] = useState<string | null>(null);
   const [togglePointerLockError, setTogglePointerLockError] = useState<string | null>(null);
   const [toggleFullscreenError, setToggleFullscreenError] = useState<string | null>(null);
@@ -762,6 +914,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
   const [toggleMicrophoneError, setToggleMicrophoneError] = useState<string | null>(null);
   const [screenshotError, setScreenshotError] = useState<string | null>(null);
   const [recordingError, setRecordingError] = useState<string | null>(null);
+  const [streamMuteError, setStreamMuteError] = useState<string | null>(null);
+  const [volumeUpError, setVolumeUpError] = useState<string | null>(null);
+  const [volumeDownError, setVolumeDownError] = useState<string | null>(null);
+  const [textInputModeError, setTextInputModeError] = useState<string | null>(null);
 
   const [keyboardLayoutDropdownOpen, setKeyboardLayoutDropdownOpen] = useState(false);
   const keyboardLayoutDropdownRef = useRef<HTMLDivElement | null>(null);
@@ -769,6 +925,7 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
   const [appLanguageDropdownOpen, setAppLanguageDropdownOpen] = useState(false);
   const appLanguageDropdownRef = useRef<HTMLDivElement | null>(null);
   const [accentColorDropdownOpen, setAccentColorDropdownOpen] = useState(false);
+  const [userTheme, setUserTheme] = useState<UserTheme | null>(null);
   const accentColorDropdownRef = useRef<HTMLDivElement | null>(null);
 
   // Game language dropdown state
@@ -776,10 +933,15 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
   const gameLanguageDropdownRef = useRef<HTMLDivElement | null>(null);
 
   const [resolutionDropdownOpen, setResolutionDropdownOpen] = useState(false);
+  const [customResolutionWidth, setCustomResolutionWidth] = useState(() => settings.resolution.split("x")[0] ?? "");
+  const [customResolutionHeight, setCustomResolutionHeight] = useState(() => settings.resolution.split("x")[1] ?? "");
   const resolutionDropdownRef = useRef<HTMLDivElement | null>(null);
   const [settingsSearch, setSettingsSearch] = useState("");
   const [codecAdvancedOpen, setCodecAdvancedOpen] = useState(false);
   const [nativeStreamerStatus, setNativeStreamerStatus] = useState<NativeStreamerStatus | null>(null);
+  const [displays, setDisplays] = useState<DisplayInfo[]>([]);
+  const [driverWorkarounds, setDriverWorkarounds] = useState<DriverWorkaroundReport | null>(null);
+  const [gamescopeStatus, setGamescopeStatus] = useState<GamescopeStatus | null>(null);
   const [nativeStreamerStatusLoading, setNativeStreamerStatusLoading] = useState(false);
   const [nativeStreamerEnablePromptOpen, setNativeStreamerEnablePromptOpen] = useState(false);
   const [nativeStreamerEnablePromptClosing, setNativeStreamerEnablePromptClosing] = useState(false);
@@ -794,16 +956,19 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     currentVersion: "0.0.0",
     currentDisplayVersion: "0.0.0",
     updateSource: "github-releases",
+    channel: "stable",
     canCheck: false,
     canDownload: false,
     canInstall: false,
     isPackaged: false,
   });
+  const [changelogOpen, setChangelogOpen] = useState(false);
 
   // Dynamic entitled resolutions from MES API
   const [entitledResolutions, setEntitledResolutions] = useState<EntitledResolution[]>([]);
   const [subscriptionInfo, setSubscriptionInfo] = useState<SubscriptionInfo | null>(null);
   const [subscriptionLoading, setSubscriptionLoading] = useState(true);
+  const [sessionTokens, setSessionTokens] = useState<AuthTokens | null>(null);
   const [storageResetState, setStorageResetState] = useState<StorageResetState>("idle");
   const [storageResetMessage, setStorageResetMessage] = useState<string | null>(null);
   const [gameAccounts, setGameAccounts] = useState<GameAccountConnection[]>([]);
@@ -847,6 +1012,22 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     setRecordingInput(settings.shortcutToggleRecording);
   }, [settings.shortcutToggleRecording]);
 
+  useEffect(() => {
+    setStreamMuteInput(settings.shortcutToggleStreamMute);
+  }, [settings.shortcutToggleStreamMute]);
+
+  useEffect(() => {
+    setVolumeUpInput(settings.shortcutVolumeUp);
+  }, [settings.shortcutVolumeUp]);
+
+  useEffect(() => {
+    setVolumeDownInput(settings.shortcutVolumeDown);
+  }, [settings.shortcutVolumeDown]);
+
+  useEffect(() => {
+    setTextInputModeInput(settings.shortcutToggleTextInput);
+  }, [settings.shortcutToggleTextInput]);
+
   useEffect(() => {
     let cancelled = false;
 
@@ -870,6 +1051,16 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     };
   }, []);
 
+  const reloadUserTheme = useCallback(() => {
+    void window.openNow.getUserTheme().then(setUserTheme).catch((error) => {
+      console.warn("[Settings] Failed to load user theme:", error);
+    });
+  }, []);
+
+  useEffect(() => {
+    reloadUserTheme();
+  }, [reloadUserTheme]);
+
   const refreshNativeStreamerStatus = useCallback(async () => {
     if (!isWindows) {
       setNativeStreamerStatus(createUnsupportedNativeStreamerStatus());
@@ -904,6 +1095,53 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     }
   }, [activeSection, refreshNativeStreamerStatus, settingsSearch.length]);
 
+  useEffect(() => {
+    let cancelled = false;
+    window.openNow.listDisplays()
+      .then((nextDisplays) => {
+        if (!cancelled) setDisplays(nextDisplays);
+      })
+      .catch((error) => {
+        console.warn("[Settings] Failed to list displays:", error);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  useEffect(() => {
+    let cancelled = false;
+    window.openNow.getDriverWorkarounds()
+      .then((report) => {
+        if (!cancelled) setDriverWorkarounds(report);
+      })
+      .catch((error) => {
+        console.warn("[Settings] Failed to load driver workarounds:", error);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [settings.driverWorkaroundsEnabled, settings.disabledDriverWorkarounds]);
+
+  useEffect(() => window.openNow.onDriverWorkaroundsChanged(setDriverWorkarounds), []);
+
+  useEffect(() => {
+    if (!isLinux) {
+      return;
+    }
+    let cancelled = false;
+    window.openNow.getGamescopeStatus()
+      .then((status) => {
+        if (!cancelled) setGamescopeStatus(status);
+      })
+      .catch((error) => {
+        console.warn("[Settings] Failed to load gamescope status:", error);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
   const loadSubscriptionData = useCallback(async (isCancelled: () => boolean = () => false): Promise<void> => {
     setSubscriptionLoading(true);
 
@@ -913,8 +1151,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       if (!session || isCancelled()) {
         setEntitledResolutions([]);
         setSubscriptionInfo(null);
+        setSessionTokens(null);
         return;
       }
+      setSessionTokens(session.tokens);
 
       const userId = session.user.userId;
       const cached = loadCachedEntitledResolutions();
@@ -1006,6 +1246,15 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     () => (useEntitledStreamOptions ? getFpsForResolution(effectiveEntitledResolutions, settings.resolution) : []),
     [effectiveEntitledResolutions, settings.resolution, useEntitledStreamOptions]
   );
+  const overrideResolutionOptions = useMemo(
+    () => (useEntitledStreamOptions ? resolutionGroups.flatMap((group) => group.resolutions) : STATIC_RESOLUTION_PRESETS),
+    [resolutionGroups, useEntitledStreamOptions],
+  );
+  const overrideFpsOptions = useMemo(() => STATIC_FPS_PRESETS.map((preset) => preset.value), []);
+  const lowLatencyGain = useMemo(
+    () => estimateLowLatencyGain(settings),
+    [settings.audioBufferMs, settings.enableCloudGsync, settings.fps, settings.jitterBufferMs, settings.lowLatencyMode],
+  );
   const resolvedEntitledProfile = useMemo(
     () => resolveEntitledStreamProfile(effectiveEntitledResolutions, {
       resolution: settings.resolution,
@@ -1024,6 +1273,7 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     : null;
   const updaterBadgeLabel = useMemo(() => getUpdaterBadgeLabel(updaterState), [updaterState]);
   const persistentStorage = subscriptionInfo?.storageAddon;
+  const clientTokenExpiryLabel = formatCredentialExpiry(sessionTokens?.clientTokenExpiresAt);
   const persistentStorageSizeGb = typeof persistentStorage?.sizeGb === "number" ? persistentStorage.sizeGb : null;
   const persistentStorageUsedGb = typeof persistentStorage?.usedGb === "number" ? persistentStorage.usedGb : null;
   const persistentStorageRemainingGb =
@@ -1159,6 +1409,31 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     [onSettingChange]
   );
 
+  // Shown fields keep their saved order; hidden ones follow so they can be switched back on.
+  const statsPanelFieldOrder = useMemo(() => [
+    ...settings.statsPanelFields,
+    ...STATS_PANEL_FIELDS.filter((field) => !settings.statsPanelFields.includes(field)),
+  ], [settings.statsPanelFields]);
+
+  const toggleStatsPanelField = useCallback((field: StatsPanelField) => {
+    const fields = settings.statsPanelFields;
+    handleChange(
+      "statsPanelFields",
+      fields.includes(field) ? fields.filter((candidate) => candidate !== field) : [...fields, field],
+    );
+  }, [handleChange, settings.statsPanelFields]);
+
+  const moveStatsPanelField = useCallback((field: StatsPanelField, delta: -1 | 1) => {
+    const fields = [...settings.statsPanelFields];
+    const from = fields.indexOf(field);
+    const to = from + delta;
+    if (from < 0 || to < 0 || to >= fields.length) {
+      return;
+    }
+    [fields[from], fields[to]] = [fields[to], fields[from]];
+    handleChange("statsPanelFields", fields);
+  }, [handleChange, settings.statsPanelFields]);
+
   const handleResolutionChange = useCallback((resolution: string) => {
     handleChange("resolution", resolution);
     const aspectRatio = inferAspectRatioFromResolution(resolution);
@@ -1167,6 +1442,17 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     }
   }, [handleChange, settings.aspectRatio]);
 
+  const customResolution = normalizeCustomResolution(
+    Number.parseInt(customResolutionWidth, 10),
+    Number.parseInt(customResolutionHeight, 10),
+  );
+
+  const handleMatchDisplayResolution = useCallback(() => {
+    const scale = window.devicePixelRatio || 1;
+    setCustomResolutionWidth(String(Math.round(window.screen.width * scale)));
+    setCustomResolutionHeight(String(Math.round(window.screen.height * scale)));
+  }, []);
+
   useEffect(() => {
     if (!useEntitledStreamOptions || !resolvedEntitledProfile) {
       return;
@@ -1350,6 +1636,25 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     [handleChange, settings.codec]
   );
 
+  const membershipTierClass = classifyMembershipTier(subscriptionInfo?.membershipTier);
+  const tierPresets = useMemo(
+    () => TIER_STREAM_PRESETS.map((preset) => ({
+      preset,
+      block: getTierPresetBlock(preset, membershipTierClass, effectiveEntitledResolutions),
+    })),
+    [effectiveEntitledResolutions, membershipTierClass],
+  );
+
+  const applyTierPreset = useCallback(
+    (preset: TierStreamPreset) => {
+      handleChange("resolution", preset.resolution);
+      handleChange("fps", preset.fps);
+      handleChange("maxBitrateMbps", preset.maxBitrateMbps);
+      handleColorQualityChange(preset.colorQuality);
+    },
+    [handleChange, handleColorQualityChange]
+  );
+
   const handleCodecChange = useCallback(
     (codec: VideoCodec) => {
       handleChange("codec", codec);
@@ -1499,7 +1804,12 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     return appLanguageOptions.find((option) => option.value === locale)?.label ?? getAppLanguageLabel(locale);
   }, [appLanguageOptions, locale]);
 
-  const selectedAccentColor = useMemo(() => getAccentColorOption(settings.appAccentColor), [settings.appAccentColor]);
+  const customAccentHex = userTheme?.accent ?? null;
+  const accentColorOptions = useMemo(() => getAccentColorOptions(customAccentHex), [customAccentHex]);
+  const selectedAccentColor = useMemo(
+    () => getAccentColorOption(settings.appAccentColor, customAccentHex),
+    [customAccentHex, settings.appAccentColor],
+  );
   const getMicrophoneModeLabel = useCallback((mode: MicrophoneMode): string => {
     switch (mode) {
       case "push-to-talk":
@@ -1579,6 +1889,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
         case "shortcutToggleMicrophone": setToggleMicrophoneError(msg); break;
         case "shortcutScreenshot": setScreenshotError(msg); break;
         case "shortcutToggleRecording": setRecordingError(msg); break;
+        case "shortcutToggleStreamMute": setStreamMuteError(msg); break;
+        case "shortcutVolumeUp": setVolumeUpError(msg); break;
+        case "shortcutVolumeDown": setVolumeDownError(msg); break;
+        case "shortcutToggleTextInput": setTextInputModeError(msg); break;
       }
       return;
     }
@@ -1595,6 +1909,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
         case "shortcutToggleMicrophone": setToggleMicrophoneError(msg); break;
         case "shortcutScreenshot": setScreenshotError(msg); break;
         case "shortcutToggleRecording": setRecordingError(msg); break;
+        case "shortcutToggleStreamMute": setStreamMuteError(msg); break;
+        case "shortcutVolumeUp": setVolumeUpError(msg); break;
+        case "shortcutVolumeDown": setVolumeDownError(msg); break;
+        case "shortcutToggleTextInput": setTextInputModeError(msg); break;
       }
       return;
     }
@@ -1610,6 +1928,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
         case "shortcutToggleMicrophone": setToggleMicrophoneError(conflict); break;
         case "shortcutScreenshot": setScreenshotError(conflict); break;
         case "shortcutToggleRecording": setRecordingError(conflict); break;
+        case "shortcutToggleStreamMute": setStreamMuteError(conflict); break;
+        case "shortcutVolumeUp": setVolumeUpError(conflict); break;
+        case "shortcutVolumeDown": setVolumeDownError(conflict); break;
+        case "shortcutToggleTextInput": setTextInputModeError(conflict); break;
       }
       return;
     }
@@ -1623,6 +1945,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       case "shortcutToggleMicrophone": setToggleMicrophoneError(null); break;
       case "shortcutScreenshot": setScreenshotError(null); break;
       case "shortcutToggleRecording": setRecordingError(null); break;
+      case "shortcutToggleStreamMute": setStreamMuteError(null); break;
+      case "shortcutVolumeUp": setVolumeUpError(null); break;
+      case "shortcutVolumeDown": setVolumeDownError(null); break;
+      case "shortcutToggleTextInput": setTextInputModeError(null); break;
     }
 
     switch (key) {
@@ -1634,6 +1960,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       case "shortcutToggleMicrophone": setToggleMicrophoneInput(normalized.canonical); break;
       case "shortcutScreenshot": setScreenshotInput(normalized.canonical); break;
       case "shortcutToggleRecording": setRecordingInput(normalized.canonical); break;
+      case "shortcutToggleStreamMute": setStreamMuteInput(normalized.canonical); break;
+      case "shortcutVolumeUp": setVolumeUpInput(normalized.canonical); break;
+      case "shortcutVolumeDown": setVolumeDownInput(normalized.canonical); break;
+      case "shortcutToggleTextInput": setTextInputModeInput(normalized.canonical); break;
     }
 
     if (settings[key] !== normalized.canonical) {
@@ -1653,6 +1983,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
         case "shortcutToggleMicrophone": setToggleMicrophoneError(conflict); break;
         case "shortcutScreenshot": setScreenshotError(conflict); break;
         case "shortcutToggleRecording": setRecordingError(conflict); break;
+        case "shortcutToggleStreamMute": setStreamMuteError(conflict); break;
+        case "shortcutVolumeUp": setVolumeUpError(conflict); break;
+        case "shortcutVolumeDown": setVolumeDownError(conflict); break;
+        case "shortcutToggleTextInput": setTextInputModeError(conflict); break;
       }
       return;
     }
@@ -1666,6 +2000,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       case "shortcutToggleMicrophone": setToggleMicrophoneError(null); break;
       case "shortcutScreenshot": setScreenshotError(null); break;
       case "shortcutToggleRecording": setRecordingError(null); break;
+      case "shortcutToggleStreamMute": setStreamMuteError(null); break;
+      case "shortcutVolumeUp": setVolumeUpError(null); break;
+      case "shortcutVolumeDown": setVolumeDownError(null); break;
+      case "shortcutToggleTextInput": setTextInputModeError(null); break;
     }
 
     switch (key) {
@@ -1677,6 +2015,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       case "shortcutToggleMicrophone": setToggleMicrophoneInput(canonical); break;
       case "shortcutScreenshot": setScreenshotInput(canonical); break;
       case "shortcutToggleRecording": setRecordingInput(canonical); break;
+      case "shortcutToggleStreamMute": setStreamMuteInput(canonical); break;
+      case "shortcutVolumeUp": setVolumeUpInput(canonical); break;
+      case "shortcutVolumeDown": setVolumeDownInput(canonical); break;
+      case "shortcutToggleTextInput": setTextInputModeInput(canonical); break;
     }
 
     if (settings[key] !== canonical) {
@@ -1722,6 +2064,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
         case "shortcutToggleMicrophone": setToggleMicrophoneError(msg); break;
         case "shortcutScreenshot": setScreenshotError(msg); break;
         case "shortcutToggleRecording": setRecordingError(msg); break;
+        case "shortcutToggleStreamMute": setStreamMuteError(msg); break;
+        case "shortcutVolumeUp": setVolumeUpError(msg); break;
+        case "shortcutVolumeDown": setVolumeDownError(msg); break;
+        case "shortcutToggleTextInput": setTextInputModeError(msg); break;
       }
       return;
     }
@@ -1737,7 +2083,11 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       && settings.shortcutToggleAntiAfk === shortcutDefaults.shortcutToggleAntiAfk
       && settings.shortcutToggleMicrophone === shortcutDefaults.shortcutToggleMicrophone
       && settings.shortcutScreenshot === shortcutDefaults.shortcutScreenshot
-      && settings.shortcutToggleRecording === shortcutDefaults.shortcutToggleRecording,
+      && settings.shortcutToggleRecording === shortcutDefaults.shortcutToggleRecording
+      && settings.shortcutToggleStreamMute === shortcutDefaults.shortcutToggleStreamMute
+      && settings.shortcutVolumeUp === shortcutDefaults.shortcutVolumeUp
+      && settings.shortcutVolumeDown === shortcutDefaults.shortcutVolumeDown
+      && settings.shortcutToggleTextInput === shortcutDefaults.shortcutToggleTextInput,
     [
       settings.shortcutToggleStats,
       settings.shortcutTogglePointerLock,
@@ -1747,6 +2097,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       settings.shortcutToggleMicrophone,
       settings.shortcutScreenshot,
       settings.shortcutToggleRecording,
+      settings.shortcutToggleStreamMute,
+      settings.shortcutVolumeUp,
+      settings.shortcutVolumeDown,
+      settings.shortcutToggleTextInput,
     ]
   );
 
@@ -1759,6 +2113,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     setToggleMicrophoneInput(shortcutDefaults.shortcutToggleMicrophone);
     setScreenshotInput(shortcutDefaults.shortcutScreenshot);
     setRecordingInput(shortcutDefaults.shortcutToggleRecording);
+    setStreamMuteInput(shortcutDefaults.shortcutToggleStreamMute);
+    setVolumeUpInput(shortcutDefaults.shortcutVolumeUp);
+    setVolumeDownInput(shortcutDefaults.shortcutVolumeDown);
+    setTextInputModeInput(shortcutDefaults.shortcutToggleTextInput);
     setToggleStatsError(null);
     setTogglePointerLockError(null);
     setToggleFullscreenError(null);
@@ -1767,6 +2125,10 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
     setToggleMicrophoneError(null);
     setScreenshotError(null);
     setRecordingError(null);
+    setStreamMuteError(null);
+    setVolumeUpError(null);
+    setVolumeDownError(null);
+    setTextInputModeError(null);
 
     for (const key of SHORTCUT_SETTING_KEYS) {
       const value = shortcutDefaults[key];
@@ -2177,11 +2539,40 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
   const showAudio = showAll ? scopeMatchesSearch("audio") : activeSection === "audio";
   const showInput = showAll ? scopeMatchesSearch("input") : activeSection === "input";
   const showInterface = showAll ? scopeMatchesSearch("interface") : activeSection === "interface";
+  const showStorage = showAll ? scopeMatchesSearch("storage") : activeSection === "storage";
+  const showDebug = showAll ? scopeMatchesSearch("debug") : activeSection === "debug";
   const showAbout = showAll ? scopeMatchesSearch("about") : activeSection === "about";
   const showThanks = showAll ? scopeMatchesSearch("thanks") : activeSection === "thanks";
-  const hasAnySearchMatches = showAccount || showStream || showNativeStreamer || showGame || showAudio || showInput || showInterface || showAbout || showThanks;
+  const hasAnySearchMatches = showAccount || showStream || showNativeStreamer || showGame || showAudio || showInput || showInterface || showStorage || showDebug || showAbout || showThanks;
+  const storageUsage = useStorageUsage(showStorage);
+  const logTail = useLogTail(showDebug);
+  const [lastSessionTimeline, setLastSessionTimeline] = useState<SessionTimeline | null>(null);
+  const lastSessionTimelineSource = useMemo<SessionTimelineSource | null>(
+    () => (lastSessionTimeline ? { getSnapshot: () => lastSessionTimeline, subscribe: () => () => {} } : null),
+    [lastSessionTimeline],
+  );
+  const visibleLogRows = useMemo(() => {
+    const query = logTailQuery.trim().toLowerCase();
+    return query ? logTail.rows.filter((row) => row.text.toLowerCase().includes(query)) : logTail.rows;
+  }, [logTail.rows, logTailQuery]);
   const shouldRenderSettingsSections = showAll || activeSection !== "thanks";
 
+  useEffect(() => {
+    if (!showDebug) {
+      return;
+    }
+    let cancelled = false;
+    void window.openNow
+      .getLastSessionTimeline()
+      .then((timeline) => {
+        if (!cancelled) setLastSessionTimeline(timeline);
+      })
+      .catch((error) => console.warn("[Settings] Failed to load the last session timeline:", error));
+    return () => {
+      cancelled = true;
+    };
+  }, [showDebug]);
+
   useEffect(() => {
     if (!showAccount) {
       return;
@@ -2218,6 +2609,8 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
       label: "App",
       items: [
         { id: "interface", label: t("settings.sections.interface"), icon: <Monitor size={15} /> },
+        { id: "storage", label: t("settings.sections.storage"), icon: <HardDrive size={15} /> },
+        { id: "debug", label: t("settings.sections.debug"), icon: <Bug size={15} /> },
         { id: "about", label: t("settings.sections.about"), icon: <Info size={15} /> },
         { id: "thanks", label: t("settings.sections.thanks"), icon: <Heart size={15} /> },
       ],
@@ -2426,6 +2819,74 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   </div>
                 </div>
               </section>
+              <section className="settings-section">
+                {showAll && <div className="settings-section-context">{t("settings.sections.account")}</div>}
+                <div className="settings-section-header settings-section-header--with-copy">
+                  <KeyRound size={18} />
+                  <div>
+                    <h2>{t("settings.signIn.title")}</h2>
+                    <p className="settings-section-subtitle">{t("settings.signIn.description")}</p>
+                  </div>
+                </div>
+                <div className="settings-rows">
+                  {sessionTokens ? (
+                    <>
+                      <div className="settings-row">
+                        <label className="settings-label">
+                          {t("settings.signIn.accessToken")}
+                          <span className="settings-hint">{t("settings.signIn.accessTokenHint")}</span>
+                        </label>
+                        <span className="settings-value-badge">
+                          {sessionTokens.expiresAt <= Date.now()
+                            ? t("settings.signIn.expired")
+                            : t("settings.signIn.expires", { time: formatCredentialExpiry(sessionTokens.expiresAt) })}
+                        </span>
+                      </div>
+                      <div className="settings-row">
+                        <label className="settings-label">
+                          {t("settings.signIn.clientToken")}
+                          <span className="settings-hint">{t("settings.signIn.clientTokenHint")}</span>
+                        </label>
+                        <span className="settings-value-badge">
+                          {!sessionTokens.clientToken
+                            ? t("settings.signIn.notSaved")
+                            : clientTokenExpiryLabel
+                              ? t("settings.signIn.expires", { time: clientTokenExpiryLabel })
+                              : t("settings.signIn.saved")}
+                        </span>
+                      </div>
+                      <div className="settings-row">
+                        <label className="settings-label">
+                          {t("settings.signIn.refreshToken")}
+                          <span className="settings-hint">{t("settings.signIn.refreshTokenHint")}</span>
+                        </label>
+                        <span className="settings-value-badge">
+                          {sessionTokens.refreshToken ? t("settings.signIn.saved") : t("settings.signIn.notSaved")}
+                        </span>
+                      </div>
+                    </>
+                  ) : (
+                    <span className="settings-subtle-hint">
+                      {subscriptionLoading ? t("settings.signIn.loading") : t("settings.signIn.signedOut")}
+                    </span>
+                  )}
+                  <div className="settings-row settings-row--column">
+                    <div className="settings-row-top settings-row-top--compact">
+                      <label className="settings-label">{t("settings.signIn.signOutEverywhere")}</label>
+                      <button
+                        type="button"
+                        className="settings-export-logs-btn"
+                        disabled={!sessionTokens}
+                        onClick={onSignOutEverywhere}
+                      >
+                        <LogOut size={16} />
+                        {t("settings.signIn.signOutEverywhereAction")}
+                      </button>
+                    </div>
+                    <span className="settings-subtle-hint">{t("settings.signIn.signOutEverywhereHint")}</span>
+                  </div>
+                </div>
+              </section>
               <section className="settings-section settings-game-accounts-section">
                 {showAll && <div className="settings-section-context">{t("settings.sections.account")}</div>}
                 <div className="settings-section-header settings-section-header--with-copy settings-game-accounts-header">
@@ -2476,6 +2937,19 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   )}
                 </div>
               </section>
+              <section className="settings-section">
+                {showAll && <div className="settings-section-context">{t("settings.sections.account")}</div>}
+                <div className="settings-section-header settings-section-header--with-copy">
+                  <Lock size={18} />
+                  <div>
+                    <h2>{t("settings.parental.title")}</h2>
+                    <p className="settings-section-subtitle">{t("settings.parental.description")}</p>
+                  </div>
+                </div>
+                <div className="settings-rows">
+                  <ParentalControlsSettings settings={settings} onChange={handleChange} />
+                </div>
+              </section>
               </>
             )}
             {/* ═══ STREAM ════════════════════════════════════ */}
@@ -2662,6 +3136,39 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                 <h2>{t("settings.video.title")}</h2>
               </div>
               <div className="settings-rows">
+                {subscriptionInfo && (
+                  <div className="settings-row settings-row--column">
+                    <label className="settings-label">{t("settings.video.tierPresets")}</label>
+                    <div className="settings-chip-row">
+                      {tierPresets.map(({ preset, block }) => (
+                        <button
+                          key={`${preset.resolution}@${preset.fps}`}
+                          type="button"
+                          className={`settings-chip ${
+                            settings.resolution === preset.resolution
+                            && settings.fps === preset.fps
+                            && settings.colorQuality === preset.colorQuality
+                              ? "active"
+                              : ""
+                          }`}
+                          disabled={block !== null}
+                          title={block === "tier"
+                            ? t("settings.video.tierPresetNeedsTier", { tier: t(`app.labels.${preset.minTier}`) })
+                            : block === "entitlement"
+                              ? t("settings.video.tierPresetNotEntitled")
+                              : undefined}
+                          onClick={() => applyTierPreset(preset)}
+                        >
+                          <span>{formatTierPresetLabel(preset)}</span>
+                        </button>
+                      ))}
+                    </div>
+                    <span className="settings-subtle-hint">
+                      {t("settings.video.tierPresetsHint", { tier: t(`app.labels.${membershipTierClass ?? "free"}`) })}
+                    </span>
+                  </div>
+                )}
+
                 {/* Resolution — grouped dropdown */}
                 <div className="settings-row settings-row--column">
                   <label className="settings-label settings-label--with-icon">
@@ -2701,6 +3208,41 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                       </div>
                     )}
                   </div>
+                  <div className="settings-input-group">
+                    <input
+                      type="number"
+                      className={`settings-text-input settings-text-input--narrow ${customResolution ? "" : "error"}`}
+                      min={640}
+                      max={7680}
+                      step={2}
+                      aria-label={t("settings.video.customResolutionWidth")}
+                      value={customResolutionWidth}
+                      onChange={(e) => setCustomResolutionWidth(e.target.value)}
+                    />
+                    <span>×</span>
+                    <input
+                      type="number"
+                      className={`settings-text-input settings-text-input--narrow ${customResolution ? "" : "error"}`}
+                      min={360}
+                      max={4320}
+                      step={2}
+                      aria-label={t("settings.video.customResolutionHeight")}
+                      value={customResolutionHeight}
+                      onChange={(e) => setCustomResolutionHeight(e.target.value)}
+                    />
+                    <button type="button" className="settings-chip" onClick={handleMatchDisplayResolution}>
+                      <span>{t("settings.video.matchDisplayResolution")}</span>
+                    </button>
+                    <button
+                      type="button"
+                      className={`settings-chip ${customResolution === settings.resolution ? "active" : ""}`}
+                      disabled={!customResolution}
+                      onClick={() => { if (customResolution) handleResolutionChange(customResolution); }}
+                    >
+                      <span>{t("settings.video.applyCustomResolution")}</span>
+                    </button>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.customResolutionHint")}</span>
                 </div>
 
                 {/* FPS — dynamic or static chips */}
@@ -2722,6 +3264,117 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   </div>
                 </div>
 
+                {/* Render FPS cap, per power source */}
+                <div className="settings-row settings-row--column">
+                  <label className="settings-label settings-label--with-icon">
+                    <Gauge size={15} className="settings-label-icon" />
+                    {t("settings.video.renderFpsCap")}
+                  </label>
+                  {([
+                    ["renderFpsCap", t("settings.video.renderFpsCapPluggedIn")],
+                    ["renderFpsCapOnBattery", t("settings.video.renderFpsCapOnBattery")],
+                  ] as const).map(([key, label]) => (
+                    <div key={key} className="settings-chip-row">
+                      <span className="settings-subtle-hint">{label}</span>
+                      {RENDER_FPS_CAP_OPTIONS.map((fps) => (
+                        <button
+                          key={fps}
+                          type="button"
+                          className={`settings-chip ${settings[key] === fps ? "active" : ""}`}
+                          onClick={() => handleChange(key, fps)}
+                        >
+                          <span>{fps === 0 ? t("settings.interface.off") : fps}</span>
+                        </button>
+                      ))}
+                    </div>
+                  ))}
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label settings-label--wrap">
+                      <span className="settings-label-title">{t("settings.video.renderFpsCapRequestServer")}</span>
+                    </label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.renderFpsCapRequestServer}
+                        onChange={(e) => handleChange("renderFpsCapRequestServer", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.renderFpsCapHint")}</span>
+                </div>
+
+                {/* Battery profile */}
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label settings-label--with-icon">
+                      <BatteryLow size={15} className="settings-label-icon" />
+                      {t("settings.video.batteryProfileEnabled")}
+                    </label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.batteryProfileEnabled}
+                        onChange={(e) => handleChange("batteryProfileEnabled", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+                  {settings.batteryProfileEnabled && (
+                    <>
+                      <div className="settings-row-top settings-row-top--compact">
+                        <label className="settings-label">{t("settings.video.batteryProfileBelow")}</label>
+                        <span className="settings-value-badge">{settings.batteryProfileBelowPercent}%</span>
+                      </div>
+                      <input
+                        type="range"
+                        className="settings-slider"
+                        min={5}
+                        max={100}
+                        step={5}
+                        value={settings.batteryProfileBelowPercent}
+                        aria-label={t("settings.video.batteryProfileBelow")}
+                        onChange={(e) => handleChange("batteryProfileBelowPercent", parseInt(e.target.value, 10))}
+                      />
+                      <StreamOverrideFields
+                        override={settings.batteryStreamProfile}
+                        resolutions={overrideResolutionOptions}
+                        fpsOptions={overrideFpsOptions}
+                        codecs={codecOptions}
+                        onChange={(next) => handleChange("batteryStreamProfile", next)}
+                      />
+                    </>
+                  )}
+                  <span className="settings-subtle-hint">{t("settings.video.batteryProfileHint")}</span>
+                </div>
+
+                {/* Competitive low-latency preset */}
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label settings-label--with-icon">
+                      <Zap size={15} className="settings-label-icon" />
+                      {t("settings.video.lowLatencyMode")}
+                    </label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.lowLatencyMode}
+                        onChange={(e) => handleChange("lowLatencyMode", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+                  <span className="settings-value-badge">
+                    {t("settings.video.lowLatencyEstimate", {
+                      videoBefore: lowLatencyGain.before.videoMs,
+                      videoAfter: lowLatencyGain.after.videoMs,
+                      audioBefore: lowLatencyGain.before.audioMs,
+                      audioAfter: lowLatencyGain.after.audioMs,
+                    })}
+                  </span>
+                  <span className="settings-subtle-hint">{t("settings.video.lowLatencyModeHint")}</span>
+                </div>
+
                 {/* Codec */}
                 <div className="settings-row">
                   <label className="settings-label settings-label--with-icon">
@@ -2800,6 +3453,19 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                     value={settings.maxBitrateMbps}
                     onChange={(e) => handleChange("maxBitrateMbps", parseInt(e.target.value, 10))}
                   />
+                  <div className="settings-chip-row">
+                    {MAX_BITRATE_PRESETS_MBPS.map((mbps) => (
+                      <button
+                        key={mbps}
+                        type="button"
+                        className={`settings-chip ${settings.maxBitrateMbps === mbps ? "active" : ""}`}
+                        onClick={() => handleChange("maxBitrateMbps", mbps)}
+                      >
+                        <span>{mbps} Mbps</span>
+                      </button>
+                    ))}
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.maxBitrateHint")}</span>
                 </div>
 
                 <div className="settings-row settings-row--column">
@@ -2840,6 +3506,19 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   <span className="settings-subtle-hint">{t("settings.video.recordingBitrateHint")}</span>
                 </div>
 
+                <div className="settings-row settings-row--column">
+                  <label className="settings-label">{t("settings.video.gameOverrides")}</label>
+                  <GameStreamOverrides
+                    overrides={settings.gameStreamOverrides}
+                    games={overrideGames}
+                    resolutions={overrideResolutionOptions}
+                    fpsOptions={overrideFpsOptions}
+                    codecs={codecOptions}
+                    onChange={(next) => handleChange("gameStreamOverrides", next)}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.video.gameOverridesHint")}</span>
+                </div>
+
                 <div className="settings-row settings-row--column">
                   <div className="settings-row-top settings-row-top--compact">
                     <label className="settings-label settings-label--wrap">
@@ -2871,6 +3550,23 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   )}
                 </div>
 
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label settings-label--wrap">
+                      <span className="settings-label-title">{t("settings.video.signalingProxy")}</span>
+                    </label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.signalingProxyEnabled}
+                        onChange={(e) => handleChange("signalingProxyEnabled", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.signalingProxyHint")}</span>
+                </div>
+
                 <div className="settings-row settings-row--column">
                   <div className="settings-row-top settings-row-top--compact">
                     <label className="settings-label settings-label--wrap">
@@ -2892,40 +3588,227 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                     {t("settings.video.experimentalL4SRequestHint")}
                   </span>
                 </div>
-              </div>
-            </section>
 
-            )}
-            {(showStreamVideo || showStreamCodecDiagnostics) && (
-            <div className="settings-advanced-wrap">
-              <button
-                type="button"
-                className="settings-advanced-toggle"
-                onClick={() => setCodecAdvancedOpen(v => !v)}
-              >
-                <SlidersHorizontal size={14} />
-                Advanced
-                <svg viewBox="0 0 16 16" width="12" height="12" fill="currentColor" className={`settings-advanced-chevron ${codecAdvancedOpen ? "flipped" : ""}`}>
-                  <path d="M4.47 5.97a.75.75 0 0 1 1.06 0L8 8.44l2.47-2.47a.75.75 0 1 1 1.06 1.06l-3 3a.75.75 0 0 1-1.06 0l-3-3a.75.75 0 0 1 0-1.06Z" />
-                </svg>
-              </button>
-              {codecAdvancedOpen && (
-                <section className="settings-section">
-                  {showAll && <div className="settings-section-context">{t("settings.sections.stream")}</div>}
-                  <div className="settings-section-header">
-                    <Cpu size={18} />
-                    <h2>Advanced</h2>
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label settings-label--wrap">
+                      <span className="settings-label-title">{t("settings.video.iceTransport")}</span>
+                    </label>
                   </div>
-                  <div className="settings-rows">
-                    {showStreamVideo && (
-                      <>
-                        <div className="settings-row settings-row--column">
-                          <label className="settings-label settings-label--with-icon">
-                            <Cpu size={15} className="settings-label-icon" />
-                            {t("settings.video.decoder")}
-                          </label>
-                          <div className="settings-chip-row">
-                            {accelerationOptions.map((option) => (
+                  <div className="settings-chip-row">
+                    <button
+                      type="button"
+                      className={`settings-chip ${settings.iceTransportPolicy === "all" ? "active" : ""}`}
+                      onClick={() => handleChange("iceTransportPolicy", "all")}
+                    >
+                      <span>{t("settings.video.iceTransportAll")}</span>
+                    </button>
+                    <button
+                      type="button"
+                      className={`settings-chip ${settings.iceTransportPolicy === "relay" ? "active" : ""}`}
+                      onClick={() => handleChange("iceTransportPolicy", "relay")}
+                    >
+                      <span>{t("settings.video.iceTransportRelay")}</span>
+                    </button>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.iceTransportHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label settings-label--wrap">
+                      <span className="settings-label-title">{t("settings.video.customIceServers")}</span>
+                    </label>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.customIceServersHint")}</span>
+                  <input
+                    type="text"
+                    className="settings-text-input"
+                    placeholder="turn:turn.example.com:3478"
+                    value={settings.customIceServerUrls}
+                    onChange={(e) => handleChange("customIceServerUrls", e.target.value)}
+                  />
+                  <input
+                    type="text"
+                    className="settings-text-input"
+                    placeholder={t("settings.video.customIceUsername")}
+                    autoComplete="off"
+                    value={settings.customIceUsername}
+                    onChange={(e) => handleChange("customIceUsername", e.target.value)}
+                  />
+                  <input
+                    type="password"
+                    className="settings-text-input"
+                    placeholder={t("settings.video.customIceCredential")}
+                    autoComplete="off"
+                    value={settings.customIceCredential}
+                    onChange={(e) => handleChange("customIceCredential", e.target.value)}
+                  />
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label settings-label--wrap">
+                      <span className="settings-label-title">{t("settings.video.webrtcUdpPortRange")}</span>
+                    </label>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.webrtcUdpPortRangeHint")}</span>
+                  <input
+                    type="text"
+                    className="settings-text-input"
+                    placeholder="50000-50100"
+                    value={settings.webrtcUdpPortRange}
+                    onChange={(e) => handleChange("webrtcUdpPortRange", e.target.value)}
+                  />
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label settings-label--wrap">
+                      <span className="settings-label-title">{t("settings.video.networkIpFamily")}</span>
+                    </label>
+                  </div>
+                  <div className="settings-chip-row">
+                    {([
+                      ["auto", "settings.video.networkIpFamilyAuto"],
+                      ["ipv4", "settings.video.networkIpFamilyIpv4"],
+                      ["ipv6", "settings.video.networkIpFamilyIpv6"],
+                    ] as const).map(([family, labelKey]) => (
+                      <button
+                        key={family}
+                        type="button"
+                        className={`settings-chip ${settings.networkIpFamily === family ? "active" : ""}`}
+                        onClick={() => handleChange("networkIpFamily", family)}
+                      >
+                        <span>{t(labelKey)}</span>
+                      </button>
+                    ))}
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.networkIpFamilyHint")}</span>
+                </div>
+
+                <div className="settings-row">
+                  <label className="settings-label">
+                    {t("settings.video.pathMtuProbe")}
+                    <span className="settings-hint">{t("settings.video.pathMtuProbeHint")}</span>
+                  </label>
+                  <label className="settings-toggle">
+                    <input
+                      type="checkbox"
+                      checked={settings.pathMtuProbe}
+                      onChange={(e) => handleChange("pathMtuProbe", e.target.checked)}
+                    />
+                    <span className="settings-toggle-track" />
+                  </label>
+                </div>
+
+                <div className="settings-row">
+                  <label className="settings-label">
+                    {t("settings.video.networkQosMarking")}
+                    <span className="settings-hint">{t("settings.video.networkQosMarkingHint")}</span>
+                  </label>
+                  <label className="settings-toggle">
+                    <input
+                      type="checkbox"
+                      checked={settings.networkQosMarking}
+                      onChange={(e) => handleChange("networkQosMarking", e.target.checked)}
+                    />
+                    <span className="settings-toggle-track" />
+                  </label>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <label className="settings-label">{t("settings.video.jitterBuffer")}</label>
+                  <div className="settings-chip-row">
+                    {JITTER_BUFFER_MS_PRESETS.map((bufferMs) => (
+                      <button
+                        key={bufferMs}
+                        type="button"
+                        className={`settings-chip ${settings.jitterBufferMs === bufferMs ? "active" : ""}`}
+                        onClick={() => handleChange("jitterBufferMs", bufferMs)}
+                      >
+                        <span>{t("settings.audio.bufferMs", { value: bufferMs })}</span>
+                      </button>
+                    ))}
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.video.jitterBufferHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">
+                      {t("settings.video.restream")}
+                      <span className="settings-hint">{t("settings.video.restreamHint")}</span>
+                    </label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.restreamEnabled}
+                        onChange={(e) => handleChange("restreamEnabled", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+                  {settings.restreamEnabled && (
+                    <>
+                      <input
+                        type="url"
+                        className="settings-text-input"
+                        placeholder="http://192.168.1.10:8889/opennow/whip"
+                        aria-label={t("settings.video.restreamWhipUrl")}
+                        value={settings.restreamWhipUrl}
+                        onChange={(e) => handleChange("restreamWhipUrl", e.target.value)}
+                      />
+                      <div className="settings-chip-row">
+                        {RESTREAM_MAX_HEIGHT_OPTIONS.map((height) => (
+                          <button
+                            key={height}
+                            type="button"
+                            className={`settings-chip ${settings.restreamMaxHeight === height ? "active" : ""}`}
+                            onClick={() => handleChange("restreamMaxHeight", height)}
+                          >
+                            <span>{height === 0 ? t("settings.video.restreamSourceResolution") : `${height}p`}</span>
+                          </button>
+                        ))}
+                      </div>
+                      <span className="settings-subtle-hint">{t("settings.video.restreamWhipUrlHint")}</span>
+                    </>
+                  )}
+                </div>
+              </div>
+            </section>
+
+            )}
+            {(showStreamVideo || showStreamCodecDiagnostics) && (
+            <div className="settings-advanced-wrap">
+              <button
+                type="button"
+                className="settings-advanced-toggle"
+                onClick={() => setCodecAdvancedOpen(v => !v)}
+              >
+                <SlidersHorizontal size={14} />
+                Advanced
+                <svg viewBox="0 0 16 16" width="12" height="12" fill="currentColor" className={`settings-advanced-chevron ${codecAdvancedOpen ? "flipped" : ""}`}>
+                  <path d="M4.47 5.97a.75.75 0 0 1 1.06 0L8 8.44l2.47-2.47a.75.75 0 1 1 1.06 1.06l-3 3a.75.75 0 0 1-1.06 0l-3-3a.75.75 0 0 1 0-1.06Z" />
+                </svg>
+              </button>
+              {codecAdvancedOpen && (
+                <section className="settings-section">
+                  {showAll && <div className="settings-section-context">{t("settings.sections.stream")}</div>}
+                  <div className="settings-section-header">
+                    <Cpu size={18} />
+                    <h2>Advanced</h2>
+                  </div>
+                  <div className="settings-rows">
+                    {showStreamVideo && (
+                      <>
+                        <div className="settings-row settings-row--column">
+                          <label className="settings-label settings-label--with-icon">
+                            <Cpu size={15} className="settings-label-icon" />
+                            {t("settings.video.decoder")}
+                          </label>
+                          <div className="settings-chip-row">
+                            {accelerationOptions.map((option) => (
                               <button
                                 key={`decoder-${option.value}`}
                                 className={`settings-chip ${settings.decoderPreference === option.value ? "active" : ""}`}
@@ -2964,6 +3847,53 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                           </div>
                           <span className="settings-subtle-hint">{t("settings.video.appliesAfterRestart")}</span>
                         </div>
+
+                        <div className="settings-row settings-row--column">
+                          <div className="settings-row-top">
+                            <label className="settings-label">{t("settings.video.driverWorkarounds")}</label>
+                            <label className="settings-toggle">
+                              <input
+                                type="checkbox"
+                                checked={settings.driverWorkaroundsEnabled}
+                                onChange={(e) => handleChange("driverWorkaroundsEnabled", e.target.checked)}
+                              />
+                              <span className="settings-toggle-track" />
+                            </label>
+                          </div>
+                          {driverWorkarounds && driverWorkarounds.matched.length > 0 ? (
+                            <div className="settings-chip-row">
+                              {driverWorkarounds.matched.map((workaround) => {
+                                const enabled = !settings.disabledDriverWorkarounds.includes(workaround.id);
+                                return (
+                                  <button
+                                    key={workaround.id}
+                                    type="button"
+                                    className={`settings-chip ${enabled ? "active" : ""}`}
+                                    aria-pressed={enabled}
+                                    title={workaround.description}
+                                    disabled={!settings.driverWorkaroundsEnabled}
+                                    onClick={() => handleChange(
+                                      "disabledDriverWorkarounds",
+                                      enabled
+                                        ? [...settings.disabledDriverWorkarounds, workaround.id]
+                                        : settings.disabledDriverWorkarounds.filter((id) => id !== workaround.id),
+                                    )}
+                                  >
+                                    <span>{workaround.id}</span>
+                                  </button>
+                                );
+                              })}
+                            </div>
+                          ) : (
+                            <span className="settings-subtle-hint">{t("settings.video.driverWorkaroundsNone")}</span>
+                          )}
+                          {driverWorkarounds?.restartRequired && (
+                            <span className="settings-subtle-hint">{t("settings.video.appliesAfterRestart")}</span>
+                          )}
+                          <span className="settings-subtle-hint">
+                            {t("settings.video.driverWorkaroundsHint", { path: driverWorkarounds?.rulesPath ?? "driver-workarounds.json" })}
+                          </span>
+                        </div>
                       </>
                     )}
 
@@ -3214,6 +4144,11 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                         : nativeStreamerStatus?.activeVideoBackend?.reason
                           ?? t("settings.nativeStreamer.videoPathDefaultHint")}
                     </span>
+                    {nativeStreamerStatus?.activeFlags && nativeStreamerStatus.activeFlags.length > 0 && (
+                      <span className="settings-subtle-hint">
+                        {t("settings.nativeStreamer.experimentalFlags", { flags: nativeStreamerStatus.activeFlags.join(", ") })}
+                      </span>
+                    )}
                   </div>
 
                   <div className="settings-row settings-row--column">
@@ -3258,6 +4193,21 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                       {t("settings.nativeStreamer.framePacingHint")}
                     </span>
                   </div>
+
+                  <div className="settings-row">
+                    <label className="settings-label">
+                      {t("settings.nativeStreamer.mediaThreadPriority")}
+                      <span className="settings-hint">{t("settings.nativeStreamer.mediaThreadPriorityHint")}</span>
+                    </label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.mediaThreadPriority}
+                        onChange={(e) => handleChange("mediaThreadPriority", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
                 </>
               )}
             </div>
@@ -3360,6 +4310,21 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   </div>
                 </div>
 
+                <div className="settings-row">
+                  <label className="settings-label">
+                    {t("settings.audio.microphoneBlocked")}
+                    <span className="settings-hint">{t("settings.audio.microphoneBlockedHint")}</span>
+                  </label>
+                  <label className="settings-toggle">
+                    <input
+                      type="checkbox"
+                      checked={settings.microphoneBlocked}
+                      onChange={(e) => handleChange("microphoneBlocked", e.target.checked)}
+                    />
+                    <span className="settings-toggle-track" />
+                  </label>
+                </div>
+
                 {settings.microphoneMode !== "disabled" && (
                   <div className="settings-row">
                     <label className="settings-label">
@@ -3425,6 +4390,94 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                     </div>
                   </div>
                 )}
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.audio.streamVolume")}</label>
+                    <span className="settings-value-badge">
+                      {settings.streamMuted ? t("settings.audio.muted") : t("settings.audio.volumePercent", { value: settings.streamVolume })}
+                    </span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={0}
+                    max={100}
+                    step={1}
+                    value={settings.streamVolume}
+                    onChange={(e) => onStreamVolumeChange(Number(e.target.value))}
+                    onPointerUp={onStreamVolumeCommit}
+                    onBlur={onStreamVolumeCommit}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.audio.streamVolumeHint")}</span>
+                </div>
+
+                <div className="settings-row">
+                  <label className="settings-label">{t("settings.audio.muteStream")}</label>
+                  <label className="settings-toggle">
+                    <input
+                      type="checkbox"
+                      checked={settings.streamMuted}
+                      onChange={(e) => handleChange("streamMuted", e.target.checked)}
+                    />
+                    <span className="settings-toggle-track" />
+                  </label>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label">{t("settings.audio.duckOnNotifications")}</label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.duckOnNotifications}
+                        onChange={(e) => handleChange("duckOnNotifications", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.audio.duckOnNotificationsHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <label className="settings-label">{t("settings.audio.outputBuffer")}</label>
+                  <div className="settings-chip-row">
+                    {AUDIO_BUFFER_MS_PRESETS.map((bufferMs) => (
+                      <button
+                        key={bufferMs}
+                        type="button"
+                        className={`settings-chip ${settings.audioBufferMs === bufferMs ? "active" : ""}`}
+                        onClick={() => handleChange("audioBufferMs", bufferMs)}
+                      >
+                        <span>{bufferMs === 0 ? t("settings.audio.systemDefault") : t("settings.audio.bufferMs", { value: bufferMs })}</span>
+                      </button>
+                    ))}
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.audio.outputBufferHint")}</span>
+                </div>
+
+                {isWindows && (
+                  <div className="settings-row settings-row--column">
+                    <label className="settings-label">{t("settings.audio.outputMode")}</label>
+                    <div className="settings-chip-row">
+                      <button
+                        type="button"
+                        className={`settings-chip ${settings.audioOutputMode === "shared" ? "active" : ""}`}
+                        onClick={() => handleChange("audioOutputMode", "shared")}
+                      >
+                        <span>{t("settings.audio.sharedMode")}</span>
+                      </button>
+                      <button
+                        type="button"
+                        className={`settings-chip ${settings.audioOutputMode === "exclusive" ? "active" : ""}`}
+                        onClick={() => handleChange("audioOutputMode", "exclusive")}
+                      >
+                        <span>{t("settings.audio.exclusiveMode")}</span>
+                      </button>
+                    </div>
+                    <span className="settings-subtle-hint">{t("settings.audio.outputModeHint")}</span>
+                  </div>
+                )}
               </div>
             </section>
         )}
@@ -3469,6 +4522,56 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   <span className="settings-subtle-hint">{t("settings.input.gyroscopeControlsHint")}</span>
                 </div>
 
+                <div className="settings-row settings-row--column">
+                  <label className="settings-label">{t("settings.input.controllerSlots")}</label>
+                  <div className="settings-chip-row">
+                    {settings.controllerSlotsEnabled.map((enabled, slot) => (
+                      <button
+                        key={slot}
+                        type="button"
+                        className={`settings-chip ${enabled ? "active" : ""}`}
+                        aria-pressed={enabled}
+                        onClick={() => handleChange(
+                          "controllerSlotsEnabled",
+                          settings.controllerSlotsEnabled.map((value, index) => (index === slot ? !value : value)),
+                        )}
+                      >
+                        <span>{t("settings.input.controllerSlot", { value: slot + 1 })}</span>
+                      </button>
+                    ))}
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.input.controllerSlotsHint")}</span>
+                </div>
+
+                {isLinux && (
+                  <div className="settings-row settings-row--column">
+                    <label className="settings-label">{t("settings.input.gamescopeCompatibility")}</label>
+                    <div className="settings-chip-row">
+                      {GAMESCOPE_COMPATIBILITY_MODES.map((mode) => (
+                        <button
+                          key={mode}
+                          type="button"
+                          className={`settings-chip ${settings.gamescopeCompatibility === mode ? "active" : ""}`}
+                          onClick={() => handleChange("gamescopeCompatibility", mode)}
+                        >
+                          <span>{mode === "off"
+                              ? t("settings.input.gamescopeCompatibilityOff")
+                              : t("settings.input.gamescopeCompatibilityAuto")}</span>
+                        </button>
+                      ))}
+                    </div>
+                    <span className="settings-subtle-hint">{t("settings.input.gamescopeCompatibilityHint")}</span>
+                    {gamescopeStatus?.session && (
+                      <span className="settings-subtle-hint">{t("settings.input.gamescopeDetected")}</span>
+                    )}
+                    {gamescopeStatus && gamescopeStatus.steamInputDevices.length > 0 && (
+                      <span className="settings-subtle-hint">
+                        {t("settings.input.steamInputDevices", { count: gamescopeStatus.steamInputDevices.length })}
+                      </span>
+                    )}
+                  </div>
+                )}
+
                 <div className="settings-row settings-row--top-aligned">
                   <label className="settings-label settings-label--wrap">
                     {t("settings.game.keyboardLayout")}
@@ -3573,10 +4676,71 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   <span className="settings-subtle-hint">{t("settings.input.mouseAcceleratorHint")}</span>
                 </div>
 
-                {/* Shortcuts */}
                 <div className="settings-row settings-row--column">
                   <div className="settings-row-top">
-                    <label className="settings-label">{t("settings.input.shortcuts")}</label>
+                    <label className="settings-label">{t("settings.input.mouseRawInput")}</label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.mouseRawInput}
+                        onChange={(e) => handleChange("mouseRawInput", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+                  <span className="settings-subtle-hint">{t("settings.input.mouseRawInputHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.input.gamepadMouseSpeed")}</label>
+                    <span className="settings-value-badge">{settings.gamepadMouseSpeed} px/s</span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={GAMEPAD_MOUSE_SPEED_MIN}
+                    max={GAMEPAD_MOUSE_SPEED_MAX}
+                    step={100}
+                    value={settings.gamepadMouseSpeed}
+                    onChange={(e) => handleChange("gamepadMouseSpeed", parseInt(e.target.value, 10))}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.input.gamepadMouseSpeedHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.input.gamepadMouseAcceleration")}</label>
+                    <span className="settings-value-badge">{settings.gamepadMouseAcceleration}%</span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={0}
+                    max={100}
+                    step={5}
+                    value={settings.gamepadMouseAcceleration}
+                    onChange={(e) => handleChange("gamepadMouseAcceleration", parseInt(e.target.value, 10))}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.input.gamepadMouseAccelerationHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.input.mouseFeelTest.title")}</label>
+                  </div>
+                  <MouseFeelTest
+                    sensitivity={settings.mouseSensitivity}
+                    accelerationPercent={settings.mouseAcceleration}
+                    rawInput={settings.mouseRawInput}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.input.mouseFeelTest.hint")}</span>
+                </div>
+
+                {/* Shortcuts */}
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.input.shortcuts")}</label>
                     <div className="settings-shortcut-actions">
                       <span className="settings-value-badge">{t("settings.input.editable")}</span>
                       <button
@@ -3743,6 +4907,82 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   />
                 </div>
 
+                <div className="settings-shortcut-row">
+                  <span className="settings-shortcut-label" id="shortcut-stream-mute-label">{t("settings.input.toggleStreamMute")}</span>
+                  <input
+                    type="text"
+                    id="shortcut-stream-mute"
+                    aria-labelledby="shortcut-stream-mute-label"
+                    readOnly
+                    className={`settings-text-input settings-shortcut-input ${streamMuteError ? "error" : ""}`}
+                    value={streamMuteInput}
+                    onFocus={(e) => e.target.select()}
+                    onBlur={() => handleShortcutBlur("shortcutToggleStreamMute", streamMuteInput)}
+                    onPaste={(e) => handleShortcutPaste("shortcutToggleStreamMute", e)}
+                    onKeyDown={(e) => handleShortcutCaptureKeyDown("shortcutToggleStreamMute", e)}
+                    placeholder={t("stream.shortcuts.clickHereThenPress")}
+                    title={t("stream.shortcuts.focusAndPress")}
+                    spellCheck={false}
+                  />
+                </div>
+
+                <div className="settings-shortcut-row">
+                  <span className="settings-shortcut-label" id="shortcut-volume-up-label">{t("settings.input.volumeUp")}</span>
+                  <input
+                    type="text"
+                    id="shortcut-volume-up"
+                    aria-labelledby="shortcut-volume-up-label"
+                    readOnly
+                    className={`settings-text-input settings-shortcut-input ${volumeUpError ? "error" : ""}`}
+                    value={volumeUpInput}
+                    onFocus={(e) => e.target.select()}
+                    onBlur={() => handleShortcutBlur("shortcutVolumeUp", volumeUpInput)}
+                    onPaste={(e) => handleShortcutPaste("shortcutVolumeUp", e)}
+                    onKeyDown={(e) => handleShortcutCaptureKeyDown("shortcutVolumeUp", e)}
+                    placeholder={t("stream.shortcuts.clickHereThenPress")}
+                    title={t("stream.shortcuts.focusAndPress")}
+                    spellCheck={false}
+                  />
+                </div>
+
+                <div className="settings-shortcut-row">
+                  <span className="settings-shortcut-label" id="shortcut-volume-down-label">{t("settings.input.volumeDown")}</span>
+                  <input
+                    type="text"
+                    id="shortcut-volume-down"
+                    aria-labelledby="shortcut-volume-down-label"
+                    readOnly
+                    className={`settings-text-input settings-shortcut-input ${volumeDownError ? "error" : ""}`}
+                    value={volumeDownInput}
+                    onFocus={(e) => e.target.select()}
+                    onBlur={() => handleShortcutBlur("shortcutVolumeDown", volumeDownInput)}
+                    onPaste={(e) => handleShortcutPaste("shortcutVolumeDown", e)}
+                    onKeyDown={(e) => handleShortcutCaptureKeyDown("shortcutVolumeDown", e)}
+                    placeholder={t("stream.shortcuts.clickHereThenPress")}
+                    title={t("stream.shortcuts.focusAndPress")}
+                    spellCheck={false}
+                  />
+                </div>
+
+                <div className="settings-shortcut-row">
+                  <span className="settings-shortcut-label" id="shortcut-text-input-label">{t("settings.input.toggleTextInput")}</span>
+                  <input
+                    type="text"
+                    id="shortcut-text-input"
+                    aria-labelledby="shortcut-text-input-label"
+                    readOnly
+                    className={`settings-text-input settings-shortcut-input ${textInputModeError ? "error" : ""}`}
+                    value={textInputModeInput}
+                    onFocus={(e) => e.target.select()}
+                    onBlur={() => handleShortcutBlur("shortcutToggleTextInput", textInputModeInput)}
+                    onPaste={(e) => handleShortcutPaste("shortcutToggleTextInput", e)}
+                    onKeyDown={(e) => handleShortcutCaptureKeyDown("shortcutToggleTextInput", e)}
+                    placeholder={t("stream.shortcuts.clickHereThenPress")}
+                    title={t("stream.shortcuts.focusAndPress")}
+                    spellCheck={false}
+                  />
+                </div>
+
                 <div className="settings-shortcut-row">
                   <span className="settings-shortcut-label" id="shortcut-sidebar-label">{t("settings.input.toggleStreamSidebar")}</span>
                   <input
@@ -3757,7 +4997,7 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                 </div>
               </div>
 
-              {(toggleStatsError || togglePointerLockError || toggleFullscreenError || stopStreamError || toggleAntiAfkError || toggleMicrophoneError || screenshotError || recordingError) && (
+              {(toggleStatsError || togglePointerLockError || toggleFullscreenError || stopStreamError || toggleAntiAfkError || toggleMicrophoneError || screenshotError || recordingError || streamMuteError || volumeUpError || volumeDownError || textInputModeError) && (
                 <span className="settings-input-hint">
                   {toggleStatsError
                     || togglePointerLockError
@@ -3766,11 +5006,15 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                     || toggleAntiAfkError
                     || toggleMicrophoneError
                     || screenshotError
-                    || recordingError}
+                    || recordingError
+                    || streamMuteError
+                    || volumeUpError
+                    || volumeDownError
+                    || textInputModeError}
                 </span>
               )}
 
-              {!toggleStatsError && !togglePointerLockError && !toggleFullscreenError && !stopStreamError && !toggleAntiAfkError && !toggleMicrophoneError && !screenshotError && !recordingError && (
+              {!toggleStatsError && !togglePointerLockError && !toggleFullscreenError && !stopStreamError && !toggleAntiAfkError && !toggleMicrophoneError && !screenshotError && !recordingError && !streamMuteError && !volumeUpError && !volumeDownError && !textInputModeError && (
                 <span className="settings-shortcut-hint">
                   {t("settings.input.shortcutHint", {
                     examples: t("stream.shortcuts.examples"),
@@ -3831,6 +5075,25 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   </div>
                 </div>
 
+                <div className="settings-row settings-row--column">
+                  <label className="settings-label">
+                    {t("settings.interface.theme")}
+                    <span className="settings-hint">{t("settings.interface.themeHint")}</span>
+                  </label>
+                  <div className="settings-chip-row">
+                    {APP_THEME_OPTIONS.map((option) => (
+                      <button
+                        key={option.value}
+                        type="button"
+                        className={`settings-chip ${settings.appTheme === option.value ? "active" : ""}`}
+                        onClick={() => handleChange("appTheme", option.value)}
+                      >
+                        <span>{t(option.labelKey)}</span>
+                      </button>
+                    ))}
+                  </div>
+                </div>
+
                 <div className="settings-row">
                   <label className="settings-label">
                     {t("settings.interface.accentColor")}
@@ -3896,6 +5159,21 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   </div>
                 </div>
 
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top settings-row-top--compact">
+                    <label className="settings-label">{t("settings.interface.themeFile")}</label>
+                    <button type="button" className="settings-export-logs-btn" onClick={reloadUserTheme}>
+                      <RefreshCcw size={16} />
+                      {t("settings.interface.reloadThemeFile")}
+                    </button>
+                  </div>
+                  <span className="settings-subtle-hint">
+                    {customAccentHex
+                      ? t("settings.interface.themeFileLoaded", { accent: customAccentHex })
+                      : t("settings.interface.themeFileHint", { path: userTheme?.path ?? "theme.json" })}
+                  </span>
+                </div>
+
                 {/* Appearance toggles */}
                 <div className="settings-toggle-grid">
                   <div className="settings-row">
@@ -3943,6 +5221,33 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                     </label>
                   </div>
 
+                  {!settings.hideServerSelector && (
+                    <div className="settings-row settings-row--column">
+                      <div className="settings-row-top settings-row-top--compact">
+                        <label className="settings-label settings-label--wrap">
+                          <span className="settings-label-title">{t("settings.interface.queueService")}</span>
+                        </label>
+                      </div>
+                      <input
+                        type="url"
+                        className="settings-text-input"
+                        placeholder="https://"
+                        aria-label={t("settings.interface.queueServiceUrl")}
+                        value={settings.queueServiceUrl}
+                        onChange={(e) => handleChange("queueServiceUrl", e.target.value)}
+                      />
+                      <input
+                        type="text"
+                        className="settings-text-input"
+                        aria-label={t("settings.interface.queueServicePublicKey")}
+                        placeholder={t("settings.interface.queueServicePublicKey")}
+                        value={settings.queueServicePublicKey}
+                        onChange={(e) => handleChange("queueServicePublicKey", e.target.value)}
+                      />
+                      <span className="settings-subtle-hint">{t("settings.interface.queueServiceHint")}</span>
+                    </div>
+                  )}
+
                   <div className="settings-row">
                     <label className="settings-label">
                       {t("settings.interface.showAntiAfkIndicator")}
@@ -3973,6 +5278,73 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                     </label>
                   </div>
 
+                  <div className="settings-row settings-row--column">
+                    <label className="settings-label">{t("settings.interface.fullscreenMode")}</label>
+                    <div className="settings-chip-row">
+                      {(["borderless", "exclusive"] as const).map((mode) => (
+                        <button
+                          key={mode}
+                          type="button"
+                          className={`settings-chip ${settings.fullscreenMode === mode ? "active" : ""}`}
+                          onClick={() => handleChange("fullscreenMode", mode)}
+                        >
+                          <span>
+                            {mode === "borderless"
+                              ? t("settings.interface.fullscreenModeBorderless")
+                              : t("settings.interface.fullscreenModeExclusive")}
+                          </span>
+                        </button>
+                      ))}
+                    </div>
+                    <span className="settings-subtle-hint">{t("settings.interface.fullscreenModeHint")}</span>
+                  </div>
+
+                  <div className="settings-row">
+                    <label className="settings-label">
+                      {t("settings.interface.altEnterFullscreen")}
+                      <span className="settings-hint">{t("settings.interface.altEnterFullscreenHint")}</span>
+                    </label>
+                    <label className="settings-toggle">
+                      <input
+                        type="checkbox"
+                        checked={settings.altEnterFullscreen}
+                        onChange={(e) => handleChange("altEnterFullscreen", e.target.checked)}
+                      />
+                      <span className="settings-toggle-track" />
+                    </label>
+                  </div>
+
+                  {displays.length > 1 && (
+                    <div className="settings-row settings-row--column">
+                      <label className="settings-label">{t("settings.interface.fullscreenDisplay")}</label>
+                      <div className="settings-chip-row">
+                        <button
+                          type="button"
+                          className={`settings-chip ${settings.fullscreenDisplayId === null ? "active" : ""}`}
+                          onClick={() => handleChange("fullscreenDisplayId", null)}
+                        >
+                          <span>{t("settings.interface.fullscreenDisplayCurrent")}</span>
+                        </button>
+                        {displays.map((display) => (
+                          <button
+                            key={display.id}
+                            type="button"
+                            className={`settings-chip ${settings.fullscreenDisplayId === display.id ? "active" : ""}`}
+                            onClick={() => handleChange("fullscreenDisplayId", display.id)}
+                            title={`${display.bounds.width}x${display.bounds.height}`}
+                          >
+                            <span>
+                              {display.primary
+                                ? t("settings.interface.fullscreenDisplayPrimary", { label: display.label })
+                                : display.label}
+                            </span>
+                          </button>
+                        ))}
+                      </div>
+                      <span className="settings-subtle-hint">{t("settings.interface.fullscreenDisplayHint")}</span>
+                    </div>
+                  )}
+
                   <div className="settings-row">
                     <label className="settings-label">
                       {t("settings.interface.controllerMode")}
@@ -4017,6 +5389,50 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                       <span className="settings-toggle-track" />
                     </label>
                   </div>
+
+                  <div className="settings-row settings-row--column">
+                    <div className="settings-row-top settings-row-top--compact">
+                      <label className="settings-label settings-label--wrap">
+                        <span className="settings-label-title">{t("settings.interface.telemetry")}</span>
+                      </label>
+                    </div>
+                    <div className="settings-chip-row">
+                      {([
+                        ["off", "settings.interface.telemetryOff"],
+                        ["local", "settings.interface.telemetryLocal"],
+                        ["upload", "settings.interface.telemetryUpload"],
+                      ] as const).map(([mode, labelKey]) => (
+                        <button
+                          key={mode}
+                          type="button"
+                          className={`settings-chip ${settings.telemetryMode === mode ? "active" : ""}`}
+                          disabled={mode === "upload" && !settings.telemetryEndpoint.trim()}
+                          onClick={() => handleChange("telemetryMode", mode)}
+                        >
+                          <span>{t(labelKey)}</span>
+                        </button>
+                      ))}
+                    </div>
+                    <span className="settings-subtle-hint">{t("settings.interface.telemetryHint")}</span>
+                    {settings.telemetryMode !== "off" && (
+                      <input
+                        type="url"
+                        className="settings-text-input"
+                        placeholder="https://"
+                        aria-label={t("settings.interface.telemetryEndpoint")}
+                        value={settings.telemetryEndpoint}
+                        onChange={(e) => {
+                          handleChange("telemetryEndpoint", e.target.value);
+                          if (settings.telemetryMode === "upload" && !e.target.value.trim()) {
+                            handleChange("telemetryMode", "local");
+                          }
+                        }}
+                      />
+                    )}
+                    {settings.telemetryMode !== "off" && !settings.telemetryEndpoint.trim() && (
+                      <span className="settings-subtle-hint">{t("settings.interface.telemetryEndpointRequired")}</span>
+                    )}
+                  </div>
                 </div>
 
                 <div className="settings-row settings-row--column">
@@ -4036,6 +5452,27 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   <span className="settings-subtle-hint">{t("settings.interface.posterSizeHint")}</span>
                 </div>
 
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.interface.librarySync")}</label>
+                    <span className="settings-value-badge">
+                      {settings.librarySyncMinutes === 0
+                        ? t("settings.interface.off")
+                        : t("settings.interface.everyMinutes", { count: settings.librarySyncMinutes })}
+                    </span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={0}
+                    max={240}
+                    step={5}
+                    value={settings.librarySyncMinutes}
+                    onChange={(e) => handleChange("librarySyncMinutes", parseInt(e.target.value, 10))}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.interface.librarySyncHint")}</span>
+                </div>
+
                 <div className="settings-row">
                   <label className="settings-label">
                     {t("settings.interface.showSessionTimeRemainingInStatsOverlay")}
@@ -4051,6 +5488,175 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                   </label>
                 </div>
 
+                <div className="settings-row settings-row--column">
+                  <label className="settings-label">
+                    {t("settings.interface.statsPanelFields")}
+                    <span className="settings-hint">{t("settings.interface.statsPanelFieldsHint")}</span>
+                  </label>
+                  <div className="settings-chip-row">
+                    {statsPanelFieldOrder.map((field) => {
+                      const shownIndex = settings.statsPanelFields.indexOf(field);
+                      const shown = shownIndex >= 0;
+                      return (
+                        <div key={field} className="settings-stats-field">
+                          {shown && (
+                            <button
+                              type="button"
+                              className="settings-stats-field-move"
+                              aria-label={t("settings.interface.statsPanelMoveEarlier", { field: t(`settings.interface.statsPanelField.${field}`) })}
+                              disabled={shownIndex === 0}
+                              onClick={() => moveStatsPanelField(field, -1)}
+                            >
+                              <ChevronLeft size={12} />
+                            </button>
+                          )}
+                          <button
+                            type="button"
+                            className={`settings-chip ${shown ? "active" : ""}`}
+                            aria-pressed={shown}
+                            onClick={() => toggleStatsPanelField(field)}
+                          >
+                            <span>{t(`settings.interface.statsPanelField.${field}`)}</span>
+                          </button>
+                          {shown && (
+                            <button
+                              type="button"
+                              className="settings-stats-field-move"
+                              aria-label={t("settings.interface.statsPanelMoveLater", { field: t(`settings.interface.statsPanelField.${field}`) })}
+                              disabled={shownIndex === settings.statsPanelFields.length - 1}
+                              onClick={() => moveStatsPanelField(field, 1)}
+                            >
+                              <ChevronRight size={12} />
+                            </button>
+                          )}
+                        </div>
+                      );
+                    })}
+                  </div>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.interface.statsPanelScale")}</label>
+                    <span className="settings-value-badge">{settings.statsPanelScale}%</span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={80}
+                    max={150}
+                    step={5}
+                    value={settings.statsPanelScale}
+                    onChange={(e) => handleChange("statsPanelScale", parseInt(e.target.value, 10))}
+                  />
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.interface.statsPanelOpacity")}</label>
+                    <span className="settings-value-badge">{settings.statsPanelOpacity}%</span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={20}
+                    max={100}
+                    step={5}
+                    value={settings.statsPanelOpacity}
+                    onChange={(e) => handleChange("statsPanelOpacity", parseInt(e.target.value, 10))}
+                  />
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.interface.idleDisconnect")}</label>
+                    <span className="settings-value-badge">
+                      {settings.idleDisconnectMinutes === 0
+                        ? t("settings.interface.off")
+                        : t("settings.interface.afterMinutes", { count: settings.idleDisconnectMinutes })}
+                    </span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={0}
+                    max={120}
+                    step={5}
+                    value={settings.idleDisconnectMinutes}
+                    onChange={(e) => handleChange("idleDisconnectMinutes", parseInt(e.target.value, 10))}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.interface.idleDisconnectHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.interface.playReminder")}</label>
+                    <span className="settings-value-badge">
+                      {settings.playReminderMinutes === 0
+                        ? t("settings.interface.off")
+                        : t("settings.interface.afterMinutes", { count: settings.playReminderMinutes })}
+                    </span>
+                  </div>
+                  <input
+                    type="range"
+                    className="settings-slider"
+                    min={0}
+                    max={480}
+                    step={15}
+                    value={settings.playReminderMinutes}
+                    onChange={(e) => handleChange("playReminderMinutes", parseInt(e.target.value, 10))}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.interface.playReminderHint")}</span>
+                </div>
+
+                <div className="settings-row settings-row--column">
+                  <div className="settings-row-top">
+                    <label className="settings-label">{t("settings.interface.playCurfew")}</label>
+                    <span className="settings-value-badge">
+                      {settings.playCurfew || t("settings.interface.off")}
+                    </span>
+                  </div>
+                  <input
+                    type="time"
+                    className="settings-text-input"
+                    aria-label={t("settings.interface.playCurfew")}
+                    value={settings.playCurfew}
+                    onChange={(e) => handleChange("playCurfew", e.target.value)}
+                  />
+                  <span className="settings-subtle-hint">{t("settings.interface.playCurfewHint")}</span>
+                </div>
+
+                <div className="settings-row">
+                  <label className="settings-label">
+                    {t("settings.interface.playLimitDisconnect")}
+                    <span className="settings-hint">{t("settings.interface.playLimitDisconnectHint")}</span>
+                  </label>
+                  <label className="settings-toggle">
+                    <input
+                      type="checkbox"
+                      checked={settings.playLimitDisconnect}
+                      disabled={settings.playReminderMinutes === 0 && !settings.playCurfew}
+                      onChange={(e) => handleChange("playLimitDisconnect", e.target.checked)}
+                    />
+                    <span className="settings-toggle-track" />
+                  </label>
+                </div>
+
+                <div className="settings-row">
+                  <label className="settings-label">
+                    {t("settings.interface.endSessionOnExit")}
+                    <span className="settings-hint">{t("settings.interface.endSessionOnExitHint")}</span>
+                  </label>
+                  <label className="settings-toggle">
+                    <input
+                      type="checkbox"
+                      checked={settings.endSessionOnExit}
+                      onChange={(e) => handleChange("endSessionOnExit", e.target.checked)}
+                    />
+                    <span className="settings-toggle-track" />
+                  </label>
+                </div>
+
                 {/* Session Counter */}
                 <div className="settings-row">
                   <label className="settings-label">
@@ -4116,6 +5722,165 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
           </>
         )}
 
+        {showStorage && (
+          <section className="settings-section">
+            {showAll && <div className="settings-section-context">{t("settings.sections.storage")}</div>}
+            <div className="settings-section-header">
+              <h2>{t("settings.sections.storage")}</h2>
+            </div>
+            <div className="settings-rows">
+              {STORAGE_CATEGORIES.map((category) => {
+                const entry = storageUsage.usage?.find((item) => item.category === category);
+                return (
+                  <div key={category} className="settings-row">
+                    <label className="settings-label settings-label--wrap">
+                      <span className="settings-label-title">
+                        {t(`settings.storage.${category}`)}
+                        <span className="settings-value-badge" title={entry?.path}>
+                          {entry ? formatBytes(entry.bytes) : "…"}
+                        </span>
+                      </span>
+                      <span className="settings-hint">{t(`settings.storage.${category}Hint`)}</span>
+                    </label>
+                    <button
+                      type="button"
+                      className="settings-delete-cache-btn"
+                      disabled={storageUsage.clearing !== null || !entry || entry.bytes === 0}
+                      onClick={async () => {
+                        if (category === "recordings" && !window.confirm(t("settings.storage.clearRecordingsConfirm"))) {
+                          return;
+                        }
+                        try {
+                          await storageUsage.clear(category);
+                        } catch (err) {
+                          console.error(`[Settings] Failed to clear ${category}:`, err);
+                          alert(t("settings.storage.clearFailed"));
+                        }
+                      }}
+                    >
+                      {storageUsage.clearing === category ? <Loader size={16} className="spin" /> : <Trash2 size={16} />}
+                      {t("settings.storage.clear")}
+                    </button>
+                  </div>
+                );
+              })}
+
+              <div className="settings-row">
+                <label className="settings-label settings-label--wrap">
+                  {t("settings.storage.mediaDirectory")}
+                  <span className="settings-hint" title={settings.mediaDirectory || undefined}>
+                    {settings.mediaDirectory || t("settings.storage.mediaDirectoryDefault")}
+                  </span>
+                </label>
+                <div className="settings-updater-actions">
+                  <button
+                    type="button"
+                    className="settings-export-logs-btn"
+                    onClick={async () => {
+                      const directory = await window.openNow.selectMediaDirectory().catch((error) => {
+                        console.error("[Settings] Failed to choose media folder:", error);
+                        return null;
+                      });
+                      if (directory) {
+                        handleChange("mediaDirectory", directory);
+                      }
+                    }}
+                  >
+                    <HardDrive size={16} />
+                    {t("settings.storage.mediaDirectoryChoose")}
+                  </button>
+                  {settings.mediaDirectory ? (
+                    <button type="button" className="settings-chip" onClick={() => handleChange("mediaDirectory", "")}>
+                      {t("settings.storage.mediaDirectoryReset")}
+                    </button>
+                  ) : null}
+                </div>
+              </div>
+
+              <div className="settings-row settings-row--column">
+                <label className="settings-label">{t("settings.storage.cacheLimit")}</label>
+                <div className="settings-chip-row">
+                  {DISK_CACHE_LIMIT_MB_PRESETS.map((limitMb) => (
+                    <button
+                      key={limitMb}
+                      type="button"
+                      className={`settings-chip ${settings.diskCacheLimitMb === limitMb ? "active" : ""}`}
+                      onClick={() => handleChange("diskCacheLimitMb", limitMb)}
+                    >
+                      <span>{limitMb === 0
+                          ? t("settings.storage.cacheLimitAuto")
+                          : limitMb >= 1024 ? `${limitMb / 1024} GB` : `${limitMb} MB`}</span>
+                    </button>
+                  ))}
+                </div>
+                <span className="settings-subtle-hint">{t("settings.storage.cacheLimitHint")}</span>
+              </div>
+            </div>
+          </section>
+        )}
+
+        {showDebug && (
+          <section className="settings-section">
+            {showAll && <div className="settings-section-context">{t("settings.sections.debug")}</div>}
+            <div className="settings-section-header">
+              <h2>{t("settings.sections.debug")}</h2>
+            </div>
+            <div className="settings-rows">
+              <div className="settings-row settings-row--column">
+                <label className="settings-label">{t("settings.debug.logFilter")}</label>
+                <span className="settings-subtle-hint">{t("settings.debug.logFilterHint")}</span>
+                <input
+                  type="text"
+                  className="settings-text-input"
+                  placeholder="webrtc=debug,nativestreamer=warn,info"
+                  spellCheck={false}
+                  value={settings.logFilter}
+                  onChange={(e) => handleChange("logFilter", e.target.value)}
+                />
+              </div>
+
+              <div className="settings-row settings-row--column">
+                <div className="settings-row-top settings-row-top--compact">
+                  <label className="settings-label">{t("settings.debug.liveLog")}</label>
+                  <button type="button" className="settings-chip" onClick={logTail.clear}>
+                    <Trash2 size={13} />
+                    <span>{t("settings.debug.clearLiveLog")}</span>
+                  </button>
+                </div>
+                <input
+                  type="search"
+                  className="settings-text-input"
+                  placeholder={t("settings.debug.searchLiveLog")}
+                  value={logTailQuery}
+                  onChange={(e) => setLogTailQuery(e.target.value)}
+                />
+                <div className="settings-log-tail" role="log" aria-live="off">
+                  {visibleLogRows.length === 0 ? (
+                    <span className="settings-subtle-hint">{t("settings.debug.liveLogEmpty")}</span>
+                  ) : (
+                    visibleLogRows.map((row) => (
+                      <div key={`${row.source}-${row.sequence}`} className={`settings-log-line settings-log-line--${row.level}`}>
+                        <span className="settings-log-source">{row.source}</span>
+                        {row.text}
+                      </div>
+                    ))
+                  )}
+                </div>
+              </div>
+
+              <div className="settings-row settings-row--column">
+                <label className="settings-label">{t("settings.debug.lastSessionTimeline")}</label>
+                <span className="settings-subtle-hint">{t("settings.debug.lastSessionTimelineHint")}</span>
+                {lastSessionTimelineSource ? (
+                  <SessionTimelinePanel source={lastSessionTimelineSource} defaultOpen />
+                ) : (
+                  <span className="settings-subtle-hint">{t("settings.debug.lastSessionTimelineEmpty")}</span>
+                )}
+              </div>
+            </div>
+          </section>
+        )}
+
         {showAbout && (
           <section className="settings-section">
             {showAll && <div className="settings-section-context">{t("settings.sections.about")}</div>}
@@ -4198,6 +5963,34 @@ export function SettingsPage({ settings, regions, onSettingChange, codecResults,
                       {t("settings.about.restartToInstall")}
                     </button>
                   ) : null}
+                  {updaterState.releaseNotes?.length ? (
+                    <button type="button" className="settings-export-logs-btn" onClick={() => setChangelogOpen(true)}>
+                      <Info size={16} />
+                      {t("settings.about.whatsNew")}
+                    </button>
+                  ) : null}
+                </div>
+              </div>
+              {changelogOpen && updaterState.releaseNotes?.length ? (
+                <ChangelogDialog notes={updaterState.releaseNotes} onClose={() => setChangelogOpen(false)} />
+              ) : null}
+
+              <div className="settings-row settings-row--column">
+                <label className="settings-label settings-label--wrap">
+                  {t("settings.about.updateChannel")}
+                  <span className="settings-hint">{t("settings.about.updateChannelHint")}</span>
+                </label>
+                <div className="settings-chip-row">
+                  {UPDATE_CHANNELS.map((channel) => (
+                    <button
+                      key={channel}
+                      type="button"
+                      className={`settings-chip ${settings.updateChannel === channel ? "active" : ""}`}
+                      onClick={() => handleChange("updateChannel", channel)}
+                    >
+                      {channel === "beta" ? t("settings.about.channelBeta") : t("settings.about.channelStable")}
+                    </button>
+                  ))}
                 </div>
               </div>
 
@@ -1,4 +1,4 @@
-import { Loader2, Monitor, Cpu, Wifi, X, XCircle } from "lucide-react";
+import { ExternalLink, Loader2, Monitor, Cpu, Wifi, X, XCircle } from "lucide-react";
 import type { JSX, Ref } from "react";
 import {
   getPreferredSessionAdMediaUrl,
@@ -30,6 +30,16 @@ export interface StreamLoadingProps {
     title: string;
     description: string;
     code?: string;
+    steps?: string[];
+    help?: {
+      label: string;
+      onClick: () => void;
+    };
+    action?: {
+      label: string;
+      busy?: boolean;
+      onClick: () => void;
+    };
   };
   onAdPlaybackEvent?: (event: QueueAdPlaybackEvent, adId: string) => void;
   adPreviewRef?: Ref<QueueAdPreviewHandle>;
@@ -217,7 +227,31 @@ export function StreamLoading({
               <>
                 <p className="sload-error-title">{error.title}</p>
                 <p className="sload-error-desc">{error.description}</p>
+                {error.steps && error.steps.length > 0 && (
+                  <ol className="sload-error-steps" aria-label={t("streamLoading.errorSteps")}>
+                    {error.steps.map((step) => (
+                      <li key={step}>{step}</li>
+                    ))}
+                  </ol>
+                )}
                 {error.code && <p className="sload-error-code">{error.code}</p>}
+                {error.help && (
+                  <button type="button" className="sload-error-help" onClick={error.help.onClick}>
+                    <ExternalLink size={13} />
+                    <span>{error.help.label}</span>
+                  </button>
+                )}
+                {error.action && (
+                  <button
+                    type="button"
+                    className="sload-error-action"
+                    onClick={error.action.onClick}
+                    disabled={error.action.busy}
+                  >
+                    {error.action.busy && <Loader2 size={14} className="sload-error-action-spinner" />}
+                    <span>{error.action.label}</span>
+                  </button>
+                )}
               </>
             )}
             {status === "queue" && estimatedWait && (
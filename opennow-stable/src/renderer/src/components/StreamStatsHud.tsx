@@ -1,7 +1,9 @@
-import { useMemo, useState } from "react";
+import { Fragment, useMemo, useState } from "react";
+import type { CSSProperties } from "react";
 import { AnimatePresence, m } from "motion/react";
 import { AlertTriangle, ChevronDown } from "lucide-react";
 import type { JSX } from "react";
+import type { StatsPanelField } from "@shared/gfn";
 import type { StreamLagReason } from "../gfn/webrtcClient";
 import type { StreamDiagnosticsStore } from "../utils/streamDiagnosticsStore";
 import { useStreamDiagnosticsStore } from "../utils/streamDiagnosticsStore";
@@ -10,9 +12,15 @@ import {
   getInputQueueColor,
   getPacketLossColor,
   getRttColor,
+  getTemperatureColor,
   getTimingColor,
+  getUsageColor,
 } from "../utils/streamDiagnosticsFormat";
+import { formatAudioStats, formatBitstreamHealth, formatPathMtu } from "../lib/streamDiagnostics";
+import { useSystemUsage } from "../hooks/useSystemUsage";
 import { panelSpring, smoothEase, surfaceRevealTransition } from "./MotionProvider";
+import { RouteTracePanel, type RouteTraceState } from "./RouteTracePanel";
+import { SessionTimelinePanel, type SessionTimelineSource } from "./SessionTimelinePanel";
 import { useTranslation } from "../i18n";
 
 function getLagReasonLabel(reason: StreamLagReason): string {
@@ -47,11 +55,30 @@ function getLagReasonColor(reason: StreamLagReason): string {
   }
 }
 
+function formatPercent(value: number | null): string {
+  return value === null ? "--" : `${value}%`;
+}
+
+export interface StatsPanelLayout {
+  fields: readonly StatsPanelField[];
+  scalePercent: number;
+  opacityPercent: number;
+}
+
+type StatsPanelKpi = { label: string; value: string; color?: string; title?: string };
+
 export interface StreamStatsHudProps {
   diagnosticsStore: StreamDiagnosticsStore;
   gstreamerEnabled: boolean;
   serverRegion?: string;
   sessionTimeRemainingText: string | null;
+  /** Session time left for the "sessionTimer" panel field, whether or not the details show it */
+  sessionTimerText: string | null;
+  layout: StatsPanelLayout;
+  /** Route diagnostics to the media server; absent when there is no server to trace */
+  routeTrace?: RouteTraceState | null;
+  /** Significant events of the running stream, for spotting when it degraded */
+  sessionTimeline?: SessionTimelineSource | null;
   hintsVisible?: boolean;
 }
 
@@ -60,12 +87,17 @@ export function StreamStatsHud({
   gstreamerEnabled,
   serverRegion,
   sessionTimeRemainingText,
+  sessionTimerText,
+  layout,
+  routeTrace = null,
+  sessionTimeline = null,
   hintsVisible = false,
 }: StreamStatsHudProps): JSX.Element {
   const { t } = useTranslation();
   const stats = useStreamDiagnosticsStore(diagnosticsStore);
   const [expanded, setExpanded] = useState(false);
   const [advancedOpen, setAdvancedOpen] = useState(false);
+  const systemUsage = useSystemUsage(expanded);
 
   const hasLiveBitrate = stats.bitrateKbps > 0;
   const bitrateKbps = hasLiveBitrate ? stats.bitrateKbps : stats.targetBitrateKbps;
@@ -84,9 +116,7 @@ export function StreamStatsHud({
   const bitratePerformanceColor = getBitratePerformanceColor(bitratePerformancePercent);
   const hasResolution = stats.nativeRendererActive || stats.resolution !== "";
   const displayFps = Math.max(stats.decodeFps, stats.renderFps);
-  const primaryText = hasResolution
-    ? `${stats.resolution || "Native renderer"}${displayFps > 0 ? ` · ${displayFps}fps` : ""}`
-    : t("stream.stats.connecting");
+  const primaryText = hasResolution ? stats.resolution || "Native renderer" : t("stream.stats.connecting");
   const hasCodec = Boolean(stats.codec && stats.codec !== "");
   const regionLabel = stats.serverRegion || serverRegion || "";
   const decodeColor = getTimingColor(stats.decodeTimeMs, 8, 16);
@@ -111,6 +141,31 @@ export function StreamStatsHud({
   const hasPacketLoss = stats.packetLossPercent > 0;
   const hasIssues = hasLagIssue || hasPacketLoss;
 
+  const kpis = layout.fields.map((field): StatsPanelKpi => {
+    switch (field) {
+      case "fps":
+        return { label: t("stream.stats.fps"), value: displayFps > 0 ? `${displayFps}` : "--" };
+      case "rtt":
+        return { label: t("stream.stats.rtt"), value: rttText, color: rttColor, title: t("stream.stats.roundTripLatency") };
+      case "bitrate":
+        return { label: t("stream.stats.bitrateShort"), value: bitrateLabel };
+      case "loss":
+        return { label: lossLabel, value: `${stats.packetLossPercent.toFixed(1)}%`, color: lossColor, title: lossTitle };
+      case "decode":
+        return { label: t("stream.stats.decode"), value: dText, color: decodeColor, title: t("stream.stats.decodeTime") };
+      case "sessionTimer":
+        return {
+          label: t("stream.stats.timeRemainingShort"),
+          value: sessionTimerText ?? "--",
+          title: t("sidebar.sessionTimeRemainingTitle"),
+        };
+    }
+  });
+  const layoutStyle = {
+    "--sv-stats-scale": layout.scalePercent / 100,
+    "--sv-stats-bg-alpha": `${layout.opacityPercent}%`,
+  } as CSSProperties;
+
   const advancedLines = useMemo(() => {
     const lines: string[] = [];
     lines.push(
@@ -130,7 +185,7 @@ export function StreamStatsHud({
     }
     if (stats.nativeTransitionSummary || stats.nativeQueueMode || stats.nativeCapsFramerate) {
       lines.push(
-        `Native transition ${stats.nativeTransitionSummary ?? "none"} · queue ${stats.nativeQueueMode ?? "unknown"} · caps ${stats.nativeCapsFramerate ?? "unknown"}${typeof stats.nativeRequestedFps === "number" ? ` · requested ${stats.nativeRequestedFps}fps` : ""}${typeof stats.nativeFramesPendingToPresent === "number" ? ` · pending ${stats.nativeFramesPendingToPresent}` : ""}${typeof stats.nativePartialFlushCount === "number" || typeof stats.nativeCompleteFlushCount === "number" ? ` · flush ${stats.nativePartialFlushCount ?? 0}/${stats.nativeCompleteFlushCount ?? 0}` : ""}`,
+        `Native transition ${stats.nativeTransitionSummary ?? "none"} · queue ${stats.nativeQueueMode ?? "unknown"} · caps ${stats.nativeCapsFramerate ?? "unknown"}${typeof stats.nativeRequestedFps === "number" ? ` · requested ${stats.nativeRequestedFps}fps` : ""}${typeof stats.nativeFramesPendingToPresent === "number" ? ` · pending ${stats.nativeFramesPendingToPresent}` : ""}${typeof stats.nativePartialFlushCount === "number" || typeof stats.nativeCompleteFlushCount === "number" ? ` · flush ${stats.nativePartialFlushCount ?? 0}/${stats.nativeCompleteFlushCount ?? 0}` : ""}${stats.nativeFramesSkippedAhead ? ` · skipped ahead ${stats.nativeFramesSkippedAhead}` : ""}`,
       );
     }
     if (stats.nativeRequestedStreamingFeaturesSummary || stats.nativeFinalizedStreamingFeaturesSummary) {
@@ -138,6 +193,47 @@ export function StreamStatsHud({
         `Stream features requested ${stats.nativeRequestedStreamingFeaturesSummary ?? "none"} · finalized ${stats.nativeFinalizedStreamingFeaturesSummary ?? "none"}`,
       );
     }
+    const bitstreamHealth = formatBitstreamHealth(stats);
+    if (bitstreamHealth) {
+      lines.push(bitstreamHealth);
+    }
+    if (stats.nativePipelineStageSummary) {
+      lines.push(`Native pipeline ${stats.nativePipelineStageSummary}`);
+    }
+    if (stats.nativeHdrMetadata) {
+      lines.push(`HDR ${stats.nativeHdrMetadata}`);
+    }
+    if (stats.nativeEncoderTimecode) {
+      const latency = stats.nativeEncoderLatencyMs !== undefined ? ` · encoder→client ${stats.nativeEncoderLatencyMs}ms` : "";
+      lines.push(`Encoder time ${stats.nativeEncoderTimecode}${latency}`);
+    }
+    if (typeof stats.nativeMemoryBudgetMb === "number") {
+      lines.push(`Decode queue budget ${stats.nativeMemoryBudgetMb}MB`);
+    }
+    if (stats.nativeThreadPrioritySummary) {
+      lines.push(`Media threads ${stats.nativeThreadPrioritySummary}`);
+    }
+    const audioStats = formatAudioStats(stats);
+    if (audioStats) {
+      lines.push(audioStats);
+    }
+    if (typeof stats.avSyncOffsetMs === "number") {
+      lines.push(
+        `A/V offset ${stats.avSyncOffsetMs > 0 ? "+" : ""}${stats.avSyncOffsetMs.toFixed(0)}ms${stats.avSyncAudioCorrectionMs ? ` · audio delay ${stats.avSyncAudioCorrectionMs.toFixed(0)}ms` : ""}`,
+      );
+    }
+    if (stats.clickToPhotonSummary) {
+      lines.push(`Click-to-photon ${stats.clickToPhotonSummary}`);
+    }
+    const pathMtu = formatPathMtu(stats);
+    if (pathMtu) {
+      lines.push(pathMtu);
+    }
+    if (stats.candidatePairs) {
+      for (const pair of stats.candidatePairs.split("\n")) {
+        lines.push(`ICE ${pair}`);
+      }
+    }
     const gpuRegion = [stats.gpuType, regionLabel].filter(Boolean).join(" · ");
     if (gpuRegion) lines.push(gpuRegion);
     if (hasLagIssue) {
@@ -167,6 +263,7 @@ export function StreamStatsHud({
       exit={{ opacity: 0, x: -10, y: 6 }}
       transition={surfaceRevealTransition}
       layout
+      style={layoutStyle}
       aria-label={t("stream.stats.overlayLabel")}
     >
       <button
@@ -178,19 +275,21 @@ export function StreamStatsHud({
       >
         <div className="sv-stats-toggle-main">
           <p className="sv-stats-primary">{primaryText}</p>
-          <div className="sv-stats-toggle-meta">
-            <span className="sv-stats-kpi">
-              <span className="sv-stats-kpi-label">{t("stream.stats.rtt")}</span>
-              <span className="sv-stats-kpi-val sv-stats-kpi-val--rtt" style={{ color: rttColor }}>
-                {rttText}
-              </span>
-            </span>
-            <span className="sv-stats-kpi-divider" aria-hidden />
-            <span className="sv-stats-kpi">
-              <span className="sv-stats-kpi-label">{t("stream.stats.bitrateShort")}</span>
-              <span className="sv-stats-kpi-val">{bitrateLabel}</span>
-            </span>
-          </div>
+          {kpis.length > 0 && (
+            <div className="sv-stats-toggle-meta">
+              {kpis.map((kpi, index) => (
+                <Fragment key={layout.fields[index]}>
+                  {index > 0 && <span className="sv-stats-kpi-divider" aria-hidden />}
+                  <span className="sv-stats-kpi" title={kpi.title}>
+                    <span className="sv-stats-kpi-label">{kpi.label}</span>
+                    <span className="sv-stats-kpi-val" style={kpi.color ? { color: kpi.color } : undefined}>
+                      {kpi.value}
+                    </span>
+                  </span>
+                </Fragment>
+              ))}
+            </div>
+          )}
         </div>
 
         <div className="sv-stats-toggle-trail">
@@ -325,6 +424,45 @@ export function StreamStatsHud({
                 )}
               </div>
 
+              {systemUsage && (
+                <div className="sv-stats-metrics">
+                  <span className="sv-stats-chip" title={t("stream.stats.cpuUsage")}>
+                    CPU{" "}
+                    <span className="sv-stats-chip-val" style={{ color: getUsageColor(systemUsage.cpuPercent) }}>
+                      {formatPercent(systemUsage.cpuPercent)}
+                    </span>
+                  </span>
+                  {systemUsage.gpuPercent !== null && (
+                    <span className="sv-stats-chip" title={t("stream.stats.gpuUsage")}>
+                      GPU{" "}
+                      <span className="sv-stats-chip-val" style={{ color: getUsageColor(systemUsage.gpuPercent) }}>
+                        {formatPercent(systemUsage.gpuPercent)}
+                      </span>
+                    </span>
+                  )}
+                  {systemUsage.temperatureC !== null && (
+                    <span className="sv-stats-chip" title={t("stream.stats.socTemperature")}>
+                      SoC{" "}
+                      <span className="sv-stats-chip-val" style={{ color: getTemperatureColor(systemUsage.temperatureC) }}>
+                        {systemUsage.temperatureC}°C
+                      </span>
+                    </span>
+                  )}
+                  {systemUsage.gpuTemperatureC !== null && (
+                    <span className="sv-stats-chip" title={t("stream.stats.gpuTemperature")}>
+                      GPU{" "}
+                      <span className="sv-stats-chip-val" style={{ color: getTemperatureColor(systemUsage.gpuTemperatureC) }}>
+                        {systemUsage.gpuTemperatureC}°C
+                      </span>
+                    </span>
+                  )}
+                </div>
+              )}
+
+              {routeTrace && <RouteTracePanel {...routeTrace} />}
+
+              {sessionTimeline && <SessionTimelinePanel source={sessionTimeline} />}
+
               {advancedLines.length > 0 && (
                 <div className="sv-stats-advanced">
                   <button
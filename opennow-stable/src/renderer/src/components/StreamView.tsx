@@ -2,21 +2,27 @@ import { useState, useEffect, useCallback, useRef, useMemo } from "react";
 import { createPortal } from "react-dom";
 import { AnimatePresence } from "motion/react";
 import type { JSX } from "react";
-import { Maximize, Minimize, Loader2, LogOut, Clock3, AlertTriangle, Mic, MicOff, Camera, ChevronLeft, ChevronRight, Save, Trash2, X, Circle, Square, Video, FolderOpen } from "lucide-react";
+import { Maximize, Minimize, PictureInPicture2, Loader2, LogOut, Clock3, AlertTriangle, Mic, MicOff, Camera, ChevronLeft, ChevronRight, Save, Trash2, X, Circle, Square, Video, FolderOpen, RotateCcw, MousePointer2, Shield, ShieldAlert } from "lucide-react";
 import SideBar from "./SideBar";
 import { SessionStartedSplash } from "./SessionStartedSplash";
-import { StreamStatsHud } from "./StreamStatsHud";
+import { StreamStatsHud, type StatsPanelLayout } from "./StreamStatsHud";
+import type { RouteTraceState } from "./RouteTracePanel";
+import type { SessionTimelineSource } from "./SessionTimelinePanel";
+import { OnScreenKeyboard } from "./OnScreenKeyboard";
 import type { StreamDiagnosticsStore } from "../utils/streamDiagnosticsStore";
 import { useStreamDiagnosticsSelector } from "../utils/streamDiagnosticsStore";
 import type { MicState } from "../gfn/microphoneManager";
 import { getStoreDisplayName, getStoreIconComponent } from "./GameCard";
 import { RemainingPlaytimeIndicator, SessionElapsedIndicator } from "./ElapsedSessionIndicators";
-import type { MicrophoneMode, ScreenshotEntry, RecordingEntry, SubscriptionInfo } from "@shared/gfn";
+import type { MicrophoneMode, NativeVideoLayer, ScreenshotEntry, RecordingEntry, SubscriptionInfo } from "@shared/gfn";
 import { formatShortcutForDisplay, isShortcutMatch, normalizeShortcut, shortcutFromKeyboardEvent } from "../shortcuts";
-import { addStreamShortcutActionListener } from "../streamShortcutActions";
+import { appEvents } from "../lib/appEvents";
 import { useMicMeter } from "../hooks/useMicMeter";
 import { formatElapsed } from "../utils/timeFormat";
 import { useTranslation } from "../i18n";
+import { takeCommittedText } from "../gfn/textInput";
+import type { OnScreenKeyboardCode } from "../gfn/onScreenKeyboard";
+import { CLIPBOARD_LIVE_WINDOW_MS, listLocalCaptures } from "../lib/privacyGuard";
 
 const ANTI_AFK_TOGGLE_ACK_MS = 5000;
 
@@ -36,6 +42,10 @@ interface StreamViewProps {
     toggleMicrophone?: string;
     screenshot: string;
     recording: string;
+    toggleStreamMute?: string;
+    volumeUp?: string;
+    volumeDown?: string;
+    toggleTextInput?: string;
   };
   hideStreamButtons?: boolean;
   serverRegion?: string;
@@ -50,6 +60,9 @@ interface StreamViewProps {
   isStreaming: boolean;
   sessionCounterEnabled: boolean;
   showSessionTimeRemainingInStatsOverlay: boolean;
+  statsPanelLayout: StatsPanelLayout;
+  routeTrace?: RouteTraceState | null;
+  sessionTimeline?: SessionTimelineSource | null;
   sessionTimeRemainingSeconds: number | null;
   sessionClockShowEveryMinutes: number;
   sessionClockShowDurationSeconds: number;
@@ -59,7 +72,14 @@ interface StreamViewProps {
     tone: "warn" | "critical";
     secondsLeft?: number;
   } | null;
+  /** Briefly shown after the local network interfaces change mid-stream */
+  networkChangeNotice?: boolean;
+  /** Requeue toggle attached to the session time warning near the end of a free-tier session */
+  sessionRequeue?: { armed: boolean; onToggle: () => void } | null;
   isFullscreen: boolean;
+  /** Watch-only always-on-top window; only offered for the web client. */
+  miniPlayer?: boolean;
+  onToggleMiniPlayer?: () => void;
   isConnecting: boolean;
   gameTitle: string;
   recordingBitrateMbps: number | null;
@@ -71,12 +91,28 @@ interface StreamViewProps {
   onToggleMicrophone?: () => void;
   mouseSensitivity: number;
   onMouseSensitivityChange: (value: number) => void;
+  streamVolume: number;
+  streamMuted: boolean;
+  onStreamVolumeChange: (value: number) => void;
+  /** Called when the volume slider is released, to save the value without waiting */
+  onStreamVolumeCommit: () => void;
+  onToggleStreamMute: () => void;
+  /** Video layers the native streamer can switch between, when the server offers more than one */
+  videoLayers?: { layers: NativeVideoLayer[]; active: string; pausedAtSender: boolean } | null;
+  onVideoLayerChange?: (layerId: string) => void;
   mouseAcceleration: number;
   onMouseAccelerationChange: (value: number) => void;
   onRequestPointerLock?: () => void;
   onReleasePointerLock?: () => void;
   microphoneMode: MicrophoneMode;
   onMicrophoneModeChange: (value: MicrophoneMode) => void;
+  /** Privacy kill switches; the indicator lists local capture and can cut it off */
+  privacy?: {
+    /** When clipboard text was last sent into the stream this session */
+    clipboardSentAtMs: number | null;
+    onBlockMicrophone: () => void;
+    onStopClipboardSync: () => void;
+  };
   onScreenshotShortcutChange: (value: string) => void;
   onRecordingShortcutChange: (value: string) => void;
   onShowSessionTimeRemainingInStatsOverlayChange: (value: boolean) => void;
@@ -84,6 +120,16 @@ interface StreamViewProps {
   micTrack?: MediaStreamTrack | null;
   className?: string;
   allowEscapeToExitFullscreen?: boolean;
+  textInputActive?: boolean;
+  onSendText?: (text: string) => void;
+  onCloseTextInput?: () => void;
+  onScreenKeyboardActive?: boolean;
+  /** A controller's right stick is currently driving the mouse pointer */
+  gamepadMouseActive?: boolean;
+  onSendKey?: (code: OnScreenKeyboardCode) => void;
+  onCloseOnScreenKeyboard?: () => void;
+  latencyProbeActive?: boolean;
+  onLatencyProbeChange?: (enabled: boolean) => void;
 }
 
 
@@ -199,6 +245,80 @@ function AntiAfkIndicator({
   );
 }
 
+function PrivacyIndicator({
+  diagnosticsStore,
+  privacy,
+}: {
+  diagnosticsStore: StreamDiagnosticsStore;
+  privacy: NonNullable<StreamViewProps["privacy"]>;
+}): JSX.Element {
+  const { t } = useTranslation();
+  const [open, setOpen] = useState(false);
+  const [nowMs, setNowMs] = useState(() => Date.now());
+  const { clipboardSentAtMs } = privacy;
+
+  useEffect(() => {
+    setNowMs(Date.now());
+    if (clipboardSentAtMs === null) return;
+    // Re-render once the send ages out so the row drops back from live.
+    const remainingMs = clipboardSentAtMs + CLIPBOARD_LIVE_WINDOW_MS - Date.now();
+    if (remainingMs <= 0) return;
+    const timer = window.setTimeout(() => setNowMs(Date.now()), remainingMs);
+    return () => window.clearTimeout(timer);
+  }, [clipboardSentAtMs]);
+
+  const { micState, micEnabled } = useStreamDiagnosticsSelector(
+    diagnosticsStore,
+    (stats) => ({ micState: stats.micState ?? "uninitialized", micEnabled: stats.micEnabled ?? false }),
+    (prev, next) => prev.micState === next.micState && prev.micEnabled === next.micEnabled,
+  );
+  const captures = listLocalCaptures({ micState, micEnabled, clipboardSentAtMs, nowMs });
+  const live = captures.some((capture) => capture.live);
+
+  return (
+    <div className={`sv-privacy${live ? " sv-privacy--live" : ""}`}>
+      <button
+        type="button"
+        className="sv-privacy-badge"
+        onClick={() => setOpen((prev) => !prev)}
+        aria-expanded={open}
+        title={t("stream.privacy.title")}
+      >
+        {captures.length > 0 ? <ShieldAlert size={14} /> : <Shield size={14} />}
+        <span>
+          {live
+            ? t("stream.privacy.live")
+            : captures.length > 0
+              ? t("stream.privacy.active", { count: captures.length })
+              : t("stream.privacy.none")}
+        </span>
+      </button>
+      {open && (
+        <div className="sv-privacy-panel">
+          {captures.length === 0 && <p className="sv-privacy-empty">{t("stream.privacy.noneDetail")}</p>}
+          {captures.map((capture) => (
+            <div key={capture.kind} className="sv-privacy-row">
+              <span className={`sv-privacy-dot${capture.live ? " is-live" : ""}`} />
+              <span className="sv-privacy-label">
+                {capture.kind === "microphone"
+                  ? t(capture.live ? "stream.privacy.microphoneLive" : "stream.privacy.microphoneMuted")
+                  : t(capture.live ? "stream.privacy.clipboardLive" : "stream.privacy.clipboardSent")}
+              </span>
+              <button
+                type="button"
+                className="sv-privacy-kill"
+                onClick={capture.kind === "microphone" ? privacy.onBlockMicrophone : privacy.onStopClipboardSync}
+              >
+                {t("stream.privacy.turnOff")}
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
 function RecordingIndicator({
   diagnosticsStore,
   showAntiAfkIndicator,
@@ -362,6 +482,20 @@ function SidebarMicMutedBadge({
   return <span className="settings-value-badge">Muted</span>;
 }
 
+function ClickToPhotonReadout({
+  diagnosticsStore,
+}: {
+  diagnosticsStore: StreamDiagnosticsStore;
+}): JSX.Element {
+  const { t } = useTranslation();
+  const summary = useStreamDiagnosticsSelector(
+    diagnosticsStore,
+    (stats) => stats.clickToPhotonSummary,
+  );
+
+  return <span className="sidebar-hint">{summary ?? t("sidebar.clickToPhotonWaiting")}</span>;
+}
+
 function VideoFocusOnReady({
   diagnosticsStore,
   isConnecting,
@@ -391,6 +525,59 @@ function VideoFocusOnReady({
   return null;
 }
 
+function TextInputBar({
+  onSendText,
+  onClose,
+}: {
+  onSendText: (text: string) => void;
+  onClose: () => void;
+}): JSX.Element {
+  const { t } = useTranslation();
+  const inputRef = useRef<HTMLInputElement | null>(null);
+  const composingRef = useRef(false);
+
+  useEffect(() => {
+    inputRef.current?.focus({ preventScroll: true });
+  }, []);
+
+  const flush = useCallback((): void => {
+    const field = inputRef.current;
+    if (!field) return;
+    const text = takeCommittedText(field, composingRef.current);
+    if (text) {
+      onSendText(text);
+    }
+  }, [onSendText]);
+
+  return (
+    <div className="sv-text-input">
+      <input
+        ref={inputRef}
+        type="text"
+        className="sv-text-input-field"
+        aria-label={t("stream.textInput.label")}
+        placeholder={t("stream.textInput.placeholder")}
+        autoComplete="off"
+        spellCheck={false}
+        onCompositionStart={() => {
+          composingRef.current = true;
+        }}
+        onCompositionEnd={() => {
+          composingRef.current = false;
+          flush();
+        }}
+        onInput={flush}
+        onKeyDown={(e) => {
+          if (e.key === "Escape" && !e.nativeEvent.isComposing) {
+            onClose();
+          }
+        }}
+        onBlur={onClose}
+      />
+    </div>
+  );
+}
+
 export function StreamView({
   videoRef,
   audioRef,
@@ -408,11 +595,18 @@ export function StreamView({
   isStreaming,
   sessionCounterEnabled,
   showSessionTimeRemainingInStatsOverlay,
+  statsPanelLayout,
+  routeTrace = null,
+  sessionTimeline = null,
   sessionTimeRemainingSeconds,
   sessionClockShowEveryMinutes,
   sessionClockShowDurationSeconds,
   streamWarning,
+  networkChangeNotice = false,
+  sessionRequeue = null,
   isFullscreen,
+  miniPlayer = false,
+  onToggleMiniPlayer,
   isConnecting,
   gameTitle,
   recordingBitrateMbps,
@@ -424,12 +618,20 @@ export function StreamView({
   onToggleMicrophone,
   mouseSensitivity,
   onMouseSensitivityChange,
+  streamVolume,
+  streamMuted,
+  onStreamVolumeChange,
+  onStreamVolumeCommit,
+  onToggleStreamMute,
+  videoLayers,
+  onVideoLayerChange,
   mouseAcceleration,
   onMouseAccelerationChange,
   onRequestPointerLock,
   onReleasePointerLock,
   microphoneMode,
   onMicrophoneModeChange,
+  privacy,
   onScreenshotShortcutChange,
   onRecordingShortcutChange,
   onShowSessionTimeRemainingInStatsOverlayChange,
@@ -437,6 +639,15 @@ export function StreamView({
   micTrack,
   hideStreamButtons = false,
   allowEscapeToExitFullscreen,
+  textInputActive = false,
+  onSendText,
+  onCloseTextInput,
+  onScreenKeyboardActive = false,
+  gamepadMouseActive = false,
+  onSendKey,
+  onCloseOnScreenKeyboard,
+  latencyProbeActive = false,
+  onLatencyProbeChange,
   className,
 }: StreamViewProps): JSX.Element {
   const { t } = useTranslation();
@@ -1338,7 +1549,7 @@ export function StreamView({
   }, [onReleasePointerLock]);
 
   useEffect(() => {
-    return addStreamShortcutActionListener((action) => {
+    return appEvents.on("stream:shortcut", (action) => {
       if (action === "screenshot") {
         void captureScreenshot();
         return;
@@ -1450,7 +1661,7 @@ export function StreamView({
   }, [exitPrompt.open, isConnecting, showSideBar]);
 
   return (
-    <div className={["sv", className].filter(Boolean).join(" ")}>
+    <div className={["sv", miniPlayer && "sv--mini", className].filter(Boolean).join(" ")}>
       <video
         ref={setVideoRef}
         autoPlay
@@ -1590,11 +1801,108 @@ export function StreamView({
                     <span className="sidebar-hint">Dynamic turn boost strength (1% = off-like, 150% = strongest).</span>
                   </div>
                 </section>
+                {onLatencyProbeChange && !gstreamerEnabled && (
+                  <>
+                    <div className="sidebar-separator" aria-hidden="true" />
+                    <section className="sidebar-section">
+                      <div className="sidebar-section-header">
+                        <span>{t("sidebar.latency")}</span>
+                        <span className="sidebar-section-sub">{t("sidebar.latencySub")}</span>
+                      </div>
+                      <div className="sidebar-row sidebar-row--column">
+                        <span className="sidebar-label">{t("sidebar.clickToPhoton")}</span>
+                        <div className="sidebar-chip-row">
+                          <button
+                            type="button"
+                            className={`sidebar-chip${latencyProbeActive ? " sidebar-chip--active" : ""}`}
+                            onClick={() => onLatencyProbeChange(!latencyProbeActive)}
+                          >
+                            <span>{latencyProbeActive ? t("sidebar.stopMeasuring") : t("sidebar.measure")}</span>
+                          </button>
+                        </div>
+                        {latencyProbeActive && <ClickToPhotonReadout diagnosticsStore={diagnosticsStore} />}
+                        <span className="sidebar-hint">{t("sidebar.clickToPhotonHint")}</span>
+                      </div>
+                    </section>
+                  </>
+                )}
+                {videoLayers && videoLayers.layers.length > 1 && onVideoLayerChange && (
+                  <>
+                    <div className="sidebar-separator" aria-hidden="true" />
+                    <section className="sidebar-section">
+                      <div className="sidebar-section-header">
+                        <span>{t("sidebar.video")}</span>
+                        <span className="sidebar-section-sub">{t("sidebar.videoLayersSub")}</span>
+                      </div>
+                      <div className="sidebar-row sidebar-row--column">
+                        <span className="sidebar-label">{t("sidebar.videoLayer")}</span>
+                        <div className="sidebar-chip-row">
+                          {videoLayers.layers.map((layer) => (
+                            <button
+                              key={layer.id}
+                              type="button"
+                              className={`sidebar-chip${videoLayers.active === layer.id ? " sidebar-chip--active" : ""}`}
+                              onClick={() => onVideoLayerChange(layer.id)}
+                            >
+                              <span>
+                                {layer.width && layer.height
+                                  ? `${layer.width}×${layer.height}`
+                                  : t("sidebar.videoLayerFallback", { id: layer.id })}
+                              </span>
+                            </button>
+                          ))}
+                        </div>
+                        <span className="sidebar-hint">
+                          {t(videoLayers.pausedAtSender ? "sidebar.videoLayerHint" : "sidebar.videoLayerHintLocal")}
+                        </span>
+                      </div>
+                    </section>
+                  </>
+                )}
                 <div className="sidebar-separator" aria-hidden="true" />
                 <section className="sidebar-section">
                   <div className="sidebar-section-header">
                     <span>Audio</span>
-                    <span className="sidebar-section-sub">Microphone handling</span>
+                    <span className="sidebar-section-sub">{t("sidebar.audioSub")}</span>
+                  </div>
+                  <div className="sidebar-row sidebar-row--column">
+                    <div className="sidebar-row-top">
+                      <span className="sidebar-label">{t("sidebar.streamVolume")}</span>
+                      <span className="settings-value-badge">{streamMuted ? t("settings.audio.muted") : t("settings.audio.volumePercent", { value: Math.round(streamVolume) })}</span>
+                    </div>
+                    <input
+                      type="range"
+                      className="settings-slider"
+                      min={0}
+                      max={100}
+                      step={1}
+                      value={Math.round(streamVolume)}
+                      aria-label={t("sidebar.streamVolume")}
+                      onChange={(event) => {
+                        const next = Number(event.target.value);
+                        if (Number.isFinite(next)) {
+                          onStreamVolumeChange(next);
+                        }
+                      }}
+                      onPointerUp={onStreamVolumeCommit}
+                      onBlur={onStreamVolumeCommit}
+                    />
+                    <div className="sidebar-chip-row">
+                      <button
+                        type="button"
+                        className={`sidebar-chip${streamMuted ? " sidebar-chip--active" : ""}`}
+                        onClick={onToggleStreamMute}
+                      >
+                        <span>{streamMuted ? t("sidebar.unmute") : t("sidebar.mute")}</span>
+                      </button>
+                    </div>
+                    <span className="sidebar-hint">
+                      {t("sidebar.streamVolumeHint")}
+                      {shortcuts.toggleStreamMute ? ` ${t("sidebar.streamMuteShortcut", { shortcut: shortcuts.toggleStreamMute })}` : ""}
+                      {shortcuts.volumeUp && shortcuts.volumeDown
+                        ? ` ${t("sidebar.streamVolumeShortcuts", { up: shortcuts.volumeUp, down: shortcuts.volumeDown })}`
+                        : ""}
+                    </span>
                   </div>
                   <div className="sidebar-row sidebar-row--column">
                     <div className="sidebar-row-top">
@@ -1990,6 +2298,23 @@ export function StreamView({
             {streamWarning.message}
             {warningSeconds ? ` · ${warningSeconds} left` : ""}
           </span>
+          {sessionRequeue && (
+            <button
+              type="button"
+              className={`sv-time-warning-action${sessionRequeue.armed ? " sv-time-warning-action--armed" : ""}`}
+              onClick={sessionRequeue.onToggle}
+            >
+              <RotateCcw size={12} />
+              <span>{sessionRequeue.armed ? t("stream.requeue.armed") : t("stream.requeue.arm")}</span>
+            </button>
+          )}
+        </div>
+      )}
+
+      {networkChangeNotice && (
+        <div className="sv-afk-ack sv-network-notice" role="status" aria-live="polite">
+          <span className="sv-afk-ack-dot" aria-hidden />
+          <span>{t("stream.networkChanged")}</span>
         </div>
       )}
 
@@ -2014,6 +2339,10 @@ export function StreamView({
             gstreamerEnabled={gstreamerEnabled}
             serverRegion={serverRegion}
             sessionTimeRemainingText={showSessionTimeRemainingInStats ? sessionTimeRemainingText : null}
+            sessionTimerText={sessionTimeRemainingText}
+            layout={statsPanelLayout}
+            routeTrace={routeTrace}
+            sessionTimeline={sessionTimeline}
             hintsVisible={showHints}
           />
         )}
@@ -2036,6 +2365,17 @@ export function StreamView({
         isConnecting={isConnecting}
       />
 
+      {privacy && !isConnecting && (
+        <PrivacyIndicator diagnosticsStore={diagnosticsStore} privacy={privacy} />
+      )}
+
+      {gamepadMouseActive && !isConnecting && (
+        <div className="sv-padmouse" title={t("stream.gamepadMouse.hint")}>
+          <MousePointer2 size={14} />
+          <span>{t("stream.gamepadMouse.label")}</span>
+        </div>
+      )}
+
       {/* Recording indicator (top-left, stacked below other badges) */}
       <RecordingIndicator
         diagnosticsStore={diagnosticsStore}
@@ -2078,6 +2418,33 @@ export function StreamView({
         document.body,
       )}
 
+      {miniPlayer && onToggleMiniPlayer && (
+        <div className="sv-mini-controls">
+          <span className="sv-mini-badge">{t("stream.miniPlayer.watchOnly")}</span>
+          <button
+            type="button"
+            className="sv-mini-expand"
+            onClick={onToggleMiniPlayer}
+            title={t("stream.miniPlayer.exit")}
+            aria-label={t("stream.miniPlayer.exit")}
+          >
+            <Maximize size={16} />
+          </button>
+        </div>
+      )}
+
+      {/* Mini player toggle */}
+      {!hideStreamButtons && !miniPlayer && onToggleMiniPlayer && !isConnecting && (
+        <button
+          className="sv-mini"
+          onClick={onToggleMiniPlayer}
+          title={t("stream.miniPlayer.enter")}
+          aria-label={t("stream.miniPlayer.enter")}
+        >
+          <PictureInPicture2 size={18} />
+        </button>
+      )}
+
       {/* Fullscreen toggle */}
       {!hideStreamButtons && (
         <button
@@ -2110,9 +2477,18 @@ export function StreamView({
           <div className="sv-hint"><kbd>{shortcuts.toggleFullscreen}</kbd><span>Full screen</span></div>
           <div className="sv-hint"><kbd>{shortcuts.stopStream}</kbd><span>Stop</span></div>
           {shortcuts.toggleMicrophone && <div className="sv-hint"><kbd>{shortcuts.toggleMicrophone}</kbd><span>Mic</span></div>}
+          {shortcuts.toggleTextInput && !gstreamerEnabled && <div className="sv-hint"><kbd>{shortcuts.toggleTextInput}</kbd><span>{t("stream.textInput.hint")}</span></div>}
         </div>
       )}
 
+      {textInputActive && onSendText && onCloseTextInput && (
+        <TextInputBar onSendText={onSendText} onClose={onCloseTextInput} />
+      )}
+
+      {onScreenKeyboardActive && onSendText && onSendKey && onCloseOnScreenKeyboard && (
+        <OnScreenKeyboard onSendText={onSendText} onSendKey={onSendKey} onClose={onCloseOnScreenKeyboard} />
+      )}
+
       {/* Game title (bottom-center, fades) */}
       <StreamTitleBar
         diagnosticsStore={diagnosticsStore}
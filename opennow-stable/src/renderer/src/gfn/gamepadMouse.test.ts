@@ -0,0 +1,51 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import {
+  GamepadMouse,
+  gamepadMouseVelocity,
+  isGamepadMouseChord,
+  normalizeGamepadMouseTuning,
+} from "./gamepadMouse";
+
+const linear = { speed: 1000, accelerationPercent: 0 };
+
+test("acceleration slows small deflections but keeps full speed at the edge", () => {
+  assert.deepEqual(gamepadMouseVelocity(1, 0, linear), { vx: 1000, vy: 0 });
+  assert.deepEqual(gamepadMouseVelocity(0, 0, linear), { vx: 0, vy: 0 });
+  const curved = { speed: 1000, accelerationPercent: 50 };
+  assert.equal(gamepadMouseVelocity(0.5, 0, curved).vx, 250);
+  assert.equal(gamepadMouseVelocity(0, -1, curved).vy, -1000);
+  assert.deepEqual(normalizeGamepadMouseTuning({ speed: 99_999, accelerationPercent: -5 }), {
+    speed: 3000,
+    accelerationPercent: 0,
+  });
+});
+
+test("movement carries sub-pixel remainders and caps long gaps", () => {
+  const mouse = new GamepadMouse();
+  const idle = { stickX: 0, stickY: 0, leftTrigger: 0, rightTrigger: 0 };
+  const slow = { ...idle, stickX: 0.1 };
+
+  assert.equal(mouse.update(slow, 0, linear).dx, 0);
+  assert.equal(mouse.update(slow, 4, linear).dx, 0);
+  assert.equal(mouse.update(slow, 8, linear).dx, 0);
+  assert.equal(mouse.update(slow, 12, linear).dx, 1);
+  assert.equal(mouse.update({ ...idle, stickY: 1 }, 5_000, linear).dy, 50);
+});
+
+test("triggers click with hysteresis and reset releases held buttons", () => {
+  const mouse = new GamepadMouse();
+  const idle = { stickX: 0, stickY: 0, leftTrigger: 0, rightTrigger: 0 };
+
+  assert.deepEqual(mouse.update({ ...idle, rightTrigger: 0.6 }, 0, linear).buttons, [{ button: 1, down: true }]);
+  assert.deepEqual(mouse.update({ ...idle, rightTrigger: 0.4 }, 4, linear).buttons, []);
+  assert.deepEqual(mouse.update({ ...idle, rightTrigger: 0.2, leftTrigger: 1 }, 8, linear).buttons, [
+    { button: 1, down: false },
+    { button: 3, down: true },
+  ]);
+  assert.deepEqual(mouse.reset(), [{ button: 3, down: false }]);
+  assert.equal(isGamepadMouseChord([false, false, false, false, false, false, false, false, true, false, false, true]), true);
+});
@@ -0,0 +1,115 @@
+export interface GamepadMouseTuning {
+  /** Pointer speed in server pixels per second at full stick deflection */
+  speed: number;
+  /** 0 = linear response, 100 = cubic: small deflections stay precise */
+  accelerationPercent: number;
+}
+
+export interface GamepadMouseInput {
+  /** Right stick after the deadzone, -1..1 with down positive */
+  stickX: number;
+  stickY: number;
+  leftTrigger: number;
+  rightTrigger: number;
+}
+
+/** GFN mouse buttons are 1-based: 1 = left, 3 = right. */
+export interface GamepadMouseButtonChange {
+  button: 1 | 3;
+  down: boolean;
+}
+
+export interface GamepadMouseFrame {
+  dx: number;
+  dy: number;
+  buttons: GamepadMouseButtonChange[];
+}
+
+export const GAMEPAD_MOUSE_SPEED_MIN = 200;
+export const GAMEPAD_MOUSE_SPEED_MAX = 3000;
+
+const TRIGGER_PRESS = 0.5;
+const TRIGGER_RELEASE = 0.3;
+// A long gap between polls (tab hidden, input paused) must not fling the pointer.
+const MAX_STEP_MS = 50;
+
+export function normalizeGamepadMouseTuning(tuning: GamepadMouseTuning): GamepadMouseTuning {
+  const speed = Number.isFinite(tuning.speed) ? tuning.speed : 1200;
+  const acceleration = Number.isFinite(tuning.accelerationPercent) ? tuning.accelerationPercent : 50;
+  return {
+    speed: Math.max(GAMEPAD_MOUSE_SPEED_MIN, Math.min(GAMEPAD_MOUSE_SPEED_MAX, Math.round(speed))),
+    accelerationPercent: Math.max(0, Math.min(100, Math.round(acceleration))),
+  };
+}
+
+/** Pointer velocity in pixels per second for a stick position. */
+export function gamepadMouseVelocity(
+  stickX: number,
+  stickY: number,
+  tuning: GamepadMouseTuning,
+): { vx: number; vy: number } {
+  const magnitude = Math.min(1, Math.hypot(stickX, stickY));
+  if (magnitude === 0) {
+    return { vx: 0, vy: 0 };
+  }
+  const exponent = 1 + (2 * tuning.accelerationPercent) / 100;
+  const scale = (tuning.speed * magnitude ** exponent) / magnitude;
+  return { vx: stickX * scale, vy: stickY * scale };
+}
+
+/**
+ * Right stick to relative pointer movement, triggers to clicks. Sub-pixel
+ * movement carries over between polls so slow drifts still arrive.
+ */
+export class GamepadMouse {
+  private residualX = 0;
+  private residualY = 0;
+  private lastUpdateMs: number | null = null;
+  private leftDown = false;
+  private rightDown = false;
+
+  update(input: GamepadMouseInput, nowMs: number, tuning: GamepadMouseTuning): GamepadMouseFrame {
+    const stepMs = this.lastUpdateMs === null ? 0 : Math.min(MAX_STEP_MS, Math.max(0, nowMs - this.lastUpdateMs));
+    this.lastUpdateMs = nowMs;
+
+    const { vx, vy } = gamepadMouseVelocity(input.stickX, input.stickY, tuning);
+    this.residualX += (vx * stepMs) / 1000;
+    this.residualY += (vy * stepMs) / 1000;
+    const dx = Math.trunc(this.residualX);
+    const dy = Math.trunc(this.residualY);
+    this.residualX -= dx;
+    this.residualY -= dy;
+
+    const buttons: GamepadMouseButtonChange[] = [];
+    const nextLeft = this.leftDown ? input.rightTrigger > TRIGGER_RELEASE : input.rightTrigger >= TRIGGER_PRESS;
+    if (nextLeft !== this.leftDown) {
+      this.leftDown = nextLeft;
+      buttons.push({ button: 1, down: nextLeft });
+    }
+    const nextRight = this.rightDown ? input.leftTrigger > TRIGGER_RELEASE : input.leftTrigger >= TRIGGER_PRESS;
+    if (nextRight !== this.rightDown) {
+      this.rightDown = nextRight;
+      buttons.push({ button: 3, down: nextRight });
+    }
+
+    return { dx, dy, buttons };
+  }
+
+  /** Forgets motion and returns releases for any held buttons. */
+  reset(): GamepadMouseButtonChange[] {
+    const releases: GamepadMouseButtonChange[] = [];
+    if (this.leftDown) releases.push({ button: 1, down: false });
+    if (this.rightDown) releases.push({ button: 3, down: false });
+    this.leftDown = false;
+    this.rightDown = false;
+    this.residualX = 0;
+    this.residualY = 0;
+    this.lastUpdateMs = null;
+    return releases;
+  }
+}
+
+/** Back + right stick click toggles the mode. */
+export function isGamepadMouseChord(buttons: readonly boolean[]): boolean {
+  return Boolean(buttons[8] && buttons[11]);
+}
@@ -0,0 +1,60 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { GamepadSlotAssigner } from "./gamepadSlots";
+
+const ALL_SLOTS = [true, true, true, true];
+const pad = (index: number, id = `pad-${index}`) => ({ index, id });
+
+test("assigns the lowest free slots regardless of browser index", () => {
+  const assigner = new GamepadSlotAssigner();
+  const changes = assigner.sync([pad(0), pad(5)], ALL_SLOTS);
+
+  assert.deepEqual([...changes.assignments], [[0, 0], [5, 1]]);
+  assert.deepEqual(changes.attached.map((entry) => entry.slot), [0, 1]);
+  assert.deepEqual(changes.released, []);
+});
+
+test("keeps slots stable when another controller is unplugged", () => {
+  const assigner = new GamepadSlotAssigner();
+  assigner.sync([pad(0), pad(1), pad(2)], ALL_SLOTS);
+
+  const changes = assigner.sync([pad(0), pad(2)], ALL_SLOTS);
+  assert.deepEqual(changes.released, [{ slot: 1, device: pad(1) }]);
+  assert.equal(assigner.slotOf(2), 2);
+
+  const newcomer = assigner.sync([pad(0), pad(2), pad(3, "other")], ALL_SLOTS);
+  assert.deepEqual(newcomer.attached.map((entry) => entry.slot), [1]);
+});
+
+test("returns a reconnected controller to its previous slot", () => {
+  const assigner = new GamepadSlotAssigner();
+  assigner.sync([pad(0, "left"), pad(1, "right")], ALL_SLOTS);
+  assigner.sync([pad(1, "right")], ALL_SLOTS);
+
+  const changes = assigner.sync([pad(1, "right"), pad(4, "extra"), pad(0, "left")], ALL_SLOTS);
+  assert.equal(assigner.slotOf(0), 0);
+  assert.equal(assigner.slotOf(4), 2);
+  assert.deepEqual(changes.attached.map((entry) => entry.slot), [0, 2]);
+});
+
+test("skips disabled slots and releases devices when their slot is disabled", () => {
+  const assigner = new GamepadSlotAssigner();
+  assigner.sync([pad(0), pad(1)], [false, true, true, true]);
+  assert.equal(assigner.slotOf(0), 1);
+  assert.equal(assigner.slotOf(1), 2);
+
+  const changes = assigner.sync([pad(0), pad(1)], [false, false, true, true]);
+  assert.deepEqual(changes.released.map((entry) => entry.slot), [1]);
+  assert.deepEqual(changes.attached.map((entry) => entry.slot), [3]);
+  assert.equal(assigner.deviceIndexForSlot(3), 0);
+});
+
+test("leaves extra controllers unassigned once every slot is taken", () => {
+  const assigner = new GamepadSlotAssigner();
+  const changes = assigner.sync([pad(0), pad(1), pad(2), pad(3), pad(4)], ALL_SLOTS);
+  assert.equal(changes.assignments.size, 4);
+  assert.equal(assigner.slotOf(4), null);
+});
@@ -0,0 +1,88 @@
+import { GAMEPAD_MAX_CONTROLLERS } from "./inputProtocol";
+
+export interface GamepadSlotDevice {
+  /** Browser `Gamepad.index`; can exceed the GFN slot range. */
+  index: number;
+  id: string;
+}
+
+export interface GamepadSlotChanges {
+  /** Browser index -> GFN player slot for every device that currently owns a slot. */
+  assignments: ReadonlyMap<number, number>;
+  /** Slots that gained a device during this sync. */
+  attached: { slot: number; device: GamepadSlotDevice }[];
+  /** Slots that lost their device (unplugged, or the slot was disabled). */
+  released: { slot: number; device: GamepadSlotDevice }[];
+}
+
+/**
+ * Keeps GFN player slots stable while controllers come and go. A device keeps
+ * its slot for as long as it stays connected, and a controller that is
+ * unplugged and plugged back in returns to its previous slot when it is free.
+ */
+export class GamepadSlotAssigner {
+  private readonly slots: (GamepadSlotDevice | null)[] = new Array(GAMEPAD_MAX_CONTROLLERS).fill(null);
+  private readonly lastSlotById = new Map<string, number>();
+
+  sync(devices: readonly GamepadSlotDevice[], enabledSlots: readonly boolean[]): GamepadSlotChanges {
+    const released: GamepadSlotChanges["released"] = [];
+    for (let slot = 0; slot < this.slots.length; slot++) {
+      const owner = this.slots[slot];
+      if (!owner) continue;
+      const stillConnected = devices.some((device) => device.index === owner.index && device.id === owner.id);
+      if (!stillConnected || enabledSlots[slot] === false) {
+        this.slots[slot] = null;
+        released.push({ slot, device: owner });
+      }
+    }
+
+    // Returning controllers claim their old slots before new ones fill gaps.
+    const pending = devices
+      .filter((device) => this.slotOf(device.index) === null)
+      .sort((a, b) => Number(this.lastSlotById.has(b.id)) - Number(this.lastSlotById.has(a.id)));
+    const attached: GamepadSlotChanges["attached"] = [];
+    for (const device of pending) {
+      const slot = this.pickSlot(device.id, enabledSlots);
+      if (slot === null) continue;
+      this.slots[slot] = { ...device };
+      this.lastSlotById.set(device.id, slot);
+      attached.push({ slot, device: { ...device } });
+    }
+
+    return { assignments: this.assignments(), attached, released };
+  }
+
+  slotOf(index: number): number | null {
+    const slot = this.slots.findIndex((owner) => owner?.index === index);
+    return slot >= 0 ? slot : null;
+  }
+
+  /** Browser index of the device in `slot`, if any. */
+  deviceIndexForSlot(slot: number): number | null {
+    return this.slots[slot]?.index ?? null;
+  }
+
+  reset(): void {
+    this.slots.fill(null);
+  }
+
+  private assignments(): Map<number, number> {
+    const assignments = new Map<number, number>();
+    this.slots.forEach((owner, slot) => {
+      if (owner) assignments.set(owner.index, slot);
+    });
+    return assignments;
+  }
+
+  private pickSlot(id: string, enabledSlots: readonly boolean[]): number | null {
+    const isFree = (slot: number): boolean => this.slots[slot] === null && enabledSlots[slot] !== false;
+    const previous = this.lastSlotById.get(id);
+    if (previous !== undefined && isFree(previous)) {
+      return previous;
+    }
+    for (let slot = 0; slot < this.slots.length; slot++) {
+      if (isFree(slot)) return slot;
+    }
+    return null;
+  }
+}
@@ -1,4 +1,6 @@
-import type { KeyboardLayout } from "@shared/gfn";
+import { GAMEPAD_MAX_CONTROLLERS, type KeyboardLayout } from "@shared/gfn";
+
+export { GAMEPAD_MAX_CONTROLLERS };
 
 export const INPUT_HEARTBEAT = 2;
 export const INPUT_KEY_DOWN = 3;
@@ -50,7 +52,6 @@ export const GAMEPAD_AXIS_LT = 4; // Left trigger
 export const GAMEPAD_AXIS_RT = 5; // Right trigger
 
 // Gamepad constants
-export const GAMEPAD_MAX_CONTROLLERS = 4;
 export const GAMEPAD_PACKET_SIZE = 38;
 export const GAMEPAD_DEADZONE = 0.15; // 15% radial deadzone
 export const PARTIALLY_RELIABLE_GAMEPAD_MASK_ALL = (1 << GAMEPAD_MAX_CONTROLLERS) - 1;
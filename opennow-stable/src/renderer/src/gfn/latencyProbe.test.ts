@@ -0,0 +1,58 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { ClickToPhotonProbe, averageLuma, summarizeClickToPhoton } from "./latencyProbe";
+
+test("averageLuma weights channels by Rec. 601", () => {
+  assert.equal(averageLuma([255, 255, 255, 255, 0, 0, 0, 255]), 127.5);
+  assert.ok(Math.abs(averageLuma([0, 255, 0, 255]) - 149.685) < 1e-9);
+  assert.equal(averageLuma([]), 0);
+});
+
+test("probe breaks a click down into send, network, decode and present", () => {
+  const probe = new ClickToPhotonProbe();
+  assert.equal(probe.arm(0, 1), false, "needs a baseline frame first");
+  probe.observeFrame({ luma: 40, presentedAtMs: 0 });
+
+  assert.equal(probe.arm(100, 101), true);
+  assert.equal(probe.arm(110, 111), false, "one measurement at a time");
+  // Still in flight before the click was sent.
+  assert.equal(probe.observeFrame({ luma: 200, presentedAtMs: 115, receiveTimeMs: 99, decodeMs: 2 }), null);
+  // Sent after the click, but the picture has not changed yet.
+  assert.equal(probe.observeFrame({ luma: 45, presentedAtMs: 140, receiveTimeMs: 130, decodeMs: 2 }), null);
+
+  const sample = probe.observeFrame({ luma: 180, presentedAtMs: 160, receiveTimeMs: 150, decodeMs: 3 });
+  assert.deepEqual(sample, {
+    captureToSendMs: 1,
+    sendToReceiveMs: 49,
+    decodeMs: 3,
+    decodeToPresentMs: 7,
+    totalMs: 60,
+  });
+  assert.equal(probe.isArmed(), false);
+});
+
+test("probe gives up on clicks with no visible response", () => {
+  const probe = new ClickToPhotonProbe(24, 500);
+  probe.observeFrame({ luma: 40, presentedAtMs: 0 });
+  probe.arm(10, 11);
+  assert.equal(probe.observeFrame({ luma: 41, presentedAtMs: 600 }), null);
+  assert.equal(probe.isArmed(), false);
+});
+
+test("summary reports the median total alongside the latest breakdown", () => {
+  const sample = (totalMs: number) => ({
+    captureToSendMs: 1,
+    sendToReceiveMs: 0,
+    decodeMs: 0,
+    decodeToPresentMs: 0,
+    totalMs,
+  });
+  assert.equal(summarizeClickToPhoton([]), null);
+  const summary = summarizeClickToPhoton([sample(80), sample(40), sample(60), sample(50)]);
+  assert.equal(summary?.count, 4);
+  assert.equal(summary?.medianTotalMs, 55);
+  assert.equal(summary?.last.totalMs, 50);
+});
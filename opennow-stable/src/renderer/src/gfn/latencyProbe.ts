@@ -0,0 +1,138 @@
+/**
+ * Click-to-photon measurement. GFN has no input echo, so the probe arms on a
+ * click and waits for the first frame whose sampled region changes brightness
+ * (a muzzle flash, a menu opening). All times are DOMHighResTimeStamps on the
+ * page clock, which event.timeStamp, performance.now() and
+ * requestVideoFrameCallback metadata all share.
+ */
+
+export interface ClickToPhotonSample {
+  /** Browser input event to the data channel send. */
+  captureToSendMs: number;
+  /** Send to the last packet of the responding frame arriving (network + server). */
+  sendToReceiveMs: number;
+  /** Decode time Chromium reported for the frame. */
+  decodeMs: number;
+  /** Decoded frame to the compositor presenting it. */
+  decodeToPresentMs: number;
+  totalMs: number;
+}
+
+export interface ProbeFrame {
+  /** Mean luma (0-255) of the sampled region. */
+  luma: number;
+  /** When the frame was handed to the compositor (metadata.expectedDisplayTime). */
+  presentedAtMs: number;
+  /** metadata.receiveTime; absent when Chromium does not report it. */
+  receiveTimeMs?: number;
+  /** metadata.processingDuration, converted to milliseconds. */
+  decodeMs?: number;
+}
+
+export interface ClickToPhotonSummary {
+  count: number;
+  medianTotalMs: number;
+  last: ClickToPhotonSample;
+}
+
+/** Luma change (0-255) that counts as the click's visible response. */
+const DEFAULT_LUMA_THRESHOLD = 24;
+/** A click with no visible response within this window is discarded. */
+const DEFAULT_TIMEOUT_MS = 1000;
+/** Baseline smoothing so slow fades and camera drift do not trigger a detection. */
+const BASELINE_WEIGHT = 0.2;
+
+/** Rec. 601 mean luma of an RGBA pixel buffer. */
+export function averageLuma(rgba: ArrayLike<number>): number {
+  const pixels = Math.floor(rgba.length / 4);
+  if (pixels === 0) {
+    return 0;
+  }
+  let sum = 0;
+  for (let i = 0; i < pixels * 4; i += 4) {
+    sum += 0.299 * rgba[i] + 0.587 * rgba[i + 1] + 0.114 * rgba[i + 2];
+  }
+  return sum / pixels;
+}
+
+export class ClickToPhotonProbe {
+  private baselineLuma: number | null = null;
+  private pending: { captureMs: number; sentMs: number } | null = null;
+
+  constructor(
+    private readonly lumaThreshold = DEFAULT_LUMA_THRESHOLD,
+    private readonly timeoutMs = DEFAULT_TIMEOUT_MS,
+  ) {}
+
+  /** Starts a measurement; ignored while one is already waiting for its frame. */
+  public arm(captureMs: number, sentMs: number): boolean {
+    if (this.pending || this.baselineLuma === null) {
+      return false;
+    }
+    this.pending = { captureMs, sentMs };
+    return true;
+  }
+
+  public isArmed(): boolean {
+    return this.pending !== null;
+  }
+
+  public reset(): void {
+    this.baselineLuma = null;
+    this.pending = null;
+  }
+
+  /** Feeds one presented frame; returns a sample when it completes a measurement. */
+  public observeFrame(frame: ProbeFrame): ClickToPhotonSample | null {
+    const pending = this.pending;
+    if (!pending || this.baselineLuma === null) {
+      this.baselineLuma = this.baselineLuma === null
+        ? frame.luma
+        : this.baselineLuma + (frame.luma - this.baselineLuma) * BASELINE_WEIGHT;
+      return null;
+    }
+
+    if (frame.presentedAtMs - pending.sentMs > this.timeoutMs) {
+      this.pending = null;
+      this.baselineLuma = frame.luma;
+      return null;
+    }
+    // Frames that were already on the wire before the click cannot be its response.
+    if (frame.receiveTimeMs !== undefined && frame.receiveTimeMs < pending.sentMs) {
+      return null;
+    }
+    if (Math.abs(frame.luma - this.baselineLuma) < this.lumaThreshold) {
+      return null;
+    }
+
+    this.pending = null;
+    this.baselineLuma = frame.luma;
+    const decodeMs = Math.max(0, frame.decodeMs ?? 0);
+    const receivedAtMs = frame.receiveTimeMs ?? frame.presentedAtMs - decodeMs;
+    return {
+      captureToSendMs: Math.max(0, pending.sentMs - pending.captureMs),
+      sendToReceiveMs: Math.max(0, receivedAtMs - pending.sentMs),
+      decodeMs,
+      decodeToPresentMs: Math.max(0, frame.presentedAtMs - receivedAtMs - decodeMs),
+      totalMs: Math.max(0, frame.presentedAtMs - pending.captureMs),
+    };
+  }
+}
+
+export function summarizeClickToPhoton(samples: readonly ClickToPhotonSample[]): ClickToPhotonSummary | null {
+  const last = samples[samples.length - 1];
+  if (!last) {
+    return null;
+  }
+  const totals = samples.map((sample) => sample.totalMs).sort((a, b) => a - b);
+  const mid = Math.floor(totals.length / 2);
+  const medianTotalMs = totals.length % 2 === 0 ? (totals[mid - 1] + totals[mid]) / 2 : totals[mid];
+  return { count: samples.length, medianTotalMs, last };
+}
+
+export function formatClickToPhotonSummary(summary: ClickToPhotonSummary): string {
+  const { last } = summary;
+  return `${last.totalMs.toFixed(0)}ms (median ${summary.medianTotalMs.toFixed(0)}ms of ${summary.count}) · `
+    + `input ${last.captureToSendMs.toFixed(1)} · network+server ${last.sendToReceiveMs.toFixed(0)} · `
+    + `decode ${last.decodeMs.toFixed(1)} · present ${last.decodeToPresentMs.toFixed(1)}`;
+}
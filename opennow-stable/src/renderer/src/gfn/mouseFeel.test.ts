@@ -0,0 +1,46 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { applyMouseFeel, requestPointerLockMovement } from "./mouseFeel";
+
+function lockTarget(supportsUnadjusted: boolean): { target: HTMLElement; requests: unknown[] } {
+  const requests: unknown[] = [];
+  const target = {
+    requestPointerLock(options?: { unadjustedMovement?: boolean }) {
+      requests.push(options);
+      if (options?.unadjustedMovement && !supportsUnadjusted) {
+        return Promise.reject(Object.assign(new Error("unsupported"), { name: "NotSupportedError" }));
+      }
+      return Promise.resolve();
+    },
+  } as unknown as HTMLElement;
+  return { target, requests };
+}
+
+test("applyMouseFeel is a pure multiplier when acceleration is off", () => {
+  assert.deepEqual(applyMouseFeel(10, -4, 1, 1), { dx: 10, dy: -4 });
+  assert.deepEqual(applyMouseFeel(10, -4, 0.5, 1), { dx: 5, dy: -2 });
+});
+
+test("applyMouseFeel boosts fast movement up to the cap", () => {
+  const slow = applyMouseFeel(1, 0, 1, 150);
+  const fast = applyMouseFeel(500, 0, 1, 150);
+  assert.ok(slow.dx > 1 && slow.dx < 1.05);
+  assert.equal(fast.dx, 500 * 1.6);
+});
+
+test("requestPointerLockMovement reports whether raw movement was granted", async () => {
+  const raw = lockTarget(true);
+  assert.equal(await requestPointerLockMovement(raw.target, true), "raw");
+  assert.deepEqual(raw.requests, [{ unadjustedMovement: true }]);
+
+  const fallback = lockTarget(false);
+  assert.equal(await requestPointerLockMovement(fallback.target, true), "accelerated");
+  assert.deepEqual(fallback.requests, [{ unadjustedMovement: true }, undefined]);
+
+  const disabled = lockTarget(true);
+  assert.equal(await requestPointerLockMovement(disabled.target, false), "accelerated");
+  assert.deepEqual(disabled.requests, [undefined]);
+});
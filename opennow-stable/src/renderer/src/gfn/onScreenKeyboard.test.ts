@@ -0,0 +1,46 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import {
+  isOnScreenKeyboardChord,
+  moveOnScreenKeyboardFocus,
+  onScreenKeyboardText,
+  OnScreenKeyboardGamepad,
+} from "./onScreenKeyboard";
+
+function pad(pressed: number[], axes: number[] = [0, 0]): { buttons: boolean[]; axes: number[] } {
+  const buttons = Array.from({ length: 17 }, (_, index) => pressed.includes(index));
+  return { buttons, axes };
+}
+
+test("focus wraps within a row and keeps its place between rows", () => {
+  const rows = [[1, 2, 3, 4, 5], [1, 2, 3], [1, 2, 3, 4, 5]];
+  assert.deepEqual(moveOnScreenKeyboardFocus({ row: 0, col: 0 }, "left", rows), { row: 0, col: 4 });
+  assert.deepEqual(moveOnScreenKeyboardFocus({ row: 0, col: 4 }, "down", rows), { row: 1, col: 2 });
+  assert.deepEqual(moveOnScreenKeyboardFocus({ row: 0, col: 3 }, "up", rows), { row: 2, col: 3 });
+  assert.deepEqual(moveOnScreenKeyboardFocus({ row: 1, col: 1 }, "down", rows), { row: 2, col: 2 });
+});
+
+test("shift types the upper-row symbol or capital", () => {
+  assert.equal(onScreenKeyboardText("q", true), "Q");
+  assert.equal(onScreenKeyboardText("2", true), "@");
+  assert.equal(onScreenKeyboardText("/", false), "/");
+});
+
+test("gamepad buttons fire once per press and held directions repeat", () => {
+  const gamepad = new OnScreenKeyboardGamepad();
+  assert.deepEqual(gamepad.update(pad([14]), 0), [{ type: "move", direction: "left" }]);
+  assert.deepEqual(gamepad.update(pad([14]), 300), []);
+  assert.deepEqual(gamepad.update(pad([14]), 400), [{ type: "move", direction: "left" }]);
+  assert.deepEqual(gamepad.update(pad([], [0, 0.9]), 450), [{ type: "move", direction: "down" }]);
+
+  assert.deepEqual(gamepad.update(pad([0, 2]), 500), [{ type: "press" }, { type: "backspace" }]);
+  assert.deepEqual(gamepad.update(pad([0, 2]), 520), []);
+  assert.deepEqual(gamepad.update(pad([9]), 540), [{ type: "enter" }]);
+  assert.deepEqual(gamepad.update(pad([8]), 560), []);
+  assert.deepEqual(gamepad.update(pad([8, 9]), 580), []);
+  assert.equal(isOnScreenKeyboardChord(pad([8, 9]).buttons), true);
+  assert.equal(isOnScreenKeyboardChord(pad([9]).buttons), false);
+});
@@ -0,0 +1,139 @@
+/** Keys that go out as raw key taps rather than composed text. */
+export type OnScreenKeyboardCode = "Backspace" | "Enter" | "Tab" | "Escape";
+
+export type OnScreenKeyboardKey =
+  | { kind: "char"; value: string }
+  | { kind: "code"; code: OnScreenKeyboardCode }
+  | { kind: "space" }
+  | { kind: "shift" }
+  | { kind: "close" };
+
+const char = (value: string): OnScreenKeyboardKey => ({ kind: "char", value });
+const chars = (values: string): OnScreenKeyboardKey[] => Array.from(values, char);
+
+export const ON_SCREEN_KEYBOARD_ROWS: readonly (readonly OnScreenKeyboardKey[])[] = [
+  [...chars("1234567890-="), { kind: "code", code: "Backspace" }],
+  [{ kind: "code", code: "Tab" }, ...chars("qwertyuiop[]")],
+  [...chars("asdfghjkl;'"), { kind: "code", code: "Enter" }],
+  [{ kind: "shift" }, ...chars("zxcvbnm,./")],
+  [{ kind: "code", code: "Escape" }, { kind: "space" }, { kind: "close" }],
+];
+
+const SHIFTED_SYMBOLS: Record<string, string> = {
+  "1": "!", "2": "@", "3": "#", "4": "$", "5": "%", "6": "^", "7": "&", "8": "*", "9": "(", "0": ")",
+  "-": "_", "=": "+", "[": "{", "]": "}", ";": ":", "'": "\"", ",": "<", ".": ">", "/": "?",
+};
+
+export function onScreenKeyboardText(value: string, shifted: boolean): string {
+  if (!shifted) return value;
+  return SHIFTED_SYMBOLS[value] ?? value.toUpperCase();
+}
+
+export interface OnScreenKeyboardFocus {
+  row: number;
+  col: number;
+}
+
+export type OnScreenKeyboardDirection = "up" | "down" | "left" | "right";
+
+/** Wraps within a row; moving between rows of different length keeps the column where it can. */
+export function moveOnScreenKeyboardFocus(
+  focus: OnScreenKeyboardFocus,
+  direction: OnScreenKeyboardDirection,
+  rows: readonly (readonly unknown[])[] = ON_SCREEN_KEYBOARD_ROWS,
+): OnScreenKeyboardFocus {
+  const rowCount = rows.length;
+  if (direction === "left" || direction === "right") {
+    const length = rows[focus.row].length;
+    const step = direction === "left" ? -1 : 1;
+    return { row: focus.row, col: (focus.col + step + length) % length };
+  }
+  const row = (focus.row + (direction === "up" ? -1 : 1) + rowCount) % rowCount;
+  const sourceLength = rows[focus.row].length;
+  const targetLength = rows[row].length;
+  const col = sourceLength === targetLength
+    ? focus.col
+    : Math.round((focus.col / Math.max(1, sourceLength - 1)) * (targetLength - 1));
+  return { row, col: Math.min(col, targetLength - 1) };
+}
+
+export type OnScreenKeyboardAction =
+  | { type: "move"; direction: OnScreenKeyboardDirection }
+  | { type: "press" }
+  | { type: "backspace" }
+  | { type: "space" }
+  | { type: "shift" }
+  | { type: "enter" }
+  | { type: "close" };
+
+export interface OnScreenKeyboardPadState {
+  buttons: readonly boolean[];
+  axes: readonly number[];
+}
+
+// Standard gamepad mapping indices.
+const BUTTON_A = 0;
+const BUTTON_B = 1;
+const BUTTON_X = 2;
+const BUTTON_Y = 3;
+const BUTTON_LB = 4;
+const BUTTON_BACK = 8;
+const BUTTON_START = 9;
+const DPAD: Array<[number, OnScreenKeyboardDirection]> = [[12, "up"], [13, "down"], [14, "left"], [15, "right"]];
+const STICK_THRESHOLD = 0.6;
+const REPEAT_DELAY_MS = 400;
+const REPEAT_INTERVAL_MS = 110;
+
+/** Back + Start together toggles the keyboard; both are rarely held at once in games. */
+export function isOnScreenKeyboardChord(buttons: readonly boolean[]): boolean {
+  return Boolean(buttons[BUTTON_BACK] && buttons[BUTTON_START]);
+}
+
+function heldDirection(pad: OnScreenKeyboardPadState): OnScreenKeyboardDirection | null {
+  for (const [index, direction] of DPAD) {
+    if (pad.buttons[index]) return direction;
+  }
+  const x = pad.axes[0] ?? 0;
+  const y = pad.axes[1] ?? 0;
+  if (Math.max(Math.abs(x), Math.abs(y)) < STICK_THRESHOLD) return null;
+  if (Math.abs(x) >= Math.abs(y)) return x < 0 ? "left" : "right";
+  return y < 0 ? "up" : "down";
+}
+
+/**
+ * Turns polled controller state into keyboard actions: buttons fire on press,
+ * a held direction repeats after a short delay.
+ */
+export class OnScreenKeyboardGamepad {
+  private previousButtons: readonly boolean[] = [];
+  private direction: OnScreenKeyboardDirection | null = null;
+  private nextRepeatAtMs = 0;
+
+  update(pad: OnScreenKeyboardPadState, nowMs: number): OnScreenKeyboardAction[] {
+    const actions: OnScreenKeyboardAction[] = [];
+    const pressed = (index: number): boolean => Boolean(pad.buttons[index] && !this.previousButtons[index]);
+
+    const direction = heldDirection(pad);
+    if (direction !== this.direction) {
+      this.direction = direction;
+      if (direction) {
+        actions.push({ type: "move", direction });
+        this.nextRepeatAtMs = nowMs + REPEAT_DELAY_MS;
+      }
+    } else if (direction && nowMs >= this.nextRepeatAtMs) {
+      actions.push({ type: "move", direction });
+      this.nextRepeatAtMs = nowMs + REPEAT_INTERVAL_MS;
+    }
+
+    if (pressed(BUTTON_A)) actions.push({ type: "press" });
+    if (pressed(BUTTON_X)) actions.push({ type: "backspace" });
+    if (pressed(BUTTON_Y)) actions.push({ type: "space" });
+    if (pressed(BUTTON_LB)) actions.push({ type: "shift" });
+    // Start while Back is held is the toggle chord, not Enter.
+    if (pressed(BUTTON_START) && !pad.buttons[BUTTON_BACK]) actions.push({ type: "enter" });
+    if (pressed(BUTTON_B)) actions.push({ type: "close" });
+
+    this.previousButtons = [...pad.buttons];
+    return actions;
+  }
+}
@@ -10,6 +10,8 @@ import {
   preferCodec,
   rewriteH265LevelIdByProfile,
   rewriteH265TierFlag,
+  videoPacketSizeForPathMtu,
+  videoPacketsMayFragment,
 } from "./sdp";
 
 test("fixServerIp replaces 0.0.0.0 candidate IPs without changing connection lines", () => {
@@ -171,3 +173,30 @@ test("buildNvstSdp keeps dynamic split encode updates enabled for 240 FPS by def
   assert.match(defaultSdp, /a=video\.updateSplitEncodeStateDynamically:1/);
   assert.match(diagnosticOffSdp, /a=video\.updateSplitEncodeStateDynamically:0/);
 });
+
+test("video packets shrink only when the path MTU cannot carry the default size", () => {
+  assert.equal(videoPacketSizeForPathMtu(null), 1140);
+  assert.equal(videoPacketSizeForPathMtu(1500), 1140);
+  assert.equal(videoPacketSizeForPathMtu(1240), 1140);
+  assert.equal(videoPacketSizeForPathMtu(1200), 1100);
+  assert.equal(videoPacketSizeForPathMtu(576), 500);
+  assert.equal(videoPacketsMayFragment(1400, 1140), false);
+  assert.equal(videoPacketsMayFragment(1200, 1140), true);
+
+  const sdp = buildNvstSdp({
+    width: 1920,
+    height: 1080,
+    fps: 60,
+    maxBitrateKbps: 50000,
+    partialReliableThresholdMs: 16,
+    codec: "H264",
+    colorQuality: "8bit_420",
+    credentials: {
+      ufrag: "ufrag-test",
+      pwd: "password-test",
+      fingerprint: "AA:BB:CC",
+    },
+    videoPacketSize: 1100,
+  });
+  assert.match(sdp, /a=video\.packetSize:1100/);
+});
@@ -413,6 +413,35 @@ interface NvstParams {
   enablePartiallyReliableTransferGamepad?: number;
   enablePartiallyReliableTransferHid?: number;
   dynamicSplitEncodeUpdatesEnabled?: boolean;
+  /** RTP payload size asked of the server encoder; defaults to DEFAULT_VIDEO_PACKET_SIZE */
+  videoPacketSize?: number;
+}
+
+/** What the official client requests; fits a 1280-byte IPv6-minimum path with room to spare. */
+export const DEFAULT_VIDEO_PACKET_SIZE = 1140;
+const MIN_VIDEO_PACKET_SIZE = 500;
+/**
+ * Bytes a video packet grows by on the wire: RTP header and extensions, SRTP
+ * auth tag, UDP, IPv4/IPv6 and a TURN channel header, rounded up.
+ */
+const VIDEO_PACKET_OVERHEAD_BYTES = 100;
+
+/**
+ * Only ever shrinks the default: a larger payload would not be meaningfully
+ * faster and the server may not honour it, while an oversized one fragments.
+ */
+export function videoPacketSizeForPathMtu(pathMtu: number | null | undefined): number {
+  if (typeof pathMtu !== "number" || !Number.isFinite(pathMtu)) {
+    return DEFAULT_VIDEO_PACKET_SIZE;
+  }
+  return Math.max(
+    MIN_VIDEO_PACKET_SIZE,
+    Math.min(DEFAULT_VIDEO_PACKET_SIZE, Math.floor(pathMtu) - VIDEO_PACKET_OVERHEAD_BYTES),
+  );
+}
+
+export function videoPacketsMayFragment(pathMtu: number, videoPacketSize: number): boolean {
+  return videoPacketSize + VIDEO_PACKET_OVERHEAD_BYTES > pathMtu;
 }
 
 /**
@@ -523,7 +552,7 @@ export function buildNvstSdp(params: NvstParams): string {
     "a=video.enableRtpNack:1",
     "a=vqos.bw.txRxLag.minFeedbackTxDeltaMs:200",
     "a=vqos.drc.bitrateIirFilterFactor:18",
-    "a=video.packetSize:1140",
+    `a=video.packetSize:${params.videoPacketSize ?? DEFAULT_VIDEO_PACKET_SIZE}`,
     "a=packetPacing.minNumPacketsPerGroup:15",
   );
 
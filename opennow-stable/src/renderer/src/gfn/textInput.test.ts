@@ -0,0 +1,36 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { shouldForwardRawKeyInTextMode, takeCommittedText, type TextInputKeyEvent } from "./textInput";
+
+function key(init: Partial<TextInputKeyEvent> & { key: string }): TextInputKeyEvent {
+  return { ctrlKey: false, metaKey: false, altKey: false, isComposing: false, keyCode: 0, ...init };
+}
+
+test("text mode leaves typing and IME composition to the text field", () => {
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "a" })), false);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "é" })), false);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "Dead" })), false);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "@", ctrlKey: true, altKey: true })), false);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "Process", keyCode: 229 })), false);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "Enter", isComposing: true })), false);
+});
+
+test("text mode still forwards editing keys and shortcuts", () => {
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "Enter", keyCode: 13 })), true);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "Backspace", keyCode: 8 })), true);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "ArrowUp", keyCode: 38 })), true);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "c", ctrlKey: true })), true);
+  assert.equal(shouldForwardRawKeyInTextMode(key({ key: "v", metaKey: true })), true);
+});
+
+test("committed text is taken once and held back while composing", () => {
+  const field = { value: "こんにちは" };
+  assert.equal(takeCommittedText(field, true), "");
+  assert.equal(field.value, "こんにちは");
+  assert.equal(takeCommittedText(field, false), "こんにちは");
+  assert.equal(field.value, "");
+  assert.equal(takeCommittedText(field, false), "");
+});
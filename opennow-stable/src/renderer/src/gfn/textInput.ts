@@ -0,0 +1,41 @@
+export type TextInputKeyEvent = Pick<KeyboardEvent, "key" | "ctrlKey" | "metaKey" | "altKey" | "isComposing" | "keyCode">;
+
+/** Chromium reports keyCode 229 for the keydown that starts an IME composition. */
+const IME_PROCESS_KEY_CODE = 229;
+
+export function isImeKeyEvent(event: TextInputKeyEvent): boolean {
+  return event.isComposing || event.keyCode === IME_PROCESS_KEY_CODE;
+}
+
+/**
+ * Whether the key types a character into the focused field. Ctrl+Alt is
+ * AltGr on Windows layouts and Option composes characters on macOS, so both
+ * still count as typing; Ctrl or Cmd alone are shortcuts.
+ */
+export function producesText(event: TextInputKeyEvent): boolean {
+  if (event.key !== "Dead" && Array.from(event.key).length !== 1) {
+    return false;
+  }
+  return !event.metaKey && (!event.ctrlKey || event.altKey);
+}
+
+/**
+ * In text input mode, typing reaches the stream as composed text; only
+ * editing, navigation and shortcut keys still go out as raw key events.
+ */
+export function shouldForwardRawKeyInTextMode(event: TextInputKeyEvent): boolean {
+  return !isImeKeyEvent(event) && !producesText(event);
+}
+
+/**
+ * Empties the field and returns what was typed, unless an IME composition is
+ * still open: that text is not final until the composition ends.
+ */
+export function takeCommittedText(field: { value: string }, composing: boolean): string {
+  if (composing) {
+    return "";
+  }
+  const text = field.value;
+  field.value = "";
+  return text;
+}
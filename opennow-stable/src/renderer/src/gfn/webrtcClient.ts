@@ -8,7 +8,11 @@ import type {
   NativeTransitionDiagnostics,
   NativeQueueMode,
   KeyboardLayout,
+  IceTransportPolicy,
 } from "@shared/gfn";
+import { normalizeControllerSlotsEnabled } from "@shared/gfn";
+import { formatCandidatePair, mergeIceServers, summarizeCandidatePairs } from "@shared/iceConfig";
+import { isSteamInputManagedGamepad } from "@shared/steamInput";
 
 import {
   InputEncoder,
@@ -29,7 +33,16 @@ import {
   type GamepadInput,
   codeMap,
 } from "./inputProtocol";
+import { GamepadSlotAssigner, type GamepadSlotDevice } from "./gamepadSlots";
 import { FULLSCREEN_KEYBOARD_LOCK_CODES } from "./keyboardLock";
+import {
+  ClickToPhotonProbe,
+  averageLuma,
+  formatClickToPhotonSummary,
+  summarizeClickToPhoton,
+  type ClickToPhotonSample,
+} from "./latencyProbe";
+import { MouseDeltaFilter, applyMouseFeel, requestPointerLockMovement } from "./mouseFeel";
 import {
   buildNvstSdp,
   extractIceCredentials,
@@ -38,8 +51,19 @@ import {
   preferCodec,
   rewriteH265LevelIdByProfile,
   rewriteH265TierFlag,
+  videoPacketSizeForPathMtu,
 } from "./sdp";
 import { MicrophoneManager, type MicState, type MicStateChange } from "./microphoneManager";
+import {
+  GamepadMouse,
+  isGamepadMouseChord,
+  normalizeGamepadMouseTuning,
+  type GamepadMouseButtonChange,
+  type GamepadMouseTuning,
+} from "./gamepadMouse";
+import { isOnScreenKeyboardChord, type OnScreenKeyboardCode } from "./onScreenKeyboard";
+import { shouldForwardRawKeyInTextMode } from "./textInput";
+import { averagePlayoutDelayMs, estimateAvSyncOffsetMs, type AudioPlayoutSample } from "../lib/streamDiagnostics";
 
 interface OfferSettings {
   codec: VideoCodec;
@@ -48,8 +72,19 @@ interface OfferSettings {
   fps: number;
   maxBitrateKbps: number;
   nativeTransitionDiagnostics?: NativeTransitionDiagnostics;
+  iceTransportPolicy?: IceTransportPolicy;
+  customIceServers?: IceServer[];
+  /** Measured before the offer; shrinks the requested video packet size when the path needs it */
+  pathMtu?: number | null;
+  /** Give the input channels high priority and ask for DSCP marking on the media socket */
+  qosMarking?: boolean;
+  /** Low-latency mode: zero video jitter target and sparser getStats polling */
+  lowLatency?: boolean;
 }
 
+/** `priority` from the WebRTC priority spec, which lib.dom does not declare. */
+type PrioritizedDataChannelInit = RTCDataChannelInit & { priority?: "very-low" | "low" | "medium" | "high" };
+
 interface RiInputCapabilities {
   partialReliableThresholdMs: number | null;
   hidDeviceMask: number;
@@ -186,9 +221,46 @@ export interface StreamDiagnostics {
   nativeFramesPendingToPresent?: number;
   nativePartialFlushCount?: number;
   nativeCompleteFlushCount?: number;
+  /** Frames dropped by the native skip-ahead when its queues stayed backed up. */
+  nativeFramesSkippedAhead?: number;
   nativeTransitionSummary?: string;
   nativeRequestedStreamingFeaturesSummary?: string;
   nativeFinalizedStreamingFeaturesSummary?: string;
+  nativePipelineStageSummary?: string;
+  nativeHdrMetadata?: string;
+  nativeEncoderTimecode?: string;
+  nativeEncoderLatencyMs?: number;
+  // Audio-minus-video playout delay; positive means audio trails the picture
+  avSyncOffsetMs?: number;
+  avSyncAudioCorrectionMs?: number;
+  // Time decoded audio waits in the output device buffer
+  audioOutputLatencyMs?: number;
+  // Output underruns and the decoded audio format
+  audioUnderruns?: number;
+  audioCodec?: string;
+  audioSampleRate?: number;
+  audioChannels?: number;
+  // Native memory budget for queued decoded frames
+  nativeMemoryBudgetMb?: number;
+  nativeThreadPrioritySummary?: string;
+
+  // Every ICE candidate pair, one formatted pair per line
+  candidatePairs?: string;
+
+  // Latest click-to-photon measurement and its stage breakdown
+  clickToPhotonSummary?: string;
+
+  // Probed path MTU to the media server and the video packet size requested for it
+  pathMtu?: number;
+  videoPacketSize?: number;
+
+  // Compressed-stream health. Damaged frames point at the network and
+  // decoder errors at the decoder; only the native client reports those two.
+  keyframesReceived?: number;
+  keyframeIntervalMs?: number;
+  keyframeRequests?: number;
+  damagedFrames?: number;
+  decoderErrors?: number;
 
   // Microphone state
   micState: MicState;
@@ -221,8 +293,16 @@ interface ClientOptions {
   mouseSensitivity?: number;
   /** Software acceleration strength percentage (1-150) */
   mouseAcceleration?: number;
+  /** Request unadjusted (OS-acceleration-free) pointer lock movement where supported */
+  mouseRawInput?: boolean;
+  /** Right-stick pointer speed and response curve for gamepad mouse mode */
+  gamepadMouseTuning?: GamepadMouseTuning;
   /** Selected GFN keyboard layout for remote physical OEM key mapping. */
   keyboardLayout?: KeyboardLayout;
+  /** Which GFN player slots (0-3) local controllers may occupy. */
+  controllerSlotsEnabled?: boolean[];
+  /** `vendor:product` of pads Steam Input has taken over; only Steam's virtual pad is read for them. */
+  steamInputDevices?: readonly string[];
   onLog: (line: string) => void;
   onStats?: (stats: StreamDiagnostics) => void;
   onTimeWarning?: (warning: StreamTimeWarning) => void;
@@ -231,6 +311,10 @@ interface ClientOptions {
   onPeerConnectionStateChange?: (state: RTCPeerConnectionState) => void;
   /** Optional host callback for Meta/Home button edge presses (button 16). */
   onControllerMetaPress?: (event: { controllerId: number; gamepad: Gamepad }) => void;
+  /** Back + Start pressed together; fires even while host input is paused. */
+  onOnScreenKeyboardChord?: (event: { controllerId: number }) => void;
+  /** Gamepad mouse mode was switched with Back + right stick click. */
+  onGamepadMouseModeChange?: (enabled: boolean) => void;
 }
 
 function timestampUs(sourceTimestampMs?: number): bigint {
@@ -357,122 +441,6 @@ export function quantizeMouseDeltaWithResidual(accumulatedDelta: number): { send
   };
 }
 
-class MouseDeltaFilter {
-  private x = 0;
-  private y = 0;
-  private lastTsMs = 0;
-  private velocityX = 0;
-  private velocityY = 0;
-  private rejectedX = 0;
-  private rejectedY = 0;
-  private pendingX = 0;
-  private pendingY = 0;
-  private sawZero = false;
-  private relaxedForRawInput = false;
-
-  public setRelaxedForRawInput(value: boolean): void {
-    this.relaxedForRawInput = value;
-  }
-
-  public getX(): number {
-    return this.x;
-  }
-
-  public getY(): number {
-    return this.y;
-  }
-
-  public reset(): void {
-    this.x = 0;
-    this.y = 0;
-    this.lastTsMs = 0;
-    this.velocityX = 0;
-    this.velocityY = 0;
-    this.rejectedX = 0;
-    this.rejectedY = 0;
-    this.pendingX = 0;
-    this.pendingY = 0;
-    this.sawZero = false;
-  }
-
-  public update(dx: number, dy: number, tsMs: number): boolean {
-    if (dx === 0 && dy === 0) {
-      if (this.sawZero) {
-        this.pendingX = 0;
-        this.pendingY = 0;
-      } else {
-        this.sawZero = true;
-      }
-      return false;
-    }
-
-    this.sawZero = false;
-    if (this.pendingX === 0 && this.pendingY === 0) {
-      if (tsMs < this.lastTsMs) {
-        this.pendingX = dx;
-        this.pendingY = dy;
-        return false;
-      }
-    } else {
-      dx += this.pendingX;
-      dy += this.pendingY;
-      this.pendingX = 0;
-      this.pendingY = 0;
-    }
-
-    const dot = dx * this.x + dy * this.y;
-    const magIncoming = dx * dx + dy * dy;
-    const magPrev = this.x * this.x + this.y * this.y;
-    let accept = true;
-
-    const dtMs = tsMs - this.lastTsMs;
-    const directionReversalCosineThreshold = this.relaxedForRawInput ? 0.89 : 0.81;
-    if (dtMs < 0.95 && dot < 0 && magPrev !== 0 && dot * dot > directionReversalCosineThreshold * magIncoming * magPrev) {
-      const ratio = Math.sqrt(magIncoming) / Math.sqrt(magPrev);
-      let distToInt = Math.abs(ratio - Math.trunc(ratio));
-      if (distToInt > 0.5) {
-        distToInt = 1 - distToInt;
-      }
-      const intRatioRejectThreshold = this.relaxedForRawInput ? 0.07 : 0.1;
-      if (distToInt < intRatioRejectThreshold) {
-        accept = false;
-      }
-    }
-
-    const diffX = dx - this.x;
-    const diffY = dy - this.y;
-    const diffMag = diffX * diffX + diffY * diffY;
-
-    if (accept) {
-      const scale = 1 + 0.1 * Math.max(1, Math.min(16, dtMs));
-      const vx2 = 2 * scale * Math.abs(this.velocityX);
-      const vy2 = 2 * scale * Math.abs(this.velocityY);
-      const threshold = Math.max(this.relaxedForRawInput ? 9800 : 8100, vx2 * vx2 + vy2 * vy2);
-      accept = diffMag < threshold;
-      if (!accept && (this.rejectedX !== 0 || this.rejectedY !== 0)) {
-        const rx = dx - this.rejectedX;
-        const ry = dy - this.rejectedY;
-        accept = rx * rx + ry * ry < threshold;
-      }
-    }
-
-    if (accept) {
-      this.velocityX = 0.4 * this.velocityX + 0.6 * diffX;
-      this.velocityY = 0.4 * this.velocityY + 0.6 * diffY;
-      this.x = dx;
-      this.y = dy;
-      this.lastTsMs = tsMs;
-      this.rejectedX = 0;
-      this.rejectedY = 0;
-      return true;
-    }
-
-    this.rejectedX = dx;
-    this.rejectedY = dy;
-    return false;
-  }
-}
-
 function parseResolution(resolution: string): { width: number; height: number } {
   const [rawWidth, rawHeight] = resolution.split("x");
   const width = Number.parseInt(rawWidth ?? "", 10);
@@ -608,8 +576,8 @@ export class GfnWebRtcClient {
 
   // Input mode: all input types (mouse, keyboard, gamepad) work simultaneously
   // Removed exclusive mode switching to allow concurrent input
-  // Timestamp of last gamepad packet sent — used for keepalive
-  private lastGamepadSendMs = 0;
+  // Timestamp of last gamepad packet sent per player slot — used for keepalive
+  private lastGamepadSendMs: number[] = [0, 0, 0, 0];
   // Gamepad keepalive interval: resend last state every 100ms to keep server controller alive
   private static readonly GAMEPAD_KEEPALIVE_MS = 100;
   private static readonly NATIVE_INPUT_PROTOCOL_FALLBACK = 3;
@@ -621,7 +589,10 @@ export class GfnWebRtcClient {
   private static readonly DEFAULT_PARTIAL_RELIABLE_THRESHOLD_MS = 300;
   private static readonly RELIABLE_MOUSE_BACKPRESSURE_BYTES = 64 * 1024;
   private static readonly BACKPRESSURE_LOG_INTERVAL_MS = 2000;
+  private static readonly LATENCY_PROBE_SAMPLE_SIZE = 16;
+  private static readonly LATENCY_PROBE_HISTORY = 20;
   private static readonly VIDEO_BASE_JITTER_TARGET_MS = 12;
+  private static readonly VIDEO_LOW_LATENCY_JITTER_TARGET_MS = 0;
   private static readonly AUDIO_BASE_JITTER_TARGET_MS = 20;
   private static readonly VIDEO_PRESSURE_JITTER_TARGET_MS = 30;
   private static readonly AUDIO_PRESSURE_JITTER_TARGET_MS = 32;
@@ -632,6 +603,8 @@ export class GfnWebRtcClient {
   private static readonly DECODER_BITRATE_STEP_FACTOR = 0.85;
   private static readonly DECODER_MIN_RECOVERY_BITRATE_KBPS = 4000;
   private static readonly RUMBLE_EFFECT_MS = 500;
+  private static readonly STATS_POLL_INTERVAL_MS = 500;
+  private static readonly LOW_LATENCY_STATS_POLL_INTERVAL_MS = 2000;
   private static readonly RUMBLE_THROTTLE_MS = 500;
   private static readonly HAPTICS_LOG_INTERVAL_MS = 5000;
 
@@ -657,9 +630,20 @@ export class GfnWebRtcClient {
     packetsLost: number;
     atMs: number;
   } | null = null;
+  private lastAudioPlayoutSample: AudioPlayoutSample | null = null;
   private renderFpsCounter = { frames: 0, lastUpdate: 0, fps: 0 };
+  // Player slots keyed by GFN controller id; browser gamepad indices are remapped onto them.
+  private gamepadSlots = new GamepadSlotAssigner();
+  private controllerSlotsEnabled: boolean[] = [true, true, true, true];
+  private steamInputDevices: readonly string[] = [];
   private connectedGamepads: Set<number> = new Set();
   private gamepadMetaPressed: Map<number, boolean> = new Map();
+  private gamepadKeyboardChordPressed: Map<number, boolean> = new Map();
+  private gamepadMouseChordPressed: Map<number, boolean> = new Map();
+  private readonly gamepadMouse = new GamepadMouse();
+  /** Player slot whose right stick and triggers drive the pointer, or null when the mode is off. */
+  private gamepadMouseSlot: number | null = null;
+  private gamepadMouseTuning: GamepadMouseTuning = { speed: 1200, accelerationPercent: 50 };
   private lastEmittedDiagnostics: StreamDiagnostics | null = null;
   private previousGamepadStates: Map<number, GamepadInput> = new Map();
   private lastRumbleWeak: number[] = [0, 0, 0, 0];
@@ -672,6 +656,11 @@ export class GfnWebRtcClient {
 
   // Track currently pressed keys (VK codes) for synthetic Escape detection
   private pressedKeys: Set<number> = new Set();
+  // Typed text goes out as INPUT_TEXT from the focused text field instead of key events
+  private textInputMode = false;
+  private latencyProbe: ClickToPhotonProbe | null = null;
+  private latencyProbeContext: CanvasRenderingContext2D | null = null;
+  private latencyProbeSamples: ClickToPhotonSample[] = [];
   // Pointer lock target reference for lock re-acquisition
   private pointerLockTarget: HTMLElement | null = null;
   // Auto-pointer-lock in progress flag
@@ -697,6 +686,7 @@ export class GfnWebRtcClient {
   private mouseDeltaFilter = new MouseDeltaFilter();
   private mouseSensitivity = 1;
   private mouseAccelerationPercent = 1;
+  private mouseRawInput = true;
   private keyboardLayout?: KeyboardLayout;
   private autoFullScreenEnabled = true;
 
@@ -722,6 +712,7 @@ export class GfnWebRtcClient {
   private lastDecoderKeyframeRequestAtMs = 0;
   private negotiatedMaxBitrateKbps = 0;
   private currentBitrateCeilingKbps = 0;
+  private lowLatency = false;
   private receiverLatencyTargets = {
     video: GfnWebRtcClient.VIDEO_BASE_JITTER_TARGET_MS,
     audio: GfnWebRtcClient.AUDIO_BASE_JITTER_TARGET_MS,
@@ -790,9 +781,32 @@ export class GfnWebRtcClient {
     nativeFramesPendingToPresent: undefined,
     nativePartialFlushCount: undefined,
     nativeCompleteFlushCount: undefined,
+    nativeFramesSkippedAhead: undefined,
     nativeTransitionSummary: undefined,
     nativeRequestedStreamingFeaturesSummary: undefined,
     nativeFinalizedStreamingFeaturesSummary: undefined,
+    nativePipelineStageSummary: undefined,
+    nativeHdrMetadata: undefined,
+    nativeEncoderTimecode: undefined,
+    nativeEncoderLatencyMs: undefined,
+    avSyncOffsetMs: undefined,
+    avSyncAudioCorrectionMs: undefined,
+    audioOutputLatencyMs: undefined,
+    audioUnderruns: undefined,
+    audioCodec: undefined,
+    audioSampleRate: undefined,
+    audioChannels: undefined,
+    nativeMemoryBudgetMb: undefined,
+    nativeThreadPrioritySummary: undefined,
+    candidatePairs: undefined,
+    keyframesReceived: undefined,
+    keyframeIntervalMs: undefined,
+    keyframeRequests: undefined,
+    damagedFrames: undefined,
+    decoderErrors: undefined,
+    clickToPhotonSummary: undefined,
+    pathMtu: undefined,
+    videoPacketSize: undefined,
     micState: "uninitialized",
     micEnabled: false,
   };
@@ -804,7 +818,13 @@ export class GfnWebRtcClient {
     options.audioElement.volume = this.outputVolume;
     this.mouseSensitivity = options.mouseSensitivity ?? 1;
     this.mouseAccelerationPercent = Math.max(1, Math.min(150, Math.round(options.mouseAcceleration ?? 1)));
+    this.mouseRawInput = options.mouseRawInput !== false;
+    if (options.gamepadMouseTuning) {
+      this.gamepadMouseTuning = normalizeGamepadMouseTuning(options.gamepadMouseTuning);
+    }
     this.keyboardLayout = options.keyboardLayout;
+    this.controllerSlotsEnabled = normalizeControllerSlotsEnabled(options.controllerSlotsEnabled);
+    this.steamInputDevices = options.steamInputDevices ?? [];
     this.autoFullScreenEnabled = options.autoFullScreen !== false;
 
     // Configure video element for lowest latency playback
@@ -866,6 +886,89 @@ export class GfnWebRtcClient {
     this.log(`Mouse sensitivity set to ${this.mouseSensitivity}`);
   }
 
+  /** Update which player slots local controllers may occupy; takes effect on the next poll. */
+  public setControllerSlotsEnabled(slots: boolean[]): void {
+    this.controllerSlotsEnabled = normalizeControllerSlotsEnabled(slots);
+    this.log(`Controller slots enabled: ${this.controllerSlotsEnabled.map((enabled) => (enabled ? "on" : "off")).join(",")}`);
+  }
+
+  /** While enabled, typing and IME composition are left to the focused text field. */
+  public setTextInputMode(enabled: boolean): void {
+    if (this.textInputMode === enabled) {
+      return;
+    }
+    this.textInputMode = enabled;
+    this.log(`Text input mode ${enabled ? "enabled" : "disabled"}`);
+  }
+
+  /**
+   * While enabled, each left click under pointer lock arms a click-to-photon
+   * measurement that completes on the first frame whose centre changes brightness.
+   */
+  public setLatencyProbeEnabled(enabled: boolean): void {
+    if ((this.latencyProbe !== null) === enabled) {
+      return;
+    }
+    if (enabled) {
+      const canvas = document.createElement("canvas");
+      canvas.width = GfnWebRtcClient.LATENCY_PROBE_SAMPLE_SIZE;
+      canvas.height = GfnWebRtcClient.LATENCY_PROBE_SAMPLE_SIZE;
+      this.latencyProbeContext = canvas.getContext("2d", { willReadFrequently: true });
+      this.latencyProbe = new ClickToPhotonProbe();
+      this.latencyProbeSamples = [];
+    } else {
+      this.latencyProbe = null;
+      this.latencyProbeContext = null;
+      this.diagnostics.clickToPhotonSummary = undefined;
+      this.emitStats();
+    }
+    this.log(`Click-to-photon probe ${enabled ? "enabled" : "disabled"}`);
+  }
+
+  private sampleLatencyProbeFrame(metadata: VideoFrameCallbackMetadata): void {
+    const probe = this.latencyProbe;
+    const context = this.latencyProbeContext;
+    const video = this.options.videoElement;
+    if (!probe || !context || video.videoWidth <= 0 || video.videoHeight <= 0) {
+      return;
+    }
+
+    // The crosshair sits at the centre under pointer lock, so that is where a click shows first.
+    const region = Math.max(8, Math.round(Math.min(video.videoWidth, video.videoHeight) * 0.1));
+    const size = GfnWebRtcClient.LATENCY_PROBE_SAMPLE_SIZE;
+    context.drawImage(
+      video,
+      (video.videoWidth - region) / 2,
+      (video.videoHeight - region) / 2,
+      region,
+      region,
+      0,
+      0,
+      size,
+      size,
+    );
+    const sample = probe.observeFrame({
+      luma: averageLuma(context.getImageData(0, 0, size, size).data),
+      presentedAtMs: metadata.expectedDisplayTime,
+      receiveTimeMs: metadata.receiveTime,
+      decodeMs: metadata.processingDuration !== undefined ? metadata.processingDuration * 1000 : undefined,
+    });
+    if (!sample) {
+      return;
+    }
+
+    this.latencyProbeSamples.push(sample);
+    if (this.latencyProbeSamples.length > GfnWebRtcClient.LATENCY_PROBE_HISTORY) {
+      this.latencyProbeSamples.shift();
+    }
+    const summary = summarizeClickToPhoton(this.latencyProbeSamples);
+    if (summary) {
+      this.diagnostics.clickToPhotonSummary = formatClickToPhotonSummary(summary);
+      this.log(`Click-to-photon ${this.diagnostics.clickToPhotonSummary}`);
+      this.emitStats();
+    }
+  }
+
   /** Update software mouse acceleration strength at runtime (1-150%). */
   public setMouseAccelerationPercent(value: number): void {
     const v = Number.isFinite(value) ? value : 1;
@@ -873,12 +976,57 @@ export class GfnWebRtcClient {
     this.log(`Mouse acceleration set to ${this.mouseAccelerationPercent}%`);
   }
 
+  public setGamepadMouseTuning(tuning: GamepadMouseTuning): void {
+    this.gamepadMouseTuning = normalizeGamepadMouseTuning(tuning);
+  }
+
+  /** Hands a controller's right stick and triggers to the pointer, or gives them back (slot null). */
+  public setGamepadMouseSlot(slot: number | null): void {
+    if (this.gamepadMouseSlot === slot) {
+      return;
+    }
+    this.sendGamepadMouseButtons(this.gamepadMouse.reset());
+    this.gamepadMouseSlot = slot;
+    this.log(slot === null ? "Gamepad mouse mode off" : `Gamepad mouse mode on for player ${slot + 1}`);
+    try {
+      this.options.onGamepadMouseModeChange?.(slot !== null);
+    } catch {
+      // Host callbacks must never break stream input polling.
+    }
+  }
+
+  /** Update whether the next pointer lock asks for unadjusted movement. */
+  public setMouseRawInput(value: boolean): void {
+    this.mouseRawInput = value;
+    this.log(`Raw mouse input ${value ? "enabled" : "disabled"}; applies on next pointer lock`);
+  }
+
   /** Update fullscreen preference used by auto pointer-lock flows at runtime. */
   public setAutoFullScreen(value: boolean): void {
     this.autoFullScreenEnabled = Boolean(value);
     this.log(`Auto fullscreen ${this.autoFullScreenEnabled ? "enabled" : "disabled"}`);
   }
 
+  /**
+   * Host-level input pause (watch-only mini player). Held keys are released and
+   * pointer lock is given back without the synthetic Escape a lost lock would send.
+   */
+  public setInputPaused(paused: boolean): void {
+    if (this.inputPaused === paused) {
+      return;
+    }
+    this.inputPaused = paused;
+    this.log(`Stream input ${paused ? "paused" : "resumed"} by host`);
+    if (!paused) {
+      return;
+    }
+    this.releasePressedKeys("host paused input");
+    if (document.pointerLockElement) {
+      this.suppressNextSyntheticEscapeOnPointerLockLoss();
+      document.exitPointerLock();
+    }
+  }
+
   public suppressNextSyntheticEscapeOnPointerLockLoss(durationMs = 1000): void {
     this.suppressNextSyntheticEscape = true;
     if (this.syntheticEscapeSuppressionTimer !== null) {
@@ -930,12 +1078,27 @@ export class GfnWebRtcClient {
     return result.join("\r\n");
   }
 
+  /**
+   * Change the user's bitrate cap mid-stream. Decoder recovery only ever
+   * steps down from this value, so lowering it also bounds later recovery.
+   */
+  public async setMaxBitrateKbps(kbps: number): Promise<void> {
+    if (!this.pc || !this.pc.localDescription) {
+      return;
+    }
+    this.negotiatedMaxBitrateKbps = Math.max(GfnWebRtcClient.DECODER_MIN_RECOVERY_BITRATE_KBPS, Math.floor(kbps));
+    this.currentBitrateCeilingKbps = this.negotiatedMaxBitrateKbps;
+    this.diagnostics.targetBitrateKbps = this.negotiatedMaxBitrateKbps;
+    this.emitStats();
+    await this.applyBitrateCeilingKbps(this.negotiatedMaxBitrateKbps);
+  }
+
   /**
    * Update the maximum receive bitrate ceiling mid-stream by replacing b=AS
    * in the local SDP and re-applying it. Chrome/Electron honours this change
    * without requiring a full ICE renegotiation.
    */
-  public async setMaxBitrateKbps(kbps: number): Promise<void> {
+  private async applyBitrateCeilingKbps(kbps: number): Promise<void> {
     if (!this.pc || !this.pc.localDescription) {
       return;
     }
@@ -996,6 +1159,13 @@ export class GfnWebRtcClient {
     }
   }
 
+  /** Low-latency mode shows frames as soon as they decode unless decoder pressure needs slack. */
+  private videoBaseJitterTargetMs(): number {
+    return this.lowLatency
+      ? GfnWebRtcClient.VIDEO_LOW_LATENCY_JITTER_TARGET_MS
+      : GfnWebRtcClient.VIDEO_BASE_JITTER_TARGET_MS;
+  }
+
   private registerReceiver(receiver: RTCRtpReceiver, kind: "audio" | "video"): void {
     const alreadyRegistered = this.activeReceivers.some((entry) => entry.receiver === receiver);
     if (!alreadyRegistered) {
@@ -1018,7 +1188,7 @@ export class GfnWebRtcClient {
     this.diagnostics.decoderPressureActive = active;
     this.receiverLatencyTargets.video = active
       ? GfnWebRtcClient.VIDEO_PRESSURE_JITTER_TARGET_MS
-      : GfnWebRtcClient.VIDEO_BASE_JITTER_TARGET_MS;
+      : this.videoBaseJitterTargetMs();
     this.receiverLatencyTargets.audio = active
       ? GfnWebRtcClient.AUDIO_PRESSURE_JITTER_TARGET_MS
       : GfnWebRtcClient.AUDIO_BASE_JITTER_TARGET_MS;
@@ -1062,7 +1232,7 @@ export class GfnWebRtcClient {
     this.lastDecoderKeyframeRequestAtMs = 0;
     this.negotiatedMaxBitrateKbps = 0;
     this.currentBitrateCeilingKbps = 0;
-    this.receiverLatencyTargets.video = GfnWebRtcClient.VIDEO_BASE_JITTER_TARGET_MS;
+    this.receiverLatencyTargets.video = this.videoBaseJitterTargetMs();
     this.receiverLatencyTargets.audio = GfnWebRtcClient.AUDIO_BASE_JITTER_TARGET_MS;
     this.activeReceivers = [];
     this.diagnostics.decoderPressureActive = false;
@@ -1074,13 +1244,32 @@ export class GfnWebRtcClient {
     this.diagnostics.nativeFramesPendingToPresent = undefined;
     this.diagnostics.nativePartialFlushCount = undefined;
     this.diagnostics.nativeCompleteFlushCount = undefined;
+    this.diagnostics.nativeFramesSkippedAhead = undefined;
     this.diagnostics.nativeTransitionSummary = undefined;
     this.diagnostics.nativeRequestedStreamingFeaturesSummary = undefined;
     this.diagnostics.nativeFinalizedStreamingFeaturesSummary = undefined;
+    this.diagnostics.nativePipelineStageSummary = undefined;
+    this.diagnostics.nativeHdrMetadata = undefined;
+    this.diagnostics.nativeEncoderTimecode = undefined;
+    this.diagnostics.nativeEncoderLatencyMs = undefined;
+    this.diagnostics.avSyncOffsetMs = undefined;
+    this.diagnostics.avSyncAudioCorrectionMs = undefined;
+    this.diagnostics.audioOutputLatencyMs = undefined;
+    this.diagnostics.audioUnderruns = undefined;
+    this.diagnostics.audioCodec = undefined;
+    this.diagnostics.audioSampleRate = undefined;
+    this.diagnostics.audioChannels = undefined;
+    this.diagnostics.candidatePairs = undefined;
+    this.diagnostics.keyframesReceived = undefined;
+    this.diagnostics.keyframeIntervalMs = undefined;
+    this.diagnostics.keyframeRequests = undefined;
+    this.diagnostics.damagedFrames = undefined;
+    this.diagnostics.decoderErrors = undefined;
   }
 
   private resetDiagnostics(): void {
     this.lastStatsSample = null;
+    this.lastAudioPlayoutSample = null;
     this.lastEmittedDiagnostics = null;
     this.currentCodec = "";
     this.currentResolution = "";
@@ -1137,9 +1326,32 @@ export class GfnWebRtcClient {
       nativeFramesPendingToPresent: undefined,
       nativePartialFlushCount: undefined,
       nativeCompleteFlushCount: undefined,
+      nativeFramesSkippedAhead: undefined,
       nativeTransitionSummary: undefined,
       nativeRequestedStreamingFeaturesSummary: undefined,
       nativeFinalizedStreamingFeaturesSummary: undefined,
+      nativePipelineStageSummary: undefined,
+      nativeHdrMetadata: undefined,
+      nativeEncoderTimecode: undefined,
+      nativeEncoderLatencyMs: undefined,
+      avSyncOffsetMs: undefined,
+      avSyncAudioCorrectionMs: undefined,
+      audioOutputLatencyMs: undefined,
+      audioUnderruns: undefined,
+      audioCodec: undefined,
+      audioSampleRate: undefined,
+      audioChannels: undefined,
+      nativeMemoryBudgetMb: undefined,
+      nativeThreadPrioritySummary: undefined,
+      candidatePairs: undefined,
+      keyframesReceived: undefined,
+      keyframeIntervalMs: undefined,
+      keyframeRequests: undefined,
+      damagedFrames: undefined,
+      decoderErrors: undefined,
+      clickToPhotonSummary: undefined,
+      pathMtu: undefined,
+      videoPacketSize: undefined,
       micState: this.micState,
       micEnabled: this.micManager?.isEnabled() ?? false,
     };
@@ -1220,7 +1432,7 @@ export class GfnWebRtcClient {
     this.clearSyntheticEscapeSuppression();
   }
 
-  private setupStatsPolling(): void {
+  private setupStatsPolling(intervalMs: number): void {
     if (this.statsTimer !== null) {
       window.clearInterval(this.statsTimer);
     }
@@ -1233,7 +1445,7 @@ export class GfnWebRtcClient {
       void this.collectStats().finally(() => {
         this.statsPollInFlight = false;
       });
-    }, 500);
+    }, intervalMs);
   }
 
   private updateRenderFps(): void {
@@ -1481,7 +1693,7 @@ export class GfnWebRtcClient {
       return false;
     }
 
-    await this.setMaxBitrateKbps(next);
+    await this.applyBitrateCeilingKbps(next);
     this.currentBitrateCeilingKbps = next;
     this.diagnostics.decoderRecoveryAction = "bitrate_step_down";
     this.log(`Decoder recovery: bitrate ceiling stepped down ${current} -> ${next} kbps`);
@@ -1545,6 +1757,8 @@ export class GfnWebRtcClient {
     const report = await this.pc.getStats();
     const now = performance.now();
     let inboundVideo: Record<string, unknown> | null = null;
+    let inboundAudio: Record<string, unknown> | null = null;
+    let audioPlayout: Record<string, unknown> | null = null;
     let activePair: Record<string, unknown> | null = null;
     const codecs = new Map<string, Record<string, unknown>>();
     let framesReceived = 0;
@@ -1558,6 +1772,14 @@ export class GfnWebRtcClient {
         inboundVideo = stats;
       }
 
+      if (entry.type === "inbound-rtp" && stats.kind === "audio") {
+        inboundAudio = stats;
+      }
+
+      if (entry.type === "media-playout") {
+        audioPlayout = stats;
+      }
+
       if (entry.type === "candidate-pair") {
         if (stats.state === "succeeded" && stats.nominated === true) {
           activePair = stats;
@@ -1571,6 +1793,26 @@ export class GfnWebRtcClient {
       }
     }
 
+    // Audio output buffering, from RTCAudioPlayoutStats
+    if (audioPlayout) {
+      const sample: AudioPlayoutSample = {
+        totalPlayoutDelay: Number(audioPlayout.totalPlayoutDelay ?? 0),
+        totalSamplesCount: Number(audioPlayout.totalSamplesCount ?? 0),
+      };
+      this.diagnostics.audioOutputLatencyMs =
+        averagePlayoutDelayMs(this.lastAudioPlayoutSample, sample) ?? this.diagnostics.audioOutputLatencyMs;
+      this.lastAudioPlayoutSample = sample;
+      // Each synthesized-samples event is the device running dry
+      this.diagnostics.audioUnderruns = Number(audioPlayout.synthesizedSamplesEvents ?? 0);
+    }
+
+    const audioCodec = inboundAudio ? codecs.get(inboundAudio.codecId as string) : undefined;
+    if (audioCodec) {
+      this.diagnostics.audioCodec = String(audioCodec.mimeType ?? "").replace(/^audio\//i, "").toUpperCase() || undefined;
+      this.diagnostics.audioSampleRate = Number(audioCodec.clockRate) || undefined;
+      this.diagnostics.audioChannels = Number(audioCodec.channels) || undefined;
+    }
+
     // Process video track stats
     if (inboundVideo) {
       const bytes = Number(inboundVideo.bytesReceived ?? 0);
@@ -1626,6 +1868,9 @@ export class GfnWebRtcClient {
         this.log("Warning: inbound video packets received but 0 frames decoded (decoder stall)");
       }
 
+      this.diagnostics.keyframesReceived = Number(inboundVideo.keyFramesDecoded ?? 0);
+      this.diagnostics.keyframeRequests = Number(inboundVideo.pliCount ?? 0) + Number(inboundVideo.firCount ?? 0);
+
       // Decode FPS
       this.diagnostics.decodeFps = Math.round(Number(inboundVideo.framesPerSecond ?? 0));
 
@@ -1645,6 +1890,11 @@ export class GfnWebRtcClient {
         this.diagnostics.jitterBufferDelayMs = Math.round((jbDelay / jbEmitted) * 1000 * 10) / 10;
       }
 
+      this.diagnostics.avSyncOffsetMs = estimateAvSyncOffsetMs(
+        inboundAudio?.estimatedPlayoutTimestamp,
+        inboundVideo.estimatedPlayoutTimestamp,
+      );
+
       // Get codec information
       const codecId = inboundVideo.codecId as string;
       if (codecId && codecs.has(codecId)) {
@@ -1713,6 +1963,13 @@ export class GfnWebRtcClient {
       await this.maybeRecoverFromDecoderPressure(pressureSignal);
     }
 
+    const candidatePairs = summarizeCandidatePairs(
+      Array.from(report.values(), (entry) => entry as unknown as Record<string, unknown>),
+    );
+    this.diagnostics.candidatePairs = candidatePairs.length > 0
+      ? candidatePairs.map(formatCandidatePair).join("\n")
+      : undefined;
+
     // RTT from active candidate pair
     if (activePair?.currentRoundTripTime !== undefined) {
       const rtt = Number(activePair.currentRoundTripTime);
@@ -1872,9 +2129,10 @@ export class GfnWebRtcClient {
     this.resetInputState();
     this.resetDiagnostics();
     this.connectedGamepads.clear();
+    this.gamepadSlots.reset();
     this.previousGamepadStates.clear();
     this.gamepadSendCount = 0;
-    this.lastGamepadSendMs = 0;
+    this.lastGamepadSendMs.fill(0);
     this.reliableDropLogged = false;
     this.gamepadBitmap = 0;
     this.pendingMouseDxFloat = 0;
@@ -1943,7 +2201,7 @@ export class GfnWebRtcClient {
     this.inputEncoder.setProtocolVersion(version);
     this.inputEncoder.resetGamepadSequences();
     this.previousGamepadStates.clear();
-    this.lastGamepadSendMs = 0;
+    this.lastGamepadSendMs.fill(0);
     this.log(`Native input protocol updated to v${version}`);
 
   }
@@ -1954,14 +2212,31 @@ export class GfnWebRtcClient {
 
       // Set up render FPS tracking using video element
       const video = this.options.videoElement;
-      const frameCallback = () => {
+      const frameCallback = (_now: DOMHighResTimeStamp, metadata: VideoFrameCallbackMetadata) => {
         this.updateRenderFps();
+        this.sampleLatencyProbeFrame(metadata);
         if (this.videoStream.active) {
           video.requestVideoFrameCallback(frameCallback);
         }
       };
       video.requestVideoFrameCallback(frameCallback);
 
+      // The server can switch resolution mid-stream (a new provisional SSRC on
+      // the same m-line). Chromium renegotiates the decoder in place, so only
+      // the pointer scaling and diagnostics need to follow the new frame size.
+      video.onresize = () => {
+        if (video.videoWidth <= 0 || video.videoHeight <= 0) {
+          return;
+        }
+        const resolution = `${video.videoWidth}x${video.videoHeight}`;
+        if (resolution === this.currentResolution) {
+          return;
+        }
+        this.log(`Stream resolution changed ${this.currentResolution || "unknown"} -> ${resolution}`);
+        this.currentResolution = resolution;
+        this.diagnostics.resolution = resolution;
+      };
+
       this.log(
         `Video element before play: paused=${video.paused}, readyState=${video.readyState}, size=${video.videoWidth}x${video.videoHeight}`,
       );
@@ -2175,111 +2450,193 @@ export class GfnWebRtcClient {
       return;
     }
 
-    let connectedCount = 0;
     const nowMs = performance.now();
+    const devices: GamepadSlotDevice[] = [];
+    for (const gamepad of gamepads) {
+      if (gamepad?.connected && !isSteamInputManagedGamepad(gamepad.id, this.steamInputDevices)) {
+        devices.push({ index: gamepad.index, id: gamepad.id });
+      }
+    }
+    const slotChanges = this.gamepadSlots.sync(devices, this.controllerSlotsEnabled);
 
-    for (let i = 0; i < Math.min(gamepads.length, GAMEPAD_MAX_CONTROLLERS); i++) {
-      const gamepad = gamepads[i];
+    for (const { slot, device } of slotChanges.released) {
+      this.releaseGamepadSlot(slot, gamepads[device.index] ?? undefined);
+    }
 
-      if (gamepad && gamepad.connected) {
-        connectedCount++;
-        this.updateGamepadBitmap(i, gamepad);
-        const metaPressed = Boolean(gamepad.buttons[16]?.pressed);
-        const prevMetaPressed = this.gamepadMetaPressed.get(i) ?? false;
-        if (metaPressed && !prevMetaPressed) {
-          try {
-            this.options.onControllerMetaPress?.({ controllerId: i, gamepad });
-          } catch {
-            // Host callbacks must never break stream input polling.
-          }
-        }
-        this.gamepadMetaPressed.set(i, metaPressed);
-
-        // Track connected gamepads and update bitmap
-        if (!this.connectedGamepads.has(i)) {
-          this.connectedGamepads.add(i);
-          this.log(`Gamepad ${i} connected: ${gamepad.id}`);
-          this.log(`  Buttons: ${gamepad.buttons.length}, Axes: ${gamepad.axes.length}, Mapping: ${gamepad.mapping}`);
-          this.log(`  Bitmap now: 0x${this.gamepadBitmap.toString(16)}`);
-          this.diagnostics.connectedGamepads = this.connectedGamepads.size;
-          this.emitStats();
-        }
+    for (const [index, slot] of slotChanges.assignments) {
+      const gamepad = gamepads[index];
+      if (!gamepad) {
+        continue;
+      }
 
-        // Read and encode gamepad state
-        // Skip forwarding to the stream if input is blocked (dashboard open) or
-        // the native renderer is handling controller input directly.
-        if (streamInputBlocked || this.nativeInputActive) {
-          continue;
+      this.updateGamepadBitmap(slot, gamepad);
+      const metaPressed = Boolean(gamepad.buttons[16]?.pressed);
+      const prevMetaPressed = this.gamepadMetaPressed.get(slot) ?? false;
+      if (metaPressed && !prevMetaPressed) {
+        try {
+          this.options.onControllerMetaPress?.({ controllerId: slot, gamepad });
+        } catch {
+          // Host callbacks must never break stream input polling.
         }
-        const gamepadInput = this.readGamepadState(gamepad, i);
-        const stateChanged = this.hasGamepadStateChanged(i, gamepadInput);
-
-        // Send if state changed OR as a keepalive to maintain server controller presence
-        // Games detect active input device by receiving packets; if we stop sending,
-        // the game falls back to showing keyboard/mouse prompts.
-        const needsKeepalive = !stateChanged
-          && (nowMs - this.lastGamepadSendMs) >= GfnWebRtcClient.GAMEPAD_KEEPALIVE_MS;
-
-        if (stateChanged || needsKeepalive) {
-          const usePR = this.canSendGamepadPartiallyReliable(i);
-          const bytes = this.inputEncoder.encodeGamepadState(gamepadInput, this.gamepadBitmap, usePR);
-          if (usePR) {
-            this.sendGamepad(bytes);
-          } else {
-            this.sendReliable(bytes);
-          }
-          this.lastGamepadSendMs = nowMs;
-
-          if (stateChanged) {
-            this.previousGamepadStates.set(i, { ...gamepadInput });
-          }
+      }
+      this.gamepadMetaPressed.set(slot, metaPressed);
 
-          // Log first N gamepad sends for debugging
-          if (stateChanged) {
-            this.gamepadSendCount++;
-            if (this.gamepadSendCount <= 20) {
-              this.log(`Gamepad send #${this.gamepadSendCount}: pad=${i} btns=0x${gamepadInput.buttons.toString(16)} lt=${gamepadInput.leftTrigger} rt=${gamepadInput.rightTrigger} lx=${gamepadInput.leftStickX} ly=${gamepadInput.leftStickY} rx=${gamepadInput.rightStickX} ry=${gamepadInput.rightStickY} bytes=${bytes.length}`);
-            }
-          }
+      const buttonsPressed = gamepad.buttons.map((button) => button.pressed);
+      const chordPressed = isOnScreenKeyboardChord(buttonsPressed);
+      if (chordPressed && !(this.gamepadKeyboardChordPressed.get(slot) ?? false)) {
+        try {
+          this.options.onOnScreenKeyboardChord?.({ controllerId: slot });
+        } catch {
+          // Host callbacks must never break stream input polling.
         }
-      } else if (this.connectedGamepads.has(i)) {
-        // Gamepad disconnected — clear bit from bitmap
-        this.stopGamepadRumble(i, gamepad ?? undefined);
-        this.connectedGamepads.delete(i);
-        this.gamepadMetaPressed.delete(i);
-        this.previousGamepadStates.delete(i);
-        this.clearGamepadBitmap(i);
-        this.log(`Gamepad ${i} disconnected, bitmap now: 0x${this.gamepadBitmap.toString(16)}`);
+      }
+      this.gamepadKeyboardChordPressed.set(slot, chordPressed);
+
+      const mouseChordPressed = isGamepadMouseChord(buttonsPressed);
+      if (mouseChordPressed && !(this.gamepadMouseChordPressed.get(slot) ?? false)) {
+        this.setGamepadMouseSlot(this.gamepadMouseSlot === slot ? null : slot);
+      }
+      this.gamepadMouseChordPressed.set(slot, mouseChordPressed);
+
+      // Track connected gamepads and update bitmap
+      if (!this.connectedGamepads.has(slot)) {
+        this.connectedGamepads.add(slot);
+        this.log(`Gamepad ${index} connected as player ${slot + 1}: ${gamepad.id}`);
+        this.log(`  Buttons: ${gamepad.buttons.length}, Axes: ${gamepad.axes.length}, Mapping: ${gamepad.mapping}`);
+        this.log(`  Bitmap now: 0x${this.gamepadBitmap.toString(16)}`);
         this.diagnostics.connectedGamepads = this.connectedGamepads.size;
         this.emitStats();
+      }
 
-        // Send state with updated bitmap (gamepad bit cleared = disconnected)
-        const disconnectState: GamepadInput = {
-          controllerId: i,
-          buttons: 0,
-          leftTrigger: 0,
-          rightTrigger: 0,
-          leftStickX: 0,
-          leftStickY: 0,
-          rightStickX: 0,
-          rightStickY: 0,
-          connected: false,
-          timestampUs: timestampUs(),
-        };
-        const usePR = this.canSendGamepadPartiallyReliable(i);
-        const bytes = this.inputEncoder.encodeGamepadState(disconnectState, this.gamepadBitmap, usePR);
+      // Read and encode gamepad state
+      // Skip forwarding to the stream if input is blocked (dashboard open) or
+      // the native renderer is handling controller input directly.
+      if (streamInputBlocked || this.nativeInputActive) {
+        continue;
+      }
+      const gamepadInput = this.readGamepadState(gamepad, slot);
+      if (this.gamepadMouseSlot === slot) {
+        this.driveGamepadMouse(gamepad, nowMs);
+        // The game must not also see the stick and triggers that now move the pointer.
+        gamepadInput.rightStickX = 0;
+        gamepadInput.rightStickY = 0;
+        gamepadInput.leftTrigger = 0;
+        gamepadInput.rightTrigger = 0;
+      }
+      const stateChanged = this.hasGamepadStateChanged(slot, gamepadInput);
+
+      // Send if state changed OR as a keepalive to maintain server controller presence
+      // Games detect active input device by receiving packets; if we stop sending,
+      // the game falls back to showing keyboard/mouse prompts.
+      const needsKeepalive = !stateChanged
+        && (nowMs - this.lastGamepadSendMs[slot]) >= GfnWebRtcClient.GAMEPAD_KEEPALIVE_MS;
+
+      if (stateChanged || needsKeepalive) {
+        const usePR = this.canSendGamepadPartiallyReliable(slot);
+        const bytes = this.inputEncoder.encodeGamepadState(gamepadInput, this.gamepadBitmap, usePR);
         if (usePR) {
           this.sendGamepad(bytes);
         } else {
           this.sendReliable(bytes);
         }
+        this.lastGamepadSendMs[slot] = nowMs;
+
+        if (stateChanged) {
+          this.previousGamepadStates.set(slot, { ...gamepadInput });
+        }
+
+        // Log first N gamepad sends for debugging
+        if (stateChanged) {
+          this.gamepadSendCount++;
+          if (this.gamepadSendCount <= 20) {
+            this.log(`Gamepad send #${this.gamepadSendCount}: player=${slot + 1} btns=0x${gamepadInput.buttons.toString(16)} lt=${gamepadInput.leftTrigger} rt=${gamepadInput.rightTrigger} lx=${gamepadInput.leftStickX} ly=${gamepadInput.leftStickY} rx=${gamepadInput.rightStickX} ry=${gamepadInput.rightStickY} bytes=${bytes.length}`);
+          }
+        }
       }
     }
 
-    this.diagnostics.connectedGamepads = connectedCount;
+    this.diagnostics.connectedGamepads = slotChanges.assignments.size;
     this.updateHapticsAdvertisement(this.hasConnectedHapticGamepad());
   }
 
+  private driveGamepadMouse(gamepad: Gamepad, nowMs: number): void {
+    const axes = readGamepadAxes(gamepad);
+    const frame = this.gamepadMouse.update(
+      {
+        stickX: axes.rightStickX,
+        stickY: -axes.rightStickY,
+        leftTrigger: axes.leftTrigger,
+        rightTrigger: axes.rightTrigger,
+      },
+      nowMs,
+      this.gamepadMouseTuning,
+    );
+    if (frame.dx !== 0 || frame.dy !== 0) {
+      const payload = this.inputEncoder.encodeMouseMove({
+        dx: Math.max(-32768, Math.min(32767, frame.dx)),
+        dy: Math.max(-32768, Math.min(32767, frame.dy)),
+        timestampUs: timestampUs(),
+      });
+      this.sendInputPacket(payload, INPUT_MOUSE_REL);
+    }
+    this.sendGamepadMouseButtons(frame.buttons);
+  }
+
+  private sendGamepadMouseButtons(changes: GamepadMouseButtonChange[]): void {
+    if (!this.inputReady) {
+      return;
+    }
+    for (const change of changes) {
+      const payload = change.down
+        ? this.inputEncoder.encodeMouseButtonDown({ button: change.button, timestampUs: timestampUs() })
+        : this.inputEncoder.encodeMouseButtonUp({ button: change.button, timestampUs: timestampUs() });
+      this.sendReliable(payload);
+    }
+  }
+
+  private releaseGamepadSlot(slot: number, gamepad?: Gamepad): void {
+    if (!this.connectedGamepads.has(slot)) {
+      return;
+    }
+    if (this.gamepadMouseSlot === slot) {
+      this.setGamepadMouseSlot(null);
+    }
+
+    // Gamepad disconnected — clear bit from bitmap
+    this.stopGamepadRumble(slot, gamepad);
+    this.connectedGamepads.delete(slot);
+    this.gamepadMetaPressed.delete(slot);
+    this.gamepadKeyboardChordPressed.delete(slot);
+    this.gamepadMouseChordPressed.delete(slot);
+    this.previousGamepadStates.delete(slot);
+    this.lastGamepadSendMs[slot] = 0;
+    this.clearGamepadBitmap(slot);
+    this.log(`Player ${slot + 1} gamepad released, bitmap now: 0x${this.gamepadBitmap.toString(16)}`);
+    this.diagnostics.connectedGamepads = this.connectedGamepads.size;
+    this.emitStats();
+
+    // Send state with updated bitmap (gamepad bit cleared = disconnected)
+    const disconnectState: GamepadInput = {
+      controllerId: slot,
+      buttons: 0,
+      leftTrigger: 0,
+      rightTrigger: 0,
+      leftStickX: 0,
+      leftStickY: 0,
+      rightStickX: 0,
+      rightStickY: 0,
+      connected: false,
+      timestampUs: timestampUs(),
+    };
+    const usePR = this.canSendGamepadPartiallyReliable(slot);
+    const bytes = this.inputEncoder.encodeGamepadState(disconnectState, this.gamepadBitmap, usePR);
+    if (usePR) {
+      this.sendGamepad(bytes);
+    } else {
+      this.sendReliable(bytes);
+    }
+  }
+
   private readGamepadState(gamepad: Gamepad, controllerId: number): GamepadInput {
     const buttons = mapGamepadButtons(gamepad);
     const axes = readGamepadAxes(gamepad);
@@ -2322,7 +2679,10 @@ export class GfnWebRtcClient {
 
   private onGamepadDisconnected = (event: GamepadEvent): void => {
     this.log(`Gamepad disconnected event: ${event.gamepad.id}`);
-    this.stopGamepadRumble(event.gamepad.index, event.gamepad);
+    const slot = this.gamepadSlots.slotOf(event.gamepad.index);
+    if (slot !== null) {
+      this.stopGamepadRumble(slot, event.gamepad);
+    }
     // The polling loop will detect and handle the disconnection
   };
 
@@ -2342,28 +2702,18 @@ export class GfnWebRtcClient {
     }
 
     const connected: ConnectedRumbleGamepad[] = [];
-    for (let i = 0; i < Math.min(gamepads.length, GAMEPAD_MAX_CONTROLLERS); i++) {
-      const gamepad = gamepads[i];
+    for (let slot = 0; slot < GAMEPAD_MAX_CONTROLLERS; slot++) {
+      const index = this.gamepadSlots.deviceIndexForSlot(slot);
+      const gamepad = index === null ? null : gamepads[index];
       if (gamepad?.connected) {
-        connected.push({ index: i, gamepad, api: getGamepadRumbleApi(gamepad) });
+        connected.push({ index: slot, gamepad, api: getGamepadRumbleApi(gamepad) });
       }
     }
     return connected;
   }
 
   private hasConnectedHapticGamepad(): boolean {
-    const gamepads = navigator.getGamepads();
-    if (!gamepads) {
-      return false;
-    }
-
-    for (let i = 0; i < Math.min(gamepads.length, GAMEPAD_MAX_CONTROLLERS); i++) {
-      const gamepad = gamepads[i];
-      if (gamepad?.connected && getGamepadRumbleApi(gamepad)) {
-        return true;
-      }
-    }
-    return false;
+    return this.getConnectedRumbleGamepads().some((candidate) => candidate.api);
   }
 
   private updateHapticsAdvertisement(enabled: boolean): void {
@@ -2701,9 +3051,11 @@ export class GfnWebRtcClient {
     }
   }
 
-  private createDataChannels(pc: RTCPeerConnection): void {
+  private createDataChannels(pc: RTCPeerConnection, highPriority: boolean): void {
+    const priority: PrioritizedDataChannelInit = highPriority ? { priority: "high" } : {};
     this.reliableInputChannel = pc.createDataChannel("input_channel_v1", {
       ordered: true,
+      ...priority,
     });
 
     this.reliableInputChannel.onopen = () => {
@@ -2718,6 +3070,7 @@ export class GfnWebRtcClient {
     this.partiallyReliableInputChannel = pc.createDataChannel("input_channel_partially_reliable", {
       ordered: false,
       maxPacketLifeTime: this.partialReliableThresholdMs,
+      ...priority,
     });
 
     this.partiallyReliableInputChannel.onopen = () => {
@@ -2840,16 +3193,6 @@ export class GfnWebRtcClient {
     }
   }
 
-  private async requestPointerLockCompat(
-    lockTarget: HTMLElement,
-    options?: { unadjustedMovement?: boolean },
-  ): Promise<void> {
-    const maybePromise = lockTarget.requestPointerLock(options as any) as unknown;
-    if (maybePromise && typeof (maybePromise as Promise<void>).then === "function") {
-      await (maybePromise as Promise<void>);
-    }
-  }
-
   private syncLockKeysState(event: KeyboardEvent): void {
     const state = lockKeysStateFromEvent(event);
     if (state === this.lastLockKeysState) {
@@ -2914,22 +3257,18 @@ export class GfnWebRtcClient {
 
     this.requestEscapeKeyboardLock();
 
-    try {
-      await this.requestPointerLockCompat(lockTarget, { unadjustedMovement: true });
+    const mode = await requestPointerLockMovement(lockTarget, this.mouseRawInput);
+    if (mode === "raw") {
       this.log("Pointer lock acquired with unadjustedMovement=true (raw/unaccelerated)");
-    } catch (err) {
-      const domErr = err as DOMException;
-      if (domErr?.name === "NotSupportedError") {
-        this.log("unadjustedMovement not supported, falling back to standard pointer lock (accelerated)");
-        await this.requestPointerLockCompat(lockTarget);
-      } else {
-        throw err;
-      }
+    } else if (this.mouseRawInput) {
+      this.log("unadjustedMovement not supported, falling back to standard pointer lock (accelerated)");
+    } else {
+      this.log("Pointer lock acquired with OS mouse acceleration (raw input disabled)");
     }
   }
 
   private async attemptAutoPointerLock(ensureFullscreen = true): Promise<void> {
-    if (this.autoPointerLockInProgress) return;
+    if (this.autoPointerLockInProgress || this.inputPaused) return;
     this.autoPointerLockInProgress = true;
     try {
       const target = this.pointerLockTarget ?? this.options.videoElement;
@@ -2946,7 +3285,7 @@ export class GfnWebRtcClient {
       } catch (err) {
         // Fallback to a simpler request if the guarded method fails
         try {
-          await this.requestPointerLockCompat(target, { unadjustedMovement: true });
+          await requestPointerLockMovement(target, this.mouseRawInput);
           this.log("Auto pointer lock acquired (fallback)");
           return;
         } catch (err2) {
@@ -3043,6 +3382,18 @@ export class GfnWebRtcClient {
     return Array.from(text).length;
   }
 
+  /** A single press and release, for keys that composed text cannot carry. */
+  public sendKeyTap(code: OnScreenKeyboardCode): boolean {
+    if (!this.inputReady) {
+      return false;
+    }
+
+    const key = codeMap[code];
+    this.sendKeyPacket(key.vk, key.scancode, 0, true);
+    this.sendKeyPacket(key.vk, key.scancode, 0, false);
+    return true;
+  }
+
   private sendGamepad(payload: Uint8Array): void {
     this.sendPartiallyReliable(payload);
   }
@@ -3360,21 +3711,14 @@ export class GfnWebRtcClient {
         return;
       }
 
-      // Apply user-configured sensitivity, then optional software acceleration.
-      let adjustedDx = this.mouseDeltaFilter.getX() * this.mouseSensitivity;
-      let adjustedDy = this.mouseDeltaFilter.getY() * this.mouseSensitivity;
-
-      if (this.mouseAccelerationPercent > 1) {
-        const speed = Math.hypot(adjustedDx, adjustedDy);
-        const strength = (this.mouseAccelerationPercent - 1) / 149;
-        // Gentle curve: low-speed precision, high-speed turn boost (caps at +60% at 150%).
-        const accelFactor = 1 + Math.min(0.6 * strength, (speed / 50) * strength);
-        adjustedDx *= accelFactor;
-        adjustedDy *= accelFactor;
-      }
-
-      this.pendingMouseDxFloat += adjustedDx;
-      this.pendingMouseDyFloat += adjustedDy;
+      const adjusted = applyMouseFeel(
+        this.mouseDeltaFilter.getX(),
+        this.mouseDeltaFilter.getY(),
+        this.mouseSensitivity,
+        this.mouseAccelerationPercent,
+      );
+      this.pendingMouseDxFloat += adjusted.dx;
+      this.pendingMouseDyFloat += adjusted.dy;
       this.pendingMouseTimestampUs = timestampUs(eventTimestampMs);
     };
 
@@ -3431,6 +3775,9 @@ export class GfnWebRtcClient {
       if (!this.inputReady) {
         return;
       }
+      if (this.textInputMode && !shouldForwardRawKeyInTextMode(event)) {
+        return;
+      }
 
       this.syncLockKeysState(event);
 
@@ -3487,6 +3834,10 @@ export class GfnWebRtcClient {
       if (!mapped) {
         return;
       }
+      if (this.textInputMode && !this.pressedKeys.has(mapped.vk)) {
+        // The key went down as text, so the server never saw it pressed.
+        return;
+      }
 
       event.preventDefault();
       this.pressedKeys.delete(mapped.vk);
@@ -3514,6 +3865,9 @@ export class GfnWebRtcClient {
       });
       // Official GFN client sends all mouse events on reliable channel (input_channel_v1)
       this.sendReliable(payload);
+      if (event.button === 0) {
+        this.latencyProbe?.arm(event.timeStamp, performance.now());
+      }
     };
 
     const onMouseUp = (event: MouseEvent) => {
@@ -4034,6 +4388,27 @@ export class GfnWebRtcClient {
     }
   }
 
+  private primaryVideoTransceiver(pc: RTCPeerConnection): RTCRtpTransceiver | undefined {
+    return pc.getTransceivers().find((t) => t.receiver.track.kind === "video");
+  }
+
+  /**
+   * The browser path renders one video layer. When the server offers several
+   * video m-lines, answer the extra ones inactive so the server does not send
+   * them. Chromium cannot receive rid-based simulcast, so such offers already
+   * end up with a single layer; switching layers mid-stream is native-only.
+   */
+  private declineExtraVideoLayers(pc: RTCPeerConnection): void {
+    const primary = this.primaryVideoTransceiver(pc);
+    for (const transceiver of pc.getTransceivers()) {
+      if (transceiver === primary || transceiver.receiver.track.kind !== "video" || transceiver.direction === "stopped") {
+        continue;
+      }
+      transceiver.direction = "inactive";
+      this.log(`Declined extra video layer mid=${transceiver.mid ?? "?"} so the server sends a single layer`);
+    }
+  }
+
   /**
    * Apply setCodecPreferences roughly matching GFN web client behavior:
    * preferred codec + RTX/FlexFEC only (receiver capabilities first).
@@ -4132,6 +4507,7 @@ export class GfnWebRtcClient {
 
   async handleOffer(offerSdp: string, session: SessionInfo, settings: OfferSettings): Promise<void> {
     this.cleanupPeerConnection();
+    this.lowLatency = settings.lowLatency === true;
     this.log("=== handleOffer START ===");
     this.log(`Session: id=${session.sessionId}, status=${session.status}, serverIp=${session.serverIp}`);
     this.log(`Signaling: server=${session.signalingServer}, url=${session.signalingUrl}`);
@@ -4175,13 +4551,35 @@ export class GfnWebRtcClient {
       }
     }
 
+    const iceServers = mergeIceServers(settings.customIceServers ?? [], session.iceServers);
+    const iceTransportPolicy = settings.iceTransportPolicy ?? "all";
+    if (settings.customIceServers?.length || iceTransportPolicy !== "all") {
+      this.log(
+        `ICE overrides: policy=${iceTransportPolicy}, custom servers=${(settings.customIceServers ?? []).map((server) => server.urls.join(",")).join(" | ") || "none"}`,
+      );
+    }
+
     const rtcConfig: RTCConfiguration = {
-      iceServers: toRtcIceServers(session.iceServers),
+      iceServers: toRtcIceServers(iceServers),
+      iceTransportPolicy,
       bundlePolicy: "max-bundle",
       rtcpMuxPolicy: "require",
     };
 
-    const pc = new RTCPeerConnection(rtcConfig);
+    // Chromium only sets DSCP on its WebRTC sockets when the legacy googDscp
+    // constraint is present; builds that no longer read it ignore the argument.
+    const pc = settings.qosMarking
+      ? new (RTCPeerConnection as unknown as new (
+        configuration: RTCConfiguration,
+        constraints: { optional: Array<Record<string, boolean>> },
+      ) => RTCPeerConnection)(rtcConfig, { optional: [{ googDscp: true }] })
+      : new RTCPeerConnection(rtcConfig);
+    if (settings.qosMarking) {
+      this.log("QoS marking requested: input channels at high priority, DSCP enabled");
+    }
+    if (this.lowLatency) {
+      this.log("Low-latency mode: zero video jitter target, stats polled every 2s");
+    }
     this.pc = pc;
     this.diagnostics.connectionState = pc.connectionState;
     this.diagnostics.serverRegion = this.serverRegion;
@@ -4190,9 +4588,11 @@ export class GfnWebRtcClient {
 
     this.resetInputState();
     this.resetDiagnostics();
-    this.createDataChannels(pc);
+    this.createDataChannels(pc, settings.qosMarking === true);
     this.installInputCapture(this.options.videoElement);
-    this.setupStatsPolling();
+    this.setupStatsPolling(
+      this.lowLatency ? GfnWebRtcClient.LOW_LATENCY_STATS_POLL_INTERVAL_MS : GfnWebRtcClient.STATS_POLL_INTERVAL_MS,
+    );
 
     let answerSent = false;
     const queuedLocalIce: IceCandidatePayload[] = [];
@@ -4279,6 +4679,10 @@ export class GfnWebRtcClient {
 
     pc.ontrack = (event) => {
       this.log(`Track received: kind=${event.track.kind}, id=${event.track.id}, readyState=${event.track.readyState}`);
+      if (event.track.kind === "video" && event.transceiver !== this.primaryVideoTransceiver(pc)) {
+        this.log(`Ignoring extra video layer mid=${event.transceiver.mid ?? "?"}; it is declined in the answer`);
+        return;
+      }
       this.attachTrack(event.track);
 
       // Configure low-latency jitter buffer for video and audio receivers
@@ -4380,6 +4784,7 @@ export class GfnWebRtcClient {
     //     Must be called after setRemoteDescription (which creates the transceiver)
     //     but before createAnswer (which generates the answer SDP).
     this.applyCodecPreferences(pc, effectiveCodec, preferredHevcProfileId);
+    this.declineExtraVideoLayers(pc);
 
     // 4. Create answer, munge SDP, and set local description
     this.log("Creating answer...");
@@ -4438,6 +4843,12 @@ export class GfnWebRtcClient {
     const credentials = extractIceCredentials(finalSdp);
     this.log(`Extracted ICE credentials: ufrag=${credentials.ufrag}, pwd=${credentials.pwd.slice(0, 8)}...`);
     const { width, height } = parseResolution(settings.resolution);
+    const videoPacketSize = videoPacketSizeForPathMtu(settings.pathMtu);
+    this.diagnostics.pathMtu = settings.pathMtu ?? undefined;
+    this.diagnostics.videoPacketSize = videoPacketSize;
+    if (typeof settings.pathMtu === "number") {
+      this.log(`Path MTU ${settings.pathMtu}; requesting ${videoPacketSize}-byte video packets`);
+    }
 
     const nvstSdp = buildNvstSdp({
       width,
@@ -4453,6 +4864,7 @@ export class GfnWebRtcClient {
       credentials,
       dynamicSplitEncodeUpdatesEnabled:
         settings.nativeTransitionDiagnostics?.disableDynamicSplitEncodeUpdates !== true,
+      videoPacketSize,
     });
 
     await window.openNow.sendAnswer({
@@ -4498,6 +4910,7 @@ export class GfnWebRtcClient {
 
   dispose(): void {
     this.cleanupPeerConnection();
+    this.options.videoElement.onresize = null;
 
     // Cleanup microphone
     if (this.micManager) {
@@ -0,0 +1,26 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { normalizeWhipEndpoint, resolveWhipResourceUrl, restreamScale } from "./whipPublisher";
+
+test("spectator video is only ever scaled down to the cap", () => {
+  assert.equal(restreamScale(2160, 720), 3);
+  assert.equal(restreamScale(1080, 720), 1.5);
+  assert.equal(restreamScale(720, 1080), 1);
+  assert.equal(restreamScale(1440, 0), 1);
+  assert.equal(restreamScale(Number.NaN, 720), 1);
+});
+
+test("WHIP endpoints must be http(s) and resources resolve against them", () => {
+  assert.equal(normalizeWhipEndpoint("  http://192.168.1.10:8889/live/whip "), "http://192.168.1.10:8889/live/whip");
+  assert.equal(normalizeWhipEndpoint(""), null);
+  assert.equal(normalizeWhipEndpoint("rtsp://192.168.1.10/live"), null);
+  assert.equal(normalizeWhipEndpoint("not a url"), null);
+
+  const endpoint = "http://192.168.1.10:8889/live/whip";
+  assert.equal(resolveWhipResourceUrl(endpoint, "/live/whip/abc"), "http://192.168.1.10:8889/live/whip/abc");
+  assert.equal(resolveWhipResourceUrl(endpoint, "http://other:8889/r/1"), "http://other:8889/r/1");
+  assert.equal(resolveWhipResourceUrl(endpoint, null), null);
+});
@@ -0,0 +1,150 @@
+export const RESTREAM_MAX_HEIGHT_OPTIONS = [0, 480, 720, 1080] as const;
+
+const ICE_GATHERING_TIMEOUT_MS = 2000;
+const SPECTATOR_MAX_BITRATE_BPS = 8_000_000;
+
+/** Downscale factor that keeps the re-published video at or under `maxHeight` (0 = no cap). */
+export function restreamScale(sourceHeight: number, maxHeight: number): number {
+  if (maxHeight <= 0 || !Number.isFinite(sourceHeight) || sourceHeight <= maxHeight) {
+    return 1;
+  }
+  return sourceHeight / maxHeight;
+}
+
+/** Only plain http(s) ingest URLs are accepted; anything else disables re-streaming. */
+export function normalizeWhipEndpoint(raw: string): string | null {
+  const trimmed = raw.trim();
+  if (!trimmed) {
+    return null;
+  }
+  try {
+    const url = new URL(trimmed);
+    return url.protocol === "http:" || url.protocol === "https:" ? url.toString() : null;
+  } catch {
+    return null;
+  }
+}
+
+/** WHIP servers answer with a relative Location for the session resource that is later DELETEd. */
+export function resolveWhipResourceUrl(endpoint: string, location: string | null): string | null {
+  if (!location) {
+    return null;
+  }
+  try {
+    return new URL(location, endpoint).toString();
+  } catch {
+    return null;
+  }
+}
+
+export interface WhipPublisherOptions {
+  endpoint: string;
+  maxHeight: number;
+  fetchImpl?: typeof fetch;
+}
+
+/**
+ * Re-publishes the tracks received from the rig to a WHIP ingest (RFC 9725) so
+ * a second device on the LAN can watch. The browser re-encodes the decoded
+ * frames, which is what lets the spectator copy be capped below the source.
+ */
+export class WhipPublisher {
+  private pc: RTCPeerConnection | null = null;
+  private resourceUrl: string | null = null;
+  private maxHeight: number;
+  private readonly fetchImpl: typeof fetch;
+
+  constructor(private readonly options: WhipPublisherOptions) {
+    this.maxHeight = options.maxHeight;
+    this.fetchImpl = options.fetchImpl ?? fetch.bind(globalThis);
+  }
+
+  async start(tracks: MediaStreamTrack[]): Promise<void> {
+    if (this.pc) {
+      throw new Error("WHIP publisher already started");
+    }
+    const pc = new RTCPeerConnection();
+    this.pc = pc;
+    for (const track of tracks) {
+      if (track.kind === "video") {
+        pc.addTransceiver(track, {
+          direction: "sendonly",
+          sendEncodings: [{
+            scaleResolutionDownBy: restreamScale(track.getSettings().height ?? 0, this.maxHeight),
+            maxBitrate: SPECTATOR_MAX_BITRATE_BPS,
+          }],
+        });
+      } else {
+        pc.addTransceiver(track, { direction: "sendonly" });
+      }
+    }
+
+    await pc.setLocalDescription(await pc.createOffer());
+    // No trickle ICE: on a LAN host candidates arrive almost immediately.
+    await waitForIceGathering(pc, ICE_GATHERING_TIMEOUT_MS);
+    if (this.pc !== pc) {
+      return;
+    }
+
+    const response = await this.fetchImpl(this.options.endpoint, {
+      method: "POST",
+      headers: { "Content-Type": "application/sdp" },
+      body: pc.localDescription?.sdp ?? "",
+    });
+    if (!response.ok) {
+      throw new Error(`WHIP endpoint answered HTTP ${response.status}`);
+    }
+    this.resourceUrl = resolveWhipResourceUrl(this.options.endpoint, response.headers.get("Location"));
+    const answer = await response.text();
+    if (this.pc !== pc) {
+      return;
+    }
+    await pc.setRemoteDescription({ type: "answer", sdp: answer });
+  }
+
+  async setMaxHeight(maxHeight: number): Promise<void> {
+    this.maxHeight = maxHeight;
+    const sender = this.pc?.getSenders().find((candidate) => candidate.track?.kind === "video");
+    if (!sender?.track) {
+      return;
+    }
+    const parameters = sender.getParameters();
+    if (parameters.encodings.length === 0) {
+      return;
+    }
+    parameters.encodings[0].scaleResolutionDownBy = restreamScale(sender.track.getSettings().height ?? 0, maxHeight);
+    await sender.setParameters(parameters);
+  }
+
+  async stop(): Promise<void> {
+    const pc = this.pc;
+    const resourceUrl = this.resourceUrl;
+    this.pc = null;
+    this.resourceUrl = null;
+    // The tracks belong to the game stream, so only the peer connection is closed here.
+    pc?.close();
+    if (resourceUrl) {
+      await this.fetchImpl(resourceUrl, { method: "DELETE" }).catch(() => undefined);
+    }
+  }
+}
+
+function waitForIceGathering(pc: RTCPeerConnection, timeoutMs: number): Promise<void> {
+  if (pc.iceGatheringState === "complete") {
+    return Promise.resolve();
+  }
+  return new Promise((resolve) => {
+    const timer = window.setTimeout(done, timeoutMs);
+    function done(): void {
+      window.clearTimeout(timer);
+      pc.removeEventListener("icegatheringstatechange", onChange);
+      resolve();
+    }
+    function onChange(): void {
+      if (pc.iceGatheringState === "complete") {
+        done();
+      }
+    }
+    pc.addEventListener("icegatheringstatechange", onChange);
+  });
+}
@@ -0,0 +1,59 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import type { LogTailLine } from "@shared/gfn";
+import { getLogCapture, tailLogs } from "@shared/logger";
+
+const POLL_INTERVAL_MS = 1000;
+/** Rows kept on screen; the full ring buffers stay available through log export. */
+const MAX_ROWS = 1000;
+
+export interface LogTailRow extends LogTailLine {
+  source: "main" | "renderer";
+}
+
+/**
+ * Follows the main and renderer log ring buffers while `active`, merging new
+ * lines from both by time. Each poll only transfers lines past the last seen
+ * sequence number; the cursors outlive a pause so reopening never repeats rows.
+ */
+export function useLogTail(active: boolean): { rows: LogTailRow[]; clear: () => void } {
+  const [rows, setRows] = useState<LogTailRow[]>([]);
+  const mainCursorRef = useRef(0);
+  const rendererCursorRef = useRef(0);
+
+  useEffect(() => {
+    if (!active) {
+      return;
+    }
+    let cancelled = false;
+    let timer: number | null = null;
+    const poll = async (): Promise<void> => {
+      try {
+        const main = await window.openNow.getLogTail(mainCursorRef.current);
+        if (cancelled) {
+          return;
+        }
+        const renderer = tailLogs(getLogCapture(), rendererCursorRef.current);
+        mainCursorRef.current = main.cursor;
+        rendererCursorRef.current = renderer.cursor;
+        const fresh = [
+          ...main.lines.map((line): LogTailRow => ({ ...line, source: "main" })),
+          ...renderer.lines.map((line): LogTailRow => ({ ...line, source: "renderer" })),
+        ].sort((a, b) => a.timestamp - b.timestamp);
+        if (fresh.length > 0) {
+          setRows((previous) => [...previous, ...fresh].slice(-MAX_ROWS));
+        }
+      } catch (error) {
+        console.warn("[LogTail] Poll failed:", error);
+      }
+      if (!cancelled) timer = window.setTimeout(() => void poll(), POLL_INTERVAL_MS);
+    };
+    void poll();
+    return () => {
+      cancelled = true;
+      if (timer !== null) window.clearTimeout(timer);
+    };
+  }, [active]);
+
+  const clear = useCallback(() => setRows([]), []);
+  return { rows, clear };
+}
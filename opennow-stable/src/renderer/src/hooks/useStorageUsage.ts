@@ -0,0 +1,41 @@
+import { useCallback, useEffect, useState } from "react";
+import type { StorageCategory, StorageCategoryUsage } from "@shared/gfn";
+
+/**
+ * Disk usage per storage category, measured each time `active` turns on
+ * since recordings and the HTTP cache grow while the page is closed.
+ */
+export function useStorageUsage(active: boolean): {
+  usage: StorageCategoryUsage[] | null;
+  clearing: StorageCategory | null;
+  clear: (category: StorageCategory) => Promise<void>;
+} {
+  const [usage, setUsage] = useState<StorageCategoryUsage[] | null>(null);
+  const [clearing, setClearing] = useState<StorageCategory | null>(null);
+
+  useEffect(() => {
+    if (!active) {
+      return;
+    }
+    let cancelled = false;
+    window.openNow.getStorageUsage()
+      .then((next) => {
+        if (!cancelled) setUsage(next);
+      })
+      .catch((error) => console.warn("[Storage] Failed to measure usage:", error));
+    return () => {
+      cancelled = true;
+    };
+  }, [active]);
+
+  const clear = useCallback(async (category: StorageCategory): Promise<void> => {
+    setClearing(category);
+    try {
+      setUsage(await window.openNow.clearStorage(category));
+    } finally {
+      setClearing(null);
+    }
+  }, []);
+
+  return { usage, clearing, clear };
+}
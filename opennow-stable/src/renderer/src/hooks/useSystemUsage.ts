@@ -0,0 +1,37 @@
+import { useEffect, useState } from "react";
+import type { SystemUsageSample } from "@shared/gfn";
+
+const POLL_INTERVAL_MS = 3000;
+
+/**
+ * Polls local CPU/GPU load and temperatures while `active`, so the main
+ * process only samples (and spawns nvidia-smi) while someone is looking.
+ */
+export function useSystemUsage(active: boolean): SystemUsageSample | null {
+  const [usage, setUsage] = useState<SystemUsageSample | null>(null);
+
+  useEffect(() => {
+    if (!active) {
+      setUsage(null);
+      return;
+    }
+    let cancelled = false;
+    let timer: number | null = null;
+    const poll = async (): Promise<void> => {
+      try {
+        const next = await window.openNow.getSystemUsage();
+        if (!cancelled) setUsage(next);
+      } catch (error) {
+        console.warn("[SystemUsage] Sample failed:", error);
+      }
+      if (!cancelled) timer = window.setTimeout(() => void poll(), POLL_INTERVAL_MS);
+    };
+    void poll();
+    return () => {
+      cancelled = true;
+      if (timer !== null) window.clearTimeout(timer);
+    };
+  }, [active]);
+
+  return usage;
+}
@@ -0,0 +1,47 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { AppEventBus, decodeStateChanged, decodeStateFromStats } from "./appEvents";
+
+test("listeners receive their event type until they unsubscribe", () => {
+  const bus = new AppEventBus();
+  const received: string[] = [];
+  const unsubscribe = bus.on("stream:shortcut", (action) => received.push(action));
+  bus.on("input:device", () => received.push("device"));
+
+  bus.emit("stream:shortcut", "screenshot");
+  unsubscribe();
+  bus.emit("stream:shortcut", "toggleRecording");
+
+  assert.deepEqual(received, ["screenshot"]);
+});
+
+test("a throwing listener does not keep the event from the others", (t) => {
+  t.mock.method(console, "error", () => {});
+  const bus = new AppEventBus();
+  let delivered = false;
+  bus.on("input:device", () => {
+    throw new Error("broken page");
+  });
+  bus.on("input:device", () => {
+    delivered = true;
+  });
+
+  bus.emit("input:device", { kind: "gamepad", change: "connected", index: 0, id: "pad" });
+  assert.equal(delivered, true);
+});
+
+test("decode state only counts as changed when the decoder or its health moves", () => {
+  const stats = {
+    codec: "H265",
+    hardwareAcceleration: "D3D11",
+    decoderPressureActive: false,
+    decoderRecoveryAction: "none",
+  };
+  const current = decodeStateFromStats(stats);
+  assert.equal(decodeStateChanged(null, current), true);
+  assert.equal(decodeStateChanged(current, decodeStateFromStats({ ...stats })), false);
+  assert.equal(decodeStateChanged(current, decodeStateFromStats({ ...stats, decoderPressureActive: true })), true);
+});
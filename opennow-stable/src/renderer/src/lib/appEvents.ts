@@ -0,0 +1,100 @@
+import type { NativeStreamerShortcutAction } from "@shared/gfn";
+import type { StreamDiagnostics } from "../gfn/webrtcClient";
+import type { LaunchErrorState, StreamStatus } from "./appTypes";
+
+/** A launch or resume failure, as shown on the loading screen. */
+export type AppErrorEvent = Pick<LaunchErrorState, "stage" | "title" | "description" | "codeLabel">;
+
+/** Session lifecycle: emitted whenever the stream status, queue position or launch error changes. */
+export interface SessionUpdateEvent {
+  status: StreamStatus;
+  queuePosition: number | undefined;
+  launchError: AppErrorEvent | null;
+  gameTitle: string | null;
+  gameCover: string | null;
+  platformStore: string | null;
+}
+
+/** The decoder the stream ended up on and whether it is struggling; emitted only on change. */
+export interface DecodeStateEvent {
+  codec: string;
+  hardwareAcceleration: string;
+  pressureActive: boolean;
+  recoveryAction: string;
+}
+
+export interface InputDeviceEvent {
+  kind: "gamepad";
+  change: "connected" | "disconnected";
+  index: number;
+  id: string;
+}
+
+export interface AppEventMap {
+  "session:update": SessionUpdateEvent;
+  "decode:state": DecodeStateEvent;
+  "input:device": InputDeviceEvent;
+  "app:error": AppErrorEvent;
+  /** Stream hotkeys that StreamView carries out (screenshot, recording). */
+  "stream:shortcut": NativeStreamerShortcutAction;
+}
+
+export type AppEventType = keyof AppEventMap;
+export type AppEventListener<K extends AppEventType> = (event: AppEventMap[K]) => void;
+
+/**
+ * Renderer-wide publish/subscribe for events that more than one module cares
+ * about, so producers need not know who is listening. Listeners run
+ * synchronously in subscription order; one that throws is logged and does not
+ * stop the rest.
+ */
+export class AppEventBus {
+  private readonly listeners = new Map<AppEventType, Set<AppEventListener<never>>>();
+
+  /** Subscribes `listener` and returns the matching unsubscribe, ready to return from an effect. */
+  on<K extends AppEventType>(type: K, listener: AppEventListener<K>): () => void {
+    let listeners = this.listeners.get(type);
+    if (!listeners) {
+      listeners = new Set();
+      this.listeners.set(type, listeners);
+    }
+    const entry = listener as AppEventListener<never>;
+    listeners.add(entry);
+    return () => {
+      listeners.delete(entry);
+    };
+  }
+
+  emit<K extends AppEventType>(type: K, event: AppEventMap[K]): void {
+    for (const listener of [...(this.listeners.get(type) ?? [])]) {
+      try {
+        (listener as AppEventListener<K>)(event);
+      } catch (error) {
+        console.error(`[AppEvents] ${type} listener failed:`, error);
+      }
+    }
+  }
+}
+
+export const appEvents = new AppEventBus();
+
+export function decodeStateFromStats(
+  stats: Pick<StreamDiagnostics, "codec" | "hardwareAcceleration" | "decoderPressureActive" | "decoderRecoveryAction">,
+): DecodeStateEvent {
+  return {
+    codec: stats.codec,
+    hardwareAcceleration: stats.hardwareAcceleration,
+    pressureActive: stats.decoderPressureActive,
+    recoveryAction: stats.decoderRecoveryAction,
+  };
+}
+
+export function decodeStateChanged(previous: DecodeStateEvent | null, next: DecodeStateEvent): boolean {
+  return (
+    previous === null ||
+    previous.codec !== next.codec ||
+    previous.hardwareAcceleration !== next.hardwareAcceleration ||
+    previous.pressureActive !== next.pressureActive ||
+    previous.recoveryAction !== next.recoveryAction
+  );
+}
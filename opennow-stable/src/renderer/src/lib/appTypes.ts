@@ -1,4 +1,8 @@
+import type { GfnErrorCategory } from "@shared/errorTaxonomy";
+
 import type { StreamTimeWarning } from "../gfn/webrtcClient";
+import type { AccountLinkRequirement } from "./launchOwnership";
+import type { PlayTimeLimitReason, PlayTimeReminderStage } from "./playTimeLimits";
 
 export type StreamStatus = "idle" | "queue" | "setup" | "starting" | "connecting" | "streaming";
 export type StreamLoadingStatus = "queue" | "setup" | "starting" | "connecting";
@@ -11,13 +15,27 @@ export type StreamWarningState = {
 };
 
 export type LocalSessionTimerWarningState = {
-  stage: "free-tier-30m" | "free-tier-15m" | "free-tier-final-minute";
+  stage: "free-tier-30m" | "free-tier-15m" | "free-tier-5m" | "free-tier-final-minute";
   shownAtMs: number;
 };
 
+export type PlayTimeReminderState = {
+  stage: PlayTimeReminderStage;
+  reason: PlayTimeLimitReason;
+  /** Only counted down in the final minute */
+  secondsLeft?: number;
+};
+
 export type LaunchErrorState = {
   stage: StreamLoadingStatus;
   title: string;
   description: string;
   codeLabel?: string;
+  category?: GfnErrorCategory;
+  /** Localized remediation steps, shown as a numbered list */
+  steps?: string[];
+  /** External page that helps resolve the error (service status, memberships, ...) */
+  helpLink?: { label: string; url: string };
+  /** Offered as a "link account" action instead of a dead-end error */
+  accountLink?: AccountLinkRequirement;
 };
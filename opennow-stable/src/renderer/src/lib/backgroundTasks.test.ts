@@ -0,0 +1,85 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import type { GameInfo } from "@shared/gfn";
+import {
+  BackgroundTaskScheduler,
+  artworkPrefetchUrls,
+  estimateScreenfulGameCount,
+  prefetchArtwork,
+} from "./backgroundTasks";
+
+const tick = (): Promise<void> => new Promise((resolve) => setTimeout(resolve, 0));
+
+test("a screenful grows with the window and shrinks with larger posters", () => {
+  const normal = estimateScreenfulGameCount(1280, 720, 1);
+  assert.ok(normal >= 12);
+  assert.ok(estimateScreenfulGameCount(1920, 1080, 1) > normal);
+  assert.ok(estimateScreenfulGameCount(1280, 720, 1.5) < normal);
+  assert.equal(estimateScreenfulGameCount(100, 100, 1), 1);
+});
+
+test("prefetch covers two screenfuls of posters without duplicates", () => {
+  const games = [
+    { imageUrl: "a" },
+    { imageUrl: "b" },
+    { imageUrl: undefined },
+    { imageUrl: "a" },
+    { imageUrl: "c" },
+  ] as GameInfo[];
+  assert.deepEqual(artworkPrefetchUrls(games, 2), ["a", "b"]);
+  assert.deepEqual(artworkPrefetchUrls(games, 3), ["a", "b", "c"]);
+});
+
+test("artwork prefetch stops starting requests once aborted", async () => {
+  const abort = new AbortController();
+  const loaded: string[] = [];
+  await prefetchArtwork(["1", "2", "3", "4", "5", "6"], abort.signal, async (url) => {
+    loaded.push(url);
+    if (url === "2") abort.abort();
+  });
+  assert.ok(loaded.length < 6);
+});
+
+test("work that comes due while paused runs once on resume", async () => {
+  const scheduler = new BackgroundTaskScheduler();
+  let runs = 0;
+  scheduler.register({ id: "sync", intervalMs: null, run: async () => { runs += 1; } });
+
+  scheduler.setPaused(true);
+  scheduler.trigger("sync");
+  scheduler.trigger("sync");
+  await tick();
+  assert.equal(runs, 0);
+
+  scheduler.setPaused(false);
+  await tick();
+  assert.equal(runs, 1);
+  scheduler.dispose();
+});
+
+test("pausing aborts a run in flight and retries it after resume", async () => {
+  const scheduler = new BackgroundTaskScheduler();
+  const signals: AbortSignal[] = [];
+  scheduler.register({
+    id: "artwork",
+    intervalMs: null,
+    run: (signal) => {
+      signals.push(signal);
+      return new Promise((resolve) => signal.addEventListener("abort", () => resolve()));
+    },
+  });
+
+  scheduler.trigger("artwork");
+  scheduler.setPaused(true);
+  assert.equal(signals[0]?.aborted, true);
+  await tick();
+  assert.equal(signals.length, 1);
+
+  scheduler.setPaused(false);
+  await tick();
+  assert.equal(signals.length, 2);
+  scheduler.dispose();
+});
@@ -0,0 +1,161 @@
+import type { GameInfo } from "@shared/gfn";
+
+// Mirrors `.game-grid` and `.game-card` in styles.css closely enough to size a screenful.
+const POSTER_MIN_WIDTH_PX = 200;
+const GRID_GAP_PX = 12;
+const POSTER_IMAGE_ASPECT = 0.5625;
+const CARD_INFO_HEIGHT_PX = 64;
+const ARTWORK_PREFETCH_CONCURRENCY = 4;
+
+/** How many game cards fit in the viewport at the current poster size. */
+export function estimateScreenfulGameCount(viewportWidth: number, viewportHeight: number, posterScale: number): number {
+  const minCardWidth = POSTER_MIN_WIDTH_PX * Math.max(0.1, posterScale);
+  const columns = Math.max(1, Math.floor((viewportWidth + GRID_GAP_PX) / (minCardWidth + GRID_GAP_PX)));
+  const cardWidth = (viewportWidth - GRID_GAP_PX * (columns - 1)) / columns;
+  const rowHeight = cardWidth * POSTER_IMAGE_ASPECT + CARD_INFO_HEIGHT_PX + GRID_GAP_PX;
+  const rows = Math.max(1, Math.ceil(viewportHeight / rowHeight));
+  return columns * rows;
+}
+
+/** Poster URLs for the visible screenful plus the next one, in display order. */
+export function artworkPrefetchUrls(games: readonly GameInfo[], screenful: number): string[] {
+  const urls = new Set<string>();
+  for (const game of games.slice(0, Math.max(0, screenful) * 2)) {
+    if (game.imageUrl) {
+      urls.add(game.imageUrl);
+    }
+  }
+  return [...urls];
+}
+
+function loadImage(url: string): Promise<void> {
+  return new Promise((resolve) => {
+    const image = new Image();
+    image.decoding = "async";
+    image.onload = () => resolve();
+    image.onerror = () => resolve();
+    image.src = url;
+  });
+}
+
+/**
+ * Warms the HTTP cache so lazy-loaded posters appear instantly on scroll.
+ * Stops starting new requests once `signal` aborts.
+ */
+export async function prefetchArtwork(
+  urls: readonly string[],
+  signal: AbortSignal,
+  load: (url: string) => Promise<void> = loadImage,
+): Promise<void> {
+  let next = 0;
+  const worker = async (): Promise<void> => {
+    while (!signal.aborted && next < urls.length) {
+      const url = urls[next]!;
+      next += 1;
+      await load(url);
+    }
+  };
+  await Promise.all(Array.from({ length: Math.min(ARTWORK_PREFETCH_CONCURRENCY, urls.length) }, worker));
+}
+
+export interface BackgroundTask {
+  id: string;
+  /** Repeat every this many milliseconds; null runs only when triggered. */
+  intervalMs: number | null;
+  run: (signal: AbortSignal) => Promise<void>;
+}
+
+interface ScheduledTask {
+  task: BackgroundTask;
+  timer: ReturnType<typeof setInterval> | null;
+  running: AbortController | null;
+  due: boolean;
+}
+
+/**
+ * Runs the app's background work (artwork prefetch, library sync). While
+ * paused, runs in flight are aborted and anything that comes due waits for
+ * resume, so a stream never shares bandwidth with housekeeping.
+ */
+export class BackgroundTaskScheduler {
+  private readonly tasks = new Map<string, ScheduledTask>();
+  private paused = false;
+
+  register(task: BackgroundTask): void {
+    this.unregister(task.id);
+    const scheduled: ScheduledTask = { task, timer: null, running: null, due: false };
+    if (task.intervalMs !== null && task.intervalMs > 0) {
+      scheduled.timer = setInterval(() => this.trigger(task.id), task.intervalMs);
+    }
+    this.tasks.set(task.id, scheduled);
+  }
+
+  unregister(id: string): void {
+    const scheduled = this.tasks.get(id);
+    if (!scheduled) {
+      return;
+    }
+    if (scheduled.timer !== null) {
+      clearInterval(scheduled.timer);
+    }
+    scheduled.running?.abort();
+    this.tasks.delete(id);
+  }
+
+  trigger(id: string): void {
+    const scheduled = this.tasks.get(id);
+    if (!scheduled) {
+      return;
+    }
+    if (this.paused || scheduled.running) {
+      scheduled.due = true;
+      return;
+    }
+    this.start(scheduled);
+  }
+
+  setPaused(paused: boolean): void {
+    if (this.paused === paused) {
+      return;
+    }
+    this.paused = paused;
+    for (const scheduled of this.tasks.values()) {
+      if (paused && scheduled.running) {
+        scheduled.running.abort();
+        scheduled.due = true;
+      } else if (!paused && scheduled.due && !scheduled.running) {
+        this.start(scheduled);
+      }
+    }
+  }
+
+  isPaused(): boolean {
+    return this.paused;
+  }
+
+  dispose(): void {
+    for (const id of [...this.tasks.keys()]) {
+      this.unregister(id);
+    }
+  }
+
+  private start(scheduled: ScheduledTask): void {
+    const controller = new AbortController();
+    scheduled.running = controller;
+    scheduled.due = false;
+    void scheduled.task
+      .run(controller.signal)
+      .catch((error: unknown) => {
+        console.warn(`[Background] Task ${scheduled.task.id} failed:`, error);
+      })
+      .finally(() => {
+        if (scheduled.running !== controller) {
+          return;
+        }
+        scheduled.running = null;
+        if (scheduled.due && !this.paused && this.tasks.get(scheduled.task.id) === scheduled) {
+          this.start(scheduled);
+        }
+      });
+  }
+}
@@ -0,0 +1,21 @@
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import type { GameInfo } from "@shared/gfn";
+
+import { collectGameGenres, matchesGameGenre } from "./gameCatalog";
+
+function game(id: string, genres?: string[]): GameInfo {
+  return { id, title: id, genres, selectedVariantIndex: 0, variants: [] };
+}
+
+test("collects distinct genres alphabetically", () => {
+  const games = [game("a", ["Shooter", "Action"]), game("b", ["Action ", ""]), game("c")];
+  assert.deepEqual(collectGameGenres(games), ["Action", "Shooter"]);
+});
+
+test("filters by exact genre and passes everything when no genre is chosen", () => {
+  assert.equal(matchesGameGenre(game("a", ["Action"]), "Action"), true);
+  assert.equal(matchesGameGenre(game("a", ["Action RPG"]), "Action"), false);
+  assert.equal(matchesGameGenre(game("b"), ""), true);
+});
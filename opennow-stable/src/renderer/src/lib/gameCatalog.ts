@@ -60,6 +60,22 @@ export function matchesGameSearch(game: GameInfo, query: string): boolean {
     .some((value) => value.toLowerCase().includes(normalizedQuery));
 }
 
+/** Distinct genres across `games`, alphabetical, for the library genre filter. */
+export function collectGameGenres(games: GameInfo[]): string[] {
+  const genres = new Set<string>();
+  for (const game of games) {
+    for (const genre of game.genres ?? []) {
+      if (genre.trim()) genres.add(genre.trim());
+    }
+  }
+  return [...genres].sort((left, right) => left.localeCompare(right));
+}
+
+export function matchesGameGenre(game: GameInfo, genre: string): boolean {
+  if (!genre) return true;
+  return (game.genres ?? []).some((value) => value.trim() === genre);
+}
+
 export function areStringArraysEqual(left: string[], right: string[]): boolean {
   if (left.length != right.length) {
     return false;
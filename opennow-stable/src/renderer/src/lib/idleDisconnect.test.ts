@@ -0,0 +1,27 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { getIdleDisconnectState, latestGamepadTimestamp } from "./idleDisconnect";
+
+test("idle disconnect warns during the last minute and then expires", () => {
+  assert.deepEqual(getIdleDisconnectState(0, 0), { phase: "active" });
+  assert.deepEqual(getIdleDisconnectState(60 * 60_000, 0), { phase: "active" });
+  assert.deepEqual(getIdleDisconnectState(14 * 60_000 - 1, 15), { phase: "active" });
+  assert.deepEqual(getIdleDisconnectState(14 * 60_000 + 500, 15), { phase: "warning", secondsLeft: 60 });
+  assert.deepEqual(getIdleDisconnectState(15 * 60_000 - 1, 15), { phase: "warning", secondsLeft: 1 });
+  assert.deepEqual(getIdleDisconnectState(15 * 60_000, 15), { phase: "expired" });
+});
+
+test("only connected pads contribute to the gamepad activity timestamp", () => {
+  const pads = [
+    null,
+    { connected: true, timestamp: 120 },
+    { connected: false, timestamp: 900 },
+    { connected: true, timestamp: 340 },
+  ] as unknown as Array<Gamepad | null>;
+
+  assert.equal(latestGamepadTimestamp(pads), 340);
+  assert.equal(latestGamepadTimestamp([]), 0);
+});
@@ -0,0 +1,44 @@
+export const IDLE_DISCONNECT_WARNING_SECONDS = 60;
+
+export type IdleDisconnectState =
+  | { phase: "active" }
+  | { phase: "warning"; secondsLeft: number }
+  | { phase: "expired" };
+
+/**
+ * Where an idle session stands after `idleMs` without input. The warning
+ * window is clamped to the timeout so very short timeouts still warn.
+ */
+export function getIdleDisconnectState(
+  idleMs: number,
+  timeoutMinutes: number,
+  warningSeconds: number = IDLE_DISCONNECT_WARNING_SECONDS,
+): IdleDisconnectState {
+  if (timeoutMinutes <= 0) {
+    return { phase: "active" };
+  }
+  const timeoutMs = timeoutMinutes * 60_000;
+  const remainingMs = timeoutMs - idleMs;
+  if (remainingMs <= 0) {
+    return { phase: "expired" };
+  }
+  if (remainingMs <= Math.min(warningSeconds * 1000, timeoutMs)) {
+    return { phase: "warning", secondsLeft: Math.ceil(remainingMs / 1000) };
+  }
+  return { phase: "active" };
+}
+
+/**
+ * Newest state-change timestamp across connected pads. Chromium only bumps
+ * `Gamepad.timestamp` when buttons or axes change, so a larger value means
+ * the player touched a controller since the last poll.
+ */
+export function latestGamepadTimestamp(gamepads: ReadonlyArray<Gamepad | null>): number {
+  let latest = 0;
+  for (const pad of gamepads) {
+    if (pad?.connected && pad.timestamp > latest) {
+      latest = pad.timestamp;
+    }
+  }
+  return latest;
+}
@@ -3,8 +3,8 @@
 import test from "node:test";
 import assert from "node:assert/strict";
 
-import type { GameInfo, GameVariant } from "@shared/gfn";
-import { chooseAccountLinked, getEpicOwnershipLaunchError } from "./launchOwnership";
+import type { GameAccountConnection, GameInfo, GameVariant } from "@shared/gfn";
+import { chooseAccountLinked, getAccountLinkRequirement, getEpicOwnershipLaunchError } from "./launchOwnership";
 
 function makeVariant(overrides: Partial<GameVariant> = {}): GameVariant {
   return {
@@ -78,3 +78,48 @@ test("never marks install-to-play titles as account linked", () => {
     false,
   );
 });
+
+function makeAccount(overrides: Partial<GameAccountConnection> = {}): GameAccountConnection {
+  return {
+    provider: overrides.provider ?? "EPIC",
+    label: overrides.label ?? "Epic Games",
+    sortOrder: 0,
+    supportsLinking: overrides.supportsLinking ?? true,
+    supportsSync: true,
+    isRequired: overrides.isRequired ?? true,
+    isConnected: overrides.isConnected ?? false,
+    status: overrides.status ?? "not_connected",
+    syncedGames: 0,
+  };
+}
+
+test("asks to link a required store account before launching an unowned variant", () => {
+  const unowned = makeVariant({ store: "Epic", libraryStatus: "NOT_OWNED" });
+  assert.deepEqual(getAccountLinkRequirement(unowned, [makeAccount()]), { provider: "EPIC", label: "Epic Games" });
+  assert.deepEqual(
+    getAccountLinkRequirement(unowned, [makeAccount({ isConnected: true, status: "expired" })]),
+    { provider: "EPIC", label: "Epic Games" },
+  );
+});
+
+test("matches store aliases to their account provider codes", () => {
+  const ubisoft = makeAccount({ provider: "UPLAY", label: "Ubisoft" });
+  assert.deepEqual(
+    getAccountLinkRequirement(makeVariant({ store: "Epic Games Store" }), [makeAccount()]),
+    { provider: "EPIC", label: "Epic Games" },
+  );
+  assert.deepEqual(getAccountLinkRequirement(makeVariant({ store: "Ubisoft" }), [ubisoft]), { provider: "UPLAY", label: "Ubisoft" });
+});
+
+test("does not block owned variants, connected accounts, or stores without required linking", () => {
+  assert.equal(getAccountLinkRequirement(makeVariant({ store: "Epic", libraryStatus: "MANUAL" }), [makeAccount()]), null);
+  assert.equal(
+    getAccountLinkRequirement(makeVariant({ store: "Epic" }), [makeAccount({ isConnected: true, status: "connected" })]),
+    null,
+  );
+  assert.equal(
+    getAccountLinkRequirement(makeVariant({ store: "Steam" }), [makeAccount({ provider: "STEAM", supportsLinking: false, isRequired: false })]),
+    null,
+  );
+  assert.equal(getAccountLinkRequirement(makeVariant({ store: "Epic" }), []), null);
+});
@@ -1,11 +1,16 @@
-import type { GameInfo, GameVariant } from "@shared/gfn";
-import { isEpicStore, isGameInLibrary, isOwnedVariant } from "@shared/gfn";
+import type { GameAccountConnection, GameInfo, GameVariant } from "@shared/gfn";
+import { isEpicStore, isGameInLibrary, isOwnedVariant, normalizeProviderCode } from "@shared/gfn";
 
 export interface LaunchOwnershipError {
   title: string;
   description: string;
 }
 
+export interface AccountLinkRequirement {
+  provider: string;
+  label: string;
+}
+
 const EPIC_OWNERSHIP_ERROR: LaunchOwnershipError = {
   title: "Epic Games Library Required",
   description:
@@ -43,3 +48,25 @@ export function getEpicOwnershipLaunchError(selectedVariant?: GameVariant): Laun
 
   return EPIC_OWNERSHIP_ERROR;
 }
+
+/**
+ * An unowned variant on a store that requires a linked account (Epic,
+ * Ubisoft, Xbox, ...) which is not connected. GFN rejects these launches on
+ * the rig, so offer the link flow up front instead.
+ */
+export function getAccountLinkRequirement(
+  selectedVariant: GameVariant | undefined,
+  accounts: GameAccountConnection[],
+): AccountLinkRequirement | null {
+  if (!selectedVariant || isOwnedVariant(selectedVariant)) {
+    return null;
+  }
+
+  const provider = normalizeProviderCode(selectedVariant.store);
+  const account = accounts.find((candidate) => candidate.provider === provider);
+  if (!account?.supportsLinking || !account.isRequired || (account.isConnected && account.status === "connected")) {
+    return null;
+  }
+
+  return { provider: account.provider, label: account.label };
+}
@@ -0,0 +1,43 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { getPlayTimeDeadline, hasPlayTimeLimit, nextPlayTimeReminder } from "./playTimeLimits";
+
+const at = (hours: number, minutes = 0, dayOffset = 0): number =>
+  new Date(2026, 4, 10 + dayOffset, hours, minutes).getTime();
+
+test("an empty bedtime disables the limit", () => {
+  assert.equal(hasPlayTimeLimit({ reminderMinutes: 0, curfew: "" }), false);
+  assert.equal(hasPlayTimeLimit({ reminderMinutes: 0, curfew: "21:30" }), true);
+});
+
+test("the earliest limit wins and a passed bedtime holds through the night", () => {
+  const started = at(20);
+  assert.deepEqual(getPlayTimeDeadline(started, at(20, 10), { reminderMinutes: 120, curfew: "" }), {
+    atMs: at(22),
+    reason: "duration",
+  });
+  assert.deepEqual(getPlayTimeDeadline(started, at(20, 10), { reminderMinutes: 240, curfew: "23:00" }), {
+    atMs: at(23),
+    reason: "curfew",
+  });
+  assert.equal(getPlayTimeDeadline(at(0, 30, 1), at(1, 0, 1), { reminderMinutes: 0, curfew: "23:00" })?.atMs, at(23));
+  assert.equal(getPlayTimeDeadline(at(9), at(9), { reminderMinutes: 0, curfew: "23:00" })?.atMs, at(23));
+  assert.equal(getPlayTimeDeadline(at(22), at(23), { reminderMinutes: 0, curfew: "00:30" })?.atMs, at(0, 30, 1));
+  assert.equal(getPlayTimeDeadline(started, at(21), { reminderMinutes: 0, curfew: "" }), null);
+});
+
+test("reminders escalate once per stage and repeat while playing overtime", () => {
+  assert.equal(nextPlayTimeReminder(null, 3600), null);
+  assert.equal(nextPlayTimeReminder(901, 900), "15m");
+  assert.equal(nextPlayTimeReminder(900, 899), null);
+  assert.equal(nextPlayTimeReminder(null, 600), "15m");
+  assert.equal(nextPlayTimeReminder(301, 300), "5m");
+  assert.equal(nextPlayTimeReminder(61, 60), "final-minute");
+  assert.equal(nextPlayTimeReminder(1, 0), "over");
+  assert.equal(nextPlayTimeReminder(0, -1), null);
+  assert.equal(nextPlayTimeReminder(-899, -900), "over");
+  assert.equal(nextPlayTimeReminder(null, -30), "over");
+});
@@ -0,0 +1,98 @@
+import { parseCurfew } from "@shared/playCurfew";
+
+/** A bedtime keeps applying this long after it passes, so late-night sessions stay over the limit. */
+export const PLAY_CURFEW_HOLD_HOURS = 6;
+/** Once over the limit without auto-disconnect, the reminder comes back this often. */
+export const PLAY_OVERTIME_REMINDER_SECONDS = 15 * 60;
+
+export interface PlayTimeLimits {
+  /** Minutes of play per session before the limit (0 = no duration limit) */
+  reminderMinutes: number;
+  /** Local "HH:MM" bedtime (empty = none) */
+  curfew: string;
+}
+
+export type PlayTimeLimitReason = "duration" | "curfew";
+
+export interface PlayTimeDeadline {
+  atMs: number;
+  reason: PlayTimeLimitReason;
+}
+
+export type PlayTimeReminderStage = "15m" | "5m" | "final-minute" | "over";
+
+export function hasPlayTimeLimit(limits: PlayTimeLimits): boolean {
+  return limits.reminderMinutes > 0 || parseCurfew(limits.curfew) !== null;
+}
+
+/**
+ * The bedtime that applies at `nowMs`: the one that passed within the hold
+ * window, otherwise the next one. Local time, so DST moves it with the clock.
+ */
+function curfewDeadlineMs(curfewMinutes: number, nowMs: number): number {
+  const today = new Date(nowMs);
+  today.setHours(Math.floor(curfewMinutes / 60), curfewMinutes % 60, 0, 0);
+  let atMs = today.getTime();
+  if (nowMs - atMs >= PLAY_CURFEW_HOLD_HOURS * 3_600_000) {
+    today.setDate(today.getDate() + 1);
+    atMs = today.getTime();
+  } else if (atMs - nowMs > (24 - PLAY_CURFEW_HOLD_HOURS) * 3_600_000) {
+    today.setDate(today.getDate() - 1);
+    atMs = today.getTime();
+  }
+  return atMs;
+}
+
+/** Whichever configured limit comes first for a session that started at `startedAtMs`. */
+export function getPlayTimeDeadline(
+  startedAtMs: number,
+  nowMs: number,
+  limits: PlayTimeLimits,
+): PlayTimeDeadline | null {
+  const candidates: PlayTimeDeadline[] = [];
+  if (limits.reminderMinutes > 0) {
+    candidates.push({ atMs: startedAtMs + limits.reminderMinutes * 60_000, reason: "duration" });
+  }
+  const curfew = parseCurfew(limits.curfew);
+  if (curfew !== null) {
+    candidates.push({ atMs: curfewDeadlineMs(curfew, nowMs), reason: "curfew" });
+  }
+  return candidates.reduce<PlayTimeDeadline | null>(
+    (earliest, candidate) => (earliest === null || candidate.atMs < earliest.atMs ? candidate : earliest),
+    null,
+  );
+}
+
+function reminderStage(secondsLeft: number): PlayTimeReminderStage | null {
+  if (secondsLeft <= 0) return "over";
+  if (secondsLeft <= 60) return "final-minute";
+  if (secondsLeft <= 5 * 60) return "5m";
+  if (secondsLeft <= 15 * 60) return "15m";
+  return null;
+}
+
+/**
+ * The reminder to show when the countdown moves from `previousSecondsLeft`
+ * (null on the first tick) to `secondsLeft`, or null when nothing new is due.
+ * Each stage fires once; overtime fires again every overtime interval.
+ */
+export function nextPlayTimeReminder(
+  previousSecondsLeft: number | null,
+  secondsLeft: number,
+): PlayTimeReminderStage | null {
+  const stage = reminderStage(secondsLeft);
+  if (stage === null) {
+    return null;
+  }
+  if (previousSecondsLeft === null || reminderStage(previousSecondsLeft) !== stage) {
+    return stage;
+  }
+  if (
+    stage === "over"
+    && Math.floor(-previousSecondsLeft / PLAY_OVERTIME_REMINDER_SECONDS)
+      !== Math.floor(-secondsLeft / PLAY_OVERTIME_REMINDER_SECONDS)
+  ) {
+    return stage;
+  }
+  return null;
+}
@@ -0,0 +1,28 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { selectPowerProfile } from "./powerProfile";
+
+test("the battery profile applies only while unplugged at or below the threshold", () => {
+  const settings = { batteryProfileEnabled: true, batteryProfileBelowPercent: 50 };
+  assert.equal(selectPowerProfile(settings, { onBattery: true, batteryPercent: 80 }), "performance");
+  assert.equal(selectPowerProfile(settings, { onBattery: true, batteryPercent: 50 }), "battery");
+  assert.equal(selectPowerProfile(settings, { onBattery: false, batteryPercent: 20 }), "performance");
+  assert.equal(
+    selectPowerProfile({ ...settings, batteryProfileEnabled: false }, { onBattery: true, batteryPercent: 20 }),
+    "performance",
+  );
+});
+
+test("a threshold of 100 switches as soon as the device is unplugged", () => {
+  const settings = { batteryProfileEnabled: true, batteryProfileBelowPercent: 100 };
+  assert.equal(selectPowerProfile(settings, { onBattery: true, batteryPercent: 100 }), "battery");
+});
+
+test("on battery with an unknown charge level the battery profile applies", () => {
+  const settings = { batteryProfileEnabled: true, batteryProfileBelowPercent: 20 };
+  assert.equal(selectPowerProfile(settings, { onBattery: true, batteryPercent: null }), "battery");
+  assert.equal(selectPowerProfile(settings, { onBattery: false, batteryPercent: null }), "performance");
+});
@@ -0,0 +1,77 @@
+import type { Settings } from "@shared/gfn";
+
+/**
+ * The system power source as main's powerMonitor reports it, so the battery
+ * profile and the render FPS cap always agree. The charge level comes from the
+ * Battery Status API and is null where Chromium cannot read it.
+ */
+export interface PowerState {
+  onBattery: boolean;
+  batteryPercent: number | null;
+}
+
+export type PowerProfile = "performance" | "battery";
+
+interface BatteryManagerLike extends EventTarget {
+  level: number;
+}
+
+export function selectPowerProfile(
+  settings: Pick<Settings, "batteryProfileEnabled" | "batteryProfileBelowPercent">,
+  state: PowerState,
+): PowerProfile {
+  if (!settings.batteryProfileEnabled || !state.onBattery) {
+    return "performance";
+  }
+  // Without a charge level the threshold cannot be checked, so any time on battery counts.
+  if (state.batteryPercent === null) {
+    return "battery";
+  }
+  return state.batteryPercent <= settings.batteryProfileBelowPercent ? "battery" : "performance";
+}
+
+/**
+ * Reports the power state once the power source is known and again on every
+ * change of source or charge level. Returns the unsubscribe.
+ */
+export function watchPowerState(listener: (state: PowerState) => void): () => void {
+  let onBattery: boolean | null = null;
+  let battery: BatteryManagerLike | null = null;
+  let disposed = false;
+  const report = (): void => {
+    if (onBattery !== null && !disposed) {
+      listener({ onBattery, batteryPercent: battery ? Math.round(battery.level * 100) : null });
+    }
+  };
+
+  const unsubscribe = window.openNow.onPowerSourceChanged((value) => {
+    onBattery = value;
+    report();
+  });
+  void window.openNow.isOnBatteryPower().then((value) => {
+    if (onBattery === null) {
+      onBattery = value;
+      report();
+    }
+  }).catch((error: unknown) => {
+    console.warn("[Power] Power source unavailable:", error);
+  });
+
+  const getBattery = (navigator as Navigator & { getBattery?: () => Promise<BatteryManagerLike> }).getBattery;
+  void getBattery?.call(navigator).then((manager) => {
+    if (disposed) {
+      return;
+    }
+    battery = manager;
+    manager.addEventListener("levelchange", report);
+    report();
+  }).catch((error: unknown) => {
+    console.warn("[Power] Battery level unavailable:", error);
+  });
+
+  return () => {
+    disposed = true;
+    unsubscribe();
+    battery?.removeEventListener("levelchange", report);
+  };
+}
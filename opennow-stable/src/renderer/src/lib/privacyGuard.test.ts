@@ -0,0 +1,32 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { CLIPBOARD_LIVE_WINDOW_MS, listLocalCaptures, type LocalCaptureState } from "./privacyGuard";
+
+const idle: LocalCaptureState = {
+  micState: "uninitialized",
+  micEnabled: false,
+  clipboardSentAtMs: null,
+  nowMs: 100_000,
+};
+
+test("captures list only sources that were actually opened or sent", () => {
+  assert.deepEqual(listLocalCaptures(idle), []);
+  assert.deepEqual(listLocalCaptures({ ...idle, micState: "permission_pending" }), []);
+  assert.deepEqual(listLocalCaptures({ ...idle, micState: "stopped" }), []);
+  assert.deepEqual(listLocalCaptures({ ...idle, micState: "started" }), [{ kind: "microphone", live: false }]);
+  assert.deepEqual(listLocalCaptures({ ...idle, micState: "started", micEnabled: true }), [
+    { kind: "microphone", live: true },
+  ]);
+});
+
+test("a clipboard send stays listed but is only live briefly", () => {
+  assert.deepEqual(listLocalCaptures({ ...idle, clipboardSentAtMs: idle.nowMs - 1_000 }), [
+    { kind: "clipboard", live: true },
+  ]);
+  assert.deepEqual(listLocalCaptures({ ...idle, clipboardSentAtMs: idle.nowMs - CLIPBOARD_LIVE_WINDOW_MS }), [
+    { kind: "clipboard", live: false },
+  ]);
+});
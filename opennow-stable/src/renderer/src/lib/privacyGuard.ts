@@ -0,0 +1,36 @@
+import type { MicState } from "../gfn/microphoneManager";
+
+export type LocalCaptureKind = "microphone" | "clipboard";
+
+export interface LocalCapture {
+  kind: LocalCaptureKind;
+  /** Data is leaving the machine right now, not just permitted */
+  live: boolean;
+}
+
+export interface LocalCaptureState {
+  micState: MicState;
+  micEnabled: boolean;
+  /** When clipboard text was last sent to the rig this session, or null if it never was */
+  clipboardSentAtMs: number | null;
+  nowMs: number;
+}
+
+/** How long a clipboard send keeps the row marked live */
+export const CLIPBOARD_LIVE_WINDOW_MS = 5_000;
+
+/** Local sources this session has actually sent to the cloud rig; game audio only ever flows the other way. */
+export function listLocalCaptures(state: LocalCaptureState): LocalCapture[] {
+  const captures: LocalCapture[] = [];
+  // Only an open capture track counts; a mode that merely permits the microphone captures nothing.
+  if (state.micState === "started") {
+    captures.push({ kind: "microphone", live: state.micEnabled });
+  }
+  if (state.clipboardSentAtMs !== null) {
+    captures.push({
+      kind: "clipboard",
+      live: state.nowMs - state.clipboardSentAtMs < CLIPBOARD_LIVE_WINDOW_MS,
+    });
+  }
+  return captures;
+}
@@ -0,0 +1,28 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import type { RouteTraceHop } from "@shared/gfn";
+
+import { findLatencyJumpHop, routeHopAverageMs } from "./routeTrace";
+
+const hop = (number: number, rttsMs: number[]): RouteTraceHop => ({
+  hop: number,
+  address: rttsMs.length > 0 ? `10.0.0.${number}` : null,
+  rttsMs,
+  lossPercent: rttsMs.length > 0 ? 0 : 100,
+});
+
+test("hop averages skip lost probes", () => {
+  assert.equal(routeHopAverageMs(hop(1, [2, 4])), 3);
+  assert.equal(routeHopAverageMs(hop(2, [])), null);
+});
+
+test("the latency jump is the hop where the destination's delay first appears", () => {
+  assert.equal(findLatencyJumpHop([hop(1, [1]), hop(2, [9]), hop(3, []), hop(4, [61]), hop(5, [63])]), 4);
+  // A router that is slow to answer itself, while the path beyond it is fast, is not the culprit.
+  assert.equal(findLatencyJumpHop([hop(1, [1]), hop(2, [90]), hop(3, [12])]), null);
+  assert.equal(findLatencyJumpHop([hop(1, [1]), hop(2, [5]), hop(3, [12])]), null);
+  assert.equal(findLatencyJumpHop([]), null);
+});
@@ -0,0 +1,39 @@
+import type { RouteTraceHop } from "@shared/gfn";
+
+/** A rise smaller than this between answering hops is normal jitter, not a culprit. */
+export const ROUTE_LATENCY_JUMP_MIN_MS = 20;
+
+export function routeHopAverageMs(hop: RouteTraceHop): number | null {
+  if (hop.rttsMs.length === 0) {
+    return null;
+  }
+  return hop.rttsMs.reduce((sum, rtt) => sum + rtt, 0) / hop.rttsMs.length;
+}
+
+/**
+ * The hop where latency rises the most over the previous hop that answered.
+ * Routers often deprioritize their own replies, so a rise only counts when
+ * the final hop keeps it; otherwise the spike is an artifact of that router.
+ */
+export function findLatencyJumpHop(
+  hops: readonly RouteTraceHop[],
+  minJumpMs: number = ROUTE_LATENCY_JUMP_MIN_MS,
+): number | null {
+  const answered = hops
+    .map((hop) => ({ hop: hop.hop, averageMs: routeHopAverageMs(hop) }))
+    .filter((entry): entry is { hop: number; averageMs: number } => entry.averageMs !== null);
+  const destinationMs = answered.at(-1)?.averageMs;
+  if (destinationMs === undefined) {
+    return null;
+  }
+
+  let best: { hop: number; jumpMs: number } | null = null;
+  for (let index = 1; index < answered.length; index++) {
+    const previousMs = answered[index - 1]!.averageMs;
+    const jumpMs = answered[index]!.averageMs - previousMs;
+    if (jumpMs >= minJumpMs && destinationMs - previousMs >= minJumpMs && (!best || jumpMs > best.jumpMs)) {
+      best = { hop: answered[index]!.hop, jumpMs };
+    }
+  }
+  return best?.hop ?? null;
+}
@@ -0,0 +1,58 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import type { StreamDiagnostics } from "../gfn/webrtcClient";
+import {
+  recordServerQuality,
+  serverQualityKey,
+  serverQualityPenalty,
+  ServerQualityTracker,
+} from "./serverQuality";
+
+function stats(overrides: Partial<StreamDiagnostics>): StreamDiagnostics {
+  return { renderFps: 0, rttMs: 0, packetLossPercent: 0, ...overrides } as StreamDiagnostics;
+}
+
+test("tracker averages a session for the server it streamed from", () => {
+  const tracker = new ServerQualityTracker();
+  tracker.start("https://NP-AMS-08.cloudmatchbeta.nvidiagrid.net/", 60, 0);
+  tracker.observe(stats({ renderFps: 60, rttMs: 20, packetLossPercent: 0 }));
+  tracker.observe(stats({ renderFps: 0, rttMs: 500 }));
+  tracker.observe(stats({ renderFps: 30, rttMs: 40, packetLossPercent: 2 }));
+
+  assert.deepEqual(tracker.finish(120_000), {
+    key: "np-ams-08",
+    sample: { rttMs: 30, packetLossPercent: 1, fpsRatio: 0.75 },
+  });
+  assert.equal(tracker.finish(240_000), null);
+
+  tracker.start("https://np-ams-08.cloudmatchbeta.nvidiagrid.net/", 60, 0);
+  tracker.observe(stats({ renderFps: 60 }));
+  assert.equal(tracker.finish(30_000), null);
+  assert.equal(serverQualityKey("not a url"), null);
+});
+
+test("queue picker zones and sessions on them share a key", () => {
+  assert.equal(
+    serverQualityKey("https://np-ams-08.cloudmatchbeta.nvidiagrid.net/"),
+    serverQualityKey("https://NP-AMS-08.cloudmatchbeta.nvidiagrid.net"),
+  );
+  assert.equal(serverQualityKey("https://gfn.partner.example/v2"), "gfn.partner.example");
+});
+
+test("penalty grows as a server keeps streaming badly", () => {
+  const bad = { rttMs: 100, packetLossPercent: 5, fpsRatio: 0.4 };
+  let history = recordServerQuality({}, "bad", bad, 1);
+  const once = serverQualityPenalty(history.bad);
+  history = recordServerQuality(history, "bad", bad, 2);
+  history = recordServerQuality(history, "bad", bad, 3);
+
+  assert.ok(once > 0 && once < 0.5);
+  assert.equal(serverQualityPenalty(history.bad), 0.5 + 0.125 + 0.25);
+  assert.equal(serverQualityPenalty(undefined), 0);
+
+  history = recordServerQuality(history, "good", { rttMs: 15, packetLossPercent: 0, fpsRatio: 1 }, 4);
+  assert.equal(serverQualityPenalty(history.good), 0);
+});
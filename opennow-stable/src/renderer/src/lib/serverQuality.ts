@@ -0,0 +1,154 @@
+import type { StreamDiagnostics } from "../gfn/webrtcClient";
+
+export const SERVER_QUALITY_LOCALSTORAGE_KEY = "opennow.serverQuality.v2";
+
+/** Sessions shorter than this say more about the launch than the server. */
+const MIN_SESSION_SECONDS = 60;
+/** Weight of the newest session in a server's running averages. */
+const SESSION_WEIGHT = 0.3;
+/** A server's history only counts fully once it has been this bad this often. */
+const FULL_CONFIDENCE_SESSIONS = 3;
+const MAX_SERVERS = 64;
+
+export interface ServerQualitySample {
+  rttMs: number;
+  packetLossPercent: number;
+  /** Achieved render fps as a share of the requested fps. */
+  fpsRatio: number;
+}
+
+export interface ServerQualityEntry extends ServerQualitySample {
+  sessions: number;
+  updatedAtMs: number;
+}
+
+export type ServerQualityHistory = Record<string, ServerQualityEntry>;
+
+const CLOUDMATCH_ZONE_HOST = /^([a-z0-9-]+)\.cloudmatchbeta\.nvidiagrid\.net$/;
+
+/**
+ * Servers are keyed by zone, taken from the zone routing URL the queue picker
+ * offers and the session is created on ("np-ams-08"). Other hosts, such as
+ * Alliance partners, key by hostname.
+ */
+export function serverQualityKey(routingUrl: string | undefined): string | null {
+  if (!routingUrl) {
+    return null;
+  }
+  try {
+    const host = new URL(routingUrl).hostname.toLowerCase();
+    return CLOUDMATCH_ZONE_HOST.exec(host)?.[1] ?? (host || null);
+  } catch {
+    return null;
+  }
+}
+
+interface ActiveServerSession {
+  key: string;
+  targetFps: number;
+  startedAtMs: number;
+  rttSum: number;
+  lossSum: number;
+  fpsSum: number;
+  samples: number;
+}
+
+/** Averages one stream's network and frame-rate quality for the server it ran on. */
+export class ServerQualityTracker {
+  private active: ActiveServerSession | null = null;
+
+  /** A no-op while a session is already tracked, so reconnects keep accumulating. */
+  public start(routingUrl: string | undefined, targetFps: number, nowMs: number): void {
+    const key = serverQualityKey(routingUrl);
+    if (this.active || !key || targetFps <= 0) {
+      return;
+    }
+    this.active = { key, targetFps, startedAtMs: nowMs, rttSum: 0, lossSum: 0, fpsSum: 0, samples: 0 };
+  }
+
+  public observe(stats: StreamDiagnostics): void {
+    const active = this.active;
+    if (!active || stats.renderFps <= 0) {
+      return;
+    }
+    active.rttSum += stats.rttMs;
+    active.lossSum += stats.packetLossPercent;
+    active.fpsSum += stats.renderFps;
+    active.samples += 1;
+  }
+
+  public finish(nowMs: number): { key: string; sample: ServerQualitySample } | null {
+    const active = this.active;
+    this.active = null;
+    if (!active || active.samples === 0 || nowMs - active.startedAtMs < MIN_SESSION_SECONDS * 1000) {
+      return null;
+    }
+    return {
+      key: active.key,
+      sample: {
+        rttMs: active.rttSum / active.samples,
+        packetLossPercent: active.lossSum / active.samples,
+        fpsRatio: Math.min(active.fpsSum / active.samples / active.targetFps, 1),
+      },
+    };
+  }
+}
+
+export function recordServerQuality(
+  history: ServerQualityHistory,
+  key: string,
+  sample: ServerQualitySample,
+  nowMs: number,
+): ServerQualityHistory {
+  const previous = history[key];
+  const blend = (older: number, newer: number): number => older + (newer - older) * SESSION_WEIGHT;
+  const entry: ServerQualityEntry = previous
+    ? {
+        rttMs: blend(previous.rttMs, sample.rttMs),
+        packetLossPercent: blend(previous.packetLossPercent, sample.packetLossPercent),
+        fpsRatio: blend(previous.fpsRatio, sample.fpsRatio),
+        sessions: previous.sessions + 1,
+        updatedAtMs: nowMs,
+      }
+    : { ...sample, sessions: 1, updatedAtMs: nowMs };
+  const entries = Object.entries({ ...history, [key]: entry })
+    .sort(([, a], [, b]) => b.updatedAtMs - a.updatedAtMs)
+    .slice(0, MAX_SERVERS);
+  return Object.fromEntries(entries);
+}
+
+/**
+ * 0 for a server that streamed cleanly (or has no history), up to 1 for one
+ * that repeatedly lost packets, ran high latency and missed its frame rate.
+ */
+export function serverQualityPenalty(entry: ServerQualityEntry | undefined): number {
+  if (!entry) {
+    return 0;
+  }
+  const loss = Math.min(entry.packetLossPercent / 5, 1);
+  const rtt = Math.min(Math.max(entry.rttMs - 40, 0) / 120, 1);
+  const fps = Math.min(Math.max(0.95 - entry.fpsRatio, 0) / 0.45, 1);
+  const confidence = Math.min(entry.sessions / FULL_CONFIDENCE_SESSIONS, 1);
+  return (loss * 0.5 + rtt * 0.25 + fps * 0.25) * confidence;
+}
+
+export function loadServerQualityHistory(): ServerQualityHistory {
+  try {
+    const raw = localStorage.getItem(SERVER_QUALITY_LOCALSTORAGE_KEY);
+    const parsed = raw ? (JSON.parse(raw) as unknown) : null;
+    if (parsed && typeof parsed === "object" && !Array.isArray(parsed)) {
+      return parsed as ServerQualityHistory;
+    }
+  } catch {
+    // ignore
+  }
+  return {};
+}
+
+export function saveServerQualityHistory(history: ServerQualityHistory): void {
+  try {
+    localStorage.setItem(SERVER_QUALITY_LOCALSTORAGE_KEY, JSON.stringify(history));
+  } catch {
+    // ignore
+  }
+}
@@ -3,7 +3,7 @@
 import test from "node:test";
 import assert from "node:assert/strict";
 
-import { toLaunchErrorState } from "./sessionState";
+import { orderActiveSessions, toLaunchErrorState } from "./sessionState";
 
 const translations: Record<string, string> = {
   "errors.duplicateSessionTitle": "Duplicate Session Detected",
@@ -13,6 +13,11 @@ const translations: Record<string, string> = {
   "errors.insufficientPlayabilityTierDescription": "This game requires {{tier}} on GeForce NOW. Upgrade your membership to play it.",
   "errors.launchFailedTitle": "Launch Failed",
   "errors.launchUnknown": "The game could not start. Please try again.",
+  "errors.categories.capacity.title": "Servers Are Full",
+  "errors.categories.capacity.description": "There are no free rigs in this region right now.",
+  "errors.categories.capacity.step1": "Wait a few minutes, then launch again.",
+  "errors.categories.capacity.step2": "Pick a different server region in Settings.",
+  "errors.links.serviceStatus": "Check GeForce NOW service status",
 };
 
 function t(key: string, values: Record<string, string | number | boolean | null | undefined> = {}): string {
@@ -53,3 +58,40 @@ test("launch error state still treats session limit as duplicate session", () =>
   assert.equal(state.description, "Another session is already running.");
 });
 
+
+test("launch error state replaces raw codes with category remediation", () => {
+  const state = toLaunchErrorState(t, {
+    gfnErrorCode: 3237093694,
+    category: "capacity",
+    title: "Error",
+    description: "An error occurred (HTTP 200).",
+  }, "queue");
+
+  assert.equal(state.category, "capacity");
+  assert.equal(state.title, "Servers Are Full");
+  assert.deepEqual(state.steps, ["Wait a few minutes, then launch again.", "Pick a different server region in Settings."]);
+  assert.equal(state.helpLink?.label, "Check GeForce NOW service status");
+  assert.equal(state.codeLabel, "GFN Error 3237093694");
+});
+
+test("launch error state keeps the service copy for uncategorised errors", () => {
+  const state = toLaunchErrorState(t, { gfnErrorCode: 3237093637, title: "Invalid Request", description: "The request was invalid." }, "setup");
+
+  assert.equal(state.category, undefined);
+  assert.equal(state.title, "Invalid Request");
+  assert.equal(state.steps, undefined);
+});
+
+test("active sessions keep the chosen one first and ready sessions before queued ones", () => {
+  const queued = { sessionId: "queued", appId: 10, status: 1 };
+  const ready = { sessionId: "ready", appId: 20, status: 2 };
+  const streaming = { sessionId: "streaming", appId: 30, status: 3 };
+  const ended = { sessionId: "ended", appId: 40, status: 6 };
+  const sessions = [queued, ready, ended, streaming];
+  const ids = (list: typeof sessions) => list.map((entry) => entry.sessionId);
+
+  assert.deepEqual(ids(orderActiveSessions(sessions, [])), ["ready", "streaming", "queued"]);
+  assert.deepEqual(ids(orderActiveSessions(sessions, [null, "streaming"], 10)), ["streaming", "queued", "ready"]);
+  assert.deepEqual(ids(orderActiveSessions(sessions, ["queued", "streaming"])), ["queued", "streaming", "ready"]);
+  assert.deepEqual(orderActiveSessions([ended], ["ended"]), []);
+});
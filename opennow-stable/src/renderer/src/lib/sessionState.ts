@@ -1,4 +1,11 @@
-import type { GameInfo, SessionInfo } from "@shared/gfn";
+import {
+  classifyGfnError,
+  GFN_MEMBERSHIPS_URL,
+  GFN_STATUS_PAGE_URL,
+  isGfnErrorCategory,
+  type GfnErrorCategory,
+} from "@shared/errorTaxonomy";
+import type { ActiveSessionInfo, GameInfo, SessionInfo } from "@shared/gfn";
 
 import type { LaunchErrorState, StreamLoadingStatus, StreamStatus } from "./appTypes";
 
@@ -8,6 +15,31 @@ export function isSessionReadyForConnect(status: number): boolean {
   return status === 2 || status === 3;
 }
 
+/**
+ * Sessions worth offering in the navbar: queued (1), ready (2) and streaming (3).
+ * The preferred session, then any session for the preferred app, sorts first.
+ */
+export function orderActiveSessions(
+  sessions: ActiveSessionInfo[],
+  preferredSessionIds: Array<string | null | undefined>,
+  preferredAppId?: number | null,
+): ActiveSessionInfo[] {
+  const tracked = sessions.filter((entry) => entry.status === 1 || isSessionReadyForConnect(entry.status));
+  const rank = (entry: ActiveSessionInfo): number => {
+    const preferredIndex = preferredSessionIds.findIndex((id) => id && id === entry.sessionId);
+    if (preferredIndex >= 0) return preferredIndex;
+    if (preferredAppId !== null && preferredAppId !== undefined && entry.appId === preferredAppId) {
+      return preferredSessionIds.length;
+    }
+    // Ready sessions before queued ones, so the default choice can be resumed.
+    return preferredSessionIds.length + (isSessionReadyForConnect(entry.status) ? 1 : 2);
+  };
+  return tracked
+    .map((entry, index) => ({ entry, index, rank: rank(entry) }))
+    .sort((a, b) => a.rank - b.rank || a.index - b.index)
+    .map(({ entry }) => entry);
+}
+
 export function isSessionInQueue(session: SessionInfo): boolean {
   // Official client treats seat setup step 1 as queue state even when queuePosition reaches 1.
   // Fallback to queuePosition-based inference for payloads that do not expose seatSetupStep.
@@ -86,6 +118,53 @@ export function extractLaunchErrorCode(error: unknown): number | undefined {
   return undefined;
 }
 
+interface CategoryCopy {
+  /** Key under errors.categories; membership and session-limit reuse their older title/description copy */
+  key: string;
+  stepCount: number;
+  link?: { labelKey: string; url: string };
+}
+
+const STATUS_LINK = { labelKey: "errors.links.serviceStatus", url: GFN_STATUS_PAGE_URL };
+const MEMBERSHIPS_LINK = { labelKey: "errors.links.memberships", url: GFN_MEMBERSHIPS_URL };
+
+const CATEGORY_COPY: Record<Exclude<GfnErrorCategory, "unknown">, CategoryCopy> = {
+  "region-unsupported": { key: "regionUnsupported", stepCount: 2 },
+  capacity: { key: "capacity", stepCount: 2, link: STATUS_LINK },
+  entitlement: { key: "entitlement", stepCount: 2, link: MEMBERSHIPS_LINK },
+  membership: { key: "membership", stepCount: 1, link: MEMBERSHIPS_LINK },
+  "session-limit": { key: "sessionLimit", stepCount: 2 },
+  auth: { key: "auth", stepCount: 1 },
+  "account-action": { key: "accountAction", stepCount: 2 },
+  "account-link": { key: "accountLink", stepCount: 2 },
+  maintenance: { key: "maintenance", stepCount: 1, link: STATUS_LINK },
+  "game-unavailable": { key: "gameUnavailable", stepCount: 2 },
+  network: { key: "network", stepCount: 2, link: STATUS_LINK },
+};
+
+export function extractLaunchErrorCategory(error: unknown, code: number | undefined, statusDescription: string): GfnErrorCategory {
+  if (error && typeof error === "object" && "category" in error && isGfnErrorCategory(error.category)) {
+    return error.category;
+  }
+  return classifyGfnError(code, statusDescription);
+}
+
+function toRemediation(
+  t: TranslateFunction,
+  category: GfnErrorCategory,
+): Pick<LaunchErrorState, "category" | "steps" | "helpLink"> {
+  if (category === "unknown") {
+    return { category };
+  }
+  const copy = CATEGORY_COPY[category];
+  const steps = Array.from({ length: copy.stepCount }, (_, index) => t(`errors.categories.${copy.key}.step${index + 1}`));
+  return {
+    category,
+    steps,
+    helpLink: copy.link ? { label: t(copy.link.labelKey), url: copy.link.url } : undefined,
+  };
+}
+
 function firstText(value: string | string[] | undefined): string {
   if (typeof value === "string") return value.trim();
   if (Array.isArray(value)) {
@@ -119,6 +198,7 @@ function toInsufficientPlayabilityState(
     title,
     description,
     codeLabel: toCodeLabel(code),
+    ...toRemediation(t, "membership"),
   };
 }
 
@@ -145,12 +225,14 @@ export function toLaunchErrorState(
   const messageFromError = error instanceof Error ? error.message.trim() : "";
   const combined = `${statusDescription} ${messageFromError}`.toUpperCase();
   const code = extractLaunchErrorCode(error);
+  const category = extractLaunchErrorCategory(error, code, statusDescription);
 
-  if (isInsufficientPlayabilityError(error) || combined.includes("INSUFFICIENT_PLAYABILITY")) {
+  if (category === "membership" || isInsufficientPlayabilityError(error) || combined.includes("INSUFFICIENT_PLAYABILITY")) {
     return toInsufficientPlayabilityState(t, stage, code, game);
   }
 
   if (
+    category === "session-limit" ||
     isSessionLimitError(error) ||
     combined.includes("SESSION_LIMIT") ||
     combined.includes("DUPLICATE SESSION")
@@ -160,6 +242,18 @@ export function toLaunchErrorState(
       title: t("errors.duplicateSessionTitle"),
       description: t("errors.duplicateSessionDescription"),
       codeLabel: toCodeLabel(code),
+      ...toRemediation(t, "session-limit"),
+    };
+  }
+
+  if (category !== "unknown") {
+    const copy = CATEGORY_COPY[category];
+    return {
+      stage,
+      title: t(`errors.categories.${copy.key}.title`),
+      description: t(`errors.categories.${copy.key}.description`),
+      codeLabel: toCodeLabel(code),
+      ...toRemediation(t, category),
     };
   }
 
@@ -0,0 +1,60 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import type { StreamDiagnostics } from "../gfn/webrtcClient";
+import { SessionTelemetryTracker } from "./sessionTelemetry";
+
+function stats(overrides: Partial<StreamDiagnostics>): StreamDiagnostics {
+  return { renderFps: 0, hardwareAcceleration: "", codec: "", nativeRendererActive: false, ...overrides } as StreamDiagnostics;
+}
+
+test("tracker summarises a session across a reconnect", () => {
+  const tracker = new SessionTelemetryTracker();
+  tracker.observe(stats({ renderFps: 144 }));
+  tracker.start("web", 0);
+  tracker.observe(stats({ renderFps: 60, codec: "H264", hardwareAcceleration: "D3D11" }));
+  tracker.observe(stats({ renderFps: 0 }));
+  tracker.noteReconnect();
+  tracker.start("web", 50_000);
+  tracker.observe(stats({ renderFps: 50 }));
+  tracker.noteError("0xC0F22226", "web", 60_000);
+  tracker.noteError("0xC0F22226", "web", 61_000);
+
+  assert.deepEqual(tracker.finish(120_000), {
+    clientMode: "web",
+    decoderBackend: "D3D11",
+    codec: "H264",
+    averageFps: 55,
+    durationSeconds: 120,
+    reconnectCount: 1,
+    errorCodes: ["0xC0F22226"],
+  });
+  assert.equal(tracker.finish(130_000), null);
+});
+
+test("tracker skips short sessions unless they failed", () => {
+  const tracker = new SessionTelemetryTracker();
+  tracker.start("native", 0);
+  assert.equal(tracker.finish(5_000), null);
+
+  tracker.start("native", 0);
+  tracker.noteError("GFN Error 3237093694", "native", 4_000);
+  assert.equal(tracker.finish(5_000)?.errorCodes[0], "GFN Error 3237093694");
+});
+
+test("tracker records a launch that failed before streaming", () => {
+  const tracker = new SessionTelemetryTracker();
+  tracker.noteError("SessionInsufficientPlayabilityLevel (3237093718)", "web", 10_000);
+
+  assert.deepEqual(tracker.finish(10_500), {
+    clientMode: "web",
+    decoderBackend: "unknown",
+    codec: "unknown",
+    averageFps: 0,
+    durationSeconds: 1,
+    reconnectCount: 0,
+    errorCodes: ["SessionInsufficientPlayabilityLevel (3237093718)"],
+  });
+});
@@ -0,0 +1,87 @@
+import type { SessionTelemetryReport } from "@shared/gfn";
+import type { StreamDiagnostics } from "../gfn/webrtcClient";
+
+/** Sessions shorter than this are launch hiccups, not a useful quality sample. */
+const MIN_SESSION_SECONDS = 30;
+
+interface ActiveSession {
+  clientMode: SessionTelemetryReport["clientMode"];
+  startedAtMs: number;
+  decoderBackend: string;
+  codec: string;
+  fpsSum: number;
+  fpsSamples: number;
+  reconnectCount: number;
+  errorCodes: Set<string>;
+}
+
+/** Follows one stream from its first frame to its end and summarises it for telemetry. */
+export class SessionTelemetryTracker {
+  private active: ActiveSession | null = null;
+
+  /** Starts tracking; a no-op while a session is already being tracked (e.g. after a reconnect). */
+  public start(clientMode: SessionTelemetryReport["clientMode"], nowMs: number): void {
+    if (this.active) {
+      return;
+    }
+    this.active = {
+      clientMode,
+      startedAtMs: nowMs,
+      decoderBackend: "",
+      codec: "",
+      fpsSum: 0,
+      fpsSamples: 0,
+      reconnectCount: 0,
+      errorCodes: new Set(),
+    };
+  }
+
+  public observe(stats: StreamDiagnostics): void {
+    const active = this.active;
+    if (!active || stats.renderFps <= 0) {
+      return;
+    }
+    active.fpsSum += stats.renderFps;
+    active.fpsSamples += 1;
+    if (stats.hardwareAcceleration) active.decoderBackend = stats.hardwareAcceleration;
+    if (stats.codec) active.codec = stats.codec;
+    if (stats.nativeRendererActive) active.clientMode = "native";
+  }
+
+  public noteReconnect(): void {
+    if (this.active) {
+      this.active.reconnectCount += 1;
+    }
+  }
+
+  /** Records a GFN error code; a launch that fails before streaming is tracked as a zero-length session. */
+  public noteError(code: string, clientMode: SessionTelemetryReport["clientMode"], nowMs: number): void {
+    if (!code) {
+      return;
+    }
+    this.start(clientMode, nowMs);
+    this.active?.errorCodes.add(code);
+  }
+
+  /** Ends tracking and returns the report, or null when there is nothing worth recording. */
+  public finish(nowMs: number): SessionTelemetryReport | null {
+    const active = this.active;
+    this.active = null;
+    if (!active) {
+      return null;
+    }
+    const durationSeconds = Math.round((nowMs - active.startedAtMs) / 1000);
+    if (durationSeconds < MIN_SESSION_SECONDS && active.errorCodes.size === 0) {
+      return null;
+    }
+    return {
+      clientMode: active.clientMode,
+      decoderBackend: active.decoderBackend || "unknown",
+      codec: active.codec || "unknown",
+      averageFps: active.fpsSamples > 0 ? Math.round((active.fpsSum / active.fpsSamples) * 10) / 10 : 0,
+      durationSeconds,
+      reconnectCount: active.reconnectCount,
+      errorCodes: [...active.errorCodes],
+    };
+  }
+}
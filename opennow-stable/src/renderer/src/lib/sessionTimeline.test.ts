@@ -0,0 +1,75 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import type { StreamDiagnostics } from "../gfn/webrtcClient";
+import { diffTimelineSnapshots, SessionTimelineRecorder } from "./sessionTimeline";
+
+const base = {
+  connectionState: "connected",
+  resolution: "1920x1080",
+  decoderPressureActive: false,
+  decoderRecoveryAction: "none",
+  keyframeRequests: 2,
+  decoderErrors: undefined,
+  nativePartialFlushCount: undefined,
+  nativeCompleteFlushCount: undefined,
+  lagReason: "stable",
+  lagReasonDetail: "",
+} as const;
+
+function snapshot(overrides: Partial<StreamDiagnostics> = {}): StreamDiagnostics {
+  return { ...base, ...overrides } as StreamDiagnostics;
+}
+
+test("snapshot changes become timeline events, with counters baselined on the first snapshot", () => {
+  assert.deepEqual(diffTimelineSnapshots(null, base), [
+    { kind: "connection", code: "connection", params: { state: "connected" } },
+    { kind: "resolution", code: "videoStarted", params: { resolution: "1920x1080" } },
+  ]);
+  assert.deepEqual(
+    diffTimelineSnapshots(base, {
+      ...base,
+      connectionState: "disconnected",
+      resolution: "1280x720",
+      keyframeRequests: 5,
+      decoderRecoveryAction: "signaling_keyframe",
+      nativeCompleteFlushCount: 1,
+    }),
+    [
+      { kind: "connection", code: "connectionChanged", params: { from: "connected", to: "disconnected" } },
+      { kind: "resolution", code: "resolutionChanged", params: { from: "1920x1080", to: "1280x720" } },
+      { kind: "keyframe", code: "keyframesRequested", params: { count: 3 } },
+      { kind: "decoder", code: "decoderRecovery", params: { action: "signaling_keyframe" } },
+      { kind: "decoder", code: "decoderResets", params: { count: 1 } },
+    ],
+  );
+  assert.deepEqual(diffTimelineSnapshots(base, base), []);
+});
+
+test("lag is recorded only once a reason holds, and the timeline closes on finish", () => {
+  const recorder = new SessionTimelineRecorder();
+  recorder.start(1000);
+  recorder.observe(snapshot(), 1000);
+  recorder.observe(snapshot({ lagReason: "network", lagReasonDetail: "5% loss" }), 1500);
+  recorder.observe(snapshot(), 2000);
+  for (const atMs of [2500, 3000, 3500]) {
+    recorder.observe(snapshot({ lagReason: "network", lagReasonDetail: "5% loss" }), atMs);
+  }
+  recorder.note("reconnect", "reconnectAttempt", { attempt: 1, total: 3, reason: "Network changed" }, 4000);
+  const timeline = recorder.finish(5000);
+  assert.deepEqual(
+    timeline?.events.map((event) => [event.atMs, event.code, event.params]),
+    [
+      [1000, "streamStarted", undefined],
+      [1000, "connection", { state: "connected" }],
+      [1000, "videoStarted", { resolution: "1920x1080" }],
+      [3500, "lagging", { reason: "network", detail: "5% loss" }],
+      [4000, "reconnectAttempt", { attempt: 1, total: 3, reason: "Network changed" }],
+      [5000, "streamEnded", undefined],
+    ],
+  );
+  assert.equal(timeline?.endedAtMs, 5000);
+  assert.equal(recorder.finish(6000), null);
+});
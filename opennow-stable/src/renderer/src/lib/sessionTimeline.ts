@@ -0,0 +1,175 @@
+import type { SessionTimeline, SessionTimelineEvent, SessionTimelineEventCode, SessionTimelineEventKind } from "@shared/gfn";
+import type { StreamDiagnostics, StreamLagReason } from "../gfn/webrtcClient";
+
+/** A link that keeps flapping would otherwise grow the timeline without bound; the oldest events go first. */
+const MAX_EVENTS = 300;
+/** The lag classifier runs on every stats poll; a reason has to hold this long before it counts. */
+const LAG_CONFIRM_SNAPSHOTS = 3;
+
+type TimelineFields = Pick<
+  StreamDiagnostics,
+  | "connectionState"
+  | "resolution"
+  | "decoderPressureActive"
+  | "decoderRecoveryAction"
+  | "keyframeRequests"
+  | "decoderErrors"
+  | "nativePartialFlushCount"
+  | "nativeCompleteFlushCount"
+>;
+
+type TimelineChange = Pick<SessionTimelineEvent, "kind" | "code" | "params">;
+
+/** Events implied by two consecutive diagnostics snapshots; counters only count from the second one. */
+export function diffTimelineSnapshots(previous: TimelineFields | null, next: TimelineFields): TimelineChange[] {
+  const changes: TimelineChange[] = [];
+  if (next.connectionState !== (previous?.connectionState ?? "closed")) {
+    changes.push(
+      previous
+        ? { kind: "connection", code: "connectionChanged", params: { from: previous.connectionState, to: next.connectionState } }
+        : { kind: "connection", code: "connection", params: { state: next.connectionState } },
+    );
+  }
+  if (next.resolution && next.resolution !== (previous?.resolution ?? "")) {
+    changes.push(
+      previous?.resolution
+        ? { kind: "resolution", code: "resolutionChanged", params: { from: previous.resolution, to: next.resolution } }
+        : { kind: "resolution", code: "videoStarted", params: { resolution: next.resolution } },
+    );
+  }
+  if (!previous) {
+    return changes;
+  }
+  const keyframes = (next.keyframeRequests ?? 0) - (previous.keyframeRequests ?? 0);
+  if (keyframes > 0) {
+    changes.push({ kind: "keyframe", code: "keyframesRequested", params: { count: keyframes } });
+  }
+  if (next.decoderRecoveryAction !== previous.decoderRecoveryAction && next.decoderRecoveryAction !== "none") {
+    changes.push({ kind: "decoder", code: "decoderRecovery", params: { action: next.decoderRecoveryAction } });
+  }
+  if (next.decoderPressureActive !== previous.decoderPressureActive) {
+    changes.push({ kind: "degraded", code: next.decoderPressureActive ? "decoderFallingBehind" : "decoderCaughtUp" });
+  }
+  const errors = (next.decoderErrors ?? 0) - (previous.decoderErrors ?? 0);
+  if (errors > 0) {
+    changes.push({ kind: "decoder", code: "decoderErrors", params: { count: errors } });
+  }
+  const resets = (next.nativeCompleteFlushCount ?? 0) - (previous.nativeCompleteFlushCount ?? 0);
+  if (resets > 0) {
+    changes.push({ kind: "decoder", code: "decoderResets", params: { count: resets } });
+  }
+  const flushes = (next.nativePartialFlushCount ?? 0) - (previous.nativePartialFlushCount ?? 0);
+  if (flushes > 0) {
+    changes.push({ kind: "decoder", code: "decoderFlushes", params: { count: flushes } });
+  }
+  return changes;
+}
+
+/**
+ * Records the significant moments of one stream for post-mortem debugging.
+ * Doubles as an external store so the stats panel can follow it live.
+ */
+export class SessionTimelineRecorder {
+  private timeline: SessionTimeline | null = null;
+  private previous: TimelineFields | null = null;
+  private lagReason: StreamLagReason = "unknown";
+  private pendingLag: { reason: StreamLagReason; detail: string; snapshots: number } | null = null;
+  private readonly listeners = new Set<() => void>();
+
+  public readonly getSnapshot = (): SessionTimeline | null => this.timeline;
+
+  public readonly subscribe = (listener: () => void): (() => void) => {
+    this.listeners.add(listener);
+    return () => this.listeners.delete(listener);
+  };
+
+  /** Starts a new timeline; a no-op while one is running (e.g. across a reconnect). */
+  public start(nowMs: number): void {
+    if (this.timeline?.endedAtMs === null) {
+      return;
+    }
+    this.timeline = { startedAtMs: nowMs, endedAtMs: null, events: [] };
+    this.previous = null;
+    this.lagReason = "unknown";
+    this.pendingLag = null;
+    this.push([{ kind: "session", code: "streamStarted" }], nowMs);
+  }
+
+  public observe(stats: StreamDiagnostics, nowMs: number): void {
+    if (this.timeline?.endedAtMs !== null) {
+      return;
+    }
+    const changes = diffTimelineSnapshots(this.previous, stats);
+    this.previous = {
+      connectionState: stats.connectionState,
+      resolution: stats.resolution,
+      decoderPressureActive: stats.decoderPressureActive,
+      decoderRecoveryAction: stats.decoderRecoveryAction,
+      keyframeRequests: stats.keyframeRequests,
+      decoderErrors: stats.decoderErrors,
+      nativePartialFlushCount: stats.nativePartialFlushCount,
+      nativeCompleteFlushCount: stats.nativeCompleteFlushCount,
+    };
+    const lag = this.confirmLagReason(stats.lagReason, stats.lagReasonDetail);
+    if (lag) {
+      changes.push(lag);
+    }
+    this.push(changes, nowMs);
+  }
+
+  public note(
+    kind: SessionTimelineEventKind,
+    code: SessionTimelineEventCode,
+    params: SessionTimelineEvent["params"],
+    nowMs: number,
+  ): void {
+    if (this.timeline?.endedAtMs === null) {
+      this.push([{ kind, code, params }], nowMs);
+    }
+  }
+
+  /** Ends the running timeline and returns it, or null when none was running. */
+  public finish(nowMs: number): SessionTimeline | null {
+    if (this.timeline?.endedAtMs !== null) {
+      return null;
+    }
+    this.push([{ kind: "session", code: "streamEnded" }], nowMs);
+    this.timeline = { ...this.timeline, endedAtMs: nowMs };
+    this.emit();
+    return this.timeline;
+  }
+
+  private confirmLagReason(reason: StreamLagReason, detail: string): TimelineChange | null {
+    if (reason === "unknown" || reason === this.lagReason) {
+      this.pendingLag = null;
+      return null;
+    }
+    const snapshots = this.pendingLag?.reason === reason ? this.pendingLag.snapshots + 1 : 1;
+    this.pendingLag = { reason, detail, snapshots };
+    if (snapshots < LAG_CONFIRM_SNAPSHOTS) {
+      return null;
+    }
+    const wasLagging = this.lagReason !== "unknown" && this.lagReason !== "stable";
+    this.lagReason = reason;
+    this.pendingLag = null;
+    if (reason === "stable") {
+      return wasLagging ? { kind: "degraded", code: "stableAgain" } : null;
+    }
+    return { kind: "degraded", code: "lagging", params: { reason, detail } };
+  }
+
+  private push(changes: TimelineChange[], atMs: number): void {
+    if (!this.timeline || changes.length === 0) {
+      return;
+    }
+    const events = [...this.timeline.events, ...changes.map((change) => ({ atMs, ...change }))];
+    this.timeline = { ...this.timeline, events: events.slice(-MAX_EVENTS) };
+    this.emit();
+  }
+
+  private emit(): void {
+    for (const listener of this.listeners) {
+      listener();
+    }
+  }
+}
@@ -0,0 +1,40 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { getLocalSessionTimerWarning, getPlayTimeWarning, hasCrossedWarningThreshold } from "./sessionWarnings";
+
+function t(key: string): string {
+  return key;
+}
+
+test("the five-minute free-tier warning counts down without escalating to critical", () => {
+  assert.deepEqual(getLocalSessionTimerWarning(t, "free-tier-5m", 299), {
+    code: 1,
+    message: "session.warnings.freeTier5MinutesRemaining",
+    tone: "warn",
+    secondsLeft: 299,
+  });
+  assert.equal(getLocalSessionTimerWarning(t, "free-tier-final-minute", 42).tone, "critical");
+});
+
+test("fires a threshold once when the countdown crosses it", () => {
+  assert.equal(hasCrossedWarningThreshold(301, 300, 300), true);
+  assert.equal(hasCrossedWarningThreshold(300, 299, 300), false);
+  assert.equal(hasCrossedWarningThreshold(null, 300, 300), true);
+});
+
+test("play time reminders turn critical in the final minute and name the limit that was hit", () => {
+  assert.deepEqual(getPlayTimeWarning(t, { stage: "15m", reason: "duration" }), {
+    code: 1,
+    message: "session.warnings.playTime15MinutesRemaining",
+    tone: "warn",
+  });
+  assert.deepEqual(getPlayTimeWarning(t, { stage: "final-minute", reason: "curfew", secondsLeft: 12 }), {
+    code: 1,
+    message: "session.warnings.bedtimeSoon",
+    tone: "critical",
+    secondsLeft: 12,
+  });
+});
@@ -1,6 +1,6 @@
 import type { SubscriptionInfo } from "@shared/gfn";
 
-import type { LocalSessionTimerWarningState, StreamWarningState } from "./appTypes";
+import type { LocalSessionTimerWarningState, PlayTimeReminderState, StreamWarningState } from "./appTypes";
 import { normalizeMembershipTier } from "./queueAds";
 
 type TranslateFunction = typeof import("../i18n").t;
@@ -68,6 +68,15 @@ export function getLocalSessionTimerWarning(
     };
   }
 
+  if (stage === "free-tier-5m") {
+    return {
+      code: 1,
+      message: t("session.warnings.freeTier5MinutesRemaining"),
+      tone: "warn",
+      secondsLeft: Math.max(0, secondsLeft),
+    };
+  }
+
   return {
     code: 1,
     message: t("session.warnings.freeTierEndsSoon"),
@@ -75,3 +84,30 @@ export function getLocalSessionTimerWarning(
     secondsLeft: Math.max(0, secondsLeft),
   };
 }
+
+const PLAY_TIME_MESSAGE_KEYS = {
+  duration: {
+    "15m": "session.warnings.playTime15MinutesRemaining",
+    "5m": "session.warnings.playTime5MinutesRemaining",
+    "final-minute": "session.warnings.playTimeEndsSoon",
+    over: "session.warnings.playTimeOver",
+  },
+  curfew: {
+    "15m": "session.warnings.bedtime15MinutesRemaining",
+    "5m": "session.warnings.bedtime5MinutesRemaining",
+    "final-minute": "session.warnings.bedtimeSoon",
+    over: "session.warnings.bedtimeOver",
+  },
+} as const;
+
+export function getPlayTimeWarning(t: TranslateFunction, reminder: PlayTimeReminderState): StreamWarningState {
+  const warning: StreamWarningState = {
+    code: 1,
+    message: t(PLAY_TIME_MESSAGE_KEYS[reminder.reason][reminder.stage]),
+    tone: reminder.stage === "15m" || reminder.stage === "5m" ? "warn" : "critical",
+  };
+  if (reminder.secondsLeft !== undefined) {
+    warning.secondsLeft = Math.max(0, reminder.secondsLeft);
+  }
+  return warning;
+}
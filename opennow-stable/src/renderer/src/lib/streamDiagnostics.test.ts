@@ -0,0 +1,93 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import {
+  averagePlayoutDelayMs,
+  defaultDiagnostics,
+  estimateAvSyncOffsetMs,
+  formatAudioStats,
+  formatBitstreamHealth,
+  formatPathMtu,
+  mergeNativeStreamStats,
+} from "./streamDiagnostics";
+
+test("A/V offset is positive when audio plays older content than video", () => {
+  assert.equal(estimateAvSyncOffsetMs(1_700_000_000_000, 1_700_000_000_042), 42);
+  assert.equal(estimateAvSyncOffsetMs(1_700_000_000_030, 1_700_000_000_000), -30);
+});
+
+test("A/V offset is unknown when a track has no playout estimate", () => {
+  assert.equal(estimateAvSyncOffsetMs(undefined, 1_700_000_000_000), undefined);
+  assert.equal(estimateAvSyncOffsetMs(0, 1_700_000_000_000), undefined);
+  assert.equal(estimateAvSyncOffsetMs(1_700_000_000_000, 1_700_000_060_000), undefined);
+});
+
+test("audio output latency averages only the samples played since the last poll", () => {
+  const first = { totalPlayoutDelay: 48_000, totalSamplesCount: 960_000 };
+  assert.equal(averagePlayoutDelayMs(null, first), 50);
+  assert.equal(
+    averagePlayoutDelayMs(first, { totalPlayoutDelay: 48_960, totalSamplesCount: 1_008_000 }),
+    20,
+  );
+  assert.equal(averagePlayoutDelayMs(first, first), undefined);
+});
+
+test("native stats carry the decoder memory budget into diagnostics", () => {
+  const stats = {
+    codec: "H265",
+    resolution: "3840x2160",
+    hardwareAcceleration: "D3D11",
+    bitrateKbps: 50_000,
+    targetBitrateKbps: 75_000,
+    bitratePerformancePercent: 66,
+    decodedFps: 60,
+    renderFps: 60,
+    framesDecoded: 600,
+    framesRendered: 598,
+    zeroCopyD3D11: true,
+    zeroCopyD3D12: false,
+    memoryBudgetMb: 1024,
+  };
+  const merged = mergeNativeStreamStats(defaultDiagnostics(), stats);
+  assert.equal(merged.nativeMemoryBudgetMb, 1024);
+
+  const { memoryBudgetMb: _budget, ...withoutMemory } = stats;
+  const next = mergeNativeStreamStats(merged, withoutMemory);
+  assert.equal(next.nativeMemoryBudgetMb, 1024);
+});
+
+test("bitstream health line only lists the counters a client reports", () => {
+  assert.equal(formatBitstreamHealth({}), null);
+  assert.equal(
+    formatBitstreamHealth({ keyframesReceived: 3, keyframeRequests: 2 }),
+    "Bitstream keyframes 3 · requested 2",
+  );
+  assert.equal(
+    formatBitstreamHealth({ keyframesReceived: 4, keyframeIntervalMs: 2500, damagedFrames: 7, decoderErrors: 0 }),
+    "Bitstream keyframes 4 every 2.5s · damaged 7 · decoder errors 0",
+  );
+});
+
+test("path MTU line flags reduced and still-fragmenting packet sizes", () => {
+  assert.equal(formatPathMtu({ videoPacketSize: 1140 }), null);
+  assert.equal(formatPathMtu({ pathMtu: 1500, videoPacketSize: 1140 }), "Path MTU 1500 · video packets 1140B");
+  assert.equal(
+    formatPathMtu({ pathMtu: 1200, videoPacketSize: 1100 }),
+    "Path MTU 1200 · video packets 1100B · reduced from 1140B to avoid fragmentation",
+  );
+  assert.equal(formatPathMtu({ pathMtu: 576, videoPacketSize: 500 }), "Path MTU 576 · video packets 500B · may fragment");
+});
+
+test("audio line combines format, playout delay and underruns", () => {
+  assert.equal(formatAudioStats({}), null);
+  assert.equal(
+    formatAudioStats({ audioCodec: "OPUS", audioSampleRate: 48000, audioChannels: 2, audioOutputLatencyMs: 21.25, audioUnderruns: 3 }),
+    "Audio OPUS 48kHz stereo · playout 21.3ms · underruns 3",
+  );
+  assert.equal(
+    formatAudioStats({ audioSampleRate: 44100, audioChannels: 6, audioUnderruns: 0 }),
+    "Audio 44.1kHz 5.1 · underruns 0",
+  );
+});
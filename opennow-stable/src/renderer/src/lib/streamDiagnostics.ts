@@ -1,5 +1,6 @@
 import type { NativeStreamStats } from "@shared/gfn";
 
+import { DEFAULT_VIDEO_PACKET_SIZE, videoPacketsMayFragment } from "../gfn/sdp";
 import type { StreamDiagnostics } from "../gfn/webrtcClient";
 
 export function defaultDiagnostics(): StreamDiagnostics {
@@ -53,9 +54,32 @@ export function defaultDiagnostics(): StreamDiagnostics {
     nativeFramesPendingToPresent: undefined,
     nativePartialFlushCount: undefined,
     nativeCompleteFlushCount: undefined,
+    nativeFramesSkippedAhead: undefined,
     nativeTransitionSummary: undefined,
     nativeRequestedStreamingFeaturesSummary: undefined,
     nativeFinalizedStreamingFeaturesSummary: undefined,
+    nativePipelineStageSummary: undefined,
+    nativeHdrMetadata: undefined,
+    nativeEncoderTimecode: undefined,
+    nativeEncoderLatencyMs: undefined,
+    avSyncOffsetMs: undefined,
+    avSyncAudioCorrectionMs: undefined,
+    audioOutputLatencyMs: undefined,
+    audioUnderruns: undefined,
+    audioCodec: undefined,
+    audioSampleRate: undefined,
+    audioChannels: undefined,
+    nativeMemoryBudgetMb: undefined,
+    nativeThreadPrioritySummary: undefined,
+    candidatePairs: undefined,
+    keyframesReceived: undefined,
+    keyframeIntervalMs: undefined,
+    keyframeRequests: undefined,
+    damagedFrames: undefined,
+    decoderErrors: undefined,
+    clickToPhotonSummary: undefined,
+    pathMtu: undefined,
+    videoPacketSize: undefined,
     micState: "uninitialized",
     micEnabled: false,
   };
@@ -89,9 +113,15 @@ export function mergeNativeStreamStats(
     targetBitrateKbps: stats.targetBitrateKbps,
     decodeFps: Math.round(stats.decodedFps),
     renderFps: Math.round(stats.renderFps),
+    decodeTimeMs: stats.decodeTimeMs ?? current.decodeTimeMs,
+    renderTimeMs: stats.renderTimeMs ?? current.renderTimeMs,
     framesReceived: stats.framesDecoded,
     framesDecoded: stats.framesDecoded,
     framesDropped: sinkDropped,
+    packetsReceived: stats.packetsReceived ?? current.packetsReceived,
+    packetsLost: stats.packetsLost ?? current.packetsLost,
+    jitterMs: typeof stats.jitterMs === "number" ? Math.round(stats.jitterMs * 10) / 10 : current.jitterMs,
+    jitterBufferDelayMs: stats.jitterBufferTargetMs ?? current.jitterBufferDelayMs,
     packetLossPercent: dropPercent,
     lagReason: dropPercent > 1 ? "render" : "stable",
     lagReasonDetail: stats.lastTransitionSummary
@@ -104,8 +134,136 @@ export function mergeNativeStreamStats(
     nativeFramesPendingToPresent: stats.framesPendingToPresent,
     nativePartialFlushCount: stats.partialFlushCount,
     nativeCompleteFlushCount: stats.completeFlushCount,
+    nativeFramesSkippedAhead: stats.framesSkippedAhead,
     nativeTransitionSummary: stats.lastTransitionSummary,
     nativeRequestedStreamingFeaturesSummary: stats.requestedStreamingFeaturesSummary,
     nativeFinalizedStreamingFeaturesSummary: stats.finalizedStreamingFeaturesSummary,
+    nativePipelineStageSummary: stats.pipelineStageSummary ?? current.nativePipelineStageSummary,
+    nativeHdrMetadata: stats.hdrMetadata,
+    nativeEncoderTimecode: stats.encoderTimecode,
+    nativeEncoderLatencyMs: stats.encoderLatencyMs,
+    avSyncOffsetMs: stats.avSyncOffsetMs,
+    avSyncAudioCorrectionMs: stats.avSyncAudioCorrectionMs,
+    audioOutputLatencyMs: stats.audioOutputLatencyMs ?? current.audioOutputLatencyMs,
+    audioUnderruns: stats.audioUnderruns ?? current.audioUnderruns,
+    audioCodec: stats.audioCodec ?? current.audioCodec,
+    audioSampleRate: stats.audioSampleRate ?? current.audioSampleRate,
+    audioChannels: stats.audioChannels ?? current.audioChannels,
+    nativeMemoryBudgetMb: stats.memoryBudgetMb ?? current.nativeMemoryBudgetMb,
+    nativeThreadPrioritySummary: stats.threadPrioritySummary ?? current.nativeThreadPrioritySummary,
+    keyframesReceived: stats.keyframesReceived ?? current.keyframesReceived,
+    keyframeIntervalMs: stats.keyframeIntervalMs ?? current.keyframeIntervalMs,
+    damagedFrames: stats.damagedFrames ?? current.damagedFrames,
+    decoderErrors: stats.decoderErrors ?? current.decoderErrors,
   };
 }
+
+export interface AudioPlayoutSample {
+  /** Cumulative seconds each played sample spent waiting for output, summed. */
+  totalPlayoutDelay: number;
+  totalSamplesCount: number;
+}
+
+/** Mean output delay of the samples played since `previous`, in milliseconds. */
+export function averagePlayoutDelayMs(
+  previous: AudioPlayoutSample | null,
+  current: AudioPlayoutSample,
+): number | undefined {
+  const delaySeconds = current.totalPlayoutDelay - (previous?.totalPlayoutDelay ?? 0);
+  const samples = current.totalSamplesCount - (previous?.totalSamplesCount ?? 0);
+  if (!(samples > 0) || !(delaySeconds >= 0)) {
+    return undefined;
+  }
+  return Math.round((delaySeconds / samples) * 1000 * 10) / 10;
+}
+
+/** Offsets beyond this mean one track stopped reporting, not a lip-sync error. */
+const MAX_PLAUSIBLE_AV_OFFSET_MS = 5_000;
+
+/**
+ * Lip-sync error from the receivers' `estimatedPlayoutTimestamp`, the sender
+ * NTP capture time of the media currently playing on each track. Returns how
+ * far audio trails video in milliseconds (negative when audio leads).
+ */
+export function estimateAvSyncOffsetMs(audioPlayout: unknown, videoPlayout: unknown): number | undefined {
+  const audioMs = Number(audioPlayout);
+  const videoMs = Number(videoPlayout);
+  if (!Number.isFinite(audioMs) || !Number.isFinite(videoMs) || audioMs <= 0 || videoMs <= 0) {
+    return undefined;
+  }
+  const offsetMs = videoMs - audioMs;
+  if (Math.abs(offsetMs) > MAX_PLAUSIBLE_AV_OFFSET_MS) {
+    return undefined;
+  }
+  return Math.round(offsetMs * 10) / 10;
+}
+
+/** Expanded-HUD line for compressed-stream health, or null before any keyframe count arrives. */
+export function formatBitstreamHealth(
+  stats: Pick<StreamDiagnostics, "keyframesReceived" | "keyframeIntervalMs" | "keyframeRequests" | "damagedFrames" | "decoderErrors">,
+): string | null {
+  if (typeof stats.keyframesReceived !== "number") {
+    return null;
+  }
+  const interval = typeof stats.keyframeIntervalMs === "number"
+    ? ` every ${(stats.keyframeIntervalMs / 1000).toFixed(1)}s`
+    : "";
+  const parts = [`Bitstream keyframes ${stats.keyframesReceived}${interval}`];
+  if (typeof stats.keyframeRequests === "number") {
+    parts.push(`requested ${stats.keyframeRequests}`);
+  }
+  if (typeof stats.damagedFrames === "number") {
+    parts.push(`damaged ${stats.damagedFrames}`);
+  }
+  if (typeof stats.decoderErrors === "number") {
+    parts.push(`decoder errors ${stats.decoderErrors}`);
+  }
+  return parts.join(" · ");
+}
+
+export function formatPathMtu(stats: Pick<StreamDiagnostics, "pathMtu" | "videoPacketSize">): string | null {
+  if (typeof stats.pathMtu !== "number" || typeof stats.videoPacketSize !== "number") {
+    return null;
+  }
+  const parts = [`Path MTU ${stats.pathMtu}`, `video packets ${stats.videoPacketSize}B`];
+  if (videoPacketsMayFragment(stats.pathMtu, stats.videoPacketSize)) {
+    parts.push("may fragment");
+  } else if (stats.videoPacketSize < DEFAULT_VIDEO_PACKET_SIZE) {
+    parts.push(`reduced from ${DEFAULT_VIDEO_PACKET_SIZE}B to avoid fragmentation`);
+  }
+  return parts.join(" · ");
+}
+
+function channelLayoutLabel(channels: number): string {
+  switch (channels) {
+    case 1:
+      return "mono";
+    case 2:
+      return "stereo";
+    case 6:
+      return "5.1";
+    case 8:
+      return "7.1";
+    default:
+      return `${channels}ch`;
+  }
+}
+
+/** Expanded-HUD audio line, or null while no audio stats have arrived. */
+export function formatAudioStats(
+  stats: Pick<StreamDiagnostics, "audioCodec" | "audioSampleRate" | "audioChannels" | "audioOutputLatencyMs" | "audioUnderruns">,
+): string | null {
+  const format = [
+    stats.audioCodec,
+    typeof stats.audioSampleRate === "number" ? `${stats.audioSampleRate / 1000}kHz` : undefined,
+    typeof stats.audioChannels === "number" ? channelLayoutLabel(stats.audioChannels) : undefined,
+  ].filter((part): part is string => Boolean(part));
+  const parts = format.length > 0 ? [format.join(" ")] : [];
+  if (typeof stats.audioOutputLatencyMs === "number") {
+    parts.push(`playout ${stats.audioOutputLatencyMs.toFixed(1)}ms`);
+  }
+  if (typeof stats.audioUnderruns === "number") {
+    parts.push(`underruns ${stats.audioUnderruns}`);
+  }
+  return parts.length > 0 ? `Audio ${parts.join(" · ")}` : null;
+}
@@ -1,9 +1,9 @@
 import type { VideoCodec } from "@shared/gfn";
-import { USER_FACING_VIDEO_CODEC_OPTIONS } from "@shared/gfn";
+import { STREAM_FPS_OPTIONS, USER_FACING_VIDEO_CODEC_OPTIONS } from "@shared/gfn";
 
 export const codecOptions: VideoCodec[] = [...USER_FACING_VIDEO_CODEC_OPTIONS];
-export const allResolutionOptions = ["1280x720", "1280x800", "1440x900", "1680x1050", "1920x1080", "1920x1200", "2560x1080", "2560x1440", "2560x1600", "3440x1440", "3840x2160", "3840x2400"];
-export const fpsOptions = [30, 60, 120, 144, 165, 240];
+export const allResolutionOptions = ["1280x720", "1280x800", "1440x900", "1680x1050", "1920x1080", "1920x1200", "2560x1080", "2560x1440", "2560x1600", "3440x1440", "3840x2160", "3840x2400", "5120x1440"];
+export const fpsOptions = [...STREAM_FPS_OPTIONS];
 export const aspectRatioOptions = ["16:9", "16:10", "21:9", "32:9"] as const;
 
 const RESOLUTION_TO_ASPECT_RATIO: Record<string, string> = {
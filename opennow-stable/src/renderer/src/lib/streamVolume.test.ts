@@ -0,0 +1,24 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { getEffectiveStreamVolume, stepStreamVolume } from "./streamVolume";
+
+test("volume hotkeys step on a five percent grid within 0-100", () => {
+  assert.equal(stepStreamVolume(50, 1), 55);
+  assert.equal(stepStreamVolume(50, -1), 45);
+  assert.equal(stepStreamVolume(47, 1), 50);
+  assert.equal(stepStreamVolume(47, -1), 45);
+  assert.equal(stepStreamVolume(98, 1), 100);
+  assert.equal(stepStreamVolume(3, -1), 0);
+  assert.equal(stepStreamVolume(0, -1), 0);
+});
+
+test("mute wins over volume and ducking scales the chosen level", () => {
+  assert.equal(getEffectiveStreamVolume(80, false, false), 0.8);
+  assert.equal(getEffectiveStreamVolume(80, true, false), 0);
+  assert.equal(getEffectiveStreamVolume(80, true, true), 0);
+  assert.ok(Math.abs(getEffectiveStreamVolume(50, false, true) - 0.15) < 1e-9);
+  assert.equal(getEffectiveStreamVolume(150, false, false), 1);
+});
@@ -0,0 +1,29 @@
+export const STREAM_VOLUME_STEP = 5;
+/** Share of the chosen volume kept while an in-stream notice is ducking audio. */
+export const NOTIFICATION_DUCK_FACTOR = 0.3;
+export const NOTIFICATION_DUCK_MS = 4000;
+
+export function clampStreamVolume(volume: number): number {
+  if (!Number.isFinite(volume)) {
+    return 100;
+  }
+  return Math.max(0, Math.min(100, Math.round(volume)));
+}
+
+/** Moves the volume one hotkey step, snapping odd values onto the step grid. */
+export function stepStreamVolume(volume: number, direction: 1 | -1, step: number = STREAM_VOLUME_STEP): number {
+  const current = clampStreamVolume(volume);
+  const snapped = direction > 0
+    ? Math.floor(current / step) * step + step
+    : Math.ceil(current / step) * step - step;
+  return clampStreamVolume(snapped);
+}
+
+/** Linear gain (0-1) handed to the audio render path. */
+export function getEffectiveStreamVolume(volume: number, muted: boolean, ducked: boolean): number {
+  if (muted) {
+    return 0;
+  }
+  const gain = clampStreamVolume(volume) / 100;
+  return ducked ? gain * NOTIFICATION_DUCK_FACTOR : gain;
+}
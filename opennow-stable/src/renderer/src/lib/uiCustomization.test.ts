@@ -0,0 +1,26 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { getAccentColorOption, getAccentColorOptions, resolveAppTheme } from "./uiCustomization";
+
+test("system theme follows the OS color scheme", () => {
+  assert.equal(resolveAppTheme("system", true), "dark");
+  assert.equal(resolveAppTheme("system", false), "light");
+  assert.equal(resolveAppTheme("light", true), "light");
+  assert.equal(resolveAppTheme("dark", false), "dark");
+});
+
+test("custom accent is offered only when the theme file provides one", () => {
+  assert.equal(getAccentColorOptions().some((option) => option.value === "custom"), false);
+  const withCustom = getAccentColorOptions("#2dd4bf");
+  assert.deepEqual(withCustom[withCustom.length - 1], {
+    value: "custom",
+    labelKey: "settings.interface.accentColorCustom",
+    hex: "#2dd4bf",
+  });
+  assert.equal(getAccentColorOption("custom", "#2dd4bf").hex, "#2dd4bf");
+  // A stale "custom" selection falls back to the default preset.
+  assert.equal(getAccentColorOption("custom").value, "green");
+});
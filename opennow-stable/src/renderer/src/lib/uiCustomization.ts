@@ -1,4 +1,6 @@
-import type { AppAccentColor } from "@shared/gfn";
+import type { AppAccentColor, AppTheme } from "@shared/gfn";
+
+export type ResolvedAppTheme = Exclude<AppTheme, "system">;
 
 export interface AccentColorOption {
   value: AppAccentColor;
@@ -78,17 +80,49 @@ function getContrastColor({ r, g, b }: RgbColor): string {
   return lightContrast > darkContrast ? "#f8fafc" : "#111318";
 }
 
-export function getAccentColorOptions(): readonly AccentColorOption[] {
-  return ACCENT_COLOR_OPTIONS;
+function customAccentOption(customAccentHex: string): AccentColorOption {
+  return {
+    value: "custom",
+    labelKey: "settings.interface.accentColorCustom",
+    hex: customAccentHex as `#${string}`,
+  };
+}
+
+/** Built-in presets, plus the theme file's accent when one is set. */
+export function getAccentColorOptions(customAccentHex: string | null = null): readonly AccentColorOption[] {
+  return customAccentHex ? [...ACCENT_COLOR_OPTIONS, customAccentOption(customAccentHex)] : ACCENT_COLOR_OPTIONS;
 }
 
-export function getAccentColorOption(accentColor: AppAccentColor): AccentColorOption {
+export function getAccentColorOption(
+  accentColor: AppAccentColor,
+  customAccentHex: string | null = null,
+): AccentColorOption {
+  if (accentColor === "custom" && customAccentHex) {
+    return customAccentOption(customAccentHex);
+  }
   return ACCENT_COLOR_MAP.get(accentColor) ?? ACCENT_COLOR_OPTIONS[0];
 }
 
-export function applyAccentColor(accentColor: AppAccentColor, root: HTMLElement | null = null): void {
+export function resolveAppTheme(theme: AppTheme, prefersDark: boolean): ResolvedAppTheme {
+  if (theme === "system") {
+    return prefersDark ? "dark" : "light";
+  }
+  return theme;
+}
+
+/** Switches the design tokens; styles.css keys the light palette off `data-theme`. */
+export function applyAppTheme(theme: ResolvedAppTheme, root: HTMLElement | null = null): void {
+  const target = root ?? document.documentElement;
+  target.dataset.theme = theme;
+}
+
+export function applyAccentColor(
+  accentColor: AppAccentColor,
+  customAccentHex: string | null = null,
+  root: HTMLElement | null = null,
+): void {
   const target = root ?? document.documentElement;
-  const option = getAccentColorOption(accentColor);
+  const option = getAccentColorOption(accentColor, customAccentHex);
   const baseRgb = hexToRgb(option.hex);
   const hoverRgb = mixColors(baseRgb, { r: 255, g: 255, b: 255 }, 0.12);
   const pressRgb = mixColors(baseRgb, { r: 0, g: 0, b: 0 }, 0.12);
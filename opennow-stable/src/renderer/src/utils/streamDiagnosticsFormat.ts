@@ -18,6 +18,21 @@ export function getTimingColor(valueMs: number, goodMax: number, warningMax: num
   return "var(--error)";
 }
 
+/** Local CPU/GPU load; sustained 90%+ leaves no headroom for decode and present. */
+export function getUsageColor(percent: number | null): string {
+  if (percent === null) return "var(--ink-muted)";
+  if (percent < 75) return "var(--success)";
+  if (percent < 90) return "var(--warning)";
+  return "var(--error)";
+}
+
+/** Most laptop and handheld chips start throttling in the high 80s to 90s °C. */
+export function getTemperatureColor(celsius: number): string {
+  if (celsius < 75) return "var(--success)";
+  if (celsius < 88) return "var(--warning)";
+  return "var(--error)";
+}
+
 export function getInputQueueColor(bufferedBytes: number, dropCount: number): string {
   if (dropCount > 0 || bufferedBytes >= 65536) return "var(--error)";
   if (bufferedBytes >= 32768) return "var(--warning)";
@@ -0,0 +1,34 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import {
+  TIER_STREAM_PRESETS,
+  classifyMembershipTier,
+  formatTierPresetLabel,
+  getTierPresetBlock,
+} from "./streamQualityPresets";
+
+const [fullHd60, qhd60, , , uhd120] = TIER_STREAM_PRESETS;
+
+test("membership tiers collapse to free, priority and ultimate", () => {
+  assert.equal(classifyMembershipTier(" ultimate "), "ultimate");
+  assert.equal(classifyMembershipTier("PERFORMANCE"), "priority");
+  assert.equal(classifyMembershipTier("PRIORITY"), "priority");
+  assert.equal(classifyMembershipTier("FREE"), "free");
+  assert.equal(classifyMembershipTier(""), null);
+});
+
+test("presets above the membership are blocked by tier before entitlements are checked", () => {
+  const freeEntitlements = [{ width: 1920, height: 1080, fps: 60 }];
+  assert.equal(getTierPresetBlock(fullHd60!, "free", freeEntitlements), null);
+  assert.equal(getTierPresetBlock(uhd120!, "free", freeEntitlements), "tier");
+  assert.equal(getTierPresetBlock(qhd60!, "priority", freeEntitlements), "entitlement");
+  assert.equal(getTierPresetBlock(uhd120!, null, [{ width: 3840, height: 2160, fps: 120 }]), null);
+});
+
+test("preset labels read like the store's mode names", () => {
+  assert.equal(formatTierPresetLabel(fullHd60!), "1080p60");
+  assert.equal(formatTierPresetLabel(uhd120!), "4K120 HDR");
+});
@@ -1,3 +1,8 @@
+import type { ColorQuality, EntitledResolution } from "@shared/gfn";
+import { resolveEntitledStreamProfile } from "@shared/gfn";
+
+import { normalizeMembershipTier } from "../lib/queueAds";
+
 export type StreamQualityPresetId = "performance" | "balanced" | "quality";
 
 export interface StreamPresetPick {
@@ -55,3 +60,62 @@ export function pickStreamPreset(
   }
   return { resolution: midRes, fps: midFps, maxBitrateMbps: midBitrate };
 }
+
+export type MembershipTierClass = "free" | "priority" | "ultimate";
+
+const MEMBERSHIP_TIER_RANK: Record<MembershipTierClass, number> = { free: 0, priority: 1, ultimate: 2 };
+
+/** Performance is the former Priority tier; account data uses both names. */
+export function classifyMembershipTier(tier: string | null | undefined): MembershipTierClass | null {
+  const normalized = normalizeMembershipTier(tier);
+  if (!normalized) return null;
+  if (normalized === "ULTIMATE") return "ultimate";
+  if (normalized === "PRIORITY" || normalized === "PERFORMANCE") return "priority";
+  return "free";
+}
+
+export interface TierStreamPreset {
+  resolution: string;
+  fps: number;
+  maxBitrateMbps: number;
+  colorQuality: ColorQuality;
+  /** Lowest membership that streams this mode */
+  minTier: MembershipTierClass;
+}
+
+/** The headline mode of each membership, plus the Ultimate esports and HDR modes. */
+export const TIER_STREAM_PRESETS: readonly TierStreamPreset[] = [
+  { resolution: "1920x1080", fps: 60, maxBitrateMbps: 50, colorQuality: "8bit_420", minTier: "free" },
+  { resolution: "2560x1440", fps: 60, maxBitrateMbps: 75, colorQuality: "8bit_420", minTier: "priority" },
+  { resolution: "1920x1080", fps: 240, maxBitrateMbps: 75, colorQuality: "8bit_420", minTier: "ultimate" },
+  { resolution: "2560x1440", fps: 120, maxBitrateMbps: 100, colorQuality: "10bit_420", minTier: "ultimate" },
+  { resolution: "3840x2160", fps: 120, maxBitrateMbps: 100, colorQuality: "10bit_420", minTier: "ultimate" },
+];
+
+export function formatTierPresetLabel(preset: TierStreamPreset): string {
+  const height = Number(preset.resolution.split("x")[1]);
+  const size = height === 2160 ? "4K" : `${height}p`;
+  return `${size}${preset.fps}${preset.colorQuality.startsWith("10bit") ? " HDR" : ""}`;
+}
+
+/**
+ * Why a preset cannot be picked: the membership is below the preset's tier,
+ * or the account's entitlements (which also reflect the region and display)
+ * do not list the mode. An unknown tier leaves the decision to entitlements.
+ */
+export function getTierPresetBlock(
+  preset: TierStreamPreset,
+  tier: MembershipTierClass | null,
+  entitledResolutions: readonly EntitledResolution[],
+): "tier" | "entitlement" | null {
+  if (tier !== null && MEMBERSHIP_TIER_RANK[tier] < MEMBERSHIP_TIER_RANK[preset.minTier]) {
+    return "tier";
+  }
+  if (entitledResolutions.length > 0) {
+    const resolved = resolveEntitledStreamProfile(entitledResolutions, { resolution: preset.resolution, fps: preset.fps });
+    if (!resolved || resolved.resolution !== preset.resolution || resolved.fps !== preset.fps) {
+      return "entitlement";
+    }
+  }
+  return null;
+}
@@ -5,6 +5,7 @@ import assert from "node:assert/strict";
 
 import {
   getSafeFallbackEntitledResolutions,
+  normalizeCustomResolution,
   resolveEntitledStreamProfile,
   SAFE_FALLBACK_STREAM_PROFILE,
 } from "./gfn";
@@ -35,3 +36,35 @@ test("safe fallback entitlements resolve oversized requests to 1080p60", () => {
     SAFE_FALLBACK_STREAM_PROFILE,
   );
 });
+
+test("keeps ultrawide requests that fit inside a larger entitled mode", () => {
+  const entitlements = [
+    { width: 3840, height: 2160, fps: 60 },
+    { width: 2560, height: 1440, fps: 120 },
+    { width: 1920, height: 1080, fps: 240 },
+  ];
+
+  assert.deepEqual(
+    resolveEntitledStreamProfile(entitlements, { resolution: "3440x1440", fps: 120 }),
+    { resolution: "3440x1440", fps: 60 },
+  );
+  assert.deepEqual(
+    resolveEntitledStreamProfile(entitlements, { resolution: "2560x1080", fps: 120 }),
+    { resolution: "2560x1080", fps: 120 },
+  );
+  assert.deepEqual(
+    resolveEntitledStreamProfile(entitlements, { resolution: "5120x1440", fps: 60 }),
+    { resolution: "5120x1440", fps: 60 },
+  );
+  assert.deepEqual(
+    resolveEntitledStreamProfile(entitlements, { resolution: "7680x2160", fps: 60 }),
+    { resolution: "3840x2160", fps: 60 },
+  );
+});
+
+test("custom resolutions are rounded to even sizes and bounded", () => {
+  assert.equal(normalizeCustomResolution(3441, 1439), "3440x1438");
+  assert.equal(normalizeCustomResolution(320, 240), null);
+  assert.equal(normalizeCustomResolution(10000, 1440), null);
+  assert.equal(normalizeCustomResolution(Number.NaN, 1080), null);
+});
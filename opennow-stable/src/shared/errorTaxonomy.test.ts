@@ -0,0 +1,40 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { classifyGfnError, classifyStatusDescription, isGfnErrorCategory } from "./errorTaxonomy";
+import { REAUTH_ERROR_CODES, SESSION_CONFLICT_ERROR_CODES } from "./gfnErrorCodes";
+
+test("known CloudMatch codes map to their category", () => {
+  assert.equal(classifyGfnError(3237093695), "region-unsupported");
+  assert.equal(classifyGfnError(3237093694), "capacity");
+  assert.equal(classifyGfnError(3237093650), "entitlement");
+  assert.equal(classifyGfnError(3237093643), "session-limit");
+  assert.equal(classifyGfnError(3237101584), "network");
+});
+
+test("unknown codes fall back to the status description", () => {
+  assert.equal(classifyGfnError(3237093632, "REGION_NOT_SUPPORTED"), "region-unsupported");
+  assert.equal(classifyGfnError(undefined, "queue full"), "capacity");
+  assert.equal(classifyGfnError(3237093632, "SOMETHING_ELSE"), "unknown");
+  assert.equal(classifyGfnError(undefined), "unknown");
+});
+
+test("category guard only accepts known categories", () => {
+  assert.equal(isGfnErrorCategory("maintenance"), true);
+  assert.equal(isGfnErrorCategory("unknown"), true);
+  assert.equal(isGfnErrorCategory("teapot"), false);
+  assert.equal(isGfnErrorCategory(42), false);
+});
+
+test("session error helpers and the taxonomy agree on reauth and conflict codes", () => {
+  for (const code of REAUTH_ERROR_CODES) {
+    assert.equal(classifyGfnError(code), "auth");
+  }
+  for (const code of SESSION_CONFLICT_ERROR_CODES) {
+    assert.equal(classifyGfnError(code), "session-limit");
+  }
+  assert.equal(classifyStatusDescription("ZONE_MAINTENANCE"), "maintenance");
+  assert.equal(classifyStatusDescription("teapot"), null);
+});
@@ -0,0 +1,151 @@
+import { GfnErrorCode, REAUTH_ERROR_CODES, SESSION_CONFLICT_ERROR_CODES } from "./gfnErrorCodes";
+
+/**
+ * Coarse error categories for CloudMatch failures.
+ *
+ * Raw service codes are too granular to act on; the UI picks its copy, remediation
+ * steps and help link from the category instead.
+ */
+export type GfnErrorCategory =
+  | "region-unsupported"
+  | "capacity"
+  | "entitlement"
+  | "membership"
+  | "session-limit"
+  | "auth"
+  | "account-action"
+  | "account-link"
+  | "maintenance"
+  | "game-unavailable"
+  | "network"
+  | "unknown";
+
+export const GFN_STATUS_PAGE_URL = "https://status.geforcenow.com/";
+export const GFN_MEMBERSHIPS_URL = "https://www.nvidia.com/en-us/geforce-now/memberships/";
+
+const CATEGORY_CODES: ReadonlyArray<readonly [GfnErrorCategory, readonly number[]]> = [
+  [
+    "region-unsupported",
+    [
+      GfnErrorCode.RegionNotSupportedForRegistration,
+      GfnErrorCode.RegionNotSupportedIndefinitely,
+      GfnErrorCode.RegionBanned,
+      GfnErrorCode.RegionOnHoldForFree,
+      GfnErrorCode.RegionOnHoldForPaid,
+      GfnErrorCode.RegionNotSupportedForStreaming,
+      GfnErrorCode.RegionNotSupportedAnonymousAccess,
+    ],
+  ],
+  [
+    "capacity",
+    [
+      GfnErrorCode.ForwardingZoneOutOfCapacity,
+      GfnErrorCode.InsufficientVmCapacity,
+      GfnErrorCode.InsufficientRouteCapacity,
+      GfnErrorCode.InsufficientScratchSpaceCapacity,
+      GfnErrorCode.ServerSessionQueueLengthExceeded,
+      GfnErrorCode.GuestModePartnerCapacityDisabled,
+      GfnErrorCode.SessionRejectedNoCapacity,
+    ],
+  ],
+  [
+    "entitlement",
+    [
+      GfnErrorCode.EntitlementFailure,
+      GfnErrorCode.NotEnoughCredits,
+      GfnErrorCode.EntitlementFailureForResource,
+      GfnErrorCode.StreamingNotAllowedInLimitedMode,
+    ],
+  ],
+  ["membership", [GfnErrorCode.SessionInsufficientPlayabilityLevel]],
+  ["session-limit", [GfnErrorCode.SessionListLimitExceeded, ...SESSION_CONFLICT_ERROR_CODES]],
+  ["auth", REAUTH_ERROR_CODES],
+  ["account-action", [GfnErrorCode.EulaUnAccepted, GfnErrorCode.EmailNotVerified]],
+  [
+    "account-link",
+    [
+      GfnErrorCode.SteamGuardRequired,
+      GfnErrorCode.SteamLoginRequired,
+      GfnErrorCode.SteamGuardInvalid,
+      GfnErrorCode.SteamProfilePrivate,
+    ],
+  ],
+  [
+    "maintenance",
+    [
+      GfnErrorCode.MaintenanceStatus,
+      GfnErrorCode.ServiceUnAvailable,
+      GfnErrorCode.AppMaintenanceStatus,
+      GfnErrorCode.SessionRemovedFromQueueMaintenance,
+      GfnErrorCode.ZoneMaintenanceStatus,
+    ],
+  ],
+  [
+    "game-unavailable",
+    [
+      GfnErrorCode.InvalidAppIdNotAvailable,
+      GfnErrorCode.InvalidAppIdNotFound,
+      GfnErrorCode.AppPatching,
+      GfnErrorCode.GameNotFound,
+      GfnErrorCode.SessionForwardGameBinariesNotAvailable,
+      GfnErrorCode.GameBinariesNotAvailableInRegion,
+    ],
+  ],
+  [
+    "network",
+    [
+      GfnErrorCode.NetworkError,
+      GfnErrorCode.NoInternetDuringSessionSetup,
+      GfnErrorCode.SocketError,
+      GfnErrorCode.AddressResolveFailed,
+      GfnErrorCode.ConnectFailed,
+      GfnErrorCode.SslError,
+      GfnErrorCode.ConnectionTimeout,
+      GfnErrorCode.DataReceiveTimeout,
+      GfnErrorCode.PeerNoResponse,
+      GfnErrorCode.DataSendFailure,
+      GfnErrorCode.DataReceiveFailure,
+      GfnErrorCode.NetworkErrorUnknown,
+    ],
+  ],
+];
+
+const CODE_TO_CATEGORY = new Map<number, GfnErrorCategory>(
+  CATEGORY_CODES.flatMap(([category, codes]) => codes.map((code) => [code, category] as const)),
+);
+
+/** Checked in order when the code alone is unknown; SessionError picks its fallback message from the same match. */
+const DESCRIPTION_PATTERNS: ReadonlyArray<readonly [string, GfnErrorCategory]> = [
+  ["INSUFFICIENT_PLAYABILITY", "membership"],
+  ["SESSION_LIMIT", "session-limit"],
+  ["REGION", "region-unsupported"],
+  ["MAINTENANCE", "maintenance"],
+  ["CAPACITY", "capacity"],
+  ["QUEUE", "capacity"],
+  ["ENTITLEMENT", "entitlement"],
+  ["AUTH", "auth"],
+  ["TOKEN", "auth"],
+];
+
+/** Category implied by a CloudMatch statusDescription, or null when nothing matches. */
+export function classifyStatusDescription(statusDescription: string | undefined): GfnErrorCategory | null {
+  const description = statusDescription?.toUpperCase() ?? "";
+  if (description) {
+    for (const [pattern, category] of DESCRIPTION_PATTERNS) {
+      if (description.includes(pattern)) {
+        return category;
+      }
+    }
+  }
+  return null;
+}
+
+export function classifyGfnError(code: number | undefined, statusDescription?: string): GfnErrorCategory {
+  const byCode = code === undefined ? undefined : CODE_TO_CATEGORY.get(code);
+  return byCode ?? classifyStatusDescription(statusDescription) ?? "unknown";
+}
+
+export function isGfnErrorCategory(value: unknown): value is GfnErrorCategory {
+  return typeof value === "string"
+    && (value === "unknown" || CATEGORY_CODES.some(([category]) => category === value));
+}
@@ -0,0 +1,55 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import type { StreamSettings } from "./gfn";
+import { applyGameStreamOverride, normalizeGameStreamOverrides } from "./gameStreamOverrides";
+
+const base = {
+  resolution: "1920x1080",
+  fps: 60,
+  maxBitrateMbps: 50,
+  codec: "H265",
+  colorQuality: "8bit_444",
+  keyboardLayout: "en-US",
+  gameLanguage: "en_US",
+  enableL4S: false,
+  enableCloudGsync: false,
+} as StreamSettings;
+
+test("unset override fields keep the global settings", () => {
+  assert.equal(applyGameStreamOverride(base, undefined), base);
+  assert.deepEqual(applyGameStreamOverride(base, { title: "Game", fps: 120 }), { ...base, fps: 120 });
+});
+
+test("hdr swaps the bit depth and keeps chroma", () => {
+  const hdr = applyGameStreamOverride(base, { title: "Game", hdr: true, resolution: "2560x1440" });
+  assert.equal(hdr.colorQuality, "10bit_444");
+  assert.equal(hdr.resolution, "2560x1440");
+
+  const sdr = applyGameStreamOverride({ ...base, colorQuality: "10bit_420" }, { title: "Game", hdr: false });
+  assert.equal(sdr.colorQuality, "8bit_420");
+});
+
+test("an H264 override drops to 8-bit 4:2:0", () => {
+  const result = applyGameStreamOverride(base, { title: "Game", codec: "H264", hdr: true });
+  assert.equal(result.codec, "H264");
+  assert.equal(result.colorQuality, "8bit_420");
+});
+
+test("normalize drops malformed entries and fields", () => {
+  assert.deepEqual(normalizeGameStreamOverrides([]), {});
+  assert.deepEqual(
+    normalizeGameStreamOverrides({
+      "100": { title: "Kept", fps: 119.6, codec: "VP9", resolution: "wide" },
+      "200": { title: "Empty", codec: "VP9" },
+      "300": "junk",
+      "400": { hdr: false },
+    }),
+    {
+      "100": { title: "Kept", fps: 120 },
+      "400": { title: "400", hdr: false },
+    },
+  );
+});
@@ -0,0 +1,84 @@
+import { isSupportedUserFacingCodec, normalizeStreamPreferences } from "./gfn";
+import type { ColorQuality, GameStreamOverride, StreamProfileOverride, StreamSettings, VideoCodec } from "./gfn";
+
+function withBitDepth(colorQuality: ColorQuality, hdr: boolean): ColorQuality {
+  const chroma = colorQuality.endsWith("444") ? "444" : "420";
+  return `${hdr ? "10bit" : "8bit"}_${chroma}`;
+}
+
+/**
+ * Layers a game's saved overrides (or the battery profile) over the global stream
+ * settings for a new session. Fields the override leaves unset keep the global
+ * value; HDR only swaps the bit depth.
+ */
+export function applyGameStreamOverride(
+  settings: StreamSettings,
+  override: StreamProfileOverride | undefined,
+): StreamSettings {
+  if (!override) {
+    return settings;
+  }
+  const codec = override.codec ?? settings.codec;
+  const requestedColorQuality = override.hdr === undefined
+    ? settings.colorQuality
+    : withBitDepth(settings.colorQuality, override.hdr);
+  const preferences = normalizeStreamPreferences(codec, requestedColorQuality);
+  return {
+    ...settings,
+    resolution: override.resolution ?? settings.resolution,
+    fps: override.fps ?? settings.fps,
+    codec: preferences.codec,
+    colorQuality: preferences.colorQuality,
+  };
+}
+
+export function hasGameStreamOverrideValues(override: StreamProfileOverride): boolean {
+  return override.resolution !== undefined
+    || override.fps !== undefined
+    || override.hdr !== undefined
+    || override.codec !== undefined;
+}
+
+/** Drops malformed entries and fields from a hand-edited settings file. */
+export function normalizeGameStreamOverrides(value: unknown): Record<string, GameStreamOverride> {
+  if (!value || typeof value !== "object" || Array.isArray(value)) {
+    return {};
+  }
+  const overrides: Record<string, GameStreamOverride> = {};
+  for (const [gameId, raw] of Object.entries(value as Record<string, unknown>)) {
+    if (!raw || typeof raw !== "object") {
+      continue;
+    }
+    const title = (raw as { title?: unknown }).title;
+    const override: GameStreamOverride = {
+      title: typeof title === "string" ? title : gameId,
+      ...normalizeStreamProfileOverride(raw),
+    };
+    if (hasGameStreamOverrideValues(override)) {
+      overrides[gameId] = override;
+    }
+  }
+  return overrides;
+}
+
+/** Keeps only the well-formed fields of one override. */
+export function normalizeStreamProfileOverride(value: unknown): StreamProfileOverride {
+  if (!value || typeof value !== "object" || Array.isArray(value)) {
+    return {};
+  }
+  const candidate = value as Partial<Record<keyof StreamProfileOverride, unknown>>;
+  const override: StreamProfileOverride = {};
+  if (typeof candidate.resolution === "string" && /^\d+x\d+$/.test(candidate.resolution)) {
+    override.resolution = candidate.resolution;
+  }
+  if (typeof candidate.fps === "number" && Number.isFinite(candidate.fps) && candidate.fps > 0) {
+    override.fps = Math.round(candidate.fps);
+  }
+  if (typeof candidate.hdr === "boolean") {
+    override.hdr = candidate.hdr;
+  }
+  if (typeof candidate.codec === "string" && isSupportedUserFacingCodec(candidate.codec as VideoCodec)) {
+    override.codec = candidate.codec as VideoCodec;
+  }
+  return override;
+}
@@ -15,6 +15,8 @@ import {
   isOwnedVariant,
   NATIVE_STREAMER_WINDOWS_ONLY_MESSAGE,
   getDefaultStreamPreferences,
+  normalizeControllerSlotsEnabled,
+  normalizeStatsPanelFields,
   normalizeStreamPreferences,
   normalizeStreamClientModeForPlatform,
 } from "./gfn";
@@ -150,6 +152,9 @@ test("buildNativeStreamerSessionContext forwards requested/finalized streaming f
       toggleMicrophone: "Ctrl+Shift+M",
       screenshot: "F11",
       toggleRecording: "F12",
+      toggleStreamMute: "Ctrl+Shift+End",
+      volumeUp: "Ctrl+Shift+PageUp",
+      volumeDown: "Ctrl+Shift+PageDown",
     },
   );
 
@@ -211,3 +216,15 @@ test("uses the exact Windows-only unsupported native streamer status message", (
   assert.equal(status.message, NATIVE_STREAMER_WINDOWS_ONLY_MESSAGE);
   assert.equal(status.gstreamerRuntime.message, NATIVE_STREAMER_WINDOWS_ONLY_MESSAGE);
 });
+
+test("normalizes controller slot settings to four enabled-by-default entries", () => {
+  assert.deepEqual(normalizeControllerSlotsEnabled(undefined), [true, true, true, true]);
+  assert.deepEqual(normalizeControllerSlotsEnabled([false, true]), [false, true, true, true]);
+  assert.deepEqual(normalizeControllerSlotsEnabled([true, "no", false, false, false]), [true, true, false, false]);
+});
+
+test("stats panel fields keep the saved order without unknown or repeated entries", () => {
+  assert.deepEqual(normalizeStatsPanelFields(undefined), ["fps", "rtt", "bitrate"]);
+  assert.deepEqual(normalizeStatsPanelFields(["loss", "fps", "loss", "jitter", 3]), ["loss", "fps"]);
+  assert.deepEqual(normalizeStatsPanelFields([]), []);
+});
@@ -6,7 +6,7 @@ export type StreamClientMode = "web" | "native";
 export type NativeStreamerBackend = "stub" | "gstreamer";
 export type NativeStreamerBackendPreference = "auto" | NativeStreamerBackend;
 export type NativeStreamerFeatureMode = "auto" | "disabled" | "forced";
-export type NativeVideoBackendPreference = "auto" | "d3d11" | "d3d12";
+export type NativeVideoBackendPreference = "auto" | "d3d11" | "d3d12" | "nvdec";
 export type NativeQueueMode = "auto" | "fixed" | "adaptive" | "vrr";
 
 export const NATIVE_STREAMER_WINDOWS_ONLY_MESSAGE = "experimental feature: Windows only. Mac and Linux support is being worked on";
@@ -98,6 +98,7 @@ export function colorQualityRequiresHevc(cq: ColorQuality): boolean {
 }
 
 export const USER_FACING_VIDEO_CODEC_OPTIONS: readonly VideoCodec[] = ["H264", "H265", "AV1"];
+export const STREAM_FPS_OPTIONS: readonly number[] = [30, 60, 120, 144, 165, 240];
 export const USER_FACING_COLOR_QUALITY_OPTIONS: readonly ColorQuality[] = ["8bit_420", "8bit_444", "10bit_420", "10bit_444"];
 
 export function isSupportedUserFacingCodec(codec: VideoCodec): boolean {
@@ -129,7 +130,38 @@ export function colorQualityIs10Bit(cq: ColorQuality): boolean {
   return cq.startsWith("10bit");
 }
 
-export type AppAccentColor = "green" | "blue" | "violet" | "amber" | "rose";
+/** "custom" takes its color from the user theme file */
+export type AppAccentColor = "green" | "blue" | "violet" | "amber" | "rose" | "custom";
+export type AppTheme = "dark" | "light" | "system";
+
+export interface UserTheme {
+  /** Location of the user theme file, whether or not it exists yet */
+  path: string;
+  /** Custom accent as #rrggbb, or null when the file is missing or invalid */
+  accent: string | null;
+}
+/** A known driver/compositor workaround that matches this machine */
+export interface DriverWorkaroundStatus {
+  id: string;
+  description: string;
+  /** False when workarounds are off or the user switched this one off */
+  applied: boolean;
+}
+
+export interface DriverWorkaroundReport {
+  /** Where a newer rules file can be placed; reloaded whenever it changes */
+  rulesPath: string;
+  rulesRevision: number;
+  matched: DriverWorkaroundStatus[];
+  /** The applied rules differ from launch, so their Chromium switches wait for a restart */
+  restartRequired: boolean;
+}
+export type FullscreenMode = "borderless" | "exclusive";
+export type IceTransportPolicy = "all" | "relay";
+export type NetworkIpFamily = "auto" | "ipv4" | "ipv6";
+/** "local" keeps the aggregate on disk only; "upload" also sends new sessions to the endpoint. */
+export type TelemetryMode = "off" | "local" | "upload";
+export type AudioOutputMode = "shared" | "exclusive";
 export type MicrophoneMode = "disabled" | "push-to-talk" | "voice-activity";
 export type AspectRatio = "16:9" | "16:10" | "21:9" | "32:9";
 export type RuntimePlatform =
@@ -183,6 +215,8 @@ export interface NativeStreamerStatus {
   activeVideoBackend?: NativeVideoBackendCapability;
   codecSummary?: string;
   zeroCopySummary?: string;
+  /** Experimental flags the streamer picked up, as "name=on|off (source)" */
+  activeFlags?: string[];
   gstreamerRuntime: NativeGstreamerRuntimeStatus;
   message: string;
 }
@@ -208,12 +242,14 @@ export type NativeVideoBackendId =
   | "vaapi"
   | "v4l2"
   | "vulkan"
+  | "nvdec"
   | "software"
   | string;
 
 export interface NativeVideoCodecCapability {
   codec: "h264" | "h265" | "av1" | string;
   available: boolean;
+  profiles?: string[];
   decoder?: string;
   parser?: string;
   depayloader?: string;
@@ -245,11 +281,32 @@ export interface NativeTransitionDiagnostics {
   disableTransitionFlushEscalation?: boolean;
 }
 
+/** Stream fields layered over the global settings; unset fields keep the global value. */
+export interface StreamProfileOverride {
+  resolution?: string;
+  fps?: number;
+  /** true requests 10-bit color, false 8-bit; unset keeps the global color quality */
+  hdr?: boolean;
+  codec?: VideoCodec;
+}
+
+/** Per-game stream preferences layered over the global settings when a session is created. */
+export interface GameStreamOverride extends StreamProfileOverride {
+  /** Game title at the time the override was saved, for display */
+  title: string;
+}
+
 export interface Settings {
   resolution: string;
   aspectRatio: AspectRatio;
   posterSizeScale: number;
   fps: number;
+  /** Decoded-frame cap while on AC power (0 = render every frame) */
+  renderFpsCap: number;
+  /** Decoded-frame cap while on battery (0 = render every frame) */
+  renderFpsCapOnBattery: number;
+  /** Also ask the server for a lower frame rate when a render cap applies */
+  renderFpsCapRequestServer: boolean;
   maxBitrateMbps: number;
   /** Recording video bitrate in Mbps; null means let MediaRecorder choose automatically */
   recordingBitrateMbps: number | null;
@@ -257,6 +314,7 @@ export interface Settings {
   nativeStreamerBackend: NativeStreamerBackendPreference;
   nativeVideoBackend: NativeVideoBackendPreference;
   nativeStreamerExecutablePath: string;
+  nativeExperimentalFlags: string;
   nativeCloudGsyncMode: NativeStreamerFeatureMode;
   nativeD3dFullscreenMode: NativeStreamerFeatureMode;
   nativeExternalRenderer: boolean;
@@ -264,15 +322,69 @@ export interface Settings {
   codec: VideoCodec;
   decoderPreference: VideoAccelerationPreference;
   encoderPreference: VideoAccelerationPreference;
+  driverWorkaroundsEnabled: boolean;
+  disabledDriverWorkarounds: string[];
   colorQuality: ColorQuality;
+  /** Keyed by catalog game id */
+  gameStreamOverrides: Record<string, GameStreamOverride>;
+  /** Switch new sessions to `batteryStreamProfile` while running on battery */
+  batteryProfileEnabled: boolean;
+  /** Battery level (percent) at or below which the battery profile applies; 100 = whenever unplugged */
+  batteryProfileBelowPercent: number;
+  batteryStreamProfile: StreamProfileOverride;
   region: string;
   sessionProxyEnabled: boolean;
   sessionProxyUrl: string;
+  /** Tunnel signaling via HTTP CONNECT/SOCKS5; falls back to HTTPS_PROXY/ALL_PROXY when no URL is set */
+  signalingProxyEnabled: boolean;
+  /** "relay" only gathers TURN candidates, for networks that block direct UDP */
+  iceTransportPolicy: IceTransportPolicy;
+  /** Extra STUN/TURN URLs (comma or whitespace separated), tried before the session's servers */
+  customIceServerUrls: string;
+  customIceUsername: string;
+  customIceCredential: string;
+  /** Restrict local WebRTC UDP ports to "min-max" (empty = any port) */
+  webrtcUdpPortRange: string;
+  /** Force IPv4 or IPv6 for signaling and ICE; "auto" races both (happy eyeballs) */
+  networkIpFamily: NetworkIpFamily;
+  /** Probe the path MTU at session start and shrink video packets when the path cannot carry them whole */
+  pathMtuProbe: boolean;
+  /** Send input at high network priority so QoS-aware routers see DSCP-marked packets */
+  networkQosMarking: boolean;
+  /** Native jitter buffer reorder window in ms; larger values trade latency for fewer decode errors on lossy links */
+  jitterBufferMs: number;
+  /** Runtime log filter such as `webrtc=debug,nativestreamer=warn,info`; empty keeps everything */
+  logFilter: string;
+  /** Size cap for Chromium's disk cache, which holds box art and other images, in MB (0 = Chromium's own sizing); applies on next launch */
+  diskCacheLimitMb: number;
+  /** Folder holding the Screenshots and Recordings subfolders; empty uses Pictures/OpenNOW */
+  mediaDirectory: string;
+  /** Raise the native decode and render threads (MMCSS / SCHED_RR) while they run the media chain */
+  mediaThreadPriority: boolean;
+  /** Competitive preset: overrides frame pacing, raw input and the audio buffer, and runs the native pipeline with no buffering slack */
+  lowLatencyMode: boolean;
+  /** Re-publish the received stream to a local WHIP endpoint so another device can watch */
+  restreamEnabled: boolean;
+  /** WHIP ingest URL on the LAN, e.g. a MediaMTX server */
+  restreamWhipUrl: string;
+  /** Height cap for the re-published video (0 = source resolution) */
+  restreamMaxHeight: number;
   clipboardPaste: boolean;
+  /** Privacy kill switch: never capture the microphone, whatever the microphone mode */
+  microphoneBlocked: boolean;
   /** Enable experimental gyroscope controller input mapping */
   enableGyroscopeControls: boolean;
+  /** Which of the four GFN player slots local controllers may occupy */
+  controllerSlotsEnabled: boolean[];
+  /** Steam Deck / gamescope compatibility mode; applies on next launch */
+  gamescopeCompatibility: GamescopeCompatibility;
   mouseSensitivity: number;
   mouseAcceleration: number;
+  mouseRawInput: boolean;
+  /** Gamepad mouse mode pointer speed in pixels per second at full right-stick deflection */
+  gamepadMouseSpeed: number;
+  /** Gamepad mouse mode response curve (0 = linear, 100 = most precise near center) */
+  gamepadMouseAcceleration: number;
   shortcutToggleStats: string;
   shortcutTogglePointerLock: string;
   shortcutToggleFullscreen: string;
@@ -281,26 +393,74 @@ export interface Settings {
   shortcutToggleMicrophone: string;
   shortcutScreenshot: string;
   shortcutToggleRecording: string;
+  shortcutToggleStreamMute: string;
+  shortcutVolumeUp: string;
+  shortcutVolumeDown: string;
+  /** Toggles the text field that sends typed and IME-composed text into the stream */
+  shortcutToggleTextInput: string;
   microphoneMode: MicrophoneMode;
   microphoneDeviceId: string;
+  /** WASAPI exclusive output for the native streamer on Windows */
+  audioOutputMode: AudioOutputMode;
+  /** Requested audio output buffer in ms (0 = platform default) */
+  audioBufferMs: number;
+  /** Stream playback volume in percent, applied by the client rather than the OS mixer */
+  streamVolume: number;
+  streamMuted: boolean;
+  /** Briefly lower stream volume while an in-stream notice pops up */
+  duckOnNotifications: boolean;
   hideStreamButtons: boolean;
   showAntiAfkIndicator: boolean;
   showStatsOnLaunch: boolean;
   /** Skip the free-tier queue server selection modal and launch with default routing */
   hideServerSelector: boolean;
+  /** Base URL of a community mirror of the queue service; empty uses PrintedWaste */
+  queueServiceUrl: string;
+  /** Base64 Ed25519 key the queue service must sign its server mapping with */
+  queueServicePublicKey: string;
   /** Desktop UI accent preset */
   appAccentColor: AppAccentColor;
+  appTheme: AppTheme;
   /** Use the large-screen controller-oriented shell and library layout */
   controllerMode: boolean;
   autoFullScreen: boolean;
+  /** macOS only: borderless uses simple fullscreen, exclusive the native Spaces fullscreen */
+  fullscreenMode: FullscreenMode;
+  /** Alt+Enter toggles fullscreen while a stream is running instead of reaching the game */
+  altEnterFullscreen: boolean;
   favoriteGameIds: string[];
   sessionCounterEnabled: boolean;
   /** Also show the session-limit countdown in the stats overlay while streaming */
   showSessionTimeRemainingInStatsOverlay: boolean;
+  /** Metrics in the collapsed stats panel, in display order */
+  statsPanelFields: StatsPanelField[];
+  /** Stats panel size in percent of the default */
+  statsPanelScale: number;
+  /** Stats panel background opacity in percent */
+  statsPanelOpacity: number;
+  /** Stop the stream after this many minutes without input (0 = never) */
+  idleDisconnectMinutes: number;
+  /** Remind after this many minutes of play in one session (0 = never) */
+  playReminderMinutes: number;
+  /** Re-sync the library from the service this often in the background (0 = never) */
+  librarySyncMinutes: number;
+  /** Local "HH:MM" bedtime for streaming (empty = none) */
+  playCurfew: string;
+  /** End the stream when the play reminder or bedtime is reached instead of only reminding */
+  playLimitDisconnect: boolean;
+  /** End the cloud session when the app quits instead of keeping it to resume */
+  endSessionOnExit: boolean;
   sessionClockShowEveryMinutes: number;
   sessionClockShowDurationSeconds: number;
   windowWidth: number;
   windowHeight: number;
+  /** Last window position (null = let the OS place the window) */
+  windowX: number | null;
+  windowY: number | null;
+  /** Display the window was last on, used when the saved position is no longer visible */
+  windowDisplayId: number | null;
+  /** Display used for fullscreen streaming (null = the display the window is on) */
+  fullscreenDisplayId: number | null;
   /** Keyboard layout for mapping physical keys inside the remote session */
   keyboardLayout: KeyboardLayout;
   /** In-game language setting (sent to GFN servers via languageCode parameter) */
@@ -313,12 +473,107 @@ export interface Settings {
   nativeTransitionDiagnostics?: NativeTransitionDiagnostics;
   /** Show the currently streaming game as Discord Rich Presence activity */
   discordRichPresence: boolean;
+  /** Opt-in anonymous session quality telemetry */
+  telemetryMode: TelemetryMode;
+  /** Community endpoint that receives uploaded telemetry aggregates */
+  telemetryEndpoint: string;
+  /** Whether a parental PIN is set. Main keeps the hash itself and derives this when sending settings. */
+  parentalPinSet: boolean;
+  /** Ask for the PIN before launching a game */
+  parentalLockLaunch: boolean;
+  /** Ask for the PIN before opening settings */
+  parentalLockSettings: boolean;
+  /** Hide library games rated above this age; null shows everything */
+  parentalMaxAge: number | null;
   /** Automatically check GitHub Releases for app updates in the background */
   autoCheckForUpdates: boolean;
+  /** Release channel the updater follows; beta also offers GitHub prereleases */
+  updateChannel: UpdateChannel;
   /** When true, pressing Escape will exit fullscreen; when false Escape is sent to the game while pointer-locked */
   allowEscapeToExitFullscreen?: boolean;
 }
 
+/** Quality summary of one finished stream; carries no account, session or server identifiers. */
+export interface SessionTelemetryReport {
+  clientMode: "web" | "native";
+  decoderBackend: string;
+  codec: string;
+  averageFps: number;
+  durationSeconds: number;
+  reconnectCount: number;
+  errorCodes: string[];
+}
+
+export type StorageCategory = "images" | "library" | "recordings" | "logs";
+
+export interface StorageCategoryUsage {
+  category: StorageCategory;
+  bytes: number;
+  /** Directory the category lives in, for display */
+  path: string;
+}
+
+export type SessionTimelineEventKind =
+  | "session"
+  | "connection"
+  | "reconnect"
+  | "resolution"
+  | "keyframe"
+  | "decoder"
+  | "degraded";
+
+/** What happened; the renderer turns it into text with `stream.timeline.event.<code>`. */
+export type SessionTimelineEventCode =
+  | "streamStarted"
+  | "streamEnded"
+  | "launchError"
+  | "connection"
+  | "connectionChanged"
+  | "videoStarted"
+  | "resolutionChanged"
+  | "keyframesRequested"
+  | "decoderRecovery"
+  | "decoderFallingBehind"
+  | "decoderCaughtUp"
+  | "decoderErrors"
+  | "decoderResets"
+  | "decoderFlushes"
+  | "lagging"
+  | "stableAgain"
+  | "reconnectAttempt";
+
+export interface SessionTimelineEvent {
+  /** Wall-clock time of the event, in ms since the epoch */
+  atMs: number;
+  kind: SessionTimelineEventKind;
+  code: SessionTimelineEventCode;
+  /** Values for the event's message; free text here is untranslated diagnostics */
+  params?: Record<string, string | number>;
+}
+
+/** Significant moments of one stream, kept for post-mortem debugging and the log export. */
+export interface SessionTimeline {
+  startedAtMs: number;
+  /** Null while the stream is still running */
+  endedAtMs: number | null;
+  events: SessionTimelineEvent[];
+}
+
+export interface DisplayRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface DisplayInfo {
+  id: number;
+  label: string;
+  bounds: DisplayRect;
+  scaleFactor: number;
+  primary: boolean;
+}
+
 export const DEFAULT_STREAM_PREFERENCES: Readonly<Pick<Settings, "codec" | "colorQuality">> = Object.freeze({
   codec: "H264",
   colorQuality: "8bit_420",
@@ -354,6 +609,12 @@ export interface AuthTokens {
   clientTokenLifetimeMs?: number;
 }
 
+/** Outcome of revoking every saved account's tokens before signing out. */
+export interface AuthRevokeResult {
+  revoked: number;
+  failed: number;
+}
+
 export interface AuthUser {
   userId: string;
   displayName: string;
@@ -391,6 +652,71 @@ function parseResolutionValue(resolution: string): { width: number; height: numb
     : null;
 }
 
+const CUSTOM_RESOLUTION_MIN = { width: 640, height: 360 };
+const CUSTOM_RESOLUTION_MAX = { width: 7680, height: 4320 };
+
+/**
+ * Validates a user-entered stream size. Encoders want even dimensions, so odd
+ * values are rounded down rather than rejected.
+ */
+export function normalizeCustomResolution(width: number, height: number): string | null {
+  if (!Number.isFinite(width) || !Number.isFinite(height)) {
+    return null;
+  }
+  const evenWidth = Math.floor(width / 2) * 2;
+  const evenHeight = Math.floor(height / 2) * 2;
+  if (
+    evenWidth < CUSTOM_RESOLUTION_MIN.width
+    || evenHeight < CUSTOM_RESOLUTION_MIN.height
+    || evenWidth > CUSTOM_RESOLUTION_MAX.width
+    || evenHeight > CUSTOM_RESOLUTION_MAX.height
+  ) {
+    return null;
+  }
+  return `${evenWidth}x${evenHeight}`;
+}
+
+/** GFN sessions accept up to four local controllers, one per player slot. */
+export const GAMEPAD_MAX_CONTROLLERS = 4;
+
+/** Missing or malformed entries leave a slot enabled. */
+export function normalizeControllerSlotsEnabled(raw: unknown): boolean[] {
+  const values: unknown[] = Array.isArray(raw) ? raw : [];
+  return Array.from({ length: GAMEPAD_MAX_CONTROLLERS }, (_, slot) => values[slot] !== false);
+}
+
+export type StatsPanelField = "fps" | "rtt" | "bitrate" | "loss" | "decode" | "sessionTimer";
+
+export const STATS_PANEL_FIELDS: readonly StatsPanelField[] = ["fps", "rtt", "bitrate", "loss", "decode", "sessionTimer"];
+
+/** What the stats panel showed before it was customizable. */
+export const DEFAULT_STATS_PANEL_FIELDS: readonly StatsPanelField[] = ["fps", "rtt", "bitrate"];
+
+/** Keeps the saved order and drops unknown or repeated fields; an empty list is a valid choice. */
+export function normalizeStatsPanelFields(raw: unknown): StatsPanelField[] {
+  if (!Array.isArray(raw)) {
+    return [...DEFAULT_STATS_PANEL_FIELDS];
+  }
+  const fields: StatsPanelField[] = [];
+  for (const value of raw) {
+    if (STATS_PANEL_FIELDS.includes(value as StatsPanelField) && !fields.includes(value as StatsPanelField)) {
+      fields.push(value as StatsPanelField);
+    }
+  }
+  return fields;
+}
+
+/**
+ * Ultrawide and custom sizes are rarely listed verbatim in the entitlements.
+ * This is a heuristic, not a documented server rule: a size is kept when an
+ * entitled mode has at least as many pixels and rows, though nothing in the
+ * entitlement response confirms the server accepts every such size.
+ */
+function coversResolution(entitlement: EntitledResolution, requested: { width: number; height: number }): boolean {
+  return entitlement.width * entitlement.height >= requested.width * requested.height
+    && entitlement.height >= requested.height;
+}
+
 function isValidEntitledResolution(resolution: EntitledResolution): boolean {
   return Number.isFinite(resolution.width)
     && resolution.width > 0
@@ -425,10 +751,15 @@ export function resolveEntitledStreamProfile(
         resolution.height === requestedResolution.height,
     )
     : [];
+  const coveringResolutionEntries = requestedResolution && matchingResolutionEntries.length === 0
+    ? validEntitlements.filter((resolution) => coversResolution(resolution, requestedResolution))
+    : [];
   const fallbackResolution = [...validEntitlements].sort(compareEntitledResolutionDescending)[0];
   const selectedResolutionEntries = matchingResolutionEntries.length > 0
     ? matchingResolutionEntries
-    : validEntitlements.filter(
+    : coveringResolutionEntries.length > 0
+      ? coveringResolutionEntries
+      : validEntitlements.filter(
       (resolution) =>
         resolution.width === fallbackResolution.width &&
         resolution.height === fallbackResolution.height,
@@ -441,7 +772,9 @@ export function resolveEntitledStreamProfile(
   const fps = requestedFps && fpsOptions.includes(requestedFps)
     ? requestedFps
     : [...fpsOptions].reverse().find((option) => requestedFps !== undefined && option <= requestedFps) ?? fpsOptions[0];
-  const selectedResolution = selectedResolutionEntries[0];
+  const selectedResolution = coveringResolutionEntries.length > 0 && requestedResolution
+    ? requestedResolution
+    : selectedResolutionEntries[0];
 
   return {
     resolution: `${selectedResolution.width}x${selectedResolution.height}`,
@@ -594,6 +927,60 @@ export interface PingResult {
   error?: string;
 }
 
+export interface RouteTraceHop {
+  hop: number;
+  /** Router that answered; null when every probe to this hop was lost */
+  address: string | null;
+  /** Round trips of the probes that answered */
+  rttsMs: number[];
+  lossPercent: number;
+}
+
+export interface RouteTraceResult {
+  /** Host or address the trace was asked for */
+  target: string;
+  /** Address the target resolved to */
+  address: string | null;
+  /** ISO timestamp */
+  startedAt: string;
+  hops: RouteTraceHop[];
+  error?: string;
+}
+
+export interface PathMtuResult {
+  /** IPv4 address that was probed, or the requested target when it did not resolve */
+  target: string;
+  /** Largest packet that crossed the path unfragmented; null when it could not be measured */
+  mtu: number | null;
+  error?: string;
+}
+
+/** Local machine load for the stats panel; null where the platform does not report it */
+export interface SystemUsageSample {
+  /** Busy share across all cores since the previous sample */
+  cpuPercent: number | null;
+  gpuPercent: number | null;
+  /** Hottest CPU/SoC thermal zone, in °C */
+  temperatureC: number | null;
+  gpuTemperatureC: number | null;
+  /** Epoch ms */
+  sampledAt: number;
+}
+
+/** Redacted log line for the live tail in debug settings */
+export interface LogTailLine {
+  sequence: number;
+  timestamp: number;
+  level: "log" | "error" | "warn" | "info" | "debug";
+  text: string;
+}
+
+export interface LogTailResult {
+  lines: LogTailLine[];
+  /** Pass back as `afterSequence` to fetch only newer lines */
+  cursor: number;
+}
+
 export interface GamesFetchRequest {
   token?: string;
   providerStreamingBaseUrl?: string;
@@ -601,6 +988,8 @@ export interface GamesFetchRequest {
   proxyUrl?: string;
   /** Stable account id used for on-disk cache scoping (avoids cache misses on token refresh). */
   userId?: string;
+  /** Skip the on-disk cache and refetch, updating the cache with the result. */
+  refresh?: boolean;
 }
 
 export interface DirectLaunchRequest {
@@ -727,6 +1116,24 @@ export function normalizeGameStore(store: string): string {
   return store.toUpperCase().replace(/[\s-]+/g, "_");
 }
 
+/** Maps store names and their aliases onto the account-linking provider codes. */
+export function normalizeProviderCode(provider: string): string {
+  const normalized = provider.trim().toUpperCase().replace(/[\s-]+/g, "_");
+  switch (normalized) {
+    case "UBISOFT":
+    case "UBISOFT_CONNECT":
+      return "UPLAY";
+    case "BATTLE_NET":
+    case "BLIZZARD":
+      return "BATTLENET";
+    case "EPIC_GAMES":
+    case "EPIC_GAMES_STORE":
+      return "EPIC";
+    default:
+      return normalized;
+  }
+}
+
 export function isOwnedLibraryStatus(status?: string): boolean {
   return typeof status === "string" && OWNED_LIBRARY_STATUSES.includes(status as (typeof OWNED_LIBRARY_STATUSES)[number]);
 }
@@ -859,6 +1266,26 @@ export interface StreamSettings {
   cloudGsyncResolution?: CloudGsyncResolution;
   /** Hidden diagnostics for native transition recovery and 240 FPS server-side stream changes. */
   nativeTransitionDiagnostics?: NativeTransitionDiagnostics;
+  /** ICE transport policy for the native streamer's webrtcbin. */
+  iceTransportPolicy?: IceTransportPolicy;
+  /** User-configured STUN/TURN servers, already parsed from settings. */
+  customIceServers?: IceServer[];
+  /** Local UDP port range for native ICE candidates. */
+  udpPortRange?: { min: number; max: number };
+  /** Create the input data channels at high priority, which webrtcbin turns into DSCP marking. */
+  networkQosMarking?: boolean;
+  /** Native jitter buffer reorder window; OPENNOW_NATIVE_JITTER_BUFFER_MS still wins when set. */
+  jitterBufferMs?: number;
+  /** Native audio sink tuning; bufferMs 0 keeps the platform default. */
+  audioOutput?: { exclusive: boolean; bufferMs: number };
+  /** Player slots (0-3) the native XInput bridge may forward. */
+  controllerSlots?: boolean[];
+  /** Native present cap for the current power source; 0 renders every decoded frame. */
+  renderFpsCap?: number;
+  /** Raise the native decode and render threads. */
+  mediaThreadPriority?: boolean;
+  /** Zero jitter buffer, newest-frame presentation, eager late-frame drops and sparser stats. */
+  lowLatency?: boolean;
 }
 
 export interface SessionCreateRequest {
@@ -1094,7 +1521,10 @@ export type NativeStreamerShortcutAction =
   | "toggleAntiAfk"
   | "toggleMicrophone"
   | "screenshot"
-  | "toggleRecording";
+  | "toggleRecording"
+  | "toggleStreamMute"
+  | "volumeUp"
+  | "volumeDown";
 
 export interface NativeStreamerShortcutBindings {
   toggleStats: string;
@@ -1105,6 +1535,9 @@ export interface NativeStreamerShortcutBindings {
   toggleMicrophone: string;
   screenshot: string;
   toggleRecording: string;
+  toggleStreamMute: string;
+  volumeUp: string;
+  volumeDown: string;
 }
 
 export interface NativeStreamerSessionContext {
@@ -1157,6 +1590,14 @@ export interface NativeVideoTransition {
   summary?: string;
 }
 
+/** An encoding the server offers for the native video stream (a simulcast rid or extra video m-line). */
+export interface NativeVideoLayer {
+  id: string;
+  mid?: string;
+  width?: number;
+  height?: number;
+}
+
 export interface NativeInputPacket {
   payload: ArrayBuffer | Uint8Array | number[];
   partiallyReliable?: boolean;
@@ -1196,6 +1637,7 @@ export type MainToRendererSignalingEvent =
   | { type: "native-stream-stopped"; reason?: string }
   | { type: "native-stream-stats"; stats: NativeStreamStats }
   | { type: "native-stream-transition"; transition: NativeVideoTransition }
+  | { type: "native-stream-layers"; layers: NativeVideoLayer[]; active: string; pausedAtSender: boolean }
   | { type: "native-input-ready"; protocolVersion: number }
   | { type: "error"; message: string }
   | { type: "log"; message: string };
@@ -1225,11 +1667,45 @@ export interface NativeStreamStats {
   presentPacingChanges?: number;
   partialFlushCount?: number;
   completeFlushCount?: number;
+  framesSkippedAhead?: number;
   lastTransitionType?: string;
   lastTransitionAtMs?: number;
   lastTransitionSummary?: string;
   requestedStreamingFeaturesSummary?: string;
   finalizedStreamingFeaturesSummary?: string;
+  pipelineStageSummary?: string;
+  /** Average time from decoder input to decoded picture. */
+  decodeTimeMs?: number;
+  /** Average upload/convert plus present-queue time after decode. */
+  renderTimeMs?: number;
+  keyframesReceived?: number;
+  keyframeIntervalMs?: number;
+  /** Access units rebuilt across lost packets or flagged corrupt before decode. */
+  damagedFrames?: number;
+  /** Warnings and errors the video decoder posted on the pipeline bus. */
+  decoderErrors?: number;
+  hdrMetadata?: string;
+  /** Encoder clock timestamp from SEI, `HH:MM:SS:FF`. */
+  encoderTimecode?: string;
+  /** Local wall clock minus the encoder time code, when the encoder stamps wall-clock time. */
+  encoderLatencyMs?: number;
+  jitterBufferTargetMs?: number;
+  jitterMs?: number;
+  packetsReceived?: number;
+  packetsLost?: number;
+  packetsLate?: number;
+  avSyncOffsetMs?: number;
+  avSyncAudioCorrectionMs?: number;
+  audioOutputLatencyMs?: number;
+  /** Times the audio device played past everything the stream had written. */
+  audioUnderruns?: number;
+  audioCodec?: string;
+  audioSampleRate?: number;
+  audioChannels?: number;
+  /** Cap on queued decoded frames, sized from free system memory */
+  memoryBudgetMb?: number;
+  /** Scheduling the OS granted the decode and render threads */
+  threadPrioritySummary?: string;
 }
 
 /** Dialog result for session conflict resolution */
@@ -1237,6 +1713,24 @@ export type SessionConflictChoice = "resume" | "new" | "cancel";
 
 export type ExistingSessionStrategy = "auto-resume" | "force-new";
 
+export type UpdateChannel = "stable" | "beta";
+
+/** "off" reads controllers directly even when Steam Input has taken them over */
+export type GamescopeCompatibility = "auto" | "off";
+
+export interface GamescopeStatus {
+  /** Launched inside gamescope, as in Steam Deck Game Mode */
+  session: boolean;
+  /** `vendor:product` of pads Steam Input has taken over; their input arrives through Steam's virtual controller */
+  steamInputDevices: string[];
+}
+
+/** One version's notes for the changelog dialog, already reduced to plain text. */
+export interface AppUpdateReleaseNote {
+  version: string;
+  notes: string;
+}
+
 export type AppUpdaterStatus =
   | "disabled"
   | "idle"
@@ -1265,6 +1759,9 @@ export interface AppUpdaterState {
   lastCheckedAt?: number;
   message?: string;
   errorCode?: string;
+  channel: UpdateChannel;
+  /** Notes for every version between the current one and the available update, newest first */
+  releaseNotes?: AppUpdateReleaseNote[];
   updateSource: "github-releases";
   canCheck: boolean;
   canDownload: boolean;
@@ -1272,17 +1769,27 @@ export interface AppUpdaterState {
   isPackaged: boolean;
 }
 
+/** Local addresses (`interface/family/address`) that appeared or disappeared */
+export interface NetworkChangeEvent {
+  added: string[];
+  removed: string[];
+}
+
 export interface OpenNowApi {
   getAuthSession(input?: AuthSessionRequest): Promise<AuthSessionResult>;
   getLoginProviders(): Promise<LoginProvider[]>;
   getRegions(input?: RegionsFetchRequest): Promise<StreamRegion[]>;
   login(input: AuthLoginRequest): Promise<AuthSession>;
+  /** Abandon a browser sign-in started by `login`; it rejects with a cancellation error. */
+  cancelLogin(): Promise<void>;
   startDeviceLogin(input: AuthDeviceLoginStartRequest): Promise<AuthDeviceLoginChallenge>;
   pollDeviceLogin(input: AuthDeviceLoginPollRequest): Promise<AuthDeviceLoginPollResult>;
   completeDeviceLogin(input: AuthDeviceLoginAttemptRequest): Promise<AuthSession>;
   cancelDeviceLogin(input: AuthDeviceLoginAttemptRequest): Promise<void>;
   logout(): Promise<void>;
   logoutAll(): Promise<void>;
+  /** Revoke all saved tokens with NVIDIA, then sign out of every account locally. */
+  revokeAllSessions(): Promise<AuthRevokeResult>;
   getSavedAccounts(): Promise<SavedAccount[]>;
   switchAccount(userId: string): Promise<AuthSession>;
   removeAccount(userId: string): Promise<void>;
@@ -1290,6 +1797,8 @@ export interface OpenNowApi {
   fetchPersistentStorageLocations(input?: PersistentStorageLocationsFetchRequest): Promise<PersistentStorageLocationsResult>;
   resetPersistentStorage(input?: PersistentStorageResetRequest): Promise<PersistentStorageResetResult>;
   fetchGameAccountConnections(): Promise<GameAccountConnectionsResult>;
+  /** Last fetched connection state, or null before the first fetch; never hits the network */
+  peekGameAccountConnections(): Promise<GameAccountConnectionsResult | null>;
   linkGameAccount(input: GameAccountOperationRequest): Promise<GameAccountOperationResult>;
   unlinkGameAccount(input: GameAccountOperationRequest): Promise<GameAccountOperationResult>;
   resyncGameAccount(input: GameAccountOperationRequest): Promise<GameAccountOperationResult>;
@@ -1313,6 +1822,7 @@ export interface OpenNowApi {
   claimSession(input: SessionClaimRequest): Promise<SessionInfo>;
   getNativeStreamerStatus(): Promise<NativeStreamerStatus>;
   getNativeCloudGsyncCapabilities(): Promise<NativeCloudGsyncCapabilities>;
+  listDisplays(): Promise<DisplayInfo[]>;
   /** Show dialog asking user how to handle session conflict */
   showSessionConflictDialog(): Promise<SessionConflictChoice>;
   connectSignaling(input: SignalingConnectRequest): Promise<void>;
@@ -1322,6 +1832,10 @@ export interface OpenNowApi {
   sendNativeInput(input: NativeInputPacket): void;
   updateNativeRenderSurface(input: NativeRenderSurfaceUpdate): void;
   updateNativeShortcuts(shortcuts: NativeStreamerShortcutBindings): void;
+  /** Effective stream volume (0-1) for the native streamer's audio path */
+  updateNativeVolume(volume: number): void;
+  /** Switch the native streamer to another offered video layer without renegotiating; rejects if the streamer refuses */
+  selectNativeVideoLayer(layerId: string): Promise<void>;
   requestKeyframe(input: KeyframeRequest): Promise<void>;
   onSignalingEvent(listener: (event: MainToRendererSignalingEvent) => void): () => void;
   /** Listen for F11 fullscreen toggle from main process */
@@ -1333,22 +1847,54 @@ export interface OpenNowApi {
   installUpdateAndRestart(): Promise<AppUpdaterState>;
   onUpdaterStateChanged(listener: (state: AppUpdaterState) => void): () => void;
   setFullscreen(v: boolean): Promise<void>;
+  /** Shrinks the window into an always-on-top 16:9 corner player, or restores it; resolves to the new state */
+  setMiniPlayer(enabled: boolean): Promise<boolean>;
   toggleFullscreen(): Promise<void>;
   togglePointerLock(): Promise<void>;
   /** Notify main process that pointer lock state changed (active = true/false) */
   notifyPointerLockChange(active: boolean): void;
+  /** Tell main whether a stream is running, which scopes the Alt+Enter fullscreen shortcut */
+  notifyStreamActiveChange(active: boolean): void;
   /** Read plain text from the OS clipboard through Electron main process */
   readClipboardText(): Promise<string>;
   getSettings(): Promise<Settings>;
-  setSetting<K extends keyof Settings>(key: K, value: Settings[K]): Promise<void>;
-  resetSettings(): Promise<Settings>;
+  getUserTheme(): Promise<UserTheme>;
+  /** Workarounds matching this machine under the current rules and toggles */
+  getDriverWorkarounds(): Promise<DriverWorkaroundReport>;
+  /** Fires when the rules file in the user data folder changes */
+  onDriverWorkaroundsChanged(listener: (report: DriverWorkaroundReport) => void): () => void;
+  getGamescopeStatus(): Promise<GamescopeStatus>;
+  /** False for a wrong PIN, and while repeated misses are cooling down */
+  verifyParentalPin(pin: string): Promise<boolean>;
+  /**
+   * Sets, changes or (nextPin null) clears the parental PIN. Resolves to whether a
+   * PIN is now set, or null when currentPin does not match the existing PIN.
+   */
+  setParentalPin(currentPin: string | null, nextPin: string | null): Promise<boolean | null>;
+  /** Changing a parental-protected setting while a PIN is set needs that PIN; rejects otherwise */
+  setSetting<K extends keyof Settings>(key: K, value: Settings[K], pin?: string): Promise<void>;
+  /** Needs the parental PIN when one is set; rejects otherwise */
+  resetSettings(pin?: string): Promise<Settings>;
   selectNativeStreamerExecutable(): Promise<string | null>;
+  /** Folder picker for the `mediaDirectory` setting; null when cancelled */
+  selectMediaDirectory(): Promise<string | null>;
   getMicrophonePermission(): Promise<MicrophonePermissionResult>;
   /** Export logs in redacted format */
   exportLogs(format?: "text" | "json"): Promise<string>;
+  /** Main-process log lines captured after `afterSequence`, redacted */
+  getLogTail(afterSequence: number): Promise<LogTailResult>;
   /** Ping all regions and return latency results */
   pingRegions(regions: StreamRegion[]): Promise<PingResult[]>;
 
+  /** Traceroute to a streaming server; the latest result is attached to exported logs */
+  traceRoute(target: string): Promise<RouteTraceResult>;
+
+  /** Don't-fragment probe of the path MTU to a media server, cached per address */
+  probePathMtu(target: string): Promise<PathMtuResult>;
+
+  /** CPU, GPU and temperature readings for the stats panel; sampled at most every two seconds */
+  getSystemUsage(): Promise<SystemUsageSample>;
+
   /** Persist a PNG screenshot from a renderer-generated data URL */
   saveScreenshot(input: ScreenshotSaveRequest): Promise<ScreenshotEntry>;
 
@@ -1367,6 +1913,15 @@ export interface OpenNowApi {
   /** Listen for external Escape events forwarded by the main process */
   onExternalEscape(listener: () => void): () => void;
 
+  /** Listen for local network interface changes (e.g. docking onto Ethernet) */
+  onNetworkChanged(listener: (change: NetworkChangeEvent) => void): () => void;
+
+  /** Whether the system runs on battery, as Electron's powerMonitor reports it */
+  isOnBatteryPower(): Promise<boolean>;
+
+  /** Listen for the system switching between battery and AC power */
+  onPowerSourceChanged(listener: (onBattery: boolean) => void): () => void;
+
   /** Open a trusted external URL in the OS default browser */
   openExternalUrl(url: string): Promise<void>;
 
@@ -1410,6 +1965,13 @@ export interface OpenNowApi {
   regenMediaThumbnail(input: { filePath: string }): Promise<{ ok: boolean; thumbnailDataUrl: string | null }>;
 
   deleteCache(): Promise<void>;
+  /** Disk space used by each local storage category. */
+  getStorageUsage(): Promise<StorageCategoryUsage[]>;
+  /** Empties one storage category and returns the updated usage. */
+  clearStorage(category: StorageCategory): Promise<StorageCategoryUsage[]>;
+
+  /** Hold the main-process catalog refresh while a stream is running */
+  setBackgroundWorkPaused(paused: boolean): Promise<void>;
 
   /** Fetch current GFN queue wait times from the PrintedWaste API */
   fetchPrintedWasteQueue(): Promise<PrintedWasteQueueData>;
@@ -1418,6 +1980,12 @@ export interface OpenNowApi {
   getThanksData(): Promise<ThankYouDataResult>;
   /** Clear Discord rich presence activity */
   clearDiscordActivity(): Promise<void>;
+  /** Fold a finished session into the telemetry aggregate when telemetry is enabled */
+  recordSessionTelemetry(report: SessionTelemetryReport): Promise<void>;
+  /** Keeps the running or last stream's event timeline for log exports. */
+  recordSessionTimeline(timeline: SessionTimeline): Promise<void>;
+  /** The timeline main last received, for a post-mortem look after the stream closed */
+  getLastSessionTimeline(): Promise<SessionTimeline | null>;
 }
 
 export interface ScreenshotSaveRequest {
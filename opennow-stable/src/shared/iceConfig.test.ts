@@ -0,0 +1,87 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import {
+  formatCandidatePair,
+  mergeIceServers,
+  parseCustomIceServers,
+  parseUdpPortRange,
+  summarizeCandidatePairs,
+} from "./iceConfig";
+
+test("parseCustomIceServers: attaches credentials to TURN urls only", () => {
+  const servers = parseCustomIceServers({
+    customIceServerUrls: "stun:stun.corp.example:3478, turn:relay.corp.example:443?transport=tcp\nhttp://ignored",
+    customIceUsername: " alice ",
+    customIceCredential: "secret",
+  });
+
+  assert.deepEqual(servers, [
+    { urls: ["stun:stun.corp.example:3478"] },
+    { urls: ["turn:relay.corp.example:443?transport=tcp"], username: "alice", credential: "secret" },
+  ]);
+});
+
+test("mergeIceServers: puts custom servers first and drops duplicate urls", () => {
+  const merged = mergeIceServers(
+    [{ urls: ["stun:s1.example"] }],
+    [{ urls: ["stun:s1.example", "stun:s2.example"] }, { urls: ["stun:s1.example"] }],
+  );
+
+  assert.deepEqual(merged, [{ urls: ["stun:s1.example"] }, { urls: ["stun:s2.example"] }]);
+});
+
+test("parseUdpPortRange: accepts ranges and single ports", () => {
+  assert.deepEqual(parseUdpPortRange("50000-50100"), { min: 50000, max: 50100 });
+  assert.deepEqual(parseUdpPortRange(" 49152 "), { min: 49152, max: 49152 });
+  assert.equal(parseUdpPortRange(""), null);
+  assert.equal(parseUdpPortRange("60000-50000"), null);
+  assert.equal(parseUdpPortRange("80-90"), null);
+  assert.equal(parseUdpPortRange("50000-70000"), null);
+});
+
+test("summarizeCandidatePairs: resolves candidates and lists the nominated pair first", () => {
+  const pairs = summarizeCandidatePairs([
+    {
+      type: "candidate-pair",
+      id: "CP1",
+      state: "failed",
+      nominated: false,
+      localCandidateId: "L1",
+      remoteCandidateId: "R1",
+    },
+    {
+      type: "candidate-pair",
+      id: "CP2",
+      state: "succeeded",
+      nominated: true,
+      localCandidateId: "L2",
+      remoteCandidateId: "R1",
+      currentRoundTripTime: 0.021,
+      bytesReceived: 4096,
+    },
+    { type: "local-candidate", id: "L1", candidateType: "host", address: "10.0.0.2", port: 50001, protocol: "udp" },
+    {
+      type: "local-candidate",
+      id: "L2",
+      candidateType: "relay",
+      relayProtocol: "tcp",
+      address: "203.0.113.5",
+      port: 62000,
+      protocol: "udp",
+    },
+    { type: "remote-candidate", id: "R1", candidateType: "host", address: "198.51.100.7", port: 49006, protocol: "udp" },
+  ]);
+
+  assert.deepEqual(
+    pairs.map((pair) => pair.id),
+    ["CP2", "CP1"],
+  );
+  assert.equal(
+    formatCandidatePair(pairs[0]),
+    "* relay/tcp 203.0.113.5:62000 → host 198.51.100.7:49006 udp · succeeded · rtt 21ms · 4KB in",
+  );
+  assert.equal(formatCandidatePair(pairs[1]), "host redacted:50001 → host 198.51.100.7:49006 udp · failed · 0KB in");
+});
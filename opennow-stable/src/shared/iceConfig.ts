@@ -0,0 +1,132 @@
+import type { IceServer } from "./gfn";
+
+export interface CustomIceServerSettings {
+  customIceServerUrls: string;
+  customIceUsername: string;
+  customIceCredential: string;
+}
+
+export interface UdpPortRange {
+  min: number;
+  max: number;
+}
+
+const ICE_URL_PATTERN = /^(stun|stuns|turn|turns):/i;
+
+/**
+ * Parses the comma/whitespace separated STUN/TURN URL list from settings.
+ * Credentials only apply to TURN URLs; STUN servers never authenticate.
+ */
+export function parseCustomIceServers(settings: CustomIceServerSettings): IceServer[] {
+  const urls = settings.customIceServerUrls
+    .split(/[\s,]+/)
+    .map((url) => url.trim())
+    .filter((url) => ICE_URL_PATTERN.test(url));
+  const stunUrls = urls.filter((url) => /^stuns?:/i.test(url));
+  const turnUrls = urls.filter((url) => /^turns?:/i.test(url));
+
+  const servers: IceServer[] = [];
+  if (stunUrls.length > 0) {
+    servers.push({ urls: stunUrls });
+  }
+  if (turnUrls.length > 0) {
+    const username = settings.customIceUsername.trim();
+    const credential = settings.customIceCredential;
+    servers.push({
+      urls: turnUrls,
+      ...(username ? { username } : {}),
+      ...(credential ? { credential } : {}),
+    });
+  }
+  return servers;
+}
+
+/** Custom servers go first so a corporate TURN relay is tried before the session defaults. */
+export function mergeIceServers(custom: IceServer[], session: IceServer[]): IceServer[] {
+  const seen = new Set(custom.flatMap((server) => server.urls));
+  const sessionServers = session
+    .map((server) => ({ ...server, urls: server.urls.filter((url) => !seen.has(url)) }))
+    .filter((server) => server.urls.length > 0);
+  return [...custom, ...sessionServers];
+}
+
+/** Accepts `min-max` or a single port; returns null for empty or invalid input. */
+export function parseUdpPortRange(value: string): UdpPortRange | null {
+  const match = /^\s*(\d{1,5})\s*(?:-\s*(\d{1,5})\s*)?$/.exec(value);
+  if (!match) return null;
+  const min = Number.parseInt(match[1], 10);
+  const max = match[2] === undefined ? min : Number.parseInt(match[2], 10);
+  if (min < 1024 || max > 65535 || max < min) return null;
+  return { min, max };
+}
+
+export interface CandidatePairSummary {
+  id: string;
+  state: string;
+  nominated: boolean;
+  localType: string;
+  localAddress: string;
+  remoteType: string;
+  remoteAddress: string;
+  protocol: string;
+  relayProtocol?: string;
+  rttMs?: number;
+  bytesSent: number;
+  bytesReceived: number;
+}
+
+/**
+ * Host and reflexive local candidates carry the user's LAN and public
+ * addresses, which end up in screenshots of the HUD; only relay addresses
+ * (allocated on the TURN server) are shown.
+ */
+function describeAddress(candidate: Record<string, unknown> | undefined, redactLocal = false): string {
+  if (!candidate) return "?";
+  if (redactLocal && candidate.candidateType !== "relay") {
+    return candidate.port !== undefined ? `redacted:${String(candidate.port)}` : "redacted";
+  }
+  const address = String(candidate.address ?? candidate.ip ?? "?");
+  const port = candidate.port !== undefined ? `:${String(candidate.port)}` : "";
+  return address.includes(":") && port ? `[${address}]${port}` : `${address}${port}`;
+}
+
+/** Collects every ICE candidate pair from an RTCStatsReport, nominated pairs first. */
+export function summarizeCandidatePairs(entries: Iterable<Record<string, unknown>>): CandidatePairSummary[] {
+  const candidates = new Map<string, Record<string, unknown>>();
+  const pairs: Record<string, unknown>[] = [];
+  for (const entry of entries) {
+    if (entry.type === "local-candidate" || entry.type === "remote-candidate") {
+      candidates.set(String(entry.id), entry);
+    } else if (entry.type === "candidate-pair") {
+      pairs.push(entry);
+    }
+  }
+
+  return pairs
+    .map((pair) => {
+      const local = candidates.get(String(pair.localCandidateId));
+      const remote = candidates.get(String(pair.remoteCandidateId));
+      const rtt = Number(pair.currentRoundTripTime);
+      return {
+        id: String(pair.id),
+        state: String(pair.state ?? "unknown"),
+        nominated: pair.nominated === true,
+        localType: String(local?.candidateType ?? "?"),
+        localAddress: describeAddress(local, true),
+        remoteType: String(remote?.candidateType ?? "?"),
+        remoteAddress: describeAddress(remote),
+        protocol: String(local?.protocol ?? remote?.protocol ?? "?"),
+        relayProtocol: typeof local?.relayProtocol === "string" ? local.relayProtocol : undefined,
+        rttMs: Number.isFinite(rtt) ? Math.round(rtt * 1000) : undefined,
+        bytesSent: Number(pair.bytesSent ?? 0),
+        bytesReceived: Number(pair.bytesReceived ?? 0),
+      };
+    })
+    .sort((a, b) => Number(b.nominated) - Number(a.nominated));
+}
+
+export function formatCandidatePair(pair: CandidatePairSummary): string {
+  const local = `${pair.localType}${pair.relayProtocol ? `/${pair.relayProtocol}` : ""} ${pair.localAddress}`;
+  const rtt = pair.rttMs !== undefined ? ` · rtt ${pair.rttMs}ms` : "";
+  return `${pair.nominated ? "* " : ""}${local} → ${pair.remoteType} ${pair.remoteAddress} ${pair.protocol} · ${pair.state}${rtt} · ${(pair.bytesReceived / 1024).toFixed(0)}KB in`;
+}
@@ -3,20 +3,26 @@ export const IPC_CHANNELS = {
   AUTH_GET_PROVIDERS: "auth:get-providers",
   AUTH_GET_REGIONS: "auth:get-regions",
   AUTH_LOGIN: "auth:login",
+  AUTH_LOGIN_CANCEL: "auth:login-cancel",
   AUTH_DEVICE_LOGIN_START: "auth:device-login-start",
   AUTH_DEVICE_LOGIN_POLL: "auth:device-login-poll",
   AUTH_DEVICE_LOGIN_COMPLETE: "auth:device-login-complete",
   AUTH_DEVICE_LOGIN_CANCEL: "auth:device-login-cancel",
   AUTH_LOGOUT: "auth:logout",
   AUTH_LOGOUT_ALL: "auth:logout-all",
+  AUTH_REVOKE_ALL: "auth:revoke-all",
   AUTH_GET_SAVED_ACCOUNTS: "auth:get-saved-accounts",
   AUTH_SWITCH_ACCOUNT: "auth:switch-account",
   AUTH_REMOVE_ACCOUNT: "auth:remove-account",
   PING_REGIONS: "gfn:ping-regions",
+  ROUTE_TRACE: "gfn:route-trace",
+  PATH_MTU_PROBE: "gfn:path-mtu-probe",
+  SYSTEM_USAGE: "app:system-usage",
   SUBSCRIPTION_FETCH: "subscription:fetch",
   PERSISTENT_STORAGE_LOCATIONS_FETCH: "persistent-storage:locations:fetch",
   PERSISTENT_STORAGE_RESET: "persistent-storage:reset",
   GAME_ACCOUNTS_FETCH: "game-accounts:fetch",
+  GAME_ACCOUNTS_PEEK: "game-accounts:peek",
   GAME_ACCOUNT_LINK: "game-account:link",
   GAME_ACCOUNT_UNLINK: "game-account:unlink",
   GAME_ACCOUNT_RESYNC: "game-account:resync",
@@ -42,13 +48,23 @@ export const IPC_CHANNELS = {
   NATIVE_INPUT: "gfn:native-input",
   NATIVE_RENDER_SURFACE: "gfn:native-render-surface",
   NATIVE_UPDATE_SHORTCUTS: "gfn:native-update-shortcuts",
+  NATIVE_UPDATE_VOLUME: "gfn:native-update-volume",
+  NATIVE_SELECT_VIDEO_LAYER: "gfn:native-select-video-layer",
   REQUEST_KEYFRAME: "gfn:request-keyframe",
   SIGNALING_EVENT: "gfn:signaling-event",
   TOGGLE_FULLSCREEN: "window:toggle-fullscreen",
+  // Alt+Enter from main; the renderer decides how to toggle fullscreen
+  FULLSCREEN_TOGGLE_REQUEST: "app:toggle-fullscreen",
   SET_FULLSCREEN: "window:set-fullscreen",
+  SET_MINI_PLAYER: "window:set-mini-player",
+  LIST_DISPLAYS: "window:list-displays",
   TOGGLE_POINTER_LOCK: "window:toggle-pointer-lock",
   POINTER_LOCK_CHANGE: "window:pointer-lock-change",
+  STREAM_ACTIVE_CHANGE: "window:stream-active-change",
   EXTERNAL_ESCAPE: "app:external-escape",
+  NETWORK_CHANGED: "app:network-changed",
+  POWER_SOURCE_GET: "app:power-source-get",
+  POWER_SOURCE_CHANGED: "app:power-source-changed",
   OPEN_EXTERNAL_URL: "app:open-external-url",
   DIRECT_LAUNCH_GET_PENDING: "app:direct-launch:get-pending",
   DIRECT_LAUNCH_REQUEST: "app:direct-launch:request",
@@ -60,14 +76,22 @@ export const IPC_CHANNELS = {
   APP_UPDATER_INSTALL: "app-updater:install",
   APP_UPDATER_STATE_CHANGED: "app-updater:state-changed",
   SETTINGS_GET: "settings:get",
+  USER_THEME_GET: "settings:get-user-theme",
+  DRIVER_WORKAROUNDS_GET: "settings:get-driver-workarounds",
+  DRIVER_WORKAROUNDS_CHANGED: "settings:driver-workarounds-changed",
+  GAMESCOPE_STATUS_GET: "settings:get-gamescope-status",
+  PARENTAL_VERIFY_PIN: "settings:parental-verify-pin",
+  PARENTAL_SET_PIN: "settings:parental-set-pin",
   SETTINGS_SET: "settings:set",
   SETTINGS_RESET: "settings:reset",
   SETTINGS_SELECT_NATIVE_STREAMER_EXECUTABLE: "settings:select-native-streamer-executable",
+  SETTINGS_SELECT_MEDIA_DIRECTORY: "settings:select-media-directory",
   NATIVE_STREAMER_STATUS: "native:streamer-status",
   NATIVE_CLOUD_GSYNC_CAPABILITIES: "native:cloud-gsync-capabilities",
   MICROPHONE_PERMISSION_GET: "microphone:permission:get",
   LOGS_EXPORT: "logs:export",
   LOGS_GET_RENDERER: "logs:get-renderer",
+  LOGS_TAIL: "logs:tail",
   SCREENSHOT_SAVE: "screenshot:save",
   SCREENSHOT_LIST: "screenshot:list",
   SCREENSHOT_DELETE: "screenshot:delete",
@@ -82,6 +106,9 @@ export const IPC_CHANNELS = {
   CACHE_REFRESH_MANUAL: "cache:refresh-manual",
   CACHE_STATUS_UPDATE: "cache:status-update",
   CACHE_DELETE_ALL: "cache:delete-all",
+  STORAGE_USAGE: "storage:usage",
+  STORAGE_CLEAR: "storage:clear",
+  BACKGROUND_WORK_SET_PAUSED: "background:set-paused",
   COMMUNITY_GET_THANKS: "community:get-thanks",
   // Media browsing
   MEDIA_LIST_BY_GAME: "media:list-by-game",
@@ -95,6 +122,11 @@ export const IPC_CHANNELS = {
   PRINTEDWASTE_SERVER_MAPPING_FETCH: "printedwaste:server-mapping-fetch",
   // Discord Rich Presence
   DISCORD_CLEAR_ACTIVITY: "discord:clear-activity",
+  // Opt-in session telemetry
+  TELEMETRY_RECORD_SESSION: "telemetry:record-session",
+  // Last stream's event timeline, included in log exports
+  SESSION_TIMELINE_RECORD: "session-timeline:record",
+  SESSION_TIMELINE_GET: "session-timeline:get",
 } as const;
 
 export type IpcChannel = (typeof IPC_CHANNELS)[keyof typeof IPC_CHANNELS];
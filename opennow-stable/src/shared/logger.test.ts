@@ -0,0 +1,35 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { LogCapture, logFilterAllows, parseLogFilter, tailLogs } from "./logger";
+
+test("module directives override the default level and the longest match wins", () => {
+  const filter = parseLogFilter("warn, WebRTC=debug, webrtc-stats=off, bogus=loud, =info");
+  assert.deepEqual(filter, {
+    defaultLevel: "warn",
+    modules: [
+      { module: "webrtc", level: "debug" },
+      { module: "webrtc-stats", level: "off" },
+    ],
+  });
+  assert.equal(logFilterAllows(filter, "WebRTC", "debug"), true);
+  assert.equal(logFilterAllows(filter, "WebRTC-Stats", "error"), false);
+  assert.equal(logFilterAllows(filter, "NativeStreamer", "log"), false);
+  assert.equal(logFilterAllows(filter, "NativeStreamer", "warn"), true);
+  assert.equal(logFilterAllows(parseLogFilter(""), "anything", "debug"), true);
+});
+
+test("the tail returns only entries past the cursor, redacted", () => {
+  const capture = new LogCapture("test");
+  capture.addEntry("info", "Auth", "signed in as player@example.com", []);
+  const first = tailLogs(capture, 0);
+  assert.equal(first.lines.length, 1);
+  assert.match(first.lines[0]!.text, /\[Auth\] signed in as \[Redacted for privacy\]$/);
+
+  capture.addEntry("warn", "WebRTC", "ice restart", []);
+  const next = tailLogs(capture, first.cursor);
+  assert.deepEqual(next.lines.map((line) => line.level), ["warn"]);
+  assert.deepEqual(tailLogs(capture, next.cursor), { lines: [], cursor: next.cursor });
+});
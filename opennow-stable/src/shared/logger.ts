@@ -3,7 +3,11 @@
  * Handles redaction of sensitive information like emails, passwords, tokens, etc.
  */
 
+import type { LogTailResult } from "./gfn";
+
 export interface LogEntry {
+  /** Increases by one per captured entry, so a tail can ask for what it has not seen */
+  sequence: number;
   timestamp: number;
   level: "log" | "error" | "warn" | "info" | "debug";
   prefix: string;
@@ -14,6 +18,63 @@ export interface LogEntry {
 /** Maximum number of log entries to keep in memory */
 const MAX_LOG_ENTRIES = 5000;
 
+/** Filter levels from quietest to noisiest; `trace` lets everything through. */
+export const LOG_FILTER_LEVELS = ["off", "error", "warn", "info", "debug", "trace"] as const;
+export type LogFilterLevel = (typeof LOG_FILTER_LEVELS)[number];
+
+/**
+ * Parsed `webrtc=debug,nativestreamer=warn,info` style filter. A bare level
+ * sets the default; `module=level` applies to entries whose prefix starts
+ * with the module name, ignoring case, and the longest match wins.
+ */
+export interface LogFilter {
+  defaultLevel: LogFilterLevel;
+  modules: Array<{ module: string; level: LogFilterLevel }>;
+}
+
+/** Captures everything, which is what an empty filter setting means. */
+export const PASS_ALL_LOG_FILTER: LogFilter = { defaultLevel: "trace", modules: [] };
+
+const ENTRY_LEVEL_RANK: Record<LogEntry["level"], number> = {
+  error: 1,
+  warn: 2,
+  info: 3,
+  log: 3,
+  debug: 4,
+};
+
+function isLogFilterLevel(value: string): value is LogFilterLevel {
+  return (LOG_FILTER_LEVELS as readonly string[]).includes(value);
+}
+
+/** Directives that do not parse are skipped so a half-typed filter keeps working. */
+export function parseLogFilter(spec: string): LogFilter {
+  const filter: LogFilter = { defaultLevel: PASS_ALL_LOG_FILTER.defaultLevel, modules: [] };
+  for (const directive of spec.split(",")) {
+    const [left, right] = directive.split("=").map((part) => part.trim().toLowerCase());
+    if (right === undefined) {
+      if (left && isLogFilterLevel(left)) {
+        filter.defaultLevel = left;
+      }
+    } else if (left && isLogFilterLevel(right)) {
+      filter.modules.push({ module: left, level: right });
+    }
+  }
+  return filter;
+}
+
+export function logFilterAllows(filter: LogFilter, prefix: string, level: LogEntry["level"]): boolean {
+  const module = prefix.toLowerCase();
+  let match: LogFilter["modules"][number] | null = null;
+  for (const directive of filter.modules) {
+    if (module.startsWith(directive.module) && (!match || directive.module.length > match.module.length)) {
+      match = directive;
+    }
+  }
+  const allowed = LOG_FILTER_LEVELS.indexOf(match?.level ?? filter.defaultLevel);
+  return ENTRY_LEVEL_RANK[level] <= allowed;
+}
+
 /** Patterns for sensitive data redaction */
 const SENSITIVE_PATTERNS = [
   // Email addresses
@@ -83,6 +144,25 @@ export function formatLogEntry(entry: LogEntry): string {
   return `${timeStr} ${levelStr} ${prefixStr}${entry.message}${argsStr}`;
 }
 
+/** Lines sent to one live-tail poll; older ones are skipped if the tail fell behind */
+const LOG_TAIL_LIMIT = 500;
+
+/**
+ * Redacted lines captured after `afterSequence`, for the debug settings tail
+ */
+export function tailLogs(capture: LogCapture | null, afterSequence: number): LogTailResult {
+  const entries = capture?.getEntriesSince(afterSequence, LOG_TAIL_LIMIT) ?? [];
+  return {
+    lines: entries.map((entry) => ({
+      sequence: entry.sequence,
+      timestamp: entry.timestamp,
+      level: entry.level,
+      text: redactSensitiveData(formatLogEntry(entry)),
+    })),
+    cursor: entries.at(-1)?.sequence ?? afterSequence,
+  };
+}
+
 /**
  * Create redacted log export
  */
@@ -100,6 +180,8 @@ export function createRedactedLogExport(entries: LogEntry[]): string {
  */
 export class LogCapture {
   private entries: LogEntry[] = [];
+  private nextSequence = 1;
+  private filter: LogFilter = PASS_ALL_LOG_FILTER;
   private originalConsole: Partial<typeof console> | null = null;
   private processName: string;
 
@@ -114,6 +196,21 @@ export class LogCapture {
     return [...this.entries];
   }
 
+  /**
+   * Entries captured after `afterSequence`, at most the newest `limit`
+   */
+  getEntriesSince(afterSequence: number, limit = MAX_LOG_ENTRIES): LogEntry[] {
+    const start = this.entries.findIndex((entry) => entry.sequence > afterSequence);
+    return start === -1 ? [] : this.entries.slice(Math.max(start, this.entries.length - limit));
+  }
+
+  /**
+   * Replace the filter; entries it rejects are neither kept nor printed
+   */
+  setFilter(filter: LogFilter): void {
+    this.filter = filter;
+  }
+
   /**
    * Clear all log entries
    */
@@ -133,6 +230,7 @@ export class LogCapture {
    */
   addEntry(level: LogEntry["level"], prefix: string, message: string, args: unknown[]): void {
     const entry: LogEntry = {
+      sequence: this.nextSequence++,
       timestamp: Date.now(),
       level,
       prefix,
@@ -185,30 +283,35 @@ export class LogCapture {
 
     console.log = (...args: unknown[]) => {
       const { prefix, message, rest } = extractPrefix(args);
+      if (!logFilterAllows(this.filter, prefix, "log")) return;
       this.addEntry("log", prefix, message, rest);
       this.originalConsole?.log?.apply(console, args);
     };
 
     console.error = (...args: unknown[]) => {
       const { prefix, message, rest } = extractPrefix(args);
+      if (!logFilterAllows(this.filter, prefix, "error")) return;
       this.addEntry("error", prefix, message, rest);
       this.originalConsole?.error?.apply(console, args);
     };
 
     console.warn = (...args: unknown[]) => {
       const { prefix, message, rest } = extractPrefix(args);
+      if (!logFilterAllows(this.filter, prefix, "warn")) return;
       this.addEntry("warn", prefix, message, rest);
       this.originalConsole?.warn?.apply(console, args);
     };
 
     console.info = (...args: unknown[]) => {
       const { prefix, message, rest } = extractPrefix(args);
+      if (!logFilterAllows(this.filter, prefix, "info")) return;
       this.addEntry("info", prefix, message, rest);
       this.originalConsole?.info?.apply(console, args);
     };
 
     console.debug = (...args: unknown[]) => {
       const { prefix, message, rest } = extractPrefix(args);
+      if (!logFilterAllows(this.filter, prefix, "debug")) return;
       this.addEntry("debug", prefix, message, rest);
       this.originalConsole?.debug?.apply(console, args);
     };
@@ -0,0 +1,35 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { applyLowLatencyPreset, estimateLowLatencyGain } from "./lowLatencyPreset";
+
+const settings = {
+  lowLatencyMode: false,
+  enableCloudGsync: true,
+  mouseRawInput: false,
+  audioBufferMs: 0,
+  fps: 120,
+  jitterBufferMs: 2,
+};
+
+test("the preset overlays the stored settings only while the mode is on", () => {
+  assert.equal(applyLowLatencyPreset(settings), settings);
+  assert.deepEqual(applyLowLatencyPreset({ ...settings, lowLatencyMode: true }), {
+    ...settings,
+    lowLatencyMode: true,
+    enableCloudGsync: false,
+    mouseRawInput: true,
+    audioBufferMs: 10,
+  });
+  assert.equal(applyLowLatencyPreset({ ...settings, lowLatencyMode: true, audioBufferMs: 5 }).audioBufferMs, 5);
+});
+
+test("the estimate drops the jitter buffer, pacing and half the present queue", () => {
+  assert.deepEqual(estimateLowLatencyGain(settings), {
+    before: { videoMs: 15, audioMs: 20 },
+    after: { videoMs: 4, audioMs: 10 },
+  });
+  assert.equal(estimateLowLatencyGain({ ...settings, jitterBufferMs: 10 }).before.videoMs, 23);
+});
@@ -0,0 +1,71 @@
+import type { Settings } from "./gfn";
+
+/** Largest audio buffer the preset leaves in place; smaller user choices stay. */
+const LOW_LATENCY_AUDIO_BUFFER_MS = 10;
+/** What shared-mode sinks typically buffer when the setting is left at 0. */
+const PLATFORM_DEFAULT_AUDIO_BUFFER_MS = 20;
+
+export type LowLatencyPresetSettings = Pick<
+  Settings,
+  "lowLatencyMode" | "enableCloudGsync" | "mouseRawInput" | "audioBufferMs"
+>;
+
+export type LatencyEstimateSettings = LowLatencyPresetSettings & Pick<Settings, "fps" | "jitterBufferMs">;
+
+export interface ClientLatencyEstimate {
+  /** Jitter buffer, present queue and frame pacing delay added on this device. */
+  videoMs: number;
+  audioMs: number;
+}
+
+/**
+ * Overlays the preset on the stored settings without touching them, so
+ * turning the mode off restores the user's own choices. The pipeline-only
+ * parts (jitter buffer, present queue, late-frame drops, stats cadence) ride
+ * along as `lowLatency` in the stream settings. Raw input means pointer lock's
+ * unadjusted movement; the preset does not change the sink's present mode.
+ */
+export function applyLowLatencyPreset<T extends LowLatencyPresetSettings>(settings: T): T {
+  if (!settings.lowLatencyMode) {
+    return settings;
+  }
+  return {
+    ...settings,
+    // Cloud G-Sync holds frames for the display's cadence instead of presenting on decode.
+    enableCloudGsync: false,
+    mouseRawInput: true,
+    audioBufferMs: settings.audioBufferMs > 0
+      ? Math.min(settings.audioBufferMs, LOW_LATENCY_AUDIO_BUFFER_MS)
+      : LOW_LATENCY_AUDIO_BUFFER_MS,
+  };
+}
+
+/**
+ * Rough client-side buffering worked out from the settings alone, not from a
+ * measurement; the stats overlay shows the real stage timings while
+ * streaming. A decoded frame waits
+ * on average one frame behind the one being shown; with newest-frame-wins
+ * presentation it is replaced instead, halving that wait. VRR pacing adds
+ * about half a frame while it lines frames up with the display.
+ */
+export function estimateClientLatency(settings: LatencyEstimateSettings): ClientLatencyEstimate {
+  const frameMs = 1000 / Math.max(1, settings.fps);
+  const jitterBufferMs = settings.lowLatencyMode ? 0 : settings.jitterBufferMs;
+  const presentQueueMs = settings.lowLatencyMode ? frameMs / 2 : frameMs;
+  const pacingMs = settings.enableCloudGsync ? frameMs / 2 : 0;
+  return {
+    videoMs: Math.round(jitterBufferMs + presentQueueMs + pacingMs),
+    audioMs: settings.audioBufferMs > 0 ? settings.audioBufferMs : PLATFORM_DEFAULT_AUDIO_BUFFER_MS,
+  };
+}
+
+/** Estimates with the preset off and on, for the settings toggle. */
+export function estimateLowLatencyGain(settings: LatencyEstimateSettings): {
+  before: ClientLatencyEstimate;
+  after: ClientLatencyEstimate;
+} {
+  return {
+    before: estimateClientLatency({ ...settings, lowLatencyMode: false }),
+    after: estimateClientLatency(applyLowLatencyPreset({ ...settings, lowLatencyMode: true })),
+  };
+}
@@ -7,6 +7,7 @@ import type {
   NativeStreamerSessionContext,
   NativeVideoTransition,
   NativeVideoBackendCapability,
+  NativeVideoLayer,
   SendAnswerRequest,
 } from "./gfn";
 
@@ -24,6 +25,7 @@ export interface NativeStreamerCapabilities {
   supportsLocalIce: boolean;
   supportsInput: boolean;
   videoBackends?: NativeVideoBackendCapability[];
+  activeFlags?: string[];
 }
 
 export interface NativeStreamerInputPacket {
@@ -77,6 +79,21 @@ export type NativeStreamerCommand =
       id: string;
       type: "update-shortcuts";
       shortcuts: import("./gfn").NativeStreamerShortcutBindings;
+    }
+  | {
+      id: string;
+      type: "volume";
+      volume: number;
+    }
+  | {
+      id: string;
+      type: "video-layer";
+      videoLayer: string;
+    }
+  | {
+      id: string;
+      type: "render-fps-cap";
+      renderFpsCap: number;
     };
 
 export type NativeStreamerResponse =
@@ -151,10 +168,26 @@ export type NativeStreamerEvent =
       zeroCopyD3D12: boolean;
       recoveryAttempt: number;
     }
+  | {
+      type: "video-recovery";
+      /** "recovered" when frames resumed, "escalated" when the watchdog gave up and asked for a reconnect. */
+      outcome: "recovered" | "escalated";
+      step?: "keyframe" | "resync" | "partial-flush" | "decoder-reset" | "reconnect";
+      recoveryAttempt: number;
+      stallMs: number;
+      likelyStage: string;
+    }
   | {
       type: "video-transition";
       transition: NativeVideoTransition;
     }
+  | {
+      type: "video-layers";
+      layers: NativeVideoLayer[];
+      active: string;
+      /** Inactive layers are paused at the server over RTCP instead of dropped locally */
+      pausedAtSender: boolean;
+    }
   | {
       type: "stats";
       stats: NativeStreamStats;
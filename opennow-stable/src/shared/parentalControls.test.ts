@@ -0,0 +1,45 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import {
+  gameMinimumAge,
+  isGameAllowedForAge,
+  isParentalProtectedSetting,
+  isValidParentalPin,
+  ratingMinimumAge,
+} from "./parentalControls";
+
+test("rating labels map to minimum ages", () => {
+  assert.equal(ratingMinimumAge("PEGI 16"), 16);
+  assert.equal(ratingMinimumAge("USK ab 12"), 12);
+  assert.equal(ratingMinimumAge("ESRB Mature 17+"), 17);
+  assert.equal(ratingMinimumAge("Teen"), 13);
+  assert.equal(ratingMinimumAge("E10+"), 10);
+  assert.equal(ratingMinimumAge("Everyone"), 0);
+  assert.equal(ratingMinimumAge("Rating Pending"), null);
+  assert.equal(ratingMinimumAge("Violence"), null);
+});
+
+test("games use their strictest rating and unrated games stay visible", () => {
+  const game = { contentRatings: ["PEGI 12", "ESRB Mature 17+"] };
+  assert.equal(gameMinimumAge(game), 17);
+  assert.equal(isGameAllowedForAge(game, 16), false);
+  assert.equal(isGameAllowedForAge(game, 18), true);
+  assert.equal(isGameAllowedForAge(game, null), true);
+  assert.equal(isGameAllowedForAge({ contentRatings: [] }, 7), true);
+});
+
+test("pins are four to eight digits", () => {
+  assert.equal(isValidParentalPin("1234"), true);
+  assert.equal(isValidParentalPin("12a4"), false);
+  assert.equal(isValidParentalPin("123"), false);
+});
+
+test("play-time limits and the streamer path are protected along with the parental options", () => {
+  assert.equal(isParentalProtectedSetting("parentalMaxAge"), true);
+  assert.equal(isParentalProtectedSetting("playCurfew"), true);
+  assert.equal(isParentalProtectedSetting("nativeStreamerExecutablePath"), true);
+  assert.equal(isParentalProtectedSetting("streamVolume"), false);
+});
@@ -0,0 +1,78 @@
+import type { GameInfo, Settings } from "./gfn";
+
+/** Age limits offered in settings; null turns rating filtering off. */
+export const PARENTAL_MAX_AGE_OPTIONS: readonly number[] = [7, 12, 16, 18];
+
+export const PARENTAL_PIN_PATTERN = /^\d{4,8}$/;
+
+export function isValidParentalPin(pin: string): boolean {
+  return PARENTAL_PIN_PATTERN.test(pin);
+}
+
+/**
+ * Settings main only changes together with the PIN while one is set. Besides the
+ * parental options this covers the play-time limits, and the streamer executable
+ * since the app would launch whatever it points at.
+ */
+const PARENTAL_PROTECTED_SETTINGS: ReadonlySet<string> = new Set<keyof Settings>([
+  "parentalLockLaunch",
+  "parentalLockSettings",
+  "parentalMaxAge",
+  "playReminderMinutes",
+  "playCurfew",
+  "playLimitDisconnect",
+  "nativeStreamerExecutablePath",
+]);
+
+export function isParentalProtectedSetting(key: string): boolean {
+  return PARENTAL_PROTECTED_SETTINGS.has(key);
+}
+
+const ESRB_AGES: ReadonlyArray<readonly [RegExp, number]> = [
+  [/\b(adults only|ao)\b/, 18],
+  [/\b(mature|m)\b/, 17],
+  [/\b(teen|t)\b/, 13],
+  [/\b(everyone|e|early childhood|ec)\b/, 0],
+];
+
+/**
+ * Minimum age for one catalog rating label, e.g. "PEGI 16", "USK 12",
+ * "ESRB Mature 17+" or "Teen". Returns null for labels it cannot read.
+ */
+export function ratingMinimumAge(label: string): number | null {
+  const normalized = label.trim().toLowerCase();
+  if (!normalized || /\b(rating pending|rp)\b/.test(normalized)) {
+    return null;
+  }
+  const numeric = /(\d{1,2})\s*\+?/.exec(normalized);
+  if (numeric) {
+    return Number.parseInt(numeric[1], 10);
+  }
+  for (const [pattern, age] of ESRB_AGES) {
+    if (pattern.test(normalized)) {
+      return age;
+    }
+  }
+  return null;
+}
+
+/** Highest age across every rating board the catalog lists, or null when unrated. */
+export function gameMinimumAge(game: Pick<GameInfo, "contentRatings">): number | null {
+  let highest: number | null = null;
+  for (const label of game.contentRatings ?? []) {
+    const age = ratingMinimumAge(label);
+    if (age !== null && (highest === null || age > highest)) {
+      highest = age;
+    }
+  }
+  return highest;
+}
+
+/** Unrated games stay visible; the catalog has no rating for many older titles. */
+export function isGameAllowedForAge(game: Pick<GameInfo, "contentRatings">, maxAge: number | null): boolean {
+  if (maxAge === null) {
+    return true;
+  }
+  const age = gameMinimumAge(game);
+  return age === null || age <= maxAge;
+}
@@ -0,0 +1,19 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { normalizePlayCurfew, parseCurfew } from "./playCurfew";
+
+test("bedtimes parse as local HH:MM", () => {
+  assert.equal(parseCurfew("23:00"), 23 * 60);
+  assert.equal(parseCurfew("7:05"), 7 * 60 + 5);
+  assert.equal(parseCurfew("24:00"), null);
+  assert.equal(parseCurfew(""), null);
+});
+
+test("stored bedtimes are zero-padded and invalid ones cleared", () => {
+  assert.equal(normalizePlayCurfew(" 7:05 "), "07:05");
+  assert.equal(normalizePlayCurfew("23:60"), "");
+  assert.equal(normalizePlayCurfew(2300), "");
+});
@@ -0,0 +1,25 @@
+/** Minutes after local midnight for an "HH:MM" string, or null when it is not a valid time. */
+export function parseCurfew(value: string): number | null {
+  const match = /^(\d{1,2}):(\d{2})$/.exec(value.trim());
+  if (!match) {
+    return null;
+  }
+  const hours = Number(match[1]);
+  const minutes = Number(match[2]);
+  if (hours > 23 || minutes > 59) {
+    return null;
+  }
+  return hours * 60 + minutes;
+}
+
+/** Rewrites a stored bedtime as zero-padded "HH:MM", or "" when it is missing or invalid. */
+export function normalizePlayCurfew(raw: unknown): string {
+  if (typeof raw !== "string") {
+    return "";
+  }
+  const minutes = parseCurfew(raw);
+  if (minutes === null) {
+    return "";
+  }
+  return `${String(Math.floor(minutes / 60)).padStart(2, "0")}:${String(minutes % 60).padStart(2, "0")}`;
+}
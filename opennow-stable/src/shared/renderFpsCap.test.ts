@@ -0,0 +1,36 @@
+/// <reference types="node" />
+
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import type { StreamSettings } from "./gfn";
+import {
+  applyRenderFpsCapToStreamSettings,
+  normalizeRenderFpsCap,
+  resolveRenderFpsCap,
+} from "./renderFpsCap";
+
+const caps = { renderFpsCap: 0, renderFpsCapOnBattery: 40, renderFpsCapRequestServer: true };
+
+test("the cap follows the power source and ignores caps at or above the stream rate", () => {
+  assert.equal(resolveRenderFpsCap(caps, false, 60), 0);
+  assert.equal(resolveRenderFpsCap(caps, true, 60), 40);
+  assert.equal(resolveRenderFpsCap(caps, true, 30), 0);
+  assert.equal(resolveRenderFpsCap({ ...caps, renderFpsCap: 90 }, false, 120), 90);
+});
+
+test("server fps drops to the lowest standard rate that still covers the cap", () => {
+  const stream = { fps: 120, resolution: "1920x1080" } as StreamSettings;
+  assert.equal(applyRenderFpsCapToStreamSettings(stream, caps, true).fps, 60);
+  assert.equal(applyRenderFpsCapToStreamSettings(stream, { ...caps, renderFpsCapOnBattery: 30 }, true).fps, 30);
+  assert.equal(applyRenderFpsCapToStreamSettings({ ...stream, fps: 60 }, caps, true).fps, 60);
+  assert.equal(applyRenderFpsCapToStreamSettings(stream, caps, false), stream);
+  assert.equal(applyRenderFpsCapToStreamSettings(stream, { ...caps, renderFpsCapRequestServer: false }, true), stream);
+});
+
+test("unknown caps fall back to off", () => {
+  assert.equal(normalizeRenderFpsCap(45), 45);
+  assert.equal(normalizeRenderFpsCap("30"), 30);
+  assert.equal(normalizeRenderFpsCap(37), 0);
+  assert.equal(normalizeRenderFpsCap(undefined), 0);
+});
@@ -0,0 +1,44 @@
+import { STREAM_FPS_OPTIONS } from "./gfn";
+import type { Settings, StreamSettings } from "./gfn";
+
+/** Caps offered in settings; 0 renders every decoded frame. */
+export const RENDER_FPS_CAP_OPTIONS: readonly number[] = [0, 30, 40, 45, 60, 90, 120];
+
+export type RenderFpsCapSettings = Pick<Settings, "renderFpsCap" | "renderFpsCapOnBattery" | "renderFpsCapRequestServer">;
+
+/** Drops unknown values from a hand-edited settings file back to "off". */
+export function normalizeRenderFpsCap(value: unknown): number {
+  const fps = Number(value);
+  return RENDER_FPS_CAP_OPTIONS.includes(fps) ? fps : 0;
+}
+
+/**
+ * The cap for the current power source, or 0 when it would not drop anything
+ * because the stream already runs at or below it.
+ */
+export function resolveRenderFpsCap(
+  settings: RenderFpsCapSettings,
+  onBatteryPower: boolean,
+  streamFps: number,
+): number {
+  const cap = onBatteryPower ? settings.renderFpsCapOnBattery : settings.renderFpsCap;
+  return cap > 0 && cap < streamFps ? cap : 0;
+}
+
+/**
+ * Lowers a new session's requested frame rate towards the render cap. Servers
+ * only stream the standard rates, so this picks the lowest one that still
+ * covers the cap and leaves the rest to client-side decimation.
+ */
+export function applyRenderFpsCapToStreamSettings(
+  settings: StreamSettings,
+  capSettings: RenderFpsCapSettings,
+  onBatteryPower: boolean,
+): StreamSettings {
+  const cap = resolveRenderFpsCap(capSettings, onBatteryPower, settings.fps);
+  if (!capSettings.renderFpsCapRequestServer || cap === 0) {
+    return settings;
+  }
+  const fps = STREAM_FPS_OPTIONS.find((option) => option >= cap && option < settings.fps);
+  return fps === undefined ? settings : { ...settings, fps };
+}
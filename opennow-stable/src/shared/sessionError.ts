@@ -1,3 +1,5 @@
+import type { GfnErrorCategory } from "./errorTaxonomy";
+
 export interface SessionErrorInfo {
   httpStatus: number;
   statusCode: number;
@@ -5,6 +7,8 @@ export interface SessionErrorInfo {
   unifiedErrorCode?: number;
   sessionErrorCode?: number;
   gfnErrorCode: number;
+  /** Coarse category the UI uses to pick localized copy and remediation steps */
+  category?: GfnErrorCategory;
   title: string;
   description: string;
 }
@@ -0,0 +1,13 @@
+import type { SessionTimelineEvent } from "./gfn";
+
+/** `+mm:ss` from the stream start; shared by the stats panel and the log export. */
+export function formatTimelineOffset(startedAtMs: number, atMs: number): string {
+  const seconds = Math.max(0, Math.round((atMs - startedAtMs) / 1000));
+  return `+${String(Math.floor(seconds / 60)).padStart(2, "0")}:${String(seconds % 60).padStart(2, "0")}`;
+}
+
+/** Event code and its values as `key=value`, for the log export. */
+export function formatTimelineEventForLog(event: Pick<SessionTimelineEvent, "code" | "params">): string {
+  const params = Object.entries(event.params ?? {}).map(([key, value]) => `${key}=${value}`);
+  return [event.code, ...params].join(" ");
+}
@@ -0,0 +1,21 @@
+/// <reference types="node" />
+import test from "node:test";
+import assert from "node:assert/strict";
+
+import { gamepadVendorProduct, isSteamInputManagedGamepad, parseSteamInputDevices } from "./steamInput";
+
+test("parses Steam's ignore list into vendor:product pairs", () => {
+  assert.deepEqual(parseSteamInputDevices("0x054C/0x0CE6,0x28de/0x1205, 0x054c/0x0ce6,bogus"), ["054c:0ce6", "28de:1205"]);
+  assert.deepEqual(parseSteamInputDevices(undefined), []);
+});
+
+test("skips physical pads Steam Input manages but keeps its virtual pad", () => {
+  const managed = ["054c:0ce6", "28de:11ff"];
+  assert.equal(gamepadVendorProduct("DualSense Wireless Controller (STANDARD GAMEPAD Vendor: 054c Product: 0ce6)"), "054c:0ce6");
+  assert.equal(
+    isSteamInputManagedGamepad("DualSense Wireless Controller (STANDARD GAMEPAD Vendor: 054c Product: 0ce6)", managed),
+    true,
+  );
+  assert.equal(isSteamInputManagedGamepad("Steam Virtual Gamepad (STANDARD GAMEPAD Vendor: 28de Product: 11ff)", managed), false);
+  assert.equal(isSteamInputManagedGamepad("Xbox 360 Controller (XInput STANDARD GAMEPAD)", managed), false);
+});
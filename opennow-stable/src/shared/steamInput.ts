@@ -0,0 +1,42 @@
+/** Steam's virtual controller, which carries the input of every pad Steam Input manages. */
+const STEAM_VIRTUAL_GAMEPAD = "28de:11ff";
+
+const HEX_ID = /^(?:0x)?([0-9a-f]{1,4})$/i;
+
+function normalizeHexId(value: string): string | null {
+  const match = HEX_ID.exec(value.trim());
+  return match?.[1] ? match[1].toLowerCase().padStart(4, "0") : null;
+}
+
+/**
+ * Steam lists the physical controllers Steam Input has taken over in
+ * SDL_GAMECONTROLLER_IGNORE_DEVICES as `0x054C/0x0CE6,...`. Returns them as
+ * `vendor:product` in lowercase hex.
+ */
+export function parseSteamInputDevices(value: string | undefined): string[] {
+  const devices = new Set<string>();
+  for (const entry of (value ?? "").split(",")) {
+    const [vendor, product] = entry.split("/");
+    const vendorId = vendor === undefined ? null : normalizeHexId(vendor);
+    const productId = product === undefined ? null : normalizeHexId(product);
+    if (vendorId && productId) {
+      devices.add(`${vendorId}:${productId}`);
+    }
+  }
+  return [...devices];
+}
+
+/** Chromium ends `Gamepad.id` with `Vendor: 054c Product: 0ce6` on Linux and Windows. */
+export function gamepadVendorProduct(id: string): string | null {
+  const match = /Vendor: ([0-9a-f]{4}) Product: ([0-9a-f]{4})/i.exec(id);
+  return match?.[1] && match[2] ? `${match[1].toLowerCase()}:${match[2].toLowerCase()}` : null;
+}
+
+/**
+ * True for a physical pad whose input already reaches the stream through
+ * Steam's virtual controller. Steam's own pads are never skipped.
+ */
+export function isSteamInputManagedGamepad(id: string, steamInputDevices: readonly string[]): boolean {
+  const device = gamepadVendorProduct(id);
+  return device !== null && device !== STEAM_VIRTUAL_GAMEPAD && steamInputDevices.includes(device);
+}